@@ -1,5 +1,7 @@
-//! Build script for compiling libavoid and generating C++ bindings.
+//! Build script for compiling libavoid and generating C++ bindings, with
+//! the `libavoid` feature.
 
+#[cfg(feature = "libavoid")]
 use std::path::Path;
 
 fn main() {
@@ -11,9 +13,10 @@ fn main() {
     // generate_bindings();
 }
 
+#[cfg(feature = "libavoid")]
 #[allow(dead_code)]
 fn build_libavoid() {
-    let libavoid_dir = Path::new("vendor/adaptagrams/cola/libavoid");
+    let libavoid_dir = Path::new("../../vendor/adaptagrams/cola/libavoid");
 
     if !libavoid_dir.exists() {
         panic!("libavoid source directory not found. Please ensure the submodule is initialized.");
@@ -36,7 +39,7 @@ fn build_libavoid() {
         .cpp(true)
         .std("c++11")
         .include(libavoid_dir)
-        .include("vendor/adaptagrams/cola") // For libavoid/header.h includes
+        .include("../../vendor/adaptagrams/cola") // For libavoid/header.h includes
         .define("LIBAVOID_STATIC", None)
         .flag_if_supported("-fPIC");
 
@@ -57,13 +60,13 @@ fn build_libavoid() {
     println!("cargo:rustc-link-lib=dylib=stdc++");
 
     // Re-run if any libavoid files change
-    println!("cargo:rerun-if-changed=vendor/adaptagrams/cola/libavoid");
+    println!("cargo:rerun-if-changed=../../vendor/adaptagrams/cola/libavoid");
 }
 
 // TODO: Implement generate_bindings() once libclang is available
 /*
 fn generate_bindings() {
-    let libavoid_dir = PathBuf::from("vendor/adaptagrams/cola/libavoid");
+    let libavoid_dir = PathBuf::from("../../vendor/adaptagrams/cola/libavoid");
 
     let mut b = autocxx_build::Builder::new("src/routing/libavoid_ffi.rs", [&libavoid_dir])
         .build()
@@ -0,0 +1,65 @@
+/* Copyright (c) 2025 John Wilger */
+/* SPDX-License-Identifier: MIT */
+
+#ifndef EVENT_MODELER_H
+#define EVENT_MODELER_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Status returned by [`em_validate_yaml`] for a valid model.
+#define EM_VALID 0
+
+// Status returned by [`em_validate_yaml`] for an invalid model.
+#define EM_INVALID 1
+
+// Status returned by [`em_validate_yaml`] when the source could not be read:
+// it is NULL or not UTF-8.
+#define EM_BAD_INPUT -1
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Renders a model in the YAML format to an SVG document.
+//
+// Returns the document, or NULL if the model could not be rendered. On
+// failure the reason is stored in `*error` unless `error` is NULL. Both
+// strings are to be released with [`em_string_free`].
+//
+// # Safety
+//
+// `source` must be NULL or point to a NUL-terminated string, and `error`
+// must be NULL or point to writable storage for a pointer.
+char *em_render_yaml_to_svg(const char *source, char **error);
+
+// Checks a model in the YAML format without rendering it.
+//
+// Returns [`EM_VALID`], [`EM_INVALID`], or [`EM_BAD_INPUT`]. Unless `report`
+// is NULL, `*report` receives the findings as JSON, an object with the
+// `error` making the model invalid (or `null`) and the `warnings` raised,
+// to be released with [`em_string_free`].
+//
+// # Safety
+//
+// `source` must be NULL or point to a NUL-terminated string, and `report`
+// must be NULL or point to writable storage for a pointer.
+int32_t em_validate_yaml(const char *source, char **report);
+
+// Releases a string returned by this library. Does nothing given NULL.
+//
+// # Safety
+//
+// `string` must be NULL or a string returned by this library that has not
+// been released yet.
+void em_string_free(char *string);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* EVENT_MODELER_H */
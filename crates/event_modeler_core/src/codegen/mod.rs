@@ -0,0 +1,12 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Generating source code from event models.
+//!
+//! The specifications in a model are meant to become tests. This module turns
+//! them into scaffolding in a target language that developers fill in and run
+//! against their implementation. It also goes the other way, turning the
+//! word list of an event-storming session into a starter model.
+
+pub mod rust_tests;
+pub mod scaffold;
@@ -0,0 +1,532 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Rust test skeletons for the Given/When/Then scenarios of commands.
+//!
+//! Each command becomes a module and each of its scenarios a `#[test]`
+//! building the `given` events, the `when` command, and the expected `then`
+//! events or error as struct literals named after the model. Placeholder
+//! values such as `A` become `todo!()` bindings typed from the data schemas,
+//! so the same placeholder is one value throughout a test, and mention the
+//! scenario's example for them if it gives one. The tests are
+//! ignored until their values and the call into the system under test are
+//! filled in.
+//!
+//! The skeletons assume the application defines a struct per command, event,
+//! and error with the fields of the model, and `Event`/`Command` types each
+//! event and command converts `into`. Enumerations declared under `types`
+//! are generated as Rust enums, and a placeholder of a declared enumeration
+//! or constrained type mentions the values it may take.
+
+use crate::event_model::yaml_types::{
+    CommandDefinition, FieldDefinition, FieldName, PlaceholderValue, TestOutcome, TestScenario,
+    TypeDefinition, YamlEventModel,
+};
+use std::collections::HashMap;
+
+/// Generates a Rust source file with a test per command scenario.
+pub fn generate(model: &YamlEventModel) -> String {
+    let workflow = model.workflow.clone().into_inner().into_inner();
+    let mut source = format!(
+        "// Test skeletons for \"{workflow}\", generated by event_modeler.\n\
+         //\n\
+         // Bring the command, event, and error types into scope here, then\n\
+         // replace each `todo!()` and remove the `#[ignore]`.\n"
+    );
+    source.push_str(&enumerations(model));
+
+    let mut commands: Vec<_> = model.commands.iter().collect();
+    commands.sort_by_key(|(name, _)| (*name).clone().into_inner().into_inner());
+    for (name, command) in commands {
+        if command.tests.is_empty() {
+            continue;
+        }
+        let name = name.clone().into_inner().into_inner();
+        source.push_str(&format!(
+            "\nmod {} {{\n    use super::*;\n",
+            snake_case(&name)
+        ));
+        let mut scenarios: Vec<_> = command.tests.iter().collect();
+        scenarios.sort_by_key(|(scenario, _)| (*scenario).clone().into_inner().into_inner());
+        for (scenario, test) in scenarios {
+            let scenario = scenario.clone().into_inner().into_inner();
+            source.push_str(&TestWriter::new(model, command).write(&scenario, test));
+        }
+        source.push_str("}\n");
+    }
+    source
+}
+
+/// Writes one test, binding each placeholder value once.
+struct TestWriter<'a> {
+    model: &'a YamlEventModel,
+    command: &'a CommandDefinition,
+    /// Placeholder values and their bindings, in order of first use.
+    bindings: Vec<(String, Binding)>,
+}
+
+/// The variable a placeholder value is bound to.
+struct Binding {
+    name: String,
+    field_type: Option<String>,
+}
+
+impl<'a> TestWriter<'a> {
+    fn new(model: &'a YamlEventModel, command: &'a CommandDefinition) -> Self {
+        Self {
+            model,
+            command,
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Returns the source of the test for a scenario.
+    fn write(mut self, scenario: &str, test: &TestScenario) -> String {
+        let given: Vec<String> = test
+            .given
+            .iter()
+            .map(|event| {
+                let name = event.name.clone().into_inner().into_inner();
+                let literal = self.literal(&name, &event.fields, self.event_data(&name));
+                format!("{literal}.into()")
+            })
+            .collect();
+
+        let when_name = |action: &crate::event_model::yaml_types::TestAction| {
+            action.name.clone().into_inner().into_inner()
+        };
+        let when = if test.when.len() == 1 {
+            let action = test.when.first();
+            let literal =
+                self.literal(&when_name(action), &action.fields, Some(&self.command.data));
+            format!("let when = {literal};")
+        } else {
+            let actions: Vec<String> = test
+                .when
+                .iter()
+                .map(|action| {
+                    let name = when_name(action);
+                    let data = self.command_data(&name);
+                    format!("{}.into()", self.literal(&name, &action.fields, data))
+                })
+                .collect();
+            format!("let when: Vec<Command> = {};", vec_literal(&actions, 2))
+        };
+
+        let mut events = Vec::new();
+        let mut errors = Vec::new();
+        for outcome in test.then.iter() {
+            match outcome {
+                TestOutcome::Event(event) => {
+                    let name = event.name.clone().into_inner().into_inner();
+                    let literal = self.literal(&name, &event.fields, self.event_data(&name));
+                    events.push(format!("{literal}.into()"));
+                }
+                TestOutcome::Error(error) => {
+                    let data = self
+                        .command
+                        .errors
+                        .get(&error.name)
+                        .map(|definition| &definition.data);
+                    let name = error.name.clone().into_inner().into_inner();
+                    errors.push(self.literal(&name, &error.fields, data));
+                }
+            }
+        }
+        let (then, check) = match errors.first() {
+            Some(error) => (
+                format!("let then = {error};"),
+                "issue `when` against a stream holding `given` and assert it is rejected with `then`",
+            ),
+            None => (
+                format!("let then: Vec<Event> = {};", vec_literal(&events, 2)),
+                "issue `when` against a stream holding `given` and assert it records `then`",
+            ),
+        };
+
+        let mut body = Vec::new();
+        for (placeholder, binding) in &self.bindings {
+            let annotation = binding
+                .field_type
+                .as_ref()
+                .map(|field_type| format!(": {field_type}"))
+                .unwrap_or_default();
+            let allowed = binding
+                .field_type
+                .as_deref()
+                .and_then(|field_type| self.restriction(field_type))
+                .map(|restriction| format!(" ({restriction})"))
+                .unwrap_or_default();
+            let example = test
+                .examples
+                .iter()
+                .find(|(value, _)| (*value).clone().into_inner().into_inner() == *placeholder)
+                .map(|(_, example)| format!(", e.g. {}", example.clone().into_inner().as_str()))
+                .unwrap_or_default();
+            body.push(format!(
+                "let {}{annotation} = todo!(\"value for {}\");",
+                binding.name,
+                format!("{placeholder}{allowed}{example}").escape_default()
+            ));
+        }
+        body.push(format!(
+            "let given: Vec<Event> = {};",
+            vec_literal(&given, 2)
+        ));
+        body.push(when);
+        body.push(then);
+        body.push(format!("todo!(\"{check}\");"));
+
+        format!(
+            "\n    /// {}\n    #[test]\n    #[ignore = \"generated skeleton\"]\n    fn {}() {{\n{}\n    }}\n",
+            scenario,
+            snake_case(scenario),
+            body.iter()
+                .map(|line| format!("        {line}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+    }
+
+    /// Returns a struct literal for an entity, binding its placeholder values.
+    fn literal(
+        &mut self,
+        name: &str,
+        fields: &HashMap<FieldName, PlaceholderValue>,
+        schema: Option<&HashMap<FieldName, FieldDefinition>>,
+    ) -> String {
+        let mut fields: Vec<(&FieldName, &PlaceholderValue)> = fields.iter().collect();
+        fields.sort_by_key(|(field, _)| (*field).clone().into_inner().into_inner());
+        if fields.is_empty() {
+            return format!("{name} {{}}");
+        }
+        let values: Vec<String> = fields
+            .into_iter()
+            .map(|(field, value)| {
+                let field_type = schema
+                    .and_then(|schema| schema.get(field))
+                    .map(|definition| definition.field_type.clone().into_inner().into_inner());
+                let binding = self.bind(value.clone().into_inner().into_inner(), field_type);
+                format!("{}: {binding}.clone()", field.clone().into_inner().as_str())
+            })
+            .collect();
+        format!("{name} {{ {} }}", values.join(", "))
+    }
+
+    /// Returns the variable bound to a placeholder value, binding it on first use.
+    fn bind(&mut self, placeholder: String, field_type: Option<String>) -> String {
+        if let Some((_, binding)) = self
+            .bindings
+            .iter_mut()
+            .find(|(bound, _)| *bound == placeholder)
+        {
+            if binding.field_type.is_none() {
+                binding.field_type = field_type;
+            }
+            return binding.name.clone();
+        }
+        let mut name = snake_case(&placeholder);
+        if self
+            .bindings
+            .iter()
+            .any(|(_, binding)| binding.name == name)
+        {
+            name = format!("{name}_{}", self.bindings.len() + 1);
+        }
+        self.bindings.push((
+            placeholder,
+            Binding {
+                name: name.clone(),
+                field_type,
+            },
+        ));
+        name
+    }
+
+    /// Describes the values a declared enumeration or constrained type may
+    /// take, e.g. `one of USD, EUR`.
+    fn restriction(&self, field_type: &str) -> Option<String> {
+        self.model
+            .types
+            .iter()
+            .find(|(name, _)| (*name).clone().into_inner().as_str() == field_type)
+            .and_then(|(_, definition)| match definition {
+                TypeDefinition::Enum(_) | TypeDefinition::Constrained(_) => {
+                    Some(definition.to_string())
+                }
+                TypeDefinition::Alias(_) | TypeDefinition::Record(_) => None,
+            })
+    }
+
+    fn event_data(&self, name: &str) -> Option<&'a HashMap<FieldName, FieldDefinition>> {
+        self.model
+            .events
+            .iter()
+            .find(|(event, _)| (*event).clone().into_inner().into_inner() == name)
+            .map(|(_, definition)| &definition.data)
+    }
+
+    fn command_data(&self, name: &str) -> Option<&'a HashMap<FieldName, FieldDefinition>> {
+        self.model
+            .commands
+            .iter()
+            .find(|(command, _)| (*command).clone().into_inner().into_inner() == name)
+            .map(|(_, definition)| &definition.data)
+    }
+}
+
+/// Declares a Rust enum for each enumeration the model declares, in name
+/// order.
+fn enumerations(model: &YamlEventModel) -> String {
+    let mut enums: Vec<(String, Vec<String>)> = model
+        .types
+        .iter()
+        .filter_map(|(name, definition)| match definition {
+            TypeDefinition::Enum(variants) => Some((
+                name.clone().into_inner().into_inner(),
+                variants
+                    .iter()
+                    .map(|variant| variant.clone().into_inner().into_inner())
+                    .collect(),
+            )),
+            _ => None,
+        })
+        .collect();
+    enums.sort();
+
+    let mut source = String::new();
+    for (name, variants) in enums {
+        let mut identifiers: Vec<String> = Vec::new();
+        for variant in &variants {
+            let mut identifier = pascal_case(variant);
+            if identifiers.contains(&identifier) {
+                identifier = format!("{identifier}{}", identifiers.len() + 1);
+            }
+            identifiers.push(identifier);
+        }
+        source.push_str(&format!(
+            "\n/// One of {}.\n#[derive(Debug, Clone, Copy, PartialEq, Eq)]\npub enum {name} {{\n{}}}\n",
+            variants.join(", "),
+            identifiers
+                .iter()
+                .map(|identifier| format!("    {identifier},\n"))
+                .collect::<String>()
+        ));
+    }
+    source
+}
+
+/// Formats items as a `vec![...]` literal indented for a test body.
+fn vec_literal(items: &[String], depth: usize) -> String {
+    if items.is_empty() {
+        return "vec![]".to_string();
+    }
+    let indent = "    ".repeat(depth + 1);
+    let items: Vec<String> = items
+        .iter()
+        .map(|item| format!("{indent}{item},"))
+        .collect();
+    format!("vec![\n{}\n{}]", items.join("\n"), "    ".repeat(depth))
+}
+
+/// Turns a name such as `Main Success` or `CreateAccount` into a Rust
+/// identifier such as `main_success` or `create_account`.
+fn snake_case(name: &str) -> String {
+    let mut identifier = String::new();
+    let mut previous_lowercase = false;
+    for c in name.chars() {
+        if c.is_alphanumeric() {
+            if c.is_uppercase() && previous_lowercase {
+                identifier.push('_');
+            }
+            identifier.extend(c.to_lowercase());
+            previous_lowercase = c.is_lowercase() || c.is_numeric();
+        } else {
+            if !identifier.is_empty() && !identifier.ends_with('_') {
+                identifier.push('_');
+            }
+            previous_lowercase = false;
+        }
+    }
+    let identifier = identifier.trim_end_matches('_').to_string();
+    match identifier.chars().next() {
+        None => "value".to_string(),
+        Some(first) if first.is_numeric() => format!("case_{identifier}"),
+        Some(_) => identifier,
+    }
+}
+
+/// Turns a value such as `USD` or `in-progress` into a Rust type or variant
+/// name such as `Usd` or `InProgress`.
+fn pascal_case(name: &str) -> String {
+    snake_case(name)
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+    #[test]
+    fn scenarios_become_ignored_tests_with_bound_placeholders() {
+        let yaml = r#"workflow: Accounts
+swimlanes:
+  - backend: "Backend"
+commands:
+  OpenAccount:
+    description: "Open an account"
+    swimlane: backend
+    data:
+      account_id: AccountId
+      owner: EmailAddress<Verified>
+    errors:
+      AlreadyOpen:
+        description: "The account is already open"
+        data:
+          account_id: AccountId
+    tests:
+      "Opens once":
+        Given:
+        When:
+          - OpenAccount:
+              account_id: A
+              owner: B
+        Then:
+          - AccountOpened:
+              account_id: A
+      "Already open":
+        examples:
+          B: jane@example.com
+        Given:
+          - AccountOpened:
+              account_id: A
+        When:
+          - OpenAccount:
+              account_id: A
+              owner: B
+        Then:
+          - AlreadyOpen:
+              account_id: A
+events:
+  AccountOpened:
+    description: "An account was opened"
+    swimlane: backend
+    data:
+      account_id: AccountId
+"#;
+        let model =
+            yaml_converter::convert_yaml_to_domain(yaml_parser::parse_yaml(yaml).unwrap()).unwrap();
+
+        let source = generate(&model);
+        let module = source.split("mod open_account {").nth(1).unwrap();
+        assert_eq!(
+            module,
+            r#"
+    use super::*;
+
+    /// Already open
+    #[test]
+    #[ignore = "generated skeleton"]
+    fn already_open() {
+        let a: AccountId = todo!("value for A");
+        let b: EmailAddress<Verified> = todo!("value for B, e.g. jane@example.com");
+        let given: Vec<Event> = vec![
+            AccountOpened { account_id: a.clone() }.into(),
+        ];
+        let when = OpenAccount { account_id: a.clone(), owner: b.clone() };
+        let then = AlreadyOpen { account_id: a.clone() };
+        todo!("issue `when` against a stream holding `given` and assert it is rejected with `then`");
+    }
+
+    /// Opens once
+    #[test]
+    #[ignore = "generated skeleton"]
+    fn opens_once() {
+        let a: AccountId = todo!("value for A");
+        let b: EmailAddress<Verified> = todo!("value for B");
+        let given: Vec<Event> = vec![];
+        let when = OpenAccount { account_id: a.clone(), owner: b.clone() };
+        let then: Vec<Event> = vec![
+            AccountOpened { account_id: a.clone() }.into(),
+        ];
+        todo!("issue `when` against a stream holding `given` and assert it records `then`");
+    }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn declared_enumerations_become_enums_and_restrict_placeholders() {
+        let yaml = r#"workflow: Payments
+swimlanes:
+  - backend: "Backend"
+types:
+  Currency:
+    enum: [USD, EUR, in-progress]
+  Amount:
+    type: Integer
+    min: 1
+commands:
+  Pay:
+    description: "Pay"
+    swimlane: backend
+    data:
+      currency: Currency
+      amount: Amount
+    tests:
+      "Pays":
+        Given:
+        When:
+          - Pay:
+              currency: C
+              amount: N
+        Then:
+          - Paid:
+              currency: C
+events:
+  Paid:
+    description: "Paid"
+    swimlane: backend
+    data:
+      currency: Currency
+"#;
+        let model =
+            yaml_converter::convert_yaml_to_domain(yaml_parser::parse_yaml(yaml).unwrap()).unwrap();
+
+        let source = generate(&model);
+
+        assert!(source.contains(
+            "\n/// One of USD, EUR, in-progress.\n#[derive(Debug, Clone, Copy, PartialEq, Eq)]\npub enum Currency {\n    Usd,\n    Eur,\n    InProgress,\n}\n"
+        ));
+        assert!(
+            source.contains(
+                r#"let c: Currency = todo!("value for C (one of USD, EUR, in-progress)");"#
+            )
+        );
+        assert!(
+            source.contains(r#"let n: Amount = todo!("value for N (Integer of at least 1)");"#)
+        );
+    }
+
+    #[test]
+    fn names_become_snake_case_identifiers() {
+        assert_eq!(snake_case("Main Success"), "main_success");
+        assert_eq!(snake_case("CreateUserAccount"), "create_user_account");
+        assert_eq!(
+            snake_case("Account Already Exists!"),
+            "account_already_exists"
+        );
+        assert_eq!(snake_case("2 retries"), "case_2_retries");
+        assert_eq!(snake_case("A"), "a");
+    }
+}
@@ -0,0 +1,231 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Starter models from the words of an event-storming session.
+//!
+//! Sticky notes name events and commands but little else. This module
+//! declares each of them with a TODO description, pairs every command with
+//! the event its name suggests it records (`PlaceOrder` with `OrderPlaced`),
+//! and gives each pair a slice, leaving the rest of the model to be filled
+//! in by hand.
+
+/// The words collected for a starter model.
+#[derive(Debug, Clone, Default)]
+pub struct WordList {
+    /// Name of the workflow, if the session gave it one.
+    pub workflow: Option<String>,
+    /// Event names, e.g. `OrderPlaced` or `Order placed`.
+    pub events: Vec<String>,
+    /// Command names, e.g. `PlaceOrder` or `place order`.
+    pub commands: Vec<String>,
+}
+
+/// Generates a starter model in the YAML format from a word list.
+pub fn generate(words: &WordList) -> String {
+    let events = entity_names(&words.events);
+    let commands = entity_names(&words.commands);
+    let workflow = words
+        .workflow
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .unwrap_or("TODO: Name this workflow");
+
+    let mut yaml = format!(
+        "# Starter model generated by event_modeler scaffold.\n\
+         # Replace each TODO, then add data fields, views, and read models.\n\
+         workflow: {}\n\
+         \n\
+         swimlanes:\n  \
+           - application:\n      name: \"Commands\"\n      role: application\n  \
+           - stream:\n      name: \"Events\"\n      role: stream\n",
+        quote(workflow)
+    );
+
+    if !commands.is_empty() {
+        yaml.push_str("\ncommands:\n");
+        for name in &commands {
+            yaml.push_str(&declaration(name, "application"));
+        }
+    }
+    if !events.is_empty() {
+        yaml.push_str("\nevents:\n");
+        for name in &events {
+            yaml.push_str(&declaration(name, "stream"));
+        }
+    }
+
+    let mut slices = String::new();
+    let mut unpaired = Vec::new();
+    for command in &commands {
+        match resulting_event(command, &events) {
+            Some(event) => slices.push_str(&format!(
+                "  - name: {}\n    connections:\n      - {command} -> {event}\n",
+                quote(&words_of(command).join(" "))
+            )),
+            None => unpaired.push(command.as_str()),
+        }
+    }
+    if !slices.is_empty() {
+        yaml.push_str("\nslices:\n");
+        yaml.push_str(&slices);
+    }
+    for command in unpaired {
+        yaml.push_str(&format!(
+            "# TODO: No event's name resembles {command}; connect it by hand.\n"
+        ));
+    }
+    yaml
+}
+
+/// Declares an entity with a TODO description in `swimlane`.
+fn declaration(name: &str, swimlane: &str) -> String {
+    format!(
+        "  {name}:\n    description: {}\n    swimlane: {swimlane}\n",
+        quote(&format!("TODO: Describe {name}"))
+    )
+}
+
+/// Turns the written words into distinct entity names in `PascalCase`,
+/// dropping blanks.
+fn entity_names(words: &[String]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for word in words {
+        let name: String = words_of(word)
+            .iter()
+            .map(|part| {
+                let mut chars = part.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect();
+        if !name.is_empty() && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Splits a name into its words at spaces, punctuation, and case changes.
+fn words_of(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lowercase = false;
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lowercase = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_lowercase {
+            words.push(std::mem::take(&mut current));
+        }
+        current.push(c);
+        previous_lowercase = c.is_lowercase() || c.is_numeric();
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Reduces a word to a stem shared by its past tense, so that `Place` and
+/// `Placed` or `Ship` and `Shipped` compare equal.
+fn stem(word: &str) -> String {
+    let mut stem = word.to_lowercase();
+    if stem.len() > 3 && stem.ends_with("ed") {
+        stem.truncate(stem.len() - 2);
+    }
+    // Shipped -> shipp -> ship, and Add -> ad to match Added
+    let mut chars = stem.chars().rev();
+    if chars.next().is_some_and(|last| chars.next() == Some(last)) {
+        stem.pop();
+    }
+    if stem.len() > 3 && stem.ends_with('e') {
+        stem.pop();
+    }
+    stem
+}
+
+/// Returns the event whose name holds every word of `command`'s, preferring
+/// the one with the fewest other words.
+fn resulting_event<'a>(command: &str, events: &'a [String]) -> Option<&'a String> {
+    let command_stems: Vec<String> = words_of(command).iter().map(|w| stem(w)).collect();
+    events
+        .iter()
+        .filter_map(|event| {
+            let event_stems: Vec<String> = words_of(event).iter().map(|w| stem(w)).collect();
+            command_stems
+                .iter()
+                .all(|stem| event_stems.contains(stem))
+                .then_some((event_stems.len(), event))
+        })
+        .min_by_key(|(extra, _)| *extra)
+        .map(|(_, event)| event)
+}
+
+/// Quotes text as a YAML double-quoted scalar.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::yaml_parser;
+
+    fn words(events: &[&str], commands: &[&str]) -> WordList {
+        WordList {
+            workflow: Some("Ordering".to_string()),
+            events: events.iter().map(|s| s.to_string()).collect(),
+            commands: commands.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn commands_are_paired_with_the_events_their_names_suggest() {
+        let yaml = generate(&words(
+            &["OrderPlaced", "OrderShipped", "OrderCancelled"],
+            &["PlaceOrder", "ShipOrder"],
+        ));
+
+        assert!(yaml.contains("      - PlaceOrder -> OrderPlaced\n"));
+        assert!(yaml.contains("      - ShipOrder -> OrderShipped\n"));
+        assert!(yaml.contains("  - name: \"Place Order\"\n"));
+        assert!(!yaml.contains("-> OrderCancelled"));
+    }
+
+    #[test]
+    fn unmatched_commands_are_left_as_todos() {
+        let yaml = generate(&words(&["PaymentTaken"], &["RefundPayment"]));
+
+        assert!(yaml.contains("# TODO: No event's name resembles RefundPayment"));
+        assert!(!yaml.contains("slices:"));
+    }
+
+    #[test]
+    fn sticky_note_spelling_becomes_entity_names() {
+        let yaml = generate(&words(&["order placed", "Order placed"], &["place order"]));
+
+        assert_eq!(yaml.matches("  OrderPlaced:\n").count(), 1);
+        assert!(yaml.contains("      - PlaceOrder -> OrderPlaced\n"));
+    }
+
+    #[test]
+    fn generated_model_parses() {
+        let yaml = generate(&words(
+            &["OrderPlaced", "ItemAdded"],
+            &["PlaceOrder", "AddItem"],
+        ));
+
+        let model = yaml_parser::parse_yaml(&yaml).expect("starter model should parse");
+
+        assert_eq!(model.commands.len(), 2);
+        assert_eq!(model.events.len(), 2);
+        assert_eq!(model.slices.len(), 2);
+    }
+}
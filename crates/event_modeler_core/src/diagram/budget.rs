@@ -0,0 +1,90 @@
+//! Limits on how much work a render may spend routing connectors.
+//!
+//! Routing is the only stage whose cost grows faster than the model, so a
+//! render that runs out of budget still finishes: connectors routed before
+//! the budget ran out keep their routes and the rest are drawn as straight
+//! lines.
+
+use std::time::{Duration, Instant};
+
+/// Wall-clock and iteration limits for a single render.
+///
+/// The default budget is unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderBudget {
+    deadline: Option<Instant>,
+    max_routing_iterations: Option<usize>,
+}
+
+impl RenderBudget {
+    /// Creates a budget without limits.
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Limits the render to finish within `timeout` of now.
+    ///
+    /// A timeout too long to represent leaves the render without a deadline.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self {
+            deadline: Instant::now().checked_add(timeout),
+            ..self
+        }
+    }
+
+    /// Limits the number of connectors routed, not counting routes reused
+    /// from the render cache.
+    pub fn with_max_routing_iterations(self, iterations: usize) -> Self {
+        Self {
+            max_routing_iterations: Some(iterations),
+            ..self
+        }
+    }
+
+    /// Returns when the render must finish, if it has a deadline.
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Returns whether the budget has run out after `iterations` connectors
+    /// have been routed.
+    pub fn is_exhausted(&self, iterations: usize) -> bool {
+        self.max_routing_iterations
+            .is_some_and(|max| iterations >= max)
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited_budget_is_never_exhausted() {
+        assert!(!RenderBudget::unlimited().is_exhausted(usize::MAX));
+    }
+
+    #[test]
+    fn iteration_limit_is_exhausted_once_reached() {
+        let budget = RenderBudget::unlimited().with_max_routing_iterations(2);
+
+        assert!(!budget.is_exhausted(1));
+        assert!(budget.is_exhausted(2));
+    }
+
+    #[test]
+    fn elapsed_timeout_is_exhausted() {
+        let budget = RenderBudget::unlimited().with_timeout(Duration::ZERO);
+
+        assert!(budget.is_exhausted(0));
+    }
+
+    #[test]
+    fn unrepresentable_timeout_sets_no_deadline() {
+        let budget = RenderBudget::unlimited().with_timeout(Duration::MAX);
+
+        assert_eq!(budget, RenderBudget::unlimited());
+    }
+}
@@ -0,0 +1,368 @@
+//! Event model diagram builder.
+//!
+//! This module provides the core diagram building functionality.
+
+use crate::event_model::hotspots::HotspotList;
+use crate::event_model::ownership::OwnershipReport;
+use crate::event_model::patterns::SlicePatterns;
+use crate::event_model::roles;
+use crate::event_model::yaml_types;
+use crate::infrastructure::types::{NonEmpty, NonEmptyString};
+use std::collections::HashMap;
+
+use super::Result;
+use super::layout_types::Px;
+
+/// Represents a complete event model diagram.
+///
+/// This type is built from a YAML event model and contains all the
+/// information needed to render the diagram.
+#[derive(Debug, Clone)]
+pub struct EventModelDiagram {
+    /// The workflow title displayed at the top of the diagram.
+    workflow_title: NonEmptyString,
+    /// The description, authors, version, and date of the model.
+    header: yaml_types::WorkflowHeader,
+    /// The swimlanes to draw, with each collapsed group reduced to one.
+    swimlanes: NonEmpty<yaml_types::Swimlane>,
+    /// The swimlane groups defined in the model.
+    swimlane_groups: Vec<yaml_types::SwimlaneGroup>,
+    /// The slices defined in the model.
+    slices: Vec<yaml_types::Slice>,
+    /// The views defined in the model.
+    views: HashMap<yaml_types::ViewName, yaml_types::ViewDefinition>,
+    /// The commands defined in the model.
+    commands: HashMap<yaml_types::CommandName, yaml_types::CommandDefinition>,
+    /// The events defined in the model.
+    events: HashMap<yaml_types::EventName, yaml_types::EventDefinition>,
+    /// The projections defined in the model.
+    projections: HashMap<yaml_types::ProjectionName, yaml_types::ProjectionDefinition>,
+    /// The queries defined in the model.
+    queries: HashMap<yaml_types::QueryName, yaml_types::QueryDefinition>,
+    /// The automations defined in the model.
+    automations: HashMap<yaml_types::AutomationName, yaml_types::AutomationDefinition>,
+    /// The effective owner of each entity.
+    ownership: OwnershipReport,
+    /// The risks raised about entities and connections.
+    hotspots: HotspotList,
+    /// The Event Modeling pattern each slice follows.
+    patterns: SlicePatterns,
+    /// How slices are arranged into columns.
+    layout: yaml_types::LayoutStrategy,
+    /// Rules the `constraints` layout places entities by.
+    layout_constraints: Vec<yaml_types::LayoutConstraint>,
+    /// How slice headers are drawn.
+    slice_headers: yaml_types::SliceHeaderStyle,
+    /// How entity boxes are sized, by entity type.
+    entity_sizing: yaml_types::EntitySizing,
+    /// Spacing of the grid positions and connector bends snap to.
+    grid: Option<Px>,
+    /// Room connectors keep from entities and from each other.
+    clearance: yaml_types::ConnectorClearance,
+    /// Background and watermark of the canvas.
+    canvas: yaml_types::Canvas,
+    /// Markers drawn at connection targets, by the types of entity joined.
+    connection_markers: yaml_types::ConnectionMarkers,
+    /// How connections drawn between the same two entities are shown.
+    duplicate_connections: yaml_types::DuplicateConnections,
+    /// How swimlanes a slice leaves empty are drawn in its column.
+    unused_lanes: yaml_types::UnusedLanes,
+    /// Declared domain types.
+    types: HashMap<yaml_types::TypeName, yaml_types::TypeDefinition>,
+    /// Custom entity kinds, by name.
+    extensions: HashMap<yaml_types::ExtensionName, yaml_types::EntityExtension>,
+}
+
+impl EventModelDiagram {
+    /// Creates a new event model diagram from a YAML model.
+    ///
+    /// Swimlanes are ordered by role when the model asks for it. Each
+    /// collapsed swimlane group is drawn as a single swimlane named after the
+    /// group, holding the entities of all its members.
+    pub fn from_yaml_model(model: &yaml_types::YamlEventModel) -> Result<Self> {
+        let swimlanes = match model.swimlane_order {
+            yaml_types::SwimlaneOrder::Declared => model.swimlanes.clone(),
+            yaml_types::SwimlaneOrder::Convention => {
+                roles::order_by_convention(&model.swimlanes, &model.swimlane_groups)
+            }
+        };
+        let (swimlanes, moved) = collapse_groups(&swimlanes, &model.swimlane_groups);
+        Ok(EventModelDiagram {
+            workflow_title: model.workflow.clone().into_inner(),
+            header: model.header.clone(),
+            swimlanes,
+            swimlane_groups: model.swimlane_groups.clone(),
+            slices: relocate_waypoints(&model.slices, &moved),
+            views: relocate(&model.views, &moved, |def| &mut def.swimlane),
+            commands: relocate(&model.commands, &moved, |def| &mut def.swimlane),
+            events: relocate(&model.events, &moved, |def| &mut def.swimlane),
+            projections: relocate(&model.projections, &moved, |def| &mut def.swimlane),
+            queries: relocate(&model.queries, &moved, |def| &mut def.swimlane),
+            automations: relocate(&model.automations, &moved, |def| &mut def.swimlane),
+            ownership: OwnershipReport::from_model(model),
+            hotspots: HotspotList::from_model(model),
+            patterns: SlicePatterns::from_model(model),
+            layout: model.layout,
+            layout_constraints: model.layout_constraints.clone(),
+            slice_headers: model.slice_headers.clone(),
+            entity_sizing: model.entity_sizing.clone(),
+            grid: model.grid.map(|grid| Px::new(grid.value())),
+            clearance: model.clearance,
+            canvas: model.canvas.clone(),
+            connection_markers: model.connection_markers.clone(),
+            duplicate_connections: model.duplicate_connections,
+            unused_lanes: model.unused_lanes,
+            types: model.types.clone(),
+            extensions: model.extensions.clone(),
+        })
+    }
+
+    /// Gets the workflow title.
+    pub fn workflow_title(&self) -> &NonEmptyString {
+        &self.workflow_title
+    }
+
+    /// Gets the description, authors, version, and date of the model.
+    pub fn header(&self) -> &yaml_types::WorkflowHeader {
+        &self.header
+    }
+
+    /// Gets the swimlanes.
+    pub fn swimlanes(&self) -> &NonEmpty<yaml_types::Swimlane> {
+        &self.swimlanes
+    }
+
+    /// Gets the swimlane groups, including collapsed ones.
+    pub fn swimlane_groups(&self) -> &[yaml_types::SwimlaneGroup] {
+        &self.swimlane_groups
+    }
+
+    /// Gets the slices.
+    pub fn slices(&self) -> &[yaml_types::Slice] {
+        &self.slices
+    }
+
+    /// Gets the views.
+    pub fn views(&self) -> &HashMap<yaml_types::ViewName, yaml_types::ViewDefinition> {
+        &self.views
+    }
+
+    /// Gets the commands.
+    pub fn commands(&self) -> &HashMap<yaml_types::CommandName, yaml_types::CommandDefinition> {
+        &self.commands
+    }
+
+    /// Gets the events.
+    pub fn events(&self) -> &HashMap<yaml_types::EventName, yaml_types::EventDefinition> {
+        &self.events
+    }
+
+    /// Gets the projections.
+    pub fn projections(
+        &self,
+    ) -> &HashMap<yaml_types::ProjectionName, yaml_types::ProjectionDefinition> {
+        &self.projections
+    }
+
+    /// Gets the queries.
+    pub fn queries(&self) -> &HashMap<yaml_types::QueryName, yaml_types::QueryDefinition> {
+        &self.queries
+    }
+
+    /// Gets the automations.
+    pub fn automations(
+        &self,
+    ) -> &HashMap<yaml_types::AutomationName, yaml_types::AutomationDefinition> {
+        &self.automations
+    }
+
+    /// Gets the effective owner of each entity.
+    pub fn ownership(&self) -> &OwnershipReport {
+        &self.ownership
+    }
+
+    /// Gets the risks raised about entities and connections.
+    pub fn hotspots(&self) -> &HotspotList {
+        &self.hotspots
+    }
+
+    /// Gets the Event Modeling pattern each slice follows.
+    pub fn patterns(&self) -> &SlicePatterns {
+        &self.patterns
+    }
+
+    /// Gets how slices are arranged into columns.
+    pub fn layout(&self) -> yaml_types::LayoutStrategy {
+        self.layout
+    }
+
+    /// Gets the rules the `constraints` layout places entities by.
+    pub fn layout_constraints(&self) -> &[yaml_types::LayoutConstraint] {
+        &self.layout_constraints
+    }
+
+    /// Gets how slice headers are drawn.
+    pub fn slice_headers(&self) -> &yaml_types::SliceHeaderStyle {
+        &self.slice_headers
+    }
+
+    /// Gets how entity boxes are sized.
+    pub fn entity_sizing(&self) -> &yaml_types::EntitySizing {
+        &self.entity_sizing
+    }
+
+    /// Gets the spacing of the grid entity positions and connector bends
+    /// snap to, if any.
+    pub fn grid(&self) -> Option<Px> {
+        self.grid
+    }
+
+    /// Gets the markers drawn at connection targets.
+    pub fn connection_markers(&self) -> &yaml_types::ConnectionMarkers {
+        &self.connection_markers
+    }
+
+    /// Gets the room connectors keep from entities and from each other.
+    pub fn clearance(&self) -> yaml_types::ConnectorClearance {
+        self.clearance
+    }
+
+    /// Gets the background and watermark of the canvas.
+    pub fn canvas(&self) -> &yaml_types::Canvas {
+        &self.canvas
+    }
+
+    /// Gets how connections drawn between the same two entities are shown.
+    pub fn duplicate_connections(&self) -> yaml_types::DuplicateConnections {
+        self.duplicate_connections
+    }
+
+    /// Gets how swimlanes a slice leaves empty are drawn in its column.
+    pub fn unused_lanes(&self) -> yaml_types::UnusedLanes {
+        self.unused_lanes
+    }
+
+    /// Gets the declared domain types.
+    pub fn types(&self) -> &HashMap<yaml_types::TypeName, yaml_types::TypeDefinition> {
+        &self.types
+    }
+
+    /// Gets the custom entity kinds.
+    pub fn extensions(&self) -> &HashMap<yaml_types::ExtensionName, yaml_types::EntityExtension> {
+        &self.extensions
+    }
+
+    /// Returns the custom kind the named entity declares, with its
+    /// declaration.
+    pub fn kind_of(
+        &self,
+        entity: &str,
+    ) -> Option<(&yaml_types::ExtensionName, &yaml_types::EntityExtension)> {
+        let name = NonEmptyString::parse(entity.to_string()).ok()?;
+        let kind = [
+            self.views
+                .get(&yaml_types::ViewName::new(name.clone()))
+                .map(|def| &def.kind),
+            self.commands
+                .get(&yaml_types::CommandName::new(name.clone()))
+                .map(|def| &def.kind),
+            self.events
+                .get(&yaml_types::EventName::new(name.clone()))
+                .map(|def| &def.kind),
+            self.projections
+                .get(&yaml_types::ProjectionName::new(name.clone()))
+                .map(|def| &def.kind),
+            self.queries
+                .get(&yaml_types::QueryName::new(name.clone()))
+                .map(|def| &def.kind),
+            self.automations
+                .get(&yaml_types::AutomationName::new(name))
+                .map(|def| &def.kind),
+        ]
+        .into_iter()
+        .flatten()
+        .next()?
+        .as_ref()?;
+        self.extensions.get_key_value(kind)
+    }
+}
+
+/// Replaces the members of each collapsed group with one swimlane labeled
+/// with the group's name, returning the swimlanes and where each removed
+/// member's entities now live.
+fn collapse_groups(
+    swimlanes: &NonEmpty<yaml_types::Swimlane>,
+    groups: &[yaml_types::SwimlaneGroup],
+) -> (
+    NonEmpty<yaml_types::Swimlane>,
+    HashMap<yaml_types::SwimlaneId, yaml_types::SwimlaneId>,
+) {
+    let mut moved = HashMap::new();
+    for group in groups.iter().filter(|group| group.collapsed) {
+        let kept = group.swimlanes.first();
+        for member in group.swimlanes.tail() {
+            moved.insert(member.clone(), kept.clone());
+        }
+    }
+
+    let lanes = swimlanes
+        .iter()
+        .filter(|lane| !moved.contains_key(&lane.id))
+        .map(|lane| {
+            let collapsed = groups
+                .iter()
+                .find(|group| group.collapsed && group.swimlanes.first() == &lane.id);
+            match collapsed {
+                Some(group) => yaml_types::Swimlane {
+                    id: lane.id.clone(),
+                    name: yaml_types::SwimlaneName::new(group.name.clone().into_inner()),
+                    owner: None,
+                    style: yaml_types::SwimlaneStyle::default(),
+                    role: lane.role,
+                },
+                None => lane.clone(),
+            }
+        });
+    // Every collapsed group keeps its first member, so at least one lane remains.
+    let swimlanes = NonEmpty::try_from_iter(lanes).unwrap_or_else(|_| swimlanes.clone());
+    (swimlanes, moved)
+}
+
+/// Moves entities out of collapsed swimlanes into their group's swimlane.
+fn relocate<K: Clone + Eq + std::hash::Hash, D: Clone>(
+    definitions: &HashMap<K, D>,
+    moved: &HashMap<yaml_types::SwimlaneId, yaml_types::SwimlaneId>,
+    swimlane: impl Fn(&mut D) -> &mut yaml_types::SwimlaneId,
+) -> HashMap<K, D> {
+    definitions
+        .iter()
+        .map(|(name, definition)| {
+            let mut definition = definition.clone();
+            let lane = swimlane(&mut definition);
+            if let Some(target) = moved.get(lane) {
+                *lane = target.clone();
+            }
+            (name.clone(), definition)
+        })
+        .collect()
+}
+
+/// Moves waypoints in collapsed swimlanes into their group's swimlane.
+fn relocate_waypoints(
+    slices: &[yaml_types::Slice],
+    moved: &HashMap<yaml_types::SwimlaneId, yaml_types::SwimlaneId>,
+) -> Vec<yaml_types::Slice> {
+    let mut slices = slices.to_vec();
+    for slice in &mut slices {
+        for waypoint in slice
+            .connections
+            .iter_mut()
+            .flat_map(|connection| &mut connection.via)
+        {
+            if let Some(target) = moved.get(&waypoint.swimlane) {
+                waypoint.swimlane = target.clone();
+            }
+        }
+    }
+    slices
+}
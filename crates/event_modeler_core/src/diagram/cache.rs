@@ -0,0 +1,31 @@
+//! Caches shared between renders of the same model.
+//!
+//! A [`RenderCache`] bundles every memoized intermediate result of the
+//! rendering pipeline. Keep one alive across renders (watch mode, several
+//! output formats in one invocation) to skip work for unchanged entities and
+//! connectors.
+
+use super::DimensionCache;
+use crate::routing::RouteCache;
+
+/// Memoized intermediate results reused across renders.
+#[derive(Debug, Clone, Default)]
+pub struct RenderCache {
+    /// Calculated entity dimensions.
+    pub dimensions: DimensionCache,
+    /// Routed connector paths.
+    pub routes: RouteCache,
+}
+
+impl RenderCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Discards everything cached so far.
+    pub fn clear(&mut self) {
+        self.dimensions.clear();
+        self.routes.clear();
+    }
+}
@@ -0,0 +1,215 @@
+//! Enforcement of the room connectors keep from entities and each other.
+//!
+//! The router lays out each connector on its own, so a connector's middle
+//! segments can pass close to entities it does not join or run alongside
+//! another connector. This pass slides such segments sideways, one connector
+//! at a time, to the nearest offset where they keep the routing margin from
+//! entities and the minimum separation from parallel segments of unrelated
+//! connectors. Segments touching either end of a connector stay put so it
+//! stays attached, and a segment with no clear offset between its neighbours
+//! is left where it is for the collision report to flag.
+
+use super::collisions::{RoutedConnection, Segment, segments};
+use super::layout_types::Px;
+use super::routing_types::{Point, Rectangle, RoutePath};
+use crate::infrastructure::types::NonEmpty;
+
+/// Offsets tried on each side of a crowded segment before giving up.
+const MAX_NUDGES: u32 = 8;
+
+/// Room each connector keeps, and the grid nudged bends stay on.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Clearance {
+    /// Pixels kept between a connector and entities it does not join.
+    pub margin: Px,
+    /// Pixels kept between parallel segments of unrelated connectors.
+    pub separation: Px,
+    /// Spacing of the grid bends snap to, if any.
+    pub grid: Option<Px>,
+}
+
+/// Slides the middle segments of each connector clear of entities and of
+/// other connectors' segments, where an offset between its neighbours is.
+pub(super) fn enforce(
+    connections: &mut [RoutedConnection],
+    entities: &[Rectangle],
+    clearance: Clearance,
+) {
+    for index in 0..connections.len() {
+        let mut nodes: Vec<Point> = connections[index].route.nodes.iter().copied().collect();
+        let mut moved = false;
+        // Only segments between two bends can move without detaching an end
+        for segment in 1..nodes.len().saturating_sub(2) {
+            if crowding(connections, index, entities, &nodes, segment, clearance) == 0 {
+                continue;
+            }
+            let clear = offsets(&nodes, segment, clearance).find_map(|candidate| {
+                let shifted = shift(&nodes, segment, candidate);
+                (crowding(connections, index, entities, &shifted, segment, clearance) == 0)
+                    .then_some(shifted)
+            });
+            if let Some(shifted) = clear {
+                nodes = shifted;
+                moved = true;
+            }
+        }
+        if moved {
+            let total_cost = nodes
+                .windows(2)
+                .map(|pair| pair[0].manhattan_distance(&pair[1]))
+                .sum();
+            if let Ok(nodes) = NonEmpty::try_from(nodes) {
+                connections[index].route = RoutePath::new(nodes, total_cost);
+            }
+        }
+    }
+}
+
+/// Returns the coordinates a segment may move to, nearest first: multiples
+/// of the separation on either side, kept strictly between the segments
+/// before and after it so the route never doubles back.
+fn offsets(nodes: &[Point], segment: usize, clearance: Clearance) -> impl Iterator<Item = Px> {
+    let vertical = nodes[segment].x == nodes[segment + 1].x;
+    let along = |point: &Point| if vertical { point.x } else { point.y };
+    let current = along(&nodes[segment]).get() as i64;
+    let (before, after) = (along(&nodes[segment - 1]), along(&nodes[segment + 2]));
+    let (low, high) = (
+        before.min(after).get() as i64,
+        before.max(after).get() as i64,
+    );
+    let step = i64::from(clearance.separation.get().max(1));
+    let grid = clearance.grid;
+    (1..=i64::from(MAX_NUDGES))
+        .flat_map(move |k| [current + k * step, current - k * step])
+        .filter(move |&candidate| candidate > low && candidate < high)
+        .map(move |candidate| {
+            let candidate = Px::new(candidate as u32);
+            grid.map_or(candidate, |grid| candidate.snap_to(grid))
+        })
+}
+
+/// Returns the nodes with a segment moved to `coordinate` across its axis.
+fn shift(nodes: &[Point], segment: usize, coordinate: Px) -> Vec<Point> {
+    let vertical = nodes[segment].x == nodes[segment + 1].x;
+    let mut shifted = nodes.to_vec();
+    for point in &mut shifted[segment..=segment + 1] {
+        if vertical {
+            point.x = coordinate;
+        } else {
+            point.y = coordinate;
+        }
+    }
+    shifted
+}
+
+/// Counts the clearances broken by a segment and the two it joins: entities
+/// other than the connector's own passed too closely, and parallel segments
+/// of unrelated connectors run alongside too closely.
+fn crowding(
+    connections: &[RoutedConnection],
+    index: usize,
+    entities: &[Rectangle],
+    nodes: &[Point],
+    segment: usize,
+    clearance: Clearance,
+) -> usize {
+    let connection = &connections[index];
+    let nearby: Vec<Segment> = nodes[segment - 1..=segment + 2]
+        .windows(2)
+        .filter_map(|pair| Segment::between(&pair[0], &pair[1]))
+        .collect();
+    let own = [&connection.source, &connection.target];
+    let near_entities = entities
+        .iter()
+        .filter(|rect| !own.contains(rect))
+        .filter(|rect| {
+            nearby
+                .iter()
+                .any(|piece| piece.crosses(rect) || piece.distance_to(rect) < clearance.margin)
+        })
+        .count();
+    let near_connectors = connections
+        .iter()
+        .enumerate()
+        .filter(|&(other, _)| other != index)
+        .filter(|(_, other)| {
+            // Connectors sharing an entity converge near it by design
+            let ends = [&other.source, &other.target];
+            !ends.contains(&&connection.source) && !ends.contains(&&connection.target)
+        })
+        .filter(|(_, other)| {
+            segments(&other.route).iter().any(|theirs| {
+                nearby.iter().any(|piece| {
+                    piece
+                        .parallel_distance(theirs)
+                        .is_some_and(|distance| distance < clearance.separation)
+                })
+            })
+        })
+        .count();
+    near_entities + near_connectors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: u32, y: u32, width: u32, height: u32) -> Rectangle {
+        Rectangle::new(Px::new(x), Px::new(y), Px::new(width), Px::new(height))
+    }
+
+    fn connection(source: Rectangle, target: Rectangle, nodes: &[(u32, u32)]) -> RoutedConnection {
+        let points: Vec<Point> = nodes
+            .iter()
+            .map(|&(x, y)| Point::new(Px::new(x), Px::new(y)))
+            .collect();
+        RoutedConnection {
+            name: "A -> B".to_string(),
+            source,
+            target,
+            route: RoutePath::new(NonEmpty::try_from(points).unwrap(), Px::ZERO),
+        }
+    }
+
+    fn xs(connection: &RoutedConnection) -> Vec<u32> {
+        connection
+            .route
+            .nodes
+            .iter()
+            .map(|point| point.x.get())
+            .collect()
+    }
+
+    const CLEARANCE: Clearance = Clearance {
+        margin: Px::new(10),
+        separation: Px::new(8),
+        grid: None,
+    };
+
+    #[test]
+    fn slides_a_middle_segment_clear_of_an_entity() {
+        let (a, b) = (rect(0, 0, 20, 20), rect(200, 100, 20, 20));
+        // The vertical run at x = 110 passes 5px from the obstacle's left edge
+        let obstacle = rect(115, 40, 40, 20);
+        let mut connections = [connection(
+            a.clone(),
+            b.clone(),
+            &[(20, 10), (110, 10), (110, 110), (200, 110)],
+        )];
+
+        enforce(&mut connections, &[a, b, obstacle], CLEARANCE);
+
+        assert_eq!(xs(&connections[0]), [20, 102, 102, 200]);
+    }
+
+    #[test]
+    fn keeps_ends_attached_and_leaves_clear_routes_alone() {
+        let (a, b) = (rect(0, 0, 20, 20), rect(200, 100, 20, 20));
+        let nodes = [(20, 10), (110, 10), (110, 110), (200, 110)];
+        let mut connections = [connection(a.clone(), b.clone(), &nodes)];
+
+        enforce(&mut connections, &[a, b], CLEARANCE);
+
+        assert_eq!(xs(&connections[0]), [20, 110, 110, 200]);
+    }
+}
@@ -0,0 +1,367 @@
+//! Detection of routing defects in rendered connectors.
+//!
+//! Once every connector is routed, each segment is checked against the
+//! entities it does not connect and against the segments of other
+//! connectors. Crossings, connectors passing closer to entities than the
+//! routing margin, and near-parallel runs are reported so regressions in
+//! routing quality show up as warnings rather than only in the picture.
+
+use super::layout_types::Px;
+use super::routing_types::{Point, Rectangle, RoutePath};
+
+/// A routed connector and the entities it joins.
+pub(super) struct RoutedConnection {
+    /// The connection as written, e.g. `PlaceOrder -> OrderPlaced`.
+    pub name: String,
+    /// The entity the connector leaves.
+    pub source: Rectangle,
+    /// The entity the connector enters.
+    pub target: Rectangle,
+    /// The routed path.
+    pub route: RoutePath,
+}
+
+/// A routing defect between a connector and an entity or another connector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Collision {
+    /// A segment passes through an entity the connector does not join.
+    CrossesEntity {
+        /// The connector.
+        connection: String,
+        /// The entity it passes through.
+        entity: String,
+    },
+    /// A segment passes closer than the routing margin to an entity the
+    /// connector does not join.
+    NearEntity {
+        /// The connector.
+        connection: String,
+        /// The entity it passes.
+        entity: String,
+        /// Distance between the segment and the entity.
+        distance: Px,
+    },
+    /// Segments of two connectors run side by side closer than the minimum
+    /// separation, or on top of each other.
+    TooClose {
+        /// The first connector.
+        first: String,
+        /// The second connector.
+        second: String,
+        /// Distance between the segments.
+        distance: Px,
+    },
+}
+
+impl Collision {
+    /// Describes the defect for a warning.
+    pub(super) fn message(&self) -> String {
+        match self {
+            Self::CrossesEntity { connection, entity } => {
+                format!("Connection {connection} passes through entity '{entity}'")
+            }
+            Self::NearEntity {
+                connection,
+                entity,
+                distance,
+            } => format!("Connection {connection} passes {distance}px from entity '{entity}'"),
+            Self::TooClose {
+                first,
+                second,
+                distance: Px::ZERO,
+            } => format!("Connections {first} and {second} overlap"),
+            Self::TooClose {
+                first,
+                second,
+                distance,
+            } => format!("Connections {first} and {second} run {distance}px apart"),
+        }
+    }
+}
+
+/// An axis-aligned piece of a route.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Segment {
+    /// Runs along `y` from `x1` to `x2`, with `x1 <= x2`.
+    Horizontal { y: Px, x1: Px, x2: Px },
+    /// Runs along `x` from `y1` to `y2`, with `y1 <= y2`.
+    Vertical { x: Px, y1: Px, y2: Px },
+}
+
+impl Segment {
+    /// Returns the segment between two points, or `None` if they coincide or
+    /// are not aligned on an axis.
+    pub(super) fn between(a: &Point, b: &Point) -> Option<Self> {
+        if a.y == b.y && a.x != b.x {
+            Some(Self::Horizontal {
+                y: a.y,
+                x1: a.x.min(b.x),
+                x2: a.x.max(b.x),
+            })
+        } else if a.x == b.x && a.y != b.y {
+            Some(Self::Vertical {
+                x: a.x,
+                y1: a.y.min(b.y),
+                y2: a.y.max(b.y),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether the segment passes through the interior of `rect`.
+    pub(super) fn crosses(&self, rect: &Rectangle) -> bool {
+        let inside_x = |x: Px| x > rect.x && x < rect.right();
+        let inside_y = |y: Px| y > rect.y && y < rect.bottom();
+        match *self {
+            Self::Horizontal { y, x1, x2 } => inside_y(y) && x1 < rect.right() && x2 > rect.x,
+            Self::Vertical { x, y1, y2 } => inside_x(x) && y1 < rect.bottom() && y2 > rect.y,
+        }
+    }
+
+    /// Returns how far the segment stays from `rect` along either axis,
+    /// zero if it touches or enters it.
+    pub(super) fn distance_to(&self, rect: &Rectangle) -> Px {
+        let gap = |low: Px, high: Px, start: Px, end: Px| {
+            if high < start {
+                start - high
+            } else if low > end {
+                low - end
+            } else {
+                Px::ZERO
+            }
+        };
+        let (x1, x2, y1, y2) = match *self {
+            Self::Horizontal { y, x1, x2 } => (x1, x2, y, y),
+            Self::Vertical { x, y1, y2 } => (x, x, y1, y2),
+        };
+        gap(x1, x2, rect.x, rect.right()).max(gap(y1, y2, rect.y, rect.bottom()))
+    }
+
+    /// Returns the distance to a parallel segment whose extent overlaps this
+    /// one's, or `None` if they are not parallel or do not overlap.
+    pub(super) fn parallel_distance(&self, other: &Self) -> Option<Px> {
+        match (*self, *other) {
+            (
+                Self::Horizontal { y, x1, x2 },
+                Self::Horizontal {
+                    y: other_y,
+                    x1: other_x1,
+                    x2: other_x2,
+                },
+            ) => (x1.max(other_x1) < x2.min(other_x2)).then(|| y.abs_diff(other_y)),
+            (
+                Self::Vertical { x, y1, y2 },
+                Self::Vertical {
+                    x: other_x,
+                    y1: other_y1,
+                    y2: other_y2,
+                },
+            ) => (y1.max(other_y1) < y2.min(other_y2)).then(|| x.abs_diff(other_x)),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the axis-aligned segments of a route.
+pub(super) fn segments(route: &RoutePath) -> Vec<Segment> {
+    let nodes: Vec<&Point> = route.nodes.iter().collect();
+    nodes
+        .windows(2)
+        .filter_map(|pair| Segment::between(pair[0], pair[1]))
+        .collect()
+}
+
+/// Finds connectors that pass through or within `margin` of entities they
+/// do not join, and pairs of connectors with parallel segments closer than
+/// `min_spacing`.
+///
+/// Connectors that share an entity are expected to converge near it, so
+/// such pairs are not compared with each other.
+pub(super) fn find_collisions(
+    connections: &[RoutedConnection],
+    entities: &[(&str, Rectangle)],
+    margin: Px,
+    min_spacing: Px,
+) -> Vec<Collision> {
+    let routed: Vec<Vec<Segment>> = connections
+        .iter()
+        .map(|connection| segments(&connection.route))
+        .collect();
+    let mut collisions = Vec::new();
+
+    for (connection, segments) in connections.iter().zip(&routed) {
+        for (entity, rect) in entities {
+            if *rect == connection.source || *rect == connection.target {
+                continue;
+            }
+            if segments.iter().any(|segment| segment.crosses(rect)) {
+                collisions.push(Collision::CrossesEntity {
+                    connection: connection.name.clone(),
+                    entity: entity.to_string(),
+                });
+                continue;
+            }
+            let closest = segments
+                .iter()
+                .map(|segment| segment.distance_to(rect))
+                .min();
+            if let Some(distance) = closest.filter(|&distance| distance < margin) {
+                collisions.push(Collision::NearEntity {
+                    connection: connection.name.clone(),
+                    entity: entity.to_string(),
+                    distance,
+                });
+            }
+        }
+    }
+
+    for (index, (first, first_segments)) in connections.iter().zip(&routed).enumerate() {
+        for (second, second_segments) in connections.iter().zip(&routed).skip(index + 1) {
+            let endpoints = [&second.source, &second.target];
+            if endpoints.contains(&&first.source) || endpoints.contains(&&first.target) {
+                continue;
+            }
+            let closest = first_segments
+                .iter()
+                .flat_map(|a| second_segments.iter().map(move |b| (a, b)))
+                .filter_map(|(a, b)| a.parallel_distance(b))
+                .min();
+            if let Some(distance) = closest.filter(|&distance| distance < min_spacing) {
+                collisions.push(Collision::TooClose {
+                    first: first.name.clone(),
+                    second: second.name.clone(),
+                    distance,
+                });
+            }
+        }
+    }
+
+    collisions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::types::NonEmpty;
+
+    fn rect(x: u32, y: u32, width: u32, height: u32) -> Rectangle {
+        Rectangle::new(Px::new(x), Px::new(y), Px::new(width), Px::new(height))
+    }
+
+    fn connection(
+        name: &str,
+        source: Rectangle,
+        target: Rectangle,
+        nodes: &[(u32, u32)],
+    ) -> RoutedConnection {
+        let points: Vec<Point> = nodes
+            .iter()
+            .map(|&(x, y)| Point::new(Px::new(x), Px::new(y)))
+            .collect();
+        RoutedConnection {
+            name: name.to_string(),
+            source,
+            target,
+            route: RoutePath::new(NonEmpty::try_from(points).unwrap(), Px::ZERO),
+        }
+    }
+
+    #[test]
+    fn reports_entity_crossings_and_close_parallel_segments() {
+        let a = rect(0, 0, 20, 20);
+        let b = rect(200, 0, 20, 20);
+        let c = rect(0, 100, 20, 20);
+        let d = rect(200, 100, 20, 20);
+        let obstacle = rect(100, 0, 20, 20);
+
+        let collisions = find_collisions(
+            &[
+                connection("A -> B", a.clone(), b.clone(), &[(20, 10), (200, 10)]),
+                connection("C -> D", c.clone(), d.clone(), &[(20, 110), (200, 110)]),
+                connection(
+                    "C -> B",
+                    c.clone(),
+                    b.clone(),
+                    &[(20, 114), (200, 114), (200, 20)],
+                ),
+            ],
+            &[
+                ("A", a),
+                ("B", b),
+                ("C", c),
+                ("D", d),
+                ("Obstacle", obstacle),
+            ],
+            Px::ZERO,
+            Px::new(8),
+        );
+
+        assert_eq!(
+            collisions,
+            [Collision::CrossesEntity {
+                connection: "A -> B".to_string(),
+                entity: "Obstacle".to_string(),
+            }]
+        );
+        assert_eq!(
+            collisions[0].message(),
+            "Connection A -> B passes through entity 'Obstacle'"
+        );
+    }
+
+    #[test]
+    fn reports_parallel_segments_of_unrelated_connectors() {
+        let collisions = find_collisions(
+            &[
+                connection(
+                    "A -> B",
+                    rect(0, 0, 20, 20),
+                    rect(200, 0, 20, 20),
+                    &[(20, 10), (200, 10)],
+                ),
+                connection(
+                    "C -> D",
+                    rect(0, 30, 20, 20),
+                    rect(200, 30, 20, 20),
+                    &[(50, 13), (150, 13)],
+                ),
+            ],
+            &[],
+            Px::ZERO,
+            Px::new(8),
+        );
+
+        assert_eq!(
+            collisions
+                .iter()
+                .map(Collision::message)
+                .collect::<Vec<_>>(),
+            ["Connections A -> B and C -> D run 3px apart"]
+        );
+    }
+
+    #[test]
+    fn reports_connectors_passing_within_the_margin_of_an_entity() {
+        let connections = [connection(
+            "A -> B",
+            rect(0, 0, 20, 20),
+            rect(200, 0, 20, 20),
+            &[(20, 10), (200, 10)],
+        )];
+        let entities = [("Nearby", rect(100, 16, 20, 20))];
+
+        let within = |margin| {
+            find_collisions(&connections, &entities, Px::new(margin), Px::new(8))
+                .iter()
+                .map(Collision::message)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            within(10),
+            ["Connection A -> B passes 6px from entity 'Nearby'"]
+        );
+        assert!(within(6).is_empty());
+    }
+}
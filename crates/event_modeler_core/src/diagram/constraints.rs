@@ -0,0 +1,274 @@
+//! The `constraints` layout, placing entities with a constraint solver.
+//!
+//! Each entity instance gets a horizontal position, and each slice column a
+//! width, solved with the Cassowary algorithm from constraints of differing
+//! strength:
+//!
+//! - required: entities stay inside their column, in their cell's order and
+//!   [`ENTITY_MARGIN`](super) apart; columns never shrink; and every `gap` the
+//!   model declares is kept;
+//! - strong: entities the model asks to `align` share a center;
+//! - medium: columns keep their natural width;
+//! - weak: entities stay where the standard layout would put them.
+//!
+//! So a model without constraints is laid out exactly as by the standard
+//! layout, and columns only widen as far as the constraints need.
+
+use super::layout_types::Px;
+use crate::event_model::yaml_types::LayoutConstraint;
+use cassowary::WeightedRelation::{EQ, GE, LE};
+use cassowary::strength::{MEDIUM, REQUIRED, STRONG, WEAK};
+use cassowary::{Expression, Solver, Variable};
+use std::collections::{BTreeMap, HashMap};
+
+/// Entities drawn in each cell, keyed by slice index and then swimlane index.
+type Cells = BTreeMap<(usize, usize), Vec<String>>;
+
+/// Where the constraint layout puts columns and entities.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ConstrainedLayout {
+    /// Width of each column, at least its natural width.
+    pub widths: Vec<Px>,
+    /// Left edge of each entity instance from its column's left edge, by
+    /// entity name and slice index.
+    pub offsets: HashMap<(String, usize), Px>,
+    /// Alignments that could not be kept, described for a warning.
+    pub unmet: Vec<String>,
+}
+
+/// Puts the entities of each cell in the order `order` and `gap` constraints
+/// ask for, leaving the others where they are.
+pub(super) fn order_cells(cells: &mut Cells, constraints: &[LayoutConstraint]) {
+    for constraint in constraints {
+        let sequence: Vec<&String> = match constraint {
+            LayoutConstraint::Order(names) => names.iter().collect(),
+            LayoutConstraint::Gap { left, right, .. } => vec![left, right],
+            LayoutConstraint::Align(_) => continue,
+        };
+        let rank = |name: &String| sequence.iter().position(|listed| *listed == name);
+        for entities in cells.values_mut() {
+            let slots: Vec<usize> = (0..entities.len())
+                .filter(|&slot| rank(&entities[slot]).is_some())
+                .collect();
+            let mut listed: Vec<String> =
+                slots.iter().map(|&slot| entities[slot].clone()).collect();
+            listed.sort_by_key(&rank);
+            for (slot, name) in slots.into_iter().zip(listed) {
+                entities[slot] = name;
+            }
+        }
+    }
+}
+
+/// Solves the positions of every entity instance in `cells`.
+///
+/// `slice_names` names each slice for warnings; `column_of` and `widths`
+/// give each slice's column and each column's natural width; `entity_widths` the width of each entity's box. Fails if
+/// the required constraints contradict each other, as `gap`s in both
+/// directions between two entities do.
+pub(super) fn solve(
+    cells: &Cells,
+    slice_names: &[String],
+    column_of: &[usize],
+    widths: &[Px],
+    entity_widths: &HashMap<String, Px>,
+    constraints: &[LayoutConstraint],
+    margin: Px,
+) -> Result<ConstrainedLayout, String> {
+    let mut solver = Solver::new();
+    let conflict = |error| format!("layout constraints contradict each other ({error:?})");
+    let width_of = |name: &str| f64::from(entity_widths.get(name).map_or(0, |width| width.get()));
+    let margin = f64::from(margin.get());
+
+    // Columns sit side by side from zero, never narrower than their content
+    let lefts: Vec<Variable> = widths.iter().map(|_| Variable::new()).collect();
+    let spans: Vec<Variable> = widths.iter().map(|_| Variable::new()).collect();
+    for (column, &natural) in widths.iter().enumerate() {
+        let natural = f64::from(natural.get());
+        let left = lefts[column];
+        let span = spans[column];
+        let start = match column {
+            0 => Expression::from_constant(0.0),
+            _ => lefts[column - 1] + spans[column - 1],
+        };
+        solver
+            .add_constraints(&[
+                left | EQ(REQUIRED) | start,
+                span | GE(REQUIRED) | natural,
+                span | EQ(MEDIUM) | natural,
+            ])
+            .map_err(conflict)?;
+    }
+
+    // Entities keep their cell's order inside their column
+    let mut positions: HashMap<(String, usize), Variable> = HashMap::new();
+    for (&(slice, _), entities) in cells {
+        let column = column_of[slice];
+        let (left, span) = (lefts[column], spans[column]);
+        let total: f64 = entities.iter().map(|name| width_of(name)).sum::<f64>()
+            + margin * (entities.len() as f64 - 1.0);
+        let mut preferred = if entities.len() == 1 {
+            (f64::from(widths[column].get()) - total) / 2.0
+        } else {
+            margin
+        };
+        let mut previous: Option<(Variable, f64)> = None;
+        for name in entities {
+            let x = Variable::new();
+            let width = width_of(name);
+            let after = match previous {
+                Some((before, before_width)) => before + before_width + margin,
+                None => left + margin,
+            };
+            solver
+                .add_constraints(&[
+                    x | GE(REQUIRED) | after,
+                    (x + width + margin) | LE(REQUIRED) | (left + span),
+                    x | EQ(WEAK) | (left + preferred),
+                ])
+                .map_err(conflict)?;
+            preferred += width + margin;
+            previous = Some((x, width));
+            positions.insert((name.clone(), slice), x);
+        }
+    }
+
+    // The model's own rules, in every slice drawing the entities they name
+    let mut alignments: Vec<(usize, Vec<&String>)> = Vec::new();
+    for constraint in constraints {
+        match constraint {
+            LayoutConstraint::Align(names) => {
+                for slice in 0..slice_names.len() {
+                    let drawn: Vec<&String> = names
+                        .iter()
+                        .filter(|name| positions.contains_key(&((*name).clone(), slice)))
+                        .collect();
+                    let Some((first, rest)) = drawn.split_first() else {
+                        continue;
+                    };
+                    let anchor = positions[&((*first).clone(), slice)] + width_of(first) / 2.0;
+                    for name in rest {
+                        let center = positions[&((*name).clone(), slice)] + width_of(name) / 2.0;
+                        solver
+                            .add_constraint(center | EQ(STRONG) | anchor.clone())
+                            .map_err(conflict)?;
+                    }
+                    if !rest.is_empty() {
+                        alignments.push((slice, drawn));
+                    }
+                }
+            }
+            LayoutConstraint::Gap { left, right, min } => {
+                for slice in 0..slice_names.len() {
+                    if let (Some(&from), Some(&to)) = (
+                        positions.get(&(left.clone(), slice)),
+                        positions.get(&(right.clone(), slice)),
+                    ) {
+                        solver
+                            .add_constraint(
+                                to | GE(REQUIRED) | (from + width_of(left) + f64::from(*min)),
+                            )
+                            .map_err(conflict)?;
+                    }
+                }
+            }
+            LayoutConstraint::Order(_) => {}
+        }
+    }
+
+    let offsets = positions
+        .iter()
+        .map(|((name, slice), &x)| {
+            let offset = solver.get_value(x) - solver.get_value(lefts[column_of[*slice]]);
+            (
+                (name.clone(), *slice),
+                Px::new(offset.round().max(0.0) as u32),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+    let unmet = alignments
+        .into_iter()
+        .filter(|(slice, names)| {
+            let centers: Vec<f64> = names
+                .iter()
+                .map(|name| {
+                    solver.get_value(positions[&((*name).clone(), *slice)]) + width_of(name) / 2.0
+                })
+                .collect();
+            centers
+                .iter()
+                .any(|center| (center - centers[0]).abs() >= 1.0)
+        })
+        .map(|(slice, names)| {
+            let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+            format!(
+                "Could not align {} in slice '{}'",
+                names.join(", "),
+                slice_names[slice]
+            )
+        })
+        .collect();
+    Ok(ConstrainedLayout {
+        widths: spans
+            .iter()
+            .map(|&span| Px::new(solver.get_value(span).ceil() as u32))
+            .collect(),
+        offsets,
+        unmet,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn keeps_orders_gaps_and_alignments_widening_columns_only_as_needed() {
+        // Slice 0 draws A and B in one swimlane and C below; slice 1 draws D
+        let mut cells = BTreeMap::from([
+            ((0, 0), names(&["A", "B"])),
+            ((0, 1), names(&["C"])),
+            ((1, 0), names(&["D"])),
+        ]);
+        let entity_widths = ["A", "B", "C", "D"]
+            .into_iter()
+            .map(|name| (name.to_string(), Px::new(100)))
+            .collect();
+        let constraints = [
+            LayoutConstraint::Order(names(&["B", "A"])),
+            LayoutConstraint::Gap {
+                left: "B".to_string(),
+                right: "A".to_string(),
+                min: 60,
+            },
+            LayoutConstraint::Align(names(&["A", "C"])),
+        ];
+
+        order_cells(&mut cells, &constraints);
+        let layout = solve(
+            &cells,
+            &names(&["First", "Second"]),
+            &[0, 1],
+            &[Px::new(240), Px::new(160)],
+            &entity_widths,
+            &constraints,
+            Px::new(20),
+        )
+        .unwrap();
+
+        assert_eq!(cells[&(0, 0)], names(&["B", "A"]));
+        // B at the margin, A 60px after it, and C under A; the first column
+        // widens to fit, the second keeps its width and centers D
+        assert_eq!(layout.widths, [Px::new(300), Px::new(160)]);
+        let offset = |name: &str, slice| layout.offsets[&(name.to_string(), slice)];
+        assert_eq!(offset("B", 0), Px::new(20));
+        assert_eq!(offset("A", 0), Px::new(180));
+        assert_eq!(offset("C", 0), Px::new(180));
+        assert_eq!(offset("D", 1), Px::new(30));
+        assert!(layout.unmet.is_empty());
+    }
+}
@@ -0,0 +1,492 @@
+//! Entity dimension calculation and caching.
+//!
+//! Entity sizes depend only on the entity's name, its kind, its size
+//! policy, the font size, and the width available for wrapped text.
+//! [`DimensionCache`] memoizes the calculation on exactly those inputs so the
+//! width-estimation and rendering passes (and repeated renders of the same
+//! model) never re-wrap a name.
+
+use super::layout_types::Px;
+use crate::event_model::entities::EntityType;
+use crate::event_model::yaml_types::SizePolicy;
+use std::collections::HashMap;
+
+pub(super) const ENTITY_BOX_WIDTH: Px = Px::new(120); // Width of entity boxes
+pub(super) const ENTITY_BOX_HEIGHT: Px = Px::new(60); // Height of entity boxes
+pub(super) const ENTITY_PADDING: Px = Px::new(10); // Padding inside entity boxes
+pub(super) const ENTITY_NAME_FONT_SIZE: Px = Px::new(10); // Font size for entity names
+
+// Automation entity constants
+pub(super) const ROBOT_ICON_SIZE: Px = Px::new(30); // Size of the robot emoji
+pub(super) const ICON_TEXT_SPACING: Px = Px::new(5); // Space between icon and text
+
+/// The kind of entity being measured.
+///
+/// Automations render as an icon with a caption rather than a box, so they
+/// are sized differently from every other kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntityKind {
+    /// A view (wireframe/screen).
+    View,
+    /// A command.
+    Command,
+    /// An event.
+    Event,
+    /// A projection (read model).
+    Projection,
+    /// A query.
+    Query,
+    /// An automation.
+    Automation,
+}
+
+impl From<EntityKind> for EntityType {
+    fn from(kind: EntityKind) -> Self {
+        match kind {
+            EntityKind::View => Self::View,
+            EntityKind::Command => Self::Command,
+            EntityKind::Event => Self::Event,
+            EntityKind::Projection => Self::Projection,
+            EntityKind::Query => Self::Query,
+            EntityKind::Automation => Self::Automation,
+        }
+    }
+}
+
+/// Everything that influences the calculated size of an entity.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DimensionKey {
+    name: String,
+    kind: EntityKind,
+    policy: SizePolicy,
+    font_size: Px,
+    width_budget: Px,
+}
+
+/// Memoized entity dimensions shared across rendering passes.
+///
+/// A cache can be kept alive between renders of the same (or an edited)
+/// model; only entities whose name, kind, or font settings changed are
+/// measured again.
+#[derive(Debug, Clone, Default)]
+pub struct DimensionCache {
+    entries: HashMap<DimensionKey, EntityDimensions>,
+    hits: usize,
+    misses: usize,
+}
+
+impl DimensionCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of cached entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if nothing has been cached yet.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns how many lookups were answered from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Returns how many lookups required a fresh calculation.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Removes all cached entries and resets the statistics.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    /// Returns the dimensions of an entity using the default font settings.
+    pub(super) fn dimensions(
+        &mut self,
+        name: &str,
+        kind: EntityKind,
+        policy: SizePolicy,
+    ) -> &EntityDimensions {
+        self.measure(
+            name,
+            kind,
+            policy,
+            ENTITY_NAME_FONT_SIZE,
+            ENTITY_BOX_WIDTH - 2 * ENTITY_PADDING,
+        )
+    }
+
+    /// Returns the dimensions of an entity, calculating them on first use.
+    pub(super) fn measure(
+        &mut self,
+        name: &str,
+        kind: EntityKind,
+        policy: SizePolicy,
+        font_size: Px,
+        width_budget: Px,
+    ) -> &EntityDimensions {
+        let key = DimensionKey {
+            name: name.to_string(),
+            kind,
+            policy,
+            font_size,
+            width_budget,
+        };
+
+        if self.entries.contains_key(&key) {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+
+        self.entries.entry(key).or_insert_with(|| match kind {
+            EntityKind::Automation => {
+                calculate_automation_dimensions(name, policy, font_size, width_budget)
+            }
+            _ => calculate_entity_dimensions(name, policy, font_size, width_budget),
+        })
+    }
+}
+
+/// Formats an entity name by inserting spaces before capital letters.
+/// E.g., "LoginScreen" becomes "Login Screen", "UserProfileScreen" becomes "User Profile Screen"
+fn format_entity_name(name: &str) -> String {
+    let mut result = String::new();
+    let mut chars = name.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch.is_uppercase() && !result.is_empty() {
+            // Add space before capital letter, unless previous char was also uppercase
+            // This handles cases like "XMLParser" -> "XML Parser" correctly
+            if let Some(last) = result.chars().last() {
+                if !last.is_uppercase() || (chars.peek().is_some_and(|&next| !next.is_uppercase()))
+                {
+                    result.push(' ');
+                }
+            }
+        }
+        result.push(ch);
+    }
+
+    result
+}
+
+/// Returns the approximate advance of one character at a font size (for
+/// Arial, roughly 0.6x the font size).
+pub(super) fn char_width(font_size: Px) -> Px {
+    font_size.scale(0.6)
+}
+
+/// Returns the distance between baselines of wrapped text.
+pub(super) fn line_height(font_size: Px) -> Px {
+    font_size.scale(1.2)
+}
+
+/// Wraps text into balanced lines, prioritizing wrapping over width expansion.
+/// Returns the wrapped lines and the actual dimensions needed.
+pub(super) fn wrap_text(text: &str, max_width: Px, font_size: Px) -> (Vec<String>, Px, Px) {
+    let char_width = char_width(font_size);
+    let max_chars_per_line = max_width / char_width;
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    if words.is_empty() {
+        return (vec![text.to_string()], max_width, font_size);
+    }
+
+    // First, try to fit within the max width using multiple lines
+    let mut lines = Vec::new();
+    let mut current_line = String::new();
+
+    for word in &words {
+        // Check if adding this word would exceed the line length
+        let test_line = if current_line.is_empty() {
+            word.to_string()
+        } else {
+            format!("{current_line} {word}")
+        };
+
+        if test_line.len() <= max_chars_per_line as usize {
+            current_line = test_line;
+        } else {
+            // Start a new line
+            if !current_line.is_empty() {
+                lines.push(current_line);
+            }
+            current_line = word.to_string();
+        }
+    }
+
+    if !current_line.is_empty() {
+        lines.push(current_line);
+    }
+
+    // If we have lines that fit, use the standard width
+    let max_line_length = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+    let mut actual_width = ENTITY_BOX_WIDTH;
+
+    // Only expand width if a single word is longer than the max characters
+    if max_line_length > max_chars_per_line as usize {
+        actual_width = (max_line_length as u32 * char_width).max(ENTITY_BOX_WIDTH);
+    }
+
+    let actual_height = lines.len() as u32 * line_height(font_size);
+
+    (lines, actual_width, actual_height)
+}
+
+/// Information about entity dimensions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct EntityDimensions {
+    pub(super) width: Px,
+    pub(super) height: Px,
+    pub(super) text_lines: Vec<String>,
+}
+
+/// Returns the width of the longest of `lines`.
+fn widest_line(lines: &[String], font_size: Px) -> Px {
+    let longest = lines.iter().map(|line| line.chars().count()).max();
+    longest.unwrap_or(0) as u32 * char_width(font_size)
+}
+
+/// Returns the number of lines of text that fit inside a standard box.
+fn fixed_line_count(font_size: Px) -> u32 {
+    ((ENTITY_BOX_HEIGHT - 2 * ENTITY_PADDING) / line_height(font_size)).max(1)
+}
+
+/// Cuts wrapped lines down to `max_lines` lines of at most `width`, ending
+/// every line that was cut short, and the last line if lines were dropped,
+/// with an ellipsis.
+fn truncate_lines(lines: Vec<String>, width: Px, max_lines: u32, font_size: Px) -> Vec<String> {
+    let max_chars = (width / char_width(font_size)).max(1) as usize;
+    let dropped = lines.len() > max_lines as usize;
+    let last = lines.len().min(max_lines as usize).saturating_sub(1);
+    lines
+        .into_iter()
+        .take(max_lines as usize)
+        .enumerate()
+        .map(|(index, line)| {
+            if line.chars().count() > max_chars || (dropped && index == last) {
+                let mut kept: String = line.chars().take(max_chars - 1).collect();
+                // Rather than split a word, end at the previous one if there is one
+                let mid_word = line
+                    .chars()
+                    .nth(kept.chars().count())
+                    .is_some_and(|c| c != ' ');
+                if let Some(boundary) = kept.rfind(' ').filter(|_| mid_word) {
+                    kept.truncate(boundary);
+                }
+                format!("{}\u{2026}", kept.trim_end())
+            } else {
+                line
+            }
+        })
+        .collect()
+}
+
+/// Calculate dimensions needed for an entity based on its text content.
+fn calculate_entity_dimensions(
+    name: &str,
+    policy: SizePolicy,
+    font_size: Px,
+    width_budget: Px,
+) -> EntityDimensions {
+    let formatted_name = format_entity_name(name);
+    let (text_lines, text_width, text_height) = wrap_text(&formatted_name, width_budget, font_size);
+
+    match policy {
+        SizePolicy::Standard | SizePolicy::Uniform => {
+            // Only use padding for height calculation (no label)
+            let total_text_height = text_height + 2 * ENTITY_PADDING;
+
+            // Prefer the standard width unless text forces us wider
+            let width = text_width.max(ENTITY_BOX_WIDTH);
+            let height = total_text_height.max(ENTITY_BOX_HEIGHT);
+
+            EntityDimensions {
+                width,
+                height,
+                text_lines,
+            }
+        }
+        SizePolicy::Fixed => EntityDimensions {
+            width: ENTITY_BOX_WIDTH,
+            height: ENTITY_BOX_HEIGHT,
+            text_lines: truncate_lines(
+                text_lines,
+                width_budget,
+                fixed_line_count(font_size),
+                font_size,
+            ),
+        },
+        SizePolicy::FitContent => EntityDimensions {
+            width: widest_line(&text_lines, font_size) + 2 * ENTITY_PADDING,
+            height: text_height + 2 * ENTITY_PADDING,
+            text_lines,
+        },
+    }
+}
+
+/// Calculate dimensions for automation entities (robot icon + text below).
+fn calculate_automation_dimensions(
+    name: &str,
+    policy: SizePolicy,
+    font_size: Px,
+    width_budget: Px,
+) -> EntityDimensions {
+    let formatted_name = format_entity_name(name);
+    let (text_lines, text_width, text_height) = wrap_text(&formatted_name, width_budget, font_size);
+
+    match policy {
+        SizePolicy::Standard | SizePolicy::Uniform => EntityDimensions {
+            // Width is the max of icon size or text width
+            width: ROBOT_ICON_SIZE.max(text_width) + 2 * ENTITY_PADDING,
+            // Height is icon + spacing + text + padding
+            height: ROBOT_ICON_SIZE + ICON_TEXT_SPACING + text_height + 2 * ENTITY_PADDING,
+            text_lines,
+        },
+        SizePolicy::Fixed => {
+            let max_lines = fixed_line_count(font_size);
+            EntityDimensions {
+                width: ENTITY_BOX_WIDTH + 2 * ENTITY_PADDING,
+                height: ROBOT_ICON_SIZE
+                    + ICON_TEXT_SPACING
+                    + max_lines * line_height(font_size)
+                    + 2 * ENTITY_PADDING,
+                text_lines: truncate_lines(text_lines, width_budget, max_lines, font_size),
+            }
+        }
+        SizePolicy::FitContent => EntityDimensions {
+            width: ROBOT_ICON_SIZE.max(widest_line(&text_lines, font_size)) + 2 * ENTITY_PADDING,
+            height: ROBOT_ICON_SIZE + ICON_TEXT_SPACING + text_height + 2 * ENTITY_PADDING,
+            text_lines,
+        },
+    }
+}
+
+/// Grows every one of `dimensions` to the width and height of the largest.
+pub(super) fn make_uniform<'a>(dimensions: impl IntoIterator<Item = &'a mut EntityDimensions>) {
+    let mut dimensions: Vec<&mut EntityDimensions> = dimensions.into_iter().collect();
+    let width = dimensions.iter().map(|d| d.width).max().unwrap_or_default();
+    let height = dimensions
+        .iter()
+        .map(|d| d.height)
+        .max()
+        .unwrap_or_default();
+    for d in &mut dimensions {
+        d.width = width;
+        d.height = height;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_reuses_previous_calculation() {
+        let mut cache = DimensionCache::new();
+        let first = cache
+            .dimensions(
+                "UserAccountCreated",
+                EntityKind::Event,
+                SizePolicy::Standard,
+            )
+            .clone();
+        let second = cache
+            .dimensions(
+                "UserAccountCreated",
+                EntityKind::Event,
+                SizePolicy::Standard,
+            )
+            .clone();
+
+        assert_eq!(first, second);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.misses(), 1);
+        assert_eq!(cache.hits(), 1);
+    }
+
+    #[test]
+    fn cache_distinguishes_kind_and_font_settings() {
+        let mut cache = DimensionCache::new();
+        let boxed = cache
+            .dimensions("EmailVerifier", EntityKind::Command, SizePolicy::Standard)
+            .clone();
+        let automation = cache
+            .dimensions(
+                "EmailVerifier",
+                EntityKind::Automation,
+                SizePolicy::Standard,
+            )
+            .clone();
+        cache.measure(
+            "EmailVerifier",
+            EntityKind::Command,
+            SizePolicy::Standard,
+            Px::new(14),
+            Px::new(100),
+        );
+
+        assert_ne!(boxed, automation);
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.hits(), 0);
+    }
+
+    #[test]
+    fn fixed_boxes_cut_names_short_and_fit_content_boxes_shrink() {
+        let mut cache = DimensionCache::new();
+        let name = "PlaceOrderForRegisteredCustomerAccountToday";
+        let fixed = cache
+            .dimensions(name, EntityKind::Command, SizePolicy::Fixed)
+            .clone();
+        let fit = cache
+            .dimensions("Go", EntityKind::Command, SizePolicy::FitContent)
+            .clone();
+
+        assert_eq!(
+            (fixed.width, fixed.height),
+            (ENTITY_BOX_WIDTH, ENTITY_BOX_HEIGHT)
+        );
+        assert_eq!(
+            fixed.text_lines,
+            ["Place Order For", "Registered", "Customer\u{2026}"]
+        );
+        assert_eq!((fit.width, fit.height), (Px::new(32), Px::new(32)));
+    }
+
+    #[test]
+    fn truncation_cuts_overlong_words_and_uniform_sizing_matches_the_largest() {
+        let lines = vec!["Supercalifragilistic".to_string()];
+        assert_eq!(
+            truncate_lines(lines, Px::new(60), 3, ENTITY_NAME_FONT_SIZE),
+            ["Supercali\u{2026}"]
+        );
+
+        let mut small = EntityDimensions {
+            width: Px::new(120),
+            height: Px::new(60),
+            text_lines: Vec::new(),
+        };
+        let mut tall = EntityDimensions {
+            height: Px::new(80),
+            ..small.clone()
+        };
+        make_uniform([&mut small, &mut tall]);
+        assert_eq!((small.width, small.height), (Px::new(120), Px::new(80)));
+    }
+
+    #[test]
+    fn format_entity_name_splits_camel_case() {
+        assert_eq!(format_entity_name("LoginScreen"), "Login Screen");
+        assert_eq!(format_entity_name("XMLParser"), "XML Parser");
+    }
+}
@@ -0,0 +1,150 @@
+//! Seeded tie-breaking for placements that would otherwise overlap exactly.
+//!
+//! Badges on connectors sit at fixed points along their routes, so two
+//! connectors sharing a midpoint put their badges on top of each other. The
+//! later badge is nudged by a pseudo-random offset drawn from a seeded
+//! generator: the same model and seed always produce the same diagram, while
+//! a different seed gives a different arrangement to try.
+
+use super::layout_types::Px;
+use super::routing_types::{Point, Rectangle};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// How many nudges to try before accepting an overlapping placement.
+const MAX_NUDGES: u32 = 8;
+
+/// Largest distance of the first nudge; each later one may move further.
+const NUDGE_STEP: u32 = 6;
+
+/// Deterministic pseudo-random generator (SplitMix64).
+#[derive(Debug, Clone)]
+pub struct Jitter {
+    state: u64,
+}
+
+impl Jitter {
+    /// Creates a generator that always yields the same sequence for `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Derives a seed from everything that identifies a model's connectors,
+    /// so an unchanged model renders identically without an explicit seed.
+    pub fn seed_for<T: Hash>(model: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        model.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns the next value of the sequence.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns an offset in `-reach..=reach`.
+    fn offset(&mut self, reach: u32) -> i64 {
+        let span = u64::from(reach) * 2 + 1;
+        (self.next_u64() % span) as i64 - i64::from(reach)
+    }
+}
+
+/// Places labels so that each avoids those placed before it.
+#[derive(Debug, Clone)]
+pub struct LabelPlacer {
+    jitter: Jitter,
+    placed: Vec<Rectangle>,
+}
+
+impl LabelPlacer {
+    /// Creates a placer whose nudges are drawn from `jitter`.
+    pub fn new(jitter: Jitter) -> Self {
+        Self {
+            jitter,
+            placed: Vec::new(),
+        }
+    }
+
+    /// Returns where to center a `width` by `height` label that would ideally
+    /// be centered on `center`.
+    ///
+    /// The ideal spot is kept when it is free. Otherwise the label is nudged
+    /// by ever larger random offsets until it clears every earlier label, or
+    /// left at the last attempt once the nudges run out.
+    pub fn place(&mut self, center: Point, width: Px, height: Px) -> Point {
+        let mut candidate = center;
+        for attempt in 1..=MAX_NUDGES {
+            if !self.overlaps(candidate, width, height) {
+                break;
+            }
+            let reach = NUDGE_STEP * attempt;
+            candidate = Point::new(
+                shift(center.x, self.jitter.offset(reach)),
+                shift(center.y, self.jitter.offset(reach)),
+            );
+        }
+        self.placed.push(bounds(candidate, width, height));
+        candidate
+    }
+
+    fn overlaps(&self, center: Point, width: Px, height: Px) -> bool {
+        let rect = bounds(center, width, height);
+        self.placed.iter().any(|placed| placed.intersects(&rect))
+    }
+}
+
+/// Moves `value` by `offset` pixels without going below zero.
+fn shift(value: Px, offset: i64) -> Px {
+    let moved = (i64::from(value.get()) + offset).max(0);
+    Px::new(u32::try_from(moved).unwrap_or(u32::MAX))
+}
+
+/// Returns the rectangle of a `width` by `height` label centered on `center`.
+fn bounds(center: Point, width: Px, height: Px) -> Rectangle {
+    Rectangle::new(
+        center.x.saturating_sub(width / 2),
+        center.y.saturating_sub(height / 2),
+        width,
+        height,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_yields_same_sequence() {
+        let mut a = Jitter::new(42);
+        let mut b = Jitter::new(42);
+
+        for _ in 0..4 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Jitter::new(1).next_u64(), Jitter::new(2).next_u64());
+    }
+
+    #[test]
+    fn free_spot_is_kept() {
+        let mut placer = LabelPlacer::new(Jitter::new(7));
+        let center = Point::new(Px::new(100), Px::new(100));
+
+        assert_eq!(placer.place(center, Px::new(20), Px::new(10)), center);
+    }
+
+    #[test]
+    fn coinciding_labels_are_nudged_apart() {
+        let mut placer = LabelPlacer::new(Jitter::new(7));
+        let center = Point::new(Px::new(100), Px::new(100));
+        let (width, height) = (Px::new(20), Px::new(10));
+
+        let first = placer.place(center, width, height);
+        let second = placer.place(center, width, height);
+
+        assert!(!bounds(first, width, height).intersects(&bounds(second, width, height)));
+    }
+}
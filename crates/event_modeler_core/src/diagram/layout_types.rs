@@ -0,0 +1,362 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Type-safe layout constants for diagram rendering.
+//!
+//! This module provides strongly-typed wrappers for layout dimensions
+//! and constants used in SVG rendering, following the type-driven
+//! development approach. Every length on the canvas is a [`Px`]; physical
+//! sizes for print and raster export are a [`Length`], and [`Dpi`] converts
+//! between the two.
+
+use nutype::nutype;
+use serde::Serialize;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+
+/// CSS pixels per inch; one SVG user unit is one CSS pixel.
+const CSS_PIXELS_PER_INCH: f64 = 96.0;
+
+/// PostScript points per inch, the unit of PDF page geometry.
+const POINTS_PER_INCH: f64 = 72.0;
+
+/// Millimeters per inch.
+const MILLIMETERS_PER_INCH: f64 = 25.4;
+
+/// A length on the diagram canvas, in SVG user units (CSS pixels).
+///
+/// Positions, sizes, margins, and font sizes are all measured in `Px`, so a
+/// count of lines or characters can only enter a layout calculation through
+/// an explicit multiplication. Subtraction panics on underflow in debug
+/// builds just like `u32`; use [`Px::saturating_sub`] where the result may
+/// be negative.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct Px(u32);
+
+impl Px {
+    /// A length of zero.
+    pub const ZERO: Self = Self(0);
+
+    /// Creates a length of `pixels` CSS pixels.
+    pub const fn new(pixels: u32) -> Self {
+        Self(pixels)
+    }
+
+    /// Returns the length as a number of CSS pixels.
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+
+    /// Subtracts `other`, stopping at zero.
+    pub const fn saturating_sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+
+    /// Returns the distance between two coordinates.
+    pub const fn abs_diff(self, other: Self) -> Self {
+        Self(self.0.abs_diff(other.0))
+    }
+
+    /// Rounds to the nearest multiple of `step`, rounding halfway values up.
+    /// A zero step leaves the length unchanged.
+    pub const fn snap_to(self, step: Self) -> Self {
+        if step.0 == 0 {
+            return self;
+        }
+        Self((self.0 + step.0 / 2) / step.0 * step.0)
+    }
+
+    /// Scales the length by `factor`, truncating to whole pixels.
+    pub fn scale(self, factor: f32) -> Self {
+        Self((self.0 as f32 * factor) as u32)
+    }
+
+    /// Returns the physical length this occupies when printed at its
+    /// nominal size of 96 pixels per inch.
+    pub fn to_length(self) -> Length {
+        Length::from_inches(f64::from(self.0) / CSS_PIXELS_PER_INCH)
+    }
+
+    /// Returns how many device pixels this covers when rasterized at `dpi`,
+    /// rounded up so that nothing is cut off.
+    pub fn to_device_pixels(self, dpi: Dpi) -> u32 {
+        self.to_length().to_device_pixels(dpi)
+    }
+}
+
+impl fmt::Display for Px {
+    /// Formats the bare number, as SVG attributes expect.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Add for Px {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Px {
+    fn add_assign(&mut self, other: Self) {
+        self.0 += other.0;
+    }
+}
+
+impl Sub for Px {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 - other.0)
+    }
+}
+
+impl SubAssign for Px {
+    fn sub_assign(&mut self, other: Self) {
+        self.0 -= other.0;
+    }
+}
+
+impl Mul<u32> for Px {
+    type Output = Self;
+
+    fn mul(self, count: u32) -> Self {
+        Self(self.0 * count)
+    }
+}
+
+impl Mul<Px> for u32 {
+    type Output = Px;
+
+    fn mul(self, length: Px) -> Px {
+        Px(self * length.0)
+    }
+}
+
+impl Div<u32> for Px {
+    type Output = Self;
+
+    fn div(self, divisor: u32) -> Self {
+        Self(self.0 / divisor)
+    }
+}
+
+impl Div for Px {
+    /// How many whole times one length fits into another.
+    type Output = u32;
+
+    fn div(self, other: Self) -> u32 {
+        self.0 / other.0
+    }
+}
+
+impl Sum for Px {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Px> for Px {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Output resolution for raster and print export, in device pixels per inch.
+#[nutype(
+    validate(finite, greater = 0.0),
+    default = 96.0,
+    derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)
+)]
+pub struct Dpi(f64);
+
+/// A physical length, as used for PDF pages and print sizes.
+///
+/// Stored in PostScript points (1/72 inch), the native unit of PDF.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Length(f64);
+
+impl Length {
+    /// Creates a length of `points` PostScript points.
+    pub const fn from_points(points: f64) -> Self {
+        Self(points)
+    }
+
+    /// Creates a length of `inches` inches.
+    pub fn from_inches(inches: f64) -> Self {
+        Self(inches * POINTS_PER_INCH)
+    }
+
+    /// Creates a length of `millimeters` millimeters.
+    pub fn from_millimeters(millimeters: f64) -> Self {
+        Self::from_inches(millimeters / MILLIMETERS_PER_INCH)
+    }
+
+    /// Returns the length in PostScript points.
+    pub const fn points(self) -> f64 {
+        self.0
+    }
+
+    /// Returns the length in inches.
+    pub fn inches(self) -> f64 {
+        self.0 / POINTS_PER_INCH
+    }
+
+    /// Returns the length in millimeters.
+    pub fn millimeters(self) -> f64 {
+        self.inches() * MILLIMETERS_PER_INCH
+    }
+
+    /// Returns how many device pixels the length covers at `dpi`, rounded
+    /// up so that nothing is cut off.
+    pub fn to_device_pixels(self, dpi: Dpi) -> u32 {
+        (self.inches() * dpi.into_inner()).ceil() as u32
+    }
+}
+
+/// Width of a component in pixels.
+#[nutype(
+    validate(greater_or_equal = 0),
+    derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)
+)]
+pub struct Width(u32);
+
+/// Height of a component in pixels.
+#[nutype(
+    validate(greater_or_equal = 0),
+    derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)
+)]
+pub struct Height(u32);
+
+/// X coordinate in pixels.
+#[nutype(
+    validate(greater_or_equal = 0),
+    derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)
+)]
+pub struct XCoordinate(u32);
+
+/// Y coordinate in pixels.
+#[nutype(
+    validate(greater_or_equal = 0),
+    derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)
+)]
+pub struct YCoordinate(u32);
+
+/// Font size in pixels.
+#[nutype(
+    validate(greater = 0),
+    derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)
+)]
+pub struct FontSize(u32);
+
+/// Color value in hex format.
+#[nutype(
+    validate(regex = r"^#[0-9a-fA-F]{6}$"),
+    derive(Debug, Clone, PartialEq, Eq)
+)]
+pub struct HexColor(String);
+
+/// Layout constants for the diagram.
+///
+/// These constants define the visual layout parameters for diagram rendering.
+/// The typed fields ensure compile-time validation of layout values.
+///
+/// TODO: Consider creating more specific domain types (e.g., DiagramWidth,
+/// SwimlaneHeight) instead of generic Width/Height types for better
+/// semantic clarity.
+#[allow(dead_code)]
+pub struct LayoutConstants {
+    /// Minimum width of the entire canvas.
+    pub min_canvas_width: Width,
+    /// Minimum height of the entire canvas.
+    pub min_canvas_height: Height,
+    /// Width of the swimlane label area.
+    pub swimlane_label_width: Width,
+    /// Minimum height of a swimlane.
+    pub min_swimlane_height: Height,
+    /// Height of the header area containing the title.
+    pub header_height: Height,
+    /// Height of slice header area.
+    pub slice_header_height: Height,
+    /// Minimum width per slice.
+    pub min_slice_width: Width,
+    /// Font size for slice headers.
+    pub slice_header_font_size: FontSize,
+    /// Font size for regular text.
+    pub regular_font_size: FontSize,
+    /// Font size for swimlane labels.
+    pub swimlane_font_size: FontSize,
+}
+
+impl Default for LayoutConstants {
+    fn default() -> Self {
+        Self {
+            min_canvas_width: Width::try_new(800).unwrap(),
+            min_canvas_height: Height::try_new(600).unwrap(),
+            swimlane_label_width: Width::try_new(80).unwrap(),
+            min_swimlane_height: Height::try_new(200).unwrap(),
+            header_height: Height::try_new(50).unwrap(),
+            slice_header_height: Height::try_new(30).unwrap(),
+            min_slice_width: Width::try_new(300).unwrap(),
+            slice_header_font_size: FontSize::try_new(11).unwrap(),
+            regular_font_size: FontSize::try_new(12).unwrap(),
+            swimlane_font_size: FontSize::try_new(10).unwrap(),
+        }
+    }
+}
+
+/// Color scheme for the diagram.
+#[allow(dead_code)]
+pub struct ColorScheme {
+    /// Background color of the canvas.
+    pub canvas_background: HexColor,
+    /// Default text color.
+    pub text_color: HexColor,
+    /// Color for swimlane borders.
+    pub swimlane_border: HexColor,
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        Self {
+            canvas_background: HexColor::try_new("#f8f8f8".to_string()).unwrap(),
+            text_color: HexColor::try_new("#333333".to_string()).unwrap(),
+            swimlane_border: HexColor::try_new("#cccccc".to_string()).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn px_arithmetic_keeps_counts_and_lengths_apart() {
+        let lines = 3;
+        let line_height = Px::new(12);
+
+        assert_eq!(lines * line_height, Px::new(36));
+        assert_eq!(Px::new(100) / Px::new(6), 16);
+        assert_eq!(Px::new(10).saturating_sub(Px::new(20)), Px::ZERO);
+        assert_eq!([Px::new(1), Px::new(2)].iter().sum::<Px>(), Px::new(3));
+        assert_eq!(Px::new(10).scale(1.2), Px::new(12));
+        assert_eq!(Px::new(42).to_string(), "42");
+        assert_eq!(Px::new(11).snap_to(Px::new(8)), Px::new(8));
+        assert_eq!(Px::new(12).snap_to(Px::new(8)), Px::new(16));
+        assert_eq!(Px::new(12).snap_to(Px::ZERO), Px::new(12));
+    }
+
+    #[test]
+    fn converts_canvas_pixels_to_physical_and_device_units() {
+        let inch = Px::new(96);
+
+        assert_eq!(inch.to_length().points(), 72.0);
+        assert_eq!(Length::from_millimeters(25.4).inches(), 1.0);
+        assert_eq!(inch.to_device_pixels(Dpi::default()), 96);
+        assert_eq!(inch.to_device_pixels(Dpi::try_new(300.0).unwrap()), 300);
+        assert!(Dpi::try_new(0.0).is_err());
+    }
+}
@@ -6,20 +6,46 @@
 use crate::event_model::yaml_types;
 use thiserror::Error;
 
+mod budget;
 mod builder;
+mod cache;
+mod clearance;
+mod collisions;
+#[cfg(feature = "constraint-layout")]
+mod constraints;
+mod dimensions;
+mod jitter;
 mod layout_types;
+mod optimize;
 pub mod routing_types;
+mod score;
 mod svg;
+mod trace;
 
+pub use self::budget::RenderBudget;
 pub use self::builder::EventModelDiagram;
-pub use self::svg::render_to_svg;
+pub use self::cache::RenderCache;
+pub use self::dimensions::{DimensionCache, EntityKind};
+pub use self::jitter::Jitter;
+pub use self::layout_types::{Dpi, Length, Px};
+pub use self::optimize::{DEFAULT_OPTIMIZE_TIME, OptimizeBudget};
+pub use self::score::LayoutScore;
+pub use self::svg::{SvgOptions, render_to_svg, render_to_svg_traced, render_to_svg_with_cache};
+pub use self::trace::{
+    ColumnTrace, EntityTrace, LayoutTrace, RenderTrace, RouteTrace, SwimlaneTrace,
+};
 
 /// Errors that can occur during diagram generation.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum DiagramError {
     /// Error occurred during SVG rendering.
     #[error("SVG rendering error: {0}")]
     SvgError(String),
+
+    /// Layout or rendering broke one of its own invariants.
+    #[error(transparent)]
+    Invariant(#[from] crate::infrastructure::types::InvariantError),
 }
 
 /// Result type for diagram operations.
@@ -0,0 +1,234 @@
+//! An optional search for entity orderings that make cleaner diagrams.
+//!
+//! Entities sharing a slice and swimlane are placed side by side in the order
+//! the slice's connections first mention them, which on dense models often
+//! sends connectors across each other. The optimization pass anneals those
+//! orderings: it swaps two entities of a cell, re-scores the layout, and keeps
+//! the swap if the score improved, or sometimes even if not, early on, so the
+//! search does not settle on the first local minimum. The best ordering seen
+//! is kept once the iteration or time budget runs out, or the render's own
+//! deadline passes.
+
+use super::jitter::Jitter;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// Wall-clock time the optimization pass may take unless told otherwise.
+pub const DEFAULT_OPTIMIZE_TIME: Duration = Duration::from_secs(2);
+
+/// How long the optimization pass may search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OptimizeBudget {
+    /// Number of orderings to try.
+    pub iterations: u32,
+    /// Time after which the search stops, however many orderings are left.
+    pub time_limit: Duration,
+}
+
+impl OptimizeBudget {
+    /// Creates a budget of `iterations` orderings within the default time.
+    pub fn new(iterations: u32) -> Self {
+        Self {
+            iterations,
+            time_limit: DEFAULT_OPTIMIZE_TIME,
+        }
+    }
+}
+
+/// Reorders the entries of each cell `movable` allows to lower `cost`,
+/// leaving `cells` in the cheapest ordering found. The same seed and budget
+/// always find the same ordering, unless the time limit or `render_deadline`,
+/// whichever comes first, cuts the search short.
+pub(super) fn anneal<K, T, E>(
+    cells: &mut BTreeMap<K, Vec<T>>,
+    budget: &OptimizeBudget,
+    render_deadline: Option<Instant>,
+    seed: u64,
+    movable: impl Fn(&K) -> bool,
+    mut cost: impl FnMut(&BTreeMap<K, Vec<T>>) -> Result<u64, E>,
+) -> Result<(), E>
+where
+    K: Ord + Clone,
+{
+    let swappable: Vec<K> = cells
+        .iter()
+        .filter(|(key, entries)| entries.len() > 1 && movable(key))
+        .map(|(key, _)| key.clone())
+        .collect();
+    if swappable.is_empty() || budget.iterations == 0 {
+        return Ok(());
+    }
+
+    // A time limit too long to represent sets no deadline of its own
+    let deadline = match (
+        Instant::now().checked_add(budget.time_limit),
+        render_deadline,
+    ) {
+        (Some(own), Some(render)) => Some(own.min(render)),
+        (own, render) => own.or(render),
+    };
+    let mut jitter = Jitter::new(seed);
+    let mut current_cost = cost(cells)?;
+    let mut best_cost = current_cost;
+    let mut best: Option<Vec<(usize, usize, usize)>> = None;
+    // Swaps made since the ordering passed in, replayed onto it for the best
+    let mut swaps: Vec<(usize, usize, usize)> = Vec::new();
+    let initial_temperature = (current_cost as f64 / 10.0).max(1.0);
+
+    for iteration in 0..budget.iterations {
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            break;
+        }
+        let cell = (jitter.next_u64() % swappable.len() as u64) as usize;
+        let len = cells.get(&swappable[cell]).map_or(0, Vec::len) as u64;
+        let first = (jitter.next_u64() % len) as usize;
+        let second = ((first as u64 + 1 + jitter.next_u64() % (len - 1)) % len) as usize;
+        swap(cells, &swappable[cell], first, second);
+
+        let candidate_cost = cost(cells)?;
+        // Cools linearly, so late in the search only improvements are kept
+        let temperature = initial_temperature * f64::from(budget.iterations - iteration)
+            / f64::from(budget.iterations);
+        let accept = candidate_cost <= current_cost || {
+            let worse = (candidate_cost - current_cost) as f64;
+            let chance = (jitter.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+            chance < (-worse / temperature).exp()
+        };
+        if accept {
+            current_cost = candidate_cost;
+            swaps.push((cell, first, second));
+            if current_cost < best_cost {
+                best_cost = current_cost;
+                best = Some(swaps.clone());
+            }
+        } else {
+            swap(cells, &swappable[cell], first, second);
+        }
+    }
+
+    // Undo every swap, then replay those leading to the best ordering
+    for &(cell, first, second) in swaps.iter().rev() {
+        swap(cells, &swappable[cell], first, second);
+    }
+    for (cell, first, second) in best.unwrap_or_default() {
+        swap(cells, &swappable[cell], first, second);
+    }
+    Ok(())
+}
+
+/// Swaps two entries of a cell.
+fn swap<K: Ord, T>(cells: &mut BTreeMap<K, Vec<T>>, cell: &K, first: usize, second: usize) {
+    if let Some(entries) = cells.get_mut(cell) {
+        entries.swap(first, second);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+
+    /// Counts the entries out of ascending order across every cell.
+    fn inversions(cells: &BTreeMap<u32, Vec<u32>>) -> Result<u64, Infallible> {
+        Ok(cells
+            .values()
+            .map(|entries| {
+                entries
+                    .iter()
+                    .enumerate()
+                    .map(|(i, a)| entries[i + 1..].iter().filter(|b| a > b).count() as u64)
+                    .sum::<u64>()
+            })
+            .sum())
+    }
+
+    #[test]
+    fn keeps_the_cheapest_ordering_found() {
+        let mut cells = BTreeMap::from([(0, vec![3, 2, 1, 0]), (1, vec![1, 0]), (2, vec![5])]);
+
+        anneal(
+            &mut cells,
+            &OptimizeBudget::new(500),
+            None,
+            7,
+            |_| true,
+            inversions,
+        )
+        .unwrap();
+
+        assert_eq!(
+            cells,
+            BTreeMap::from([(0, vec![0, 1, 2, 3]), (1, vec![0, 1]), (2, vec![5])])
+        );
+    }
+
+    #[test]
+    fn leaves_cells_that_are_not_movable_alone() {
+        let mut cells = BTreeMap::from([(0, vec![3, 2, 1, 0]), (1, vec![1, 0])]);
+
+        anneal(
+            &mut cells,
+            &OptimizeBudget::new(500),
+            None,
+            7,
+            |&cell| cell != 1,
+            inversions,
+        )
+        .unwrap();
+
+        assert_eq!(
+            cells,
+            BTreeMap::from([(0, vec![0, 1, 2, 3]), (1, vec![1, 0])])
+        );
+    }
+
+    #[test]
+    fn same_seed_finds_the_same_ordering() {
+        let start = BTreeMap::from([(0, vec![4, 1, 3, 0, 2])]);
+        let search = |seed| {
+            let mut cells = start.clone();
+            anneal(
+                &mut cells,
+                &OptimizeBudget::new(3),
+                None,
+                seed,
+                |_| true,
+                inversions,
+            )
+            .unwrap();
+            cells
+        };
+
+        assert_eq!(search(11), search(11));
+    }
+
+    #[test]
+    fn unrepresentable_time_limit_searches_without_a_deadline() {
+        let mut cells = BTreeMap::from([(0, vec![1, 0])]);
+        let budget = OptimizeBudget {
+            iterations: 50,
+            time_limit: Duration::MAX,
+        };
+
+        anneal(&mut cells, &budget, None, 7, |_| true, inversions).unwrap();
+
+        assert_eq!(cells, BTreeMap::from([(0, vec![0, 1])]));
+    }
+
+    #[test]
+    fn passed_render_deadline_stops_the_search() {
+        let mut cells = BTreeMap::from([(0, vec![1, 0])]);
+
+        anneal(
+            &mut cells,
+            &OptimizeBudget::new(500),
+            Some(Instant::now()),
+            7,
+            |_| true,
+            inversions,
+        )
+        .unwrap();
+
+        assert_eq!(cells, BTreeMap::from([(0, vec![1, 0])]));
+    }
+}
@@ -5,49 +5,51 @@
 
 #![allow(dead_code)] // Types will be used once libavoid is integrated
 
+use super::layout_types::Px;
 use crate::infrastructure::types::NonEmpty;
+use serde::Serialize;
 
 /// A point in 2D space for routing calculations.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 pub struct Point {
-    pub x: u32,
-    pub y: u32,
+    pub x: Px,
+    pub y: Px,
 }
 
 impl Point {
     /// Creates a new point.
-    pub fn new(x: u32, y: u32) -> Self {
+    pub fn new(x: Px, y: Px) -> Self {
         Self { x, y }
     }
 
     /// Calculates the Manhattan distance to another point.
-    pub fn manhattan_distance(&self, other: &Point) -> u32 {
-        ((self.x as i32 - other.x as i32).abs() + (self.y as i32 - other.y as i32).abs()) as u32
+    pub fn manhattan_distance(&self, other: &Point) -> Px {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
     }
 
     /// Returns the right edge X coordinate
-    pub fn right(&self) -> u32 {
+    pub fn right(&self) -> Px {
         self.x
     }
 
     /// Returns the bottom edge Y coordinate
-    pub fn bottom(&self) -> u32 {
+    pub fn bottom(&self) -> Px {
         self.y
     }
 }
 
 /// A rectangular area representing an entity's position and dimensions.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Rectangle {
-    pub x: u32,
-    pub y: u32,
-    pub width: u32,
-    pub height: u32,
+    pub x: Px,
+    pub y: Px,
+    pub width: Px,
+    pub height: Px,
 }
 
 impl Rectangle {
     /// Creates a new rectangle.
-    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+    pub fn new(x: Px, y: Px, width: Px, height: Px) -> Self {
         Self {
             x,
             y,
@@ -62,12 +64,12 @@ impl Rectangle {
     }
 
     /// Returns the right edge coordinate
-    pub fn right(&self) -> u32 {
+    pub fn right(&self) -> Px {
         self.x + self.width
     }
 
     /// Returns the bottom edge coordinate
-    pub fn bottom(&self) -> u32 {
+    pub fn bottom(&self) -> Px {
         self.y + self.height
     }
 
@@ -112,12 +114,12 @@ impl Rectangle {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RoutePath {
     pub nodes: NonEmpty<Point>,
-    pub total_cost: u32,
+    pub total_cost: Px,
 }
 
 impl RoutePath {
     /// Creates a new route path.
-    pub fn new(nodes: NonEmpty<Point>, total_cost: u32) -> Self {
+    pub fn new(nodes: NonEmpty<Point>, total_cost: Px) -> Self {
         Self { nodes, total_cost }
     }
 
@@ -138,31 +140,39 @@ impl RoutePath {
 mod tests {
     use super::*;
 
+    fn point(x: u32, y: u32) -> Point {
+        Point::new(Px::new(x), Px::new(y))
+    }
+
+    fn rect(x: u32, y: u32, width: u32, height: u32) -> Rectangle {
+        Rectangle::new(Px::new(x), Px::new(y), Px::new(width), Px::new(height))
+    }
+
     #[test]
     fn test_point_manhattan_distance() {
-        let p1 = Point::new(0, 0);
-        let p2 = Point::new(3, 4);
-        assert_eq!(p1.manhattan_distance(&p2), 7);
+        let p1 = point(0, 0);
+        let p2 = point(3, 4);
+        assert_eq!(p1.manhattan_distance(&p2), Px::new(7));
     }
 
     #[test]
     fn test_rectangle_center() {
-        let rect = Rectangle::new(10, 20, 40, 30);
-        assert_eq!(rect.center(), Point::new(30, 35));
+        let rect = rect(10, 20, 40, 30);
+        assert_eq!(rect.center(), point(30, 35));
     }
 
     #[test]
     fn test_rectangle_contains() {
-        let rect = Rectangle::new(10, 10, 20, 20);
-        assert!(rect.contains(&Point::new(15, 15)));
-        assert!(!rect.contains(&Point::new(5, 5)));
+        let rect = rect(10, 10, 20, 20);
+        assert!(rect.contains(&point(15, 15)));
+        assert!(!rect.contains(&point(5, 5)));
     }
 
     #[test]
     fn test_rectangle_intersects() {
-        let rect1 = Rectangle::new(0, 0, 10, 10);
-        let rect2 = Rectangle::new(5, 5, 10, 10);
-        let rect3 = Rectangle::new(20, 20, 10, 10);
+        let rect1 = rect(0, 0, 10, 10);
+        let rect2 = rect(5, 5, 10, 10);
+        let rect3 = rect(20, 20, 10, 10);
 
         assert!(rect1.intersects(&rect2));
         assert!(!rect1.intersects(&rect3));
@@ -170,11 +180,8 @@ mod tests {
 
     #[test]
     fn test_route_path_to_svg() {
-        let points = NonEmpty::from_head_and_tail(
-            Point::new(0, 0),
-            vec![Point::new(10, 0), Point::new(10, 10)],
-        );
-        let path = RoutePath::new(points, 20);
+        let points = NonEmpty::from_head_and_tail(point(0, 0), vec![point(10, 0), point(10, 10)]);
+        let path = RoutePath::new(points, Px::new(20));
         assert_eq!(path.to_svg_path(), "M 0 0 L 10 0 L 10 10");
     }
 }
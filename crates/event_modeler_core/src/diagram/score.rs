@@ -0,0 +1,184 @@
+//! How readable a rendered layout is, as a handful of numbers.
+//!
+//! A score is read off the [`RenderTrace`] of a render: the total length of
+//! the connectors, the bends in them, the places two connectors cross, and
+//! the area of the canvas. Lower is better for each. Comparing the score of
+//! a fixture against one recorded earlier catches a change to layout or
+//! routing that makes diagrams worse before it is released.
+
+use super::layout_types::Px;
+use super::routing_types::Point;
+use super::trace::{RenderTrace, RouteTrace};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Measures of a layout's quality; lower is better for each.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayoutScore {
+    /// Total length of every connector, in pixels.
+    pub connector_length: u32,
+    /// Number of bends across every connector.
+    pub bends: u32,
+    /// Number of places one connector crosses another.
+    pub crossings: u32,
+    /// Area of the canvas, in square pixels.
+    pub area: u64,
+}
+
+impl LayoutScore {
+    /// Scores the layout and routes of a render.
+    pub fn from_trace(trace: &RenderTrace) -> Self {
+        let routes = &trace.routes;
+        let mut crossings = 0;
+        for (i, route) in routes.iter().enumerate() {
+            for other in &routes[i + 1..] {
+                crossings += crossings_between(route, other);
+            }
+        }
+        Self {
+            connector_length: routes
+                .iter()
+                .flat_map(|route| route.points.windows(2))
+                .map(|segment| segment[0].manhattan_distance(&segment[1]))
+                .sum::<Px>()
+                .get(),
+            bends: routes.iter().map(|route| bends(&route.points)).sum(),
+            crossings,
+            area: u64::from(trace.layout.width.get()) * u64::from(trace.layout.height.get()),
+        }
+    }
+
+    /// Returns the measures that are worse than in `baseline`, by name.
+    pub fn regressions(&self, baseline: &Self) -> Vec<&'static str> {
+        [
+            (
+                "connector length",
+                self.connector_length > baseline.connector_length,
+            ),
+            ("bends", self.bends > baseline.bends),
+            ("crossings", self.crossings > baseline.crossings),
+            ("area", self.area > baseline.area),
+        ]
+        .into_iter()
+        .filter(|(_, worse)| *worse)
+        .map(|(name, _)| name)
+        .collect()
+    }
+}
+
+impl fmt::Display for LayoutScore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "connector length {}px, {} bends, {} crossings, area {}px²",
+            self.connector_length, self.bends, self.crossings, self.area
+        )
+    }
+}
+
+/// Counts the points where a path turns, skipping those it passes straight
+/// through.
+fn bends(points: &[Point]) -> u32 {
+    points
+        .windows(3)
+        .filter(|turn| cross(turn[0], turn[1], turn[2]) != 0)
+        .count() as u32
+}
+
+/// Counts the places two paths cross. Paths meeting at an end, as the
+/// connectors of one entity do, or running along each other do not cross.
+fn crossings_between(a: &RouteTrace, b: &RouteTrace) -> u32 {
+    let mut crossings = 0;
+    for first in a.points.windows(2) {
+        for second in b.points.windows(2) {
+            if segments_cross(first[0], first[1], second[0], second[1]) {
+                crossings += 1;
+            }
+        }
+    }
+    crossings
+}
+
+/// Returns whether segment `a`–`b` passes through segment `c`–`d`, each
+/// leaving the other's line on both sides.
+pub(super) fn segments_cross(a: Point, b: Point, c: Point, d: Point) -> bool {
+    let sides = |p: Point, q: Point, r: Point, s: Point| {
+        let first = cross(p, q, r).signum();
+        let second = cross(p, q, s).signum();
+        first != 0 && second != 0 && first != second
+    };
+    sides(a, b, c, d) && sides(c, d, a, b)
+}
+
+/// Returns the z component of the cross product of `a`→`b` and `a`→`c`:
+/// positive if `c` lies to one side of the line through `a` and `b`,
+/// negative if to the other, and zero if on it.
+fn cross(a: Point, b: Point, c: Point) -> i64 {
+    let (ax, ay) = (i64::from(a.x.get()), i64::from(a.y.get()));
+    let (bx, by) = (i64::from(b.x.get()), i64::from(b.y.get()));
+    let (cx, cy) = (i64::from(c.x.get()), i64::from(c.y.get()));
+    (bx - ax) * (cy - ay) - (by - ay) * (cx - ax)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagram::LayoutTrace;
+
+    fn route(points: &[(u32, u32)]) -> RouteTrace {
+        RouteTrace {
+            connection: "A -> B".to_string(),
+            slice: "Slice".to_string(),
+            points: points
+                .iter()
+                .map(|&(x, y)| Point::new(Px::new(x), Px::new(y)))
+                .collect(),
+            cost: Px::ZERO,
+        }
+    }
+
+    #[test]
+    fn scores_length_bends_crossings_and_area() {
+        let trace = RenderTrace {
+            layout: LayoutTrace {
+                width: Px::new(200),
+                height: Px::new(100),
+                ..LayoutTrace::default()
+            },
+            routes: vec![
+                // Turning once, and passing straight through (50, 50)
+                route(&[(0, 50), (50, 50), (150, 50), (150, 100)]),
+                // Crossing the first
+                route(&[(100, 0), (100, 100)]),
+                // Starting where the first ends
+                route(&[(150, 100), (190, 100)]),
+                // Running along the first
+                route(&[(20, 50), (40, 50)]),
+            ],
+        };
+
+        let score = LayoutScore::from_trace(&trace);
+
+        assert_eq!(
+            score,
+            LayoutScore {
+                connector_length: 360,
+                bends: 1,
+                crossings: 1,
+                area: 20_000,
+            }
+        );
+        assert!(
+            score
+                .regressions(&LayoutScore { bends: 2, ..score })
+                .is_empty()
+        );
+        assert_eq!(
+            score.regressions(&LayoutScore {
+                crossings: 0,
+                ..score
+            }),
+            ["crossings"]
+        );
+    }
+}
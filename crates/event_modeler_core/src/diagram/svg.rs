@@ -0,0 +1,4010 @@
+//! SVG rendering for event model diagrams.
+//!
+//! This module provides functionality to render event model diagrams as SVG.
+
+use super::clearance;
+use super::collisions::{self, RoutedConnection};
+#[cfg(feature = "constraint-layout")]
+use super::constraints;
+use super::dimensions::{
+    ENTITY_BOX_WIDTH, ENTITY_NAME_FONT_SIZE, ENTITY_PADDING, EntityDimensions, EntityKind,
+    ICON_TEXT_SPACING, ROBOT_ICON_SIZE, char_width, line_height, make_uniform, wrap_text,
+};
+use super::jitter::{Jitter, LabelPlacer};
+use super::layout_types::Px;
+use super::optimize::{OptimizeBudget, anneal};
+use super::routing_types::{Point, Rectangle, RoutePath};
+use super::score::segments_cross;
+use super::trace::{ColumnTrace, EntityTrace, LayoutTrace, RenderTrace, RouteTrace, SwimlaneTrace};
+use super::{EventModelDiagram, Result};
+use super::{RenderBudget, RenderCache};
+use crate::event_model::entities::EntityType;
+use crate::event_model::ownership::OwnershipReport;
+use crate::event_model::patterns::{SlicePattern, SlicePatterns};
+use crate::event_model::yaml_types;
+use crate::infrastructure::issues::IssueState;
+use crate::infrastructure::markup::escape_xml;
+use crate::infrastructure::progress::{Progress, ProgressStage};
+use crate::infrastructure::types::{InvariantError, NonEmpty, PositiveInt};
+use crate::infrastructure::warnings::{WarningKind, Warnings};
+use crate::routing::{ObstacleSetHash, RouteCache, RoutingConfig};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+// Constants for SVG dimensions and text coordinates
+const MIN_WIDTH: Px = Px::new(1200); // Minimum reasonable width
+const PADDING: Px = Px::new(20); // Consistent padding around elements
+const TITLE_FONT_SIZE: Px = Px::new(12);
+const TITLE_Y: Px = Px::new(35);
+const DESCRIPTION_FONT_SIZE: Px = Px::new(10);
+
+// Swimlane constants
+const MIN_SWIMLANE_HEIGHT: Px = Px::new(200); // Minimum height for empty swimlane
+const UNUSED_SWIMLANE_HEIGHT: Px = Px::new(30); // Thin band for a swimlane no slice uses
+const SWIMLANE_LABEL_WIDTH: Px = Px::new(80); // Width for rotated labels
+const SWIMLANE_LABEL_FONT_SIZE: Px = Px::new(10);
+const SWIMLANE_TINT_OPACITY: f32 = 0.15; // Keeps tinted bands subtle behind entities
+const SWIMLANE_ACCENT_WIDTH: Px = Px::new(4); // Solid tint strip along a tinted swimlane's label
+const GROUP_LABEL_WIDTH: Px = Px::new(24); // Column for group labels within the label area
+const GROUP_LABEL_FONT_SIZE: Px = Px::new(11);
+const GROUP_BRACKET_INSET: Px = Px::new(6); // Gap between a group bracket and its lanes' borders
+const GROUP_BRACKET_TICK: Px = Px::new(4); // Length of the ticks closing a group bracket
+const HEADER_HEIGHT: Px = Px::new(50); // Space for title area
+
+// Slice constants
+const DEFAULT_SLICE_HEADER_HEIGHT: Px = Px::new(30); // Minimum height of slice header area
+const DEFAULT_SLICE_HEADER_LINES: u32 = 2; // Lines a slice title may wrap onto
+const SLICE_HEADER_LINE_HEIGHT: Px = Px::new(13);
+const SLICE_HEADER_PADDING: Px = Px::new(8); // Space around a slice title
+const MIN_SLICE_WIDTH: Px = Px::new(300); // Minimum width per slice
+const SLICE_HEADER_FONT_SIZE: Px = Px::new(11);
+const SHARED_COLUMN_LABEL_FONT_SIZE: Px = Px::new(9);
+const SHARED_COLUMN_LABEL_INSET: Px = Px::new(4); // Offset of a slice label from its cell's corner
+
+// Bounded context constants
+const CONTEXT_BAND_HEIGHT: Px = Px::new(22); // Row naming the contexts above the slice headers
+const CONTEXT_LABEL_FONT_SIZE: Px = Px::new(11);
+const CONTEXT_REGION_OPACITY: f32 = 0.08; // Keeps context regions faint behind entities
+const CONTEXT_BAND_OPACITY: f32 = 0.3;
+const PATTERN_TINT_OPACITY: f32 = 0.1; // Keeps pattern tints faint behind entities
+const PATTERN_STATE_CHANGE_COLOR: &str = "#3498db"; // Blue, like commands
+const PATTERN_STATE_VIEW_COLOR: &str = "#2ecc71"; // Green, like read models
+const PATTERN_AUTOMATION_COLOR: &str = "#95a5a6"; // Grey, like automations
+const PATTERN_TRANSLATION_COLOR: &str = "#9b59b6"; // Purple, like events
+const CONTEXT_COLORS: [&str; 6] = [
+    "#2980b9", "#27ae60", "#e67e22", "#8e44ad", "#16a085", "#c0392b",
+];
+
+// Colors
+const BACKGROUND_COLOR: &str = "#f8f8f8"; // Light gray background
+const WATERMARK_COLOR: &str = "#999999";
+const WATERMARK_OPACITY: f32 = 0.15; // Keeps the watermark faint behind the diagram
+const WATERMARK_SPAN: f64 = 0.7; // Share of the canvas diagonal the watermark spans
+const TEXT_COLOR: &str = "#333333"; // Dark gray text
+const DESCRIPTION_COLOR: &str = "#666666"; // Muted gray for the workflow description
+const DEBUG_GRID_COLOR: &str = "#4a90d9"; // Light blue gridlines
+const DEBUG_GRID_OPACITY: f32 = 0.3;
+/// Spacing of the debug gridlines for models without a layout grid.
+const DEBUG_GRID_DEFAULT_SPACING: Px = Px::new(8);
+const SWIMLANE_BORDER_COLOR: &str = "#cccccc"; // Light gray for borders
+const SHARED_COLUMN_LABEL_COLOR: &str = "#888888"; // Mid gray for slice labels in shared columns
+const UNUSED_LANE_COLOR: &str = "#e6e6e6"; // Gray over swimlanes a slice leaves empty
+
+// Entity constants
+const ENTITY_MARGIN: Px = Px::new(20); // Margin between entities
+
+// Entity colors
+const VIEW_BACKGROUND_COLOR: &str = "#ffffff"; // White for views
+const COMMAND_BACKGROUND_COLOR: &str = "#4a90e2"; // Blue for commands
+const EVENT_BACKGROUND_COLOR: &str = "#9b59b6"; // Purple for events
+const PROJECTION_BACKGROUND_COLOR: &str = "#f1c40f"; // Yellow for projections
+const QUERY_BACKGROUND_COLOR: &str = "#27ae60"; // Green for queries
+const AUTOMATION_ICON: &str = "⚙️"; // Gear for automations
+
+// Custom entity kind constants
+const KIND_ICON_FONT_SIZE: Px = Px::new(12);
+const KIND_ICON_INSET: Px = Px::new(3); // Distance of the icon from the entity's corner
+const KIND_AUTOMATION_CORNER_RADIUS: Px = Px::new(6); // Tinted backdrop of automation-based kinds
+
+// Error outcome constants
+const ERROR_OUTCOME_HEIGHT: Px = Px::new(16);
+const ERROR_OUTCOME_GAP: Px = Px::new(4); // Space above each outcome box
+const ERROR_OUTCOME_FONT_SIZE: Px = Px::new(9);
+const ERROR_OUTCOME_CHAR_WIDTH: Px = Px::new(5); // Approximate advance of one outcome character
+const ERROR_OUTCOME_BACKGROUND_COLOR: &str = "#fdecea"; // Light red tint
+const ERROR_OUTCOME_COLOR: &str = "#c0392b"; // Dark red border and text
+
+// Command access constants
+const ACCESS_BADGE_HEIGHT: Px = Px::new(14);
+const ACCESS_BADGE_FONT_SIZE: Px = Px::new(8);
+const ACCESS_BADGE_CHAR_WIDTH: Px = Px::new(5); // Approximate advance of one badge character
+
+// Volume constants
+const VOLUME_BADGE_HEIGHT: Px = Px::new(14);
+const VOLUME_BADGE_FONT_SIZE: Px = Px::new(8);
+const VOLUME_BADGE_CHAR_WIDTH: Px = Px::new(5); // Approximate advance of one badge character
+const VOLUME_BADGE_COLOR: &str = "#555555"; // Dark grey
+
+// Ownership constants
+const OWNER_BADGE_HEIGHT: Px = Px::new(14);
+const OWNER_BADGE_FONT_SIZE: Px = Px::new(8);
+const OWNER_BADGE_CHAR_WIDTH: Px = Px::new(5); // Approximate advance of one badge character
+const ESTIMATE_BADGE_HEIGHT: Px = Px::new(14);
+const ESTIMATE_BADGE_FONT_SIZE: Px = Px::new(9);
+const ESTIMATE_BADGE_CHAR_WIDTH: Px = Px::new(6); // Approximate advance of one badge character
+const ESTIMATE_BADGE_INSET: Px = Px::new(4);
+const ESTIMATE_BADGE_COLOR: &str = "#34495e";
+const ISSUE_BADGE_HEIGHT: Px = Px::new(14);
+const ISSUE_BADGE_FONT_SIZE: Px = Px::new(9);
+const ISSUE_BADGE_CHAR_WIDTH: Px = Px::new(6); // Approximate advance of one badge character
+const ISSUE_BADGE_INSET: Px = Px::new(4);
+const ISSUE_UNKNOWN_COLOR: &str = "#57606a";
+const ISSUE_OPEN_COLOR: &str = "#1a7f37";
+const ISSUE_CLOSED_COLOR: &str = "#8250df";
+const STATUS_SHADING_OPACITY: &str = "0.3";
+const STATUS_DONE_COLOR: &str = "#27ae60";
+const STATUS_IN_PROGRESS_COLOR: &str = "#f1c40f";
+const STATUS_TODO_COLOR: &str = "#bdc3c7";
+const OWNER_LEGEND_ROW_HEIGHT: Px = Px::new(20);
+const OWNER_LEGEND_SWATCH_SIZE: Px = Px::new(10);
+const OWNER_LEGEND_FONT_SIZE: Px = Px::new(10);
+const OWNER_LEGEND_CHAR_WIDTH: Px = Px::new(6); // Approximate advance of one legend character
+const OWNER_COLORS: [&str; 8] = [
+    "#e67e22", "#16a085", "#c0392b", "#2980b9", "#8e44ad", "#d35400", "#27ae60", "#7f8c8d",
+];
+const MISSING_OWNER_COLOR: &str = "#bbbbbb";
+
+// Implementation link constants
+const LINK_MARKER_FONT_SIZE: Px = Px::new(10);
+const LINK_MARKER_INSET: Px = Px::new(4); // Distance of the marker from the entity's corner
+
+// PII constants
+const PII_SHIELD_WIDTH: Px = Px::new(8);
+const PII_SHIELD_HEIGHT: Px = Px::new(10);
+const PII_SHIELD_COLOR: &str = "#8e44ad"; // Purple
+
+// Hotspot constants
+const HOTSPOT_SIZE: Px = Px::new(14); // Width and height of the warning triangle
+const HOTSPOT_INSET: Px = Px::new(3); // Distance of an entity's triangle from its corner
+const HOTSPOT_FONT_SIZE: Px = Px::new(10);
+const HOTSPOT_COLOR: &str = "#f39c12"; // Amber
+
+// Arrow rendering constants
+const MIN_ARROW_EXTENSION: Px = Px::new(30); // Minimum extension for arrow lead lines
+const CONNECTOR_COLOR: &str = "#333333"; // Dark gray connectors and markers
+const CONNECTOR_WIDTH: Px = Px::new(2);
+const FOCUSED_CONNECTOR_WIDTH: Px = Px::new(3); // Connectors of the slice in focus
+const FADED_CONNECTOR_OPACITY: f32 = 0.3; // Connectors outside the slice in focus
+const DIMMED_VEIL_OPACITY: f32 = 0.6; // Canvas-colored veil over dimmed entities
+const HIGHLIGHT_COLOR: &str = "#e91e63"; // Pink, unlike any entity type
+const HIGHLIGHT_WIDTH: Px = Px::new(3);
+const HIGHLIGHT_GAP: Px = Px::new(4); // Room between a highlighted entity and its outline
+const MULTIPLICITY_BADGE_HEIGHT: Px = Px::new(14);
+const MULTIPLICITY_BADGE_FONT_SIZE: Px = Px::new(9);
+const MULTIPLICITY_BADGE_CHAR_WIDTH: Px = Px::new(5); // Approximate advance of one badge character
+const CLOCK_BADGE_RADIUS: Px = Px::new(7); // Eventual consistency badge on event-to-projection connectors
+const CROSSING_COST: u64 = 200; // Connector length a crossing is worth avoiding, when optimizing
+const SELF_LOOP_EXTENT: Px = Px::new(24); // Height of a loop back to its own entity, within the lane margin
+
+/// Creates a lookup map from view names to their definitions.
+fn create_view_lookup(
+    views: &HashMap<yaml_types::ViewName, yaml_types::ViewDefinition>,
+) -> HashMap<String, &yaml_types::ViewDefinition> {
+    views
+        .iter()
+        .map(|(name, def)| {
+            let s = name.clone().into_inner();
+            (s.as_str().to_string(), def)
+        })
+        .collect()
+}
+
+/// Creates a lookup map from command names to their definitions.
+fn create_command_lookup(
+    commands: &HashMap<yaml_types::CommandName, yaml_types::CommandDefinition>,
+) -> HashMap<String, &yaml_types::CommandDefinition> {
+    commands
+        .iter()
+        .map(|(name, def)| {
+            let s = name.clone().into_inner();
+            (s.as_str().to_string(), def)
+        })
+        .collect()
+}
+
+/// Creates a lookup map from event names to their definitions.
+fn create_event_lookup(
+    events: &HashMap<yaml_types::EventName, yaml_types::EventDefinition>,
+) -> HashMap<String, &yaml_types::EventDefinition> {
+    events
+        .iter()
+        .map(|(name, def)| {
+            let s = name.clone().into_inner();
+            (s.as_str().to_string(), def)
+        })
+        .collect()
+}
+
+/// Creates a lookup map from projection names to their definitions.
+fn create_projection_lookup(
+    projections: &HashMap<yaml_types::ProjectionName, yaml_types::ProjectionDefinition>,
+) -> HashMap<String, &yaml_types::ProjectionDefinition> {
+    projections
+        .iter()
+        .map(|(name, def)| {
+            let s = name.clone().into_inner();
+            (s.as_str().to_string(), def)
+        })
+        .collect()
+}
+
+/// Creates a lookup map from query names to their definitions.
+fn create_query_lookup(
+    queries: &HashMap<yaml_types::QueryName, yaml_types::QueryDefinition>,
+) -> HashMap<String, &yaml_types::QueryDefinition> {
+    queries
+        .iter()
+        .map(|(name, def)| {
+            let s = name.clone().into_inner();
+            (s.as_str().to_string(), def)
+        })
+        .collect()
+}
+
+/// Creates a lookup map from automation names to their definitions.
+fn create_automation_lookup(
+    automations: &HashMap<yaml_types::AutomationName, yaml_types::AutomationDefinition>,
+) -> HashMap<String, &yaml_types::AutomationDefinition> {
+    automations
+        .iter()
+        .map(|(name, def)| {
+            let s = name.clone().into_inner();
+            (s.as_str().to_string(), def)
+        })
+        .collect()
+}
+
+/// Creates a lookup map from entity names to the index of the swimlane they belong to.
+///
+/// Entities referencing a swimlane that is not part of the diagram are omitted.
+fn create_entity_swimlane_index(
+    lookups: &EntityLookups,
+    swimlane_indices: &HashMap<&yaml_types::SwimlaneId, usize>,
+) -> HashMap<String, usize> {
+    let view_swimlanes = lookups
+        .view_lookup
+        .iter()
+        .map(|(name, def)| (name, &def.swimlane));
+    let command_swimlanes = lookups
+        .command_lookup
+        .iter()
+        .map(|(name, def)| (name, &def.swimlane));
+    let event_swimlanes = lookups
+        .event_lookup
+        .iter()
+        .map(|(name, def)| (name, &def.swimlane));
+    let projection_swimlanes = lookups
+        .projection_lookup
+        .iter()
+        .map(|(name, def)| (name, &def.swimlane));
+    let query_swimlanes = lookups
+        .query_lookup
+        .iter()
+        .map(|(name, def)| (name, &def.swimlane));
+    let automation_swimlanes = lookups
+        .automation_lookup
+        .iter()
+        .map(|(name, def)| (name, &def.swimlane));
+
+    view_swimlanes
+        .chain(command_swimlanes)
+        .chain(event_swimlanes)
+        .chain(projection_swimlanes)
+        .chain(query_swimlanes)
+        .chain(automation_swimlanes)
+        .filter_map(|(name, swimlane_id)| {
+            swimlane_indices
+                .get(swimlane_id)
+                .map(|&index| (name.clone(), index))
+        })
+        .collect()
+}
+
+/// Optional content drawn on top of the diagram.
+#[derive(Debug, Clone, Default)]
+pub struct SvgOptions {
+    /// Whether to badge each entity with its owner and add an ownership legend.
+    pub show_owners: bool,
+    /// Whether to badge each slice header with the slice's estimate.
+    pub show_estimates: bool,
+    /// Whether to tint each slice's column by the pattern the slice follows:
+    /// state change, state view, or automation.
+    pub tint_patterns: bool,
+    /// Open or closed state of slice issues, by issue URL; issues not listed
+    /// are drawn without a state.
+    pub issue_states: HashMap<String, IssueState>,
+    /// Whether to draw the errors each command may be rejected with beneath it.
+    pub show_errors: bool,
+    /// Slice whose connections are drawn thicker while every other
+    /// connection fades, giving a slice in focus within its context.
+    pub focus_slice: Option<yaml_types::SliceName>,
+    /// Entities drawn faded, such as those no view or automation leads to.
+    pub dimmed: Vec<String>,
+    /// Entities outlined to stand out, such as the results of a search.
+    pub highlighted: Vec<String>,
+    /// Link target for every entity, in which `{name}` stands for the
+    /// entity's name, making the diagram a clickable map.
+    pub entity_links: Option<yaml_types::UrlTemplate>,
+    /// Whether to overlay faint gridlines at the model's layout grid, or
+    /// every 8 pixels when it has none.
+    pub show_grid: bool,
+    /// Time and routing work the render may spend before the remaining
+    /// connectors are drawn as straight lines.
+    pub budget: RenderBudget,
+    /// Seed for nudging apart connector badges that would overlap, or `None`
+    /// to derive one from the model so unchanged models render identically.
+    pub seed: Option<u64>,
+    /// Budget for searching entity orderings with fewer and shorter
+    /// connectors, or `None` to keep the order connections mention them in.
+    pub optimize: Option<OptimizeBudget>,
+    /// Whether to trade polish for speed, for an overview of a very large
+    /// model: every box is drawn at the standard size with its name cut
+    /// short rather than wrapped to fit, connectors are drawn as straight
+    /// lines without routing, orderings are not optimized, and connectors
+    /// are not checked for collisions.
+    pub fast: bool,
+    /// Whether the diagram is drawn for a dark page, using the model's dark
+    /// canvas background when it sets one.
+    pub dark: bool,
+    /// Where layout, routing, and rendering report how far they have got.
+    pub progress: Progress,
+}
+
+/// Renders an event model diagram to SVG format.
+///
+/// This function takes a constructed diagram and produces the SVG representation.
+pub fn render_to_svg(diagram: &EventModelDiagram) -> Result<String> {
+    render_to_svg_with_cache(
+        diagram,
+        &SvgOptions::default(),
+        &mut RenderCache::new(),
+        &mut Warnings::new(),
+    )
+}
+
+/// Renders an event model diagram to SVG format, reusing entity dimensions and
+/// connector routes calculated by earlier renders sharing the same `cache`.
+///
+/// Callers that render the same model repeatedly (watch mode, several output
+/// formats) should keep one cache alive across calls. Entities left off the
+/// diagram and connections that could not be drawn are reported to `warnings`.
+pub fn render_to_svg_with_cache(
+    diagram: &EventModelDiagram,
+    options: &SvgOptions,
+    cache: &mut RenderCache,
+    warnings: &mut Warnings,
+) -> Result<String> {
+    render_to_svg_traced(diagram, options, cache, warnings).map(|(svg, _)| svg)
+}
+
+/// Renders an event model diagram to SVG format like
+/// [`render_to_svg_with_cache`], also returning where everything was placed
+/// and how each connector was routed.
+pub fn render_to_svg_traced(
+    diagram: &EventModelDiagram,
+    options: &SvgOptions,
+    cache: &mut RenderCache,
+    warnings: &mut Warnings,
+) -> Result<(String, RenderTrace)> {
+    options.progress.start(ProgressStage::Layout);
+    let swimlanes = diagram.swimlanes();
+    let num_swimlanes = swimlanes.len();
+    let slices = diagram.slices();
+    let num_slices = slices.len();
+
+    // First, pre-calculate dimensions for all entities
+    let mut entity_dimensions_map: HashMap<String, EntityDimensions> = HashMap::new();
+    let entity_names = diagram
+        .views()
+        .keys()
+        .map(|name| (name.clone().into_inner(), EntityKind::View))
+        .chain(
+            diagram
+                .commands()
+                .keys()
+                .map(|name| (name.clone().into_inner(), EntityKind::Command)),
+        )
+        .chain(
+            diagram
+                .events()
+                .keys()
+                .map(|name| (name.clone().into_inner(), EntityKind::Event)),
+        )
+        .chain(
+            diagram
+                .projections()
+                .keys()
+                .map(|name| (name.clone().into_inner(), EntityKind::Projection)),
+        )
+        .chain(
+            diagram
+                .queries()
+                .keys()
+                .map(|name| (name.clone().into_inner(), EntityKind::Query)),
+        )
+        .chain(
+            diagram
+                .automations()
+                .keys()
+                .map(|name| (name.clone().into_inner(), EntityKind::Automation)),
+        );
+    let mut uniform_by_kind: HashMap<EntityKind, HashSet<String>> = HashMap::new();
+    for (name, kind) in entity_names {
+        let policy = if options.fast {
+            yaml_types::SizePolicy::Fixed
+        } else {
+            diagram.entity_sizing().policy_for(kind.into())
+        };
+        let dimensions = cache
+            .dimensions
+            .dimensions(name.as_str(), kind, policy)
+            .clone();
+        let name = name.into_inner();
+        if policy == yaml_types::SizePolicy::Uniform {
+            uniform_by_kind
+                .entry(kind)
+                .or_default()
+                .insert(name.clone());
+        }
+        entity_dimensions_map.insert(name, dimensions);
+    }
+    // Uniformly sized entities match the largest entity of their type
+    for names in uniform_by_kind.values() {
+        make_uniform(
+            entity_dimensions_map
+                .iter_mut()
+                .filter(|(name, _)| names.contains(*name))
+                .map(|(_, dimensions)| dimensions),
+        );
+    }
+
+    // Build temporary maps for entity lookups
+    let lookups = EntityLookups {
+        view_lookup: create_view_lookup(diagram.views()),
+        command_lookup: create_command_lookup(diagram.commands()),
+        event_lookup: create_event_lookup(diagram.events()),
+        projection_lookup: create_projection_lookup(diagram.projections()),
+        query_lookup: create_query_lookup(diagram.queries()),
+        automation_lookup: create_automation_lookup(diagram.automations()),
+    };
+
+    // Index swimlanes and entity placement once so later passes avoid linear scans
+    let swimlane_indices: HashMap<&yaml_types::SwimlaneId, usize> = swimlanes
+        .iter()
+        .enumerate()
+        .map(|(index, swimlane)| (&swimlane.id, index))
+        .collect();
+    let entity_swimlane_indices = create_entity_swimlane_index(&lookups, &swimlane_indices);
+    let mut cells = entity_cells(slices, &entity_swimlane_indices);
+
+    // Size each slice to fit its widest cell, and find the swimlanes each
+    // slice occupies from the same cells its entities are placed in
+    let swimlane_ids: Vec<&yaml_types::SwimlaneId> =
+        swimlanes.iter().map(|swimlane| &swimlane.id).collect();
+    let mut slice_required_widths = vec![MIN_SLICE_WIDTH; num_slices];
+    let mut occupied_swimlanes: Vec<HashSet<&yaml_types::SwimlaneId>> =
+        vec![HashSet::new(); num_slices];
+    for (&(slice_index, swimlane_index), entities) in &cells {
+        let total_entity_width: Px = entities
+            .iter()
+            .map(|name| {
+                entity_dimensions_map
+                    .get(name)
+                    .map(|d| d.width)
+                    .unwrap_or(ENTITY_BOX_WIDTH)
+            })
+            .sum();
+        let spacing_width = (entities.len() as u32 + 1) * ENTITY_MARGIN;
+        slice_required_widths[slice_index] =
+            slice_required_widths[slice_index].max(total_entity_width + spacing_width);
+        occupied_swimlanes[slice_index].insert(swimlane_ids[swimlane_index]);
+    }
+    let slice_swimlanes: Vec<HashSet<&yaml_types::SwimlaneId>> = slices
+        .iter()
+        .zip(occupied_swimlanes)
+        .map(|(slice, occupied)| {
+            if slice.swimlanes.is_empty() {
+                occupied
+            } else {
+                slice.swimlanes.iter().collect()
+            }
+        })
+        .collect();
+
+    let mut columns = match diagram.layout() {
+        yaml_types::LayoutStrategy::Standard | yaml_types::LayoutStrategy::Constraints => {
+            SliceColumns::standard(&slice_required_widths)
+        }
+        yaml_types::LayoutStrategy::Compact => {
+            let contexts: Vec<_> = slices.iter().map(|slice| slice.context.as_ref()).collect();
+            SliceColumns::compact(&slice_required_widths, &slice_swimlanes, &contexts)
+        }
+    };
+
+    // The constraints layout widens columns and moves entities as its rules ask
+    let constrained_offsets = match diagram.layout() {
+        yaml_types::LayoutStrategy::Constraints => constrain_layout(
+            diagram,
+            &mut cells,
+            &mut columns,
+            &entity_dimensions_map,
+            warnings,
+        )?,
+        _ => HashMap::new(),
+    };
+
+    // Calculate total width based on actual requirements
+    let total_width = if num_slices > 0 {
+        SWIMLANE_LABEL_WIDTH + columns.widths.iter().sum::<Px>()
+    } else {
+        MIN_WIDTH
+    };
+
+    // Calculate swimlane heights based on the tallest entity in each swimlane
+    let mut swimlane_content_heights: Vec<Px> = vec![Px::ZERO; num_swimlanes];
+    for (entity_name, &swimlane_index) in &entity_swimlane_indices {
+        if let Some(dimensions) = entity_dimensions_map.get(entity_name) {
+            // Account for entity height plus margins, and room for its error
+            // outcomes on both sides so it stays centered
+            let outcomes_height = if options.show_errors {
+                error_outcomes_height(lookups.command_errors(entity_name).len())
+            } else {
+                Px::ZERO
+            };
+            swimlane_content_heights[swimlane_index] = swimlane_content_heights[swimlane_index]
+                .max(dimensions.height + 2 * (ENTITY_MARGIN + outcomes_height));
+        }
+    }
+
+    // Ensure minimum height for each swimlane, shrinking those no slice uses
+    // when unused swimlanes are shaded
+    let shade_unused = diagram.unused_lanes() == yaml_types::UnusedLanes::Shaded;
+    let swimlane_heights: Vec<Px> = swimlanes
+        .iter()
+        .zip(&swimlane_content_heights)
+        .map(|(swimlane, &content_height)| {
+            if shade_unused
+                && !slice_swimlanes
+                    .iter()
+                    .any(|used| used.contains(&swimlane.id))
+            {
+                UNUSED_SWIMLANE_HEIGHT
+            } else {
+                content_height.max(MIN_SWIMLANE_HEIGHT)
+            }
+        })
+        .collect();
+
+    let total_swimlane_height: Px = swimlane_heights.iter().sum();
+    // The workflow description wraps beneath the title, pushing the rest down
+    let description_lines =
+        diagram
+            .header()
+            .description
+            .as_ref()
+            .map_or_else(Vec::new, |description| {
+                wrap_text(
+                    description.clone().into_inner().as_str(),
+                    total_width.saturating_sub(2 * PADDING),
+                    DESCRIPTION_FONT_SIZE,
+                )
+                .0
+            });
+    let header_height =
+        HEADER_HEIGHT + description_lines.len() as u32 * line_height(DESCRIPTION_FONT_SIZE);
+
+    // Bounded contexts are named in a row above the slice headers
+    let contexts = ContextSpan::locate(slices, &columns);
+    let context_band_height = if contexts.is_empty() {
+        Px::ZERO
+    } else {
+        CONTEXT_BAND_HEIGHT
+    };
+    let slice_header = SliceHeaderLayout::layout(slices, &columns, diagram.slice_headers());
+    let swimlanes_start_y = header_height + context_band_height + slice_header.height;
+    let total_height = swimlanes_start_y + total_swimlane_height + PADDING;
+
+    // The ownership legend sits below the swimlanes and grows the canvas
+    let owner_legend = options
+        .show_owners
+        .then(|| OwnerLegend::layout(diagram.ownership(), total_width));
+    let canvas_height = total_height
+        + owner_legend
+            .as_ref()
+            .map_or(Px::ZERO, |legend| legend.height() + PADDING);
+
+    let mut svg_content = String::new();
+
+    // SVG header
+    svg_content.push_str(&format!(
+        r##"<?xml version="1.0" encoding="UTF-8"?>
+<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {} {}">
+  <!-- Connection marker definitions -->
+  <defs>
+{}  </defs>
+  
+{}  
+  <!-- Workflow title -->
+  <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{}" font-weight="normal" fill="{}">
+    {}
+  </text>
+"##,
+        total_width,
+        canvas_height,
+        render_marker_defs(&marker_styles_in_use(
+            slices,
+            &lookups,
+            diagram.connection_markers()
+        )),
+        render_canvas(diagram.canvas(), options.dark, total_width, canvas_height),
+        PADDING,
+        TITLE_Y,
+        TITLE_FONT_SIZE,
+        TEXT_COLOR,
+        diagram.workflow_title().as_str()
+    ));
+    svg_content.push_str(&render_description(&description_lines));
+
+    // Group backgrounds go first so slice dividers and swimlane borders stay visible
+    let groups = GroupSpan::locate(swimlanes, diagram.swimlane_groups());
+    svg_content.push_str(&render_group_backgrounds(
+        &groups,
+        &swimlane_heights,
+        swimlanes_start_y,
+        total_width,
+    ));
+    svg_content.push_str(&render_swimlane_tints(
+        swimlanes,
+        &swimlane_heights,
+        swimlanes_start_y,
+        total_width,
+    ));
+    if shade_unused {
+        svg_content.push_str(&render_unused_lanes(
+            swimlanes,
+            &slice_swimlanes,
+            &columns,
+            &swimlane_heights,
+            swimlanes_start_y,
+        ));
+    }
+
+    svg_content.push_str(&render_context_regions(
+        &contexts,
+        &columns,
+        header_height,
+        total_height - PADDING,
+    ));
+
+    if options.tint_patterns {
+        svg_content.push_str(&render_pattern_tints(
+            slices,
+            diagram.patterns(),
+            &columns,
+            header_height + context_band_height,
+            total_height - PADDING,
+        ));
+    }
+
+    // Render slice headers
+    if !slices.is_empty() {
+        svg_content.push_str(&render_slice_headers(
+            &slice_header,
+            header_height + context_band_height,
+            &columns,
+            SWIMLANE_LABEL_WIDTH,
+            total_width,
+            total_height,
+        ));
+        svg_content.push_str(&render_status_shading(
+            slices,
+            &columns,
+            header_height + context_band_height,
+            slice_header.height,
+            SWIMLANE_LABEL_WIDTH,
+        ));
+        svg_content.push_str(&render_issue_badges(
+            slices,
+            &columns,
+            header_height + context_band_height,
+            SWIMLANE_LABEL_WIDTH,
+            &options.issue_states,
+        ));
+        if options.show_estimates {
+            svg_content.push_str(&render_estimate_badges(
+                slices,
+                &columns,
+                header_height + context_band_height,
+                SWIMLANE_LABEL_WIDTH,
+            ));
+        }
+    }
+
+    // Render swimlanes
+    svg_content.push_str(&render_swimlanes(
+        swimlanes,
+        &groups,
+        &swimlane_heights,
+        swimlanes_start_y,
+        total_width,
+    ));
+
+    // Render entities (views, commands, etc.)
+    let render_ctx = EntityRenderContext {
+        slices,
+        lookups: &lookups,
+        columns: &columns,
+        swimlane_heights: &swimlane_heights,
+        swimlanes_start_y,
+        start_x: SWIMLANE_LABEL_WIDTH,
+        entity_dimensions_map: &entity_dimensions_map,
+        kinds: &entity_dimensions_map
+            .keys()
+            .filter_map(|name| Some((name.clone(), diagram.kind_of(name)?.1)))
+            .collect(),
+        grid: diagram.grid(),
+        offsets: &constrained_offsets,
+    };
+    // Orderings the constraints layout settled on are left as they are, and
+    // a fast render has no time to search for better ones
+    let optimize = options
+        .optimize
+        .filter(|_| !options.fast && diagram.layout() != yaml_types::LayoutStrategy::Constraints);
+    if let Some(budget) = &optimize {
+        let seed = options
+            .seed
+            .unwrap_or_else(|| Jitter::seed_for(&(diagram.workflow_title(), &cells)));
+        // Cells a slice orders itself are kept as the author wrote them
+        let movable = |&(slice_index, _): &(usize, usize)| slices[slice_index].order.is_empty();
+        anneal(
+            &mut cells,
+            budget,
+            options.budget.deadline(),
+            seed,
+            movable,
+            |cells| placement_cost(&render_ctx, cells),
+        )?;
+    }
+    let (entities_svg, entity_positions) = render_entities(&render_ctx, &cells)?;
+    svg_content.push_str(&entities_svg);
+    options.progress.finish(ProgressStage::Layout);
+
+    // Entities no slice references never receive a position and are left out
+    let mut orphaned_entities: Vec<&String> = entity_swimlane_indices
+        .keys()
+        .filter(|name| !entity_positions.contains_key(*name))
+        .collect();
+    orphaned_entities.sort();
+    for name in orphaned_entities {
+        warnings.warn(
+            WarningKind::OrphanedEntity,
+            format!("'{name}' is not referenced by any slice and was left off the diagram"),
+        );
+    }
+
+    // Render connections (arrows between entities)
+    let waypoint_grid = WaypointGrid {
+        slice_indices: slices
+            .iter()
+            .enumerate()
+            .map(|(index, slice)| (&slice.name, index))
+            .collect(),
+        swimlane_indices: &swimlane_indices,
+        columns: &columns,
+        swimlane_heights: &swimlane_heights,
+        start_x: SWIMLANE_LABEL_WIDTH,
+        start_y: swimlanes_start_y,
+    };
+    let (connections_svg, routes) = render_connections(
+        diagram,
+        &lookups,
+        &entity_positions,
+        &waypoint_grid,
+        &mut cache.routes,
+        options,
+        warnings,
+    );
+    svg_content.push_str(&connections_svg);
+    options.progress.start(ProgressStage::Rendering);
+
+    svg_content.push_str(&render_implementation_links(diagram, &entity_positions));
+
+    svg_content.push_str(&render_command_access(&lookups, &entity_positions));
+    svg_content.push_str(&render_volumes(&lookups, &entity_positions));
+    svg_content.push_str(&render_pii_shields(&lookups, &entity_positions));
+    svg_content.push_str(&render_entity_hotspots(
+        &diagram.hotspots().by_entity(),
+        &entity_positions,
+    ));
+    if options.show_errors {
+        svg_content.push_str(&render_error_outcomes(&lookups, &entity_positions));
+    }
+    svg_content.push_str(&render_dimmed(&options.dimmed, &entity_positions));
+    svg_content.push_str(&render_highlighted(&options.highlighted, &entity_positions));
+    if let Some(template) = &options.entity_links {
+        svg_content.push_str(&render_entity_links(template, &entity_positions));
+    }
+
+    if let Some(legend) = &owner_legend {
+        svg_content.push_str(&render_owner_badges(&entity_positions, legend));
+        svg_content.push_str(&legend.render(total_height));
+    }
+
+    if options.show_grid {
+        svg_content.push_str(&render_debug_grid(
+            diagram.grid().unwrap_or(DEBUG_GRID_DEFAULT_SPACING),
+            total_width,
+            canvas_height,
+        ));
+    }
+
+    // Close SVG
+    svg_content.push_str("</svg>");
+    options.progress.finish(ProgressStage::Rendering);
+
+    let layout = LayoutTrace {
+        width: total_width,
+        height: canvas_height,
+        swimlanes: swimlanes
+            .iter()
+            .zip(&swimlane_heights)
+            .scan(swimlanes_start_y, |y, (swimlane, &height)| {
+                let top = *y;
+                *y += height;
+                Some(SwimlaneTrace {
+                    id: swimlane.id.clone().into_inner().into_inner(),
+                    y: top,
+                    height,
+                })
+            })
+            .collect(),
+        columns: columns
+            .widths
+            .iter()
+            .enumerate()
+            .scan(SWIMLANE_LABEL_WIDTH, |x, (column, &width)| {
+                let left = *x;
+                *x += width;
+                Some(ColumnTrace {
+                    slices: columns
+                        .slices_in(column)
+                        .map(|slice| slices[slice].name.clone().into_inner().into_inner())
+                        .collect(),
+                    x: left,
+                    width,
+                })
+            })
+            .collect(),
+        entities: trace_entities(slices, &entity_positions),
+    };
+
+    Ok((svg_content, RenderTrace { layout, routes }))
+}
+
+/// Lists every drawn entity instance, ordered by name and then slice.
+fn trace_entities(
+    slices: &[yaml_types::Slice],
+    entity_positions: &EntityPositions,
+) -> Vec<EntityTrace> {
+    let mut entities: Vec<EntityTrace> = entity_positions
+        .iter()
+        .flat_map(|(name, positions)| {
+            positions.iter().map(move |position| EntityTrace {
+                name: name.clone(),
+                slice: slices[position.slice_index]
+                    .name
+                    .clone()
+                    .into_inner()
+                    .into_inner(),
+                x: position.x,
+                y: position.y,
+                width: position.width,
+                height: position.height,
+            })
+        })
+        .collect();
+    entities.sort_by(|a, b| (&a.name, &a.slice).cmp(&(&b.name, &b.slice)));
+    entities
+}
+
+/// Renders the canvas background, unless it is transparent, and the
+/// watermark, if any, centered along the canvas diagonal.
+fn render_canvas(canvas: &yaml_types::Canvas, dark: bool, width: Px, height: Px) -> String {
+    let mut svg = String::from("  <!-- Canvas background -->\n");
+    let fill = match canvas.background_for(dark) {
+        None => Some(BACKGROUND_COLOR),
+        Some(yaml_types::CanvasBackground::Color(color)) => Some(color.as_ref()),
+        Some(yaml_types::CanvasBackground::Transparent) => None,
+    };
+    if let Some(fill) = fill {
+        svg.push_str(&format!(
+            r#"  <rect x="0" y="0" width="{width}" height="{height}" fill="{fill}" stroke="none"/>
+"#
+        ));
+    }
+    if let Some(watermark) = &canvas.watermark {
+        let text = watermark.as_str();
+        let (w, h) = (f64::from(width.get()), f64::from(height.get()));
+        let diagonal = w.hypot(h);
+        // Fill most of the diagonal, without growing taller than half the canvas
+        let chars = text.chars().count().max(1) as f64;
+        let font_size = (diagonal * WATERMARK_SPAN / (chars * 0.6))
+            .min(h / 2.0)
+            .max(1.0);
+        let angle = -h.atan2(w).to_degrees();
+        svg.push_str(&format!(
+            r#"  <text class="watermark" x="{}" y="{}" font-family="Arial, sans-serif" font-size="{:.0}" font-weight="bold" fill="{WATERMARK_COLOR}" fill-opacity="{WATERMARK_OPACITY}" text-anchor="middle" dominant-baseline="central" transform="rotate({angle:.1} {} {})">{}</text>
+"#,
+            width / 2,
+            height / 2,
+            font_size,
+            width / 2,
+            height / 2,
+            escape_xml(text)
+        ));
+    }
+    svg
+}
+
+/// Renders the wrapped workflow description beneath the title.
+fn render_description(lines: &[String]) -> String {
+    let Some((first, rest)) = lines.split_first() else {
+        return String::new();
+    };
+    let line_height = line_height(DESCRIPTION_FONT_SIZE);
+    let mut svg = format!(
+        r#"  <text x="{PADDING}" y="{}" font-family="Arial, sans-serif" font-size="{DESCRIPTION_FONT_SIZE}" fill="{DESCRIPTION_COLOR}">
+    <tspan x="{PADDING}" dy="0">{}</tspan>
+"#,
+        TITLE_Y + line_height,
+        escape_xml(first)
+    );
+    for line in rest {
+        svg.push_str(&format!(
+            r#"    <tspan x="{PADDING}" dy="{line_height}">{}</tspan>
+"#,
+            escape_xml(line)
+        ));
+    }
+    svg.push_str("  </text>\n");
+    svg
+}
+
+/// Draws faint gridlines every `spacing` pixels across the whole canvas, for
+/// checking what the layout grid aligns.
+fn render_debug_grid(spacing: Px, width: Px, height: Px) -> String {
+    let mut svg = format!(
+        "  <!-- Debug grid -->\n  <g class=\"debug-grid\" stroke=\"{DEBUG_GRID_COLOR}\" stroke-width=\"0.5\" stroke-opacity=\"{DEBUG_GRID_OPACITY}\">\n"
+    );
+    let mut x = spacing;
+    while x < width {
+        svg.push_str(&format!(
+            r#"    <line x1="{x}" y1="0" x2="{x}" y2="{height}"/>
+"#
+        ));
+        x += spacing;
+    }
+    let mut y = spacing;
+    while y < height {
+        svg.push_str(&format!(
+            r#"    <line x1="0" y1="{y}" x2="{width}" y2="{y}"/>
+"#
+        ));
+        y += spacing;
+    }
+    svg.push_str("  </g>\n");
+    svg
+}
+
+/// The owners shown in the legend, their colors, and where each entry goes.
+struct OwnerLegend<'a> {
+    ownership: &'a OwnershipReport,
+    /// Legend label, color, and position (x, row) of each entry.
+    entries: Vec<(String, &'static str, Px, u32)>,
+    rows: u32,
+}
+
+impl<'a> OwnerLegend<'a> {
+    /// Assigns each owner a color and flows the entries into rows that fit
+    /// within `total_width`.
+    fn layout(ownership: &'a OwnershipReport, total_width: Px) -> Self {
+        let mut labeled: Vec<(String, &'static str)> = ownership
+            .owners()
+            .iter()
+            .enumerate()
+            .map(|(index, (owner, entities))| {
+                (
+                    format!("{owner} ({})", entities.len()),
+                    OWNER_COLORS[index % OWNER_COLORS.len()],
+                )
+            })
+            .collect();
+        if !ownership.unowned().is_empty() {
+            labeled.push((
+                format!("Missing owner ({})", ownership.unowned().len()),
+                MISSING_OWNER_COLOR,
+            ));
+        }
+
+        let start_x = PADDING + "Owners:".len() as u32 * OWNER_LEGEND_CHAR_WIDTH + PADDING;
+        let mut entries = Vec::with_capacity(labeled.len());
+        let (mut x, mut row) = (start_x, 0);
+        for (label, color) in labeled {
+            let width = OWNER_LEGEND_SWATCH_SIZE
+                + Px::new(6)
+                + label.len() as u32 * OWNER_LEGEND_CHAR_WIDTH;
+            if x > start_x && x + width > total_width.saturating_sub(PADDING) {
+                x = start_x;
+                row += 1;
+            }
+            entries.push((label, color, x, row));
+            x += width + PADDING;
+        }
+
+        Self {
+            ownership,
+            entries,
+            rows: row + 1,
+        }
+    }
+
+    /// Returns the height the legend occupies.
+    fn height(&self) -> Px {
+        self.rows * OWNER_LEGEND_ROW_HEIGHT
+    }
+
+    /// Returns the badge color for an owner.
+    fn color_of(&self, owner: &str) -> &'static str {
+        self.ownership
+            .owners()
+            .keys()
+            .position(|name| name == owner)
+            .map_or(MISSING_OWNER_COLOR, |index| {
+                OWNER_COLORS[index % OWNER_COLORS.len()]
+            })
+    }
+
+    /// Renders the legend starting at `top`.
+    fn render(&self, top: Px) -> String {
+        let mut svg = String::from("  <!-- Ownership legend -->\n");
+        svg.push_str(&format!(
+            r#"  <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{}" fill="{}">Owners:</text>
+"#,
+            PADDING,
+            top + OWNER_LEGEND_SWATCH_SIZE,
+            OWNER_LEGEND_FONT_SIZE,
+            TEXT_COLOR
+        ));
+        for (label, color, x, row) in &self.entries {
+            let y = top + *row * OWNER_LEGEND_ROW_HEIGHT;
+            svg.push_str(&format!(
+                r#"  <rect x="{}" y="{}" width="{}" height="{}" rx="2" fill="{}"/>
+  <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{}" fill="{}">{}</text>
+"#,
+                x,
+                y + Px::new(1),
+                OWNER_LEGEND_SWATCH_SIZE,
+                OWNER_LEGEND_SWATCH_SIZE,
+                color,
+                *x + OWNER_LEGEND_SWATCH_SIZE + Px::new(6),
+                y + OWNER_LEGEND_SWATCH_SIZE,
+                OWNER_LEGEND_FONT_SIZE,
+                TEXT_COLOR,
+                escape_xml(label)
+            ));
+        }
+        svg
+    }
+}
+
+/// Draws a badge across the bottom-left corner of each command stating
+/// whether it is idempotent and which roles may issue it.
+fn render_command_access(lookups: &EntityLookups, entity_positions: &EntityPositions) -> String {
+    let mut names: Vec<&String> = entity_positions.keys().collect();
+    names.sort();
+
+    let mut svg = String::new();
+    for name in names {
+        let Some(label) = lookups.command_access(name) else {
+            continue;
+        };
+        let width = label.chars().count() as u32 * ACCESS_BADGE_CHAR_WIDTH + Px::new(8);
+        for position in &entity_positions[name] {
+            let x = position.x;
+            let y = (position.y + position.height).saturating_sub(ACCESS_BADGE_HEIGHT / 2);
+            svg.push_str(&format!(
+                r#"  <rect x="{x}" y="{y}" width="{width}" height="{ACCESS_BADGE_HEIGHT}" rx="{}" fill="{BACKGROUND_COLOR}" stroke="{COMMAND_BACKGROUND_COLOR}" stroke-width="1"/>
+  <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{ACCESS_BADGE_FONT_SIZE}" fill="{TEXT_COLOR}" text-anchor="middle">{}</text>
+"#,
+                ACCESS_BADGE_HEIGHT / 2,
+                x + width / 2,
+                y + ACCESS_BADGE_HEIGHT / 2 + ACCESS_BADGE_FONT_SIZE / 2 - Px::new(1),
+                escape_xml(&label)
+            ));
+        }
+    }
+    if svg.is_empty() {
+        svg
+    } else {
+        format!("  <!-- Command access -->\n{svg}")
+    }
+}
+
+/// Draws a small label across the top-left corner of each event and command
+/// stating its expected volume, e.g. `10k/day`.
+fn render_volumes(lookups: &EntityLookups, entity_positions: &EntityPositions) -> String {
+    let mut names: Vec<&String> = entity_positions.keys().collect();
+    names.sort();
+
+    let mut svg = String::new();
+    for name in names {
+        let Some(volume) = lookups.volume(name) else {
+            continue;
+        };
+        let label = volume.to_string();
+        let width = label.chars().count() as u32 * VOLUME_BADGE_CHAR_WIDTH + Px::new(8);
+        for position in &entity_positions[name] {
+            let x = position.x;
+            let y = position.y.saturating_sub(VOLUME_BADGE_HEIGHT / 2);
+            svg.push_str(&format!(
+                r#"  <rect x="{x}" y="{y}" width="{width}" height="{VOLUME_BADGE_HEIGHT}" rx="2" fill="{VOLUME_BADGE_COLOR}"/>
+  <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{VOLUME_BADGE_FONT_SIZE}" fill="white" text-anchor="middle">{}</text>
+"#,
+                x + width / 2,
+                y + VOLUME_BADGE_HEIGHT / 2 + VOLUME_BADGE_FONT_SIZE / 2 - Px::new(1),
+                escape_xml(&label)
+            ));
+        }
+    }
+    if svg.is_empty() {
+        svg
+    } else {
+        format!("  <!-- Volumes -->\n{svg}")
+    }
+}
+
+/// Draws a shield in the top-left of each command and event whose data
+/// includes fields tagged as PII.
+fn render_pii_shields(lookups: &EntityLookups, entity_positions: &EntityPositions) -> String {
+    let mut names: Vec<&String> = entity_positions.keys().collect();
+    names.sort();
+
+    let mut svg = String::new();
+    for name in names.into_iter().filter(|name| lookups.carries_pii(name)) {
+        for position in &entity_positions[name] {
+            let x = position.x + LINK_MARKER_INSET;
+            let y = position.y + VOLUME_BADGE_HEIGHT / 2 + LINK_MARKER_INSET;
+            let (mid, right, bottom) = (
+                x + PII_SHIELD_WIDTH / 2,
+                x + PII_SHIELD_WIDTH,
+                y + PII_SHIELD_HEIGHT,
+            );
+            svg.push_str(&format!(
+                r#"  <path class="pii" d="M {x} {y} L {right} {y} L {right} {} Q {right} {} {mid} {bottom} Q {x} {} {x} {} Z" fill="{PII_SHIELD_COLOR}" stroke="white" stroke-width="1"><title>Carries PII</title></path>
+"#,
+                y + PII_SHIELD_HEIGHT / 2,
+                bottom - Px::new(2),
+                bottom - Px::new(2),
+                y + PII_SHIELD_HEIGHT / 2,
+            ));
+        }
+    }
+    if svg.is_empty() {
+        svg
+    } else {
+        format!("  <!-- PII -->\n{svg}")
+    }
+}
+
+/// Draws a warning triangle in the bottom-right corner of each entity with
+/// a hotspot, titled with the risk raised.
+fn render_entity_hotspots(
+    hotspots: &HashMap<&str, &str>,
+    entity_positions: &EntityPositions,
+) -> String {
+    let mut names: Vec<&String> = entity_positions
+        .keys()
+        .filter(|name| hotspots.contains_key(name.as_str()))
+        .collect();
+    names.sort();
+
+    let mut svg = String::new();
+    for name in names {
+        for position in &entity_positions[name] {
+            let center = Point::new(
+                position.x + position.width - HOTSPOT_INSET - HOTSPOT_SIZE / 2,
+                position.y + position.height - HOTSPOT_INSET - HOTSPOT_SIZE / 2,
+            );
+            svg.push_str(&render_hotspot(center, hotspots[name.as_str()]));
+        }
+    }
+    if svg.is_empty() {
+        svg
+    } else {
+        format!("  <!-- Hotspots -->\n{svg}")
+    }
+}
+
+/// Renders a warning triangle centered on `center`, titled with `reason`.
+fn render_hotspot(center: Point, reason: &str) -> String {
+    let half = HOTSPOT_SIZE / 2;
+    let (top, bottom) = (center.y - half, center.y + half);
+    format!(
+        r#"  <g class="hotspot">
+    <title>{}</title>
+    <path d="M {} {top} L {} {bottom} L {} {bottom} Z" fill="{HOTSPOT_COLOR}" stroke="white" stroke-width="1"/>
+    <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{HOTSPOT_FONT_SIZE}" font-weight="bold" fill="white" text-anchor="middle">!</text>
+  </g>
+"#,
+        escape_xml(reason),
+        center.x,
+        center.x + half,
+        center.x - half,
+        center.x,
+        bottom - Px::new(2),
+    )
+}
+
+/// Fades entities by veiling each of their instances in the canvas color.
+fn render_dimmed(dimmed: &[String], entity_positions: &EntityPositions) -> String {
+    let mut names: Vec<&String> = dimmed.iter().collect();
+    names.sort();
+    names.dedup();
+
+    let mut svg = String::new();
+    for name in names {
+        for position in entity_positions.get(name).into_iter().flatten() {
+            svg.push_str(&format!(
+                r#"  <rect class="dimmed" x="{}" y="{}" width="{}" height="{}" fill="{BACKGROUND_COLOR}" fill-opacity="{DIMMED_VEIL_OPACITY}"/>
+"#,
+                position.x, position.y, position.width, position.height
+            ));
+        }
+    }
+    if svg.is_empty() {
+        svg
+    } else {
+        format!("  <!-- Dimmed -->\n{svg}")
+    }
+}
+
+/// Outlines every instance of the highlighted entities, just outside their
+/// boxes.
+fn render_highlighted(highlighted: &[String], entity_positions: &EntityPositions) -> String {
+    let mut names: Vec<&String> = highlighted.iter().collect();
+    names.sort();
+    names.dedup();
+
+    let mut svg = String::new();
+    for name in names {
+        for position in entity_positions.get(name).into_iter().flatten() {
+            svg.push_str(&format!(
+                r#"  <rect class="highlighted" x="{}" y="{}" width="{}" height="{}" rx="{HIGHLIGHT_GAP}" fill="none" stroke="{HIGHLIGHT_COLOR}" stroke-width="{HIGHLIGHT_WIDTH}"><title>{}</title></rect>
+"#,
+                position.x.saturating_sub(HIGHLIGHT_GAP),
+                position.y.saturating_sub(HIGHLIGHT_GAP),
+                position.width + HIGHLIGHT_GAP * 2,
+                position.height + HIGHLIGHT_GAP * 2,
+                escape_xml(name)
+            ));
+        }
+    }
+    if svg.is_empty() {
+        svg
+    } else {
+        format!("  <!-- Highlighted -->\n{svg}")
+    }
+}
+
+/// Covers every entity with a transparent link to its target under
+/// `template`, titled with the entity's name. Drawn over everything else on
+/// the entity, so it takes the place of any implementation link.
+fn render_entity_links(
+    template: &yaml_types::UrlTemplate,
+    entity_positions: &EntityPositions,
+) -> String {
+    let mut names: Vec<&String> = entity_positions.keys().collect();
+    names.sort();
+
+    let mut svg = String::from("  <!-- Entity links -->\n");
+    for name in names {
+        let href = template
+            .clone()
+            .into_inner()
+            .as_str()
+            .replace(yaml_types::URL_TEMPLATE_PLACEHOLDER, name);
+        for position in &entity_positions[name] {
+            svg.push_str(&format!(
+                r#"  <a class="entity-link" href="{}">
+    <title>{}</title>
+    <rect x="{}" y="{}" width="{}" height="{}" fill="transparent"/>
+  </a>
+"#,
+                escape_xml(&href),
+                escape_xml(name),
+                position.x,
+                position.y,
+                position.width,
+                position.height
+            ));
+        }
+    }
+    svg
+}
+
+/// Returns the height of a stack of `count` error outcome boxes.
+fn error_outcomes_height(count: usize) -> Px {
+    count as u32 * (ERROR_OUTCOME_HEIGHT + ERROR_OUTCOME_GAP)
+}
+
+/// Draws the errors each command may be rejected with as red-tinted boxes
+/// stacked beneath it, right of the connectors leaving its bottom edge.
+fn render_error_outcomes(lookups: &EntityLookups, entity_positions: &EntityPositions) -> String {
+    let mut names: Vec<&String> = entity_positions.keys().collect();
+    names.sort();
+
+    let mut svg = String::from("  <!-- Error outcomes -->\n");
+    for name in names {
+        let errors = lookups.command_errors(name);
+        for position in &entity_positions[name] {
+            let x = position.x + position.width / 2 + ERROR_OUTCOME_GAP;
+            let mut y = position.y + position.height;
+            for error in &errors {
+                y += ERROR_OUTCOME_GAP;
+                let width = error.chars().count() as u32 * ERROR_OUTCOME_CHAR_WIDTH + Px::new(8);
+                svg.push_str(&format!(
+                    r#"  <rect x="{x}" y="{y}" width="{width}" height="{ERROR_OUTCOME_HEIGHT}" rx="3" fill="{ERROR_OUTCOME_BACKGROUND_COLOR}" stroke="{ERROR_OUTCOME_COLOR}" stroke-width="1"/>
+  <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{ERROR_OUTCOME_FONT_SIZE}" fill="{ERROR_OUTCOME_COLOR}" text-anchor="middle">{}</text>
+"#,
+                    x + width / 2,
+                    y + ERROR_OUTCOME_HEIGHT / 2 + ERROR_OUTCOME_FONT_SIZE / 2 - Px::new(1),
+                    escape_xml(error)
+                ));
+                y += ERROR_OUTCOME_HEIGHT;
+            }
+        }
+    }
+    svg
+}
+
+/// Draws a badge naming the owner above the top-right corner of each owned entity.
+fn render_owner_badges(entity_positions: &EntityPositions, legend: &OwnerLegend) -> String {
+    let mut names: Vec<&String> = entity_positions.keys().collect();
+    names.sort();
+
+    let mut svg = String::from("  <!-- Owner badges -->\n");
+    for name in names {
+        let Some(owner) = legend.ownership.owner_of(name) else {
+            continue;
+        };
+        let width = owner.len() as u32 * OWNER_BADGE_CHAR_WIDTH + Px::new(8);
+        for position in &entity_positions[name] {
+            let x = (position.x + position.width).saturating_sub(width);
+            let y = position.y.saturating_sub(OWNER_BADGE_HEIGHT / 2);
+            svg.push_str(&format!(
+                r#"  <rect x="{}" y="{}" width="{}" height="{}" rx="{}" fill="{}"/>
+  <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{}" fill="white" text-anchor="middle">{}</text>
+"#,
+                x,
+                y,
+                width,
+                OWNER_BADGE_HEIGHT,
+                OWNER_BADGE_HEIGHT / 2,
+                legend.color_of(owner),
+                x + width / 2,
+                y + OWNER_BADGE_HEIGHT / 2 + OWNER_BADGE_FONT_SIZE / 2 - Px::new(1),
+                OWNER_BADGE_FONT_SIZE,
+                escape_xml(owner)
+            ));
+        }
+    }
+    svg
+}
+
+/// Makes each entity with `implemented_by` references clickable.
+///
+/// A transparent overlay on the entity links to its first followable
+/// reference and lists them all in a tooltip; a small marker in the corner
+/// shows the entity is linked. Entities whose references are all Rust paths
+/// only get the tooltip.
+fn render_implementation_links(
+    diagram: &EventModelDiagram,
+    entity_positions: &EntityPositions,
+) -> String {
+    let mut linked: Vec<(String, &[yaml_types::ImplementationLink])> = diagram
+        .commands()
+        .iter()
+        .map(|(name, def)| {
+            (
+                name.clone().into_inner().into_inner(),
+                &def.implemented_by[..],
+            )
+        })
+        .chain(diagram.events().iter().map(|(name, def)| {
+            (
+                name.clone().into_inner().into_inner(),
+                &def.implemented_by[..],
+            )
+        }))
+        .filter(|(name, links)| !links.is_empty() && entity_positions.contains_key(name))
+        .collect();
+    if linked.is_empty() {
+        return String::new();
+    }
+    linked.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut svg = String::from("  <!-- Implementation links -->\n");
+    for (name, links) in linked {
+        let title = links
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let href = links.iter().find_map(|link| link.href(&name));
+        for position in &entity_positions[&name] {
+            let overlay = format!(
+                r#"<title>Implemented by: {}</title>
+    <rect x="{}" y="{}" width="{}" height="{}" fill="transparent"/>
+    <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{}" fill="white" text-anchor="end">&#8599;</text>"#,
+                escape_xml(&title),
+                position.x,
+                position.y,
+                position.width,
+                position.height,
+                position.x + position.width - LINK_MARKER_INSET,
+                position.y + position.height - LINK_MARKER_INSET,
+                LINK_MARKER_FONT_SIZE
+            );
+            svg.push_str(&match &href {
+                Some(href) => format!(
+                    "  <a href=\"{}\">\n    {overlay}\n  </a>\n",
+                    escape_xml(href)
+                ),
+                None => format!("  <g>\n    {overlay}\n  </g>\n"),
+            });
+        }
+    }
+    svg
+}
+
+/// Which column each slice occupies, and how wide each column is.
+struct SliceColumns {
+    /// Column index of each slice.
+    column_of: Vec<usize>,
+    /// Width of each column.
+    widths: Vec<Px>,
+}
+
+impl SliceColumns {
+    /// Gives every slice its own column.
+    fn standard(slice_widths: &[Px]) -> Self {
+        Self {
+            column_of: (0..slice_widths.len()).collect(),
+            widths: slice_widths.to_vec(),
+        }
+    }
+
+    /// Lets a slice share the previous slice's column when no slice already
+    /// in that column has entities in the same swimlanes. Only consecutive
+    /// slices of the same bounded context are packed together, so columns
+    /// still read left to right and never straddle a context boundary.
+    fn compact(
+        slice_widths: &[Px],
+        slice_swimlanes: &[HashSet<&yaml_types::SwimlaneId>],
+        slice_contexts: &[Option<&yaml_types::ContextName>],
+    ) -> Self {
+        let mut columns = Self {
+            column_of: Vec::with_capacity(slice_widths.len()),
+            widths: Vec::new(),
+        };
+        let mut occupied: Vec<HashSet<&yaml_types::SwimlaneId>> = Vec::new();
+        for (slice, (&width, swimlanes)) in slice_widths.iter().zip(slice_swimlanes).enumerate() {
+            let same_context = slice > 0 && slice_contexts[slice] == slice_contexts[slice - 1];
+            match occupied.last_mut() {
+                Some(taken) if same_context && taken.is_disjoint(swimlanes) => {
+                    taken.extend(swimlanes.iter().copied());
+                    let column = columns.widths.len() - 1;
+                    columns.widths[column] = columns.widths[column].max(width);
+                }
+                _ => {
+                    occupied.push(swimlanes.clone());
+                    columns.widths.push(width);
+                }
+            }
+            columns.column_of.push(columns.widths.len() - 1);
+        }
+        columns
+    }
+
+    /// Returns the indices of the slices in a column, in model order.
+    fn slices_in(&self, column: usize) -> impl Iterator<Item = usize> + '_ {
+        self.column_of
+            .iter()
+            .enumerate()
+            .filter(move |&(_, &of)| of == column)
+            .map(|(slice, _)| slice)
+    }
+}
+
+/// A run of consecutive columns whose slices belong to one bounded context.
+struct ContextSpan<'a> {
+    name: &'a yaml_types::ContextName,
+    color: &'static str,
+    /// Index of the run's first column.
+    first: usize,
+    /// Index of the run's last column.
+    last: usize,
+}
+
+impl<'a> ContextSpan<'a> {
+    /// Finds the runs of columns sharing a context. A context keeps one
+    /// color, even when its slices are not all next to each other.
+    fn locate(slices: &'a [yaml_types::Slice], columns: &SliceColumns) -> Vec<Self> {
+        let mut named: Vec<&yaml_types::ContextName> = Vec::new();
+        let mut spans: Vec<Self> = Vec::new();
+        for (slice, &column) in slices.iter().zip(&columns.column_of) {
+            let Some(name) = &slice.context else {
+                continue;
+            };
+            match spans.last_mut() {
+                Some(span) if span.name == name && column <= span.last + 1 => span.last = column,
+                _ => {
+                    let index = named
+                        .iter()
+                        .position(|known| *known == name)
+                        .unwrap_or_else(|| {
+                            named.push(name);
+                            named.len() - 1
+                        });
+                    spans.push(Self {
+                        name,
+                        color: CONTEXT_COLORS[index % CONTEXT_COLORS.len()],
+                        first: column,
+                        last: column,
+                    });
+                }
+            }
+        }
+        spans
+    }
+}
+
+/// Renders a faint region behind the columns of each bounded context,
+/// named in a band running from `top` along the top of the region.
+fn render_context_regions(
+    spans: &[ContextSpan],
+    columns: &SliceColumns,
+    top: Px,
+    bottom: Px,
+) -> String {
+    if spans.is_empty() {
+        return String::new();
+    }
+    let mut svg = String::from("  <!-- Bounded contexts -->\n");
+    for span in spans {
+        let x = SWIMLANE_LABEL_WIDTH + columns.widths[..span.first].iter().sum::<Px>();
+        let width: Px = columns.widths[span.first..=span.last].iter().sum();
+        let label_x = x + width / 2;
+        let label_y = top + CONTEXT_BAND_HEIGHT / 2;
+        svg.push_str(&format!(
+            r#"  <rect x="{x}" y="{top}" width="{width}" height="{}" fill="{color}" fill-opacity="{CONTEXT_REGION_OPACITY}"/>
+  <rect x="{x}" y="{top}" width="{width}" height="{CONTEXT_BAND_HEIGHT}" fill="{color}" fill-opacity="{CONTEXT_BAND_OPACITY}"/>
+  <text x="{label_x}" y="{label_y}" font-family="Arial, sans-serif" font-size="{CONTEXT_LABEL_FONT_SIZE}" font-weight="bold" fill="{TEXT_COLOR}" text-anchor="middle" dominant-baseline="middle">{}</text>
+"#,
+            bottom.saturating_sub(top),
+            escape_xml(span.name.clone().into_inner().as_str()),
+            color = span.color,
+        ));
+    }
+    svg
+}
+
+/// Tints each slice's column, below the context band, by the pattern the
+/// slice follows. Slices sharing a column split it in model order; slices
+/// following no pattern are left untinted.
+fn render_pattern_tints(
+    slices: &[yaml_types::Slice],
+    patterns: &SlicePatterns,
+    columns: &SliceColumns,
+    top: Px,
+    bottom: Px,
+) -> String {
+    let mut svg = String::from("  <!-- Slice patterns -->\n");
+    for (column, &width) in columns.widths.iter().enumerate() {
+        let column_x = SWIMLANE_LABEL_WIDTH + columns.widths[..column].iter().sum::<Px>();
+        let sharing: Vec<&yaml_types::Slice> = slices
+            .iter()
+            .zip(&columns.column_of)
+            .filter(|(_, of)| **of == column)
+            .map(|(slice, _)| slice)
+            .collect();
+        let share = width / sharing.len().max(1) as u32;
+        for (index, slice) in sharing.iter().enumerate() {
+            let name = slice.name.clone().into_inner().into_inner();
+            let Some(pattern) = patterns.of(&name) else {
+                continue;
+            };
+            let color = match pattern {
+                SlicePattern::StateChange => PATTERN_STATE_CHANGE_COLOR,
+                SlicePattern::StateView => PATTERN_STATE_VIEW_COLOR,
+                SlicePattern::Automation => PATTERN_AUTOMATION_COLOR,
+                SlicePattern::Translation => PATTERN_TRANSLATION_COLOR,
+            };
+            svg.push_str(&format!(
+                r#"  <rect class="pattern" x="{}" y="{top}" width="{share}" height="{}" fill="{color}" fill-opacity="{PATTERN_TINT_OPACITY}"><title>{}: {}</title></rect>
+"#,
+                column_x + share * index as u32,
+                bottom.saturating_sub(top),
+                escape_xml(&name),
+                pattern.label()
+            ));
+        }
+    }
+    svg
+}
+
+/// Locates the cells that connection waypoints pass through.
+struct WaypointGrid<'a> {
+    /// Index of each slice by name.
+    slice_indices: HashMap<&'a yaml_types::SliceName, usize>,
+    /// Index of each drawn swimlane by identifier.
+    swimlane_indices: &'a HashMap<&'a yaml_types::SwimlaneId, usize>,
+    columns: &'a SliceColumns,
+    swimlane_heights: &'a [Px],
+    /// Left edge of the first column.
+    start_x: Px,
+    /// Top edge of the first swimlane.
+    start_y: Px,
+}
+
+impl WaypointGrid<'_> {
+    /// Returns the center of the cell where the waypoint's slice column
+    /// crosses its swimlane, if both are on the diagram.
+    fn center(&self, waypoint: &yaml_types::Waypoint) -> Option<Point> {
+        let slice = *self.slice_indices.get(&waypoint.slice)?;
+        let lane = *self.swimlane_indices.get(&waypoint.swimlane)?;
+        let column = self.columns.column_of[slice];
+        let x = self.start_x
+            + self.columns.widths[..column].iter().sum::<Px>()
+            + self.columns.widths[column] / 2;
+        let y = self.start_y
+            + self.swimlane_heights[..lane].iter().sum::<Px>()
+            + self.swimlane_heights[lane] / 2;
+        Some(Point::new(x, y))
+    }
+}
+
+/// The swimlanes a group spans on the diagram.
+struct GroupSpan<'a> {
+    group: &'a yaml_types::SwimlaneGroup,
+    /// Index of the group's first swimlane.
+    first: usize,
+    /// Index of the group's last swimlane.
+    last: usize,
+}
+
+impl<'a> GroupSpan<'a> {
+    /// Finds the swimlanes of each group; a collapsed group spans only the
+    /// swimlane it was collapsed into.
+    fn locate(
+        swimlanes: &NonEmpty<yaml_types::Swimlane>,
+        groups: &'a [yaml_types::SwimlaneGroup],
+    ) -> Vec<Self> {
+        groups
+            .iter()
+            .filter_map(|group| {
+                let indices: Vec<usize> = swimlanes
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, lane)| group.swimlanes.iter().any(|id| id == &lane.id))
+                    .map(|(index, _)| index)
+                    .collect();
+                Some(GroupSpan {
+                    group,
+                    first: *indices.first()?,
+                    last: *indices.last()?,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns whether the group's label column is drawn beside its swimlanes.
+    fn is_labeled(&self) -> bool {
+        !self.group.collapsed
+    }
+
+    /// Returns the top and height of the group's swimlanes.
+    fn bounds(&self, swimlane_heights: &[Px], start_y: Px) -> (Px, Px) {
+        let top = start_y + swimlane_heights[..self.first].iter().sum::<Px>();
+        let height = swimlane_heights[self.first..=self.last].iter().sum();
+        (top, height)
+    }
+}
+
+/// Renders the fills behind themed swimlane groups.
+fn render_group_backgrounds(
+    groups: &[GroupSpan],
+    swimlane_heights: &[Px],
+    start_y: Px,
+    total_width: Px,
+) -> String {
+    let mut svg = String::new();
+    for span in groups {
+        let Some(background) = &span.group.background else {
+            continue;
+        };
+        let (top, height) = span.bounds(swimlane_heights, start_y);
+        svg.push_str(&format!(
+            r#"  <rect x="0" y="{}" width="{}" height="{}" fill="{}"/>
+"#,
+            top,
+            total_width,
+            height,
+            background.as_ref()
+        ));
+    }
+    svg
+}
+
+/// Renders the tinted bands and label accents of swimlanes with a color.
+fn render_swimlane_tints(
+    swimlanes: &NonEmpty<yaml_types::Swimlane>,
+    swimlane_heights: &[Px],
+    start_y: Px,
+    total_width: Px,
+) -> String {
+    let mut svg = String::new();
+    let mut top = start_y;
+    for (swimlane, &height) in swimlanes.iter().zip(swimlane_heights) {
+        if let Some(color) = &swimlane.style.color {
+            let color = color.as_ref();
+            let accent_x = SWIMLANE_LABEL_WIDTH - SWIMLANE_ACCENT_WIDTH;
+            svg.push_str(&format!(
+                r#"  <rect x="0" y="{top}" width="{total_width}" height="{height}" fill="{color}" fill-opacity="{SWIMLANE_TINT_OPACITY}"/>
+  <rect x="{accent_x}" y="{top}" width="{SWIMLANE_ACCENT_WIDTH}" height="{height}" fill="{color}"/>
+"#
+            ));
+        }
+        top += height;
+    }
+    svg
+}
+
+/// Shades each swimlane in the columns whose slices all leave it empty.
+fn render_unused_lanes(
+    swimlanes: &NonEmpty<yaml_types::Swimlane>,
+    slice_swimlanes: &[HashSet<&yaml_types::SwimlaneId>],
+    columns: &SliceColumns,
+    swimlane_heights: &[Px],
+    start_y: Px,
+) -> String {
+    let mut svg = String::from("  <!-- Unused swimlanes -->\n");
+    for (column, &width) in columns.widths.iter().enumerate() {
+        let column_x = SWIMLANE_LABEL_WIDTH + columns.widths[..column].iter().sum::<Px>();
+        let mut top = start_y;
+        for (swimlane, &height) in swimlanes.iter().zip(swimlane_heights) {
+            let used = columns
+                .column_of
+                .iter()
+                .zip(slice_swimlanes)
+                .any(|(&of, used)| of == column && used.contains(&swimlane.id));
+            if !used {
+                svg.push_str(&format!(
+                    r#"  <rect class="unused-lane" x="{column_x}" y="{top}" width="{width}" height="{height}" fill="{UNUSED_LANE_COLOR}"/>
+"#
+                ));
+            }
+            top += height;
+        }
+    }
+    svg
+}
+
+/// Renders a group's rotated label and the bracket joining its swimlanes.
+fn render_group_label(span: &GroupSpan, top: Px, height: Px) -> String {
+    let label_x = GROUP_LABEL_WIDTH / 2;
+    let label_y = top + height / 2;
+    let bracket_x = GROUP_LABEL_WIDTH;
+    let bracket_top = top + GROUP_BRACKET_INSET;
+    let bracket_bottom = top + height - GROUP_BRACKET_INSET;
+    let tick_x = bracket_x + GROUP_BRACKET_TICK;
+    format!(
+        r#"  <text x="{label_x}" y="{label_y}" font-family="Arial, sans-serif" font-size="{GROUP_LABEL_FONT_SIZE}" font-weight="bold" fill="{TEXT_COLOR}" text-anchor="middle" dominant-baseline="middle" transform="rotate(-90 {label_x} {label_y})">
+    {}
+  </text>
+  <path d="M {tick_x} {bracket_top} L {bracket_x} {bracket_top} L {bracket_x} {bracket_bottom} L {tick_x} {bracket_bottom}" fill="none" stroke="{TEXT_COLOR}" stroke-width="1"/>
+"#,
+        escape_xml(span.group.name.clone().into_inner().as_str())
+    )
+}
+
+/// Renders the swimlanes with labels and dividers, and the labels of the
+/// groups they belong to.
+fn render_swimlanes(
+    swimlanes: &NonEmpty<yaml_types::Swimlane>,
+    groups: &[GroupSpan],
+    swimlane_heights: &[Px],
+    start_y: Px,
+    total_width: Px,
+) -> String {
+    let mut svg = String::new();
+
+    svg.push_str("  <!-- Swimlanes -->\n");
+
+    let mut current_y = start_y;
+
+    // Draw top border of first swimlane
+    svg.push_str(&format!(
+        r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="1"/>
+"#,
+        0, current_y, total_width, current_y, SWIMLANE_BORDER_COLOR
+    ));
+
+    for (index, (swimlane, &height)) in swimlanes.iter().zip(swimlane_heights.iter()).enumerate() {
+        let group = groups
+            .iter()
+            .find(|span| span.is_labeled() && (span.first..=span.last).contains(&index));
+
+        // Draw horizontal line between swimlanes (not before the first one),
+        // leaving the label column of a group they share uncrossed
+        if index > 0 {
+            let start_x = match group {
+                Some(span) if span.first < index => GROUP_LABEL_WIDTH,
+                _ => Px::ZERO,
+            };
+            svg.push_str(&format!(
+                r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="1"/>
+"#,
+                start_x, current_y, total_width, current_y, SWIMLANE_BORDER_COLOR
+            ));
+        }
+
+        // Draw rotated label on the left, beside its group's label if it has one
+        if let Some(span) = group.filter(|span| span.first == index) {
+            let (top, height) = span.bounds(swimlane_heights, start_y);
+            svg.push_str(&render_group_label(span, top, height));
+        }
+        let label_x = match group {
+            Some(_) => GROUP_LABEL_WIDTH + (SWIMLANE_LABEL_WIDTH - GROUP_LABEL_WIDTH) / 2,
+            None => SWIMLANE_LABEL_WIDTH / 2,
+        };
+        let label_y = current_y + (height / 2);
+
+        // A shrunk swimlane is too short for its label to run upwards
+        let orientation = if height < MIN_SWIMLANE_HEIGHT {
+            r#" dominant-baseline="middle""#.to_string()
+        } else {
+            format!(r#" transform="rotate(-90 {label_x} {label_y})""#)
+        };
+        svg.push_str(&format!(
+            r#"  <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{}" fill="{}" text-anchor="middle"{}>
+    {}
+  </text>
+"#,
+            label_x,
+            label_y,
+            SWIMLANE_LABEL_FONT_SIZE,
+            TEXT_COLOR,
+            orientation,
+            swimlane_label(swimlane)
+        ));
+
+        // Draw vertical line to separate label area from content area
+        svg.push_str(&format!(
+            r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="1"/>
+"#,
+            SWIMLANE_LABEL_WIDTH,
+            current_y,
+            SWIMLANE_LABEL_WIDTH,
+            current_y + height,
+            SWIMLANE_BORDER_COLOR
+        ));
+
+        current_y += height;
+    }
+
+    // Draw bottom border
+    svg.push_str(&format!(
+        r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="1"/>
+"#,
+        0, current_y, total_width, current_y, SWIMLANE_BORDER_COLOR
+    ));
+
+    svg
+}
+
+/// Badges the top-left corner of each slice column with links to the issues
+/// tracking its slices, colored by their state when it was looked up.
+fn render_issue_badges(
+    slices: &[yaml_types::Slice],
+    columns: &SliceColumns,
+    top: Px,
+    start_x: Px,
+    states: &HashMap<String, IssueState>,
+) -> String {
+    if slices.iter().all(|slice| slice.issue.is_none()) {
+        return String::new();
+    }
+    let mut svg = String::from("  <!-- Issues -->\n");
+    let mut next_x: Vec<Px> = (0..columns.widths.len())
+        .map(|column| start_x + columns.widths[..column].iter().sum::<Px>() + ISSUE_BADGE_INSET)
+        .collect();
+    for (slice, &column) in slices.iter().zip(&columns.column_of) {
+        let Some(issue) = &slice.issue else {
+            continue;
+        };
+        let label = escape_xml(&issue.label());
+        let url = issue.url();
+        let state = states.get(&url).copied();
+        let (color, title) = match state {
+            Some(IssueState::Open) => (ISSUE_OPEN_COLOR, format!("{label}: open")),
+            Some(IssueState::Closed) => (ISSUE_CLOSED_COLOR, format!("{label}: closed")),
+            None => (ISSUE_UNKNOWN_COLOR, label.clone()),
+        };
+        let width = issue.label().chars().count() as u32 * ISSUE_BADGE_CHAR_WIDTH + Px::new(8);
+        let x = next_x[column];
+        let y = top + ISSUE_BADGE_INSET;
+        next_x[column] = x + width + ISSUE_BADGE_INSET;
+        svg.push_str(&format!(
+            r#"  <a class="issue{}" href="{}">
+    <title>{}</title>
+    <rect x="{}" y="{}" width="{}" height="{}" rx="3" fill="{}"/>
+    <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{}" fill="white" text-anchor="middle">{}</text>
+  </a>
+"#,
+            state.map_or(String::new(), |state| format!(" issue-{}", state.as_str())),
+            escape_xml(&url),
+            title,
+            x,
+            y,
+            width,
+            ISSUE_BADGE_HEIGHT,
+            color,
+            x + width / 2,
+            y + ISSUE_BADGE_HEIGHT / 2 + ISSUE_BADGE_FONT_SIZE / 2 - Px::new(1),
+            ISSUE_BADGE_FONT_SIZE,
+            label
+        ));
+    }
+    svg
+}
+
+/// Shades each slice's share of its column header by the slice's status.
+/// Slices sharing a column split its width in model order; slices without
+/// a status are left unshaded.
+fn render_status_shading(
+    slices: &[yaml_types::Slice],
+    columns: &SliceColumns,
+    top: Px,
+    height: Px,
+    start_x: Px,
+) -> String {
+    if slices.iter().all(|slice| slice.status.is_none()) {
+        return String::new();
+    }
+    let mut svg = String::from("  <!-- Slice status -->\n");
+    for (column, &width) in columns.widths.iter().enumerate() {
+        let column_x = start_x + columns.widths[..column].iter().sum::<Px>();
+        let sharing: Vec<&yaml_types::Slice> = slices
+            .iter()
+            .zip(&columns.column_of)
+            .filter(|(_, of)| **of == column)
+            .map(|(slice, _)| slice)
+            .collect();
+        let share = width / sharing.len().max(1) as u32;
+        for (index, slice) in sharing.iter().enumerate() {
+            let Some(status) = slice.status else {
+                continue;
+            };
+            let color = match status {
+                yaml_types::SliceStatus::Done => STATUS_DONE_COLOR,
+                yaml_types::SliceStatus::InProgress => STATUS_IN_PROGRESS_COLOR,
+                yaml_types::SliceStatus::Todo => STATUS_TODO_COLOR,
+            };
+            svg.push_str(&format!(
+                r#"  <rect class="status-{}" x="{}" y="{}" width="{}" height="{}" fill="{}" fill-opacity="{}"><title>{}: {}</title></rect>
+"#,
+                status.as_str().replace('_', "-"),
+                column_x + share * index as u32,
+                top,
+                share,
+                height,
+                color,
+                STATUS_SHADING_OPACITY,
+                escape_xml(slice.name.clone().into_inner().as_str()),
+                status.as_str().replace('_', " ")
+            ));
+        }
+    }
+    svg
+}
+
+/// Badges the top-right corner of each slice column with the estimates of
+/// its slices, joined by `+` when slices share the column.
+fn render_estimate_badges(
+    slices: &[yaml_types::Slice],
+    columns: &SliceColumns,
+    top: Px,
+    start_x: Px,
+) -> String {
+    let mut svg = String::from("  <!-- Estimates -->\n");
+    let mut column_x = start_x;
+    for (column, &width) in columns.widths.iter().enumerate() {
+        let estimates: Vec<String> = slices
+            .iter()
+            .zip(&columns.column_of)
+            .filter(|(_, of)| **of == column)
+            .filter_map(|(slice, _)| slice.estimate.map(|estimate| estimate.to_string()))
+            .collect();
+        if !estimates.is_empty() {
+            let label = estimates.join(" + ");
+            let badge_width = label.chars().count() as u32 * ESTIMATE_BADGE_CHAR_WIDTH + Px::new(8);
+            let x = (column_x + width).saturating_sub(badge_width + ESTIMATE_BADGE_INSET);
+            let y = top + ESTIMATE_BADGE_INSET;
+            svg.push_str(&format!(
+                r#"  <g class="estimate">
+    <rect x="{}" y="{}" width="{}" height="{}" rx="{}" fill="{}"/>
+    <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{}" fill="white" text-anchor="middle">{}</text>
+  </g>
+"#,
+                x,
+                y,
+                badge_width,
+                ESTIMATE_BADGE_HEIGHT,
+                ESTIMATE_BADGE_HEIGHT / 2,
+                ESTIMATE_BADGE_COLOR,
+                x + badge_width / 2,
+                y + ESTIMATE_BADGE_HEIGHT / 2 + ESTIMATE_BADGE_FONT_SIZE / 2 - Px::new(1),
+                ESTIMATE_BADGE_FONT_SIZE,
+                label
+            ));
+        }
+        column_x += width;
+    }
+    svg
+}
+
+/// Returns a swimlane's label text, preceded by its icon if it has one.
+fn swimlane_label(swimlane: &yaml_types::Swimlane) -> String {
+    let name = swimlane.name.clone().into_inner().into_inner();
+    match &swimlane.style.icon {
+        Some(icon) => format!("{} {name}", escape_xml(icon.as_ref())),
+        None => name,
+    }
+}
+
+/// Renders the slice headers with dividers, the header row starting at `top`.
+fn render_slice_headers(
+    header: &SliceHeaderLayout,
+    top: Px,
+    columns: &SliceColumns,
+    start_x: Px,
+    total_width: Px,
+    total_height: Px,
+) -> String {
+    let mut svg = String::new();
+
+    svg.push_str("  <!-- Slice headers -->\n");
+
+    if let Some(background) = &header.background {
+        svg.push_str(&format!(
+            r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>
+"#,
+            start_x,
+            top,
+            total_width - start_x,
+            header.height,
+            background.as_ref()
+        ));
+    }
+
+    let mut current_x = start_x;
+
+    for (index, (&slice_width, title)) in columns.widths.iter().zip(&header.titles).enumerate() {
+        let x_position = current_x;
+
+        // Draw vertical divider through all swimlanes (except before the first slice)
+        if index > 0 {
+            svg.push_str(&format!(
+                r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="1"/>
+"#,
+                x_position,
+                top,
+                x_position,
+                total_height - PADDING,
+                SWIMLANE_BORDER_COLOR
+            ));
+        }
+
+        // Draw slice header text (centered in slice)
+        let text_x = x_position + (slice_width / 2);
+        let center_y = top + header.height / 2;
+
+        // The slice names are already in display format from the YAML
+        match title {
+            HeaderTitle::Lines(lines) if lines.len() == 1 => svg.push_str(&format!(
+                r#"  <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{}" fill="{}" text-anchor="middle">
+    {}
+  </text>
+"#,
+                text_x,
+                center_y + Px::new(3), // +3 for vertical centering
+                SLICE_HEADER_FONT_SIZE,
+                TEXT_COLOR,
+                lines[0]
+            )),
+            HeaderTitle::Lines(lines) => {
+                let first_y = (center_y + Px::new(3))
+                    .saturating_sub((lines.len() as u32 - 1) * SLICE_HEADER_LINE_HEIGHT / 2);
+                svg.push_str(&format!(
+                    r#"  <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{}" fill="{}" text-anchor="middle">
+"#,
+                    text_x, first_y, SLICE_HEADER_FONT_SIZE, TEXT_COLOR
+                ));
+                for (line_index, line) in lines.iter().enumerate() {
+                    let dy = if line_index == 0 {
+                        Px::ZERO
+                    } else {
+                        SLICE_HEADER_LINE_HEIGHT
+                    };
+                    svg.push_str(&format!(
+                        r#"    <tspan x="{text_x}" dy="{dy}">{line}</tspan>
+"#
+                    ));
+                }
+                svg.push_str("  </text>\n");
+            }
+            HeaderTitle::Rotated(title) => svg.push_str(&format!(
+                r#"  <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{}" fill="{}" text-anchor="middle" transform="rotate(-90 {} {})">
+    {}
+  </text>
+"#,
+                text_x + Px::new(3), // +3 to center the rotated baseline
+                center_y,
+                SLICE_HEADER_FONT_SIZE,
+                TEXT_COLOR,
+                text_x + Px::new(3),
+                center_y,
+                title
+            )),
+        }
+
+        current_x += slice_width;
+    }
+
+    // Draw horizontal line below slice headers
+    svg.push_str(&format!(
+        r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="1"/>
+"#,
+        start_x,
+        top + header.height,
+        total_width,
+        top + header.height,
+        SWIMLANE_BORDER_COLOR
+    ));
+
+    svg
+}
+
+/// The titles of the slice header row and the height they need.
+struct SliceHeaderLayout {
+    /// Title of each column.
+    titles: Vec<HeaderTitle>,
+    /// Height of the header row.
+    height: Px,
+    /// Fill behind the header row.
+    background: Option<yaml_types::SvgColor>,
+}
+
+impl SliceHeaderLayout {
+    /// Fits each column's title (its slice names) to the column width and
+    /// grows the row to the tallest title.
+    fn layout(
+        slices: &[yaml_types::Slice],
+        columns: &SliceColumns,
+        style: &yaml_types::SliceHeaderStyle,
+    ) -> Self {
+        let max_lines = style
+            .max_lines
+            .map_or(DEFAULT_SLICE_HEADER_LINES, PositiveInt::value);
+        let titles: Vec<HeaderTitle> = columns
+            .widths
+            .iter()
+            .enumerate()
+            .map(|(column, &width)| {
+                let title = columns
+                    .slices_in(column)
+                    .map(|slice_index| slices[slice_index].name.clone().into_inner().into_inner())
+                    .collect::<Vec<_>>()
+                    .join(" / ");
+                HeaderTitle::fit(
+                    title,
+                    width.saturating_sub(2 * SLICE_HEADER_PADDING),
+                    max_lines,
+                )
+            })
+            .collect();
+        let height = titles.iter().map(HeaderTitle::height).fold(
+            style.height.map_or(DEFAULT_SLICE_HEADER_HEIGHT, |height| {
+                Px::new(height.value())
+            }),
+            Px::max,
+        );
+        Self {
+            titles,
+            height,
+            background: style.background.clone(),
+        }
+    }
+}
+
+/// How a column's title is drawn in the header row.
+enum HeaderTitle {
+    /// Word-wrapped onto lines that fit across the column.
+    Lines(Vec<String>),
+    /// Drawn vertically, for titles that do not fit in the allowed lines.
+    Rotated(String),
+}
+
+impl HeaderTitle {
+    /// Wraps a title to `width`, rotating it if it needs more than
+    /// `max_lines` lines or a single word is wider than the column.
+    fn fit(title: String, width: Px, max_lines: u32) -> Self {
+        let (lines, _, _) = wrap_text(&title, width, SLICE_HEADER_FONT_SIZE);
+        let char_width = char_width(SLICE_HEADER_FONT_SIZE);
+        let fits = lines.len() as u32 <= max_lines
+            && lines
+                .iter()
+                .all(|line| line.len() as u32 * char_width <= width);
+        if fits {
+            Self::Lines(lines)
+        } else {
+            Self::Rotated(title)
+        }
+    }
+
+    /// Returns the header height the title needs.
+    fn height(&self) -> Px {
+        let text_height = match self {
+            Self::Lines(lines) => lines.len() as u32 * SLICE_HEADER_LINE_HEIGHT,
+            Self::Rotated(title) => title.len() as u32 * char_width(SLICE_HEADER_FONT_SIZE),
+        };
+        text_height + 2 * SLICE_HEADER_PADDING
+    }
+}
+
+/// Process an entity reference and add it to the entities_by_slice_and_swimlane map if it's a view, command, event, projection, or query.
+fn process_entity_reference(
+    entity_ref: &yaml_types::EntityReference,
+    slice_index: usize,
+    entity_swimlane_indices: &HashMap<String, usize>,
+    entities_by_slice_and_swimlane: &mut BTreeMap<(usize, usize), Vec<String>>,
+) {
+    let entity_name = extract_entity_name(entity_ref);
+    if let Some(&swimlane_index) = entity_swimlane_indices.get(&entity_name) {
+        entities_by_slice_and_swimlane
+            .entry((slice_index, swimlane_index))
+            .or_default()
+            .push(entity_name);
+    }
+}
+
+/// Entities drawn in each cell, keyed by slice index and then swimlane index,
+/// in the order they are placed from left to right.
+type EntityCells = BTreeMap<(usize, usize), Vec<String>>;
+
+/// Finds which entities each slice draws in each swimlane, in the order the
+/// slice's connections first mention them unless the slice gives its own.
+fn entity_cells(
+    slices: &[yaml_types::Slice],
+    entity_swimlane_indices: &HashMap<String, usize>,
+) -> EntityCells {
+    // Ordered so entities are drawn the same way every render
+    let mut cells: EntityCells = BTreeMap::new();
+
+    // Parse slice connections to find entity positions
+    for (slice_index, slice) in slices.iter().enumerate() {
+        for connection in slice.connections.iter() {
+            // Process both sides of the connection
+            process_entity_reference(
+                &connection.from,
+                slice_index,
+                entity_swimlane_indices,
+                &mut cells,
+            );
+            process_entity_reference(
+                &connection.to,
+                slice_index,
+                entity_swimlane_indices,
+                &mut cells,
+            );
+        }
+    }
+
+    // Remove duplicates while preserving order
+    for (&(slice_index, _), entities) in cells.iter_mut() {
+        let mut seen = std::collections::HashSet::new();
+        entities.retain(|item| seen.insert(item.clone()));
+        order_listed(entities, &slices[slice_index].order);
+    }
+    cells
+}
+
+/// Puts the entities `order` lists in that order, in the places they already
+/// take, leaving the others where they are.
+fn order_listed(entities: &mut [String], order: &[yaml_types::EntityReference]) {
+    let rank = |name: &String| {
+        order
+            .iter()
+            .position(|listed| listed.entity_name() == *name)
+    };
+    let slots: Vec<usize> = (0..entities.len())
+        .filter(|&slot| rank(&entities[slot]).is_some())
+        .collect();
+    let mut listed: Vec<String> = slots.iter().map(|&slot| entities[slot].clone()).collect();
+    listed.sort_by_key(rank);
+    for (slot, name) in slots.into_iter().zip(listed) {
+        entities[slot] = name;
+    }
+}
+
+/// Orders the entities of each cell, widens columns, and solves where each
+/// entity goes as the model's layout constraints ask, returning the left edge
+/// of each entity instance from its column's left edge.
+#[cfg(feature = "constraint-layout")]
+fn constrain_layout(
+    diagram: &EventModelDiagram,
+    cells: &mut EntityCells,
+    columns: &mut SliceColumns,
+    entity_dimensions_map: &HashMap<String, EntityDimensions>,
+    warnings: &mut Warnings,
+) -> Result<HashMap<(String, usize), Px>> {
+    constraints::order_cells(cells, diagram.layout_constraints());
+    let entity_widths = entity_dimensions_map
+        .iter()
+        .map(|(name, dimensions)| (name.clone(), dimensions.width))
+        .collect();
+    let slice_names: Vec<String> = diagram
+        .slices()
+        .iter()
+        .map(|slice| slice.name.clone().into_inner().into_inner())
+        .collect();
+    let solved = constraints::solve(
+        cells,
+        &slice_names,
+        &columns.column_of,
+        &columns.widths,
+        &entity_widths,
+        diagram.layout_constraints(),
+        ENTITY_MARGIN,
+    )
+    .map_err(super::DiagramError::SvgError)?;
+    for unmet in solved.unmet {
+        warnings.warn(WarningKind::UnmetLayoutConstraint, unmet);
+    }
+    columns.widths = solved.widths;
+    Ok(solved.offsets)
+}
+
+/// Leaves the layout as it is; without the `constraint-layout` feature, models
+/// asking for the constraints layout are rejected before rendering.
+#[cfg(not(feature = "constraint-layout"))]
+fn constrain_layout(
+    _diagram: &EventModelDiagram,
+    _cells: &mut EntityCells,
+    _columns: &mut SliceColumns,
+    _entity_dimensions_map: &HashMap<String, EntityDimensions>,
+    _warnings: &mut Warnings,
+) -> Result<HashMap<(String, usize), Px>> {
+    Ok(HashMap::new())
+}
+
+/// Places the entities of every cell side by side within their slice column,
+/// centered vertically in their swimlane.
+fn place_entities(ctx: &EntityRenderContext, cells: &EntityCells) -> Result<EntityPositions> {
+    // Calculate swimlane Y positions, indexed the same as the swimlanes
+    let mut swimlane_y_positions = Vec::with_capacity(ctx.swimlane_heights.len());
+    let mut current_y = ctx.swimlanes_start_y;
+    for &height in ctx.swimlane_heights {
+        swimlane_y_positions.push(current_y);
+        current_y += height;
+    }
+
+    // Calculate column X positions using the pre-calculated widths
+    let mut column_x_positions = Vec::new();
+    let mut current_x = ctx.start_x;
+    for &width in &ctx.columns.widths {
+        column_x_positions.push(current_x);
+        current_x += width;
+    }
+
+    let mut entity_positions: EntityPositions = HashMap::new();
+    for (&(slice_index, swimlane_index), entity_names) in cells {
+        let swimlane_y = swimlane_y_positions[swimlane_index];
+        let swimlane_height = ctx.swimlane_heights[swimlane_index];
+        let column = ctx.columns.column_of[slice_index];
+        let slice_x = column_x_positions[column];
+        let slice_width = ctx.columns.widths[column];
+        let num_entities = entity_names.len();
+
+        // Position entities horizontally within the slice
+        // Since we calculated slice width to fit all entities, we know they will fit
+        let mut cumulative_width = ENTITY_MARGIN;
+        for entity_name in entity_names {
+            // Get entity dimensions
+            let dimensions = ctx.entity_dimensions_map.get(entity_name).ok_or_else(|| {
+                InvariantError::new(format!("no dimensions calculated for '{entity_name}'"))
+            })?;
+
+            // Calculate entity position - entities are evenly spaced with proper margins
+            let offset = ctx.offsets.get(&(entity_name.clone(), slice_index));
+            let entity_x = snap(
+                if let Some(&offset) = offset {
+                    // Solved by the constraints layout
+                    slice_x + offset
+                } else if num_entities == 1 {
+                    // Center single entity
+                    slice_x + (slice_width - dimensions.width) / 2
+                } else {
+                    // Multiple entities - place after the cumulative width of previous entities
+                    slice_x + cumulative_width
+                },
+                ctx.grid,
+            );
+            cumulative_width = entity_x - slice_x + dimensions.width + ENTITY_MARGIN;
+
+            // Center entity vertically in swimlane
+            let entity_y = snap(
+                swimlane_y + (swimlane_height - dimensions.height) / 2,
+                ctx.grid,
+            );
+
+            // Store entity position with slice index to handle multiple instances
+            entity_positions
+                .entry(entity_name.clone())
+                .or_default()
+                .push(EntityPosition {
+                    x: entity_x,
+                    y: entity_y,
+                    width: dimensions.width,
+                    height: dimensions.height,
+                    slice_index,
+                });
+        }
+    }
+    Ok(entity_positions)
+}
+
+/// Estimates how tangled the connectors of a placement would be, drawing
+/// each as a straight line between entity centers: the total length, with
+/// each crossing counted as [`CROSSING_COST`] more.
+fn placement_cost(ctx: &EntityRenderContext, cells: &EntityCells) -> Result<u64> {
+    let positions = place_entities(ctx, cells)?;
+    let center = |position: &EntityPosition| {
+        Point::new(
+            position.x + position.width / 2,
+            position.y + position.height / 2,
+        )
+    };
+    let mut lines: Vec<(Point, Point)> = Vec::new();
+    for (slice_index, slice) in ctx.slices.iter().enumerate() {
+        for connection in slice.connections.iter() {
+            let from = extract_entity_name(&connection.from);
+            let to = extract_entity_name(&connection.to);
+            if let (Some(from), Some(to)) = (
+                find_entity_position(&from, slice_index, &positions),
+                find_entity_position(&to, slice_index, &positions),
+            ) {
+                lines.push((center(from), center(to)));
+            }
+        }
+    }
+
+    let mut cost: u64 = lines
+        .iter()
+        .map(|(from, to)| u64::from(from.manhattan_distance(to).get()))
+        .sum();
+    for (i, &(a, b)) in lines.iter().enumerate() {
+        for &(c, d) in &lines[i + 1..] {
+            if segments_cross(a, b, c, d) {
+                cost += CROSSING_COST;
+            }
+        }
+    }
+    Ok(cost)
+}
+
+/// Renders all entities (views, commands, events, etc.) in their respective positions.
+/// Returns the SVG string and a map of entity names to the positions of each of their instances.
+fn render_entities(
+    ctx: &EntityRenderContext,
+    cells: &EntityCells,
+) -> Result<(String, EntityPositions)> {
+    let mut svg = String::new();
+    let entity_positions = place_entities(ctx, cells)?;
+
+    svg.push_str("  <!-- Entities -->\n");
+
+    for (&(slice_index, swimlane_index), entity_names) in cells {
+        let column = ctx.columns.column_of[slice_index];
+
+        // In a shared column, label each slice's entities with the slice name
+        if ctx.columns.slices_in(column).nth(1).is_some() {
+            let swimlane_y =
+                ctx.swimlanes_start_y + ctx.swimlane_heights[..swimlane_index].iter().sum::<Px>();
+            let slice_x = ctx.start_x + ctx.columns.widths[..column].iter().sum::<Px>();
+            svg.push_str(&format!(
+                r#"  <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{}" fill="{}">{}</text>
+"#,
+                slice_x + SHARED_COLUMN_LABEL_INSET,
+                swimlane_y + SHARED_COLUMN_LABEL_INSET + SHARED_COLUMN_LABEL_FONT_SIZE,
+                SHARED_COLUMN_LABEL_FONT_SIZE,
+                SHARED_COLUMN_LABEL_COLOR,
+                ctx.slices[slice_index].name.clone().into_inner().as_str()
+            ));
+        }
+
+        for entity_name in entity_names {
+            let Some(&EntityPosition {
+                x: entity_x,
+                y: entity_y,
+                ..
+            }) = entity_positions
+                .get(entity_name)
+                .and_then(|positions| positions.iter().find(|p| p.slice_index == slice_index))
+            else {
+                continue;
+            };
+            let dimensions = ctx.entity_dimensions_map.get(entity_name).ok_or_else(|| {
+                InvariantError::new(format!("no dimensions calculated for '{entity_name}'"))
+            })?;
+
+            // Determine entity type and render appropriate box
+            let lookups = ctx.lookups;
+            if let Some(extension) = ctx.kinds.get(entity_name) {
+                svg.push_str(&render_kind(entity_x, entity_y, dimensions, extension));
+            } else if lookups.view_lookup.contains_key(entity_name) {
+                svg.push_str(&render_view_box(entity_x, entity_y, dimensions));
+            } else if lookups.command_lookup.contains_key(entity_name) {
+                svg.push_str(&render_command_box(entity_x, entity_y, dimensions));
+            } else if lookups.event_lookup.contains_key(entity_name) {
+                svg.push_str(&render_event_box(entity_x, entity_y, dimensions));
+            } else if lookups.projection_lookup.contains_key(entity_name) {
+                svg.push_str(&render_projection_box(entity_x, entity_y, dimensions));
+            } else if lookups.query_lookup.contains_key(entity_name) {
+                svg.push_str(&render_query_box(entity_x, entity_y, dimensions));
+            } else if lookups.automation_lookup.contains_key(entity_name) {
+                svg.push_str(&render_automation(
+                    entity_x,
+                    entity_y,
+                    dimensions,
+                    AUTOMATION_ICON,
+                ));
+            }
+        }
+    }
+
+    Ok((svg, entity_positions))
+}
+
+/// A connection whose endpoints were found on the diagram.
+struct ResolvedConnection<'a> {
+    name: String,
+    slice: &'a yaml_types::SliceName,
+    self_loop: bool,
+    from: &'a EntityPosition,
+    to: &'a EntityPosition,
+    via: Vec<Point>,
+    marker: yaml_types::MarkerStyle,
+    bidirectional: bool,
+    consistency: Option<yaml_types::Consistency>,
+    hotspot: Option<&'a yaml_types::Hotspot>,
+}
+
+impl ResolvedConnection<'_> {
+    /// Returns whether both connections would be drawn as the same connector.
+    fn coincides_with(&self, other: &Self) -> bool {
+        self.from.to_rectangle() == other.from.to_rectangle()
+            && self.to.to_rectangle() == other.to.to_rectangle()
+            && self.via == other.via
+            && self.marker == other.marker
+            && self.bidirectional == other.bidirectional
+            && self.consistency == other.consistency
+    }
+}
+
+/// How prominently a connector is drawn when a slice is in focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Emphasis {
+    /// No slice is in focus.
+    Normal,
+    /// The connector belongs to the slice in focus.
+    Focused,
+    /// The connector belongs only to other slices.
+    Faded,
+}
+
+impl Emphasis {
+    /// Returns the emphasis of a connector standing for `group`.
+    fn of(group: &[ResolvedConnection], focus: Option<&yaml_types::SliceName>) -> Self {
+        match focus {
+            None => Self::Normal,
+            Some(focus) if group.iter().any(|connection| connection.slice == focus) => {
+                Self::Focused
+            }
+            Some(_) => Self::Faded,
+        }
+    }
+
+    /// Returns the stroke width of the connector.
+    fn stroke_width(self) -> Px {
+        match self {
+            Self::Focused => FOCUSED_CONNECTOR_WIDTH,
+            Self::Normal | Self::Faded => CONNECTOR_WIDTH,
+        }
+    }
+
+    /// Returns the opacity attribute for the connector, if it is faded.
+    fn opacity_attribute(self) -> String {
+        match self {
+            Self::Faded => format!(r#" opacity="{FADED_CONNECTOR_OPACITY}""#),
+            Self::Normal | Self::Focused => String::new(),
+        }
+    }
+}
+
+/// Renders connection arrows between entities based on slice definitions,
+/// emphasizing those of the `focus` slice if one is given.
+fn render_connections(
+    diagram: &EventModelDiagram,
+    lookups: &EntityLookups,
+    entity_positions: &EntityPositions,
+    waypoint_grid: &WaypointGrid,
+    route_cache: &mut RouteCache,
+    options: &SvgOptions,
+    warnings: &mut Warnings,
+) -> (String, Vec<RouteTrace>) {
+    let focus = options.focus_slice.as_ref();
+    let budget = options.budget;
+    let mut svg = String::new();
+    let mut traces = Vec::new();
+
+    svg.push_str("  <!-- Connections -->\n");
+
+    // TODO: Routing implementation will be replaced with libavoid integration
+    let defaults = RoutingConfig::default();
+    let configured = diagram.clearance();
+    let routing_config = RoutingConfig {
+        grid: diagram.grid(),
+        obstacle_margin: configured
+            .margin
+            .map_or(defaults.obstacle_margin, |margin| Px::new(margin.value())),
+        min_separation: configured
+            .separation
+            .map_or(defaults.min_separation, |separation| {
+                Px::new(separation.value())
+            }),
+        ..defaults
+    };
+
+    // Every rendered entity is an obstacle; source and target are part of the
+    // route key, so fingerprinting the full set once is sufficient.
+    let obstacle_rects: Vec<Rectangle> = entity_positions
+        .values()
+        .flatten()
+        .map(EntityPosition::to_rectangle)
+        .collect();
+    let obstacles = ObstacleSetHash::from_obstacles(&obstacle_rects);
+
+    // Find the entity instances each connection joins
+    let mut resolved: Vec<ResolvedConnection> = Vec::new();
+    for (slice_index, slice) in diagram.slices().iter().enumerate() {
+        for connection in slice.connections.iter() {
+            // Extract entity names from references
+            let from_name = extract_entity_name(&connection.from);
+            let to_name = extract_entity_name(&connection.to);
+
+            // Find the correct entity instances
+            let from_pos = find_entity_position(&from_name, slice_index, entity_positions);
+            let to_pos = find_entity_position(&to_name, slice_index, entity_positions);
+
+            match (from_pos, to_pos) {
+                (Some(from_pos), Some(to_pos)) => resolved.push(ResolvedConnection {
+                    name: format!("{from_name} -> {to_name}"),
+                    slice: &slice.name,
+                    self_loop: from_name == to_name,
+                    from: from_pos,
+                    to: to_pos,
+                    via: connection
+                        .via
+                        .iter()
+                        .filter_map(|waypoint| waypoint_grid.center(waypoint))
+                        .collect(),
+                    marker: connection_marker(connection, lookups, diagram.connection_markers()),
+                    bidirectional: connection.bidirectional,
+                    consistency: connection.consistency,
+                    hotspot: connection.hotspot.as_ref(),
+                }),
+                (from_pos, _) => {
+                    let missing = if from_pos.is_none() {
+                        &from_name
+                    } else {
+                        &to_name
+                    };
+                    warnings.warn(
+                        WarningKind::UnresolvedConnection,
+                        format!(
+                            "Skipped connection {from_name} -> {to_name} in slice '{}': '{missing}' is not a declared entity",
+                            slice.name.clone().into_inner().as_str()
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    // Connections drawn as the same connector share one when merging
+    let merge = diagram.duplicate_connections() != yaml_types::DuplicateConnections::Separate;
+    let mut connectors: Vec<Vec<ResolvedConnection>> = Vec::new();
+    for connection in resolved {
+        match connectors
+            .iter_mut()
+            .find(|group| merge && group[0].coincides_with(&connection))
+        {
+            Some(group) => group.push(connection),
+            None => connectors.push(vec![connection]),
+        }
+    }
+
+    // Badges that would land on top of each other are nudged apart by a
+    // seeded jitter, defaulting to one derived from the connectors drawn
+    let seed = options.seed.unwrap_or_else(|| {
+        let names: Vec<&str> = connectors
+            .iter()
+            .map(|group| group[0].name.as_str())
+            .collect();
+        Jitter::seed_for(&(diagram.workflow_title(), names))
+    });
+    let mut placer = LabelPlacer::new(Jitter::new(seed));
+
+    let mut routed: Vec<RoutedConnection> = Vec::new();
+    let mut iterations = 0;
+    let mut degraded = 0;
+    for (index, group) in connectors.iter().enumerate() {
+        options
+            .progress
+            .report(ProgressStage::Routing, index, connectors.len());
+        let connection = &group[0];
+        // Once the budget runs out, connectors are drawn as straight lines
+        // without touching the cache, so a later render with time to spare
+        // still routes them properly
+        let straight_line;
+        let route = if options.fast && !connection.self_loop {
+            straight_line = route_straight_line(connection.from, connection.to);
+            &straight_line
+        } else if budget.is_exhausted(iterations) && !connection.self_loop {
+            degraded += 1;
+            straight_line = route_straight_line(connection.from, connection.to);
+            &straight_line
+        } else {
+            // Use simple straight arrow for now (until libavoid integration)
+            route_cache.get_or_route_via(
+                &connection.from.to_rectangle(),
+                &connection.to.to_rectangle(),
+                &connection.via,
+                obstacles,
+                &routing_config,
+                || {
+                    iterations += 1;
+                    let route = match connection.via.as_slice() {
+                        [] if connection.self_loop => route_self_loop(connection.from),
+                        [] => route_straight_arrow(connection.from, connection.to),
+                        via => route_through_waypoints(connection.from, connection.to, via),
+                    };
+                    match routing_config.grid {
+                        Some(grid) => snap_route_bends(route, grid),
+                        None => route,
+                    }
+                },
+            )
+        };
+        routed.push(RoutedConnection {
+            name: connection.name.clone(),
+            source: connection.from.to_rectangle(),
+            target: connection.to.to_rectangle(),
+            route: route.clone(),
+        });
+    }
+    options
+        .progress
+        .report(ProgressStage::Routing, connectors.len(), connectors.len());
+
+    let entities: Vec<(&str, Rectangle)> = entity_positions
+        .iter()
+        .flat_map(|(name, positions)| {
+            positions
+                .iter()
+                .map(move |pos| (name.as_str(), pos.to_rectangle()))
+        })
+        .collect();
+    // Straight lines of a fast render are left as drawn
+    if !options.fast {
+        let obstacles: Vec<Rectangle> = entities.iter().map(|(_, rect)| rect.clone()).collect();
+        clearance::enforce(
+            &mut routed,
+            &obstacles,
+            clearance::Clearance {
+                margin: routing_config.obstacle_margin,
+                separation: routing_config.min_separation,
+                grid: routing_config.grid,
+            },
+        );
+    }
+
+    let mut badges = String::new();
+    for (group, RoutedConnection { route, .. }) in connectors.iter().zip(&routed) {
+        let connection = &group[0];
+        let emphasis = Emphasis::of(group, focus);
+        svg.push_str(&render_routed_path(
+            route,
+            connection.marker,
+            connection.bidirectional,
+            emphasis,
+        ));
+        let mut badge = String::new();
+        if let [_, _, ..] = group.as_slice() {
+            badge.push_str(&render_multiplicity_badge(
+                route,
+                group,
+                diagram.duplicate_connections(),
+                &mut placer,
+            ));
+        }
+        if connection.consistency == Some(yaml_types::Consistency::Eventual) {
+            badge.push_str(&render_eventual_consistency_badge(route, &mut placer));
+        }
+        if let Some(hotspot) = group.iter().find_map(|connection| connection.hotspot) {
+            let center = placer.place(route_midpoint(route), HOTSPOT_SIZE, HOTSPOT_SIZE);
+            badge.push_str(&render_hotspot(center, hotspot.as_ref().as_str()));
+        }
+        if emphasis == Emphasis::Faded && !badge.is_empty() {
+            badges.push_str(&format!(
+                "  <g{}>\n{badge}  </g>\n",
+                emphasis.opacity_attribute()
+            ));
+        } else {
+            badges.push_str(&badge);
+        }
+        traces.push(RouteTrace {
+            connection: connection.name.clone(),
+            slice: connection.slice.clone().into_inner().into_inner(),
+            points: route.nodes.iter().copied().collect(),
+            cost: route.total_cost,
+        });
+    }
+    svg.push_str(&badges);
+    if degraded > 0 {
+        warnings.warn(
+            WarningKind::RenderBudgetExceeded,
+            format!(
+                "Render budget ran out after routing {iterations} connectors; the remaining {degraded} were drawn as straight lines"
+            ),
+        );
+    }
+
+    // Straight lines cross entities freely; listing every crossing of a
+    // fast render would bury the warnings that matter
+    if options.fast {
+        return (svg, traces);
+    }
+    for collision in collisions::find_collisions(
+        &routed,
+        &entities,
+        routing_config.obstacle_margin,
+        routing_config.min_separation,
+    ) {
+        warnings.warn(WarningKind::ConnectorCollision, collision.message());
+    }
+
+    (svg, traces)
+}
+
+/// Rounds `value` to the nearest multiple of `grid`, if the model has one.
+fn snap(value: Px, grid: Option<Px>) -> Px {
+    grid.map_or(value, |grid| value.snap_to(grid))
+}
+
+/// Moves a route's bends onto the grid while its ends stay on the entity
+/// edges they attach to.
+///
+/// Coordinates are snapped by value, so points sharing an x or y before
+/// snapping still share it afterwards and orthogonal segments stay orthogonal.
+/// Coordinates shared with either end stay put, keeping the end segments
+/// attached.
+fn snap_route_bends(route: RoutePath, grid: Px) -> RoutePath {
+    let (first, last) = (*route.nodes.first(), *route.nodes.last());
+    let snap_coordinate = |value: Px, pinned: [Px; 2]| {
+        if pinned.contains(&value) {
+            value
+        } else {
+            value.snap_to(grid)
+        }
+    };
+    RoutePath::new(
+        route.nodes.map(|point| Point {
+            x: snap_coordinate(point.x, [first.x, last.x]),
+            y: snap_coordinate(point.y, [first.y, last.y]),
+        }),
+        route.total_cost,
+    )
+}
+
+/// Renders the badge on a connector standing for several connections: their
+/// number, or the distinct names of the slices they come from.
+fn render_multiplicity_badge(
+    route: &RoutePath,
+    group: &[ResolvedConnection],
+    style: yaml_types::DuplicateConnections,
+    placer: &mut LabelPlacer,
+) -> String {
+    let label = match style {
+        yaml_types::DuplicateConnections::Slices => {
+            let mut names: Vec<String> = Vec::new();
+            for connection in group {
+                let name = connection.slice.clone().into_inner().into_inner();
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+            names.join(", ")
+        }
+        _ => format!("\u{d7}{}", group.len()),
+    };
+    let width = label.chars().count() as u32 * MULTIPLICITY_BADGE_CHAR_WIDTH + Px::new(8);
+    let center = placer.place(route_midpoint(route), width, MULTIPLICITY_BADGE_HEIGHT);
+    let x = center.x.saturating_sub(width / 2);
+    let y = center.y.saturating_sub(MULTIPLICITY_BADGE_HEIGHT / 2);
+    format!(
+        r#"  <rect x="{x}" y="{y}" width="{width}" height="{MULTIPLICITY_BADGE_HEIGHT}" rx="{}" fill="{BACKGROUND_COLOR}" stroke="{CONNECTOR_COLOR}" stroke-width="1"/>
+  <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{MULTIPLICITY_BADGE_FONT_SIZE}" fill="{CONNECTOR_COLOR}" text-anchor="middle">{}</text>
+"#,
+        MULTIPLICITY_BADGE_HEIGHT / 2,
+        center.x,
+        y + MULTIPLICITY_BADGE_HEIGHT / 2 + MULTIPLICITY_BADGE_FONT_SIZE / 2 - Px::new(1),
+        escape_xml(&label)
+    )
+}
+
+/// Renders a clock on a connector feeding a projection that is updated
+/// eventually, where the connector turns into its target.
+fn render_eventual_consistency_badge(route: &RoutePath, placer: &mut LabelPlacer) -> String {
+    let nodes: Vec<&Point> = route.nodes.iter().collect();
+    let corner = match nodes.as_slice() {
+        [.., corner, _] => **corner,
+        _ => *route.nodes.first(),
+    };
+    let center = placer.place(corner, CLOCK_BADGE_RADIUS * 2, CLOCK_BADGE_RADIUS * 2);
+    let (x, y) = (center.x, center.y);
+    let hand = CLOCK_BADGE_RADIUS - Px::new(3);
+    format!(
+        r#"  <g class="eventual-consistency">
+    <title>Eventually consistent</title>
+    <circle cx="{x}" cy="{y}" r="{CLOCK_BADGE_RADIUS}" fill="{BACKGROUND_COLOR}" stroke="{CONNECTOR_COLOR}" stroke-width="1"/>
+    <line x1="{x}" y1="{y}" x2="{x}" y2="{}" stroke="{CONNECTOR_COLOR}" stroke-width="1"/>
+    <line x1="{x}" y1="{y}" x2="{}" y2="{y}" stroke="{CONNECTOR_COLOR}" stroke-width="1"/>
+  </g>
+"#,
+        y - hand,
+        x + hand - Px::new(1)
+    )
+}
+
+/// Returns the point halfway along a route.
+fn route_midpoint(route: &RoutePath) -> Point {
+    let nodes: Vec<&Point> = route.nodes.iter().collect();
+    let length: Px = nodes
+        .windows(2)
+        .map(|pair| pair[0].manhattan_distance(pair[1]))
+        .sum();
+    let mut remaining = length / 2;
+    for pair in nodes.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let segment = a.manhattan_distance(b);
+        if remaining <= segment {
+            let along = |from: Px, to: Px| {
+                if to >= from {
+                    from + remaining.min(to - from)
+                } else {
+                    from - remaining.min(from - to)
+                }
+            };
+            return Point::new(along(a.x, b.x), along(a.y, b.y));
+        }
+        remaining -= segment;
+    }
+    *route.nodes.first()
+}
+
+/// Finds the position of an entity, preferring instances in the current or nearby slices.
+fn find_entity_position<'a>(
+    entity_name: &str,
+    current_slice: usize,
+    entity_positions: &'a EntityPositions,
+) -> Option<&'a EntityPosition> {
+    entity_positions
+        .get(entity_name)?
+        .iter()
+        .min_by_key(|pos| pos.slice_index.abs_diff(current_slice))
+}
+
+/// Extracts the base entity name from an EntityReference.
+fn extract_entity_name(entity_ref: &yaml_types::EntityReference) -> String {
+    entity_ref.entity_name()
+}
+
+/// Routes a straight (orthogonal) arrow between two entities.
+fn route_straight_arrow(from: &EntityPosition, to: &EntityPosition) -> RoutePath {
+    let (from_x, from_y) = calculate_connection_point(from, to, true);
+    let (to_x, to_y) = calculate_connection_point(to, from, false);
+
+    // Add minimum lead line extensions for proper spacing
+    let min_extension = MIN_ARROW_EXTENSION; // Match the routing system's minimum extension
+
+    // Calculate extended start and end points
+    let (extended_from_x, extended_from_y) =
+        extend_connection_point(from_x, from_y, from, to, min_extension, true);
+    let (extended_to_x, extended_to_y) =
+        extend_connection_point(to_x, to_y, to, from, min_extension, false);
+
+    // Create an orthogonal path with proper extensions
+    route_orthogonal_fallback(
+        extended_from_x,
+        extended_from_y,
+        extended_to_x,
+        extended_to_y,
+    )
+}
+
+/// Draws a single straight segment between the facing edges of two
+/// entities, for connectors the render budget left no time to route.
+fn route_straight_line(from: &EntityPosition, to: &EntityPosition) -> RoutePath {
+    let (from_x, from_y) = calculate_connection_point(from, to, true);
+    let (to_x, to_y) = calculate_connection_point(to, from, false);
+    let start = Point::new(from_x, from_y);
+    let end = Point::new(to_x, to_y);
+    let total_cost = start.manhattan_distance(&end);
+    RoutePath::new(NonEmpty::from_head_and_tail(start, vec![end]), total_cost)
+}
+
+/// Routes a connection from an entity back to itself as a small orthogonal
+/// loop off its top edge, where the swimlane margin leaves room for it.
+fn route_self_loop(entity: &EntityPosition) -> RoutePath {
+    let left = entity.x + entity.width / 3;
+    let right = entity.x + entity.width * 2 / 3;
+    let outer = entity.y.saturating_sub(SELF_LOOP_EXTENT);
+    let start = Point::new(left, entity.y);
+    let tail = vec![
+        Point::new(left, outer),
+        Point::new(right, outer),
+        Point::new(right, entity.y),
+    ];
+    let total_cost = 2 * (entity.y - outer) + (right - left);
+    RoutePath::new(NonEmpty::from_head_and_tail(start, tail), total_cost)
+}
+
+/// Creates an orthogonal path that leaves `from` toward the first waypoint,
+/// passes through every waypoint in order, and enters `to` from the last.
+fn route_through_waypoints(from: &EntityPosition, to: &EntityPosition, via: &[Point]) -> RoutePath {
+    let toward = |point: &Point| EntityPosition {
+        x: point.x,
+        y: point.y,
+        width: Px::ZERO,
+        height: Px::ZERO,
+        slice_index: from.slice_index,
+    };
+    let (first, last) = match (via.first(), via.last()) {
+        (Some(first), Some(last)) => (toward(first), toward(last)),
+        _ => return route_straight_arrow(from, to),
+    };
+
+    let (from_x, from_y) = calculate_connection_point(from, &first, true);
+    let (to_x, to_y) = calculate_connection_point(to, &last, false);
+    let start = extend_connection_point(from_x, from_y, from, &first, MIN_ARROW_EXTENSION, true);
+    let end = extend_connection_point(to_x, to_y, to, &last, MIN_ARROW_EXTENSION, false);
+
+    let stops: Vec<(Px, Px)> = std::iter::once(start)
+        .chain(via.iter().map(|point| (point.x, point.y)))
+        .chain(std::iter::once(end))
+        .collect();
+    let mut nodes = NonEmpty::singleton(Point::new(start.0, start.1));
+    let mut total_cost = Px::ZERO;
+    for leg in stops.windows(2) {
+        let path = route_orthogonal_fallback(leg[0].0, leg[0].1, leg[1].0, leg[1].1);
+        for &node in path.nodes.tail() {
+            nodes.push(node);
+        }
+        total_cost += path.total_cost;
+    }
+
+    RoutePath::new(nodes, total_cost)
+}
+
+/// Extends a connection point away from an entity by the specified distance.
+fn extend_connection_point(
+    x: Px,
+    y: Px,
+    entity: &EntityPosition,
+    _other: &EntityPosition,
+    extension: Px,
+    _is_source: bool,
+) -> (Px, Px) {
+    // Determine which edge this connection point is on
+    let on_left = x == entity.x;
+    let on_right = x == entity.x + entity.width;
+    let on_top = y == entity.y;
+    let on_bottom = y == entity.y + entity.height;
+
+    // Extend away from the entity
+    if on_left {
+        // Left edge - extend leftward
+        (x.saturating_sub(extension), y)
+    } else if on_right {
+        // Right edge - extend rightward
+        (x + extension, y)
+    } else if on_top {
+        // Top edge - extend upward
+        (x, y.saturating_sub(extension))
+    } else if on_bottom {
+        // Bottom edge - extend downward
+        (x, y + extension)
+    } else {
+        // Fallback - no extension
+        (x, y)
+    }
+}
+
+/// Creates a simple orthogonal path between two points as a fallback.
+fn route_orthogonal_fallback(from_x: Px, from_y: Px, to_x: Px, to_y: Px) -> RoutePath {
+    let start = Point::new(from_x, from_y);
+    let end = Point::new(to_x, to_y);
+
+    // If points are already aligned, draw a straight line
+    let bends = if from_x == to_x || from_y == to_y {
+        vec![]
+    } else {
+        // Create an L-shaped path
+        // Go horizontally first, then vertically
+        let mid_x = if from_x < to_x {
+            from_x + (to_x - from_x) / 2
+        } else {
+            to_x + (from_x - to_x) / 2
+        };
+        vec![Point::new(mid_x, from_y), Point::new(mid_x, to_y)]
+    };
+
+    let mut tail = bends;
+    tail.push(end);
+    let total_cost = std::iter::once(&start)
+        .chain(tail.iter())
+        .zip(tail.iter())
+        .map(|(a, b)| a.manhattan_distance(b))
+        .sum();
+
+    RoutePath::new(NonEmpty::from_head_and_tail(start, tail), total_cost)
+}
+
+/// Renders a curved arrow using bezier curves.
+#[allow(dead_code)]
+fn render_curved_arrow(
+    from: &EntityPosition,
+    to: &EntityPosition,
+    entity_positions: &HashMap<String, EntityPosition>,
+) -> String {
+    let (from_x, from_y) = calculate_connection_point(from, to, true);
+    let (to_x, to_y) = calculate_connection_point(to, from, false);
+
+    // Calculate centers for better curve control
+    let _from_center_x = from.x + from.width / 2;
+    let from_center_y = from.y + from.height / 2;
+    let to_center_x = to.x + to.width / 2;
+    let to_center_y = to.y + to.height / 2;
+
+    // Calculate control points for bezier curve
+    let dx = to_x.get() as i32 - from_x.get() as i32;
+    let dy = to_y.get() as i32 - from_y.get() as i32;
+
+    // Check if we need to avoid any entities
+    let entities_to_avoid =
+        find_entities_in_path(from_x, from_y, to_x, to_y, from, to, entity_positions);
+
+    // Determine curve control points based on relative positions and obstacles
+    let (cx1, cy1, cx2, cy2) = if !entities_to_avoid.is_empty() {
+        // Complex routing to avoid entities
+        calculate_avoidance_curve(from_x, from_y, to_x, to_y, from, to, &entities_to_avoid)
+    } else if from.slice_index < to.slice_index {
+        // Moving right across slices
+        if (from_center_y.get() as i32 - to_center_y.get() as i32).abs()
+            < (from.height / 3).get() as i32
+        {
+            // Same row - gentle horizontal curve
+            let _curve_offset = Px::new(30).min(Px::new(dx.unsigned_abs()) / 4);
+            (
+                from_x + Px::new(dx.unsigned_abs()) / 3,
+                from_y,
+                to_x - Px::new(dx.unsigned_abs()) / 3,
+                to_y,
+            )
+        } else {
+            // Diagonal movement
+            (
+                from_x + Px::new(dx.unsigned_abs()) / 2,
+                from_y,
+                to_x - Px::new(20),
+                to_y,
+            )
+        }
+    } else if from.slice_index == to.slice_index {
+        // Same slice
+        if dx.abs() < 20 {
+            // Vertical in same column - curve out to avoid overlap
+            let curve_width = Px::new(60);
+            if dy > 0 {
+                (
+                    from_x + curve_width,
+                    from_y + Px::new(20),
+                    to_x + curve_width,
+                    to_y - Px::new(20),
+                )
+            } else {
+                (
+                    from_x - curve_width,
+                    from_y - Px::new(20),
+                    to_x - curve_width,
+                    to_y + Px::new(20),
+                )
+            }
+        } else {
+            // Within same slice - create appropriate curve
+            if dy.abs() > dx.abs() {
+                // More vertical than horizontal
+                let offset = Px::new(40).min(Px::new(dx.unsigned_abs()) / 2 + Px::new(20));
+                if from_x < to_x {
+                    // Going right and down/up
+                    (from_x + offset, from_y, to_x - offset, to_y)
+                } else {
+                    // Going left and down/up
+                    (from_x - offset, from_y, to_x + offset, to_y)
+                }
+            } else {
+                // More horizontal - gentle curve
+                (
+                    from_x + Px::new(dx.unsigned_abs()) / 3,
+                    from_y,
+                    to_x - Px::new(dx.unsigned_abs()) / 3,
+                    to_y,
+                )
+            }
+        }
+    } else {
+        // Moving left (back to previous slice)
+        let _mid_x = (from_x + to_x) / 2;
+        let mid_y = (from_y + to_y) / 2;
+        (from_x - Px::new(40), mid_y, to_x + Px::new(40), mid_y)
+    };
+
+    // Adjust the last control point to ensure arrow points at center
+    // Calculate where the arrow should be pointing
+    let target_angle_x = to_center_x.get() as i32 - cx2.get() as i32;
+    let target_angle_y = to_center_y.get() as i32 - cy2.get() as i32;
+
+    // Adjust control point to create proper approach angle
+    let adjusted_cx2 = if target_angle_x.abs() > 10 {
+        // Need to adjust horizontal approach
+        if (to_x.get() as i32 - to_center_x.get() as i32).abs() < 5 {
+            // Entering from top/bottom, adjust to point at center
+            to_center_x
+        } else {
+            cx2
+        }
+    } else {
+        cx2
+    };
+
+    let adjusted_cy2 = if target_angle_y.abs() > 10 {
+        // Need to adjust vertical approach
+        if (to_y.get() as i32 - to_center_y.get() as i32).abs() < 5 {
+            // Entering from left/right, adjust to point at center
+            to_center_y
+        } else {
+            cy2
+        }
+    } else {
+        cy2
+    };
+
+    format!(
+        r##"  <path d="M {} {} C {} {}, {} {}, {} {}" stroke="#333333" stroke-width="2" fill="none" marker-end="url(#arrowhead)" />
+"##,
+        from_x, from_y, cx1, cy1, adjusted_cx2, adjusted_cy2, to_x, to_y
+    )
+}
+
+/// Find entities that are in the path between two points
+#[allow(dead_code)]
+fn find_entities_in_path<'a>(
+    from_x: Px,
+    from_y: Px,
+    to_x: Px,
+    to_y: Px,
+    from_entity: &EntityPosition,
+    to_entity: &EntityPosition,
+    entity_positions: &'a HashMap<String, EntityPosition>,
+) -> Vec<&'a EntityPosition> {
+    let mut obstacles = Vec::new();
+
+    // Create a bounding box for the path
+    let min_x = from_x.min(to_x);
+    let max_x = from_x.max(to_x);
+    let min_y = from_y.min(to_y);
+    let max_y = from_y.max(to_y);
+
+    for pos in entity_positions.values() {
+        // Skip source and target
+        if (pos.x == from_entity.x && pos.y == from_entity.y)
+            || (pos.x == to_entity.x && pos.y == to_entity.y)
+        {
+            continue;
+        }
+
+        // Check if entity overlaps with path bounding box
+        if pos.x + pos.width >= min_x
+            && pos.x <= max_x
+            && pos.y + pos.height >= min_y
+            && pos.y <= max_y
+        {
+            // More precise check if entity is actually in the path
+            if is_entity_in_line_path(from_x, from_y, to_x, to_y, pos) {
+                obstacles.push(pos);
+            }
+        }
+    }
+
+    obstacles
+}
+
+/// Check if an entity intersects with a line path
+#[allow(dead_code)]
+fn is_entity_in_line_path(x1: Px, y1: Px, x2: Px, y2: Px, entity: &EntityPosition) -> bool {
+    // Simple rectangle-line intersection check
+    let entity_left = entity.x;
+    let entity_right = entity.x + entity.width;
+    let entity_top = entity.y;
+    let entity_bottom = entity.y + entity.height;
+
+    let dx = x2.get() as i32 - x1.get() as i32;
+    let dy = y2.get() as i32 - y1.get() as i32;
+
+    // Check for zero-length line
+    if dx == 0 && dy == 0 {
+        return false;
+    }
+
+    // If line is mostly horizontal
+    if dx.abs() > dy.abs() && dx != 0 {
+        let y_at_left = y1.get() as i32 + ((entity_left.get() as i32 - x1.get() as i32) * dy) / dx;
+        let y_at_right =
+            y1.get() as i32 + ((entity_right.get() as i32 - x1.get() as i32) * dy) / dx;
+
+        (y_at_left >= entity_top.get() as i32 && y_at_left <= entity_bottom.get() as i32)
+            || (y_at_right >= entity_top.get() as i32 && y_at_right <= entity_bottom.get() as i32)
+    } else if dy != 0 {
+        // Line is mostly vertical
+        let x_at_top = x1.get() as i32 + ((entity_top.get() as i32 - y1.get() as i32) * dx) / dy;
+        let x_at_bottom =
+            x1.get() as i32 + ((entity_bottom.get() as i32 - y1.get() as i32) * dx) / dy;
+
+        (x_at_top >= entity_left.get() as i32 && x_at_top <= entity_right.get() as i32)
+            || (x_at_bottom >= entity_left.get() as i32 && x_at_bottom <= entity_right.get() as i32)
+    } else {
+        false
+    }
+}
+
+/// Calculate curve control points to avoid obstacles
+#[allow(dead_code)]
+fn calculate_avoidance_curve(
+    from_x: Px,
+    from_y: Px,
+    to_x: Px,
+    to_y: Px,
+    _from_entity: &EntityPosition,
+    _to_entity: &EntityPosition,
+    obstacles: &[&EntityPosition],
+) -> (Px, Px, Px, Px) {
+    // Simple avoidance: route around the side with more space
+    let dx = to_x.get() as i32 - from_x.get() as i32;
+    let dy = to_y.get() as i32 - from_y.get() as i32;
+
+    // Find the main obstacle (closest to the midpoint)
+    let mid_x = (from_x + to_x) / 2;
+    let mid_y = (from_y + to_y) / 2;
+
+    let main_obstacle = obstacles.iter().min_by_key(|obs| {
+        let obs_center_x = obs.x + obs.width / 2;
+        let obs_center_y = obs.y + obs.height / 2;
+        ((obs_center_x.get() as i32 - mid_x.get() as i32).pow(2)
+            + (obs_center_y.get() as i32 - mid_y.get() as i32).pow(2)) as u32
+    });
+
+    if let Some(obstacle) = main_obstacle {
+        // Route around the obstacle
+        let obs_center_y = obstacle.y + obstacle.height / 2;
+
+        if dx.abs() > dy.abs() {
+            // Mostly horizontal - route above or below
+            if mid_y < obs_center_y {
+                // Route above
+                let detour_y = obstacle.y.saturating_sub(Px::new(30));
+                (
+                    from_x + Px::new(dx.unsigned_abs()) / 3,
+                    detour_y,
+                    to_x - Px::new(dx.unsigned_abs()) / 3,
+                    detour_y,
+                )
+            } else {
+                // Route below
+                let detour_y = obstacle.y + obstacle.height + Px::new(30);
+                (
+                    from_x + Px::new(dx.unsigned_abs()) / 3,
+                    detour_y,
+                    to_x - Px::new(dx.unsigned_abs()) / 3,
+                    detour_y,
+                )
+            }
+        } else {
+            // Mostly vertical - route left or right
+            let obs_center_x = obstacle.x + obstacle.width / 2;
+            if mid_x < obs_center_x {
+                // Route left
+                let detour_x = obstacle.x.saturating_sub(Px::new(30));
+                (
+                    detour_x,
+                    from_y + Px::new(dy.unsigned_abs()) / 3,
+                    detour_x,
+                    to_y - Px::new(dy.unsigned_abs()) / 3,
+                )
+            } else {
+                // Route right
+                let detour_x = obstacle.x + obstacle.width + Px::new(30);
+                (
+                    detour_x,
+                    from_y + Px::new(dy.unsigned_abs()) / 3,
+                    detour_x,
+                    to_y - Px::new(dy.unsigned_abs()) / 3,
+                )
+            }
+        }
+    } else {
+        // Fallback to simple curve
+        (
+            from_x + Px::new(dx.unsigned_abs()) / 3,
+            from_y,
+            to_x - Px::new(dx.unsigned_abs()) / 3,
+            to_y,
+        )
+    }
+}
+
+/// Calculates the connection point on an entity's edge.
+fn calculate_connection_point(
+    entity: &EntityPosition,
+    other: &EntityPosition,
+    is_source: bool,
+) -> (Px, Px) {
+    let entity_center_x = entity.x + entity.width / 2;
+    let entity_center_y = entity.y + entity.height / 2;
+    let other_center_x = other.x + other.width / 2;
+    let other_center_y = other.y + other.height / 2;
+
+    // Calculate angle from entity center to other center
+    let dx = other_center_x.get() as i32 - entity_center_x.get() as i32;
+    let dy = other_center_y.get() as i32 - entity_center_y.get() as i32;
+
+    // Determine primary direction based on angle
+    let abs_dx = dx.abs();
+    let abs_dy = dy.abs();
+
+    if is_source {
+        // For source, exit toward target
+        if abs_dx > abs_dy {
+            // Primarily horizontal
+            if dx > 0 {
+                // Exit right
+                (entity.x + entity.width, entity_center_y)
+            } else {
+                // Exit left
+                (entity.x, entity_center_y)
+            }
+        } else {
+            // Primarily vertical
+            if dy > 0 {
+                // Exit bottom
+                (entity_center_x, entity.y + entity.height)
+            } else {
+                // Exit top
+                (entity_center_x, entity.y)
+            }
+        }
+    } else {
+        // For target, enter from direction of source
+        if abs_dx > abs_dy {
+            // Primarily horizontal
+            if dx > 0 {
+                // Enter from left
+                (entity.x, entity_center_y)
+            } else {
+                // Enter from right
+                (entity.x + entity.width, entity_center_y)
+            }
+        } else {
+            // Primarily vertical
+            if dy > 0 {
+                // Enter from top
+                (entity_center_x, entity.y)
+            } else {
+                // Enter from bottom
+                (entity_center_x, entity.y + entity.height)
+            }
+        }
+    }
+}
+
+/// Renders a routed path as an SVG path element ending in `marker`, and
+/// starting in it too when the connection runs both ways.
+fn render_routed_path(
+    route: &RoutePath,
+    marker: yaml_types::MarkerStyle,
+    bidirectional: bool,
+    emphasis: Emphasis,
+) -> String {
+    let markers = marker_id(marker)
+        .map(|id| {
+            let start = if bidirectional {
+                format!(r#" marker-start="url(#{id})""#)
+            } else {
+                String::new()
+            };
+            format!(r#"{start} marker-end="url(#{id})""#)
+        })
+        .unwrap_or_default();
+    format!(
+        r#"  <path d="{}" fill="none" stroke="{CONNECTOR_COLOR}" stroke-width="{}"{}{markers} />
+"#,
+        route.to_svg_path(),
+        emphasis.stroke_width(),
+        emphasis.opacity_attribute()
+    )
+}
+
+/// Returns the id of the `<defs>` entry drawing a marker, or `None` for a
+/// connector without one.
+fn marker_id(marker: yaml_types::MarkerStyle) -> Option<&'static str> {
+    match marker {
+        yaml_types::MarkerStyle::Filled => Some("arrowhead"),
+        yaml_types::MarkerStyle::Open => Some("arrowhead-open"),
+        yaml_types::MarkerStyle::Diamond => Some("marker-diamond"),
+        yaml_types::MarkerStyle::Circle => Some("marker-circle"),
+        yaml_types::MarkerStyle::None => None,
+    }
+}
+
+/// Chooses the marker for a connection: its own, or the model's marker for
+/// the types of entity it joins.
+fn connection_marker(
+    connection: &yaml_types::Connection,
+    lookups: &EntityLookups,
+    markers: &yaml_types::ConnectionMarkers,
+) -> yaml_types::MarkerStyle {
+    connection.marker.unwrap_or_else(|| {
+        let from = lookups.entity_type(&extract_entity_name(&connection.from));
+        let to = lookups.entity_type(&extract_entity_name(&connection.to));
+        match (from, to) {
+            (Some(from), Some(to)) => markers.style_for(from, to),
+            _ => yaml_types::MarkerStyle::default(),
+        }
+    })
+}
+
+/// Returns the markers drawn by any connection, always including the filled
+/// arrowhead, in a stable order.
+fn marker_styles_in_use(
+    slices: &[yaml_types::Slice],
+    lookups: &EntityLookups,
+    markers: &yaml_types::ConnectionMarkers,
+) -> Vec<yaml_types::MarkerStyle> {
+    let used: HashSet<yaml_types::MarkerStyle> = slices
+        .iter()
+        .flat_map(|slice| slice.connections.iter())
+        .map(|connection| connection_marker(connection, lookups, markers))
+        .collect();
+    [
+        yaml_types::MarkerStyle::Filled,
+        yaml_types::MarkerStyle::Open,
+        yaml_types::MarkerStyle::Diamond,
+        yaml_types::MarkerStyle::Circle,
+    ]
+    .into_iter()
+    .filter(|style| *style == yaml_types::MarkerStyle::Filled || used.contains(style))
+    .collect()
+}
+
+/// Renders one `<marker>` definition per style.
+fn render_marker_defs(styles: &[yaml_types::MarkerStyle]) -> String {
+    styles
+        .iter()
+        .filter_map(|&style| {
+            let (width, height, ref_x, ref_y, shape) = match style {
+                yaml_types::MarkerStyle::Filled => (
+                    10,
+                    7,
+                    "9",
+                    "3.5",
+                    format!(r#"<polygon points="0 0, 10 3.5, 0 7" fill="{CONNECTOR_COLOR}" />"#),
+                ),
+                yaml_types::MarkerStyle::Open => (
+                    10,
+                    7,
+                    "9",
+                    "3.5",
+                    format!(
+                        r#"<polyline points="0 0, 10 3.5, 0 7" fill="none" stroke="{CONNECTOR_COLOR}" stroke-width="1" />"#
+                    ),
+                ),
+                yaml_types::MarkerStyle::Diamond => (
+                    12,
+                    8,
+                    "11",
+                    "4",
+                    format!(
+                        r#"<polygon points="0.5 4, 6 0.5, 11.5 4, 6 7.5" fill="{BACKGROUND_COLOR}" stroke="{CONNECTOR_COLOR}" stroke-width="1" />"#
+                    ),
+                ),
+                yaml_types::MarkerStyle::Circle => (
+                    8,
+                    8,
+                    "7",
+                    "4",
+                    format!(
+                        r#"<circle cx="4" cy="4" r="3.5" fill="{BACKGROUND_COLOR}" stroke="{CONNECTOR_COLOR}" stroke-width="1" />"#
+                    ),
+                ),
+                yaml_types::MarkerStyle::None => return None,
+            };
+            let id = marker_id(style)?;
+            Some(format!(
+                r#"    <marker id="{id}" markerWidth="{width}" markerHeight="{height}" refX="{ref_x}" refY="{ref_y}" orient="auto-start-reverse">
+      {shape}
+    </marker>
+"#
+            ))
+        })
+        .collect()
+}
+
+// TODO: Debug function removed - will be replaced with libavoid debug info
+
+/// Entity lookup maps for avoiding too many function parameters.
+struct EntityLookups<'a> {
+    view_lookup: HashMap<String, &'a yaml_types::ViewDefinition>,
+    command_lookup: HashMap<String, &'a yaml_types::CommandDefinition>,
+    event_lookup: HashMap<String, &'a yaml_types::EventDefinition>,
+    projection_lookup: HashMap<String, &'a yaml_types::ProjectionDefinition>,
+    query_lookup: HashMap<String, &'a yaml_types::QueryDefinition>,
+    automation_lookup: HashMap<String, &'a yaml_types::AutomationDefinition>,
+}
+
+impl EntityLookups<'_> {
+    /// Describes whether a command is idempotent and the roles allowed to
+    /// issue it, e.g. `idempotent · admin, support`; `None` if it states
+    /// neither or is not a command.
+    fn command_access(&self, name: &str) -> Option<String> {
+        let command = self.command_lookup.get(name)?;
+        let mut parts = Vec::new();
+        match command.idempotent {
+            Some(true) => parts.push("idempotent".to_string()),
+            Some(false) => parts.push("not idempotent".to_string()),
+            None => {}
+        }
+        if !command.authorized_roles.is_empty() {
+            let roles: Vec<String> = command
+                .authorized_roles
+                .iter()
+                .map(|role| role.clone().into_inner().into_inner())
+                .collect();
+            parts.push(format!("\u{1f512} {}", roles.join(", ")));
+        }
+        (!parts.is_empty()).then(|| parts.join(" \u{b7} "))
+    }
+
+    /// Returns whether an event or command has data fields tagged as PII.
+    fn carries_pii(&self, name: &str) -> bool {
+        self.command_lookup
+            .get(name)
+            .is_some_and(|command| crate::event_model::pii::carries_pii(&command.data))
+            || self
+                .event_lookup
+                .get(name)
+                .is_some_and(|event| crate::event_model::pii::carries_pii(&event.data))
+    }
+
+    /// Returns the volume stated for an event or command.
+    fn volume(&self, name: &str) -> Option<&yaml_types::Volume> {
+        self.command_lookup
+            .get(name)
+            .and_then(|command| command.volume.as_ref())
+            .or_else(|| {
+                self.event_lookup
+                    .get(name)
+                    .and_then(|event| event.volume.as_ref())
+            })
+    }
+
+    /// Returns the names of the errors a command may be rejected with, sorted;
+    /// empty for any other entity.
+    fn command_errors(&self, name: &str) -> Vec<String> {
+        let mut errors: Vec<String> = self
+            .command_lookup
+            .get(name)
+            .map(|command| {
+                command
+                    .errors
+                    .keys()
+                    .map(|error| error.clone().into_inner().into_inner())
+                    .collect()
+            })
+            .unwrap_or_default();
+        errors.sort();
+        errors
+    }
+
+    /// Returns the type of the entity with the given name, if it is defined.
+    fn entity_type(&self, name: &str) -> Option<EntityType> {
+        if self.view_lookup.contains_key(name) {
+            Some(EntityType::View)
+        } else if self.command_lookup.contains_key(name) {
+            Some(EntityType::Command)
+        } else if self.event_lookup.contains_key(name) {
+            Some(EntityType::Event)
+        } else if self.projection_lookup.contains_key(name) {
+            Some(EntityType::Projection)
+        } else if self.query_lookup.contains_key(name) {
+            Some(EntityType::Query)
+        } else if self.automation_lookup.contains_key(name) {
+            Some(EntityType::Automation)
+        } else {
+            None
+        }
+    }
+}
+
+/// Position information for a rendered entity.
+#[derive(Debug, Clone)]
+struct EntityPosition {
+    x: Px,
+    y: Px,
+    width: Px,
+    height: Px,
+    slice_index: usize,
+}
+
+impl EntityPosition {
+    /// Returns the area occupied by this entity instance.
+    fn to_rectangle(&self) -> Rectangle {
+        Rectangle::new(self.x, self.y, self.width, self.height)
+    }
+}
+
+/// Positions of every rendered instance of each entity, keyed by entity name.
+type EntityPositions = HashMap<String, Vec<EntityPosition>>;
+
+/// Context for rendering entities.
+struct EntityRenderContext<'a> {
+    slices: &'a [yaml_types::Slice],
+    lookups: &'a EntityLookups<'a>,
+    columns: &'a SliceColumns,
+    swimlane_heights: &'a [Px],
+    swimlanes_start_y: Px,
+    start_x: Px,
+    entity_dimensions_map: &'a HashMap<String, EntityDimensions>,
+    /// Custom kinds of the entities declaring one, by entity name.
+    kinds: &'a HashMap<String, &'a yaml_types::EntityExtension>,
+    grid: Option<Px>,
+    /// Left edges solved by the constraints layout, from the column's left
+    /// edge, by entity name and slice index.
+    offsets: &'a HashMap<(String, usize), Px>,
+}
+
+/// Renders a box with text, using the specified colors.
+fn render_box_with_text(
+    x: Px,
+    y: Px,
+    dimensions: &EntityDimensions,
+    background_color: &str,
+    text_color: &str,
+) -> String {
+    let mut svg = String::new();
+
+    // Draw the box
+    svg.push_str(&format!(
+        r#"  <rect x="{x}" y="{y}" width="{}" height="{}" fill="{background_color}" stroke="{SWIMLANE_BORDER_COLOR}" stroke-width="1"/>
+"#,
+        dimensions.width, dimensions.height
+    ));
+
+    // Draw the entity name with multiple lines
+    let line_height = line_height(ENTITY_NAME_FONT_SIZE);
+    let text_center_x = x + dimensions.width / 2;
+
+    // Center the text vertically in the box
+    let total_text_height = dimensions.text_lines.len() as u32 * line_height;
+    let text_start_y = y + (dimensions.height - total_text_height) / 2 + ENTITY_NAME_FONT_SIZE;
+
+    for (i, line) in dimensions.text_lines.iter().enumerate() {
+        let text_y = text_start_y + (i as u32 * line_height);
+        svg.push_str(&format!(
+            r#"  <text x="{text_center_x}" y="{text_y}" font-family="Arial, sans-serif" font-size="{ENTITY_NAME_FONT_SIZE}" fill="{text_color}" text-anchor="middle">{line}</text>
+"#
+        ));
+    }
+
+    svg
+}
+
+/// Renders a single view box with proper text wrapping.
+fn render_view_box(x: Px, y: Px, dimensions: &EntityDimensions) -> String {
+    render_box_with_text(x, y, dimensions, VIEW_BACKGROUND_COLOR, TEXT_COLOR)
+}
+
+/// Renders a single command box with proper text wrapping.
+fn render_command_box(x: Px, y: Px, dimensions: &EntityDimensions) -> String {
+    render_box_with_text(x, y, dimensions, COMMAND_BACKGROUND_COLOR, "#ffffff")
+}
+
+/// Renders a single event box with proper text wrapping.
+fn render_event_box(x: Px, y: Px, dimensions: &EntityDimensions) -> String {
+    render_box_with_text(x, y, dimensions, EVENT_BACKGROUND_COLOR, "#ffffff")
+}
+
+/// Renders a single projection box with proper text wrapping.
+fn render_projection_box(x: Px, y: Px, dimensions: &EntityDimensions) -> String {
+    render_box_with_text(x, y, dimensions, PROJECTION_BACKGROUND_COLOR, TEXT_COLOR)
+}
+
+/// Renders a single query box with proper text wrapping.
+fn render_query_box(x: Px, y: Px, dimensions: &EntityDimensions) -> String {
+    render_box_with_text(x, y, dimensions, QUERY_BACKGROUND_COLOR, "#ffffff")
+}
+
+/// Renders an automation entity with an icon and text below.
+fn render_automation(x: Px, y: Px, dimensions: &EntityDimensions, icon: &str) -> String {
+    let mut svg = String::new();
+
+    // Center the icon horizontally
+    let icon_x = x + dimensions.width / 2;
+    let icon_y = y + ENTITY_PADDING + Px::new(15); // 15 is half the icon size for vertical centering
+
+    svg.push_str(&format!(
+        r#"  <text x="{icon_x}" y="{icon_y}" font-family="Arial, sans-serif" font-size="30" text-anchor="middle">{}</text>
+"#,
+        escape_xml(icon)
+    ));
+
+    // Render automation name below the icon
+    let text_start_y =
+        y + ENTITY_PADDING + ROBOT_ICON_SIZE + ICON_TEXT_SPACING + ENTITY_NAME_FONT_SIZE;
+    let text_center_x = x + dimensions.width / 2;
+
+    let line_height = line_height(ENTITY_NAME_FONT_SIZE);
+    for (i, line) in dimensions.text_lines.iter().enumerate() {
+        let text_y = text_start_y + (i as u32 * line_height);
+        svg.push_str(&format!(
+            r#"  <text x="{text_center_x}" y="{text_y}" font-family="Arial, sans-serif" font-size="{ENTITY_NAME_FONT_SIZE}" fill="{TEXT_COLOR}" text-anchor="middle">{line}</text>
+"#
+        ));
+    }
+
+    svg
+}
+
+/// Renders an entity of a custom kind the way its base type is drawn, in
+/// the kind's color and marked with its icon.
+fn render_kind(
+    x: Px,
+    y: Px,
+    dimensions: &EntityDimensions,
+    extension: &yaml_types::EntityExtension,
+) -> String {
+    let color: Option<&str> = extension.color.as_ref().map(|color| color.as_ref());
+    let icon: Option<&str> = extension.icon.as_ref().map(|icon| icon.as_ref());
+    let (background, text_color) = match extension.base {
+        EntityType::View | EntityType::Wireframe => (VIEW_BACKGROUND_COLOR, TEXT_COLOR),
+        EntityType::Command => (COMMAND_BACKGROUND_COLOR, "#ffffff"),
+        EntityType::Event => (EVENT_BACKGROUND_COLOR, "#ffffff"),
+        EntityType::Projection => (PROJECTION_BACKGROUND_COLOR, TEXT_COLOR),
+        EntityType::Query => (QUERY_BACKGROUND_COLOR, "#ffffff"),
+        EntityType::Automation => {
+            // The kind's icon takes the gear's place, over a backdrop in its color
+            let mut svg = String::new();
+            if let Some(color) = color {
+                svg.push_str(&format!(
+                    r#"  <rect x="{x}" y="{y}" width="{}" height="{}" rx="{KIND_AUTOMATION_CORNER_RADIUS}" fill="{}"/>
+"#,
+                    dimensions.width,
+                    dimensions.height,
+                    escape_xml(color)
+                ));
+            }
+            svg.push_str(&render_automation(
+                x,
+                y,
+                dimensions,
+                icon.unwrap_or(AUTOMATION_ICON),
+            ));
+            return svg;
+        }
+    };
+
+    let mut svg = match color {
+        Some(color) => render_box_with_text(
+            x,
+            y,
+            dimensions,
+            color,
+            text_color_on(color).unwrap_or(text_color),
+        ),
+        None => render_box_with_text(x, y, dimensions, background, text_color),
+    };
+    if let Some(icon) = icon {
+        svg.push_str(&format!(
+            r#"  <text x="{}" y="{}" font-family="Arial, sans-serif" font-size="{KIND_ICON_FONT_SIZE}">{}</text>
+"#,
+            x + KIND_ICON_INSET,
+            y + KIND_ICON_INSET + KIND_ICON_FONT_SIZE,
+            escape_xml(icon)
+        ));
+    }
+    svg
+}
+
+/// Returns the text color readable on a `#rgb` or `#rrggbb` background, or
+/// `None` for named colors.
+fn text_color_on(background: &str) -> Option<&'static str> {
+    let hex = background.strip_prefix('#')?;
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).ok().map(f32::from);
+    let (r, g, b) = match hex.len() {
+        3 => {
+            let digit = |i: usize| channel(&hex[i..=i].repeat(2));
+            (digit(0)?, digit(1)?, digit(2)?)
+        }
+        _ => (
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+        ),
+    };
+    let luminance = (0.299 * r + 0.587 * g + 0.114 * b) / 255.0;
+    Some(if luminance > 0.6 {
+        TEXT_COLOR
+    } else {
+        "#ffffff"
+    })
+}
@@ -0,0 +1,85 @@
+//! What a render settled on, for inspecting where a diagram goes wrong.
+//!
+//! A trace records the canvas size, where every swimlane, slice column, and
+//! entity was placed, and the path each connector takes, in the same pixel
+//! units as the SVG.
+
+use super::layout_types::Px;
+use super::routing_types::Point;
+use serde::Serialize;
+
+/// The layout and routes of one render.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RenderTrace {
+    /// Where swimlanes, columns, and entities were placed.
+    pub layout: LayoutTrace,
+    /// The path of each connector, in drawing order.
+    pub routes: Vec<RouteTrace>,
+}
+
+/// Where everything on the canvas was placed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LayoutTrace {
+    /// Width of the canvas.
+    pub width: Px,
+    /// Height of the canvas.
+    pub height: Px,
+    /// Swimlanes from top to bottom.
+    pub swimlanes: Vec<SwimlaneTrace>,
+    /// Slice columns from left to right.
+    pub columns: Vec<ColumnTrace>,
+    /// Every drawn entity instance, ordered by name and then slice.
+    pub entities: Vec<EntityTrace>,
+}
+
+/// A swimlane's band across the canvas.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SwimlaneTrace {
+    /// Identifier of the swimlane.
+    pub id: String,
+    /// Top of the band.
+    pub y: Px,
+    /// Height of the band.
+    pub height: Px,
+}
+
+/// A column holding one slice, or several with the compact layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ColumnTrace {
+    /// Names of the slices in the column.
+    pub slices: Vec<String>,
+    /// Left edge of the column.
+    pub x: Px,
+    /// Width of the column.
+    pub width: Px,
+}
+
+/// One drawn instance of an entity.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EntityTrace {
+    /// Name of the entity.
+    pub name: String,
+    /// Slice this instance is drawn in.
+    pub slice: String,
+    /// Left edge of the box.
+    pub x: Px,
+    /// Top edge of the box.
+    pub y: Px,
+    /// Width of the box.
+    pub width: Px,
+    /// Height of the box.
+    pub height: Px,
+}
+
+/// The path a connector takes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RouteTrace {
+    /// The connection drawn, e.g. `PlaceOrder -> OrderPlaced`.
+    pub connection: String,
+    /// Slice declaring the connection.
+    pub slice: String,
+    /// Points the path passes through, from source to target.
+    pub points: Vec<Point>,
+    /// Cost the router assigned to the path.
+    pub cost: Px,
+}
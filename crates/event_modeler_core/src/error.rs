@@ -0,0 +1,158 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! The crate-level error type.
+//!
+//! Each stage of the pipeline keeps its own error type, so its variants can
+//! describe exactly what went wrong. [`Error`] gathers them behind one type
+//! with a `From` impl for each, so code driving several stages can use `?`
+//! throughout and match on the stage that failed. [`Error`] is
+//! non-exhaustive, leaving room for stages to be added, and each variant
+//! wraps the stage's error as is, so it can gain detail without breaking
+//! code that only matches on the stage.
+
+use crate::diagram::DiagramError;
+use crate::event_model::roles::MisplacedEntities;
+use crate::event_model::{converter, yaml_to_diagram_converter};
+use crate::export::MarkdownExportError;
+#[cfg(feature = "pdf")]
+use crate::export::PdfExportError;
+#[cfg(feature = "png")]
+use crate::export::PngExportError;
+#[cfg(feature = "sqlite")]
+use crate::export::sqlite::SqliteExportError;
+use crate::infrastructure::git::GitError;
+use crate::infrastructure::issues::IssueError;
+use crate::infrastructure::parsing::merge::MergeError;
+use crate::infrastructure::parsing::profiles::ProfileError;
+use crate::infrastructure::parsing::rename::RenameError;
+use crate::infrastructure::parsing::yaml_converter;
+use crate::infrastructure::parsing::yaml_edit::YamlEditError;
+use crate::infrastructure::parsing::yaml_parser::YamlParseError;
+#[cfg(feature = "plugins")]
+use crate::infrastructure::plugins::PluginError;
+use crate::infrastructure::{parsing, types};
+use crate::routing::RoutingError;
+use crate::validation::ValidationError;
+
+/// Any error the crate reports, by the stage it comes from.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// A `.eventmodel` file is not valid YAML for the schema.
+    #[error(transparent)]
+    Parse(#[from] YamlParseError),
+
+    /// A file in the text format could not be parsed.
+    #[error(transparent)]
+    TextParse(#[from] parsing::ParseError),
+
+    /// A value failed validation at a system boundary.
+    #[error(transparent)]
+    Value(#[from] types::ParseError),
+
+    /// Parsed YAML does not make a valid domain model.
+    #[error(transparent)]
+    Conversion(#[from] yaml_converter::ConversionError),
+
+    /// A parsed text-format model does not make a valid set of entities.
+    #[error(transparent)]
+    EntityConversion(#[from] converter::ConversionError),
+
+    /// A domain model could not be turned into a diagram description.
+    #[error(transparent)]
+    DiagramConversion(#[from] yaml_to_diagram_converter::ConversionError),
+
+    /// Entities sit in swimlanes whose role does not suit them.
+    #[error(transparent)]
+    Placement(#[from] MisplacedEntities),
+
+    /// A profile could not be applied.
+    #[error(transparent)]
+    Profile(#[from] ProfileError),
+
+    /// Models could not be merged.
+    #[error(transparent)]
+    Merge(#[from] MergeError),
+
+    /// A model's source could not be edited in place.
+    #[error(transparent)]
+    Edit(#[from] YamlEditError),
+
+    /// An entity could not be renamed.
+    #[error(transparent)]
+    Rename(#[from] RenameError),
+
+    /// A lint plugin could not be loaded or run.
+    #[cfg(feature = "plugins")]
+    #[error(transparent)]
+    Plugin(#[from] PluginError),
+
+    /// A diagram could not be laid out or rendered.
+    #[error(transparent)]
+    Diagram(#[from] DiagramError),
+
+    /// A connector could not be routed.
+    #[error(transparent)]
+    Routing(#[from] RoutingError),
+
+    /// A diagram could not be exported as Markdown.
+    #[error(transparent)]
+    Markdown(#[from] MarkdownExportError),
+
+    /// A diagram could not be exported as PDF.
+    #[cfg(feature = "pdf")]
+    #[error(transparent)]
+    Pdf(#[from] PdfExportError),
+
+    /// A diagram could not be rasterized to PNG.
+    #[cfg(feature = "png")]
+    #[error(transparent)]
+    Png(#[from] PngExportError),
+
+    /// A model could not be exported to SQLite.
+    #[cfg(feature = "sqlite")]
+    #[error(transparent)]
+    Sqlite(#[from] SqliteExportError),
+
+    /// A model's history could not be read from git.
+    #[error(transparent)]
+    Git(#[from] GitError),
+
+    /// The issues tracking slices could not be looked up.
+    #[error(transparent)]
+    Issue(#[from] IssueError),
+
+    /// The definitions shared by models validated together could not be read.
+    #[error(transparent)]
+    Validation(#[from] ValidationError),
+
+    /// Reading or writing a file failed.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// Result type using the crate-level [`Error`].
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::yaml_parser::parse_yaml;
+
+    /// Parses and converts a model, as a library consumer would.
+    fn load(input: &str) -> Result<crate::event_model::yaml_types::YamlEventModel> {
+        let parsed = parse_yaml(input)?;
+        Ok(yaml_converter::convert_yaml_to_domain(parsed)?)
+    }
+
+    #[test]
+    fn stage_errors_convert_into_the_crate_error() {
+        assert!(matches!(load("workflow: ["), Err(Error::Parse(_))));
+
+        let unknown_swimlane = "workflow: Test\nswimlanes:\n  - backend: \"Backend\"\nevents:\n  Placed:\n    description: \"Placed\"\n    swimlane: nowhere\n";
+        let error = load(unknown_swimlane).unwrap_err();
+        assert!(matches!(error, Error::Conversion(_)));
+        assert!(error.to_string().contains("nowhere"));
+    }
+}
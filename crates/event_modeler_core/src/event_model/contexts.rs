@@ -0,0 +1,320 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Bounded contexts and the events crossing between them.
+//!
+//! Slices may declare the bounded context they belong to (`context:
+//! Billing`). An event is published by the context of the first slice in
+//! which a command or automation records it, or failing that the first slice
+//! mentioning it; other contexts whose slices react to it consume it. The
+//! [`ContextMap`] lists each context's slices and published events, and the
+//! shared events connecting contexts, so integration points stand out.
+
+use super::yaml_types::YamlEventModel;
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// The slices of one bounded context and the events it publishes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BoundedContext {
+    /// The context name, e.g. `Billing`.
+    pub name: String,
+    /// The slices in the context, in model order.
+    pub slices: Vec<String>,
+    /// The events the context publishes, in the order the slices mention them.
+    pub publishes: Vec<String>,
+}
+
+/// An event published by one context and consumed by others.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SharedEvent {
+    /// The event name.
+    pub event: String,
+    /// The context publishing the event.
+    pub published_by: String,
+    /// The other contexts reacting to the event, in model order.
+    pub consumed_by: Vec<String>,
+}
+
+/// The bounded contexts of a model and the events shared between them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ContextMap {
+    workflow: String,
+    contexts: Vec<BoundedContext>,
+    shared_events: Vec<SharedEvent>,
+}
+
+impl ContextMap {
+    /// Groups the slices of the model by context and follows each event
+    /// across the contexts of the slices connecting it.
+    pub fn from_model(model: &YamlEventModel) -> Self {
+        let events: HashSet<String> = model
+            .events
+            .keys()
+            .map(|name| name.clone().into_inner().into_inner())
+            .collect();
+
+        // Each connection with the context of the slice it is in
+        let connections: Vec<(String, String, String)> = model
+            .slices
+            .iter()
+            .filter_map(|slice| {
+                let context = slice.context.as_ref()?.clone().into_inner().into_inner();
+                Some(slice.connections.iter().map(move |connection| {
+                    (
+                        context.clone(),
+                        connection.from.entity_name(),
+                        connection.to.entity_name(),
+                    )
+                }))
+            })
+            .flatten()
+            .collect();
+
+        let mut contexts: Vec<BoundedContext> = Vec::new();
+        for slice in &model.slices {
+            let Some(context) = &slice.context else {
+                continue;
+            };
+            let name = context.clone().into_inner().into_inner();
+            let slice_name = slice.name.clone().into_inner().into_inner();
+            match contexts.iter_mut().find(|context| context.name == name) {
+                Some(context) => context.slices.push(slice_name),
+                None => contexts.push(BoundedContext {
+                    name,
+                    slices: vec![slice_name],
+                    publishes: Vec::new(),
+                }),
+            }
+        }
+
+        // Events in the order the slices first mention them
+        let mut mentioned: Vec<&String> = Vec::new();
+        for (_, from, to) in &connections {
+            for name in [from, to] {
+                if events.contains(name) && !mentioned.contains(&name) {
+                    mentioned.push(name);
+                }
+            }
+        }
+
+        let mut shared_events = Vec::new();
+        for event in mentioned {
+            let recorded_in = connections
+                .iter()
+                .find(|(_, from, to)| to == event && !events.contains(from));
+            let mentioned_in = connections
+                .iter()
+                .find(|(_, from, to)| from == event || to == event);
+            let Some((publisher, _, _)) = recorded_in.or(mentioned_in) else {
+                continue;
+            };
+            if let Some(context) = contexts.iter_mut().find(|c| &c.name == publisher) {
+                context.publishes.push(event.clone());
+            }
+
+            let mut consumed_by: Vec<String> = Vec::new();
+            for (context, from, _) in &connections {
+                if from == event && context != publisher && !consumed_by.contains(context) {
+                    consumed_by.push(context.clone());
+                }
+            }
+            if !consumed_by.is_empty() {
+                shared_events.push(SharedEvent {
+                    event: event.clone(),
+                    published_by: publisher.clone(),
+                    consumed_by,
+                });
+            }
+        }
+
+        Self {
+            workflow: model.workflow.clone().into_inner().into_inner(),
+            contexts,
+            shared_events,
+        }
+    }
+
+    /// Returns the contexts, in the order their first slice appears.
+    pub fn contexts(&self) -> &[BoundedContext] {
+        &self.contexts
+    }
+
+    /// Returns the events crossing a context boundary, in the order the
+    /// slices first mention them.
+    pub fn shared_events(&self) -> &[SharedEvent] {
+        &self.shared_events
+    }
+
+    /// Formats the map as a Markdown document: a Mermaid diagram of the
+    /// contexts joined by the events they share, a section per context, and
+    /// a table of the shared events.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# Context map of {}\n", self.workflow);
+        if self.contexts.is_empty() {
+            markdown.push_str("\nNo slice declares a `context`.\n");
+            return markdown;
+        }
+
+        markdown.push_str("\n```mermaid\nflowchart LR\n");
+        for (index, context) in self.contexts.iter().enumerate() {
+            markdown.push_str(&format!(
+                "  c{index}[\"{}\"]\n",
+                mermaid_label(&context.name)
+            ));
+        }
+        let index_of = |name: &str| self.contexts.iter().position(|c| c.name == name);
+        let mut relationships: Vec<(usize, usize, Vec<&str>)> = Vec::new();
+        for shared in &self.shared_events {
+            let Some(upstream) = index_of(&shared.published_by) else {
+                continue;
+            };
+            for consumer in &shared.consumed_by {
+                let Some(downstream) = index_of(consumer) else {
+                    continue;
+                };
+                match relationships
+                    .iter_mut()
+                    .find(|(from, to, _)| (*from, *to) == (upstream, downstream))
+                {
+                    Some((_, _, events)) => events.push(&shared.event),
+                    None => relationships.push((upstream, downstream, vec![&shared.event])),
+                }
+            }
+        }
+        for (upstream, downstream, events) in relationships {
+            markdown.push_str(&format!(
+                "  c{upstream} -- \"{}\" --> c{downstream}\n",
+                mermaid_label(&events.join(", "))
+            ));
+        }
+        markdown.push_str("```\n");
+
+        for context in &self.contexts {
+            markdown.push_str(&format!("\n## {}\n\n", context.name));
+            let publishes = if context.publishes.is_empty() {
+                "none".to_string()
+            } else {
+                context.publishes.join(", ")
+            };
+            markdown.push_str(&format!("- Slices: {}\n", context.slices.join(", ")));
+            markdown.push_str(&format!("- Publishes: {publishes}\n"));
+        }
+
+        markdown.push_str("\n## Shared events\n\n");
+        if self.shared_events.is_empty() {
+            markdown.push_str("No event crosses a context boundary.\n");
+            return markdown;
+        }
+        markdown.push_str("| Event | Published by | Consumed by |\n|---|---|---|\n");
+        for shared in &self.shared_events {
+            markdown.push_str(&format!(
+                "| {} | {} | {} |\n",
+                shared.event,
+                shared.published_by,
+                shared.consumed_by.join(", ")
+            ));
+        }
+        markdown
+    }
+
+    /// Formats the map as JSON.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Escapes text for a quoted Mermaid label.
+fn mermaid_label(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+    const MODEL: &str = r#"workflow: Shop
+swimlanes:
+  - backend: "Backend"
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+  ShipOrder:
+    description: "Ship an order"
+    swimlane: backend
+  SendInvoice:
+    description: "Send an invoice"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+  OrderShipped:
+    description: "An order was shipped"
+    swimlane: backend
+slices:
+  - name: Ordering
+    context: Sales
+    connections:
+      - PlaceOrder -> OrderPlaced
+  - name: Shipping
+    context: Fulfillment
+    connections:
+      - OrderPlaced -> ShipOrder
+      - ShipOrder -> OrderShipped
+  - name: Invoicing
+    context: Billing
+    connections:
+      - OrderPlaced -> SendInvoice
+  - name: Untracked
+    connections:
+      - OrderShipped -> SendInvoice
+"#;
+
+    fn map() -> ContextMap {
+        let parsed = yaml_parser::parse_yaml(MODEL).unwrap();
+        ContextMap::from_model(&yaml_converter::convert_yaml_to_domain(parsed).unwrap())
+    }
+
+    #[test]
+    fn events_are_published_where_they_are_recorded() {
+        let map = map();
+
+        let publishes: Vec<(&str, &[String])> = map
+            .contexts()
+            .iter()
+            .map(|context| (context.name.as_str(), context.publishes.as_slice()))
+            .collect();
+        assert_eq!(
+            publishes,
+            [
+                ("Sales", &["OrderPlaced".to_string()][..]),
+                ("Fulfillment", &["OrderShipped".to_string()][..]),
+                ("Billing", &[][..]),
+            ]
+        );
+        assert_eq!(
+            map.shared_events(),
+            [SharedEvent {
+                event: "OrderPlaced".to_string(),
+                published_by: "Sales".to_string(),
+                consumed_by: vec!["Fulfillment".to_string(), "Billing".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn markdown_draws_the_contexts_joined_by_shared_events() {
+        let markdown = map().to_markdown();
+
+        assert!(markdown.contains(
+            "flowchart LR\n  c0[\"Sales\"]\n  c1[\"Fulfillment\"]\n  c2[\"Billing\"]\n  c0 -- \"OrderPlaced\" --> c1\n  c0 -- \"OrderPlaced\" --> c2\n"
+        ));
+        assert!(
+            markdown.contains("## Fulfillment\n\n- Slices: Shipping\n- Publishes: OrderShipped\n")
+        );
+        assert!(markdown.contains("| OrderPlaced | Sales | Fulfillment, Billing |\n"));
+    }
+}
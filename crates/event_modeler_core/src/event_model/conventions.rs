@@ -0,0 +1,242 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Compliance with a model's envelope conventions.
+//!
+//! Event-driven systems trace messages through correlation and causation ids
+//! carried on every event. A model may declare such fields under
+//! `conventions`: `envelope` fields every event must include, and `propagate`
+//! fields every command must declare so the events it records can carry them
+//! on. The [`ConventionReport`] lists the events and commands missing any,
+//! for governance reviews; nothing is checked when neither list is declared.
+
+use super::yaml_types::{FieldDefinition, FieldName, YamlEventModel};
+use crate::infrastructure::warnings::{WarningKind, Warnings};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// An event or command and the convention fields it lacks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EntityCompliance {
+    /// The entity type, `event` or `command`.
+    pub kind: &'static str,
+    /// The entity name.
+    pub name: String,
+    /// The required fields it does not declare, in convention order.
+    pub missing: Vec<String>,
+}
+
+/// How far a model's events and commands follow its envelope conventions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ConventionReport {
+    workflow: String,
+    envelope: Vec<String>,
+    propagate: Vec<String>,
+    entities: Vec<EntityCompliance>,
+}
+
+impl ConventionReport {
+    /// Checks every event for the envelope fields and every command for the
+    /// propagated fields, ordered by type and then name.
+    pub fn from_model(model: &YamlEventModel) -> Self {
+        let names = |fields: &[FieldName]| -> Vec<String> {
+            fields
+                .iter()
+                .map(|field| field.clone().into_inner().into_inner())
+                .collect()
+        };
+        let envelope = names(&model.conventions.envelope);
+        let propagate = names(&model.conventions.propagate);
+
+        let mut entities: Vec<EntityCompliance> = Vec::new();
+        if !envelope.is_empty() {
+            entities.extend(model.events.iter().map(|(name, def)| {
+                compliance(
+                    "event",
+                    name.clone().into_inner().into_inner(),
+                    &def.data,
+                    &envelope,
+                )
+            }));
+        }
+        if !propagate.is_empty() {
+            entities.extend(model.commands.iter().map(|(name, def)| {
+                compliance(
+                    "command",
+                    name.clone().into_inner().into_inner(),
+                    &def.data,
+                    &propagate,
+                )
+            }));
+        }
+        entities.sort_by(|a, b| (a.kind != "event", &a.name).cmp(&(b.kind != "event", &b.name)));
+
+        Self {
+            workflow: model.workflow.clone().into_inner().into_inner(),
+            envelope,
+            propagate,
+            entities,
+        }
+    }
+
+    /// Returns whether the model declares any convention.
+    pub fn is_configured(&self) -> bool {
+        !self.envelope.is_empty() || !self.propagate.is_empty()
+    }
+
+    /// Returns the checked events, then commands, each ordered by name.
+    pub fn entities(&self) -> &[EntityCompliance] {
+        &self.entities
+    }
+
+    /// Returns the events and commands lacking a required field.
+    pub fn violations(&self) -> impl Iterator<Item = &EntityCompliance> {
+        self.entities
+            .iter()
+            .filter(|entity| !entity.missing.is_empty())
+    }
+
+    /// Reports each event or command lacking a required field.
+    pub fn warn(&self, warnings: &mut Warnings) {
+        for entity in self.violations() {
+            let role = if entity.kind == "event" {
+                "envelope"
+            } else {
+                "propagated"
+            };
+            warnings.warn(
+                WarningKind::MissingConventionField,
+                format!(
+                    "The {} '{}' lacks the {role} fields {}",
+                    entity.kind,
+                    entity.name,
+                    entity.missing.join(", ")
+                ),
+            );
+        }
+    }
+
+    /// Formats the report as a Markdown document: the conventions, the share
+    /// of entities following them, then each entity lacking a field.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# Envelope conventions of {}\n", self.workflow);
+        if !self.is_configured() {
+            markdown.push_str("\nThe model declares no conventions.\n");
+            return markdown;
+        }
+
+        if !self.envelope.is_empty() {
+            markdown.push_str(&format!("\nEvents include: {}\n", self.envelope.join(", ")));
+        }
+        if !self.propagate.is_empty() {
+            markdown.push_str(&format!(
+                "\nCommands declare: {}\n",
+                self.propagate.join(", ")
+            ));
+        }
+        let violations: Vec<&EntityCompliance> = self.violations().collect();
+        let compliant = self.entities.len() - violations.len();
+        markdown.push_str(&format!(
+            "\n{compliant} of {} entities compliant.\n",
+            self.entities.len()
+        ));
+        if violations.is_empty() {
+            return markdown;
+        }
+
+        markdown.push_str("\n| Entity | Type | Missing |\n|---|---|---|\n");
+        for entity in violations {
+            markdown.push_str(&format!(
+                "| {} | {} | {} |\n",
+                entity.name,
+                entity.kind,
+                entity.missing.join(", ")
+            ));
+        }
+        markdown
+    }
+
+    /// Formats the report as JSON.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Checks an entity's data for the required fields.
+fn compliance(
+    kind: &'static str,
+    name: String,
+    data: &HashMap<FieldName, FieldDefinition>,
+    required: &[String],
+) -> EntityCompliance {
+    let declared: Vec<String> = data
+        .keys()
+        .map(|field| field.clone().into_inner().into_inner())
+        .collect();
+    EntityCompliance {
+        kind,
+        name,
+        missing: required
+            .iter()
+            .filter(|field| !declared.contains(field))
+            .cloned()
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+    #[test]
+    fn lists_events_and_commands_lacking_convention_fields() {
+        let yaml = r#"workflow: Shop
+swimlanes:
+  - backend: "Backend"
+conventions:
+  envelope: [correlation_id, causation_id]
+  propagate: [correlation_id]
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+    data:
+      correlation_id: CorrelationId
+  ShipOrder:
+    description: "Ship an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+    data:
+      correlation_id: CorrelationId
+      causation_id: CausationId
+  OrderShipped:
+    description: "An order was shipped"
+    swimlane: backend
+    data:
+      correlation_id: CorrelationId
+"#;
+        let model =
+            yaml_converter::convert_yaml_to_domain(yaml_parser::parse_yaml(yaml).unwrap()).unwrap();
+
+        let report = ConventionReport::from_model(&model);
+
+        let violations: Vec<(&str, &[String])> = report
+            .violations()
+            .map(|entity| (entity.name.as_str(), entity.missing.as_slice()))
+            .collect();
+        assert_eq!(
+            violations,
+            [
+                ("OrderShipped", &["causation_id".to_string()][..]),
+                ("ShipOrder", &["correlation_id".to_string()][..]),
+            ]
+        );
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("2 of 4 entities compliant.\n"));
+        assert!(markdown.contains("| ShipOrder | command | correlation_id |\n"));
+    }
+}
@@ -10,11 +10,12 @@ use crate::event_model::diagram::{
 use crate::event_model::entities::EntityId;
 use crate::event_model::registry::{Empty, EntityRegistry};
 use crate::infrastructure::parsing::simple_parser::{ParsedEntity, ParsedEventModel};
-use crate::infrastructure::types::{NonEmpty, NonEmptyString, NonNegativeInt};
+use crate::infrastructure::types::{InvariantError, NonEmpty, NonEmptyString, NonNegativeInt};
 use std::collections::HashMap;
 
 /// Errors that can occur during conversion.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum ConversionError {
     /// No swimlanes found in the parsed model.
     #[error("Event model must contain at least one swimlane")]
@@ -31,6 +32,10 @@ pub enum ConversionError {
     /// Failed to create a non-empty collection.
     #[error("Failed to create non-empty collection: {0}")]
     NonEmptyCreationFailed(String),
+
+    /// The converter broke one of its own invariants.
+    #[error(transparent)]
+    Invariant(#[from] InvariantError),
 }
 
 /// Convert a parsed event model into a domain diagram model.
@@ -93,7 +98,7 @@ pub fn convert_to_diagram(
             };
 
             // Create a unique entity ID
-            let entity_id = EntityId::new(NonEmptyString::parse(entity_name.to_string()).unwrap());
+            let entity_id = EntityId::new(NonEmptyString::derived(entity_name.to_string())?);
             entity_lookup.insert(entity_name.to_string(), entity_id.clone());
             entity_type_lookup.insert(entity_id.clone(), entity_type);
             lane_entities.push(entity_id.clone());
@@ -142,31 +147,19 @@ pub fn convert_to_diagram(
     }
 
     // Convert to NonEmpty
-    let (first_swimlane, rest_swimlanes) = swimlanes
-        .split_first()
-        .ok_or_else(|| ConversionError::NonEmptyCreationFailed("swimlanes".to_string()))?;
-    let swimlanes = NonEmpty::from_head_and_tail(first_swimlane.clone(), rest_swimlanes.to_vec());
+    let swimlanes = NonEmpty::try_from(swimlanes)
+        .map_err(|_| ConversionError::NonEmptyCreationFailed("swimlanes".to_string()))?;
 
     // Create a default slice containing all entities and connections
     let default_slice = Slice {
-        id: SliceId::new(
-            NonEmptyString::parse("default_slice".to_string())
-                .expect("Default slice ID is always non-empty"),
-        ),
-        name: SliceName::new(
-            NonEmptyString::parse("Full Model".to_string())
-                .expect("Default slice name is always non-empty"),
-        ),
+        id: SliceId::new(NonEmptyString::derived("default_slice".to_string())?),
+        name: SliceName::new(NonEmptyString::derived("Full Model".to_string())?),
         boundaries: SliceBoundaries {
             start_x: HorizontalPosition::new(NonNegativeInt::new(0)),
             end_x: HorizontalPosition::new(NonNegativeInt::new(1000)),
         },
-        entities: {
-            let (first_entity, rest_entities) = all_entity_ids.split_first().ok_or_else(|| {
-                ConversionError::NonEmptyCreationFailed("slice entities".to_string())
-            })?;
-            NonEmpty::from_head_and_tail(first_entity.clone(), rest_entities.to_vec())
-        },
+        entities: NonEmpty::try_from(all_entity_ids)
+            .map_err(|_| ConversionError::NonEmptyCreationFailed("slice entities".to_string()))?,
         connections: connectors,
         acceptance_criteria: None,
     };
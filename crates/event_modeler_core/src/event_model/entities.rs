@@ -7,15 +7,16 @@
 //! All types use compile-time validation to ensure invariants are
 //! maintained throughout the application.
 
+use super::yaml_types::Documentation;
 use crate::infrastructure::types::{
-    EventName as SafeEventName, File, MarkdownFile, MaybeExists, NonEmpty, NonEmptyString,
-    NonNegativeInt, TypedPath,
+    EventName as SafeEventName, NonEmpty, NonEmptyString, NonNegativeInt,
 };
 use nutype::nutype;
+use serde::Serialize;
 use std::collections::HashMap;
 
 /// Type of entity in the event model.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 pub enum EntityType {
     /// UI wireframe entity.
     Wireframe,
@@ -33,6 +34,21 @@ pub enum EntityType {
     Automation,
 }
 
+impl EntityType {
+    /// Returns the name of the entity type as written in a model.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Wireframe => "wireframe",
+            Self::Command => "command",
+            Self::Event => "event",
+            Self::View => "view",
+            Self::Projection => "projection",
+            Self::Query => "query",
+            Self::Automation => "automation",
+        }
+    }
+}
+
 /// A UI wireframe showing user interface elements.
 #[derive(Debug, Clone)]
 pub struct Wireframe {
@@ -44,8 +60,8 @@ pub struct Wireframe {
     pub inputs: NonEmpty<InputField>,
     /// Output fields displayed in the UI.
     pub outputs: NonEmpty<OutputField>,
-    /// Optional link to detailed documentation.
-    pub documentation: Option<TypedPath<MarkdownFile, File, MaybeExists>>,
+    /// Optional detailed documentation, inline or in a linked file.
+    pub documentation: Option<Documentation>,
 }
 
 /// A command representing a user's intention to change state.
@@ -63,8 +79,8 @@ pub struct Command {
     pub data_schema: Option<HashMap<FieldName, FieldDefinition>>,
     /// Test scenarios for this command.
     pub test_scenarios: Option<HashMap<TestScenarioName, TestScenario>>,
-    /// Optional link to detailed documentation.
-    pub documentation: Option<TypedPath<MarkdownFile, File, MaybeExists>>,
+    /// Optional detailed documentation, inline or in a linked file.
+    pub documentation: Option<Documentation>,
 }
 
 /// An event representing a state change that has occurred.
@@ -78,8 +94,8 @@ pub struct Event {
     pub timestamp: EventTimestamp,
     /// Data recorded with the event.
     pub data: NonEmpty<EventDataField>,
-    /// Optional link to detailed documentation.
-    pub documentation: Option<TypedPath<MarkdownFile, File, MaybeExists>>,
+    /// Optional detailed documentation, inline or in a linked file.
+    pub documentation: Option<Documentation>,
 }
 
 /// A projection representing a derived read model.
@@ -93,8 +109,8 @@ pub struct Projection {
     pub sources: NonEmpty<EventId>,
     /// Fields available in the projection with type annotations.
     pub fields: HashMap<FieldName, FieldType>,
-    /// Optional link to detailed documentation.
-    pub documentation: Option<TypedPath<MarkdownFile, File, MaybeExists>>,
+    /// Optional detailed documentation, inline or in a linked file.
+    pub documentation: Option<Documentation>,
 }
 
 /// A query for retrieving data from a projection.
@@ -108,8 +124,8 @@ pub struct Query {
     pub inputs: HashMap<FieldName, FieldType>,
     /// Output specification (can be single or one-of multiple options).
     pub outputs: OutputSpec,
-    /// Optional link to detailed documentation.
-    pub documentation: Option<TypedPath<MarkdownFile, File, MaybeExists>>,
+    /// Optional detailed documentation, inline or in a linked file.
+    pub documentation: Option<Documentation>,
 }
 
 /// A view representing a UI screen with component hierarchy.
@@ -121,8 +137,8 @@ pub struct View {
     pub name: ViewName,
     /// UI components in this view.
     pub components: NonEmpty<Component>,
-    /// Optional link to detailed documentation.
-    pub documentation: Option<TypedPath<MarkdownFile, File, MaybeExists>>,
+    /// Optional detailed documentation, inline or in a linked file.
+    pub documentation: Option<Documentation>,
 }
 
 /// An automation that triggers actions based on events.
@@ -136,8 +152,8 @@ pub struct Automation {
     pub trigger: EventId,
     /// Command executed by this automation.
     pub action: CommandId,
-    /// Optional link to detailed documentation.
-    pub documentation: Option<TypedPath<MarkdownFile, File, MaybeExists>>,
+    /// Optional detailed documentation, inline or in a linked file.
+    pub documentation: Option<Documentation>,
 }
 
 // Distinct newtype wrappers using nutype without validation
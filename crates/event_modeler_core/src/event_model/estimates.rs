@@ -0,0 +1,327 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Estimates of slices and their roll-up.
+//!
+//! Slices may carry an `estimate`: story points (`estimate: 5`) or a t-shirt
+//! size (`estimate: M`). A slice belongs to the owner of most of the entities
+//! it connects, the first of them breaking ties. The [`EstimateRollup`] adds
+//! up the estimates of the whole workflow and of each owner, and counts the
+//! slices nobody has estimated yet.
+
+use super::ownership::OwnershipReport;
+use super::yaml_types::{Estimate, TShirtSize, YamlEventModel};
+use serde::Serialize;
+
+/// The estimate of one slice and who it falls to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EstimatedSlice {
+    /// The slice name.
+    pub name: String,
+    /// The owner of most entities in the slice, if any has one.
+    pub owner: Option<String>,
+    /// The estimate as written, e.g. `5` or `M`; `None` if not estimated.
+    pub estimate: Option<String>,
+}
+
+/// How many slices are estimated at one t-shirt size.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SizeCount {
+    /// The size, e.g. `M`.
+    pub size: &'static str,
+    /// The number of slices of that size.
+    pub slices: usize,
+}
+
+/// The estimates of a group of slices added up.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Tally {
+    /// The number of slices in the group, estimated or not.
+    pub slices: usize,
+    /// The story points of the slices estimated in points.
+    pub points: u32,
+    /// The slices estimated in t-shirt sizes, smallest size first.
+    pub sizes: Vec<SizeCount>,
+    /// The number of slices without an estimate.
+    pub unestimated: usize,
+}
+
+impl Tally {
+    /// Adds a slice with the given estimate to the tally.
+    fn add(&mut self, estimate: Option<Estimate>) {
+        self.slices += 1;
+        match estimate {
+            Some(Estimate::Points(points)) => self.points += points,
+            Some(Estimate::Size(size)) => {
+                match self
+                    .sizes
+                    .iter_mut()
+                    .find(|count| count.size == size.as_str())
+                {
+                    Some(count) => count.slices += 1,
+                    None => {
+                        self.sizes.push(SizeCount {
+                            size: size.as_str(),
+                            slices: 1,
+                        });
+                        self.sizes.sort_by_key(|count| {
+                            TShirtSize::ALL
+                                .iter()
+                                .position(|s| s.as_str() == count.size)
+                        });
+                    }
+                }
+            }
+            None => self.unestimated += 1,
+        }
+    }
+
+    /// Describes the sizes, e.g. `2 × M, 1 × XL`, or `-` if there are none.
+    fn describe_sizes(&self) -> String {
+        if self.sizes.is_empty() {
+            return "-".to_string();
+        }
+        self.sizes
+            .iter()
+            .map(|count| format!("{} × {}", count.slices, count.size))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// The tally of the slices falling to one owner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OwnerTally {
+    /// The owner, or `None` for the slices without one.
+    pub owner: Option<String>,
+    /// The estimates of the owner's slices added up.
+    #[serde(flatten)]
+    pub tally: Tally,
+}
+
+/// The estimates of a model, by slice, by owner, and in total.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct EstimateRollup {
+    workflow: String,
+    slices: Vec<EstimatedSlice>,
+    owners: Vec<OwnerTally>,
+    total: Tally,
+}
+
+impl EstimateRollup {
+    /// Assigns every slice an owner and adds up the estimates.
+    pub fn from_model(model: &YamlEventModel) -> Self {
+        let ownership = OwnershipReport::from_model(model);
+        let mut rollup = Self {
+            workflow: model.workflow.clone().into_inner().into_inner(),
+            ..Self::default()
+        };
+
+        for slice in &model.slices {
+            // The owner of most entities in the slice, the first one on a tie
+            let mut counts: Vec<(&str, usize)> = Vec::new();
+            for connection in slice.connections.iter() {
+                for entity in [connection.from.entity_name(), connection.to.entity_name()] {
+                    let Some(owner) = ownership.owner_of(&entity) else {
+                        continue;
+                    };
+                    match counts.iter_mut().find(|(name, _)| *name == owner) {
+                        Some((_, count)) => *count += 1,
+                        None => counts.push((owner, 1)),
+                    }
+                }
+            }
+            let owner = counts
+                .iter()
+                .rev()
+                .max_by_key(|(_, count)| *count)
+                .map(|(owner, _)| owner.to_string());
+
+            rollup.total.add(slice.estimate);
+            match rollup.owners.iter_mut().find(|tally| tally.owner == owner) {
+                Some(tally) => tally.tally.add(slice.estimate),
+                None => {
+                    let mut tally = Tally::default();
+                    tally.add(slice.estimate);
+                    rollup.owners.push(OwnerTally {
+                        owner: owner.clone(),
+                        tally,
+                    });
+                }
+            }
+            rollup.slices.push(EstimatedSlice {
+                name: slice.name.clone().into_inner().into_inner(),
+                owner,
+                estimate: slice.estimate.map(|estimate| estimate.to_string()),
+            });
+        }
+
+        // Owners by name, with the slices nobody owns last
+        rollup
+            .owners
+            .sort_by(|a, b| (a.owner.is_none(), &a.owner).cmp(&(b.owner.is_none(), &b.owner)));
+        rollup
+    }
+
+    /// Returns the slices in model order.
+    pub fn slices(&self) -> &[EstimatedSlice] {
+        &self.slices
+    }
+
+    /// Returns the tally of each owner, by owner name, with the slices
+    /// without an owner last.
+    pub fn owners(&self) -> &[OwnerTally] {
+        &self.owners
+    }
+
+    /// Returns the tally of the whole workflow.
+    pub fn total(&self) -> &Tally {
+        &self.total
+    }
+
+    /// Formats the roll-up as a Markdown document: a table of the tallies by
+    /// owner with the workflow's total, then the estimate of each slice.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# Estimates of {}\n", self.workflow);
+        if self.slices.is_empty() {
+            markdown.push_str("\nThe model has no slices.\n");
+            return markdown;
+        }
+
+        markdown.push_str(
+            "\n| Owner | Slices | Points | Sizes | Unestimated |\n|---|---|---|---|---|\n",
+        );
+        let row = |owner: &str, tally: &Tally| {
+            format!(
+                "| {owner} | {} | {} | {} | {} |\n",
+                tally.slices,
+                tally.points,
+                tally.describe_sizes(),
+                tally.unestimated
+            )
+        };
+        for tally in &self.owners {
+            markdown.push_str(&row(
+                tally.owner.as_deref().unwrap_or("(no owner)"),
+                &tally.tally,
+            ));
+        }
+        markdown.push_str(&row("**Total**", &self.total));
+
+        markdown.push_str("\n## Slices\n\n| Slice | Owner | Estimate |\n|---|---|---|\n");
+        for slice in &self.slices {
+            markdown.push_str(&format!(
+                "| {} | {} | {} |\n",
+                slice.name,
+                slice.owner.as_deref().unwrap_or("-"),
+                slice.estimate.as_deref().unwrap_or("-")
+            ));
+        }
+        markdown
+    }
+
+    /// Formats the roll-up as JSON.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+    const MODEL: &str = r#"workflow: Shop
+swimlanes:
+  - ui: "Storefront"
+  - backend:
+      name: "Backend"
+      owner: team-orders
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+  ChargeCard:
+    description: "Charge the card"
+    swimlane: backend
+    owner: team-payments
+  AddToCart:
+    description: "Add an item to the cart"
+    swimlane: ui
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+  CardCharged:
+    description: "The card was charged"
+    swimlane: backend
+    owner: team-payments
+views:
+  Cart:
+    description: "The cart"
+    swimlane: ui
+    components:
+      - Checkout: Button
+slices:
+  - name: Ordering
+    estimate: 5
+    connections:
+      - Cart -> PlaceOrder
+      - PlaceOrder -> OrderPlaced
+  - name: Charging
+    estimate: m
+    connections:
+      - OrderPlaced -> ChargeCard
+      - ChargeCard -> CardCharged
+  - name: Refunding
+    estimate: 3
+    connections:
+      - CardCharged -> ChargeCard
+  - name: Browsing
+    connections:
+      - Cart -> AddToCart
+"#;
+
+    fn rollup() -> EstimateRollup {
+        let parsed = yaml_parser::parse_yaml(MODEL).unwrap();
+        EstimateRollup::from_model(&yaml_converter::convert_yaml_to_domain(parsed).unwrap())
+    }
+
+    #[test]
+    fn slices_fall_to_the_owner_of_most_of_their_entities() {
+        let rollup = rollup();
+
+        let owners: Vec<(&str, Option<&str>, Option<&str>)> = rollup
+            .slices()
+            .iter()
+            .map(|slice| {
+                (
+                    slice.name.as_str(),
+                    slice.owner.as_deref(),
+                    slice.estimate.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            owners,
+            [
+                ("Ordering", Some("team-orders"), Some("5")),
+                ("Charging", Some("team-payments"), Some("M")),
+                ("Refunding", Some("team-payments"), Some("3")),
+                ("Browsing", None, None),
+            ]
+        );
+        assert_eq!(rollup.total().points, 8);
+        assert_eq!(rollup.total().unestimated, 1);
+    }
+
+    #[test]
+    fn markdown_tallies_each_owner_and_the_workflow() {
+        let markdown = rollup().to_markdown();
+
+        assert!(markdown.contains(
+            "| team-orders | 1 | 5 | - | 0 |\n| team-payments | 2 | 3 | 1 × M | 0 |\n| (no owner) | 1 | 0 | - | 1 |\n| **Total** | 4 | 8 | 1 × M | 1 |\n"
+        ));
+        assert!(markdown.contains("| Charging | team-payments | M |\n"));
+    }
+}
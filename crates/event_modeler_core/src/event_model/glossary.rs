@@ -0,0 +1,465 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! The ubiquitous language of a model, as a glossary.
+//!
+//! Every entity name is a term of the domain's language. The [`Glossary`]
+//! lists them per swimlane, each with its description, its fields and their
+//! types, and where it is used: the slices mentioning it and the entities
+//! connected to it. Field names form a second vocabulary, listed with their
+//! types and the entities carrying them.
+
+use super::yaml_types::{FieldName, FieldType, OutputSpec, YamlEventModel};
+use crate::infrastructure::markup::escape_xml;
+use std::collections::{BTreeMap, HashMap};
+
+/// One entity name and what the model says about it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GlossaryTerm {
+    /// The entity name, e.g. `OrderPlaced`.
+    pub name: String,
+    /// The entity type, e.g. `event`.
+    pub kind: &'static str,
+    /// The entity's description, if it has one.
+    pub description: Option<String>,
+    /// The entity's fields and their types, ordered by name.
+    pub fields: Vec<(String, String)>,
+    /// Slices whose connections mention the entity, in model order.
+    pub slices: Vec<String>,
+    /// Entities connected to or from the entity, ordered by name.
+    pub related: Vec<String>,
+}
+
+/// The terms of one swimlane.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GlossarySection {
+    /// Display name of the swimlane.
+    pub swimlane: String,
+    /// The swimlane's terms, ordered by name.
+    pub terms: Vec<GlossaryTerm>,
+}
+
+/// One field name and where it is used.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldTerm {
+    /// The field name, e.g. `order_id`.
+    pub name: String,
+    /// The distinct types the field is declared with.
+    pub types: Vec<String>,
+    /// Entities carrying the field, ordered by name.
+    pub used_in: Vec<String>,
+}
+
+/// A glossary of the terms of a model.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Glossary {
+    workflow: String,
+    sections: Vec<GlossarySection>,
+    fields: Vec<FieldTerm>,
+}
+
+impl Glossary {
+    /// Collects the terms of a model from its entities and slices.
+    pub fn from_model(model: &YamlEventModel) -> Self {
+        let mut terms: HashMap<String, (String, GlossaryTerm)> = HashMap::new();
+        let mut add = |name: String,
+                       kind: &'static str,
+                       swimlane: String,
+                       description: Option<String>,
+                       fields: Vec<(String, String)>| {
+            terms.insert(
+                name.clone(),
+                (
+                    swimlane,
+                    GlossaryTerm {
+                        name,
+                        kind,
+                        description,
+                        fields,
+                        ..GlossaryTerm::default()
+                    },
+                ),
+            );
+        };
+
+        for (name, event) in &model.events {
+            add(
+                name.clone().into_inner().into_inner(),
+                "event",
+                event.swimlane.clone().into_inner().into_inner(),
+                Some(event.description.clone().into_inner().into_inner()),
+                typed_fields(event.data.iter().map(|(name, def)| (name, &def.field_type))),
+            );
+        }
+        for (name, command) in &model.commands {
+            add(
+                name.clone().into_inner().into_inner(),
+                "command",
+                command.swimlane.clone().into_inner().into_inner(),
+                Some(command.description.clone().into_inner().into_inner()),
+                typed_fields(
+                    command
+                        .data
+                        .iter()
+                        .map(|(name, def)| (name, &def.field_type)),
+                ),
+            );
+        }
+        for (name, view) in &model.views {
+            add(
+                name.clone().into_inner().into_inner(),
+                "view",
+                view.swimlane.clone().into_inner().into_inner(),
+                Some(view.description.clone().into_inner().into_inner()),
+                Vec::new(),
+            );
+        }
+        for (name, projection) in &model.projections {
+            add(
+                name.clone().into_inner().into_inner(),
+                "projection",
+                projection.swimlane.clone().into_inner().into_inner(),
+                Some(projection.description.clone().into_inner().into_inner()),
+                typed_fields(projection.fields.iter()),
+            );
+        }
+        for (name, query) in &model.queries {
+            let mut fields = typed_fields(query.inputs.iter());
+            if let OutputSpec::Single(outputs) = &query.outputs {
+                fields.extend(typed_fields(outputs.iter()));
+                fields.sort();
+                fields.dedup();
+            }
+            add(
+                name.clone().into_inner().into_inner(),
+                "query",
+                query.swimlane.clone().into_inner().into_inner(),
+                None,
+                fields,
+            );
+        }
+        for (name, automation) in &model.automations {
+            add(
+                name.clone().into_inner().into_inner(),
+                "automation",
+                automation.swimlane.clone().into_inner().into_inner(),
+                None,
+                Vec::new(),
+            );
+        }
+
+        for slice in &model.slices {
+            let slice_name = slice.name.clone().into_inner().into_inner();
+            for connection in &slice.connections {
+                let (from, to) = (connection.from.entity_name(), connection.to.entity_name());
+                for (name, other) in [(&from, &to), (&to, &from)] {
+                    if let Some((_, term)) = terms.get_mut(name) {
+                        if !term.slices.contains(&slice_name) {
+                            term.slices.push(slice_name.clone());
+                        }
+                        if !term.related.contains(other) {
+                            term.related.push(other.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut fields: BTreeMap<String, FieldTerm> = BTreeMap::new();
+        for (_, term) in terms.values() {
+            for (field, field_type) in &term.fields {
+                let entry = fields.entry(field.clone()).or_insert_with(|| FieldTerm {
+                    name: field.clone(),
+                    ..FieldTerm::default()
+                });
+                if !entry.types.contains(field_type) {
+                    entry.types.push(field_type.clone());
+                }
+                entry.used_in.push(term.name.clone());
+            }
+        }
+        let mut fields: Vec<FieldTerm> = fields.into_values().collect();
+        for field in &mut fields {
+            field.types.sort();
+            field.used_in.sort();
+        }
+
+        // Sections follow the swimlanes' declared order
+        let sections = model
+            .swimlanes
+            .iter()
+            .filter_map(|lane| {
+                let id = lane.id.clone().into_inner().into_inner();
+                let mut lane_terms: Vec<GlossaryTerm> = terms
+                    .values()
+                    .filter(|(swimlane, _)| *swimlane == id)
+                    .map(|(_, term)| term.clone())
+                    .collect();
+                if lane_terms.is_empty() {
+                    return None;
+                }
+                lane_terms.sort_by(|a, b| a.name.cmp(&b.name));
+                for term in &mut lane_terms {
+                    term.related.sort();
+                }
+                Some(GlossarySection {
+                    swimlane: lane.name.clone().into_inner().into_inner(),
+                    terms: lane_terms,
+                })
+            })
+            .collect();
+
+        Self {
+            workflow: model.workflow.clone().into_inner().into_inner(),
+            sections,
+            fields,
+        }
+    }
+
+    /// Returns the terms per swimlane, in the swimlanes' declared order.
+    pub fn sections(&self) -> &[GlossarySection] {
+        &self.sections
+    }
+
+    /// Returns the field names, ordered by name.
+    pub fn fields(&self) -> &[FieldTerm] {
+        &self.fields
+    }
+
+    /// Formats the glossary as a Markdown document with a section per
+    /// swimlane and one for the field names.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# Glossary of {}\n", self.workflow);
+        for section in &self.sections {
+            markdown.push_str(&format!("\n## {}\n", section.swimlane));
+            for term in &section.terms {
+                markdown.push_str(&format!("\n### {}\n\n*{}*", term.name, term.kind));
+                if let Some(description) = &term.description {
+                    markdown.push_str(&format!(" — {description}"));
+                }
+                markdown.push('\n');
+                if !term.fields.is_empty() {
+                    markdown.push('\n');
+                    for (field, field_type) in &term.fields {
+                        markdown.push_str(&format!("- `{field}`: `{field_type}`\n"));
+                    }
+                }
+                markdown.push('\n');
+                markdown.push_str(&format!("Used in: {}\n", listing(&term.slices)));
+                markdown.push_str(&format!(
+                    "Related: {}\n",
+                    listing(&linked(&term.related, |name| format!(
+                        "[{name}](#{})",
+                        anchor(name)
+                    )))
+                ));
+            }
+        }
+        if !self.fields.is_empty() {
+            markdown.push_str("\n## Fields\n\n| Field | Type | Used in |\n|---|---|---|\n");
+            for field in &self.fields {
+                markdown.push_str(&format!(
+                    "| `{}` | {} | {} |\n",
+                    field.name,
+                    field
+                        .types
+                        .iter()
+                        .map(|t| format!("`{t}`"))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    field.used_in.join(", ")
+                ));
+            }
+        }
+        markdown
+    }
+
+    /// Formats the glossary as a standalone HTML page, with the related
+    /// entities of each term linking to their own entries.
+    pub fn to_html(&self) -> String {
+        let workflow = escape_xml(&self.workflow);
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Glossary of {workflow}</title>\n</head>\n<body>\n\
+             <h1>Glossary of {workflow}</h1>\n"
+        );
+        for section in &self.sections {
+            html.push_str(&format!(
+                "<h2>{}</h2>\n<dl>\n",
+                escape_xml(&section.swimlane)
+            ));
+            for term in &section.terms {
+                let name = escape_xml(&term.name);
+                html.push_str(&format!(
+                    "<dt id=\"{}\">{name} <em>{}</em></dt>\n<dd>\n",
+                    anchor(&term.name),
+                    term.kind
+                ));
+                if let Some(description) = &term.description {
+                    html.push_str(&format!("<p>{}</p>\n", escape_xml(description)));
+                }
+                if !term.fields.is_empty() {
+                    html.push_str("<ul>\n");
+                    for (field, field_type) in &term.fields {
+                        html.push_str(&format!(
+                            "<li><code>{}</code>: <code>{}</code></li>\n",
+                            escape_xml(field),
+                            escape_xml(field_type)
+                        ));
+                    }
+                    html.push_str("</ul>\n");
+                }
+                let slices: Vec<String> = term.slices.iter().map(|s| escape_xml(s)).collect();
+                html.push_str(&format!("<p>Used in: {}</p>\n", listing(&slices)));
+                let related = linked(&term.related, |name| {
+                    format!("<a href=\"#{}\">{}</a>", anchor(name), escape_xml(name))
+                });
+                html.push_str(&format!("<p>Related: {}</p>\n</dd>\n", listing(&related)));
+            }
+            html.push_str("</dl>\n");
+        }
+        if !self.fields.is_empty() {
+            html.push_str(
+                "<h2>Fields</h2>\n<table>\n\
+                 <tr><th>Field</th><th>Type</th><th>Used in</th></tr>\n",
+            );
+            for field in &self.fields {
+                let types: Vec<String> = field.types.iter().map(|t| escape_xml(t)).collect();
+                let used_in = linked(&field.used_in, |name| {
+                    format!("<a href=\"#{}\">{}</a>", anchor(name), escape_xml(name))
+                });
+                html.push_str(&format!(
+                    "<tr><td><code>{}</code></td><td>{}</td><td>{}</td></tr>\n",
+                    escape_xml(&field.name),
+                    types.join(", "),
+                    used_in.join(", ")
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+/// Lists fields with their declared types, ordered by name.
+fn typed_fields<'a>(
+    fields: impl Iterator<Item = (&'a FieldName, &'a FieldType)>,
+) -> Vec<(String, String)> {
+    let mut fields: Vec<(String, String)> = fields
+        .map(|(name, field_type)| {
+            (
+                name.clone().into_inner().into_inner(),
+                field_type.clone().into_inner().into_inner(),
+            )
+        })
+        .collect();
+    fields.sort();
+    fields
+}
+
+/// Formats each name with `link`.
+fn linked(names: &[String], link: impl Fn(&str) -> String) -> Vec<String> {
+    names.iter().map(|name| link(name)).collect()
+}
+
+/// Joins items with commas, or says `nowhere` when there are none.
+fn listing(items: &[String]) -> String {
+    if items.is_empty() {
+        "nowhere".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+/// Returns the HTML element id of a term's entry.
+fn anchor(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+    const MODEL: &str = r#"workflow: Ordering
+swimlanes:
+  - ui: "Storefront"
+  - backend: "Order Service"
+views:
+  Cart:
+    description: "Items about to be ordered"
+    swimlane: ui
+    components:
+      - Checkout: Button
+commands:
+  PlaceOrder:
+    description: "Place an order for the cart's items"
+    swimlane: backend
+    data:
+      order_id: OrderId
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+    data:
+      order_id: OrderId
+      total: Money
+slices:
+  - name: Checkout
+    connections:
+      - Cart.Checkout -> PlaceOrder
+      - PlaceOrder -> OrderPlaced
+"#;
+
+    fn glossary() -> Glossary {
+        let model = yaml_converter::convert_yaml_to_domain(yaml_parser::parse_yaml(MODEL).unwrap())
+            .unwrap();
+        Glossary::from_model(&model)
+    }
+
+    #[test]
+    fn terms_are_grouped_by_swimlane_with_reverse_references() {
+        let glossary = glossary();
+
+        let sections: Vec<&str> = glossary
+            .sections()
+            .iter()
+            .map(|section| section.swimlane.as_str())
+            .collect();
+        assert_eq!(sections, ["Storefront", "Order Service"]);
+
+        let place_order = &glossary.sections()[1].terms[1];
+        assert_eq!(place_order.name, "PlaceOrder");
+        assert_eq!(place_order.slices, ["Checkout"]);
+        assert_eq!(place_order.related, ["Cart", "OrderPlaced"]);
+    }
+
+    #[test]
+    fn fields_list_their_types_and_users() {
+        let glossary = glossary();
+
+        let order_id = &glossary.fields()[0];
+        assert_eq!(order_id.name, "order_id");
+        assert_eq!(order_id.types, ["OrderId"]);
+        assert_eq!(order_id.used_in, ["OrderPlaced", "PlaceOrder"]);
+    }
+
+    #[test]
+    fn markdown_and_html_link_related_terms() {
+        let glossary = glossary();
+
+        let markdown = glossary.to_markdown();
+        assert!(markdown.contains("### OrderPlaced\n\n*event* — An order was placed\n"));
+        assert!(markdown.contains("Related: [PlaceOrder](#placeorder)\n"));
+        assert!(markdown.contains("| `total` | `Money` | OrderPlaced |\n"));
+
+        let html = glossary.to_html();
+        assert!(html.contains("<dt id=\"placeorder\">PlaceOrder <em>command</em></dt>"));
+        assert!(html.contains("<p>Related: <a href=\"#placeorder\">PlaceOrder</a></p>"));
+        assert!(html.contains("<p>Place an order for the cart's items</p>"));
+    }
+}
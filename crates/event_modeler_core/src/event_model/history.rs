@@ -0,0 +1,287 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! How a model has grown over its history.
+//!
+//! [`HistoryReport`] computes [`ModelStats`] for past versions of a model,
+//! typically one per commit that changed it, and lays them out as a trend
+//! table in Markdown or CSV for architecture health dashboards. Versions that
+//! no longer parse are kept as rows so gaps in the trend stay visible.
+
+use super::yaml_types::YamlEventModel;
+use crate::infrastructure::git::Revision;
+use crate::infrastructure::parsing::yaml_parser::UnknownKeyPolicy;
+use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+use crate::infrastructure::warnings::Warnings;
+
+/// Size and test coverage of a model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ModelStats {
+    /// Number of events.
+    pub events: usize,
+    /// Number of commands.
+    pub commands: usize,
+    /// Number of views.
+    pub views: usize,
+    /// Number of projections.
+    pub projections: usize,
+    /// Number of queries.
+    pub queries: usize,
+    /// Number of automations.
+    pub automations: usize,
+    /// Number of slices.
+    pub slices: usize,
+    /// Number of connections across all slices.
+    pub connections: usize,
+    /// Number of test scenarios across all commands.
+    pub scenarios: usize,
+    /// Number of commands with at least one test scenario.
+    pub tested_commands: usize,
+}
+
+impl ModelStats {
+    /// Counts the entities, connections, and scenarios of a model.
+    pub fn from_model(model: &YamlEventModel) -> Self {
+        Self {
+            events: model.events.len(),
+            commands: model.commands.len(),
+            views: model.views.len(),
+            projections: model.projections.len(),
+            queries: model.queries.len(),
+            automations: model.automations.len(),
+            slices: model.slices.len(),
+            connections: model
+                .slices
+                .iter()
+                .map(|slice| slice.connections.len())
+                .sum(),
+            scenarios: model.commands.values().map(|def| def.tests.len()).sum(),
+            tested_commands: model
+                .commands
+                .values()
+                .filter(|def| !def.tests.is_empty())
+                .count(),
+        }
+    }
+
+    /// Returns the number of entities of all types.
+    pub fn entities(&self) -> usize {
+        self.events
+            + self.commands
+            + self.views
+            + self.projections
+            + self.queries
+            + self.automations
+    }
+
+    /// Returns the percentage of commands with a test scenario, rounded
+    /// down, or `None` for a model without commands.
+    pub fn test_coverage(&self) -> Option<usize> {
+        (self.commands > 0).then(|| self.tested_commands * 100 / self.commands)
+    }
+}
+
+/// The statistics of one past version of a model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    /// The abbreviated commit hash.
+    pub commit: String,
+    /// The commit date, as `YYYY-MM-DD`.
+    pub date: String,
+    /// The statistics, or `None` if this version was deleted or does not
+    /// parse.
+    pub stats: Option<ModelStats>,
+}
+
+/// Statistics of successive versions of a model, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HistoryReport {
+    entries: Vec<HistoryEntry>,
+}
+
+/// Column headings shared by the Markdown and CSV forms.
+const COLUMNS: [&str; 14] = [
+    "commit",
+    "date",
+    "entities",
+    "events",
+    "commands",
+    "views",
+    "projections",
+    "queries",
+    "automations",
+    "slices",
+    "connections",
+    "scenarios",
+    "tested_commands",
+    "test_coverage",
+];
+
+impl HistoryReport {
+    /// Parses each revision leniently, so versions written against an older
+    /// schema still count, and computes its statistics.
+    pub fn from_revisions(revisions: &[Revision]) -> Self {
+        let entries = revisions
+            .iter()
+            .map(|revision| {
+                let stats = revision.content.as_deref().and_then(|content| {
+                    // Warnings about old versions are of no interest here
+                    let mut warnings = Warnings::new();
+                    let model = yaml_parser::parse_yaml_with_policy(
+                        content,
+                        UnknownKeyPolicy::Warn,
+                        &mut warnings,
+                    )
+                    .ok()?;
+                    let model =
+                        yaml_converter::convert_yaml_to_domain_with_warnings(model, &mut warnings)
+                            .ok()?;
+                    Some(ModelStats::from_model(&model))
+                });
+                HistoryEntry {
+                    commit: revision.commit.clone(),
+                    date: revision.date.clone(),
+                    stats,
+                }
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Returns the statistics of each version, oldest first.
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    /// Formats the trend as a Markdown table.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("# Model History\n\n");
+        if self.entries.is_empty() {
+            markdown.push_str("No commit changes the model.\n");
+            return markdown;
+        }
+        markdown.push_str(&format!("| {} |\n", COLUMNS.join(" | ")));
+        markdown.push_str(&format!("|{}\n", "---|".repeat(COLUMNS.len())));
+        for entry in &self.entries {
+            let cells = match &entry.stats {
+                Some(stats) => {
+                    let coverage = stats
+                        .test_coverage()
+                        .map_or_else(|| "n/a".to_string(), |coverage| format!("{coverage}%"));
+                    stat_cells(stats, coverage)
+                }
+                None => {
+                    let mut cells = vec![String::new(); COLUMNS.len() - 2];
+                    cells[0] = "does not parse".to_string();
+                    cells
+                }
+            };
+            markdown.push_str(&format!(
+                "| {} | {} | {} |\n",
+                entry.commit,
+                entry.date,
+                cells.join(" | ")
+            ));
+        }
+        markdown
+    }
+
+    /// Formats the trend as CSV, leaving the cells of unparseable versions
+    /// and the coverage of models without commands empty.
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{}\n", COLUMNS.join(","));
+        for entry in &self.entries {
+            let cells = match &entry.stats {
+                Some(stats) => {
+                    let coverage = stats
+                        .test_coverage()
+                        .map(|coverage| coverage.to_string())
+                        .unwrap_or_default();
+                    stat_cells(stats, coverage)
+                }
+                None => vec![String::new(); COLUMNS.len() - 2],
+            };
+            csv.push_str(&format!(
+                "{},{},{}\n",
+                entry.commit,
+                entry.date,
+                cells.join(",")
+            ));
+        }
+        csv
+    }
+}
+
+/// Returns the statistic cells of a row, after the commit and date.
+fn stat_cells(stats: &ModelStats, coverage: String) -> Vec<String> {
+    vec![
+        stats.entities().to_string(),
+        stats.events.to_string(),
+        stats.commands.to_string(),
+        stats.views.to_string(),
+        stats.projections.to_string(),
+        stats.queries.to_string(),
+        stats.automations.to_string(),
+        stats.slices.to_string(),
+        stats.connections.to_string(),
+        stats.scenarios.to_string(),
+        stats.tested_commands.to_string(),
+        coverage,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_growth_and_test_coverage_across_revisions() {
+        let first = r#"workflow: Orders
+swimlanes:
+  - backend: "Backend"
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+  CancelOrder:
+    description: "Cancel an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+"#;
+        let second = first.replace(
+            "    description: \"Place an order\"\n    swimlane: backend\n",
+            "    description: \"Place an order\"\n    swimlane: backend\n    tests:\n      \"Places\":\n        Given:\n        When:\n          - PlaceOrder:\n              order_id: A\n        Then:\n          - OrderPlaced:\n              order_id: A\n",
+        );
+        let revision = |commit: &str, content: Option<String>| Revision {
+            commit: commit.to_string(),
+            date: "2025-01-01".to_string(),
+            content,
+        };
+
+        let report = HistoryReport::from_revisions(&[
+            revision("aaa1111", Some(first.to_string())),
+            revision("bbb2222", Some("workflow: [".to_string())),
+            revision("ccc3333", Some(second)),
+        ]);
+
+        assert_eq!(
+            report.to_csv(),
+            "commit,date,entities,events,commands,views,projections,queries,automations,slices,connections,scenarios,tested_commands,test_coverage\n\
+             aaa1111,2025-01-01,3,1,2,0,0,0,0,1,1,0,0,0\n\
+             bbb2222,2025-01-01,,,,,,,,,,,,\n\
+             ccc3333,2025-01-01,3,1,2,0,0,0,0,1,1,1,1,50\n"
+        );
+        assert!(
+            report
+                .to_markdown()
+                .contains("| bbb2222 | 2025-01-01 | does not parse |")
+        );
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Risks and open questions attached to a model.
+//!
+//! Entities and connections written with attributes may carry a `hotspot`
+//! naming a risk raised while modeling, e.g. in a workshop. Diagrams mark
+//! them with a warning triangle, and the [`HotspotList`] gathers them so
+//! document exports can list every one in an appendix.
+
+use super::yaml_types::{Hotspot, YamlEventModel};
+use std::collections::HashMap;
+
+/// A hotspot and the entity or connection it is attached to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkedHotspot {
+    /// What the hotspot is attached to: an entity type, or `connection`.
+    pub kind: &'static str,
+    /// The entity name, or the connection written `From -> To`.
+    pub subject: String,
+    /// The slice holding the connection; `None` for entities.
+    pub slice: Option<String>,
+    /// The risk raised.
+    pub reason: String,
+}
+
+/// Every hotspot in a model.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HotspotList {
+    hotspots: Vec<MarkedHotspot>,
+}
+
+impl HotspotList {
+    /// Gathers the hotspots of the entities, ordered by name, followed by
+    /// those of the connections in slice order.
+    pub fn from_model(model: &YamlEventModel) -> Self {
+        let mut entities: Vec<MarkedHotspot> = Vec::new();
+        let mut mark = |kind, name: String, hotspot: &Option<Hotspot>| {
+            if let Some(hotspot) = hotspot {
+                entities.push(MarkedHotspot {
+                    kind,
+                    subject: name,
+                    slice: None,
+                    reason: hotspot.clone().into_inner().into_inner(),
+                });
+            }
+        };
+        for (name, def) in &model.views {
+            mark("view", name.clone().into_inner().into_inner(), &def.hotspot);
+        }
+        for (name, def) in &model.commands {
+            mark(
+                "command",
+                name.clone().into_inner().into_inner(),
+                &def.hotspot,
+            );
+        }
+        for (name, def) in &model.events {
+            mark(
+                "event",
+                name.clone().into_inner().into_inner(),
+                &def.hotspot,
+            );
+        }
+        for (name, def) in &model.projections {
+            mark(
+                "projection",
+                name.clone().into_inner().into_inner(),
+                &def.hotspot,
+            );
+        }
+        for (name, def) in &model.queries {
+            mark(
+                "query",
+                name.clone().into_inner().into_inner(),
+                &def.hotspot,
+            );
+        }
+        for (name, def) in &model.automations {
+            mark(
+                "automation",
+                name.clone().into_inner().into_inner(),
+                &def.hotspot,
+            );
+        }
+        entities.sort_by(|a, b| a.subject.cmp(&b.subject));
+
+        let mut hotspots = entities;
+        for slice in &model.slices {
+            for connection in slice.connections.iter() {
+                let Some(hotspot) = &connection.hotspot else {
+                    continue;
+                };
+                let arrow = if connection.bidirectional {
+                    "<->"
+                } else {
+                    "->"
+                };
+                hotspots.push(MarkedHotspot {
+                    kind: "connection",
+                    subject: format!(
+                        "{} {arrow} {}",
+                        connection.from.entity_name(),
+                        connection.to.entity_name()
+                    ),
+                    slice: Some(slice.name.clone().into_inner().into_inner()),
+                    reason: hotspot.clone().into_inner().into_inner(),
+                });
+            }
+        }
+        Self { hotspots }
+    }
+
+    /// Returns the hotspots: entities by name, then connections in slice order.
+    pub fn hotspots(&self) -> &[MarkedHotspot] {
+        &self.hotspots
+    }
+
+    /// Returns whether the model raises no hotspot.
+    pub fn is_empty(&self) -> bool {
+        self.hotspots.is_empty()
+    }
+
+    /// Returns the hotspot of each entity that has one, by entity name.
+    pub fn by_entity(&self) -> HashMap<&str, &str> {
+        self.hotspots
+            .iter()
+            .filter(|hotspot| hotspot.slice.is_none())
+            .map(|hotspot| (hotspot.subject.as_str(), hotspot.reason.as_str()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+    #[test]
+    fn lists_entity_hotspots_by_name_then_connections_by_slice() {
+        let yaml = r#"workflow: Payments
+swimlanes:
+  - backend: "Backend"
+commands:
+  ChargeCard:
+    description: "Charge the card"
+    swimlane: backend
+    hotspot: "Provider times out under load"
+events:
+  CardCharged:
+    description: "The card was charged"
+    swimlane: backend
+  AuthorizationExpired:
+    description: "The authorization expired"
+    swimlane: backend
+    hotspot: "Nobody knows how long authorizations last"
+slices:
+  - name: Charging
+    connections:
+      - connection: ChargeCard -> CardCharged
+        hotspot: "Duplicate charges on retry?"
+"#;
+        let model =
+            yaml_converter::convert_yaml_to_domain(yaml_parser::parse_yaml(yaml).unwrap()).unwrap();
+
+        let list = HotspotList::from_model(&model);
+
+        let listed: Vec<(&str, &str, Option<&str>)> = list
+            .hotspots()
+            .iter()
+            .map(|hotspot| {
+                (
+                    hotspot.kind,
+                    hotspot.subject.as_str(),
+                    hotspot.slice.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            listed,
+            [
+                ("event", "AuthorizationExpired", None),
+                ("command", "ChargeCard", None),
+                ("connection", "ChargeCard -> CardCharged", Some("Charging")),
+            ]
+        );
+        assert_eq!(
+            list.by_entity().get("ChargeCard"),
+            Some(&"Provider times out under load")
+        );
+    }
+}
@@ -14,10 +14,27 @@
 //! - **Automations**: System reactions to events
 //! - **Wireframes**: Visual mockups showing user interactions
 
+pub mod contexts;
+pub mod conventions;
 pub mod converter;
 pub mod diagram;
 pub mod entities;
+pub mod estimates;
+pub mod glossary;
+pub mod history;
+pub mod hotspots;
+pub mod ownership;
+pub mod patterns;
+pub mod pii;
+pub mod progress;
+pub mod reachability;
 pub mod registry;
+pub mod roles;
+pub mod scale;
+pub mod search;
+pub mod simulation;
+pub mod streams;
+pub mod terminology;
 pub mod yaml_registry;
 pub mod yaml_to_diagram_converter;
 pub mod yaml_types;
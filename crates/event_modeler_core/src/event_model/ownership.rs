@@ -0,0 +1,230 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Ownership of model entities.
+//!
+//! Swimlanes and entities may name an owner (`owner: team-payments`). An
+//! entity's own owner wins; otherwise it inherits its swimlane's owner. The
+//! [`OwnershipReport`] groups entities by their effective owner and lists
+//! those without one, so gaps in governance are easy to spot.
+
+use super::yaml_types::{OwnerName, SwimlaneId, YamlEventModel};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// An entity and how its owner was determined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedEntity {
+    /// The entity type, e.g. `command`.
+    pub kind: &'static str,
+    /// The entity name.
+    pub name: String,
+    /// The swimlane the owner was inherited from, if the entity names none itself.
+    pub inherited_from: Option<String>,
+}
+
+/// Entities grouped by owner.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OwnershipReport {
+    workflow: String,
+    owners: BTreeMap<String, Vec<OwnedEntity>>,
+    unowned: Vec<OwnedEntity>,
+    owner_by_entity: HashMap<String, String>,
+}
+
+impl OwnershipReport {
+    /// Determines the effective owner of every entity in the model.
+    pub fn from_model(model: &YamlEventModel) -> Self {
+        let lane_owners: HashMap<&SwimlaneId, &OwnerName> = model
+            .swimlanes
+            .iter()
+            .filter_map(|lane| lane.owner.as_ref().map(|owner| (&lane.id, owner)))
+            .collect();
+
+        let entities = model
+            .views
+            .iter()
+            .map(|(name, def)| ("view", name.clone().into_inner(), &def.swimlane, &def.owner))
+            .chain(model.commands.iter().map(|(name, def)| {
+                (
+                    "command",
+                    name.clone().into_inner(),
+                    &def.swimlane,
+                    &def.owner,
+                )
+            }))
+            .chain(model.events.iter().map(|(name, def)| {
+                (
+                    "event",
+                    name.clone().into_inner(),
+                    &def.swimlane,
+                    &def.owner,
+                )
+            }))
+            .chain(model.projections.iter().map(|(name, def)| {
+                (
+                    "projection",
+                    name.clone().into_inner(),
+                    &def.swimlane,
+                    &def.owner,
+                )
+            }))
+            .chain(model.queries.iter().map(|(name, def)| {
+                (
+                    "query",
+                    name.clone().into_inner(),
+                    &def.swimlane,
+                    &def.owner,
+                )
+            }))
+            .chain(model.automations.iter().map(|(name, def)| {
+                (
+                    "automation",
+                    name.clone().into_inner(),
+                    &def.swimlane,
+                    &def.owner,
+                )
+            }));
+
+        let mut report = Self {
+            workflow: model.workflow.clone().into_inner().into_inner(),
+            ..Self::default()
+        };
+        for (kind, name, swimlane, owner) in entities {
+            let (owner, inherited_from) = match (owner, lane_owners.get(swimlane)) {
+                (Some(owner), _) => (Some(owner), None),
+                (None, Some(lane_owner)) => (
+                    Some(*lane_owner),
+                    Some(swimlane.clone().into_inner().into_inner()),
+                ),
+                (None, None) => (None, None),
+            };
+            let entity = OwnedEntity {
+                kind,
+                name: name.into_inner(),
+                inherited_from,
+            };
+            match owner {
+                Some(owner) => {
+                    let owner = owner.clone().into_inner().into_inner();
+                    report
+                        .owner_by_entity
+                        .insert(entity.name.clone(), owner.clone());
+                    report.owners.entry(owner).or_default().push(entity);
+                }
+                None => report.unowned.push(entity),
+            }
+        }
+
+        for entities in report.owners.values_mut() {
+            entities.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+        report.unowned.sort_by(|a, b| a.name.cmp(&b.name));
+        report
+    }
+
+    /// Returns the owners and their entities, ordered by owner name.
+    pub fn owners(&self) -> &BTreeMap<String, Vec<OwnedEntity>> {
+        &self.owners
+    }
+
+    /// Returns the entities without an owner, ordered by name.
+    pub fn unowned(&self) -> &[OwnedEntity] {
+        &self.unowned
+    }
+
+    /// Returns the effective owner of an entity.
+    pub fn owner_of(&self, entity: &str) -> Option<&str> {
+        self.owner_by_entity.get(entity).map(String::as_str)
+    }
+}
+
+impl fmt::Display for OwnershipReport {
+    /// Formats the report as plain text, one section per owner followed by
+    /// the entities missing an owner.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ownership of {}", self.workflow)?;
+        for (owner, entities) in &self.owners {
+            write!(f, "\n\n{owner} ({})", entities.len())?;
+            write_entities(f, entities)?;
+        }
+        if self.unowned.is_empty() {
+            write!(f, "\n\nEvery entity has an owner.")
+        } else {
+            write!(f, "\n\nMissing owner ({})", self.unowned.len())?;
+            write_entities(f, &self.unowned)
+        }
+    }
+}
+
+/// Writes one indented line per entity, each starting on a new line.
+fn write_entities(f: &mut fmt::Formatter<'_>, entities: &[OwnedEntity]) -> fmt::Result {
+    for entity in entities {
+        write!(f, "\n  {} {}", entity.kind, entity.name)?;
+        if let Some(swimlane) = &entity.inherited_from {
+            write!(f, " (from swimlane '{swimlane}')")?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+    const MODEL: &str = r#"workflow: Checkout
+swimlanes:
+  - ui: "Storefront"
+  - payments:
+      name: "Payments"
+      owner: team-payments
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: ui
+    owner: team-checkout
+  ChargeCard:
+    description: "Charge the card"
+    swimlane: payments
+events:
+  CardCharged:
+    description: "The card was charged"
+    swimlane: payments
+    owner: team-ledger
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: ui
+"#;
+
+    fn report() -> OwnershipReport {
+        let parsed = yaml_parser::parse_yaml(MODEL).unwrap();
+        OwnershipReport::from_model(&yaml_converter::convert_yaml_to_domain(parsed).unwrap())
+    }
+
+    #[test]
+    fn entity_owners_override_swimlane_owners() {
+        let report = report();
+
+        assert_eq!(report.owner_of("PlaceOrder"), Some("team-checkout"));
+        assert_eq!(report.owner_of("ChargeCard"), Some("team-payments"));
+        assert_eq!(report.owner_of("CardCharged"), Some("team-ledger"));
+        assert_eq!(report.owner_of("OrderPlaced"), None);
+    }
+
+    #[test]
+    fn report_lists_entities_per_owner_and_those_missing_one() {
+        assert_eq!(
+            report().to_string(),
+            "Ownership of Checkout\n\
+             \n\
+             team-checkout (1)\n  command PlaceOrder\n\
+             \n\
+             team-ledger (1)\n  event CardCharged\n\
+             \n\
+             team-payments (1)\n  command ChargeCard (from swimlane 'payments')\n\
+             \n\
+             Missing owner (1)\n  event OrderPlaced"
+        );
+    }
+}
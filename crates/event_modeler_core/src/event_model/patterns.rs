@@ -0,0 +1,329 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! The Event Modeling pattern each slice follows.
+//!
+//! Event Modeling builds workflows from four slice shapes, read here from the
+//! connections of each slice and the types of entity they join, so models need
+//! not declare them:
+//!
+//! - a *translation* carries facts from one stream into another: an event
+//!   connected straight to an event, or an automation reacting to events in
+//!   one swimlane with commands recording events in another;
+//! - an *automation* issues commands from an automation;
+//! - a *state change* records events from a command;
+//! - a *state view* reads events into a projection, query, or view.
+//!
+//! The first shape a slice has wins. A slice with none of them, such as a
+//! command recording nothing, matches no pattern and is reported.
+
+use super::entities::EntityType;
+use super::yaml_types::{EventName, Slice, SwimlaneId, YamlEventModel};
+use crate::infrastructure::types::NonEmptyString;
+use crate::infrastructure::warnings::{WarningKind, Warnings};
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// The shape of a slice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlicePattern {
+    /// A command records events, usually issued from a view.
+    StateChange,
+    /// Events are read into a projection, query, or view.
+    StateView,
+    /// An automation reacts to events by issuing commands.
+    Automation,
+    /// Events of one stream are turned into events of another.
+    Translation,
+}
+
+impl SlicePattern {
+    /// Returns the pattern's name, e.g. `State change`.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::StateChange => "State change",
+            Self::StateView => "State view",
+            Self::Automation => "Automation",
+            Self::Translation => "Translation",
+        }
+    }
+}
+
+/// A slice and the pattern it follows.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ClassifiedSlice {
+    /// The slice name.
+    pub name: String,
+    /// The pattern, or `None` if the slice matches none.
+    pub pattern: Option<SlicePattern>,
+}
+
+/// The pattern of every slice in a model.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SlicePatterns {
+    workflow: String,
+    slices: Vec<ClassifiedSlice>,
+}
+
+impl SlicePatterns {
+    /// Classifies each slice by the shape of its connections.
+    pub fn from_model(model: &YamlEventModel) -> Self {
+        let slices = model
+            .slices
+            .iter()
+            .map(|slice| ClassifiedSlice {
+                name: slice.name.clone().into_inner().into_inner(),
+                pattern: classify(slice, model),
+            })
+            .collect();
+        Self {
+            workflow: model.workflow.clone().into_inner().into_inner(),
+            slices,
+        }
+    }
+
+    /// Returns the slices in model order.
+    pub fn slices(&self) -> &[ClassifiedSlice] {
+        &self.slices
+    }
+
+    /// Returns the pattern of the named slice, or `None` if it matches none.
+    pub fn of(&self, slice: &str) -> Option<SlicePattern> {
+        self.slices
+            .iter()
+            .find(|classified| classified.name == slice)
+            .and_then(|classified| classified.pattern)
+    }
+
+    /// Returns the names of the slices matching no pattern, in model order.
+    pub fn unmatched(&self) -> Vec<&str> {
+        self.slices
+            .iter()
+            .filter(|slice| slice.pattern.is_none())
+            .map(|slice| slice.name.as_str())
+            .collect()
+    }
+
+    /// Reports each slice matching no pattern.
+    pub fn warn(&self, warnings: &mut Warnings) {
+        for name in self.unmatched() {
+            warnings.warn(
+                WarningKind::UnpatternedSlice,
+                format!(
+                    "Slice '{name}' matches no Event Modeling pattern; it neither records, reads, nor translates events"
+                ),
+            );
+        }
+    }
+
+    /// Formats the classification as a Markdown document: the number of
+    /// slices following each pattern, then the pattern of each slice.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# Slice patterns of {}\n", self.workflow);
+        if self.slices.is_empty() {
+            markdown.push_str("\nThe model has no slices.\n");
+            return markdown;
+        }
+
+        markdown.push_str("\n| Pattern | Slices |\n|---|---|\n");
+        for pattern in [
+            SlicePattern::StateChange,
+            SlicePattern::StateView,
+            SlicePattern::Automation,
+            SlicePattern::Translation,
+        ] {
+            let count = self
+                .slices
+                .iter()
+                .filter(|slice| slice.pattern == Some(pattern))
+                .count();
+            markdown.push_str(&format!("| {} | {count} |\n", pattern.label()));
+        }
+        let unmatched = self.unmatched();
+        if !unmatched.is_empty() {
+            markdown.push_str(&format!("| None | {} |\n", unmatched.len()));
+        }
+
+        markdown.push_str("\n| Slice | Pattern |\n|---|---|\n");
+        for slice in &self.slices {
+            markdown.push_str(&format!(
+                "| {} | {} |\n",
+                slice.name,
+                slice.pattern.map_or("none", SlicePattern::label)
+            ));
+        }
+        markdown
+    }
+
+    /// Formats the classification as JSON.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Returns the pattern of a slice from the types of entity its connections
+/// join, trying translation, automation, state change, and state view in turn.
+fn classify(slice: &Slice, model: &YamlEventModel) -> Option<SlicePattern> {
+    let mut has_automation = false;
+    let mut issues_commands = false;
+    let mut records_events = false;
+    let mut reads_events = false;
+    let mut relays_events = false;
+    // Streams of the events the slice reacts to and of those it records
+    let mut read_streams: HashSet<&SwimlaneId> = HashSet::new();
+    let mut recorded_streams: HashSet<&SwimlaneId> = HashSet::new();
+    for connection in slice.connections.iter() {
+        let from = connection.from.entity_name();
+        let to = connection.to.entity_name();
+        match (model.entity_type(&from), model.entity_type(&to)) {
+            (Some(EntityType::Event), Some(EntityType::Event)) => relays_events = true,
+            (Some(EntityType::Automation), Some(EntityType::Command)) => {
+                has_automation = true;
+                issues_commands = true;
+            }
+            (Some(EntityType::Command), Some(EntityType::Event)) => {
+                records_events = true;
+                recorded_streams.extend(stream(model, &to));
+            }
+            (
+                Some(EntityType::Event),
+                Some(
+                    target @ (EntityType::Automation
+                    | EntityType::Projection
+                    | EntityType::Query
+                    | EntityType::View),
+                ),
+            ) => {
+                reads_events = true;
+                has_automation |= target == EntityType::Automation;
+                read_streams.extend(stream(model, &from));
+            }
+            (
+                Some(EntityType::Projection | EntityType::Query),
+                Some(EntityType::Query | EntityType::View),
+            ) => reads_events = true,
+            (Some(EntityType::Projection | EntityType::Query), Some(EntityType::Automation)) => {
+                has_automation = true
+            }
+            _ => {}
+        }
+    }
+
+    let crosses_streams = !read_streams.is_empty()
+        && !recorded_streams.is_empty()
+        && read_streams.is_disjoint(&recorded_streams);
+    if relays_events || (has_automation && records_events && crosses_streams) {
+        Some(SlicePattern::Translation)
+    } else if issues_commands {
+        Some(SlicePattern::Automation)
+    } else if records_events {
+        Some(SlicePattern::StateChange)
+    } else if reads_events {
+        Some(SlicePattern::StateView)
+    } else {
+        None
+    }
+}
+
+/// Returns the swimlane of the named event.
+fn stream<'a>(model: &'a YamlEventModel, event: &str) -> Option<&'a SwimlaneId> {
+    let name = EventName::new(NonEmptyString::parse(event.to_string()).ok()?);
+    model
+        .events
+        .get(&name)
+        .map(|definition| &definition.swimlane)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+    #[test]
+    fn classifies_slices_by_the_shape_of_their_connections() {
+        let yaml = r#"workflow: Shop
+swimlanes:
+  - ui: "UI"
+  - backend: "Backend"
+  - payments: "Payments"
+views:
+  Cart:
+    description: "The cart"
+    swimlane: ui
+    components:
+      - Checkout: Button
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+  ShipOrder:
+    description: "Ship an order"
+    swimlane: backend
+  MarkPaid:
+    description: "Mark an order paid"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+  OrderPaid:
+    description: "An order was paid"
+    swimlane: backend
+  PaymentSettled:
+    description: "The provider settled a payment"
+    swimlane: payments
+projections:
+  OpenOrders:
+    description: "Orders not yet shipped"
+    swimlane: backend
+    fields:
+      id: String
+automations:
+  Shipper:
+    swimlane: backend
+  Reconciler:
+    swimlane: backend
+slices:
+  - name: Ordering
+    connections:
+      - Cart -> PlaceOrder
+      - PlaceOrder -> OrderPlaced
+  - name: Listing
+    connections:
+      - OrderPlaced -> OpenOrders
+  - name: Shipping
+    connections:
+      - OrderPlaced -> Shipper
+      - Shipper -> ShipOrder
+  - name: Settling
+    connections:
+      - PaymentSettled -> Reconciler
+      - Reconciler -> MarkPaid
+      - MarkPaid -> OrderPaid
+  - name: Browsing
+    connections:
+      - Cart -> ShipOrder
+"#;
+        let model =
+            yaml_converter::convert_yaml_to_domain(yaml_parser::parse_yaml(yaml).unwrap()).unwrap();
+
+        let patterns = SlicePatterns::from_model(&model);
+
+        assert_eq!(
+            ["Ordering", "Listing", "Shipping", "Settling", "Browsing"]
+                .map(|slice| patterns.of(slice)),
+            [
+                Some(SlicePattern::StateChange),
+                Some(SlicePattern::StateView),
+                Some(SlicePattern::Automation),
+                Some(SlicePattern::Translation),
+                None,
+            ]
+        );
+        assert_eq!(patterns.unmatched(), ["Browsing"]);
+        let markdown = patterns.to_markdown();
+        assert!(markdown.contains("| Translation | 1 |\n| None | 1 |\n"));
+        assert!(markdown.contains("| Browsing | none |\n"));
+    }
+}
@@ -0,0 +1,312 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Lineage of personally identifiable information.
+//!
+//! Command and event data fields may be tagged `pii: true`. The
+//! [`PiiReport`] follows the connections of every slice downstream from each
+//! entity carrying tagged fields and lists the projections, queries, and
+//! views those fields can reach, so a privacy review knows where personal
+//! data ends up being read.
+
+use super::yaml_types::{FieldDefinition, FieldName, YamlEventModel};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fmt;
+
+/// A command or event and its data fields tagged as PII.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiiCarrier {
+    /// The entity type, `command` or `event`.
+    pub kind: &'static str,
+    /// The entity name.
+    pub name: String,
+    /// The tagged fields, ordered by name.
+    pub fields: Vec<String>,
+}
+
+/// A projection, query, or view reachable from entities carrying PII.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiiExposure {
+    /// The entity type: `projection`, `query`, or `view`.
+    pub kind: &'static str,
+    /// The entity name.
+    pub name: String,
+    /// The tagged fields reaching it, written `Entity.field` and ordered.
+    pub fields: Vec<String>,
+}
+
+/// Entities carrying PII and the read side they expose it to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PiiReport {
+    workflow: String,
+    carriers: Vec<PiiCarrier>,
+    exposures: Vec<PiiExposure>,
+}
+
+impl PiiReport {
+    /// Finds the fields tagged as PII and traces them through the
+    /// connections of every slice.
+    pub fn from_model(model: &YamlEventModel) -> Self {
+        let commands = model
+            .commands
+            .iter()
+            .map(|(name, def)| ("command", name.clone().into_inner(), &def.data));
+        let events = model
+            .events
+            .iter()
+            .map(|(name, def)| ("event", name.clone().into_inner(), &def.data));
+        let mut carriers: Vec<PiiCarrier> = commands
+            .chain(events)
+            .filter_map(|(kind, name, data)| {
+                let fields = tagged_fields(data);
+                (!fields.is_empty()).then(|| PiiCarrier {
+                    kind,
+                    name: name.into_inner(),
+                    fields,
+                })
+            })
+            .collect();
+        carriers.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut downstream: HashMap<String, Vec<String>> = HashMap::new();
+        for connection in model
+            .slices
+            .iter()
+            .flat_map(|slice| slice.connections.iter())
+        {
+            downstream
+                .entry(connection.from.entity_name())
+                .or_default()
+                .push(connection.to.entity_name());
+        }
+        // Connection endpoints are typed by naming convention, so the read
+        // side is recognized by looking names up in the entity tables.
+        let read_side: HashMap<String, (usize, &'static str)> = model
+            .projections
+            .keys()
+            .map(|name| (name.clone().into_inner(), (0, "projection")))
+            .chain(
+                model
+                    .queries
+                    .keys()
+                    .map(|name| (name.clone().into_inner(), (1, "query"))),
+            )
+            .chain(
+                model
+                    .views
+                    .keys()
+                    .map(|name| (name.clone().into_inner(), (2, "view"))),
+            )
+            .map(|(name, kind)| (name.into_inner(), kind))
+            .collect();
+
+        let mut exposed: BTreeMap<(usize, &'static str, String), Vec<String>> = BTreeMap::new();
+        for carrier in &carriers {
+            let mut seen = HashSet::from([carrier.name.clone()]);
+            let mut queue = VecDeque::from([carrier.name.clone()]);
+            while let Some(entity) = queue.pop_front() {
+                for target in downstream.get(&entity).into_iter().flatten() {
+                    if !seen.insert(target.clone()) {
+                        continue;
+                    }
+                    if let Some(&(rank, kind)) = read_side.get(target) {
+                        exposed
+                            .entry((rank, kind, target.clone()))
+                            .or_default()
+                            .extend(
+                                carrier
+                                    .fields
+                                    .iter()
+                                    .map(|field| format!("{}.{field}", carrier.name)),
+                            );
+                    }
+                    queue.push_back(target.clone());
+                }
+            }
+        }
+
+        Self {
+            workflow: model.workflow.clone().into_inner().into_inner(),
+            carriers,
+            exposures: exposed
+                .into_iter()
+                .map(|((_, kind, name), mut fields)| {
+                    fields.sort();
+                    PiiExposure { kind, name, fields }
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the commands and events with fields tagged as PII, ordered by name.
+    pub fn carriers(&self) -> &[PiiCarrier] {
+        &self.carriers
+    }
+
+    /// Returns the projections, queries, and views the tagged fields reach,
+    /// ordered by type and then name.
+    pub fn exposures(&self) -> &[PiiExposure] {
+        &self.exposures
+    }
+}
+
+impl fmt::Display for PiiReport {
+    /// Formats the report as plain text: the tagged fields, followed by the
+    /// entities exposing them.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PII lineage of {}", self.workflow)?;
+        if self.carriers.is_empty() {
+            return write!(f, "\n\nNo fields are tagged pii.");
+        }
+        write!(f, "\n\nTagged fields")?;
+        for carrier in &self.carriers {
+            write!(
+                f,
+                "\n  {} {}: {}",
+                carrier.kind,
+                carrier.name,
+                carrier.fields.join(", ")
+            )?;
+        }
+        if self.exposures.is_empty() {
+            return write!(
+                f,
+                "\n\nNo projection, query, or view is reachable from them."
+            );
+        }
+        write!(f, "\n\nExposed by")?;
+        for exposure in &self.exposures {
+            write!(
+                f,
+                "\n  {} {}: {}",
+                exposure.kind,
+                exposure.name,
+                exposure.fields.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns whether any of the data fields is tagged as PII.
+pub fn carries_pii(data: &HashMap<FieldName, FieldDefinition>) -> bool {
+    data.values().any(|field| field.pii)
+}
+
+/// Returns the names of the data fields tagged as PII, ordered.
+fn tagged_fields(data: &HashMap<FieldName, FieldDefinition>) -> Vec<String> {
+    let mut fields: Vec<String> = data
+        .iter()
+        .filter(|(_, field)| field.pii)
+        .map(|(name, _)| name.clone().into_inner().into_inner())
+        .collect();
+    fields.sort();
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+    fn report(yaml: &str) -> PiiReport {
+        let parsed = yaml_parser::parse_yaml(yaml).unwrap();
+        PiiReport::from_model(&yaml_converter::convert_yaml_to_domain(parsed).unwrap())
+    }
+
+    #[test]
+    fn tagged_fields_are_traced_to_the_read_side() {
+        let report = report(
+            r#"workflow: Signup
+swimlanes:
+  - ui: "UI"
+  - backend: "Backend"
+  - read: "Read Models"
+views:
+  ProfileScreen:
+    description: "Shows a profile"
+    swimlane: ui
+    components:
+      - Name: TextField
+commands:
+  Register:
+    description: "Register a user"
+    swimlane: backend
+    data:
+      email:
+        type: EmailAddress
+        pii: true
+      plan: Plan
+events:
+  UserRegistered:
+    description: "A user registered"
+    swimlane: backend
+    data:
+      email:
+        type: EmailAddress
+        pii: true
+      name:
+        type: FullName
+        pii: true
+  PlanChosen:
+    description: "A plan was chosen"
+    swimlane: backend
+projections:
+  Profiles:
+    description: "Profiles by user"
+    swimlane: read
+  Plans:
+    description: "Plans by user"
+    swimlane: read
+queries:
+  GetProfile:
+    swimlane: read
+    outputs:
+      one_of:
+        found: Profile
+slices:
+  - name: Signup
+    connections:
+      - Register -> UserRegistered
+      - UserRegistered -> Profiles
+      - Profiles -> GetProfile
+      - GetProfile -> ProfileScreen
+      - PlanChosen -> Plans
+"#,
+        );
+
+        assert_eq!(
+            report.to_string(),
+            "PII lineage of Signup\n\
+             \n\
+             Tagged fields\n  command Register: email\n  event UserRegistered: email, name\n\
+             \n\
+             Exposed by\n  \
+             projection Profiles: Register.email, UserRegistered.email, UserRegistered.name\n  \
+             query GetProfile: Register.email, UserRegistered.email, UserRegistered.name\n  \
+             view ProfileScreen: Register.email, UserRegistered.email, UserRegistered.name"
+        );
+    }
+
+    #[test]
+    fn models_without_tagged_fields_say_so() {
+        let report = report(
+            r#"workflow: Plain
+swimlanes:
+  - backend: "Backend"
+events:
+  Happened:
+    description: "Something happened"
+    swimlane: backend
+    data:
+      at: Timestamp
+"#,
+        );
+
+        assert!(report.carriers().is_empty());
+        assert_eq!(
+            report.to_string(),
+            "PII lineage of Plain\n\nNo fields are tagged pii."
+        );
+    }
+}
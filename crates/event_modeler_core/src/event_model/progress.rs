@@ -0,0 +1,196 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Implementation progress of a model's slices.
+//!
+//! Slices may declare a `status`: `done`, `in_progress`, or `todo`; slices
+//! without one count as to do. The [`ProgressReport`] gives the share of
+//! slices done and, when slices are estimated in story points, the share of
+//! points done, so the model doubles as a roadmap.
+
+use super::yaml_types::{Estimate, SliceStatus, YamlEventModel};
+use serde::Serialize;
+
+/// The status of one slice.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SliceProgress {
+    /// The slice name.
+    pub name: String,
+    /// The status as written, or `todo` if the slice declares none.
+    pub status: &'static str,
+    /// The slice's story points, if it is estimated in points.
+    pub points: Option<u32>,
+}
+
+/// How far implementation of a model's slices has come.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ProgressReport {
+    workflow: String,
+    slices: Vec<SliceProgress>,
+}
+
+impl ProgressReport {
+    /// Reads the status of every slice.
+    pub fn from_model(model: &YamlEventModel) -> Self {
+        let slices = model
+            .slices
+            .iter()
+            .map(|slice| SliceProgress {
+                name: slice.name.clone().into_inner().into_inner(),
+                status: slice.status.unwrap_or(SliceStatus::Todo).as_str(),
+                points: match slice.estimate {
+                    Some(Estimate::Points(points)) => Some(points),
+                    _ => None,
+                },
+            })
+            .collect();
+        Self {
+            workflow: model.workflow.clone().into_inner().into_inner(),
+            slices,
+        }
+    }
+
+    /// Returns the slices in model order.
+    pub fn slices(&self) -> &[SliceProgress] {
+        &self.slices
+    }
+
+    /// Returns the number of slices with the given status.
+    pub fn count(&self, status: SliceStatus) -> usize {
+        self.slices
+            .iter()
+            .filter(|slice| slice.status == status.as_str())
+            .count()
+    }
+
+    /// Returns the percentage of slices done, rounded down; 0 without slices.
+    pub fn percent_done(&self) -> usize {
+        percent(self.count(SliceStatus::Done), self.slices.len())
+    }
+
+    /// Returns the story points done and the points estimated in total, or
+    /// `None` if no slice is estimated in points.
+    pub fn points_done(&self) -> Option<(u32, u32)> {
+        let mut totals: Option<(u32, u32)> = None;
+        for slice in &self.slices {
+            let Some(points) = slice.points else {
+                continue;
+            };
+            let (done, total) = totals.get_or_insert((0, 0));
+            *total += points;
+            if slice.status == SliceStatus::Done.as_str() {
+                *done += points;
+            }
+        }
+        totals
+    }
+
+    /// Formats the report as a Markdown document: a summary of the slices
+    /// and points done, then the status of each slice.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# Progress of {}\n", self.workflow);
+        if self.slices.is_empty() {
+            markdown.push_str("\nThe model has no slices.\n");
+            return markdown;
+        }
+
+        markdown.push_str(&format!(
+            "\n{} of {} slices done ({}%), {} in progress, {} to do.\n",
+            self.count(SliceStatus::Done),
+            self.slices.len(),
+            self.percent_done(),
+            self.count(SliceStatus::InProgress),
+            self.count(SliceStatus::Todo)
+        ));
+        if let Some((done, total)) = self.points_done() {
+            markdown.push_str(&format!(
+                "{done} of {total} points done ({}%).\n",
+                percent(done as usize, total as usize)
+            ));
+        }
+
+        markdown.push_str("\n| Slice | Status | Points |\n|---|---|---|\n");
+        for slice in &self.slices {
+            let points = slice
+                .points
+                .map_or_else(|| "-".to_string(), |points| points.to_string());
+            markdown.push_str(&format!(
+                "| {} | {} | {points} |\n",
+                slice.name, slice.status
+            ));
+        }
+        markdown
+    }
+
+    /// Formats the report as JSON, with the percentage of slices done.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        #[derive(Serialize)]
+        struct Json<'a> {
+            #[serde(flatten)]
+            report: &'a ProgressReport,
+            percent_done: usize,
+        }
+        serde_json::to_string_pretty(&Json {
+            report: self,
+            percent_done: self.percent_done(),
+        })
+    }
+}
+
+/// Returns `part` as a percentage of `whole`, rounded down; 0 if `whole` is 0.
+fn percent(part: usize, whole: usize) -> usize {
+    if whole == 0 { 0 } else { part * 100 / whole }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+    #[test]
+    fn summarizes_slices_and_points_done() {
+        let yaml = r#"workflow: Shop
+swimlanes:
+  - backend: "Backend"
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+  ShipOrder:
+    description: "Ship an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+  OrderShipped:
+    description: "An order was shipped"
+    swimlane: backend
+slices:
+  - name: Ordering
+    status: done
+    estimate: 3
+    connections:
+      - PlaceOrder -> OrderPlaced
+  - name: Shipping
+    status: in_progress
+    estimate: 5
+    connections:
+      - OrderPlaced -> ShipOrder
+  - name: Tracking
+    connections:
+      - ShipOrder -> OrderShipped
+"#;
+        let model =
+            yaml_converter::convert_yaml_to_domain(yaml_parser::parse_yaml(yaml).unwrap()).unwrap();
+
+        let report = ProgressReport::from_model(&model);
+
+        assert_eq!(report.percent_done(), 33);
+        assert_eq!(report.points_done(), Some((3, 8)));
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("1 of 3 slices done (33%), 1 in progress, 1 to do.\n"));
+        assert!(markdown.contains("3 of 8 points done (37%).\n"));
+        assert!(markdown.contains("| Tracking | todo | - |\n"));
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Reachability of the entities in a model's slices.
+//!
+//! Everything a system does starts at a view a user acts on or an automation
+//! reacting to something. Following the connections of every slice from
+//! those starting points, [`Reachability`] finds the entities nothing leads
+//! to, which is dead functionality, and the views from which no event can
+//! ever result, which leave a user at a dead end. A model without views or
+//! automations has no starting points and is not analyzed.
+
+use super::yaml_types::YamlEventModel;
+use crate::infrastructure::warnings::{WarningKind, Warnings};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Entities unreachable from any view or automation, and views leading to
+/// no event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Reachability {
+    unreachable: Vec<String>,
+    dead_ends: Vec<String>,
+}
+
+impl Reachability {
+    /// Follows the connections of every slice from the model's views and
+    /// automations.
+    pub fn from_model(model: &YamlEventModel) -> Self {
+        let mut downstream: HashMap<String, Vec<String>> = HashMap::new();
+        let mut connected: Vec<String> = Vec::new();
+        for connection in model
+            .slices
+            .iter()
+            .flat_map(|slice| slice.connections.iter())
+        {
+            let (from, to) = (connection.from.entity_name(), connection.to.entity_name());
+            for name in [&from, &to] {
+                if !connected.contains(name) {
+                    connected.push(name.clone());
+                }
+            }
+            downstream.entry(from).or_default().push(to);
+        }
+
+        // Connection endpoints are typed by naming convention, so entity
+        // types are looked up in the model's tables instead.
+        let views: HashSet<String> = model
+            .views
+            .keys()
+            .map(|name| name.clone().into_inner().into_inner())
+            .collect();
+        let automations: HashSet<String> = model
+            .automations
+            .keys()
+            .map(|name| name.clone().into_inner().into_inner())
+            .collect();
+        let events: HashSet<String> = model
+            .events
+            .keys()
+            .map(|name| name.clone().into_inner().into_inner())
+            .collect();
+
+        let starts: Vec<&String> = connected
+            .iter()
+            .filter(|name| views.contains(*name) || automations.contains(*name))
+            .collect();
+        if starts.is_empty() {
+            return Self::default();
+        }
+
+        let mut reached = reachable_from(&starts, &downstream);
+        reached.extend(starts.iter().map(|name| (*name).clone()));
+        let mut unreachable: Vec<String> = connected
+            .iter()
+            .filter(|name| !reached.contains(*name))
+            .cloned()
+            .collect();
+        unreachable.sort();
+
+        let mut dead_ends: Vec<String> = starts
+            .iter()
+            .filter(|name| views.contains(**name))
+            .filter(|view| {
+                !reachable_from(&[**view], &downstream)
+                    .iter()
+                    .any(|name| events.contains(name))
+            })
+            .map(|view| (*view).clone())
+            .collect();
+        dead_ends.sort();
+
+        Self {
+            unreachable,
+            dead_ends,
+        }
+    }
+
+    /// Returns the entities no view or automation leads to, ordered by name.
+    pub fn unreachable(&self) -> &[String] {
+        &self.unreachable
+    }
+
+    /// Returns the views from which no event can result, ordered by name.
+    pub fn dead_ends(&self) -> &[String] {
+        &self.dead_ends
+    }
+
+    /// Records a warning for each unreachable entity and dead-end view.
+    pub fn warn(&self, warnings: &mut Warnings) {
+        for name in &self.unreachable {
+            warnings.warn(
+                WarningKind::UnreachableEntity,
+                format!("'{name}' cannot be reached from any view or automation"),
+            );
+        }
+        for name in &self.dead_ends {
+            warnings.warn(
+                WarningKind::DeadEndView,
+                format!("View '{name}' leads to no event"),
+            );
+        }
+    }
+}
+
+/// Returns the entities reachable from the starting points, excluding the
+/// starting points themselves unless a connection leads back to them.
+fn reachable_from(
+    starts: &[&String],
+    downstream: &HashMap<String, Vec<String>>,
+) -> HashSet<String> {
+    let mut reached: HashSet<String> = HashSet::new();
+    let mut queue: VecDeque<&String> = starts.iter().copied().collect();
+    while let Some(entity) = queue.pop_front() {
+        for target in downstream.get(entity).into_iter().flatten() {
+            if reached.insert(target.clone()) {
+                queue.push_back(target);
+            }
+        }
+    }
+    reached
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+    #[test]
+    fn finds_dead_functionality_and_dead_end_views() {
+        let model = yaml_converter::convert_yaml_to_domain(
+            yaml_parser::parse_yaml(
+                r#"workflow: Orders
+swimlanes:
+  - ui: "UI"
+  - backend: "Backend"
+views:
+  OrderForm:
+    description: "Place an order"
+    swimlane: ui
+    components:
+      - Submit: Button
+  Receipt:
+    description: "Shows the receipt"
+    swimlane: ui
+    components:
+      - Total: TextField
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+  RefundOrder:
+    description: "Refund an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+  OrderRefunded:
+    description: "An order was refunded"
+    swimlane: backend
+slices:
+  - name: Ordering
+    connections:
+      - OrderForm -> PlaceOrder
+      - PlaceOrder -> OrderPlaced
+      - OrderPlaced -> Receipt
+  - name: Refunding
+    connections:
+      - RefundOrder -> OrderRefunded
+"#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let reachability = Reachability::from_model(&model);
+        assert_eq!(reachability.unreachable(), ["OrderRefunded", "RefundOrder"]);
+        assert_eq!(reachability.dead_ends(), ["Receipt"]);
+    }
+}
@@ -324,12 +324,10 @@ impl<W, C, E, P, Q, A> EntityRegistry<W, C, E, P, Q, A> {
         }
         // Check events
         if let Some((_, event)) = self.events.iter().find(|(eid, _)| eid == id) {
-            return Some(
-                crate::infrastructure::types::NonEmptyString::parse(
-                    event.name.clone().into_inner().as_str().to_string(),
-                )
-                .unwrap(),
-            );
+            return crate::infrastructure::types::NonEmptyString::parse(
+                event.name.clone().into_inner().as_str().to_string(),
+            )
+            .ok();
         }
         // Check projections
         if let Some((_, projection)) = self.projections.iter().find(|(eid, _)| eid == id) {
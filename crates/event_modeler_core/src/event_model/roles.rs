@@ -0,0 +1,304 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Swimlane roles.
+//!
+//! Event Modeling draws interfaces and automations at the top, commands and
+//! read models in the middle, and event streams at the bottom. Swimlanes may
+//! declare which of these they hold (`role: interface`), which lets the
+//! diagram order them by convention. An entity placed in the wrong kind of
+//! swimlane, such as an event in an interface swimlane, is an error unless
+//! placement is checked leniently.
+
+use super::yaml_types::{Swimlane, SwimlaneGroup, SwimlaneId, SwimlaneRole, YamlEventModel};
+use crate::infrastructure::types::NonEmpty;
+use crate::infrastructure::warnings::{WarningKind, Warnings};
+use std::collections::HashMap;
+use std::fmt;
+
+impl SwimlaneRole {
+    /// Returns the role as written in a model.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Interface => "interface",
+            Self::Application => "application",
+            Self::Stream => "stream",
+        }
+    }
+
+    /// Returns the role of swimlanes that conventionally hold an entity type,
+    /// e.g. `stream` for `event`.
+    pub fn for_kind(kind: &str) -> Self {
+        match kind {
+            "view" | "automation" => Self::Interface,
+            "event" => Self::Stream,
+            _ => Self::Application,
+        }
+    }
+}
+
+impl fmt::Display for SwimlaneRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An entity in a swimlane whose role does not suit the entity's type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Misplacement {
+    /// The entity type, e.g. `event`.
+    pub kind: &'static str,
+    /// The entity name.
+    pub name: String,
+    /// The swimlane the entity is in.
+    pub swimlane: String,
+    /// The role the swimlane declares.
+    pub role: SwimlaneRole,
+}
+
+impl Misplacement {
+    /// Returns the role of swimlanes the entity belongs in.
+    pub fn expected(&self) -> SwimlaneRole {
+        SwimlaneRole::for_kind(self.kind)
+    }
+}
+
+impl fmt::Display for Misplacement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} '{}' is in swimlane '{}', whose role is {}; {}s belong in {} swimlanes",
+            self.kind,
+            self.name,
+            self.swimlane,
+            self.role,
+            self.kind,
+            self.expected()
+        )
+    }
+}
+
+/// Every entity placed in a swimlane whose role does not suit it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MisplacedEntities(pub Vec<Misplacement>);
+
+impl fmt::Display for MisplacedEntities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Entities in swimlanes of the wrong role:")?;
+        for misplaced in &self.0 {
+            write!(f, "\n  {misplaced}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for MisplacedEntities {}
+
+/// How entities in swimlanes of the wrong role are treated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PlacementPolicy {
+    /// Fail, so the diagram never shows an entity where it does not belong.
+    #[default]
+    Reject,
+    /// Draw the entities where they are and report each one as a warning.
+    Warn,
+}
+
+/// Checks that every entity suits the role of its swimlane.
+pub fn check_placement(
+    model: &YamlEventModel,
+    policy: PlacementPolicy,
+    warnings: &mut Warnings,
+) -> Result<(), MisplacedEntities> {
+    let misplaced = misplaced_entities(model);
+    match policy {
+        PlacementPolicy::Reject if !misplaced.is_empty() => Err(MisplacedEntities(misplaced)),
+        PlacementPolicy::Reject => Ok(()),
+        PlacementPolicy::Warn => {
+            for misplacement in misplaced {
+                warnings.warn(WarningKind::MisplacedEntity, misplacement.to_string());
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Finds the entities placed in a swimlane whose declared role does not
+/// suit them, ordered by entity name.
+pub fn misplaced_entities(model: &YamlEventModel) -> Vec<Misplacement> {
+    let roles: HashMap<&SwimlaneId, SwimlaneRole> = model
+        .swimlanes
+        .iter()
+        .filter_map(|lane| lane.role.map(|role| (&lane.id, role)))
+        .collect();
+
+    let entities = model
+        .views
+        .iter()
+        .map(|(name, def)| ("view", name.clone().into_inner(), &def.swimlane))
+        .chain(
+            model
+                .commands
+                .iter()
+                .map(|(name, def)| ("command", name.clone().into_inner(), &def.swimlane)),
+        )
+        .chain(
+            model
+                .events
+                .iter()
+                .map(|(name, def)| ("event", name.clone().into_inner(), &def.swimlane)),
+        )
+        .chain(
+            model
+                .projections
+                .iter()
+                .map(|(name, def)| ("projection", name.clone().into_inner(), &def.swimlane)),
+        )
+        .chain(
+            model
+                .queries
+                .iter()
+                .map(|(name, def)| ("query", name.clone().into_inner(), &def.swimlane)),
+        )
+        .chain(
+            model
+                .automations
+                .iter()
+                .map(|(name, def)| ("automation", name.clone().into_inner(), &def.swimlane)),
+        );
+
+    let mut misplaced: Vec<Misplacement> = entities
+        .filter_map(|(kind, name, swimlane)| {
+            let role = *roles.get(swimlane)?;
+            (role != SwimlaneRole::for_kind(kind)).then(|| Misplacement {
+                kind,
+                name: name.into_inner(),
+                swimlane: swimlane.clone().into_inner().into_inner(),
+                role,
+            })
+        })
+        .collect();
+    misplaced.sort_by(|a, b| a.name.cmp(&b.name));
+    misplaced
+}
+
+/// Orders swimlanes by role: interface, then application, then stream.
+///
+/// Lanes without a role rank with application lanes, and lanes of the same
+/// rank keep their declared order. A group moves as a unit, ranked by its
+/// highest member, and its members are ordered within it.
+pub fn order_by_convention(
+    swimlanes: &NonEmpty<Swimlane>,
+    groups: &[SwimlaneGroup],
+) -> NonEmpty<Swimlane> {
+    let rank = |lane: &Swimlane| lane.role.unwrap_or(SwimlaneRole::Application);
+
+    // Gather lanes into units: a whole group, or a single ungrouped lane
+    let mut units: Vec<Vec<&Swimlane>> = Vec::new();
+    let mut unit_of_group: HashMap<usize, usize> = HashMap::new();
+    for lane in swimlanes.iter() {
+        let group = groups
+            .iter()
+            .position(|group| group.swimlanes.iter().any(|id| id == &lane.id));
+        match group.and_then(|group| unit_of_group.get(&group)) {
+            Some(&unit) => units[unit].push(lane),
+            None => {
+                if let Some(group) = group {
+                    unit_of_group.insert(group, units.len());
+                }
+                units.push(vec![lane]);
+            }
+        }
+    }
+
+    for unit in &mut units {
+        unit.sort_by_key(|lane| rank(lane));
+    }
+    units.sort_by_key(|unit| unit.iter().map(|lane| rank(lane)).min());
+
+    NonEmpty::try_from_iter(units.into_iter().flatten().cloned())
+        .unwrap_or_else(|_| swimlanes.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+    fn model(yaml: &str) -> YamlEventModel {
+        yaml_converter::convert_yaml_to_domain(yaml_parser::parse_yaml(yaml).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn orders_lanes_and_groups_by_role() {
+        let model = model(
+            r#"
+workflow: Ordering
+swimlanes:
+  - events:
+      name: "Events"
+      role: stream
+  - misc
+  - group: "Backend"
+    swimlanes:
+      - audit:
+          name: "Audit"
+          role: stream
+      - handlers:
+          name: "Handlers"
+          role: application
+  - ui:
+      name: "UI"
+      role: interface
+"#,
+        );
+
+        let ordered = order_by_convention(&model.swimlanes, &model.swimlane_groups);
+
+        let ids: Vec<_> = ordered
+            .iter()
+            .map(|lane| lane.id.clone().into_inner().into_inner())
+            .collect();
+        assert_eq!(ids, ["ui", "misc", "handlers", "audit", "events"]);
+    }
+
+    #[test]
+    fn reports_entities_in_lanes_of_another_role() {
+        let model = model(
+            r#"
+workflow: Placement
+swimlanes:
+  - ui:
+      name: "UI"
+      role: interface
+  - stream:
+      name: "Stream"
+      role: stream
+  - other
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: other
+events:
+  OrderPlaced:
+    description: "Order placed"
+    swimlane: ui
+  OrderShipped:
+    description: "Order shipped"
+    swimlane: stream
+"#,
+        );
+
+        let error =
+            check_placement(&model, PlacementPolicy::Reject, &mut Warnings::new()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Entities in swimlanes of the wrong role:\n  \
+             event 'OrderPlaced' is in swimlane 'ui', whose role is interface; events belong in stream swimlanes"
+        );
+
+        let mut warnings = Warnings::new();
+        check_placement(&model, PlacementPolicy::Warn, &mut warnings).unwrap();
+        assert_eq!(warnings.of_kind(WarningKind::MisplacedEntity).count(), 1);
+    }
+}
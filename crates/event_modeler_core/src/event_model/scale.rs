@@ -0,0 +1,183 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Expected throughput of model entities.
+//!
+//! Events and commands may state how often they occur (`volume: 10k/day`).
+//! The [`ScaleReport`] converts every stated volume to a daily figure, ranks
+//! the entities by it, totals each entity type, and lists the events and
+//! commands that state no volume, so capacity discussions start from the
+//! model rather than from memory.
+
+use super::yaml_types::{Volume, VolumePeriod, YamlEventModel};
+use std::fmt;
+
+/// An entity and its stated volume.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScaledEntity {
+    /// The entity name.
+    pub name: String,
+    /// The volume stated for it.
+    pub volume: Volume,
+}
+
+/// Stated volumes of the events and commands of a model.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScaleReport {
+    workflow: String,
+    commands: Vec<ScaledEntity>,
+    events: Vec<ScaledEntity>,
+    unscaled: Vec<(&'static str, String)>,
+}
+
+impl ScaleReport {
+    /// Collects the stated volume of every event and command in the model.
+    pub fn from_model(model: &YamlEventModel) -> Self {
+        let mut report = Self {
+            workflow: model.workflow.clone().into_inner().into_inner(),
+            ..Self::default()
+        };
+        for (name, def) in &model.commands {
+            let name = name.clone().into_inner().into_inner();
+            match &def.volume {
+                Some(volume) => report.commands.push(ScaledEntity {
+                    name,
+                    volume: volume.clone(),
+                }),
+                None => report.unscaled.push(("command", name)),
+            }
+        }
+        for (name, def) in &model.events {
+            let name = name.clone().into_inner().into_inner();
+            match &def.volume {
+                Some(volume) => report.events.push(ScaledEntity {
+                    name,
+                    volume: volume.clone(),
+                }),
+                None => report.unscaled.push(("event", name)),
+            }
+        }
+
+        let busiest_first = |a: &ScaledEntity, b: &ScaledEntity| {
+            b.volume
+                .per_day()
+                .total_cmp(&a.volume.per_day())
+                .then_with(|| a.name.cmp(&b.name))
+        };
+        report.commands.sort_by(busiest_first);
+        report.events.sort_by(busiest_first);
+        report.unscaled.sort_by(|a, b| a.1.cmp(&b.1));
+        report
+    }
+
+    /// Returns the commands stating a volume, busiest first.
+    pub fn commands(&self) -> &[ScaledEntity] {
+        &self.commands
+    }
+
+    /// Returns the events stating a volume, busiest first.
+    pub fn events(&self) -> &[ScaledEntity] {
+        &self.events
+    }
+}
+
+impl fmt::Display for ScaleReport {
+    /// Formats the report as plain text: commands and events with their
+    /// daily totals, followed by the entities stating no volume.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Scale of {}", self.workflow)?;
+        for (title, entities) in [("Commands", &self.commands), ("Events", &self.events)] {
+            if entities.is_empty() {
+                continue;
+            }
+            let total: f64 = entities.iter().map(|entity| entity.volume.per_day()).sum();
+            write!(f, "\n\n{title} (~{}/day)", format_count(total))?;
+            for entity in entities {
+                write!(f, "\n  {} {}", entity.name, entity.volume)?;
+                if entity.volume.period() != VolumePeriod::Day {
+                    write!(f, " (~{}/day)", format_count(entity.volume.per_day()))?;
+                }
+            }
+        }
+        if self.unscaled.is_empty() {
+            write!(f, "\n\nEvery event and command states a volume.")
+        } else {
+            write!(f, "\n\nMissing volume ({})", self.unscaled.len())?;
+            for (kind, name) in &self.unscaled {
+                write!(f, "\n  {kind} {name}")?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Formats a count compactly, e.g. `1.5M` or `240`.
+fn format_count(count: f64) -> String {
+    let (scaled, suffix) = if count >= 1e9 {
+        (count / 1e9, "B")
+    } else if count >= 1e6 {
+        (count / 1e6, "M")
+    } else if count >= 1e3 {
+        (count / 1e3, "k")
+    } else {
+        (count, "")
+    };
+    let rounded = format!("{scaled:.1}");
+    let trimmed = rounded.strip_suffix(".0").unwrap_or(&rounded);
+    format!("{trimmed}{suffix}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+    const MODEL: &str = r#"workflow: Checkout
+swimlanes:
+  - backend: "Backend"
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+    volume: 10k/day
+  CancelOrder:
+    description: "Cancel an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+    volume: 10k/day
+  CartViewed:
+    description: "A cart was viewed"
+    swimlane: backend
+    volume: 2/s
+"#;
+
+    #[test]
+    fn volumes_are_ranked_and_totalled_per_day() {
+        let parsed = yaml_parser::parse_yaml(MODEL).unwrap();
+        let report =
+            ScaleReport::from_model(&yaml_converter::convert_yaml_to_domain(parsed).unwrap());
+
+        assert_eq!(
+            report.to_string(),
+            "Scale of Checkout\n\
+             \n\
+             Commands (~10k/day)\n  PlaceOrder 10k/day\n\
+             \n\
+             Events (~182.8k/day)\n  CartViewed 2/s (~172.8k/day)\n  OrderPlaced 10k/day\n\
+             \n\
+             Missing volume (1)\n  command CancelOrder"
+        );
+    }
+
+    #[test]
+    fn volumes_must_be_a_count_per_period() {
+        assert_eq!(Volume::parse("1.5M/month").unwrap().per_day(), 50_000.0);
+        assert!(Volume::parse("lots/day").is_none());
+        assert!(Volume::parse("10k").is_none());
+        assert!(Volume::parse("10k/fortnight").is_none());
+        assert!(Volume::parse("0/day").is_none());
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Finding entities by name in a model.
+//!
+//! Large models are hard to find one's way around. [`SearchResults`] lists
+//! the entities whose names contain a piece of text, ignoring case, with
+//! their type, swimlane, and the slices mentioning them, so the entities can
+//! be located in the diagram or highlighted in it.
+
+use super::glossary::Glossary;
+use super::yaml_types::YamlEventModel;
+use std::fmt;
+
+/// An entity whose name matches the search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityMatch {
+    /// The entity name, e.g. `EmailVerificationMessageSent`.
+    pub name: String,
+    /// The entity type, e.g. `event`.
+    pub kind: &'static str,
+    /// Display name of the entity's swimlane.
+    pub swimlane: String,
+    /// Slices whose connections mention the entity, in model order.
+    pub slices: Vec<String>,
+}
+
+/// The entities of a model whose names contain a piece of text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResults {
+    query: String,
+    matches: Vec<EntityMatch>,
+}
+
+impl SearchResults {
+    /// Finds the entities whose names contain `query`, ignoring case, in the
+    /// swimlanes' declared order and then by name.
+    pub fn search(model: &YamlEventModel, query: &str) -> Self {
+        let needle = query.to_lowercase();
+        let matches = Glossary::from_model(model)
+            .sections()
+            .iter()
+            .flat_map(|section| {
+                section.terms.iter().map(move |term| EntityMatch {
+                    name: term.name.clone(),
+                    kind: term.kind,
+                    swimlane: section.swimlane.clone(),
+                    slices: term.slices.clone(),
+                })
+            })
+            .filter(|entity| entity.name.to_lowercase().contains(&needle))
+            .collect();
+        Self {
+            query: query.to_string(),
+            matches,
+        }
+    }
+
+    /// Returns the matching entities.
+    pub fn matches(&self) -> &[EntityMatch] {
+        &self.matches
+    }
+
+    /// Returns the names of the matching entities.
+    pub fn names(&self) -> Vec<String> {
+        self.matches
+            .iter()
+            .map(|entity| entity.name.clone())
+            .collect()
+    }
+}
+
+impl fmt::Display for SearchResults {
+    /// Formats the results as plain text, one line per entity.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.matches.len() {
+            0 => return write!(f, "No entity matches '{}'.", self.query),
+            1 => write!(f, "1 entity matches '{}':", self.query)?,
+            count => write!(f, "{count} entities match '{}':", self.query)?,
+        }
+        for entity in &self.matches {
+            write!(
+                f,
+                "\n  {} {} in swimlane '{}'",
+                entity.kind, entity.name, entity.swimlane
+            )?;
+            if entity.slices.is_empty() {
+                write!(f, ", used in no slice")?;
+            } else {
+                write!(f, ", used in {}", entity.slices.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+    fn model() -> YamlEventModel {
+        let yaml = r#"
+workflow: Signup
+swimlanes:
+  - ux: "Screens"
+  - backend: "Backend"
+events:
+  EmailVerified:
+    description: "A user verified their email"
+    swimlane: backend
+    data:
+      id: { type: Id, generated: true }
+  AccountCreated:
+    description: "An account was created"
+    swimlane: backend
+    data:
+      id: { type: Id, generated: true }
+commands:
+  VerifyEmail:
+    description: "Verify an email"
+    swimlane: backend
+    data:
+      id: { type: Id }
+views:
+  VerificationScreen:
+    description: "Where users verify"
+    swimlane: ux
+    components:
+      - Verify: Button
+slices:
+  - name: Verify
+    connections:
+      - VerificationScreen -> VerifyEmail
+      - VerifyEmail -> EmailVerified
+"#;
+        yaml_converter::convert_yaml_to_domain(yaml_parser::parse_yaml(yaml).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn finds_entities_by_part_of_their_name_ignoring_case() {
+        let results = SearchResults::search(&model(), "verif");
+
+        assert_eq!(
+            results.names(),
+            ["VerificationScreen", "EmailVerified", "VerifyEmail"]
+        );
+        assert_eq!(
+            results.to_string(),
+            "3 entities match 'verif':\n  \
+             view VerificationScreen in swimlane 'Screens', used in Verify\n  \
+             event EmailVerified in swimlane 'Backend', used in Verify\n  \
+             command VerifyEmail in swimlane 'Backend', used in Verify"
+        );
+        assert_eq!(
+            SearchResults::search(&model(), "Refund").to_string(),
+            "No entity matches 'Refund'."
+        );
+    }
+}
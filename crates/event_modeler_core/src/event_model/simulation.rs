@@ -0,0 +1,379 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Dry runs of command scenarios against the read side of a model.
+//!
+//! [`simulate`] interprets one Given/When/Then scenario: the `Given` events
+//! are folded into the projections their slices connect them to, then the
+//! `Then` events are applied on top, and the projections whose fields end up
+//! with different values are reported along with the views reading them. A
+//! projection only folds the fields it declares, so scenario data that no
+//! projection declares is reported as uncarried, pointing at read models that
+//! are missing fields or scenarios referring to data nothing keeps.
+
+use super::yaml_types::{
+    EventName, FieldName, PlaceholderValue, TestOutcome, TestScenario, YamlEventModel,
+};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fmt;
+
+/// A projection field whose value a scenario changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    /// The field name.
+    pub field: String,
+    /// The value after the `Given` events, if any had set it.
+    pub before: Option<String>,
+    /// The value after the `Then` events.
+    pub after: String,
+}
+
+/// A projection whose state a scenario changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectionChange {
+    /// The projection name.
+    pub projection: String,
+    /// The changed fields, ordered by name.
+    pub fields: Vec<FieldChange>,
+}
+
+/// The outcome of dry-running one scenario.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScenarioRun {
+    /// The command under test.
+    pub command: String,
+    /// The scenario name.
+    pub scenario: String,
+    /// The error the command is rejected with, if the scenario expects one.
+    pub rejected_with: Option<String>,
+    /// Projections the `Then` events change, ordered by name.
+    pub changes: Vec<ProjectionChange>,
+    /// Views reading a changed projection, directly or through queries,
+    /// ordered by name.
+    pub views: Vec<String>,
+    /// Event data no projection folding the event declares, written
+    /// `Event.field` and ordered.
+    pub uncarried: Vec<String>,
+}
+
+/// Dry runs of every scenario in a model.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SimulationReport {
+    workflow: String,
+    runs: Vec<ScenarioRun>,
+}
+
+impl SimulationReport {
+    /// Simulates every scenario of every command, ordered by command and
+    /// scenario name.
+    pub fn from_model(model: &YamlEventModel) -> Self {
+        let mut runs: Vec<ScenarioRun> = model
+            .commands
+            .iter()
+            .flat_map(|(command, definition)| {
+                definition.tests.iter().map(move |(scenario, test)| {
+                    let mut run = simulate(model, test);
+                    run.command = command.clone().into_inner().into_inner();
+                    run.scenario = scenario.clone().into_inner().into_inner();
+                    run
+                })
+            })
+            .collect();
+        runs.sort_by(|a, b| (&a.command, &a.scenario).cmp(&(&b.command, &b.scenario)));
+        Self {
+            workflow: model.workflow.clone().into_inner().into_inner(),
+            runs,
+        }
+    }
+
+    /// Returns the outcome of each scenario.
+    pub fn runs(&self) -> &[ScenarioRun] {
+        &self.runs
+    }
+}
+
+impl fmt::Display for SimulationReport {
+    /// Formats the report as plain text, a paragraph per scenario.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Simulation of {}", self.workflow)?;
+        if self.runs.is_empty() {
+            return write!(f, "\n\nNo command has test scenarios.");
+        }
+        for run in &self.runs {
+            write!(f, "\n\n{}: {}", run.command, run.scenario)?;
+            if let Some(error) = &run.rejected_with {
+                write!(f, "\n  rejected with {error}; nothing changes")?;
+            } else if run.changes.is_empty() {
+                write!(f, "\n  changes no projection")?;
+            }
+            for change in &run.changes {
+                let fields: Vec<String> = change
+                    .fields
+                    .iter()
+                    .map(|field| match &field.before {
+                        Some(before) => format!("{} {} (was {before})", field.field, field.after),
+                        None => format!("{} {}", field.field, field.after),
+                    })
+                    .collect();
+                write!(
+                    f,
+                    "\n  changes projection {}: {}",
+                    change.projection,
+                    fields.join(", ")
+                )?;
+            }
+            for view in &run.views {
+                write!(f, "\n  changes view {view}")?;
+            }
+            if !run.uncarried.is_empty() {
+                write!(f, "\n  no projection carries {}", run.uncarried.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Dry-runs a scenario: folds its `Given` events into the projections, then
+/// applies its `Then` events and reports what changes. The command and
+/// scenario names of the result are left empty for the caller to fill in.
+pub fn simulate(model: &YamlEventModel, scenario: &TestScenario) -> ScenarioRun {
+    let mut downstream: HashMap<String, Vec<String>> = HashMap::new();
+    for connection in model
+        .slices
+        .iter()
+        .flat_map(|slice| slice.connections.iter())
+    {
+        downstream
+            .entry(connection.from.entity_name())
+            .or_default()
+            .push(connection.to.entity_name());
+    }
+    // Connection endpoints are typed by naming convention, so projections
+    // and views are recognized by looking names up in the entity tables.
+    let projections: HashMap<String, HashSet<String>> = model
+        .projections
+        .iter()
+        .map(|(name, definition)| {
+            (
+                name.clone().into_inner().into_inner(),
+                definition
+                    .fields
+                    .keys()
+                    .map(|field| field.clone().into_inner().into_inner())
+                    .collect(),
+            )
+        })
+        .collect();
+    let views: HashSet<String> = model
+        .views
+        .keys()
+        .map(|name| name.clone().into_inner().into_inner())
+        .collect();
+
+    let mut state: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+    let mut uncarried: Vec<String> = Vec::new();
+    let mut fold = |state: &mut BTreeMap<String, BTreeMap<String, String>>,
+                    event: &EventName,
+                    fields: &HashMap<FieldName, PlaceholderValue>| {
+        let event = event.clone().into_inner().into_inner();
+        let folding: Vec<&String> = downstream
+            .get(&event)
+            .into_iter()
+            .flatten()
+            .filter(|target| projections.contains_key(*target))
+            .collect();
+        for (field, value) in fields {
+            let field = field.clone().into_inner().into_inner();
+            let value = value.clone().into_inner().into_inner();
+            let mut carried = false;
+            for projection in &folding {
+                if projections
+                    .get(*projection)
+                    .is_some_and(|declared| declared.contains(&field))
+                {
+                    carried = true;
+                    state
+                        .entry((*projection).clone())
+                        .or_default()
+                        .insert(field.clone(), value.clone());
+                }
+            }
+            let entry = format!("{event}.{field}");
+            if !carried && !uncarried.contains(&entry) {
+                uncarried.push(entry);
+            }
+        }
+    };
+
+    for event in &scenario.given {
+        fold(&mut state, &event.name, &event.fields);
+    }
+    let before = state.clone();
+    let mut rejected_with = None;
+    for outcome in scenario.then.iter() {
+        match outcome {
+            TestOutcome::Event(event) => fold(&mut state, &event.name, &event.fields),
+            TestOutcome::Error(error) => {
+                rejected_with = Some(error.name.clone().into_inner().into_inner());
+            }
+        }
+    }
+    // A rejected command records nothing, whatever else `Then` lists
+    if rejected_with.is_some() {
+        state = before.clone();
+    }
+
+    let changes: Vec<ProjectionChange> = state
+        .iter()
+        .filter_map(|(projection, after)| {
+            let previous = before.get(projection);
+            let fields: Vec<FieldChange> = after
+                .iter()
+                .filter_map(|(field, value)| {
+                    let was = previous.and_then(|previous| previous.get(field));
+                    (was != Some(value)).then(|| FieldChange {
+                        field: field.clone(),
+                        before: was.cloned(),
+                        after: value.clone(),
+                    })
+                })
+                .collect();
+            (!fields.is_empty()).then(|| ProjectionChange {
+                projection: projection.clone(),
+                fields,
+            })
+        })
+        .collect();
+
+    let mut changed_views: Vec<String> = Vec::new();
+    let mut seen: HashSet<&String> = HashSet::new();
+    let mut queue: VecDeque<&String> = changes.iter().map(|change| &change.projection).collect();
+    while let Some(entity) = queue.pop_front() {
+        for target in downstream.get(entity).into_iter().flatten() {
+            if !seen.insert(target) || projections.contains_key(target) {
+                continue;
+            }
+            // Views lead on to the write side, so the trail ends there
+            if views.contains(target) {
+                changed_views.push(target.clone());
+            } else {
+                queue.push_back(target);
+            }
+        }
+    }
+    changed_views.sort();
+    uncarried.sort();
+
+    ScenarioRun {
+        rejected_with,
+        changes,
+        views: changed_views,
+        uncarried,
+        ..ScenarioRun::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+    #[test]
+    fn scenarios_change_the_projections_their_events_fold_into() {
+        let model = yaml_converter::convert_yaml_to_domain(
+            yaml_parser::parse_yaml(
+                r#"workflow: Accounts
+swimlanes:
+  - ui: "UI"
+  - backend: "Backend"
+  - read: "Read Models"
+views:
+  AccountScreen:
+    description: "Shows an account"
+    swimlane: ui
+    components:
+      - Email: TextField
+commands:
+  ChangeEmail:
+    description: "Change an account's email"
+    swimlane: backend
+    errors:
+      UnknownAccount:
+        description: "No such account"
+    tests:
+      "Changes":
+        Given:
+          - AccountOpened:
+              account_id: A
+              email: B
+              nickname: N
+        When:
+          - ChangeEmail:
+              account_id: A
+              email: C
+        Then:
+          - EmailChanged:
+              account_id: A
+              email: C
+      "Unknown":
+        Given:
+        When:
+          - ChangeEmail:
+              account_id: A
+              email: C
+        Then:
+          - UnknownAccount:
+              account_id: A
+events:
+  AccountOpened:
+    description: "An account was opened"
+    swimlane: backend
+  EmailChanged:
+    description: "An account's email changed"
+    swimlane: backend
+projections:
+  Accounts:
+    description: "Accounts by id"
+    swimlane: read
+    fields:
+      account_id: AccountId
+      email: EmailAddress
+queries:
+  GetAccount:
+    swimlane: read
+    inputs:
+      account_id: AccountId
+    outputs:
+      one_of:
+        found:
+          email: EmailAddress
+        not_found: UnknownAccount
+slices:
+  - name: Changing
+    connections:
+      - AccountOpened -> Accounts
+      - EmailChanged -> Accounts
+      - Accounts -> GetAccount
+      - GetAccount -> AccountScreen
+      - AccountScreen -> ChangeEmail
+      - ChangeEmail -> EmailChanged
+"#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            SimulationReport::from_model(&model).to_string(),
+            "Simulation of Accounts\n\
+             \n\
+             ChangeEmail: Changes\n  \
+             changes projection Accounts: email C (was B)\n  \
+             changes view AccountScreen\n  \
+             no projection carries AccountOpened.nickname\n\
+             \n\
+             ChangeEmail: Unknown\n  \
+             rejected with UnknownAccount; nothing changes"
+        );
+    }
+}
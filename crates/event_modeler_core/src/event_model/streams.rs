@@ -0,0 +1,352 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Lifecycle of the event streams in a model.
+//!
+//! Events sharing a `stream-id` field belong to one stream, named after that
+//! field. The [`StreamReport`] lists each stream's events in the order the
+//! slices first mention them, the commands appending to it, and the
+//! projections and automations consuming it. A stream is created by an event
+//! whose stream identifier is `generated`, either on the event itself or on a
+//! command appending it; streams that nothing creates or nothing consumes are
+//! flagged.
+
+use super::yaml_types::{FieldDefinition, FieldName, YamlEventModel};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// The events of one stream and the entities around them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StreamLifecycle {
+    /// The stream identifier field, e.g. `account_id`.
+    pub stream: String,
+    /// The events of the stream, in the order the slices first mention them.
+    pub events: Vec<String>,
+    /// The events that create the stream.
+    pub creating_events: Vec<String>,
+    /// Commands appending events to the stream.
+    pub appended_by: Vec<String>,
+    /// Projections folding the stream's events.
+    pub folded_by: Vec<String>,
+    /// Automations reacting to the stream's events.
+    pub reacted_to_by: Vec<String>,
+}
+
+impl StreamLifecycle {
+    /// Returns whether no event creates the stream.
+    pub fn missing_creation(&self) -> bool {
+        self.creating_events.is_empty()
+    }
+
+    /// Returns whether no projection or automation consumes the stream.
+    pub fn unconsumed(&self) -> bool {
+        self.folded_by.is_empty() && self.reacted_to_by.is_empty()
+    }
+}
+
+/// Lifecycle of every stream in a model.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StreamReport {
+    workflow: String,
+    streams: Vec<StreamLifecycle>,
+}
+
+impl StreamReport {
+    /// Gathers the streams of the model from its events' `stream-id` fields
+    /// and the connections of its slices.
+    pub fn from_model(model: &YamlEventModel) -> Self {
+        // Connection endpoints are typed by naming convention, so entity
+        // types are looked up in the model's tables instead.
+        let commands: HashSet<String> = model
+            .commands
+            .keys()
+            .map(|name| name.clone().into_inner().into_inner())
+            .collect();
+        let projections: HashSet<String> = model
+            .projections
+            .keys()
+            .map(|name| name.clone().into_inner().into_inner())
+            .collect();
+        let automations: HashSet<String> = model
+            .automations
+            .keys()
+            .map(|name| name.clone().into_inner().into_inner())
+            .collect();
+
+        let connections: Vec<(String, String)> = model
+            .slices
+            .iter()
+            .flat_map(|slice| slice.connections.iter())
+            .map(|connection| (connection.from.entity_name(), connection.to.entity_name()))
+            .collect();
+        let mut modeled_order: Vec<String> = Vec::new();
+        for (from, to) in &connections {
+            for name in [from, to] {
+                if !modeled_order.contains(name) {
+                    modeled_order.push(name.clone());
+                }
+            }
+        }
+        let position = |name: &str| {
+            modeled_order
+                .iter()
+                .position(|modeled| modeled == name)
+                .unwrap_or(usize::MAX)
+        };
+        let in_modeled_order = |names: &mut Vec<String>| {
+            names.sort_by(|a, b| position(a).cmp(&position(b)).then_with(|| a.cmp(b)));
+        };
+
+        let command_data: HashMap<String, &HashMap<FieldName, FieldDefinition>> = model
+            .commands
+            .iter()
+            .map(|(name, def)| (name.clone().into_inner().into_inner(), &def.data))
+            .collect();
+
+        let mut streams: BTreeMap<String, StreamLifecycle> = BTreeMap::new();
+        for (name, def) in &model.events {
+            let event = name.clone().into_inner().into_inner();
+            for (field, definition) in def.data.iter().filter(|(_, def)| def.stream_id) {
+                let stream = field.clone().into_inner().into_inner();
+                let lifecycle = streams
+                    .entry(stream.clone())
+                    .or_insert_with(|| StreamLifecycle {
+                        stream: stream.clone(),
+                        ..StreamLifecycle::default()
+                    });
+                lifecycle.events.push(event.clone());
+
+                let appended_by: Vec<&String> = connections
+                    .iter()
+                    .filter(|(from, to)| *to == event && commands.contains(from))
+                    .map(|(from, _)| from)
+                    .collect();
+                let generated_by_command = appended_by.iter().any(|command| {
+                    command_data
+                        .get(command.as_str())
+                        .and_then(|data| data.get(field))
+                        .is_some_and(|field| field.generated)
+                });
+                if definition.generated || generated_by_command {
+                    lifecycle.creating_events.push(event.clone());
+                }
+                for command in appended_by {
+                    if !lifecycle.appended_by.contains(command) {
+                        lifecycle.appended_by.push(command.clone());
+                    }
+                }
+                for (_, to) in connections.iter().filter(|(from, _)| *from == event) {
+                    let consumers = if projections.contains(to) {
+                        &mut lifecycle.folded_by
+                    } else if automations.contains(to) {
+                        &mut lifecycle.reacted_to_by
+                    } else {
+                        continue;
+                    };
+                    if !consumers.contains(to) {
+                        consumers.push(to.clone());
+                    }
+                }
+            }
+        }
+
+        let mut streams: Vec<StreamLifecycle> = streams.into_values().collect();
+        for lifecycle in &mut streams {
+            in_modeled_order(&mut lifecycle.events);
+            in_modeled_order(&mut lifecycle.creating_events);
+            in_modeled_order(&mut lifecycle.appended_by);
+            in_modeled_order(&mut lifecycle.folded_by);
+            in_modeled_order(&mut lifecycle.reacted_to_by);
+        }
+        Self {
+            workflow: model.workflow.clone().into_inner().into_inner(),
+            streams,
+        }
+    }
+
+    /// Returns the streams, ordered by their identifier field.
+    pub fn streams(&self) -> &[StreamLifecycle] {
+        &self.streams
+    }
+
+    /// Formats the report as a Markdown document with a section per stream.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# Streams of {}\n", self.workflow);
+        if self.streams.is_empty() {
+            markdown.push_str("\nNo event has a `stream-id` field.\n");
+            return markdown;
+        }
+        for lifecycle in &self.streams {
+            markdown.push_str(&format!("\n## {}\n\n", lifecycle.stream));
+            for (index, event) in lifecycle.events.iter().enumerate() {
+                let creates = if lifecycle.creating_events.contains(event) {
+                    " (creates the stream)"
+                } else {
+                    ""
+                };
+                markdown.push_str(&format!("{}. {event}{creates}\n", index + 1));
+            }
+            markdown.push('\n');
+            for (label, names) in [
+                ("Appended by", &lifecycle.appended_by),
+                ("Folded by", &lifecycle.folded_by),
+                ("Reacted to by", &lifecycle.reacted_to_by),
+            ] {
+                let names = if names.is_empty() {
+                    "none".to_string()
+                } else {
+                    names.join(", ")
+                };
+                markdown.push_str(&format!("- {label}: {names}\n"));
+            }
+            if lifecycle.missing_creation() {
+                markdown.push_str("\n> **Warning:** no event creates this stream.\n");
+            }
+            if lifecycle.unconsumed() {
+                markdown.push_str(
+                    "\n> **Warning:** no projection or automation consumes this stream.\n",
+                );
+            }
+        }
+        markdown
+    }
+
+    /// Formats the report as JSON, with each stream's flags spelled out.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        #[derive(Serialize)]
+        struct Flagged<'a> {
+            #[serde(flatten)]
+            lifecycle: &'a StreamLifecycle,
+            missing_creation: bool,
+            unconsumed: bool,
+        }
+        #[derive(Serialize)]
+        struct Report<'a> {
+            workflow: &'a str,
+            streams: Vec<Flagged<'a>>,
+        }
+
+        serde_json::to_string_pretty(&Report {
+            workflow: &self.workflow,
+            streams: self
+                .streams
+                .iter()
+                .map(|lifecycle| Flagged {
+                    lifecycle,
+                    missing_creation: lifecycle.missing_creation(),
+                    unconsumed: lifecycle.unconsumed(),
+                })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+    const MODEL: &str = r#"workflow: Accounts
+swimlanes:
+  - backend: "Backend"
+  - read: "Read Models"
+commands:
+  OpenAccount:
+    description: "Open an account"
+    swimlane: backend
+    data:
+      account_id:
+        type: AccountId
+        stream-id: true
+        generated: true
+  CloseAccount:
+    description: "Close an account"
+    swimlane: backend
+  ShipParcel:
+    description: "Ship a parcel"
+    swimlane: backend
+events:
+  AccountClosed:
+    description: "An account was closed"
+    swimlane: backend
+    data:
+      account_id:
+        type: AccountId
+        stream-id: true
+  AccountOpened:
+    description: "An account was opened"
+    swimlane: backend
+    data:
+      account_id:
+        type: AccountId
+        stream-id: true
+  ParcelShipped:
+    description: "A parcel was shipped"
+    swimlane: backend
+    data:
+      parcel_id:
+        type: ParcelId
+        stream-id: true
+projections:
+  Accounts:
+    description: "Open accounts"
+    swimlane: read
+slices:
+  - name: Opening
+    connections:
+      - OpenAccount -> AccountOpened
+      - AccountOpened -> Accounts
+  - name: Closing
+    connections:
+      - CloseAccount -> AccountClosed
+      - AccountClosed -> Accounts
+  - name: Shipping
+    connections:
+      - ShipParcel -> ParcelShipped
+"#;
+
+    fn report() -> StreamReport {
+        let parsed = yaml_parser::parse_yaml(MODEL).unwrap();
+        StreamReport::from_model(&yaml_converter::convert_yaml_to_domain(parsed).unwrap())
+    }
+
+    #[test]
+    fn streams_list_events_in_modeled_order_and_flag_gaps() {
+        assert_eq!(
+            report().to_markdown(),
+            "# Streams of Accounts\n\
+             \n\
+             ## account_id\n\
+             \n\
+             1. AccountOpened (creates the stream)\n\
+             2. AccountClosed\n\
+             \n\
+             - Appended by: OpenAccount, CloseAccount\n\
+             - Folded by: Accounts\n\
+             - Reacted to by: none\n\
+             \n\
+             ## parcel_id\n\
+             \n\
+             1. ParcelShipped\n\
+             \n\
+             - Appended by: ShipParcel\n\
+             - Folded by: none\n\
+             - Reacted to by: none\n\
+             \n\
+             > **Warning:** no event creates this stream.\n\
+             \n\
+             > **Warning:** no projection or automation consumes this stream.\n"
+        );
+    }
+
+    #[test]
+    fn json_spells_out_the_flags() {
+        let json: serde_json::Value = serde_json::from_str(&report().to_json().unwrap()).unwrap();
+
+        assert_eq!(json["workflow"], "Accounts");
+        assert_eq!(json["streams"][0]["stream"], "account_id");
+        assert_eq!(json["streams"][0]["missing_creation"], false);
+        assert_eq!(json["streams"][1]["unconsumed"], true);
+        assert_eq!(json["streams"][1]["events"][0], "ParcelShipped");
+    }
+}
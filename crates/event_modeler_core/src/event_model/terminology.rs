@@ -0,0 +1,496 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Consistency of the names a model uses.
+//!
+//! Large models are written by many people, and the same concept drifts into
+//! several spellings: `UserCreated` next to `UserAccountCreated`,
+//! `OrderCanceled` next to `OrderCancelled`, `account_id` next to
+//! `accountId`. [`TerminologyReport`] finds entities of one type whose names
+//! differ by a single word or letter, field names mixing naming styles, and
+//! uses of the words a model bans under `terminology: banned_words:`.
+
+use super::yaml_types::{FieldName, YamlEventModel};
+use crate::infrastructure::warnings::{WarningKind, Warnings};
+use std::collections::{BTreeMap, HashMap};
+
+/// Two entity names so alike they are likely the same concept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimilarNames {
+    /// The entity type both names belong to, e.g. `event`.
+    pub kind: &'static str,
+    /// The name that sorts first.
+    pub first: String,
+    /// The name that sorts second.
+    pub second: String,
+}
+
+/// A naming style of field names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FieldStyle {
+    /// `account_id`
+    SnakeCase,
+    /// `accountId`
+    CamelCase,
+    /// `AccountId`
+    PascalCase,
+}
+
+impl FieldStyle {
+    /// Returns the style's conventional name, e.g. `snake_case`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::SnakeCase => "snake_case",
+            Self::CamelCase => "camelCase",
+            Self::PascalCase => "PascalCase",
+        }
+    }
+
+    /// Returns the style of a field name, or `None` for single lowercase
+    /// words such as `email`, which fit any style.
+    fn of(name: &str) -> Option<Self> {
+        let mut chars = name.chars();
+        let first = chars.next()?;
+        let has_upper = chars.clone().any(char::is_uppercase);
+        if first.is_uppercase() {
+            Some(Self::PascalCase)
+        } else if has_upper {
+            Some(Self::CamelCase)
+        } else if name.contains('_') {
+            Some(Self::SnakeCase)
+        } else {
+            None
+        }
+    }
+}
+
+/// A banned word and where the model uses it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BannedWordUse {
+    /// The banned word, as configured.
+    pub word: String,
+    /// Entity names, `Entity.field` names, and `Entity description`s using
+    /// it, ordered by name.
+    pub places: Vec<String>,
+}
+
+/// Naming inconsistencies found in a model.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TerminologyReport {
+    similar: Vec<SimilarNames>,
+    field_styles: BTreeMap<FieldStyle, Vec<String>>,
+    banned: Vec<BannedWordUse>,
+}
+
+impl TerminologyReport {
+    /// Checks the entity names, field names, and descriptions of a model.
+    pub fn from_model(model: &YamlEventModel) -> Self {
+        let entities = named_entities(model);
+
+        // Each name is split once rather than once for every other name
+        let spellings: Vec<Spelling> = entities
+            .iter()
+            .map(|entity| Spelling::of(&entity.name))
+            .collect();
+        let mut similar = Vec::new();
+        for (index, entity) in entities.iter().enumerate() {
+            for (offset, other) in entities[index + 1..].iter().enumerate() {
+                if entity.kind == other.kind
+                    && spellings[index].alike(&spellings[index + 1 + offset])
+                {
+                    let (first, second) = if entity.name <= other.name {
+                        (entity, other)
+                    } else {
+                        (other, entity)
+                    };
+                    similar.push(SimilarNames {
+                        kind: entity.kind,
+                        first: first.name.clone(),
+                        second: second.name.clone(),
+                    });
+                }
+            }
+        }
+        similar.sort_by(|a, b| (a.kind, &a.first, &a.second).cmp(&(b.kind, &b.first, &b.second)));
+
+        let mut field_styles: BTreeMap<FieldStyle, Vec<String>> = BTreeMap::new();
+        for entity in &entities {
+            for field in &entity.fields {
+                if let Some(style) = FieldStyle::of(field) {
+                    field_styles
+                        .entry(style)
+                        .or_default()
+                        .push(format!("{}.{field}", entity.name));
+                }
+            }
+        }
+        for fields in field_styles.values_mut() {
+            fields.sort();
+        }
+
+        let banned = model
+            .terminology
+            .banned_words
+            .iter()
+            .filter_map(|word| {
+                let word = word.clone().into_inner().into_inner();
+                let banned = word.to_lowercase();
+                let mut places = Vec::new();
+                for entity in &entities {
+                    if uses_word(&entity.name, &banned) {
+                        places.push(entity.name.clone());
+                    }
+                    for field in &entity.fields {
+                        if uses_word(field, &banned) {
+                            places.push(format!("{}.{field}", entity.name));
+                        }
+                    }
+                    if entity
+                        .description
+                        .as_deref()
+                        .is_some_and(|description| uses_word(description, &banned))
+                    {
+                        places.push(format!("{} description", entity.name));
+                    }
+                }
+                places.sort();
+                (!places.is_empty()).then_some(BannedWordUse { word, places })
+            })
+            .collect();
+
+        Self {
+            similar,
+            field_styles,
+            banned,
+        }
+    }
+
+    /// Returns pairs of entity names likely meaning the same thing.
+    pub fn similar_names(&self) -> &[SimilarNames] {
+        &self.similar
+    }
+
+    /// Returns the naming styles field names use, each with the fields using
+    /// it, when more than one style is in use.
+    pub fn mixed_field_styles(&self) -> Option<&BTreeMap<FieldStyle, Vec<String>>> {
+        (self.field_styles.len() > 1).then_some(&self.field_styles)
+    }
+
+    /// Returns each banned word the model uses, with where it is used.
+    pub fn banned_words(&self) -> &[BannedWordUse] {
+        &self.banned
+    }
+
+    /// Records a warning for each inconsistency found.
+    pub fn warn(&self, warnings: &mut Warnings) {
+        for pair in &self.similar {
+            warnings.warn(
+                WarningKind::SimilarEntityNames,
+                format!(
+                    "The {} names '{}' and '{}' are nearly the same; use one name for one concept",
+                    pair.kind, pair.first, pair.second
+                ),
+            );
+        }
+        if let Some(styles) = self.mixed_field_styles() {
+            // The most used style is the model's convention
+            let (convention, _) = styles
+                .iter()
+                .max_by_key(|(style, fields)| (fields.len(), std::cmp::Reverse(**style)))
+                .expect("mixed styles are never empty");
+            let others: Vec<String> = styles
+                .iter()
+                .filter(|(style, _)| *style != convention)
+                .map(|(style, fields)| format!("{} in {}", style.name(), fields.join(", ")))
+                .collect();
+            warnings.warn(
+                WarningKind::MixedFieldNaming,
+                format!(
+                    "Field names are mostly {} but use {}",
+                    convention.name(),
+                    others.join("; ")
+                ),
+            );
+        }
+        for banned in &self.banned {
+            warnings.warn(
+                WarningKind::BannedWord,
+                format!(
+                    "The banned word '{}' is used in {}",
+                    banned.word,
+                    banned.places.join(", ")
+                ),
+            );
+        }
+    }
+}
+
+/// An entity's name and the text checked with it.
+struct NamedEntity {
+    kind: &'static str,
+    name: String,
+    fields: Vec<String>,
+    description: Option<String>,
+}
+
+/// Lists every entity of a model, ordered by type and name.
+fn named_entities(model: &YamlEventModel) -> Vec<NamedEntity> {
+    fn field_names<V>(fields: &HashMap<FieldName, V>) -> Vec<String> {
+        let mut names: Vec<String> = fields
+            .keys()
+            .map(|name| name.clone().into_inner().into_inner())
+            .collect();
+        names.sort();
+        names
+    }
+
+    let mut entities = Vec::new();
+    for (name, view) in &model.views {
+        entities.push(NamedEntity {
+            kind: "view",
+            name: name.clone().into_inner().into_inner(),
+            fields: Vec::new(),
+            description: Some(view.description.clone().into_inner().into_inner()),
+        });
+    }
+    for (name, command) in &model.commands {
+        entities.push(NamedEntity {
+            kind: "command",
+            name: name.clone().into_inner().into_inner(),
+            fields: field_names(&command.data),
+            description: Some(command.description.clone().into_inner().into_inner()),
+        });
+    }
+    for (name, event) in &model.events {
+        entities.push(NamedEntity {
+            kind: "event",
+            name: name.clone().into_inner().into_inner(),
+            fields: field_names(&event.data),
+            description: Some(event.description.clone().into_inner().into_inner()),
+        });
+    }
+    for (name, projection) in &model.projections {
+        entities.push(NamedEntity {
+            kind: "projection",
+            name: name.clone().into_inner().into_inner(),
+            fields: field_names(&projection.fields),
+            description: Some(projection.description.clone().into_inner().into_inner()),
+        });
+    }
+    for (name, query) in &model.queries {
+        entities.push(NamedEntity {
+            kind: "query",
+            name: name.clone().into_inner().into_inner(),
+            fields: field_names(&query.inputs),
+            description: None,
+        });
+    }
+    for name in model.automations.keys() {
+        entities.push(NamedEntity {
+            kind: "automation",
+            name: name.clone().into_inner().into_inner(),
+            fields: Vec::new(),
+            description: None,
+        });
+    }
+    entities.sort_by(|a, b| (a.kind, &a.name).cmp(&(b.kind, &b.name)));
+    entities
+}
+
+/// Returns whether two names differ only by one extra word, as in
+/// `UserCreated` and `UserAccountCreated`, or by one letter, as in
+/// `OrderCanceled` and `OrderCancelled`.
+pub(crate) fn alike(a: &str, b: &str) -> bool {
+    Spelling::of(a).alike(&Spelling::of(b))
+}
+
+/// A name split into the forms [`alike`] compares.
+struct Spelling {
+    words: Vec<String>,
+    lowercase: Vec<char>,
+}
+
+impl Spelling {
+    fn of(name: &str) -> Self {
+        Self {
+            words: words(name),
+            lowercase: name.to_lowercase().chars().collect(),
+        }
+    }
+
+    fn alike(&self, other: &Self) -> bool {
+        let (shorter, longer) = if self.words.len() <= other.words.len() {
+            (&self.words, &other.words)
+        } else {
+            (&other.words, &self.words)
+        };
+        let one_word_added = shorter.len() >= 2
+            && longer.len() == shorter.len() + 1
+            && (0..longer.len()).any(|skipped| {
+                longer
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| *index != skipped)
+                    .map(|(_, word)| word)
+                    .eq(shorter.iter())
+            });
+
+        let (a, b) = (&self.lowercase, &other.lowercase);
+        let one_letter_apart = a.len().min(b.len()) >= 6 && one_edit_apart(a, b);
+
+        one_word_added || one_letter_apart
+    }
+}
+
+/// Returns whether a single-letter insertion, deletion, or substitution
+/// turns `a` into `b`.
+fn one_edit_apart(a: &[char], b: &[char]) -> bool {
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if longer.len() - shorter.len() > 1 {
+        return false;
+    }
+    // Past the first difference, the rest must match once the edit is made
+    let same = shorter
+        .iter()
+        .zip(longer)
+        .take_while(|(a, b)| a == b)
+        .count();
+    if shorter.len() == longer.len() {
+        same < shorter.len() && shorter[same + 1..] == longer[same + 1..]
+    } else {
+        shorter[same..] == longer[same + 1..]
+    }
+}
+
+/// Splits a name or sentence into lowercase words at spaces, punctuation,
+/// underscores, and case changes.
+pub(crate) fn words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lowercase = false;
+    for c in text.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lowercase = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_lowercase {
+            words.push(std::mem::take(&mut current));
+        }
+        current.extend(c.to_lowercase());
+        previous_lowercase = c.is_lowercase() || c.is_numeric();
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Returns whether `text` contains the lowercase word `banned` as a whole
+/// word.
+fn uses_word(text: &str, banned: &str) -> bool {
+    words(text).iter().any(|word| word == banned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+    fn report(yaml: &str) -> TerminologyReport {
+        let model =
+            yaml_converter::convert_yaml_to_domain(yaml_parser::parse_yaml(yaml).unwrap()).unwrap();
+        TerminologyReport::from_model(&model)
+    }
+
+    const MODEL: &str = r#"workflow: Accounts
+swimlanes:
+  - backend: "Backend"
+terminology:
+  banned_words: [Client]
+commands:
+  CreateUser:
+    description: "Create a user for a client"
+    swimlane: backend
+    data:
+      account_id: AccountId
+      emailAddress: Email
+events:
+  UserCreated:
+    description: "A user was created"
+    swimlane: backend
+    data:
+      account_id: AccountId
+      display_name: Name
+  UserAccountCreated:
+    description: "A user account was created"
+    swimlane: backend
+  OrderCanceled:
+    description: "An order was canceled"
+    swimlane: backend
+  OrderCancelled:
+    description: "An order was cancelled"
+    swimlane: backend
+  ClientRegistered:
+    description: "A new customer signed up"
+    swimlane: backend
+"#;
+
+    #[test]
+    fn finds_names_one_word_or_letter_apart() {
+        let report = report(MODEL);
+
+        let pairs: Vec<(&str, &str)> = report
+            .similar_names()
+            .iter()
+            .map(|pair| (pair.first.as_str(), pair.second.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                ("OrderCanceled", "OrderCancelled"),
+                ("UserAccountCreated", "UserCreated")
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_mixed_field_naming_styles() {
+        let report = report(MODEL);
+
+        let styles = report.mixed_field_styles().expect("styles are mixed");
+        assert_eq!(styles[&FieldStyle::CamelCase], ["CreateUser.emailAddress"]);
+        assert_eq!(styles[&FieldStyle::SnakeCase].len(), 3);
+
+        let mut warnings = Warnings::new();
+        report.warn(&mut warnings);
+        assert!(warnings.to_string().contains(
+            "Field names are mostly snake_case but use camelCase in CreateUser.emailAddress"
+        ));
+    }
+
+    #[test]
+    fn finds_banned_words_in_names_and_descriptions() {
+        let report = report(MODEL);
+
+        assert_eq!(
+            report.banned_words(),
+            [BannedWordUse {
+                word: "Client".to_string(),
+                places: vec![
+                    "ClientRegistered".to_string(),
+                    "CreateUser description".to_string()
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn unrelated_names_are_not_alike() {
+        assert!(!alike("OrderPlaced", "OrderShipped"));
+        assert!(!alike("Order", "OrderPlaced"));
+        assert!(!alike("UserCreated", "UserCreated"));
+    }
+}
@@ -160,7 +160,7 @@ impl YamlEntityRegistry {
                 // For now, we just check if the view exists
                 // TODO: Validate full path including components
                 let path_str = path.clone().into_inner();
-                let view_name = path_str.as_str().split('.').next().unwrap();
+                let view_name = path_str.as_str().split('.').next().unwrap_or_default();
                 if self.views.keys().any(|n| {
                     let n_str = n.clone().into_inner();
                     n_str.as_str() == view_name
@@ -245,6 +245,7 @@ pub struct EntityNamesByType {
 
 /// Validation errors for entity references.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum ValidationError {
     /// Invalid source entity in connection.
     InvalidSource {
@@ -11,6 +11,7 @@ use crate::event_model::{
     entities::{Automation, Command, Event, Projection, Query, Wireframe},
     yaml_types as yaml,
 };
+use crate::infrastructure::types::InvariantError;
 use std::collections::HashMap;
 
 /// Converts a YAML event model into a diagram representation.
@@ -71,7 +72,7 @@ pub fn convert_yaml_to_diagram(
         &yaml_model.projections,
         &yaml_model.queries,
         &yaml_model.automations,
-    );
+    )?;
 
     // Build registry - For now we use an empty registry as the typestate pattern
     // makes it difficult to add multiple entities of the same type.
@@ -108,9 +109,11 @@ fn convert_swimlanes_with_entities(
     yaml_projections: &std::collections::HashMap<yaml::ProjectionName, yaml::ProjectionDefinition>,
     yaml_queries: &std::collections::HashMap<yaml::QueryName, yaml::QueryDefinition>,
     yaml_automations: &std::collections::HashMap<yaml::AutomationName, yaml::AutomationDefinition>,
-) -> crate::infrastructure::types::NonEmpty<crate::event_model::diagram::Swimlane> {
+) -> Result<
+    crate::infrastructure::types::NonEmpty<crate::event_model::diagram::Swimlane>,
+    ConversionError,
+> {
     use crate::event_model::diagram::{Swimlane, SwimlaneId, SwimlaneName, SwimlanePosition};
-    use crate::infrastructure::types::NonEmpty;
     use std::collections::HashMap;
 
     // Build map of swimlane ID to entities
@@ -126,13 +129,7 @@ fn convert_swimlanes_with_entities(
 
     // Add events to their swimlanes by looking up the YAML definitions
     for (yaml_event_name, event_def) in yaml_events {
-        let entity_id = crate::event_model::entities::EntityId::new(
-            crate::infrastructure::types::NonEmptyString::parse(format!(
-                "event_{}",
-                yaml_event_name.clone().into_inner().as_str()
-            ))
-            .unwrap(),
-        );
+        let entity_id = prefixed_id("event", yaml_event_name.clone().into_inner().as_str())?;
         if let Some(entity_list) = swimlane_entities.get_mut(&event_def.swimlane) {
             entity_list.push(entity_id);
         }
@@ -140,13 +137,7 @@ fn convert_swimlanes_with_entities(
 
     // Add commands to their swimlanes by looking up the YAML definitions
     for (yaml_command_name, command_def) in yaml_commands {
-        let entity_id = crate::event_model::entities::EntityId::new(
-            crate::infrastructure::types::NonEmptyString::parse(format!(
-                "command_{}",
-                yaml_command_name.clone().into_inner().as_str()
-            ))
-            .unwrap(),
-        );
+        let entity_id = prefixed_id("command", yaml_command_name.clone().into_inner().as_str())?;
         if let Some(entity_list) = swimlane_entities.get_mut(&command_def.swimlane) {
             entity_list.push(entity_id);
         }
@@ -154,13 +145,7 @@ fn convert_swimlanes_with_entities(
 
     // Add views to their swimlanes
     for (yaml_view_name, view_def) in yaml_views {
-        let entity_id = crate::event_model::entities::EntityId::new(
-            crate::infrastructure::types::NonEmptyString::parse(format!(
-                "view_{}",
-                yaml_view_name.clone().into_inner().as_str()
-            ))
-            .unwrap(),
-        );
+        let entity_id = prefixed_id("view", yaml_view_name.clone().into_inner().as_str())?;
         if let Some(entity_list) = swimlane_entities.get_mut(&view_def.swimlane) {
             entity_list.push(entity_id);
         }
@@ -168,13 +153,10 @@ fn convert_swimlanes_with_entities(
 
     // Add projections to their swimlanes
     for (yaml_projection_name, projection_def) in yaml_projections {
-        let entity_id = crate::event_model::entities::EntityId::new(
-            crate::infrastructure::types::NonEmptyString::parse(format!(
-                "projection_{}",
-                yaml_projection_name.clone().into_inner().as_str()
-            ))
-            .unwrap(),
-        );
+        let entity_id = prefixed_id(
+            "projection",
+            yaml_projection_name.clone().into_inner().as_str(),
+        )?;
         if let Some(entity_list) = swimlane_entities.get_mut(&projection_def.swimlane) {
             entity_list.push(entity_id);
         }
@@ -182,13 +164,7 @@ fn convert_swimlanes_with_entities(
 
     // Add queries to their swimlanes
     for (yaml_query_name, query_def) in yaml_queries {
-        let entity_id = crate::event_model::entities::EntityId::new(
-            crate::infrastructure::types::NonEmptyString::parse(format!(
-                "query_{}",
-                yaml_query_name.clone().into_inner().as_str()
-            ))
-            .unwrap(),
-        );
+        let entity_id = prefixed_id("query", yaml_query_name.clone().into_inner().as_str())?;
         if let Some(entity_list) = swimlane_entities.get_mut(&query_def.swimlane) {
             entity_list.push(entity_id);
         }
@@ -196,45 +172,31 @@ fn convert_swimlanes_with_entities(
 
     // Add automations to their swimlanes
     for (yaml_automation_name, automation_def) in yaml_automations {
-        let entity_id = crate::event_model::entities::EntityId::new(
-            crate::infrastructure::types::NonEmptyString::parse(format!(
-                "automation_{}",
-                yaml_automation_name.clone().into_inner().as_str()
-            ))
-            .unwrap(),
-        );
+        let entity_id = prefixed_id(
+            "automation",
+            yaml_automation_name.clone().into_inner().as_str(),
+        )?;
         if let Some(entity_list) = swimlane_entities.get_mut(&automation_def.swimlane) {
             entity_list.push(entity_id);
         }
     }
 
-    let head = yaml_swimlanes.first();
-    let tail: Vec<_> = yaml_swimlanes.iter().skip(1).cloned().collect();
-
-    let head_swimlane = Swimlane {
-        id: SwimlaneId::new(head.id.clone().into_inner()),
-        name: SwimlaneName::new(head.name.clone().into_inner()),
-        position: SwimlanePosition::new(crate::infrastructure::types::NonNegativeInt::new(0)),
-        entities: swimlane_entities.get(&head.id).cloned().unwrap_or_default(),
-    };
-
-    let tail_swimlanes: Vec<_> = tail
-        .into_iter()
-        .enumerate()
-        .map(|(index, yaml_swimlane)| Swimlane {
+    let mut position = 0;
+    Ok(yaml_swimlanes.as_ref().map(|yaml_swimlane| {
+        let swimlane = Swimlane {
             id: SwimlaneId::new(yaml_swimlane.id.clone().into_inner()),
-            name: SwimlaneName::new(yaml_swimlane.name.into_inner()),
+            name: SwimlaneName::new(yaml_swimlane.name.clone().into_inner()),
             position: SwimlanePosition::new(crate::infrastructure::types::NonNegativeInt::new(
-                (index + 1) as u32,
+                position,
             )),
             entities: swimlane_entities
                 .get(&yaml_swimlane.id)
                 .cloned()
                 .unwrap_or_default(),
-        })
-        .collect();
-
-    NonEmpty::from_head_and_tail(head_swimlane, tail_swimlanes)
+        };
+        position += 1;
+        swimlane
+    }))
 }
 
 /// Convert YAML events to diagram events.
@@ -242,8 +204,8 @@ fn convert_events(
     yaml_events: &std::collections::HashMap<yaml::EventName, yaml::EventDefinition>,
     swimlanes: &crate::infrastructure::types::NonEmpty<yaml::Swimlane>,
 ) -> Result<Vec<Event>, ConversionError> {
-    use crate::event_model::entities::{EntityId, EventDataField, EventName, EventTimestamp};
-    use crate::infrastructure::types::{EventName as SafeEventName, NonEmpty, NonEmptyString};
+    use crate::event_model::entities::{EventDataField, EventName, EventTimestamp};
+    use crate::infrastructure::types::{EventName as SafeEventName, NonEmptyString};
 
     let mut events = Vec::new();
 
@@ -273,9 +235,9 @@ fn convert_events(
         // For now, create one field per data entry
         let data_fields: Vec<EventDataField> = if event_def.data.is_empty() {
             // If no data defined, create a default field
-            vec![EventDataField::new(
-                NonEmptyString::parse("data".to_string()).unwrap(),
-            )]
+            vec![EventDataField::new(NonEmptyString::derived(
+                "data".to_string(),
+            )?)]
         } else {
             event_def
                 .data
@@ -285,23 +247,10 @@ fn convert_events(
         };
 
         // Create NonEmpty collection
-        let data = if data_fields.len() == 1 {
-            NonEmpty::singleton(data_fields.into_iter().next().unwrap())
-        } else {
-            let mut iter = data_fields.into_iter();
-            let head = iter.next().unwrap();
-            let tail: Vec<_> = iter.collect();
-            NonEmpty::from_head_and_tail(head, tail)
-        };
+        let data = non_empty(data_fields, "event data")?;
 
         // Create unique ID based on event name
-        let event_id = EntityId::new(
-            NonEmptyString::parse(format!(
-                "event_{}",
-                yaml_event_name.clone().into_inner().as_str()
-            ))
-            .unwrap(),
-        );
+        let event_id = prefixed_id("event", yaml_event_name.clone().into_inner().as_str())?;
 
         let event = Event {
             id: event_id,
@@ -310,7 +259,7 @@ fn convert_events(
                 timestamp as u32,
             )),
             data,
-            documentation: None,
+            documentation: event_def.documentation.clone(),
         };
 
         events.push(event);
@@ -325,8 +274,8 @@ fn convert_commands(
     swimlanes: &crate::infrastructure::types::NonEmpty<yaml::Swimlane>,
 ) -> Result<Vec<Command>, ConversionError> {
     use crate::event_model::entities::{
-        Actor, CommandName, EntityId, FieldDefinition, FieldName, FieldType, PayloadField,
-        PlaceholderValue, TestAction, TestEvent, TestScenario, TestScenarioName,
+        Actor, CommandName, FieldDefinition, FieldName, FieldType, PayloadField, TestScenario,
+        TestScenarioName,
     };
     use crate::infrastructure::types::{NonEmpty, NonEmptyString};
 
@@ -350,9 +299,9 @@ fn convert_commands(
 
         // Convert data fields to PayloadField for backward compatibility
         let payload_fields: Vec<PayloadField> = if command_def.data.is_empty() {
-            vec![PayloadField::new(
-                NonEmptyString::parse("payload".to_string()).unwrap(),
-            )]
+            vec![PayloadField::new(NonEmptyString::derived(
+                "payload".to_string(),
+            )?)]
         } else {
             command_def
                 .data
@@ -361,14 +310,7 @@ fn convert_commands(
                 .collect()
         };
 
-        let payload = if payload_fields.len() == 1 {
-            NonEmpty::singleton(payload_fields.into_iter().next().unwrap())
-        } else {
-            let mut iter = payload_fields.into_iter();
-            let head = iter.next().unwrap();
-            let tail: Vec<_> = iter.collect();
-            NonEmpty::from_head_and_tail(head, tail)
-        };
+        let payload = non_empty(payload_fields, "command payload")?;
 
         // Convert data schema
         let data_schema = if command_def.data.is_empty() {
@@ -396,79 +338,30 @@ fn convert_commands(
                 let test_name = TestScenarioName::new(scenario_name.clone().into_inner());
 
                 // Convert given events
-                let given: Vec<TestEvent> = yaml_scenario
+                let given = yaml_scenario
                     .given
                     .iter()
-                    .map(|yaml_event| {
-                        let mut fields = HashMap::new();
-                        for (field_name, placeholder) in &yaml_event.fields {
-                            fields.insert(
-                                FieldName::new(field_name.clone().into_inner()),
-                                PlaceholderValue::new(placeholder.clone().into_inner()),
-                            );
-                        }
-                        TestEvent {
-                            name: crate::event_model::entities::EventName::new(
-                                crate::infrastructure::types::EventName::parse(
-                                    yaml_event.name.clone().into_inner().as_str().to_string(),
-                                )
-                                .unwrap(),
-                            ),
-                            fields,
-                        }
-                    })
-                    .collect();
+                    .map(convert_test_event)
+                    .collect::<Result<Vec<_>, _>>()?;
 
                 // Convert when actions
-                let when_actions: Vec<TestAction> = yaml_scenario
-                    .when
-                    .iter()
-                    .map(|yaml_action| {
-                        let mut fields = HashMap::new();
-                        for (field_name, placeholder) in &yaml_action.fields {
-                            fields.insert(
-                                FieldName::new(field_name.clone().into_inner()),
-                                PlaceholderValue::new(placeholder.clone().into_inner()),
-                            );
-                        }
-                        TestAction {
-                            name: CommandName::new(yaml_action.name.clone().into_inner()),
-                            fields,
-                        }
-                    })
-                    .collect();
-
-                let when = NonEmpty::from_head_and_tail(
-                    when_actions[0].clone(),
-                    when_actions[1..].to_vec(),
-                );
+                let when = yaml_scenario.when.as_ref().map(convert_test_action);
 
-                // Convert then events
-                let then_events: Vec<TestEvent> = yaml_scenario
+                // Convert then events; this domain has no notion of rejected
+                // commands, so scenarios expecting only errors are left out
+                let then_events = yaml_scenario
                     .then
                     .iter()
-                    .map(|yaml_event| {
-                        let mut fields = HashMap::new();
-                        for (field_name, placeholder) in &yaml_event.fields {
-                            fields.insert(
-                                FieldName::new(field_name.clone().into_inner()),
-                                PlaceholderValue::new(placeholder.clone().into_inner()),
-                            );
-                        }
-                        TestEvent {
-                            name: crate::event_model::entities::EventName::new(
-                                crate::infrastructure::types::EventName::parse(
-                                    yaml_event.name.clone().into_inner().as_str().to_string(),
-                                )
-                                .unwrap(),
-                            ),
-                            fields,
-                        }
+                    .filter_map(|outcome| match outcome {
+                        yaml::TestOutcome::Event(event) => Some(event),
+                        yaml::TestOutcome::Error(_) => None,
                     })
-                    .collect();
+                    .map(convert_test_event)
+                    .collect::<Result<Vec<_>, _>>()?;
 
-                let then =
-                    NonEmpty::from_head_and_tail(then_events[0].clone(), then_events[1..].to_vec());
+                let Ok(then) = NonEmpty::try_from(then_events) else {
+                    continue;
+                };
 
                 let scenario = TestScenario { given, when, then };
                 scenarios.insert(test_name, scenario);
@@ -477,20 +370,13 @@ fn convert_commands(
         };
 
         // Create unique ID based on command name
-        let command_id = EntityId::new(
-            NonEmptyString::parse(format!(
-                "command_{}",
-                yaml_command_name.clone().into_inner().as_str()
-            ))
-            .unwrap(),
-        );
+        let command_id = prefixed_id("command", yaml_command_name.clone().into_inner().as_str())?;
 
         // Infer actor from swimlane name
-        let actor_name = swimlanes
-            .iter()
-            .find(|s| s.id == command_def.swimlane)
-            .map(|s| s.name.clone().into_inner())
-            .unwrap_or_else(|| NonEmptyString::parse("User".to_string()).unwrap());
+        let actor_name = match swimlanes.iter().find(|s| s.id == command_def.swimlane) {
+            Some(swimlane) => swimlane.name.clone().into_inner(),
+            None => NonEmptyString::derived("User".to_string())?,
+        };
 
         let command = Command {
             id: command_id,
@@ -499,7 +385,7 @@ fn convert_commands(
             payload,
             data_schema,
             test_scenarios,
-            documentation: None,
+            documentation: command_def.documentation.clone(),
         };
 
         commands.push(command);
@@ -524,16 +410,16 @@ fn convert_yaml_slices_to_diagram_slices(
     if yaml_slices.is_empty() || entity_ids.is_empty() {
         // Create a default slice if no slices or entities
         let dummy_id = if entity_ids.is_empty() {
-            crate::event_model::entities::EntityId::new(
-                NonEmptyString::parse("dummy".to_string()).unwrap(),
-            )
+            crate::event_model::entities::EntityId::new(NonEmptyString::derived(
+                "dummy".to_string(),
+            )?)
         } else {
             entity_ids[0].clone()
         };
 
         let slice = Slice {
-            id: SliceId::new(NonEmptyString::parse("default".to_string()).unwrap()),
-            name: SliceName::new(NonEmptyString::parse("Default".to_string()).unwrap()),
+            id: SliceId::new(NonEmptyString::derived("default".to_string())?),
+            name: SliceName::new(NonEmptyString::derived("Default".to_string())?),
             boundaries: SliceBoundaries {
                 start_x: HorizontalPosition::new(
                     crate::infrastructure::types::NonNegativeInt::new(0),
@@ -571,11 +457,7 @@ fn convert_yaml_slices_to_diagram_slices(
                 )),
             },
             // For now, put all entities in all slices (this could be refined later)
-            entities: if entity_ids.len() == 1 {
-                NonEmpty::singleton(entity_ids[0].clone())
-            } else {
-                NonEmpty::from_head_and_tail(entity_ids[0].clone(), entity_ids[1..].to_vec())
-            },
+            entities: non_empty(entity_ids.to_vec(), "slice entities")?,
             connections,
             acceptance_criteria: None,
         };
@@ -583,16 +465,7 @@ fn convert_yaml_slices_to_diagram_slices(
         slices.push(slice);
     }
 
-    if slices.is_empty() {
-        return Err(ConversionError::InvalidReference(
-            "No slices created".to_string(),
-        ));
-    }
-
-    Ok(NonEmpty::from_head_and_tail(
-        slices[0].clone(),
-        slices[1..].to_vec(),
-    ))
+    non_empty(slices, "slices")
 }
 
 /// Convert YAML connections to diagram connectors.
@@ -600,115 +473,118 @@ fn convert_yaml_connections_to_connectors(
     yaml_connections: &crate::infrastructure::types::NonEmpty<yaml::Connection>,
 ) -> Result<Vec<crate::event_model::diagram::Connector>, ConversionError> {
     use crate::event_model::diagram::Connector;
-    use crate::event_model::entities::EntityId;
-    use crate::infrastructure::types::NonEmptyString;
 
-    let mut connectors = Vec::new();
+    yaml_connections
+        .iter()
+        .map(|connection| {
+            Ok(Connector {
+                from: reference_id(&connection.from)?,
+                to: reference_id(&connection.to)?,
+                label: None, // YAML connections don't specify labels
+            })
+        })
+        .collect()
+}
 
-    for connection in yaml_connections.iter() {
-        // Extract entity names from the connection
-        let from_entity_id = match &connection.from {
-            yaml::EntityReference::Event(event_name) => EntityId::new(
-                NonEmptyString::parse(format!(
-                    "event_{}",
-                    event_name.clone().into_inner().as_str()
-                ))
-                .unwrap(),
-            ),
-            yaml::EntityReference::Command(command_name) => EntityId::new(
-                NonEmptyString::parse(format!(
-                    "command_{}",
-                    command_name.clone().into_inner().as_str()
-                ))
-                .unwrap(),
-            ),
-            yaml::EntityReference::View(view_path) => {
-                // Extract top-level view name from view path (e.g., "NewAccountScreen" from "NewAccountScreen.AccountCredentials.Submit")
-                let path_string = view_path.clone().into_inner().into_inner();
-                let top_level_view = path_string.split('.').next().unwrap_or(&path_string);
-                EntityId::new(NonEmptyString::parse(format!("view_{top_level_view}")).unwrap())
-            }
-            yaml::EntityReference::Projection(projection_name) => EntityId::new(
-                NonEmptyString::parse(format!(
-                    "projection_{}",
-                    projection_name.clone().into_inner().as_str()
-                ))
-                .unwrap(),
-            ),
-            yaml::EntityReference::Query(query_name) => EntityId::new(
-                NonEmptyString::parse(format!(
-                    "query_{}",
-                    query_name.clone().into_inner().as_str()
-                ))
-                .unwrap(),
-            ),
-            yaml::EntityReference::Automation(automation_name) => EntityId::new(
-                NonEmptyString::parse(format!(
-                    "automation_{}",
-                    automation_name.clone().into_inner().as_str()
-                ))
-                .unwrap(),
-            ),
-        };
+/// Returns the ID of the entity an entity reference points at.
+fn reference_id(
+    reference: &yaml::EntityReference,
+) -> Result<crate::event_model::entities::EntityId, InvariantError> {
+    match reference {
+        yaml::EntityReference::Event(name) => {
+            prefixed_id("event", name.clone().into_inner().as_str())
+        }
+        yaml::EntityReference::Command(name) => {
+            prefixed_id("command", name.clone().into_inner().as_str())
+        }
+        yaml::EntityReference::View(path) => {
+            // Extract top-level view name from view path (e.g., "NewAccountScreen" from "NewAccountScreen.AccountCredentials.Submit")
+            let path_string = path.clone().into_inner().into_inner();
+            let top_level_view = path_string.split('.').next().unwrap_or(&path_string);
+            prefixed_id("view", top_level_view)
+        }
+        yaml::EntityReference::Projection(name) => {
+            prefixed_id("projection", name.clone().into_inner().as_str())
+        }
+        yaml::EntityReference::Query(name) => {
+            prefixed_id("query", name.clone().into_inner().as_str())
+        }
+        yaml::EntityReference::Automation(name) => {
+            prefixed_id("automation", name.clone().into_inner().as_str())
+        }
+    }
+}
 
-        let to_entity_id = match &connection.to {
-            yaml::EntityReference::Event(event_name) => EntityId::new(
-                NonEmptyString::parse(format!(
-                    "event_{}",
-                    event_name.clone().into_inner().as_str()
-                ))
-                .unwrap(),
-            ),
-            yaml::EntityReference::Command(command_name) => EntityId::new(
-                NonEmptyString::parse(format!(
-                    "command_{}",
-                    command_name.clone().into_inner().as_str()
-                ))
-                .unwrap(),
-            ),
-            yaml::EntityReference::View(view_path) => {
-                // Extract top-level view name from view path (e.g., "NewAccountScreen" from "NewAccountScreen.AccountCredentials.Submit")
-                let path_string = view_path.clone().into_inner().into_inner();
-                let top_level_view = path_string.split('.').next().unwrap_or(&path_string);
-                EntityId::new(NonEmptyString::parse(format!("view_{top_level_view}")).unwrap())
-            }
-            yaml::EntityReference::Projection(projection_name) => EntityId::new(
-                NonEmptyString::parse(format!(
-                    "projection_{}",
-                    projection_name.clone().into_inner().as_str()
-                ))
-                .unwrap(),
-            ),
-            yaml::EntityReference::Query(query_name) => EntityId::new(
-                NonEmptyString::parse(format!(
-                    "query_{}",
-                    query_name.clone().into_inner().as_str()
-                ))
-                .unwrap(),
-            ),
-            yaml::EntityReference::Automation(automation_name) => EntityId::new(
-                NonEmptyString::parse(format!(
-                    "automation_{}",
-                    automation_name.clone().into_inner().as_str()
-                ))
-                .unwrap(),
-            ),
-        };
+/// Returns the ID of an entity: its name behind a prefix naming its type,
+/// such as `event_OrderPlaced`.
+fn prefixed_id(
+    prefix: &str,
+    name: &str,
+) -> Result<crate::event_model::entities::EntityId, InvariantError> {
+    use crate::infrastructure::types::NonEmptyString;
 
-        let connector = Connector {
-            from: from_entity_id,
-            to: to_entity_id,
-            label: None, // YAML connections don't specify labels
-        };
+    Ok(crate::event_model::entities::EntityId::new(
+        NonEmptyString::derived(format!("{prefix}_{name}"))?,
+    ))
+}
+
+/// Converts an event of a test scenario, whose name must start with an
+/// uppercase letter.
+fn convert_test_event(
+    yaml_event: &yaml::TestEvent,
+) -> Result<crate::event_model::entities::TestEvent, ConversionError> {
+    use crate::event_model::entities::{EventName, TestEvent};
+
+    let name = yaml_event.name.clone().into_inner();
+    let name = crate::infrastructure::types::EventName::parse(name.as_str().to_string()).map_err(
+        |_| ConversionError::InvalidReference(format!("Invalid event name: {}", name.as_str())),
+    )?;
+    Ok(TestEvent {
+        name: EventName::new(name),
+        fields: convert_test_fields(&yaml_event.fields),
+    })
+}
 
-        connectors.push(connector);
+/// Converts a command of a test scenario.
+fn convert_test_action(yaml_action: &yaml::TestAction) -> crate::event_model::entities::TestAction {
+    use crate::event_model::entities::{CommandName, TestAction};
+
+    TestAction {
+        name: CommandName::new(yaml_action.name.clone().into_inner()),
+        fields: convert_test_fields(&yaml_action.fields),
     }
+}
 
-    Ok(connectors)
+/// Converts the field values of an event or command in a test scenario.
+fn convert_test_fields(
+    fields: &HashMap<yaml::FieldName, yaml::PlaceholderValue>,
+) -> HashMap<crate::event_model::entities::FieldName, crate::event_model::entities::PlaceholderValue>
+{
+    use crate::event_model::entities::{FieldName, PlaceholderValue};
+
+    fields
+        .iter()
+        .map(|(field_name, placeholder)| {
+            (
+                FieldName::new(field_name.clone().into_inner()),
+                PlaceholderValue::new(placeholder.clone().into_inner()),
+            )
+        })
+        .collect()
+}
+
+/// Collects items that must not be empty.
+fn non_empty<T>(
+    items: Vec<T>,
+    name: &str,
+) -> Result<crate::infrastructure::types::NonEmpty<T>, ConversionError> {
+    crate::infrastructure::types::NonEmpty::try_from(items)
+        .map_err(|_| ConversionError::EmptyCollection(name.to_string()))
 }
 
 /// Errors that can occur during YAML to diagram conversion.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum ConversionError {
     /// An entity referenced in a slice was not found.
     #[error("Unknown entity in slice: {0}")]
@@ -721,6 +597,14 @@ pub enum ConversionError {
     /// Failed to parse an entity reference.
     #[error("Invalid entity reference: {0}")]
     InvalidReference(String),
+
+    /// A collection that must not be empty was empty.
+    #[error("Collection '{0}' must not be empty")]
+    EmptyCollection(String),
+
+    /// The converter broke one of its own invariants.
+    #[error(transparent)]
+    Invariant(#[from] InvariantError),
 }
 
 #[cfg(test)]
@@ -743,6 +627,9 @@ mod tests {
         let swimlane = Swimlane {
             id: swimlane_id.clone(),
             name: swimlane_name,
+            owner: None,
+            style: Default::default(),
+            role: None,
         };
 
         let event_name = EventName::new(NonEmptyString::parse("TestEvent".to_string()).unwrap());
@@ -752,6 +639,12 @@ mod tests {
             description: event_desc,
             swimlane: swimlane_id,
             data: HashMap::new(),
+            owner: None,
+            kind: None,
+            hotspot: None,
+            documentation: None,
+            implemented_by: Vec::new(),
+            volume: None,
         };
 
         let mut events = HashMap::new();
@@ -760,6 +653,7 @@ mod tests {
         let yaml_model = YamlEventModel {
             version: None, // Optional version
             workflow,
+            header: Default::default(),
             swimlanes: NonEmpty::singleton(swimlane),
             events,
             commands: HashMap::new(),
@@ -768,6 +662,24 @@ mod tests {
             queries: HashMap::new(),
             automations: HashMap::new(),
             slices: Vec::new(),
+            layout: Default::default(),
+            layout_constraints: Vec::new(),
+            slice_headers: Default::default(),
+            clearance: Default::default(),
+            canvas: Default::default(),
+            connection_markers: Default::default(),
+            duplicate_connections: Default::default(),
+            unused_lanes: Default::default(),
+            terminology: Default::default(),
+            conventions: Default::default(),
+            lint: Default::default(),
+            extensions: Default::default(),
+            entity_sizing: Default::default(),
+            grid: None,
+            roles: Vec::new(),
+            types: HashMap::new(),
+            swimlane_groups: Vec::new(),
+            swimlane_order: Default::default(),
         };
 
         // Convert to diagram
@@ -794,6 +706,9 @@ mod tests {
         let swimlane = Swimlane {
             id: swimlane_id.clone(),
             name: swimlane_name,
+            owner: None,
+            style: Default::default(),
+            role: None,
         };
 
         // Create a command
@@ -805,7 +720,16 @@ mod tests {
             description: command_desc,
             swimlane: swimlane_id.clone(),
             data: HashMap::new(),
+            errors: HashMap::new(),
+            idempotent: None,
+            authorized_roles: Vec::new(),
             tests: HashMap::new(),
+            owner: None,
+            kind: None,
+            hotspot: None,
+            documentation: None,
+            implemented_by: Vec::new(),
+            volume: None,
         };
 
         // Create an event
@@ -815,6 +739,12 @@ mod tests {
             description: event_desc,
             swimlane: swimlane_id,
             data: HashMap::new(),
+            owner: None,
+            kind: None,
+            hotspot: None,
+            documentation: None,
+            implemented_by: Vec::new(),
+            volume: None,
         };
 
         // Create a slice connecting them
@@ -824,6 +754,11 @@ mod tests {
         let connection = Connection {
             from: EntityReference::Command(command_name.clone()),
             to: EntityReference::Event(event_name.clone()),
+            via: Vec::new(),
+            marker: None,
+            bidirectional: false,
+            consistency: None,
+            hotspot: None,
         };
         let connections = NonEmpty::singleton(connection);
 
@@ -835,6 +770,12 @@ mod tests {
 
         let slice = yaml::Slice {
             name: slice_name,
+            context: None,
+            estimate: None,
+            status: None,
+            issue: None,
+            order: Vec::new(),
+            swimlanes: Vec::new(),
             connections,
         };
         let slices = vec![slice];
@@ -842,6 +783,7 @@ mod tests {
         let yaml_model = YamlEventModel {
             version: None,
             workflow,
+            header: Default::default(),
             swimlanes: NonEmpty::singleton(swimlane),
             events,
             commands,
@@ -850,6 +792,24 @@ mod tests {
             queries: HashMap::new(),
             automations: HashMap::new(),
             slices,
+            layout: Default::default(),
+            layout_constraints: Vec::new(),
+            slice_headers: Default::default(),
+            clearance: Default::default(),
+            canvas: Default::default(),
+            connection_markers: Default::default(),
+            duplicate_connections: Default::default(),
+            unused_lanes: Default::default(),
+            terminology: Default::default(),
+            conventions: Default::default(),
+            lint: Default::default(),
+            extensions: Default::default(),
+            entity_sizing: Default::default(),
+            grid: None,
+            roles: Vec::new(),
+            types: HashMap::new(),
+            swimlane_groups: Vec::new(),
+            swimlane_order: Default::default(),
         };
 
         // Convert to diagram
@@ -878,6 +838,9 @@ mod tests {
         let swimlane = Swimlane {
             id: swimlane_id.clone(),
             name: swimlane_name,
+            owner: None,
+            style: Default::default(),
+            role: None,
         };
 
         // Create command with data schema
@@ -893,6 +856,7 @@ mod tests {
                 field_type: FieldType::new(NonEmptyString::parse("AccountId".to_string()).unwrap()),
                 stream_id: true,
                 generated: true,
+                pii: false,
             },
         );
         data_fields.insert(
@@ -903,6 +867,7 @@ mod tests {
                 ),
                 stream_id: false,
                 generated: false,
+                pii: false,
             },
         );
 
@@ -951,7 +916,8 @@ mod tests {
         let test_scenario = TestScenario {
             given: vec![given_event],
             when: NonEmpty::singleton(when_action),
-            then: NonEmpty::singleton(then_event),
+            then: NonEmpty::singleton(TestOutcome::Event(then_event)),
+            examples: HashMap::new(),
         };
 
         let mut tests = HashMap::new();
@@ -961,7 +927,16 @@ mod tests {
             description: command_desc,
             swimlane: swimlane_id,
             data: data_fields,
+            errors: HashMap::new(),
+            idempotent: None,
+            authorized_roles: Vec::new(),
             tests,
+            owner: None,
+            kind: None,
+            hotspot: None,
+            documentation: None,
+            implemented_by: Vec::new(),
+            volume: None,
         };
 
         let mut commands = HashMap::new();
@@ -970,6 +945,7 @@ mod tests {
         let yaml_model = YamlEventModel {
             version: None,
             workflow,
+            header: Default::default(),
             swimlanes: NonEmpty::singleton(swimlane),
             events: HashMap::new(),
             commands,
@@ -978,6 +954,24 @@ mod tests {
             queries: HashMap::new(),
             automations: HashMap::new(),
             slices: Vec::new(),
+            layout: Default::default(),
+            layout_constraints: Vec::new(),
+            slice_headers: Default::default(),
+            clearance: Default::default(),
+            canvas: Default::default(),
+            connection_markers: Default::default(),
+            duplicate_connections: Default::default(),
+            unused_lanes: Default::default(),
+            terminology: Default::default(),
+            conventions: Default::default(),
+            lint: Default::default(),
+            extensions: Default::default(),
+            entity_sizing: Default::default(),
+            grid: None,
+            roles: Vec::new(),
+            types: HashMap::new(),
+            swimlane_groups: Vec::new(),
+            swimlane_order: Default::default(),
         };
 
         // Convert to diagram
@@ -991,4 +985,43 @@ mod tests {
         assert_eq!(diagram.swimlanes.len(), 1);
         // Can't verify command details directly due to empty registry
     }
+
+    #[test]
+    fn reports_scenario_events_with_invalid_names_instead_of_panicking() {
+        use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+
+        let yaml = r#"workflow: Accounts
+swimlanes:
+  - backend: "Backend"
+commands:
+  OpenAccount:
+    description: "Open an account"
+    swimlane: backend
+    tests:
+      "Opens":
+        Given:
+          - accountRequested:
+              id: A
+        When:
+          - OpenAccount:
+              id: A
+        Then:
+          - accountRequested:
+              id: A
+events:
+  accountRequested:
+    description: "An account was requested"
+    swimlane: backend
+slices:
+  - name: Opening
+    connections:
+      - OpenAccount -> accountRequested
+"#;
+        let model =
+            yaml_converter::convert_yaml_to_domain(yaml_parser::parse_yaml(yaml).unwrap()).unwrap();
+
+        let result = convert_yaml_to_diagram(model);
+
+        assert!(matches!(result, Err(ConversionError::InvalidReference(_))));
+    }
 }
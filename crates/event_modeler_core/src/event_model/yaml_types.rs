@@ -0,0 +1,1508 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Type definitions for the YAML-based event model format.
+//!
+//! This module defines the rich type system needed to represent the YAML format
+//! discovered in example.eventmodel. These types support:
+//! - Data schemas with type annotations
+//! - Test scenarios (Given/When/Then)
+//! - UI component hierarchies
+//! - Slice-based flow definitions
+//!
+//! # Type Safety Guarantees
+//!
+//! All types in this module follow the "parse, don't validate" principle and make
+//! illegal states unrepresentable at the type level:
+//!
+//! 1. **Non-empty guarantees**: Using `NonEmpty<T>` and `NonEmptyString` ensures
+//!    collections and strings can never be empty after construction.
+//!
+//! 2. **Newtype wrappers**: All string-based types (names, IDs, etc.) are wrapped
+//!    in distinct types using `nutype`, preventing type confusion at compile time.
+//!
+//! 3. **Structured data**: Complex concepts like test scenarios and UI components
+//!    are represented as proper types, not stringly-typed data.
+//!
+//! 4. **Validation at boundaries**: All validation happens when parsing YAML into
+//!    these types. Once constructed, the types are guaranteed to be valid.
+//!
+//! 5. **Exhaustive matching**: Enums like `EntityReference` and `ComponentType`
+//!    ensure all cases are handled at compile time.
+
+use super::entities::EntityType;
+use crate::infrastructure::types::{
+    File, MarkdownFile, MaybeExists, NonEmpty, NonEmptyString, NonNegativeInt, PositiveInt,
+    TypedPath,
+};
+use nutype::nutype;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// The root structure of a YAML event model file.
+///
+/// # Type Safety
+/// - `workflow` is guaranteed non-empty via `WorkflowName(NonEmptyString)`
+/// - `swimlanes` must have at least one entry via `NonEmpty<Swimlane>`
+/// - Entity maps use distinct key types preventing cross-type lookups
+/// - `slices` connections are guaranteed non-empty via `NonEmpty<Connection>`
+#[derive(Debug, Clone, Serialize)]
+pub struct YamlEventModel {
+    /// Optional schema version (defaults to current app version).
+    pub version: Option<SchemaVersion>,
+    /// Name of the workflow being modeled.
+    pub workflow: WorkflowName,
+    /// Descriptive metadata given alongside the workflow name.
+    pub header: WorkflowHeader,
+    /// Swimlanes that organize entities vertically.
+    pub swimlanes: NonEmpty<Swimlane>,
+    /// Labelled groups of adjacent swimlanes, in declaration order.
+    pub swimlane_groups: Vec<SwimlaneGroup>,
+    /// How swimlanes are ordered top to bottom.
+    pub swimlane_order: SwimlaneOrder,
+    /// Events that represent state changes.
+    pub events: HashMap<EventName, EventDefinition>,
+    /// Commands that represent user intentions.
+    pub commands: HashMap<CommandName, CommandDefinition>,
+    /// Views that represent UI screens.
+    pub views: HashMap<ViewName, ViewDefinition>,
+    /// Projections that represent derived read models.
+    pub projections: HashMap<ProjectionName, ProjectionDefinition>,
+    /// Queries for retrieving data.
+    pub queries: HashMap<QueryName, QueryDefinition>,
+    /// Automations that trigger based on events.
+    pub automations: HashMap<AutomationName, AutomationDefinition>,
+    /// Slices that define connections between entities.
+    /// Now uses a Vec to preserve order explicitly with named slices.
+    pub slices: Vec<Slice>,
+    /// How slices are arranged into columns.
+    pub layout: LayoutStrategy,
+    /// Rules the constraint layout keeps; unused by the other layouts.
+    pub layout_constraints: Vec<LayoutConstraint>,
+    /// How slice headers are drawn.
+    pub slice_headers: SliceHeaderStyle,
+    /// How entity boxes are sized, by entity type.
+    pub entity_sizing: EntitySizing,
+    /// Spacing in pixels of the grid entity positions and connector bends
+    /// snap to; nothing snaps when unset.
+    pub grid: Option<PositiveInt>,
+    /// Room connectors keep from entities and from each other.
+    pub clearance: ConnectorClearance,
+    /// Background and watermark of the canvas.
+    pub canvas: Canvas,
+    /// Markers drawn at connection targets, by the types of entity joined.
+    pub connection_markers: ConnectionMarkers,
+    /// How connections drawn between the same two entities are shown.
+    pub duplicate_connections: DuplicateConnections,
+    /// How swimlanes a slice leaves empty are drawn in its column.
+    pub unused_lanes: UnusedLanes,
+    /// Roles commands may be authorized for.
+    pub roles: Vec<RoleName>,
+    /// Naming rules checked across entity and field names.
+    pub terminology: Terminology,
+    /// Envelope fields events and commands must carry.
+    pub conventions: Conventions,
+    /// Custom lint rules run against the model.
+    pub lint: LintConfig,
+    /// Custom entity kinds, by name.
+    pub extensions: HashMap<ExtensionName, EntityExtension>,
+    /// Declared domain types; empty when the model declares none.
+    pub types: HashMap<TypeName, TypeDefinition>,
+}
+
+impl YamlEventModel {
+    /// Returns the type of the entity with the given name, if it is defined.
+    pub fn entity_type(&self, name: &str) -> Option<EntityType> {
+        let name = NonEmptyString::parse(name.to_string()).ok()?;
+        if self.events.contains_key(&EventName::new(name.clone())) {
+            Some(EntityType::Event)
+        } else if self.commands.contains_key(&CommandName::new(name.clone())) {
+            Some(EntityType::Command)
+        } else if self.views.contains_key(&ViewName::new(name.clone())) {
+            Some(EntityType::View)
+        } else if self
+            .projections
+            .contains_key(&ProjectionName::new(name.clone()))
+        {
+            Some(EntityType::Projection)
+        } else if self.queries.contains_key(&QueryName::new(name.clone())) {
+            Some(EntityType::Query)
+        } else if self.automations.contains_key(&AutomationName::new(name)) {
+            Some(EntityType::Automation)
+        } else {
+            None
+        }
+    }
+}
+
+/// How the row of slice headers is drawn.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SliceHeaderStyle {
+    /// Minimum height of the header row in pixels; it grows to fit wrapped
+    /// or rotated titles. The renderer's default when unset.
+    pub height: Option<PositiveInt>,
+    /// Fill behind the header row, transparent when unset.
+    pub background: Option<SvgColor>,
+    /// Lines a title may wrap onto before it is rotated instead. The
+    /// renderer's default when unset.
+    pub max_lines: Option<PositiveInt>,
+}
+
+/// Room connectors keep from entities and from each other.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ConnectorClearance {
+    /// Pixels kept between a connector and entities it does not join. The
+    /// renderer's default when unset.
+    pub margin: Option<NonNegativeInt>,
+    /// Pixels kept between parallel segments of different connectors. The
+    /// renderer's default when unset.
+    pub separation: Option<PositiveInt>,
+}
+
+/// Background and watermark of the canvas behind the diagram.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Canvas {
+    /// Fill of the canvas in the light theme. The renderer's default when
+    /// unset.
+    pub background: Option<CanvasBackground>,
+    /// Fill of the canvas in the dark theme, the light theme's when unset.
+    pub dark_background: Option<CanvasBackground>,
+    /// Text drawn diagonally across the canvas, beneath every entity and
+    /// connector, such as `DRAFT` or `CONFIDENTIAL`.
+    pub watermark: Option<NonEmptyString>,
+}
+
+impl Canvas {
+    /// Returns the fill set for the dark or light theme, if any.
+    pub fn background_for(&self, dark: bool) -> Option<&CanvasBackground> {
+        if dark {
+            self.dark_background.as_ref().or(self.background.as_ref())
+        } else {
+            self.background.as_ref()
+        }
+    }
+}
+
+/// The fill of the canvas.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum CanvasBackground {
+    /// No fill, so the diagram sits on whatever page it is embedded in.
+    Transparent,
+    /// A solid color.
+    Color(SvgColor),
+}
+
+/// How an entity box is sized from the entity's name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize)]
+pub enum SizePolicy {
+    /// The standard box, widened only when a single word does not fit and
+    /// made taller for every wrapped line.
+    #[default]
+    Standard,
+    /// Exactly the standard box; names that do not fit are cut short with
+    /// an ellipsis.
+    Fixed,
+    /// Just large enough for the wrapped name, narrower than the standard
+    /// box for short names.
+    FitContent,
+    /// Sized like `Standard`, then grown to the largest entity of the same
+    /// type so every box of that type matches.
+    Uniform,
+}
+
+/// Size policies by entity type.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct EntitySizing {
+    /// Policy for entity types without an override.
+    pub default: SizePolicy,
+    /// Policies for individual entity types.
+    pub overrides: HashMap<EntityType, SizePolicy>,
+}
+
+impl EntitySizing {
+    /// Returns the policy for entities of the given type.
+    pub fn policy_for(&self, entity_type: EntityType) -> SizePolicy {
+        self.overrides
+            .get(&entity_type)
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+/// An SVG color: `#rgb`, `#rrggbb`, or a named color such as `lavender`.
+#[nutype(
+    validate(predicate = |color: &str| is_svg_color(color)),
+    derive(Debug, Clone, PartialEq, Eq, AsRef, Serialize)
+)]
+pub struct SvgColor(String);
+
+/// Returns whether text is a hex or named SVG color.
+fn is_svg_color(color: &str) -> bool {
+    match color.strip_prefix('#') {
+        Some(hex) => matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()),
+        None => !color.is_empty() && color.chars().all(|c| c.is_ascii_alphabetic()),
+    }
+}
+
+/// How connections drawn between the same two entities, through the same
+/// waypoints, are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum DuplicateConnections {
+    /// Each connection gets its own connector, drawn on top of the others.
+    #[default]
+    Separate,
+    /// One connector, badged with how many connections it stands for.
+    Count,
+    /// One connector, badged with the names of the slices it comes from.
+    Slices,
+}
+
+/// How swimlanes a slice leaves empty are drawn in its column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum UnusedLanes {
+    /// Like any other part of the swimlane.
+    #[default]
+    Full,
+    /// Shaded, with swimlanes no slice uses shrunk to a thin band.
+    Shaded,
+}
+
+/// The shape drawn where a connection meets its target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize)]
+pub enum MarkerStyle {
+    /// A solid triangular arrowhead.
+    #[default]
+    Filled,
+    /// An outlined arrowhead.
+    Open,
+    /// A hollow diamond, for aggregation-like links.
+    Diamond,
+    /// A hollow circle, for subscriptions.
+    Circle,
+    /// No marker; the connector simply ends.
+    None,
+}
+
+/// How soon a projection reflects the events feeding it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Consistency {
+    /// Updated asynchronously, so reads may briefly lag behind the event.
+    Eventual,
+    /// Updated in the same transaction that records the event.
+    Immediate,
+}
+
+/// The types of entity a connection joins; `None` on either side matches
+/// any type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct ConnectionPattern {
+    /// Type of the source entity.
+    pub from: Option<EntityType>,
+    /// Type of the target entity.
+    pub to: Option<EntityType>,
+}
+
+/// Marker styles chosen by the types of entity a connection joins, e.g. a
+/// circle for every `event -> automation` connection. Connections no
+/// pattern matches get a filled arrowhead.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionMarkers(pub HashMap<ConnectionPattern, MarkerStyle>);
+
+/// Serializes as a map keyed by patterns written as in the model, e.g.
+/// `event -> *`.
+impl Serialize for ConnectionMarkers {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let side = |entity_type: Option<EntityType>| entity_type.map_or("*", EntityType::name);
+        serializer.collect_map(self.0.iter().map(|(pattern, style)| {
+            (
+                format!("{} -> {}", side(pattern.from), side(pattern.to)),
+                style,
+            )
+        }))
+    }
+}
+
+impl ConnectionMarkers {
+    /// Returns the marker for a connection between entities of the given
+    /// types. An exact pattern wins over one naming only the source type,
+    /// which wins over one naming only the target type, then `* -> *`.
+    pub fn style_for(&self, from: EntityType, to: EntityType) -> MarkerStyle {
+        [
+            (Some(from), Some(to)),
+            (Some(from), None),
+            (None, Some(to)),
+            (None, None),
+        ]
+        .into_iter()
+        .find_map(|(from, to)| self.0.get(&ConnectionPattern { from, to }).copied())
+        .unwrap_or_default()
+    }
+}
+
+/// How slices are arranged into columns on the diagram.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum LayoutStrategy {
+    /// Every slice gets its own column.
+    #[default]
+    Standard,
+    /// A slice shares the previous slice's column when none of their
+    /// entities are in the same swimlane, shrinking wide diagrams.
+    Compact,
+    /// Every slice gets its own column, and a constraint solver places the
+    /// entities to keep the model's layout constraints, widening columns
+    /// where it must.
+    Constraints,
+}
+
+/// A rule the constraint layout keeps when placing entities, naming entities
+/// of any type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum LayoutConstraint {
+    /// The entities' centers line up in every slice drawing more than one of
+    /// them.
+    Align(Vec<String>),
+    /// The entities are drawn left to right in this order wherever a slice
+    /// draws them side by side in one swimlane.
+    Order(Vec<String>),
+    /// At least `min` pixels separate the right edge of `left` from the left
+    /// edge of `right` in every slice drawing both.
+    Gap {
+        /// The entity on the left.
+        left: String,
+        /// The entity on the right.
+        right: String,
+        /// Minimum space between them in pixels.
+        min: u32,
+    },
+}
+
+/// Schema version following semantic versioning.
+///
+/// # Type Safety
+/// - Guaranteed non-empty via `NonEmptyString`
+/// - Distinct type prevents confusion with other version strings
+/// - Validation of semantic version format happens at parse time
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct SchemaVersion(NonEmptyString);
+
+/// Name of the workflow.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct WorkflowName(NonEmptyString);
+
+/// Naming rules for a model.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Terminology {
+    /// Words that names and descriptions must not use.
+    pub banned_words: Vec<BannedWord>,
+}
+
+/// A word a model's names and descriptions must not use.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct BannedWord(NonEmptyString);
+
+/// Envelope fields a model's events and commands must carry; none unless the
+/// model declares `conventions`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Conventions {
+    /// Fields every event must include.
+    pub envelope: Vec<FieldName>,
+    /// Fields every command must declare to pass on to its events.
+    pub propagate: Vec<FieldName>,
+}
+
+/// Custom lint rules for a model.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LintConfig {
+    /// WebAssembly lint plugins, relative to the model file.
+    pub plugins: Vec<PluginPath>,
+}
+
+/// Path of a WebAssembly lint plugin.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct PluginPath(NonEmptyString);
+
+/// A risk or open question attached to an entity or connection, e.g. one
+/// raised in a modeling workshop.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, AsRef))]
+pub struct Hotspot(NonEmptyString);
+
+/// Name of a custom entity kind, e.g. `policy`.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct ExtensionName(NonEmptyString);
+
+/// A custom entity kind: entities of a built-in type that are drawn in their
+/// own color and icon and may only connect as the kind allows.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EntityExtension {
+    /// The built-in type the kind's entities are declared and laid out as.
+    pub base: EntityType,
+    /// Fill of the kind's entities; the base type's when unset.
+    pub color: Option<SvgColor>,
+    /// Symbol marking the kind's entities.
+    pub icon: Option<EntityIcon>,
+    /// Entity types and kinds the kind's entities may connect to; any when
+    /// empty.
+    pub connects_to: Vec<KindName>,
+}
+
+/// A built-in entity type such as `command`, or a custom kind.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct KindName(NonEmptyString);
+
+/// A short symbol, such as an emoji, marking the entities of a custom kind.
+#[nutype(
+    validate(predicate = |icon: &str| is_icon(icon)),
+    derive(Debug, Clone, PartialEq, Eq, AsRef, Serialize)
+)]
+pub struct EntityIcon(String);
+
+/// Descriptive metadata about the model, rendered with its title.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WorkflowHeader {
+    /// What the workflow is about.
+    pub description: Option<Description>,
+    /// People who wrote the model, in the order given.
+    pub authors: Vec<AuthorName>,
+    /// Version of the model itself, as opposed to the schema version.
+    pub version: Option<ModelVersion>,
+    /// When this version of the model was written.
+    pub date: Option<ModelDate>,
+}
+
+impl WorkflowHeader {
+    /// The version, date, and authors on one line, e.g.
+    /// `Version 2 · 2026-10-17 · Alice, Bob`, or `None` when none are given.
+    pub fn byline(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(version) = &self.version {
+            parts.push(format!("Version {}", version.as_ref().as_str()));
+        }
+        if let Some(date) = &self.date {
+            parts.push(date.as_ref().to_string());
+        }
+        if !self.authors.is_empty() {
+            let authors: Vec<&str> = self
+                .authors
+                .iter()
+                .map(|author| author.as_ref().as_str())
+                .collect();
+            parts.push(authors.join(", "));
+        }
+        (!parts.is_empty()).then(|| parts.join(" \u{b7} "))
+    }
+}
+
+/// Name of a model's author.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, AsRef, Serialize))]
+pub struct AuthorName(NonEmptyString);
+
+/// Version of a model, in whatever scheme its authors use.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, AsRef, Serialize))]
+pub struct ModelVersion(NonEmptyString);
+
+/// A calendar date written as `YYYY-MM-DD`.
+#[nutype(
+    validate(regex = r"^\d{4}-(0[1-9]|1[0-2])-(0[1-9]|[12]\d|3[01])$"),
+    derive(Debug, Clone, PartialEq, Eq, AsRef, Serialize)
+)]
+pub struct ModelDate(String);
+
+/// Swimlane definition with ID and display name.
+///
+/// # Type Safety
+/// - ID and name are distinct types preventing confusion
+/// - Both guaranteed non-empty
+/// - ID used for references, name for display
+#[derive(Debug, Clone, Serialize)]
+pub struct Swimlane {
+    /// Unique identifier for the swimlane.
+    pub id: SwimlaneId,
+    /// Display name for the swimlane.
+    pub name: SwimlaneName,
+    /// Default owner of the entities in this swimlane.
+    pub owner: Option<OwnerName>,
+    /// How the swimlane is set apart from the others.
+    pub style: SwimlaneStyle,
+    /// The kind of entities the swimlane holds, if declared.
+    pub role: Option<SwimlaneRole>,
+}
+
+/// The kind of entities a swimlane holds, following Event Modeling
+/// convention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum SwimlaneRole {
+    /// Views and automations: what users and processes see and do.
+    Interface,
+    /// Commands, projections, and queries.
+    Application,
+    /// Events.
+    Stream,
+}
+
+/// How swimlanes are ordered top to bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum SwimlaneOrder {
+    /// In the order they are written.
+    #[default]
+    Declared,
+    /// By role: interface lanes first, then application lanes, then stream
+    /// lanes. Lanes without a role sit with the application lanes.
+    Convention,
+}
+
+/// Decoration that distinguishes a swimlane, e.g. UI from infrastructure.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SwimlaneStyle {
+    /// Tint of the swimlane's background band and label accent.
+    pub color: Option<SvgColor>,
+    /// Symbol drawn before the swimlane's label.
+    pub icon: Option<SwimlaneIcon>,
+}
+
+/// A short symbol, such as an emoji, marking a swimlane's label.
+#[nutype(
+    validate(predicate = |icon: &str| is_icon(icon)),
+    derive(Debug, Clone, PartialEq, Eq, AsRef, Serialize)
+)]
+pub struct SwimlaneIcon(String);
+
+/// Longest icon in characters; enough for an emoji with modifiers.
+const MAX_ICON_CHARS: usize = 8;
+
+/// Returns whether text is short enough, without whitespace, to be an icon.
+fn is_icon(icon: &str) -> bool {
+    !icon.is_empty()
+        && icon.chars().count() <= MAX_ICON_CHARS
+        && !icon.chars().any(char::is_whitespace)
+}
+
+/// Swimlanes gathered under a shared parent label, e.g. "Backend".
+#[derive(Debug, Clone, Serialize)]
+pub struct SwimlaneGroup {
+    /// Label drawn beside the group's swimlanes.
+    pub name: SwimlaneGroupName,
+    /// Swimlanes in the group, in declaration order.
+    pub swimlanes: NonEmpty<SwimlaneId>,
+    /// Whether the group is drawn as a single swimlane holding all of its
+    /// members' entities.
+    pub collapsed: bool,
+    /// Fill behind the group's swimlanes, transparent when unset.
+    pub background: Option<SvgColor>,
+}
+
+/// Display name for a group of swimlanes.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct SwimlaneGroupName(NonEmptyString);
+
+/// Unique identifier for a swimlane.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct SwimlaneId(NonEmptyString);
+
+/// Display name for a swimlane.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct SwimlaneName(NonEmptyString);
+
+/// Team or person responsible for part of the model, e.g. `team-payments`.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct OwnerName(NonEmptyString);
+
+/// Expected throughput of an entity, written as a count per period such as
+/// `10k/day` or `1.5M/month`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Volume {
+    count: f64,
+    period: VolumePeriod,
+    written: String,
+}
+
+/// The period a [`Volume`] is counted over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum VolumePeriod {
+    /// Per second (`s` or `second`).
+    Second,
+    /// Per minute (`min` or `minute`).
+    Minute,
+    /// Per hour (`h` or `hour`).
+    Hour,
+    /// Per day (`day`).
+    Day,
+    /// Per week (`week`).
+    Week,
+    /// Per month of 30 days (`month`).
+    Month,
+}
+
+impl VolumePeriod {
+    /// Returns how many of this period make up a day.
+    fn per_day(self) -> f64 {
+        match self {
+            Self::Second => 86_400.0,
+            Self::Minute => 1_440.0,
+            Self::Hour => 24.0,
+            Self::Day => 1.0,
+            Self::Week => 1.0 / 7.0,
+            Self::Month => 1.0 / 30.0,
+        }
+    }
+}
+
+impl Volume {
+    /// Parses a volume such as `10k/day`: a positive number with an optional
+    /// `k`, `M`, or `B` multiplier, a slash, and a period. Returns `None` if
+    /// the text is not in that form.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (count, period) = text.split_once('/')?;
+        let count = count.trim();
+        let (digits, multiplier) = match count.char_indices().last()? {
+            (index, 'k' | 'K') => (&count[..index], 1e3),
+            (index, 'M') => (&count[..index], 1e6),
+            (index, 'B') => (&count[..index], 1e9),
+            _ => (count, 1.0),
+        };
+        let count = digits
+            .parse::<f64>()
+            .ok()
+            .filter(|n| n.is_finite() && *n > 0.0)?
+            * multiplier;
+        let period = match period.trim() {
+            "s" | "sec" | "second" => VolumePeriod::Second,
+            "min" | "minute" => VolumePeriod::Minute,
+            "h" | "hour" => VolumePeriod::Hour,
+            "day" => VolumePeriod::Day,
+            "week" => VolumePeriod::Week,
+            "month" => VolumePeriod::Month,
+            _ => return None,
+        };
+        Some(Self {
+            count,
+            period,
+            written: text.trim().to_string(),
+        })
+    }
+
+    /// Returns the period the volume is counted over.
+    pub fn period(&self) -> VolumePeriod {
+        self.period
+    }
+
+    /// Returns the volume converted to a count per day.
+    pub fn per_day(&self) -> f64 {
+        self.count * self.period.per_day()
+    }
+}
+
+impl std::fmt::Display for Volume {
+    /// Formats the volume as written in the model.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.written)
+    }
+}
+
+/// Role a command may be authorized for, e.g. `support-agent`.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct RoleName(NonEmptyString);
+
+/// Name of a declared domain type, e.g. `EmailAddress`.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct TypeName(NonEmptyString);
+
+/// How a declared domain type is represented.
+#[derive(Debug, Clone, Serialize)]
+pub enum TypeDefinition {
+    /// Represented as another type, e.g. `String`.
+    Alias(FieldType),
+    /// Made of named fields.
+    Record(HashMap<FieldName, FieldType>),
+    /// One of a fixed list of values.
+    Enum(NonEmpty<EnumVariant>),
+    /// Another type whose values are restricted.
+    Constrained(ConstrainedType),
+}
+
+impl std::fmt::Display for TypeDefinition {
+    /// Describes the definition, e.g. `one of USD, EUR` or
+    /// `Integer from 1 to 100`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Alias(base) => write!(f, "{}", base.clone().into_inner().as_str()),
+            Self::Record(fields) => {
+                let mut fields: Vec<String> = fields
+                    .iter()
+                    .map(|(name, field_type)| {
+                        format!(
+                            "{}: {}",
+                            name.clone().into_inner().as_str(),
+                            field_type.clone().into_inner().as_str()
+                        )
+                    })
+                    .collect();
+                fields.sort();
+                write!(f, "{{ {} }}", fields.join(", "))
+            }
+            Self::Enum(variants) => {
+                let variants: Vec<String> = variants
+                    .iter()
+                    .map(|variant| variant.clone().into_inner().into_inner())
+                    .collect();
+                write!(f, "one of {}", variants.join(", "))
+            }
+            Self::Constrained(constrained) => {
+                write!(f, "{}", constrained.base.clone().into_inner().as_str())?;
+                match (constrained.min, constrained.max) {
+                    (Some(min), Some(max)) => write!(f, " from {min} to {max}")?,
+                    (Some(min), None) => write!(f, " of at least {min}")?,
+                    (None, Some(max)) => write!(f, " of at most {max}")?,
+                    (None, None) => {}
+                }
+                if let Some(pattern) = &constrained.pattern {
+                    write!(f, " matching `{}`", pattern.as_str())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A value of an enumerated type, e.g. `USD`.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct EnumVariant(NonEmptyString);
+
+/// A type whose values are restricted to a range or a pattern.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConstrainedType {
+    /// The type being restricted.
+    pub base: FieldType,
+    /// Smallest allowed value, inclusive.
+    pub min: Option<f64>,
+    /// Largest allowed value, inclusive.
+    pub max: Option<f64>,
+    /// Regular expression every value must match.
+    #[serde(serialize_with = "serialize_pattern")]
+    pub pattern: Option<regex::Regex>,
+}
+
+/// Serializes a pattern as its source text.
+fn serialize_pattern<S: serde::Serializer>(
+    pattern: &Option<regex::Regex>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    pattern
+        .as_ref()
+        .map(regex::Regex::as_str)
+        .serialize(serializer)
+}
+
+/// Documentation written for an entity beyond its one-line description.
+#[derive(Debug, Clone, Serialize)]
+pub enum Documentation {
+    /// Markdown written inline in the model.
+    Markdown(DocumentationText),
+    /// A Markdown file holding the documentation, relative to the model file.
+    File(TypedPath<MarkdownFile, File, MaybeExists>),
+}
+
+/// Markdown text documenting an entity.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct DocumentationText(NonEmptyString);
+
+/// A reference from an entity to the code that implements it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum ImplementationLink {
+    /// A Rust item path, e.g. `crate::orders::PlaceOrder`.
+    RustPath(RustItemPath),
+    /// A source file, relative to the model file.
+    File(SourceFilePath),
+    /// A URL, in which `{name}` stands for the entity's name.
+    Url(UrlTemplate),
+}
+
+impl ImplementationLink {
+    /// Returns the link target for an entity, if the reference can be followed
+    /// directly. Rust paths name items rather than locations, so they have none,
+    /// and neither does a file path carrying a URI scheme.
+    pub fn href(&self, entity: &str) -> Option<String> {
+        match self {
+            Self::RustPath(_) => None,
+            Self::File(path) => {
+                let path = path.clone().into_inner().into_inner();
+                (!has_uri_scheme(&path)).then_some(path)
+            }
+            Self::Url(template) => Some(
+                template
+                    .clone()
+                    .into_inner()
+                    .as_str()
+                    .replace(URL_TEMPLATE_PLACEHOLDER, entity),
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for ImplementationLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Self::RustPath(path) => path.clone().into_inner(),
+            Self::File(path) => path.clone().into_inner(),
+            Self::Url(template) => template.clone().into_inner(),
+        };
+        f.write_str(text.as_str())
+    }
+}
+
+/// Returns whether `text` starts with a URI scheme such as `javascript:`.
+///
+/// A single letter before the colon is a Windows drive, not a scheme.
+pub fn has_uri_scheme(text: &str) -> bool {
+    text.split_once(':').is_some_and(|(scheme, _)| {
+        scheme.len() > 1
+            && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    })
+}
+
+/// The placeholder replaced by the entity name in a [`UrlTemplate`].
+pub const URL_TEMPLATE_PLACEHOLDER: &str = "{name}";
+
+/// A Rust item path such as `crate::orders::PlaceOrder`.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct RustItemPath(NonEmptyString);
+
+/// A path to a source file, relative to the model file.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct SourceFilePath(NonEmptyString);
+
+/// A URL that may contain the `{name}` placeholder.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct UrlTemplate(NonEmptyString);
+
+/// Event definition with data schema.
+///
+/// # Type Safety
+/// - `description` guaranteed non-empty
+/// - `swimlane` reference type-checked against defined swimlanes
+/// - `data` fields use structured `FieldDefinition` not raw strings
+#[derive(Debug, Clone, Serialize)]
+pub struct EventDefinition {
+    /// Description of what this event represents.
+    pub description: Description,
+    /// Swimlane this event belongs to.
+    pub swimlane: SwimlaneId,
+    /// Data fields with type annotations.
+    pub data: HashMap<FieldName, FieldDefinition>,
+    /// Owner declared on the entity itself, overriding its swimlane's owner.
+    pub owner: Option<OwnerName>,
+    /// Custom kind declared under `extensions`.
+    pub kind: Option<ExtensionName>,
+    /// Risk or open question raised about the entity.
+    pub hotspot: Option<Hotspot>,
+    /// Longer documentation, shown in exports.
+    pub documentation: Option<Documentation>,
+    /// Code implementing this entity.
+    pub implemented_by: Vec<ImplementationLink>,
+    /// Expected throughput.
+    pub volume: Option<Volume>,
+}
+
+/// Command definition with data schema and test scenarios.
+///
+/// # Type Safety
+/// - All string fields guaranteed non-empty via newtype wrappers
+/// - Test scenarios use structured `TestScenario` type
+/// - Field definitions include metadata (stream_id, generated flags)
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandDefinition {
+    /// Description of what this command does.
+    pub description: Description,
+    /// Swimlane this command belongs to.
+    pub swimlane: SwimlaneId,
+    /// Data fields with type annotations.
+    pub data: HashMap<FieldName, FieldDefinition>,
+    /// Errors this command may be rejected with.
+    pub errors: HashMap<ErrorTypeName, CommandErrorDefinition>,
+    /// Whether issuing the command again with the same data has no further
+    /// effect; unstated when `None`.
+    pub idempotent: Option<bool>,
+    /// Roles allowed to issue this command, all declared in the model.
+    pub authorized_roles: Vec<RoleName>,
+    /// Expected throughput.
+    pub volume: Option<Volume>,
+    /// Test scenarios for this command.
+    pub tests: HashMap<TestScenarioName, TestScenario>,
+    /// Owner declared on the entity itself, overriding its swimlane's owner.
+    pub owner: Option<OwnerName>,
+    /// Custom kind declared under `extensions`.
+    pub kind: Option<ExtensionName>,
+    /// Risk or open question raised about the entity.
+    pub hotspot: Option<Hotspot>,
+    /// Longer documentation, shown in exports.
+    pub documentation: Option<Documentation>,
+    /// Code implementing this entity.
+    pub implemented_by: Vec<ImplementationLink>,
+}
+
+/// Error a command may be rejected with instead of recording events.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandErrorDefinition {
+    /// Why the command is rejected.
+    pub description: Description,
+    /// Data carried by the error.
+    pub data: HashMap<FieldName, FieldDefinition>,
+}
+
+/// View definition with UI component hierarchy.
+///
+/// # Type Safety
+/// - `components` guaranteed non-empty via `NonEmpty<Component>`
+/// - Component types are structured, not stringly-typed
+/// - Nested form structures properly modeled with `ComponentType` enum
+#[derive(Debug, Clone, Serialize)]
+pub struct ViewDefinition {
+    /// Description of this view's purpose.
+    pub description: Description,
+    /// Swimlane this view belongs to.
+    pub swimlane: SwimlaneId,
+    /// UI components in this view.
+    pub components: NonEmpty<Component>,
+    /// Owner declared on the entity itself, overriding its swimlane's owner.
+    pub owner: Option<OwnerName>,
+    /// Custom kind declared under `extensions`.
+    pub kind: Option<ExtensionName>,
+    /// Risk or open question raised about the entity.
+    pub hotspot: Option<Hotspot>,
+    /// Longer documentation, shown in exports.
+    pub documentation: Option<Documentation>,
+}
+
+/// Projection definition with field schemas.
+///
+/// # Type Safety
+/// - Field names and types use distinct wrappers
+/// - Type annotations support generic parameters (e.g., `List<UserId>`)
+/// - All strings guaranteed non-empty
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectionDefinition {
+    /// Description of what this projection represents.
+    pub description: Description,
+    /// Swimlane this projection belongs to.
+    pub swimlane: SwimlaneId,
+    /// Fields available in the projection.
+    pub fields: HashMap<FieldName, FieldType>,
+    /// Owner declared on the entity itself, overriding its swimlane's owner.
+    pub owner: Option<OwnerName>,
+    /// Custom kind declared under `extensions`.
+    pub kind: Option<ExtensionName>,
+    /// Risk or open question raised about the entity.
+    pub hotspot: Option<Hotspot>,
+    /// Longer documentation, shown in exports.
+    pub documentation: Option<Documentation>,
+}
+
+/// Query definition with input/output contracts.
+///
+/// # Type Safety
+/// - Input/output contracts are strongly typed
+/// - `OutputSpec` enum handles single vs one-of patterns
+/// - Error cases explicitly modeled in output specifications
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryDefinition {
+    /// Swimlane this query belongs to.
+    pub swimlane: SwimlaneId,
+    /// Input parameters for the query.
+    pub inputs: HashMap<FieldName, FieldType>,
+    /// Output specification (can be one_of multiple options).
+    pub outputs: OutputSpec,
+    /// Owner declared on the entity itself, overriding its swimlane's owner.
+    pub owner: Option<OwnerName>,
+    /// Custom kind declared under `extensions`.
+    pub kind: Option<ExtensionName>,
+    /// Risk or open question raised about the entity.
+    pub hotspot: Option<Hotspot>,
+    /// Longer documentation, shown in exports.
+    pub documentation: Option<Documentation>,
+}
+
+/// Automation definition.
+#[derive(Debug, Clone, Serialize)]
+pub struct AutomationDefinition {
+    /// Swimlane this automation belongs to.
+    pub swimlane: SwimlaneId,
+    /// Owner declared on the entity itself, overriding its swimlane's owner.
+    pub owner: Option<OwnerName>,
+    /// Custom kind declared under `extensions`.
+    pub kind: Option<ExtensionName>,
+    /// Risk or open question raised about the entity.
+    pub hotspot: Option<Hotspot>,
+    /// Longer documentation, shown in exports.
+    pub documentation: Option<Documentation>,
+}
+
+/// Field definition with type annotation and metadata.
+///
+/// # Type Safety
+/// - Boolean flags prevent invalid combinations at runtime
+/// - Type annotations are strings but validated at parse time
+/// - Metadata cannot be lost or confused with other fields
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldDefinition {
+    /// Type annotation for this field.
+    pub field_type: FieldType,
+    /// Whether this field is a stream identifier.
+    pub stream_id: bool,
+    /// Whether this field is generated by the system.
+    pub generated: bool,
+    /// Whether this field holds personally identifiable information.
+    pub pii: bool,
+}
+
+/// Type annotation for a field (e.g., "UserAccountId", "UserEmailAddress\<Verified\>").
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct FieldType(NonEmptyString);
+
+/// Field name.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct FieldName(NonEmptyString);
+
+/// Description text.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct Description(NonEmptyString);
+
+/// Event name.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct EventName(NonEmptyString);
+
+/// Command name.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct CommandName(NonEmptyString);
+
+/// View name.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct ViewName(NonEmptyString);
+
+/// Projection name.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct ProjectionName(NonEmptyString);
+
+/// Query name.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct QueryName(NonEmptyString);
+
+/// Automation name.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct AutomationName(NonEmptyString);
+
+/// Slice name.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct SliceName(NonEmptyString);
+
+/// Name of a bounded context, e.g. `Billing`.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct ContextName(NonEmptyString);
+
+/// Effort a slice is estimated to take.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Estimate {
+    /// Story points.
+    Points(u32),
+    /// A relative t-shirt size.
+    Size(TShirtSize),
+}
+
+impl std::fmt::Display for Estimate {
+    /// Formats the estimate as written in the model, e.g. `5` or `M`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Points(points) => write!(f, "{points}"),
+            Self::Size(size) => f.write_str(size.as_str()),
+        }
+    }
+}
+
+/// How far implementation of a slice has come.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SliceStatus {
+    /// Implemented.
+    Done,
+    /// Being implemented.
+    InProgress,
+    /// Not started.
+    Todo,
+}
+
+impl SliceStatus {
+    /// Returns the status as written in the model, e.g. `in_progress`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Done => "done",
+            Self::InProgress => "in_progress",
+            Self::Todo => "todo",
+        }
+    }
+}
+
+/// An issue in a tracker, such as the one tracking a slice.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub enum IssueLink {
+    /// A Jira issue.
+    Jira {
+        /// Base URL of the Jira site, e.g. `https://acme.atlassian.net`.
+        site: String,
+        /// The issue key, e.g. `PROJ-123`.
+        key: String,
+    },
+    /// A GitHub issue or pull request.
+    GitHub {
+        /// The account owning the repository.
+        owner: String,
+        /// The repository name.
+        repo: String,
+        /// The issue or pull request number.
+        number: u64,
+    },
+}
+
+impl IssueLink {
+    /// Returns a short label for the issue, e.g. `PROJ-123` or `#42`.
+    pub fn label(&self) -> String {
+        match self {
+            Self::Jira { key, .. } => key.clone(),
+            Self::GitHub { number, .. } => format!("#{number}"),
+        }
+    }
+
+    /// Returns the address of the issue's page.
+    pub fn url(&self) -> String {
+        match self {
+            Self::Jira { site, key } => format!("{site}/browse/{key}"),
+            Self::GitHub {
+                owner,
+                repo,
+                number,
+            } => format!("https://github.com/{owner}/{repo}/issues/{number}"),
+        }
+    }
+}
+
+/// A relative t-shirt size, from `XS` to `XXL`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum TShirtSize {
+    /// `XS`
+    ExtraSmall,
+    /// `S`
+    Small,
+    /// `M`
+    Medium,
+    /// `L`
+    Large,
+    /// `XL`
+    ExtraLarge,
+    /// `XXL`
+    DoubleExtraLarge,
+}
+
+impl TShirtSize {
+    /// Every size, smallest first.
+    pub const ALL: [Self; 6] = [
+        Self::ExtraSmall,
+        Self::Small,
+        Self::Medium,
+        Self::Large,
+        Self::ExtraLarge,
+        Self::DoubleExtraLarge,
+    ];
+
+    /// Parses a size written as `XS`, `S`, `M`, `L`, `XL`, or `XXL`, in any
+    /// case. Returns `None` for anything else.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().to_ascii_uppercase();
+        Self::ALL.into_iter().find(|size| size.as_str() == text)
+    }
+
+    /// Returns the size as written, e.g. `XL`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ExtraSmall => "XS",
+            Self::Small => "S",
+            Self::Medium => "M",
+            Self::Large => "L",
+            Self::ExtraLarge => "XL",
+            Self::DoubleExtraLarge => "XXL",
+        }
+    }
+}
+
+/// A slice containing a name and connections between entities.
+///
+/// # Type Safety
+/// - `name` guaranteed non-empty via `SliceName(NonEmptyString)`
+/// - `connections` guaranteed non-empty via `NonEmpty<Connection>`
+#[derive(Debug, Clone, Serialize)]
+pub struct Slice {
+    /// Display name of the slice.
+    pub name: SliceName,
+    /// Bounded context the slice belongs to.
+    pub context: Option<ContextName>,
+    /// Effort the slice is estimated to take.
+    pub estimate: Option<Estimate>,
+    /// How far implementation of the slice has come.
+    pub status: Option<SliceStatus>,
+    /// Issue tracking the slice.
+    pub issue: Option<IssueLink>,
+    /// Entities drawn left to right in this order wherever the slice draws
+    /// them side by side in one swimlane.
+    pub order: Vec<EntityReference>,
+    /// Swimlanes the slice uses, or empty for those it draws entities in.
+    pub swimlanes: Vec<SwimlaneId>,
+    /// Connections within this slice.
+    pub connections: NonEmpty<Connection>,
+}
+
+/// Test scenario name.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct TestScenarioName(NonEmptyString);
+
+/// Test scenario with Given/When/Then structure.
+///
+/// # Type Safety
+/// - `when` and `then` guaranteed non-empty via `NonEmpty<T>`
+/// - Actions and events use distinct types preventing confusion
+/// - Placeholder values are type-safe via `PlaceholderValue` wrapper
+#[derive(Debug, Clone, Serialize)]
+pub struct TestScenario {
+    /// Given: initial state (list of events).
+    pub given: Vec<TestEvent>,
+    /// When: action taken (command or event).
+    pub when: NonEmpty<TestAction>,
+    /// Then: expected outcome (events, or errors rejecting the command).
+    pub then: NonEmpty<TestOutcome>,
+    /// Realistic values standing in for some of the placeholders.
+    pub examples: HashMap<PlaceholderValue, ExampleValue>,
+}
+
+/// Expected outcome of a test scenario.
+#[derive(Debug, Clone, Serialize)]
+pub enum TestOutcome {
+    /// The command records an event.
+    Event(TestEvent),
+    /// The command is rejected with one of its declared errors.
+    Error(TestError),
+}
+
+/// Error reference in a test scenario.
+#[derive(Debug, Clone, Serialize)]
+pub struct TestError {
+    /// Name of the error, declared on the command under test.
+    pub name: ErrorTypeName,
+    /// Field values using placeholder variables.
+    pub fields: HashMap<FieldName, PlaceholderValue>,
+}
+
+/// Event reference in a test scenario.
+#[derive(Debug, Clone, Serialize)]
+pub struct TestEvent {
+    /// Name of the event.
+    pub name: EventName,
+    /// Field values using placeholder variables (A, B, C, etc.).
+    pub fields: HashMap<FieldName, PlaceholderValue>,
+}
+
+/// Action in a test scenario (command execution).
+#[derive(Debug, Clone, Serialize)]
+pub struct TestAction {
+    /// Name of the command.
+    pub name: CommandName,
+    /// Field values using placeholder variables.
+    pub fields: HashMap<FieldName, PlaceholderValue>,
+}
+
+/// Placeholder value in test scenarios (e.g., "A", "B", "C").
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct PlaceholderValue(NonEmptyString);
+
+/// Realistic value for a placeholder (e.g., "jane@example.com").
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct ExampleValue(NonEmptyString);
+
+/// UI component definition.
+#[derive(Debug, Clone, Serialize)]
+pub struct Component {
+    /// Name of the component.
+    pub name: ComponentName,
+    /// Type of component or nested structure.
+    pub component_type: ComponentType,
+}
+
+/// Component name.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct ComponentName(NonEmptyString);
+
+/// Type of UI component.
+///
+/// # Type Safety
+/// - Enum ensures exhaustive matching for all component types
+/// - Form components have structured fields and actions
+/// - Simple components wrapped in distinct type
+/// - Compiler enforces handling of all variants
+#[derive(Debug, Clone, Serialize)]
+pub enum ComponentType {
+    /// Simple component type (e.g., "Link", "TextInput").
+    Simple(SimpleComponentType),
+    /// Form component with fields and actions.
+    Form {
+        /// Form fields.
+        fields: HashMap<FieldName, SimpleComponentType>,
+        /// Form actions (e.g., Submit).
+        actions: NonEmpty<ActionName>,
+    },
+}
+
+/// Simple component type name.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct SimpleComponentType(NonEmptyString);
+
+/// Action name (e.g., "Submit").
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct ActionName(NonEmptyString);
+
+/// Output specification for queries.
+///
+/// # Type Safety
+/// - Enum enforces handling both single and one-of cases
+/// - Output cases can be either field sets or error types
+/// - Compiler ensures exhaustive matching
+#[derive(Debug, Clone, Serialize)]
+pub enum OutputSpec {
+    /// Single output structure.
+    Single(HashMap<FieldName, FieldType>),
+    /// One of multiple possible outputs.
+    OneOf(HashMap<OutputCaseName, OutputCase>),
+}
+
+/// Name of an output case.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct OutputCaseName(NonEmptyString);
+
+/// An output case definition.
+#[derive(Debug, Clone, Serialize)]
+pub enum OutputCase {
+    /// Success case with fields.
+    Fields(HashMap<FieldName, FieldType>),
+    /// Error case with error type.
+    Error(ErrorTypeName),
+}
+
+/// Error type name.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Hash, Serialize))]
+pub struct ErrorTypeName(NonEmptyString);
+
+/// Connection in a slice.
+///
+/// # Type Safety
+/// - Source and target use same `EntityReference` type
+/// - Ensures connections only reference valid entity types
+/// - Validated at parse time against registry
+#[derive(Debug, Clone, Serialize)]
+pub struct Connection {
+    /// Source entity reference.
+    pub from: EntityReference,
+    /// Target entity reference.
+    pub to: EntityReference,
+    /// Points the connection must pass through, in order.
+    pub via: Vec<Waypoint>,
+    /// Marker drawn at the target, overriding the model's connection markers.
+    pub marker: Option<MarkerStyle>,
+    /// Whether the connection runs both ways, drawn with a marker at each end.
+    pub bidirectional: bool,
+    /// How soon the target projection reflects the source event, for
+    /// connections from an event to a projection.
+    pub consistency: Option<Consistency>,
+    /// Risk or open question raised about the connection.
+    pub hotspot: Option<Hotspot>,
+}
+
+/// A point a connection must pass through: the cell where a slice's column
+/// crosses a swimlane.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Waypoint {
+    /// The slice whose column the connection passes through.
+    pub slice: SliceName,
+    /// The swimlane the connection passes through.
+    pub swimlane: SwimlaneId,
+}
+
+/// Reference to an entity in a connection.
+///
+/// # Type Safety
+/// - Enum ensures only valid entity types can be referenced
+/// - Each variant wraps the appropriate name type
+/// - Exhaustive matching required when processing references
+/// - View paths support dot notation for component references
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum EntityReference {
+    /// Reference to an event.
+    Event(EventName),
+    /// Reference to a command.
+    Command(CommandName),
+    /// Reference to a view or view component.
+    View(ViewPath),
+    /// Reference to a projection.
+    Projection(ProjectionName),
+    /// Reference to a query.
+    Query(QueryName),
+    /// Reference to an automation.
+    Automation(AutomationName),
+}
+
+/// Path to a view or view component (e.g., "LoginScreen.CreateAccountLink").
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Serialize))]
+pub struct ViewPath(NonEmptyString);
+
+impl EntityReference {
+    /// Returns the name of the referenced entity; for a view path, the view.
+    pub fn entity_name(&self) -> String {
+        let name = match self {
+            Self::View(path) => path.clone().into_inner(),
+            Self::Command(name) => name.clone().into_inner(),
+            Self::Event(name) => name.clone().into_inner(),
+            Self::Projection(name) => name.clone().into_inner(),
+            Self::Query(name) => name.clone().into_inner(),
+            Self::Automation(name) => name.clone().into_inner(),
+        }
+        .into_inner();
+        match name.split_once('.') {
+            Some((view, _)) => view.to_string(),
+            None => name,
+        }
+    }
+
+    /// Parses an entity reference from a string.
+    ///
+    /// Handles formats like:
+    /// - "EventName" - interpreted based on context
+    /// - "ViewName.ComponentPath" - view with component path
+    ///
+    /// Returns None if the string is empty or invalid.
+    pub fn parse(s: &str) -> Option<Self> {
+        if s.is_empty() {
+            return None;
+        }
+
+        // For now, we'll use a simple heuristic:
+        // - If it contains a dot, it's a view path
+        // - Otherwise, we'll need context to determine the type
+        // In practice, the converter should know the expected type from context
+
+        if s.contains('.') {
+            // View path like "LoginScreen.CreateAccountLink"
+            // TODO: Implement proper parsing with NonEmptyString
+            None
+        } else {
+            // For other entities, we need more context
+            // This is a limitation - in real usage, the parser should
+            // know what type of entity is expected based on the slice context
+            // For now, we'll return None and let the converter handle it
+            None
+        }
+    }
+}
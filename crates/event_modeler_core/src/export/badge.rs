@@ -0,0 +1,180 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! A status badge summarizing a model, for embedding in a README.
+//!
+//! `event_modeler badge` writes a small SVG in the style of the badges many
+//! repositories show, e.g. "Event Model | 12 slices, 48 entities, ✓ valid",
+//! and prints a Markdown snippet embedding it. Regenerated in CI, it keeps a
+//! repository's docs honest about the health of its model: the badge is green
+//! for a model converting cleanly, yellow for one with warnings, and red for
+//! one that fails to convert.
+
+use crate::event_model::yaml_types::YamlEventModel;
+use crate::infrastructure::markup::escape_xml;
+
+/// Text on the left half of the badge.
+const BADGE_LABEL: &str = "Event Model";
+
+/// Height of the badge.
+const BADGE_HEIGHT: u32 = 20;
+
+/// Approximate width of a character of the badge text, at 11px Verdana.
+const BADGE_CHAR_WIDTH: u32 = 7;
+
+/// Space either side of each half's text.
+const BADGE_PADDING: u32 = 6;
+
+/// Distance of the text baseline from the top of the badge.
+const BADGE_BASELINE: u32 = 14;
+
+const BADGE_LABEL_COLOR: &str = "#555555"; // Grey, like most badge labels
+const BADGE_VALID_COLOR: &str = "#4c1"; // Green
+const BADGE_WARNING_COLOR: &str = "#dfb317"; // Yellow
+const BADGE_INVALID_COLOR: &str = "#e05d44"; // Red
+
+/// The health of a model, as the badge shows it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelHealth {
+    /// The model converted, with this many warnings.
+    Valid {
+        /// Number of slices.
+        slices: usize,
+        /// Number of entities of every type.
+        entities: usize,
+        /// Number of warnings raised while checking the model.
+        warnings: usize,
+    },
+    /// The model could not be parsed or converted.
+    Invalid,
+}
+
+impl ModelHealth {
+    /// Summarizes a model that converted, with the warnings it raised.
+    pub fn of(model: &YamlEventModel, warnings: usize) -> Self {
+        Self::Valid {
+            slices: model.slices.len(),
+            entities: model.views.len()
+                + model.commands.len()
+                + model.events.len()
+                + model.projections.len()
+                + model.queries.len()
+                + model.automations.len(),
+            warnings,
+        }
+    }
+
+    /// Returns the text on the right half of the badge, e.g.
+    /// `12 slices, 48 entities, ✓ valid`.
+    pub fn message(&self) -> String {
+        match self {
+            Self::Valid {
+                slices,
+                entities,
+                warnings,
+            } => {
+                let mut message = format!(
+                    "{}, {}, \u{2713} valid",
+                    plural(*slices, "slice"),
+                    plural(*entities, "entity")
+                );
+                if *warnings > 0 {
+                    message.push_str(&format!(", {}", plural(*warnings, "warning")));
+                }
+                message
+            }
+            Self::Invalid => "\u{2717} invalid".to_string(),
+        }
+    }
+
+    /// Returns the fill of the right half of the badge.
+    fn color(&self) -> &'static str {
+        match self {
+            Self::Valid { warnings: 0, .. } => BADGE_VALID_COLOR,
+            Self::Valid { .. } => BADGE_WARNING_COLOR,
+            Self::Invalid => BADGE_INVALID_COLOR,
+        }
+    }
+}
+
+/// Renders the badge as a standalone SVG document.
+pub fn render_badge(health: &ModelHealth) -> String {
+    let message = health.message();
+    let label_width = text_width(BADGE_LABEL);
+    let message_width = text_width(&message);
+    let width = label_width + message_width;
+    // Text is drawn at ten times its size and scaled down, for finer kerning
+    let text_y = BADGE_BASELINE * 10;
+    let text = format!("{BADGE_LABEL}: {message}");
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{BADGE_HEIGHT}" role="img" aria-label="{label}">
+  <title>{label}</title>
+  <rect width="{label_width}" height="{BADGE_HEIGHT}" fill="{BADGE_LABEL_COLOR}"/>
+  <rect x="{label_width}" width="{message_width}" height="{BADGE_HEIGHT}" fill="{color}"/>
+  <g fill="#ffffff" text-anchor="middle" font-family="Verdana,DejaVu Sans,sans-serif" font-size="110" transform="scale(.1)">
+    <text x="{label_x}" y="{text_y}">{BADGE_LABEL}</text>
+    <text x="{message_x}" y="{text_y}">{message}</text>
+  </g>
+</svg>
+"##,
+        label = escape_xml(&text),
+        color = health.color(),
+        label_x = label_width * 10 / 2,
+        message_x = (label_width + message_width / 2) * 10,
+        message = escape_xml(&message),
+    )
+}
+
+/// Returns a Markdown snippet embedding the badge written to `badge_path`,
+/// with the summary as its alternative text.
+pub fn markdown_snippet(health: &ModelHealth, badge_path: &str) -> String {
+    format!("![{BADGE_LABEL}: {}]({badge_path})\n", health.message())
+}
+
+/// Returns the width of one half of the badge holding `text`.
+fn text_width(text: &str) -> u32 {
+    text.chars().count() as u32 * BADGE_CHAR_WIDTH + 2 * BADGE_PADDING
+}
+
+/// Returns e.g. `1 slice` or `12 slices`; `entity` becomes `entities`.
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {noun}")
+    } else if let Some(stem) = noun.strip_suffix('y') {
+        format!("{count} {stem}ies")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_slices_entities_and_warnings() {
+        let healthy = ModelHealth::Valid {
+            slices: 12,
+            entities: 48,
+            warnings: 0,
+        };
+        let warned = ModelHealth::Valid {
+            slices: 1,
+            entities: 1,
+            warnings: 2,
+        };
+
+        assert_eq!(healthy.message(), "12 slices, 48 entities, \u{2713} valid");
+        assert_eq!(
+            warned.message(),
+            "1 slice, 1 entity, \u{2713} valid, 2 warnings"
+        );
+        assert!(render_badge(&healthy).contains(BADGE_VALID_COLOR));
+        assert!(render_badge(&warned).contains(BADGE_WARNING_COLOR));
+        assert!(render_badge(&ModelHealth::Invalid).contains("\u{2717} invalid"));
+        assert_eq!(
+            markdown_snippet(&healthy, "docs/model-badge.svg"),
+            "![Event Model: 12 slices, 48 entities, \u{2713} valid](docs/model-badge.svg)\n"
+        );
+    }
+}
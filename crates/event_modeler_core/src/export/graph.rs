@@ -0,0 +1,231 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Export of the entity/connection graph for network analysis tools.
+//!
+//! Every entity becomes a node carrying its type, custom kind, swimlane,
+//! owner, and the slices that mention it; every slice connection becomes a
+//! directed edge carrying its slice. The graph can be written as GraphML,
+//! which Gephi and yEd open, or as Cytoscape JSON.
+
+use crate::diagram::EventModelDiagram;
+use crate::event_model::yaml_types::Consistency;
+use crate::infrastructure::markup::escape_xml;
+use serde_json::json;
+
+/// An entity in the exported graph.
+pub(super) struct Node {
+    pub(super) name: String,
+    pub(super) kind: &'static str,
+    pub(super) custom_kind: Option<String>,
+    pub(super) swimlane: String,
+    pub(super) owner: Option<String>,
+    pub(super) slices: Vec<String>,
+}
+
+/// A connection in the exported graph.
+pub(super) struct Edge {
+    source: String,
+    target: String,
+    slice: String,
+    bidirectional: bool,
+    consistency: Option<Consistency>,
+}
+
+/// Writes the graph as a GraphML document.
+pub fn to_graphml(diagram: &EventModelDiagram) -> String {
+    let (nodes, edges) = graph(diagram);
+    let mut graphml = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="label" for="node" attr.name="label" attr.type="string"/>
+  <key id="type" for="node" attr.name="type" attr.type="string"/>
+  <key id="kind" for="node" attr.name="kind" attr.type="string"/>
+  <key id="swimlane" for="node" attr.name="swimlane" attr.type="string"/>
+  <key id="owner" for="node" attr.name="owner" attr.type="string"/>
+  <key id="slices" for="node" attr.name="slices" attr.type="string"/>
+  <key id="slice" for="edge" attr.name="slice" attr.type="string"/>
+  <key id="consistency" for="edge" attr.name="consistency" attr.type="string"/>
+"#,
+    );
+    graphml.push_str(&format!(
+        "  <graph id=\"{}\" edgedefault=\"directed\">\n",
+        escape_xml(diagram.workflow_title().as_str())
+    ));
+    for node in &nodes {
+        let name = escape_xml(&node.name);
+        graphml.push_str(&format!("    <node id=\"{name}\">\n"));
+        graphml.push_str(&format!("      <data key=\"label\">{name}</data>\n"));
+        graphml.push_str(&format!("      <data key=\"type\">{}</data>\n", node.kind));
+        if let Some(kind) = &node.custom_kind {
+            graphml.push_str(&format!(
+                "      <data key=\"kind\">{}</data>\n",
+                escape_xml(kind)
+            ));
+        }
+        graphml.push_str(&format!(
+            "      <data key=\"swimlane\">{}</data>\n",
+            escape_xml(&node.swimlane)
+        ));
+        if let Some(owner) = &node.owner {
+            graphml.push_str(&format!(
+                "      <data key=\"owner\">{}</data>\n",
+                escape_xml(owner)
+            ));
+        }
+        graphml.push_str(&format!(
+            "      <data key=\"slices\">{}</data>\n",
+            escape_xml(&node.slices.join(", "))
+        ));
+        graphml.push_str("    </node>\n");
+    }
+    for (index, edge) in edges.iter().enumerate() {
+        let directed = if edge.bidirectional {
+            " directed=\"false\""
+        } else {
+            ""
+        };
+        graphml.push_str(&format!(
+            "    <edge id=\"e{index}\" source=\"{}\" target=\"{}\"{directed}>\n",
+            escape_xml(&edge.source),
+            escape_xml(&edge.target)
+        ));
+        graphml.push_str(&format!(
+            "      <data key=\"slice\">{}</data>\n",
+            escape_xml(&edge.slice)
+        ));
+        if let Some(consistency) = edge.consistency {
+            graphml.push_str(&format!(
+                "      <data key=\"consistency\">{}</data>\n",
+                consistency_name(consistency)
+            ));
+        }
+        graphml.push_str("    </edge>\n");
+    }
+    graphml.push_str("  </graph>\n</graphml>\n");
+    graphml
+}
+
+/// Writes the graph in Cytoscape's JSON format.
+pub fn to_cytoscape_json(diagram: &EventModelDiagram) -> Result<String, serde_json::Error> {
+    let (nodes, edges) = graph(diagram);
+    let nodes: Vec<serde_json::Value> = nodes
+        .into_iter()
+        .map(|node| {
+            json!({
+                "data": {
+                    "id": node.name,
+                    "name": node.name,
+                    "type": node.kind,
+                    "kind": node.custom_kind,
+                    "swimlane": node.swimlane,
+                    "owner": node.owner,
+                    "slices": node.slices,
+                }
+            })
+        })
+        .collect();
+    let edges: Vec<serde_json::Value> = edges
+        .into_iter()
+        .enumerate()
+        .map(|(index, edge)| {
+            json!({
+                "data": {
+                    "id": format!("e{index}"),
+                    "source": edge.source,
+                    "target": edge.target,
+                    "slice": edge.slice,
+                    "bidirectional": edge.bidirectional,
+                    "consistency": edge.consistency.map(consistency_name),
+                }
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&json!({
+        "data": { "name": diagram.workflow_title().as_str() },
+        "elements": { "nodes": nodes, "edges": edges },
+    }))
+}
+
+/// Collects the nodes, ordered by name, and the edges, in slice order.
+pub(super) fn graph(diagram: &EventModelDiagram) -> (Vec<Node>, Vec<Edge>) {
+    let mut edges = Vec::new();
+    for slice in diagram.slices() {
+        let slice_name = slice.name.clone().into_inner().into_inner();
+        for connection in slice.connections.iter() {
+            edges.push(Edge {
+                source: connection.from.entity_name(),
+                target: connection.to.entity_name(),
+                slice: slice_name.clone(),
+                bidirectional: connection.bidirectional,
+                consistency: connection.consistency,
+            });
+        }
+    }
+
+    let entities = diagram
+        .views()
+        .iter()
+        .map(|(name, def)| (name.clone().into_inner(), "view", &def.swimlane))
+        .chain(
+            diagram
+                .commands()
+                .iter()
+                .map(|(name, def)| (name.clone().into_inner(), "command", &def.swimlane)),
+        )
+        .chain(
+            diagram
+                .events()
+                .iter()
+                .map(|(name, def)| (name.clone().into_inner(), "event", &def.swimlane)),
+        )
+        .chain(
+            diagram
+                .projections()
+                .iter()
+                .map(|(name, def)| (name.clone().into_inner(), "projection", &def.swimlane)),
+        )
+        .chain(
+            diagram
+                .queries()
+                .iter()
+                .map(|(name, def)| (name.clone().into_inner(), "query", &def.swimlane)),
+        )
+        .chain(
+            diagram
+                .automations()
+                .iter()
+                .map(|(name, def)| (name.clone().into_inner(), "automation", &def.swimlane)),
+        );
+    let mut nodes: Vec<Node> = entities
+        .map(|(name, kind, swimlane)| {
+            let name = name.into_inner();
+            let mut slices: Vec<String> = Vec::new();
+            for edge in &edges {
+                if (edge.source == name || edge.target == name) && !slices.contains(&edge.slice) {
+                    slices.push(edge.slice.clone());
+                }
+            }
+            Node {
+                owner: diagram.ownership().owner_of(&name).map(str::to_string),
+                custom_kind: diagram
+                    .kind_of(&name)
+                    .map(|(kind, _)| kind.clone().into_inner().into_inner()),
+                name,
+                kind,
+                swimlane: swimlane.clone().into_inner().into_inner(),
+                slices,
+            }
+        })
+        .collect();
+    nodes.sort_by(|a, b| a.name.cmp(&b.name));
+    (nodes, edges)
+}
+
+/// Returns the name of a consistency as written in a model.
+fn consistency_name(consistency: Consistency) -> &'static str {
+    match consistency {
+        Consistency::Eventual => "eventual",
+        Consistency::Immediate => "immediate",
+    }
+}
@@ -0,0 +1,972 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Markdown export functionality for Event Model diagrams.
+//!
+//! This module handles the generation of Markdown documentation from
+//! Event Model diagrams, including SVG embedding and cross-referencing.
+
+use super::graph;
+use crate::diagram::EventModelDiagram;
+use crate::event_model::yaml_types::{
+    Consistency, Documentation, EntityReference, FieldName, PlaceholderValue, TestOutcome,
+    TestScenario,
+};
+use crate::infrastructure::types::{NonEmptyString, PositiveInt};
+use nutype::nutype;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A complete Markdown document.
+#[derive(Debug, Clone)]
+pub struct MarkdownDocument {
+    /// Sections in the document.
+    pub sections: Vec<MarkdownSection>,
+}
+
+/// Types of sections in a Markdown document.
+#[derive(Debug, Clone)]
+pub enum MarkdownSection {
+    /// Heading section.
+    Heading(HeadingSection),
+    /// Paragraph of text.
+    Paragraph(ParagraphSection),
+    /// Image embed.
+    Image(ImageSection),
+    /// Code block.
+    CodeBlock(CodeBlockSection),
+    /// Table.
+    Table(TableSection),
+    /// List (ordered or unordered).
+    List(ListSection),
+    /// Raw HTML passed through unchanged (e.g. an inline SVG).
+    RawHtml(RawHtmlContent),
+}
+
+/// A heading in the Markdown document.
+#[derive(Debug, Clone)]
+pub struct HeadingSection {
+    /// Heading level (1-6).
+    pub level: HeadingLevel,
+    /// Heading text.
+    pub content: HeadingContent,
+}
+
+/// A paragraph of text.
+#[derive(Debug, Clone)]
+pub struct ParagraphSection {
+    /// Paragraph content.
+    pub content: ParagraphContent,
+}
+
+/// An embedded image.
+#[derive(Debug, Clone)]
+pub struct ImageSection {
+    /// Alternative text for accessibility.
+    pub alt_text: ImageAltText,
+    /// Path to the image.
+    pub path: ImagePath,
+    /// Optional title/tooltip.
+    pub title: Option<ImageTitle>,
+}
+
+/// A code block with optional syntax highlighting.
+#[derive(Debug, Clone)]
+pub struct CodeBlockSection {
+    /// Programming language for syntax highlighting.
+    pub language: Option<CodeLanguage>,
+    /// Code content.
+    pub content: CodeContent,
+}
+
+/// A table in Markdown format.
+#[derive(Debug, Clone)]
+pub struct TableSection {
+    /// Table headers.
+    pub headers: Vec<TableHeader>,
+    /// Table rows.
+    pub rows: Vec<TableRow>,
+}
+
+/// A row in a table.
+#[derive(Debug, Clone)]
+pub struct TableRow {
+    /// Cells in the row.
+    pub cells: Vec<TableCell>,
+}
+
+/// A list (ordered or unordered).
+#[derive(Debug, Clone)]
+pub struct ListSection {
+    /// Type of list.
+    pub list_type: ListType,
+    /// List items.
+    pub items: Vec<ListItem>,
+}
+
+/// Type of list.
+#[derive(Debug, Clone)]
+pub enum ListType {
+    /// Numbered list.
+    Ordered,
+    /// Bulleted list.
+    Unordered,
+}
+
+/// An item in a list.
+#[derive(Debug, Clone)]
+pub struct ListItem {
+    /// Item content.
+    pub content: ListItemContent,
+    /// Nested sub-items.
+    pub sub_items: Option<Vec<ListItem>>,
+}
+
+/// Heading level (1-6).
+#[nutype(derive(Debug, Clone, Copy))]
+pub struct HeadingLevel(PositiveInt);
+
+/// Content of a heading.
+#[nutype(derive(Debug, Clone))]
+pub struct HeadingContent(NonEmptyString);
+
+/// Content of a paragraph.
+#[nutype(derive(Debug, Clone))]
+pub struct ParagraphContent(NonEmptyString);
+
+/// Alternative text for an image.
+#[nutype(derive(Debug, Clone))]
+pub struct ImageAltText(NonEmptyString);
+
+/// Path to an image file.
+#[nutype(derive(Debug, Clone))]
+pub struct ImagePath(NonEmptyString);
+
+/// Title/tooltip for an image.
+#[nutype(derive(Debug, Clone))]
+pub struct ImageTitle(NonEmptyString);
+
+/// Programming language for syntax highlighting.
+#[nutype(derive(Debug, Clone))]
+pub struct CodeLanguage(NonEmptyString);
+
+/// Content of a code block.
+#[nutype(derive(Debug, Clone))]
+pub struct CodeContent(NonEmptyString);
+
+/// Header text for a table column.
+#[nutype(derive(Debug, Clone))]
+pub struct TableHeader(NonEmptyString);
+
+/// Content of a table cell.
+#[nutype(derive(Debug, Clone))]
+pub struct TableCell(String);
+
+/// Content of a list item.
+#[nutype(derive(Debug, Clone))]
+pub struct ListItemContent(NonEmptyString);
+
+/// Raw HTML content embedded verbatim.
+#[nutype(derive(Debug, Clone))]
+pub struct RawHtmlContent(NonEmptyString);
+
+/// Exporter for generating Markdown documentation.
+pub struct MarkdownExporter {
+    /// Export configuration.
+    config: MarkdownExportConfig,
+}
+
+/// Configuration for Markdown export.
+#[derive(Debug, Clone)]
+pub struct MarkdownExportConfig {
+    /// Markdown flavor to use.
+    pub flavor: MarkdownFlavor,
+    /// How to embed SVG diagrams.
+    pub embed_svg: EmbedSvgOption,
+    /// Style for links.
+    pub link_style: LinkStyle,
+    /// Whether scenario tables show a scenario's example values in place of
+    /// the placeholders they stand for.
+    pub show_examples: bool,
+}
+
+/// Markdown syntax flavor.
+#[derive(Debug, Clone)]
+pub enum MarkdownFlavor {
+    /// GitHub Flavored Markdown.
+    Github,
+    /// CommonMark standard.
+    CommonMark,
+    /// Pandoc extended Markdown.
+    Pandoc,
+}
+
+/// How to embed SVG diagrams in Markdown.
+#[derive(Debug, Clone)]
+pub enum EmbedSvgOption {
+    /// Inline SVG in the Markdown.
+    Inline,
+    /// Reference an external SVG file at the given path.
+    Reference(ImagePath),
+}
+
+/// Style for links in Markdown.
+#[derive(Debug, Clone)]
+pub enum LinkStyle {
+    /// Relative paths.
+    Relative,
+    /// Absolute paths.
+    Absolute,
+}
+
+impl MarkdownExporter {
+    /// Create a new Markdown exporter.
+    pub fn new(config: MarkdownExportConfig) -> Self {
+        Self { config }
+    }
+
+    /// Export a diagram to Markdown format.
+    ///
+    /// The document contains the workflow title with its description, version,
+    /// date, and authors, the rendered diagram, the connections of every
+    /// slice, a table per entity type, and the documentation written for
+    /// individual entities.
+    pub fn export_diagram(
+        &self,
+        diagram: &EventModelDiagram,
+        svg: &str,
+    ) -> Result<MarkdownDocument, MarkdownExportError> {
+        let mut sections = vec![heading(1, diagram.workflow_title().as_str())?];
+        let header = diagram.header();
+        if let Some(description) = &header.description {
+            sections.push(MarkdownSection::Paragraph(ParagraphSection {
+                content: ParagraphContent::new(description.clone().into_inner()),
+            }));
+        }
+        if let Some(byline) = header.byline() {
+            sections.push(MarkdownSection::Paragraph(ParagraphSection {
+                content: ParagraphContent::new(non_empty(format!("*{byline}*"), "byline")?),
+            }));
+        }
+
+        sections.push(match &self.config.embed_svg {
+            EmbedSvgOption::Inline => MarkdownSection::RawHtml(RawHtmlContent::new(non_empty(
+                svg.trim().to_string(),
+                "inline SVG",
+            )?)),
+            EmbedSvgOption::Reference(path) => MarkdownSection::Image(ImageSection {
+                alt_text: ImageAltText::new(non_empty(
+                    diagram.workflow_title().as_str().to_string(),
+                    "image alt text",
+                )?),
+                path: path.clone(),
+                title: None,
+            }),
+        });
+
+        if !diagram.slices().is_empty() {
+            sections.push(heading(2, "Slices")?);
+            for slice in diagram.slices() {
+                sections.push(heading(3, slice.name.clone().into_inner().as_str())?);
+                let items = slice
+                    .connections
+                    .iter()
+                    .map(|connection| {
+                        let consistency = connection
+                            .consistency
+                            .map(|consistency| {
+                                format!(" ({} consistency)", consistency_name(consistency))
+                            })
+                            .unwrap_or_default();
+                        list_item(format!(
+                            "`{}` → `{}`{consistency}",
+                            reference_name(&connection.from),
+                            reference_name(&connection.to)
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                sections.push(MarkdownSection::List(ListSection {
+                    list_type: ListType::Unordered,
+                    items,
+                }));
+            }
+        }
+
+        // Access columns appear once any command states idempotency or roles
+        let show_access = diagram
+            .commands()
+            .values()
+            .any(|def| def.idempotent.is_some() || !def.authorized_roles.is_empty());
+        let mut commands: Vec<_> = diagram
+            .commands()
+            .iter()
+            .map(|(name, def)| {
+                let mut row = vec![
+                    name.clone().into_inner().into_inner(),
+                    def.description.clone().into_inner().into_inner(),
+                    def.swimlane.clone().into_inner().into_inner(),
+                ];
+                if show_access {
+                    row.push(
+                        match def.idempotent {
+                            Some(true) => "yes",
+                            Some(false) => "no",
+                            None => "",
+                        }
+                        .to_string(),
+                    );
+                    row.push(
+                        def.authorized_roles
+                            .iter()
+                            .map(|role| role.clone().into_inner().into_inner())
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    );
+                }
+                row
+            })
+            .collect();
+        let mut command_headers = vec!["Command", "Description", "Swimlane"];
+        if show_access {
+            command_headers.extend(["Idempotent", "Authorized Roles"]);
+        }
+        push_entity_table(&mut sections, "Commands", &command_headers, &mut commands)?;
+
+        let mut events: Vec<_> = diagram
+            .events()
+            .iter()
+            .map(|(name, def)| {
+                vec![
+                    name.clone().into_inner().into_inner(),
+                    def.description.clone().into_inner().into_inner(),
+                    def.swimlane.clone().into_inner().into_inner(),
+                ]
+            })
+            .collect();
+        push_entity_table(
+            &mut sections,
+            "Events",
+            &["Event", "Description", "Swimlane"],
+            &mut events,
+        )?;
+
+        let mut views: Vec<_> = diagram
+            .views()
+            .iter()
+            .map(|(name, def)| {
+                vec![
+                    name.clone().into_inner().into_inner(),
+                    def.description.clone().into_inner().into_inner(),
+                    def.swimlane.clone().into_inner().into_inner(),
+                ]
+            })
+            .collect();
+        push_entity_table(
+            &mut sections,
+            "Views",
+            &["View", "Description", "Swimlane"],
+            &mut views,
+        )?;
+
+        // Consistency gets a column once any connection into a projection declares it
+        let feeds = projection_consistency(diagram);
+        let mut projections: Vec<_> = diagram
+            .projections()
+            .iter()
+            .map(|(name, def)| {
+                let name = name.clone().into_inner().into_inner();
+                let mut row = vec![
+                    name.clone(),
+                    def.description.clone().into_inner().into_inner(),
+                    def.swimlane.clone().into_inner().into_inner(),
+                ];
+                if !feeds.is_empty() {
+                    row.push(feeds.get(&name).cloned().unwrap_or_default());
+                }
+                row
+            })
+            .collect();
+        let mut projection_headers = vec!["Projection", "Description", "Swimlane"];
+        if !feeds.is_empty() {
+            projection_headers.push("Consistency");
+        }
+        push_entity_table(
+            &mut sections,
+            "Projections",
+            &projection_headers,
+            &mut projections,
+        )?;
+
+        let mut queries: Vec<_> = diagram
+            .queries()
+            .iter()
+            .map(|(name, def)| {
+                vec![
+                    name.clone().into_inner().into_inner(),
+                    def.swimlane.clone().into_inner().into_inner(),
+                ]
+            })
+            .collect();
+        push_entity_table(
+            &mut sections,
+            "Queries",
+            &["Query", "Swimlane"],
+            &mut queries,
+        )?;
+
+        let mut automations: Vec<_> = diagram
+            .automations()
+            .iter()
+            .map(|(name, def)| {
+                vec![
+                    name.clone().into_inner().into_inner(),
+                    def.swimlane.clone().into_inner().into_inner(),
+                ]
+            })
+            .collect();
+        push_entity_table(
+            &mut sections,
+            "Automations",
+            &["Automation", "Swimlane"],
+            &mut automations,
+        )?;
+
+        // Nodes come ordered by name
+        let (nodes, _) = graph::graph(diagram);
+        let mut kinds: Vec<_> = diagram
+            .extensions()
+            .iter()
+            .map(|(kind, extension)| {
+                let kind = kind.clone().into_inner().into_inner();
+                let entities: Vec<&str> = nodes
+                    .iter()
+                    .filter(|node| node.custom_kind.as_ref() == Some(&kind))
+                    .map(|node| node.name.as_str())
+                    .collect();
+                vec![kind, extension.base.name().to_string(), entities.join(", ")]
+            })
+            .collect();
+        push_entity_table(
+            &mut sections,
+            "Kinds",
+            &["Kind", "Base Type", "Entities"],
+            &mut kinds,
+        )?;
+
+        let mut types: Vec<_> = diagram
+            .types()
+            .iter()
+            .map(|(name, definition)| {
+                vec![
+                    name.clone().into_inner().into_inner(),
+                    definition.to_string(),
+                ]
+            })
+            .collect();
+        push_entity_table(&mut sections, "Types", &["Type", "Definition"], &mut types)?;
+
+        push_specifications(&mut sections, diagram, self.config.show_examples)?;
+        push_documentation(&mut sections, diagram)?;
+        push_hotspots(&mut sections, diagram)?;
+
+        Ok(MarkdownDocument { sections })
+    }
+
+    /// Write a Markdown document to a file.
+    pub fn write_to_file(
+        &self,
+        document: &MarkdownDocument,
+        path: &Path,
+    ) -> Result<(), MarkdownExportError> {
+        std::fs::write(path, document.render())?;
+        Ok(())
+    }
+
+    /// Get the current configuration.
+    pub fn config(&self) -> &MarkdownExportConfig {
+        &self.config
+    }
+}
+
+impl MarkdownDocument {
+    /// Renders the document as Markdown text.
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+        for section in &self.sections {
+            match section {
+                MarkdownSection::Heading(heading) => {
+                    let level = heading.level.into_inner().value() as usize;
+                    output.push_str(&format!(
+                        "{} {}\n\n",
+                        "#".repeat(level),
+                        heading.content.clone().into_inner().as_str()
+                    ));
+                }
+                MarkdownSection::Paragraph(paragraph) => {
+                    output.push_str(paragraph.content.clone().into_inner().as_str());
+                    output.push_str("\n\n");
+                }
+                MarkdownSection::Image(image) => {
+                    let alt = image.alt_text.clone().into_inner();
+                    let path = image.path.clone().into_inner();
+                    match &image.title {
+                        Some(title) => output.push_str(&format!(
+                            "![{}]({} \"{}\")\n\n",
+                            alt.as_str(),
+                            path.as_str(),
+                            title.clone().into_inner().as_str()
+                        )),
+                        None => {
+                            output.push_str(&format!("![{}]({})\n\n", alt.as_str(), path.as_str()))
+                        }
+                    }
+                }
+                MarkdownSection::CodeBlock(code) => {
+                    let language = code
+                        .language
+                        .as_ref()
+                        .map(|language| language.clone().into_inner().into_inner())
+                        .unwrap_or_default();
+                    output.push_str(&format!(
+                        "```{}\n{}\n```\n\n",
+                        language,
+                        code.content.clone().into_inner().as_str()
+                    ));
+                }
+                MarkdownSection::Table(table) => {
+                    let headers: Vec<String> = table
+                        .headers
+                        .iter()
+                        .map(|header| escape_table_cell(header.clone().into_inner().as_str()))
+                        .collect();
+                    output.push_str(&format!("| {} |\n", headers.join(" | ")));
+                    output.push_str(&format!("|{}\n", " --- |".repeat(headers.len())));
+                    for row in &table.rows {
+                        let cells: Vec<String> = row
+                            .cells
+                            .iter()
+                            .map(|cell| escape_table_cell(&cell.clone().into_inner()))
+                            .collect();
+                        output.push_str(&format!("| {} |\n", cells.join(" | ")));
+                    }
+                    output.push('\n');
+                }
+                MarkdownSection::List(list) => {
+                    render_list_items(&mut output, &list.list_type, &list.items, 0);
+                    output.push('\n');
+                }
+                MarkdownSection::RawHtml(html) => {
+                    output.push_str(html.clone().into_inner().as_str());
+                    output.push_str("\n\n");
+                }
+            }
+        }
+        output
+    }
+}
+
+/// Appends list items (and their nested sub-items) at the given depth.
+fn render_list_items(output: &mut String, list_type: &ListType, items: &[ListItem], depth: usize) {
+    for (index, item) in items.iter().enumerate() {
+        let marker = match list_type {
+            ListType::Ordered => format!("{}.", index + 1),
+            ListType::Unordered => "-".to_string(),
+        };
+        output.push_str(&format!(
+            "{}{} {}\n",
+            "  ".repeat(depth),
+            marker,
+            item.content.clone().into_inner().as_str()
+        ));
+        if let Some(sub_items) = &item.sub_items {
+            render_list_items(output, list_type, sub_items, depth + 1);
+        }
+    }
+}
+
+/// Escapes characters that would break a Markdown table cell.
+fn escape_table_cell(content: &str) -> String {
+    content.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Parses a required string for a document element.
+fn non_empty(value: String, what: &str) -> Result<NonEmptyString, MarkdownExportError> {
+    NonEmptyString::parse(value)
+        .map_err(|_| MarkdownExportError::InvalidDiagram(format!("{what} cannot be empty")))
+}
+
+/// Creates a heading section.
+fn heading(level: u32, content: &str) -> Result<MarkdownSection, MarkdownExportError> {
+    let level = PositiveInt::parse(level)
+        .map_err(|e| MarkdownExportError::ExportFailed(format!("Invalid heading level: {e}")))?;
+    Ok(MarkdownSection::Heading(HeadingSection {
+        level: HeadingLevel::new(level),
+        content: HeadingContent::new(non_empty(content.to_string(), "heading")?),
+    }))
+}
+
+/// Creates a list item without sub-items.
+fn list_item(content: String) -> Result<ListItem, MarkdownExportError> {
+    Ok(ListItem {
+        content: ListItemContent::new(non_empty(content, "list item")?),
+        sub_items: None,
+    })
+}
+
+/// Appends a heading and a table of entity rows (sorted by the first column),
+/// skipping entity types the model does not use.
+fn push_entity_table(
+    sections: &mut Vec<MarkdownSection>,
+    title: &str,
+    headers: &[&str],
+    rows: &mut [Vec<String>],
+) -> Result<(), MarkdownExportError> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+    rows.sort();
+
+    sections.push(heading(2, title)?);
+    sections.push(MarkdownSection::Table(TableSection {
+        headers: headers
+            .iter()
+            .map(|header| non_empty(header.to_string(), "table header").map(TableHeader::new))
+            .collect::<Result<Vec<_>, _>>()?,
+        rows: rows
+            .iter()
+            .map(|row| TableRow {
+                cells: row.iter().cloned().map(TableCell::new).collect(),
+            })
+            .collect(),
+    }));
+    Ok(())
+}
+
+/// Appends a table of Given/When/Then scenarios per command that has any,
+/// ordered by command and scenario name.
+fn push_specifications(
+    sections: &mut Vec<MarkdownSection>,
+    diagram: &EventModelDiagram,
+    show_examples: bool,
+) -> Result<(), MarkdownExportError> {
+    let mut specified: Vec<_> = diagram
+        .commands()
+        .iter()
+        .filter(|(_, def)| !def.tests.is_empty())
+        .map(|(name, def)| (name.clone().into_inner().into_inner(), &def.tests))
+        .collect();
+    if specified.is_empty() {
+        return Ok(());
+    }
+    specified.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    sections.push(heading(2, "Specifications")?);
+    for (command, tests) in specified {
+        sections.push(heading(3, &command)?);
+        let mut rows: Vec<Vec<String>> = tests
+            .iter()
+            .map(|(name, scenario)| {
+                let mut row = vec![name.clone().into_inner().into_inner()];
+                row.extend(scenario_cells(scenario, show_examples));
+                row
+            })
+            .collect();
+        rows.sort();
+        sections.push(MarkdownSection::Table(TableSection {
+            headers: ["Scenario", "Given", "When", "Then"]
+                .iter()
+                .map(|header| non_empty(header.to_string(), "table header").map(TableHeader::new))
+                .collect::<Result<Vec<_>, _>>()?,
+            rows: rows
+                .into_iter()
+                .map(|row| TableRow {
+                    cells: row.into_iter().map(TableCell::new).collect(),
+                })
+                .collect(),
+        }));
+    }
+    Ok(())
+}
+
+/// Describes the Given, When, and Then steps of a scenario.
+///
+/// Placeholders such as `A` are replaced by the name of the field where the
+/// scenario first uses them, so `A` reads the same in every step: an
+/// `old_email: B` after `email: B` shows as `old_email: *email*`. With
+/// `show_examples`, placeholders the scenario gives an example for show that
+/// value instead.
+fn scenario_cells(scenario: &TestScenario, show_examples: bool) -> [String; 3] {
+    let mut labels: Vec<(String, String)> = Vec::new();
+    let mut describe = |name: String, fields: &HashMap<FieldName, PlaceholderValue>| {
+        let mut fields: Vec<(String, String)> = fields
+            .iter()
+            .map(|(field, value)| {
+                (
+                    field.clone().into_inner().into_inner(),
+                    value.clone().into_inner().into_inner(),
+                )
+            })
+            .collect();
+        fields.sort();
+        if fields.is_empty() {
+            return name;
+        }
+        let values: Vec<String> = fields
+            .into_iter()
+            .map(|(field, value)| {
+                let example = scenario
+                    .examples
+                    .iter()
+                    .find(|(placeholder, _)| {
+                        (*placeholder).clone().into_inner().into_inner() == value
+                    })
+                    .filter(|_| show_examples);
+                if let Some((_, example)) = example {
+                    return format!("{field}: `{}`", example.clone().into_inner().as_str());
+                }
+                if !is_placeholder(&value) {
+                    return format!("{field}: `{value}`");
+                }
+                let label = match labels.iter().find(|(bound, _)| *bound == value) {
+                    Some((_, label)) => label.clone(),
+                    None => {
+                        let taken = labels
+                            .iter()
+                            .filter(|(_, label)| {
+                                *label == field || label.starts_with(&format!("{field} "))
+                            })
+                            .count();
+                        let label = match taken {
+                            0 => field.clone(),
+                            n => format!("{field} {}", n + 1),
+                        };
+                        labels.push((value, label.clone()));
+                        label
+                    }
+                };
+                format!("{field}: *{label}*")
+            })
+            .collect();
+        format!("{name}({})", values.join(", "))
+    };
+
+    let given: Vec<String> = scenario
+        .given
+        .iter()
+        .map(|event| describe(event.name.clone().into_inner().into_inner(), &event.fields))
+        .collect();
+    let when: Vec<String> = scenario
+        .when
+        .iter()
+        .map(|action| {
+            describe(
+                action.name.clone().into_inner().into_inner(),
+                &action.fields,
+            )
+        })
+        .collect();
+    let then: Vec<String> = scenario
+        .then
+        .iter()
+        .map(|outcome| match outcome {
+            TestOutcome::Event(event) => {
+                describe(event.name.clone().into_inner().into_inner(), &event.fields)
+            }
+            TestOutcome::Error(error) => format!(
+                "rejected with {}",
+                describe(error.name.clone().into_inner().into_inner(), &error.fields)
+            ),
+        })
+        .collect();
+
+    let cell = |steps: Vec<String>| {
+        if steps.is_empty() {
+            "nothing".to_string()
+        } else {
+            steps.join("<br>")
+        }
+    };
+    [cell(given), cell(when), cell(then)]
+}
+
+/// Returns whether a scenario value is a placeholder such as `A`, `B2`, or
+/// `C'` rather than a literal value.
+fn is_placeholder(value: &str) -> bool {
+    let mut chars = value.chars();
+    chars.next().is_some_and(|first| first.is_ascii_uppercase())
+        && chars.all(|c| c.is_ascii_digit() || c == '\'')
+}
+
+/// Appends a section per documented entity, ordered by name. Inline
+/// Markdown is included as written; documentation files are linked.
+fn push_documentation(
+    sections: &mut Vec<MarkdownSection>,
+    diagram: &EventModelDiagram,
+) -> Result<(), MarkdownExportError> {
+    let mut documented: Vec<(String, &Documentation)> = diagram
+        .events()
+        .iter()
+        .filter_map(|(name, def)| {
+            Some((
+                name.clone().into_inner().into_inner(),
+                def.documentation.as_ref()?,
+            ))
+        })
+        .chain(diagram.commands().iter().filter_map(|(name, def)| {
+            Some((
+                name.clone().into_inner().into_inner(),
+                def.documentation.as_ref()?,
+            ))
+        }))
+        .chain(diagram.views().iter().filter_map(|(name, def)| {
+            Some((
+                name.clone().into_inner().into_inner(),
+                def.documentation.as_ref()?,
+            ))
+        }))
+        .chain(diagram.projections().iter().filter_map(|(name, def)| {
+            Some((
+                name.clone().into_inner().into_inner(),
+                def.documentation.as_ref()?,
+            ))
+        }))
+        .chain(diagram.queries().iter().filter_map(|(name, def)| {
+            Some((
+                name.clone().into_inner().into_inner(),
+                def.documentation.as_ref()?,
+            ))
+        }))
+        .chain(diagram.automations().iter().filter_map(|(name, def)| {
+            Some((
+                name.clone().into_inner().into_inner(),
+                def.documentation.as_ref()?,
+            ))
+        }))
+        .collect();
+    if documented.is_empty() {
+        return Ok(());
+    }
+    documented.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    sections.push(heading(2, "Documentation")?);
+    for (name, documentation) in documented {
+        sections.push(heading(3, &name)?);
+        let content = match documentation {
+            Documentation::Markdown(text) => text.clone().into_inner(),
+            Documentation::File(path) => {
+                let path = path.as_path_buf().display().to_string();
+                non_empty(format!("See [{path}]({path})."), "documentation link")?
+            }
+        };
+        sections.push(MarkdownSection::Paragraph(ParagraphSection {
+            content: ParagraphContent::new(content),
+        }));
+    }
+    Ok(())
+}
+
+/// Appends an appendix listing every hotspot: entities by name, then
+/// connections in slice order.
+fn push_hotspots(
+    sections: &mut Vec<MarkdownSection>,
+    diagram: &EventModelDiagram,
+) -> Result<(), MarkdownExportError> {
+    let hotspots = diagram.hotspots();
+    if hotspots.is_empty() {
+        return Ok(());
+    }
+
+    sections.push(heading(2, "Hotspots")?);
+    sections.push(MarkdownSection::Table(TableSection {
+        headers: ["Where", "Risk"]
+            .iter()
+            .map(|header| non_empty(header.to_string(), "table header").map(TableHeader::new))
+            .collect::<Result<Vec<_>, _>>()?,
+        rows: hotspots
+            .hotspots()
+            .iter()
+            .map(|hotspot| {
+                let place = match &hotspot.slice {
+                    Some(slice) => format!("{} in slice {slice}", hotspot.subject),
+                    None => format!("{} {}", hotspot.kind, hotspot.subject),
+                };
+                TableRow {
+                    cells: vec![
+                        TableCell::new(format!("\u{26a0} {place}")),
+                        TableCell::new(hotspot.reason.clone()),
+                    ],
+                }
+            })
+            .collect(),
+    }));
+    Ok(())
+}
+
+/// Describes, for each projection fed by a connection declaring its
+/// consistency, which events update it and how soon, e.g.
+/// `OrderPlaced: eventual, OrderShipped: immediate`.
+fn projection_consistency(diagram: &EventModelDiagram) -> HashMap<String, String> {
+    let mut feeds: HashMap<String, Vec<String>> = HashMap::new();
+    for connection in diagram
+        .slices()
+        .iter()
+        .flat_map(|slice| slice.connections.iter())
+    {
+        let Some(consistency) = connection.consistency else {
+            continue;
+        };
+        let feed = format!(
+            "{}: {}",
+            reference_name(&connection.from),
+            consistency_name(consistency)
+        );
+        let projection = feeds.entry(reference_name(&connection.to)).or_default();
+        if !projection.contains(&feed) {
+            projection.push(feed);
+        }
+    }
+    feeds
+        .into_iter()
+        .map(|(projection, mut feeds)| {
+            feeds.sort();
+            (projection, feeds.join(", "))
+        })
+        .collect()
+}
+
+/// Returns the name of a consistency as written in a model.
+fn consistency_name(consistency: Consistency) -> &'static str {
+    match consistency {
+        Consistency::Eventual => "eventual",
+        Consistency::Immediate => "immediate",
+    }
+}
+
+/// Returns the name used to display an entity reference.
+fn reference_name(reference: &EntityReference) -> String {
+    match reference {
+        EntityReference::Event(name) => name.clone().into_inner().into_inner(),
+        EntityReference::Command(name) => name.clone().into_inner().into_inner(),
+        EntityReference::View(path) => path.clone().into_inner().into_inner(),
+        EntityReference::Projection(name) => name.clone().into_inner().into_inner(),
+        EntityReference::Query(name) => name.clone().into_inner().into_inner(),
+        EntityReference::Automation(name) => name.clone().into_inner().into_inner(),
+    }
+}
+
+/// Errors that can occur during Markdown export.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum MarkdownExportError {
+    /// I/O error occurred.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The diagram is invalid.
+    #[error("Invalid diagram: {0}")]
+    InvalidDiagram(String),
+
+    /// Export failed for another reason.
+    #[error("Export failed: {0}")]
+    ExportFailed(String),
+}
@@ -0,0 +1,127 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Export of a diagram as an MDX page for Docusaurus sites.
+//!
+//! The page embeds the SVG as a string and hands it, together with a JSON
+//! props file describing every entity, to the `EventModel` React component
+//! shipped in `integrations/docusaurus/`. The component shows a tooltip per
+//! entity and filters the diagram by slice, type, or owner. It finds the
+//! entities in the SVG through the links rendered with
+//! [`MDX_ENTITY_LINKS`].
+
+use super::graph;
+use crate::diagram::EventModelDiagram;
+use serde_json::json;
+
+/// Entity link template the embedded SVG must be rendered with, so the
+/// component can tell which entity each link covers.
+pub const MDX_ENTITY_LINKS: &str = "#entity-{name}";
+
+/// Where a page imports the `EventModel` component from, following the
+/// Docusaurus convention for site components.
+const COMPONENT_IMPORT: &str = "@site/src/components/EventModel";
+
+/// Writes the MDX page, importing its props from `props_file`, a path
+/// relative to the page. The workflow description becomes the page's
+/// description.
+pub fn to_mdx(diagram: &EventModelDiagram, svg: &str, props_file: &str) -> String {
+    // The XML declaration is not allowed inside an HTML document
+    let svg = svg
+        .strip_prefix(r#"<?xml version="1.0" encoding="UTF-8"?>"#)
+        .unwrap_or(svg)
+        .trim_start();
+    let title = diagram.workflow_title().as_str();
+    // JSON string literals are valid JavaScript string literals, and valid
+    // YAML scalars in the front matter
+    let quoted = |text: &str| serde_json::Value::from(text).to_string();
+    let description = diagram
+        .header()
+        .description
+        .as_ref()
+        .map(|description| {
+            format!(
+                "description: {}\n",
+                quoted(description.clone().into_inner().as_str())
+            )
+        })
+        .unwrap_or_default();
+    format!(
+        "---\ntitle: {}\n{description}---\n\nimport EventModel from \"{COMPONENT_IMPORT}\";\nimport props from {};\n\nexport const svg = {};\n\n<EventModel svg={{svg}} {{...props}} />\n",
+        quoted(title),
+        quoted(&format!("./{props_file}")),
+        quoted(svg)
+    )
+}
+
+/// Writes the props of the `EventModel` component: the workflow with its
+/// description, authors, version, and date, its slices in order, and every
+/// entity with its type, custom kind, swimlane, owner, description, and
+/// slices.
+pub fn to_props_json(diagram: &EventModelDiagram) -> Result<String, serde_json::Error> {
+    let (nodes, _) = graph::graph(diagram);
+    let descriptions = descriptions(diagram);
+    let entities: Vec<serde_json::Value> = nodes
+        .into_iter()
+        .map(|node| {
+            json!({
+                "description": descriptions.get(&node.name),
+                "name": node.name,
+                "type": node.kind,
+                "kind": node.custom_kind,
+                "swimlane": node.swimlane,
+                "owner": node.owner,
+                "slices": node.slices,
+            })
+        })
+        .collect();
+    let slices: Vec<String> = diagram
+        .slices()
+        .iter()
+        .map(|slice| slice.name.clone().into_inner().into_inner())
+        .collect();
+    let header = diagram.header();
+    serde_json::to_string_pretty(&json!({
+        "workflow": diagram.workflow_title().as_str(),
+        "description": header
+            .description
+            .as_ref()
+            .map(|description| description.clone().into_inner().into_inner()),
+        "authors": header
+            .authors
+            .iter()
+            .map(|author| author.as_ref().as_str())
+            .collect::<Vec<_>>(),
+        "version": header.version.as_ref().map(|version| version.as_ref().as_str()),
+        "date": header.date.as_ref().map(AsRef::<str>::as_ref),
+        "slices": slices,
+        "entities": entities,
+    }))
+}
+
+/// Returns the description of each entity that has one.
+fn descriptions(diagram: &EventModelDiagram) -> std::collections::HashMap<String, String> {
+    let describe = |name: String, description: &crate::event_model::yaml_types::Description| {
+        (name, description.clone().into_inner().into_inner())
+    };
+    diagram
+        .views()
+        .iter()
+        .map(|(name, def)| describe(name.clone().into_inner().into_inner(), &def.description))
+        .chain(
+            diagram.commands().iter().map(|(name, def)| {
+                describe(name.clone().into_inner().into_inner(), &def.description)
+            }),
+        )
+        .chain(
+            diagram.events().iter().map(|(name, def)| {
+                describe(name.clone().into_inner().into_inner(), &def.description)
+            }),
+        )
+        .chain(
+            diagram.projections().iter().map(|(name, def)| {
+                describe(name.clone().into_inner().into_inner(), &def.description)
+            }),
+        )
+        .collect()
+}
@@ -0,0 +1,165 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Diagrams for MkDocs sites, written where pages ask for them.
+//!
+//! Pages written for the MkDocs macros plugin embed a diagram with a
+//! placeholder such as `{{ eventmodel("models/orders.eventmodel") }}`, or
+//! `{{ eventmodel("models/orders.eventmodel", slice="Place Order") }}` for the
+//! diagram with one slice in focus. `event_modeler docs mkdocs` finds these
+//! placeholders in every page of a docs directory, renders each diagram asked
+//! for once into the assets folder, and replaces each placeholder with a
+//! Markdown image of the diagram, so the site builds without the plugin.
+
+use regex::Regex;
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
+
+/// Folder diagrams are written into unless told otherwise, relative to the
+/// docs directory.
+pub const DEFAULT_ASSETS_DIR: &str = "assets/eventmodels";
+
+/// A placeholder: the model path, then optionally the slice in focus.
+const PLACEHOLDER_PATTERN: &str =
+    r#"\{\{\s*eventmodel\(\s*"([^"]+)"\s*(?:,\s*slice\s*=\s*"([^"]+)"\s*)?\)\s*\}\}"#;
+
+/// A diagram a page asks for.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DiagramRequest {
+    /// The model file, relative to the docs directory.
+    pub model: PathBuf,
+    /// The slice to put in focus, if any.
+    pub slice: Option<String>,
+}
+
+impl DiagramRequest {
+    /// Returns the name of the file the diagram is written to, named after
+    /// the model's path so models of the same name in different folders do
+    /// not collide, e.g. `models-orders.svg` or
+    /// `models-orders--place-order.svg`.
+    pub fn asset_name(&self) -> String {
+        let stem = slug(&self.model.with_extension("").to_string_lossy());
+        match &self.slice {
+            Some(slice) => format!("{stem}--{}.svg", slug(slice)),
+            None => format!("{stem}.svg"),
+        }
+    }
+
+    /// Returns the alternative text of the image replacing the placeholder.
+    fn alt_text(&self) -> String {
+        match &self.slice {
+            Some(slice) => slice.clone(),
+            None => self
+                .model
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// A placeholder in a page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholder {
+    /// Where the placeholder is in the page, in bytes.
+    pub range: Range<usize>,
+    /// The diagram it asks for.
+    pub request: DiagramRequest,
+}
+
+/// Finds every placeholder in a page, in order.
+pub fn find_placeholders(page: &str) -> Vec<Placeholder> {
+    let Ok(pattern) = Regex::new(PLACEHOLDER_PATTERN) else {
+        return Vec::new();
+    };
+    pattern
+        .captures_iter(page)
+        .filter_map(|captures| {
+            Some(Placeholder {
+                range: captures.get(0)?.range(),
+                request: DiagramRequest {
+                    model: PathBuf::from(captures.get(1)?.as_str()),
+                    slice: captures.get(2).map(|slice| slice.as_str().to_string()),
+                },
+            })
+        })
+        .collect()
+}
+
+/// Replaces each placeholder in a page with an image of its diagram.
+///
+/// `page` and `assets` are relative to the docs directory: the page being
+/// rewritten and the folder the diagrams were written into.
+pub fn rewrite(content: &str, placeholders: &[Placeholder], page: &Path, assets: &Path) -> String {
+    let mut rewritten = String::with_capacity(content.len());
+    let mut rest = 0;
+    for placeholder in placeholders {
+        let asset = assets.join(placeholder.request.asset_name());
+        rewritten.push_str(&content[rest..placeholder.range.start]);
+        rewritten.push_str(&format!(
+            "![{}]({})",
+            placeholder.request.alt_text(),
+            relative_link(page, &asset)
+        ));
+        rest = placeholder.range.end;
+    }
+    rewritten.push_str(&content[rest..]);
+    rewritten
+}
+
+/// Returns the link from `page` to `target`, both relative to the docs
+/// directory, with `/` separators as Markdown expects.
+fn relative_link(page: &Path, target: &Path) -> String {
+    let normal = |path: &Path| -> Vec<String> {
+        path.components()
+            .filter_map(|component| match component {
+                Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect()
+    };
+    let from = normal(page.parent().unwrap_or(Path::new("")));
+    let to = normal(target);
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let mut parts = vec![".."; from.len() - common];
+    parts.extend(to[common..].iter().map(String::as_str));
+    parts.join("/")
+}
+
+/// Lowercases a name and joins its words with hyphens, for file names.
+pub fn slug(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_placeholders_with_images_of_their_diagrams() {
+        let page = "# Ordering\n\n{{ eventmodel(\"models/orders.eventmodel\") }}\n\nPlacing:\n{{eventmodel( \"models/orders.eventmodel\", slice=\"Place Order\" )}}\n";
+
+        let placeholders = find_placeholders(page);
+
+        assert_eq!(
+            placeholders
+                .iter()
+                .map(|placeholder| placeholder.request.asset_name())
+                .collect::<Vec<_>>(),
+            ["models-orders.svg", "models-orders--place-order.svg"]
+        );
+        assert_eq!(
+            rewrite(
+                page,
+                &placeholders,
+                Path::new("guide/ordering.md"),
+                Path::new(DEFAULT_ASSETS_DIR)
+            ),
+            "# Ordering\n\n![orders](../assets/eventmodels/models-orders.svg)\n\nPlacing:\n![Place Order](../assets/eventmodels/models-orders--place-order.svg)\n"
+        );
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Exporting Event Model diagrams to documentation formats.
+//!
+//! Once you have an Event Model diagram, you want to share it with your team.
+//! This module handles exporting diagrams to different formats suitable for
+//! documentation, presentations, and reports.
+//!
+//! Formats pulling in dependencies of their own sit behind cargo features:
+//! `pdf` for the `pdf` and `poster` modules, `png` for `png`, `html` for
+//! `site` and `storyboard`, and `sqlite` for `sqlite`. The rest are always available.
+
+pub mod badge;
+pub mod graph;
+pub mod markdown;
+pub mod mdx;
+pub mod mkdocs;
+#[cfg(feature = "pdf")]
+pub mod pdf;
+#[cfg(feature = "png")]
+pub mod png;
+#[cfg(feature = "pdf")]
+pub mod poster;
+#[cfg(feature = "html")]
+pub mod site;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "html")]
+pub mod storyboard;
+
+pub use markdown::{MarkdownExportConfig, MarkdownExportError, MarkdownExporter};
+#[cfg(feature = "pdf")]
+pub use pdf::{PdfExportConfig, PdfExportError, PdfExporter};
+#[cfg(feature = "png")]
+pub use png::{PngExportConfig, PngExportError, PngExporter};
@@ -0,0 +1,453 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! PDF export functionality for Event Model diagrams.
+//!
+//! This module handles the conversion of SVG diagrams to PDF format,
+//! including page layout, metadata, and font embedding. [`PdfExporter`]
+//! converts a rendered diagram with svg2pdf, onto a page of its own size or
+//! scaled to fit a sheet of paper, with the workflow's title as the
+//! document's title.
+
+use super::poster::{PosterLayout, PosterOptions, write_pages};
+use crate::diagram::Length;
+use crate::infrastructure::types::{NonEmptyString, NonNegativeFloat, PositiveFloat};
+use nutype::nutype;
+use std::path::Path;
+use svg2pdf::usvg::{self, TreeParsing, TreePostProc};
+
+/// A complete PDF document.
+#[derive(Debug, Clone)]
+pub struct PdfDocument {
+    /// Document metadata.
+    pub metadata: PdfMetadata,
+    /// Pages in the document.
+    pub pages: Vec<PdfPage>,
+}
+
+/// PDF document metadata.
+#[derive(Debug, Clone)]
+pub struct PdfMetadata {
+    /// Document title.
+    pub title: Option<PdfTitle>,
+    /// Document author.
+    pub author: Option<PdfAuthor>,
+    /// Document subject.
+    pub subject: Option<PdfSubject>,
+    /// Document keywords.
+    pub keywords: Option<PdfKeywords>,
+    /// Software that created the PDF.
+    pub creator: PdfCreator,
+    /// Creation date.
+    pub creation_date: PdfDate,
+}
+
+/// A single page in a PDF document.
+#[derive(Debug, Clone)]
+pub struct PdfPage {
+    /// Page size.
+    pub size: PageSize,
+    /// Page orientation.
+    pub orientation: PageOrientation,
+    /// Page margins.
+    pub margins: PageMargins,
+    /// Page content.
+    pub content: PageContent,
+}
+
+/// Standard page sizes.
+#[derive(Debug, Clone)]
+pub enum PageSize {
+    /// A4 (210mm × 297mm).
+    A4,
+    /// US Letter (8.5" × 11").
+    Letter,
+    /// US Legal (8.5" × 14").
+    Legal,
+    /// A3 (297mm × 420mm).
+    A3,
+    /// A2 (420mm × 594mm).
+    A2,
+    /// A1 (594mm × 841mm).
+    A1,
+    /// A0 (841mm × 1189mm).
+    A0,
+    /// Custom size, in PostScript points.
+    Custom(PageWidth, PageHeight),
+}
+
+impl PageSize {
+    /// Parses a paper name such as `a0` or `letter`, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "a0" => Some(Self::A0),
+            "a1" => Some(Self::A1),
+            "a2" => Some(Self::A2),
+            "a3" => Some(Self::A3),
+            "a4" => Some(Self::A4),
+            "letter" => Some(Self::Letter),
+            "legal" => Some(Self::Legal),
+            _ => None,
+        }
+    }
+
+    /// Returns the width and height of the page in portrait orientation.
+    pub fn dimensions(&self) -> (Length, Length) {
+        match self {
+            Self::A4 => (
+                Length::from_millimeters(210.0),
+                Length::from_millimeters(297.0),
+            ),
+            Self::Letter => (Length::from_inches(8.5), Length::from_inches(11.0)),
+            Self::Legal => (Length::from_inches(8.5), Length::from_inches(14.0)),
+            Self::A3 => (
+                Length::from_millimeters(297.0),
+                Length::from_millimeters(420.0),
+            ),
+            Self::A2 => (
+                Length::from_millimeters(420.0),
+                Length::from_millimeters(594.0),
+            ),
+            Self::A1 => (
+                Length::from_millimeters(594.0),
+                Length::from_millimeters(841.0),
+            ),
+            Self::A0 => (
+                Length::from_millimeters(841.0),
+                Length::from_millimeters(1189.0),
+            ),
+            Self::Custom(width, height) => (
+                Length::from_points(f64::from(width.into_inner().value())),
+                Length::from_points(f64::from(height.into_inner().value())),
+            ),
+        }
+    }
+}
+
+/// Page orientation.
+#[derive(Debug, Clone)]
+pub enum PageOrientation {
+    /// Taller than wide.
+    Portrait,
+    /// Wider than tall.
+    Landscape,
+}
+
+/// Margins for a PDF page.
+#[derive(Debug, Clone)]
+pub struct PageMargins {
+    /// Top margin.
+    pub top: MarginValue,
+    /// Right margin.
+    pub right: MarginValue,
+    /// Bottom margin.
+    pub bottom: MarginValue,
+    /// Left margin.
+    pub left: MarginValue,
+}
+
+/// Content that can be placed on a PDF page.
+#[derive(Debug, Clone)]
+pub enum PageContent {
+    /// SVG diagram content.
+    // TODO: Re-enable when SvgDocument is available
+    // Svg(SvgDocument),
+    Svg(String),
+    /// Text content.
+    Text(PdfText),
+}
+
+/// Text content for a PDF page.
+#[derive(Debug, Clone)]
+pub struct PdfText {
+    /// The text to display.
+    pub content: TextContent,
+    /// Text styling.
+    pub style: PdfTextStyle,
+}
+
+/// Style properties for PDF text.
+#[derive(Debug, Clone)]
+pub struct PdfTextStyle {
+    /// Font selection.
+    pub font: PdfFont,
+    /// Font size.
+    pub size: PdfFontSize,
+    /// Text color.
+    pub color: PdfColor,
+}
+
+/// Available PDF fonts.
+#[derive(Debug, Clone)]
+pub enum PdfFont {
+    /// Helvetica (sans-serif).
+    Helvetica,
+    /// Times Roman (serif).
+    TimesRoman,
+    /// Courier (monospace).
+    Courier,
+    /// Custom font.
+    Custom(PdfFontName),
+}
+
+/// PDF document title.
+#[nutype(derive(Debug, Clone, AsRef))]
+pub struct PdfTitle(NonEmptyString);
+
+/// PDF document author.
+#[nutype(derive(Debug, Clone))]
+pub struct PdfAuthor(NonEmptyString);
+
+/// PDF document subject.
+#[nutype(derive(Debug, Clone))]
+pub struct PdfSubject(NonEmptyString);
+
+/// PDF document keywords.
+#[nutype(derive(Debug, Clone))]
+pub struct PdfKeywords(NonEmptyString);
+
+/// PDF creator software name.
+#[nutype(derive(Debug, Clone))]
+pub struct PdfCreator(NonEmptyString);
+
+/// PDF creation date.
+#[nutype(derive(Debug, Clone))]
+pub struct PdfDate(NonEmptyString);
+
+/// Width of a PDF page.
+#[nutype(derive(Debug, Clone, Copy))]
+pub struct PageWidth(PositiveFloat);
+
+/// Height of a PDF page.
+#[nutype(derive(Debug, Clone, Copy))]
+pub struct PageHeight(PositiveFloat);
+
+/// Margin value.
+#[nutype(derive(Debug, Clone, Copy))]
+pub struct MarginValue(NonNegativeFloat);
+
+/// Text content for PDF.
+#[nutype(derive(Debug, Clone))]
+pub struct TextContent(NonEmptyString);
+
+/// Font size for PDF text.
+#[nutype(derive(Debug, Clone, Copy))]
+pub struct PdfFontSize(PositiveFloat);
+
+/// Color for PDF text.
+#[nutype(derive(Debug, Clone))]
+pub struct PdfColor(NonEmptyString);
+
+/// Custom font name.
+#[nutype(derive(Debug, Clone))]
+pub struct PdfFontName(NonEmptyString);
+
+/// Exporter for converting SVG to PDF.
+///
+/// Text is drawn as outlines of the system fonts, so the document shows the
+/// same whether or not the reader has the fonts installed.
+pub struct PdfExporter {
+    /// Export configuration.
+    config: PdfExportConfig,
+}
+
+/// Configuration for PDF export.
+#[derive(Debug, Clone)]
+pub struct PdfExportConfig {
+    /// Whether to compress the PDF.
+    pub compress: CompressionEnabled,
+    /// Whether to embed fonts.
+    pub embed_fonts: EmbedPdfFonts,
+    /// Color space for the PDF.
+    pub color_space: ColorSpace,
+    /// Paper the diagram is scaled to fit, turned to its orientation, or
+    /// `None` for a page the diagram's own size.
+    pub page_size: Option<PageSize>,
+    /// Title recorded in the document's metadata.
+    pub title: Option<PdfTitle>,
+}
+
+impl Default for PdfExportConfig {
+    fn default() -> Self {
+        Self {
+            compress: CompressionEnabled::new(true),
+            embed_fonts: EmbedPdfFonts::new(true),
+            color_space: ColorSpace::Rgb,
+            page_size: None,
+            title: None,
+        }
+    }
+}
+
+/// PDF color space options.
+#[derive(Debug, Clone)]
+pub enum ColorSpace {
+    /// RGB color space.
+    Rgb,
+    /// CMYK color space.
+    Cmyk,
+    /// Grayscale.
+    Grayscale,
+}
+
+/// Whether PDF compression is enabled.
+#[nutype(derive(Debug, Clone))]
+pub struct CompressionEnabled(bool);
+
+/// Whether to embed fonts in the PDF.
+#[nutype(derive(Debug, Clone))]
+pub struct EmbedPdfFonts(bool);
+
+impl PdfExporter {
+    /// Create a new PDF exporter.
+    pub fn new(config: PdfExportConfig) -> Self {
+        Self { config }
+    }
+
+    /// Export an SVG document to a PDF file.
+    pub fn export(&self, svg: &str, path: &Path) -> Result<(), PdfExportError> {
+        std::fs::write(path, self.export_to_buffer(svg)?)?;
+        Ok(())
+    }
+
+    /// Export an SVG document to a PDF byte buffer.
+    ///
+    /// Only the RGB color space is supported; asking for another fails
+    /// rather than producing colors the printer would not expect.
+    pub fn export_to_buffer(&self, svg: &str) -> Result<Vec<u8>, PdfExportError> {
+        if !matches!(self.config.color_space, ColorSpace::Rgb) {
+            return Err(PdfExportError::ExportFailed(format!(
+                "the {:?} color space is not supported",
+                self.config.color_space
+            )));
+        }
+        let tree = load_svg(svg)?;
+
+        let (width, height) = (f64::from(tree.size.width()), f64::from(tree.size.height()));
+        let layout = match &self.config.page_size {
+            Some(paper) => PosterLayout::plan(
+                width,
+                height,
+                &PosterOptions {
+                    paper: paper.clone(),
+                    tile: None,
+                    overlap: Length::from_millimeters(0.0),
+                    title: None,
+                },
+            ),
+            None => PosterLayout::actual_size(width, height),
+        };
+        let title = self
+            .config
+            .title
+            .as_ref()
+            .map(|title| title.as_ref().as_str());
+        Ok(write_pages(
+            &tree,
+            &layout,
+            title,
+            svg2pdf::Options {
+                compress: self.config.compress.clone().into_inner(),
+                ..svg2pdf::Options::default()
+            },
+        ))
+    }
+
+    /// Get the current configuration.
+    pub fn config(&self) -> &PdfExportConfig {
+        &self.config
+    }
+}
+
+/// Parses an SVG for conversion to PDF, turning its text into paths drawn
+/// with the system fonts.
+pub fn load_svg(svg: &str) -> Result<usvg::Tree, PdfExportError> {
+    let mut tree = usvg::Tree::from_str(svg, &usvg::Options::default())
+        .map_err(|e| PdfExportError::InvalidSvg(e.to_string()))?;
+    let mut fonts = usvg::fontdb::Database::new();
+    fonts.load_system_fonts();
+    tree.postprocess(usvg::PostProcessingSteps::default(), &fonts);
+    Ok(tree)
+}
+
+/// Errors that can occur during PDF export.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum PdfExportError {
+    /// I/O error occurred.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The SVG document is invalid.
+    #[error("Invalid SVG: {0}")]
+    InvalidSvg(String),
+
+    /// A required font was not found.
+    #[error("Font not found: {0}")]
+    FontNotFound(String),
+
+    /// Export failed for another reason.
+    #[error("Export failed: {0}")]
+    ExportFailed(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 400 200" width="400" height="200"><rect width="400" height="200" fill="red"/></svg>"#;
+
+    fn contains(pdf: &[u8], text: &str) -> bool {
+        pdf.windows(text.len())
+            .any(|window| window == text.as_bytes())
+    }
+
+    #[test]
+    fn exports_a_titled_page_the_size_of_the_diagram() {
+        let config = PdfExportConfig {
+            title: Some(PdfTitle::new(
+                NonEmptyString::parse("Ordering".to_string()).unwrap(),
+            )),
+            ..PdfExportConfig::default()
+        };
+
+        let pdf = PdfExporter::new(config).export_to_buffer(SVG).unwrap();
+
+        assert!(pdf.starts_with(b"%PDF-"));
+        assert!(contains(&pdf, "/Count 1"));
+        assert!(contains(&pdf, "/MediaBox [0 0 400 200]"));
+        assert!(contains(&pdf, "/Title (Ordering)"));
+    }
+
+    #[test]
+    fn fits_the_diagram_to_the_paper_asked_for() {
+        let config = PdfExportConfig {
+            page_size: Some(PageSize::A4),
+            ..PdfExportConfig::default()
+        };
+
+        let pdf = PdfExporter::new(config).export_to_buffer(SVG).unwrap();
+
+        // A4 turned to landscape, as the diagram is wider than tall
+        let (width, height) = PageSize::A4.dimensions();
+        let media_box = format!(
+            "/MediaBox [0 0 {} {}]",
+            height.points() as f32,
+            width.points() as f32
+        );
+        assert!(contains(&pdf, &media_box), "no {media_box}");
+    }
+
+    #[test]
+    fn refuses_color_spaces_other_than_rgb() {
+        let config = PdfExportConfig {
+            color_space: ColorSpace::Cmyk,
+            ..PdfExportConfig::default()
+        };
+
+        assert!(matches!(
+            PdfExporter::new(config).export_to_buffer(SVG),
+            Err(PdfExportError::ExportFailed(_))
+        ));
+    }
+}
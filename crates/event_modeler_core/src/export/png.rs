@@ -0,0 +1,143 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! PNG export for Event Model diagrams.
+//!
+//! [`PngExporter`] rasterizes a rendered SVG with resvg, drawing its text
+//! with the system fonts. Pixels the diagram leaves unpainted stay
+//! transparent, so a canvas declared transparent stays so in the image.
+
+use nutype::nutype;
+use resvg::{tiny_skia, usvg};
+use std::path::Path;
+
+/// How many pixels each SVG unit covers; 2 renders for high-density screens.
+#[nutype(
+    validate(finite, greater = 0.0, less_or_equal = 16.0),
+    derive(Debug, Clone, Copy, PartialEq, PartialOrd)
+)]
+pub struct PngScale(f32);
+
+/// Configuration for PNG export.
+#[derive(Debug, Clone)]
+pub struct PngExportConfig {
+    /// Pixels per SVG unit.
+    pub scale: PngScale,
+}
+
+impl Default for PngExportConfig {
+    fn default() -> Self {
+        Self {
+            scale: PngScale::try_new(1.0).expect("1 is a valid scale"),
+        }
+    }
+}
+
+/// Exporter for rasterizing SVG to PNG.
+pub struct PngExporter {
+    /// Export configuration.
+    config: PngExportConfig,
+}
+
+impl PngExporter {
+    /// Create a new PNG exporter.
+    pub fn new(config: PngExportConfig) -> Self {
+        Self { config }
+    }
+
+    /// Rasterize an SVG document to a PNG file.
+    pub fn export(&self, svg: &str, path: &Path) -> Result<(), PngExportError> {
+        std::fs::write(path, self.export_to_buffer(svg)?)?;
+        Ok(())
+    }
+
+    /// Rasterize an SVG document to PNG bytes.
+    pub fn export_to_buffer(&self, svg: &str) -> Result<Vec<u8>, PngExportError> {
+        let mut options = usvg::Options::default();
+        options.fontdb_mut().load_system_fonts();
+        let tree = usvg::Tree::from_str(svg, &options)
+            .map_err(|e| PngExportError::InvalidSvg(e.to_string()))?;
+
+        let scale = self.config.scale.into_inner();
+        let size = tree.size();
+        let width = (size.width() * scale).ceil() as u32;
+        let height = (size.height() * scale).ceil() as u32;
+        let mut pixmap = tiny_skia::Pixmap::new(width, height)
+            .ok_or(PngExportError::EmptyImage { width, height })?;
+        resvg::render(
+            &tree,
+            tiny_skia::Transform::from_scale(scale, scale),
+            &mut pixmap.as_mut(),
+        );
+        pixmap
+            .encode_png()
+            .map_err(|e| PngExportError::Encoding(e.to_string()))
+    }
+
+    /// Get the current configuration.
+    pub fn config(&self) -> &PngExportConfig {
+        &self.config
+    }
+}
+
+/// Errors that can occur during PNG export.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum PngExportError {
+    /// I/O error occurred.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The SVG document is invalid.
+    #[error("Invalid SVG: {0}")]
+    InvalidSvg(String),
+
+    /// The image would have no pixels, or too many to allocate.
+    #[error("Cannot rasterize a {width}x{height} image")]
+    EmptyImage {
+        /// Width in pixels.
+        width: u32,
+        /// Height in pixels.
+        height: u32,
+    },
+
+    /// The pixels could not be encoded as PNG.
+    #[error("PNG encoding failed: {0}")]
+    Encoding(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="40" height="30">
+  <rect x="0" y="0" width="20" height="30" fill="red"/>
+</svg>"#;
+
+    /// Reads the width and height from a PNG's header chunk.
+    fn dimensions(png: &[u8]) -> (u32, u32) {
+        let field = |at: usize| u32::from_be_bytes(png[at..at + 4].try_into().unwrap());
+        (field(16), field(20))
+    }
+
+    #[test]
+    fn rasterizes_at_the_configured_scale() {
+        let png = PngExporter::new(PngExportConfig::default())
+            .export_to_buffer(SVG)
+            .unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert_eq!(dimensions(&png), (40, 30));
+
+        let doubled = PngExporter::new(PngExportConfig {
+            scale: PngScale::try_new(2.0).unwrap(),
+        })
+        .export_to_buffer(SVG)
+        .unwrap();
+        assert_eq!(dimensions(&doubled), (80, 60));
+
+        assert!(matches!(
+            PngExporter::new(PngExportConfig::default()).export_to_buffer("<svg"),
+            Err(PngExportError::InvalidSvg(_))
+        ));
+    }
+}
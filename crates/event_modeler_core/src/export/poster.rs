@@ -0,0 +1,404 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Posters of Event Model diagrams for the team room.
+//!
+//! A poster scales the diagram to fill a large sheet such as A0. Few
+//! printers take A0, so the poster can instead be tiled across smaller
+//! sheets: each PDF page carries one tile with crop marks at the corners of
+//! its printed area, and neighbouring tiles share a strip of overlap to
+//! glue them by. A label in each page's margin says where its tile goes.
+
+use super::pdf::{PageSize, PdfExportError, load_svg};
+use crate::diagram::Length;
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+use svg2pdf::usvg;
+
+/// Margin left blank around the diagram on the poster.
+const POSTER_MARGIN_MM: f64 = 10.0;
+
+/// Margin of each tiled page, holding the crop marks and the tile's label.
+const SHEET_MARGIN_MM: f64 = 12.0;
+
+/// Length of a crop mark.
+const CROP_MARK_MM: f64 = 6.0;
+
+/// Gap between a crop mark and the corner it marks.
+const CROP_MARK_GAP_MM: f64 = 2.0;
+
+/// How a poster is printed.
+#[derive(Debug, Clone)]
+pub struct PosterOptions {
+    /// Paper size the diagram is scaled to fill.
+    pub paper: PageSize,
+    /// Paper size of the sheets the poster is tiled across, if it is tiled.
+    pub tile: Option<PageSize>,
+    /// How far neighbouring tiles overlap.
+    pub overlap: Length,
+    /// Title recorded in the document's metadata.
+    pub title: Option<String>,
+}
+
+/// One sheet's share of a tiled poster.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tile {
+    /// Zero-based row, counted from the top.
+    pub row: usize,
+    /// Zero-based column, counted from the left.
+    pub column: usize,
+    /// Left edge of the tile's area on the poster, in points.
+    pub x: f64,
+    /// Top edge of the tile's area on the poster, in points.
+    pub y: f64,
+}
+
+/// Where a diagram goes on a poster and on each printed sheet.
+///
+/// Lengths are in PostScript points, measured on the poster from its
+/// top-left corner.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PosterLayout {
+    /// Width of the poster.
+    pub poster_width: f64,
+    /// Height of the poster.
+    pub poster_height: f64,
+    /// Points per diagram pixel.
+    pub scale: f64,
+    /// Left edge of the diagram on the poster.
+    pub diagram_x: f64,
+    /// Top edge of the diagram on the poster.
+    pub diagram_y: f64,
+    /// Width of each printed page.
+    pub sheet_width: f64,
+    /// Height of each printed page.
+    pub sheet_height: f64,
+    /// Blank margin of each printed page; zero when the poster is not tiled.
+    pub sheet_margin: f64,
+    /// Number of rows of tiles.
+    pub rows: usize,
+    /// Number of columns of tiles.
+    pub columns: usize,
+    /// The tiles, row by row; a single tile when the poster is not tiled.
+    pub tiles: Vec<Tile>,
+}
+
+impl PosterLayout {
+    /// Plans a poster for a diagram of `width` by `height` pixels.
+    ///
+    /// The poster is turned to the diagram's orientation and the diagram
+    /// scaled to fill it within a margin. Sheets are turned whichever way
+    /// needs fewer of them.
+    pub fn plan(width: f64, height: f64, options: &PosterOptions) -> Self {
+        let (poster_width, poster_height) = oriented(&options.paper, width > height);
+        let margin = Length::from_millimeters(POSTER_MARGIN_MM).points();
+        let scale = ((poster_width - 2.0 * margin) / width.max(1.0))
+            .min((poster_height - 2.0 * margin) / height.max(1.0));
+        let diagram_x = (poster_width - width * scale) / 2.0;
+        let diagram_y = (poster_height - height * scale) / 2.0;
+
+        let Some(tile) = &options.tile else {
+            return Self {
+                poster_width,
+                poster_height,
+                scale,
+                diagram_x,
+                diagram_y,
+                sheet_width: poster_width,
+                sheet_height: poster_height,
+                sheet_margin: 0.0,
+                rows: 1,
+                columns: 1,
+                tiles: vec![Tile {
+                    row: 0,
+                    column: 0,
+                    x: 0.0,
+                    y: 0.0,
+                }],
+            };
+        };
+
+        let sheet_margin = Length::from_millimeters(SHEET_MARGIN_MM).points();
+        let grid = |landscape: bool| {
+            let (sheet_width, sheet_height) = oriented(tile, landscape);
+            let printed = (
+                sheet_width - 2.0 * sheet_margin,
+                sheet_height - 2.0 * sheet_margin,
+            );
+            // At most half a tile may overlap its neighbour
+            let overlap = options
+                .overlap
+                .points()
+                .min(printed.0 / 2.0)
+                .min(printed.1 / 2.0);
+            let count = |poster: f64, printed: f64| {
+                (((poster - overlap) / (printed - overlap)).ceil() as usize).max(1)
+            };
+            let columns = count(poster_width, printed.0);
+            let rows = count(poster_height, printed.1);
+            (
+                rows * columns,
+                sheet_width,
+                sheet_height,
+                printed,
+                overlap,
+                rows,
+                columns,
+            )
+        };
+        let (same, turned) = (grid(width > height), grid(width <= height));
+        let (_, sheet_width, sheet_height, printed, overlap, rows, columns) =
+            if turned.0 < same.0 { turned } else { same };
+
+        let tiles = (0..rows)
+            .flat_map(|row| {
+                (0..columns).map(move |column| Tile {
+                    row,
+                    column,
+                    x: column as f64 * (printed.0 - overlap),
+                    y: row as f64 * (printed.1 - overlap),
+                })
+            })
+            .collect();
+
+        Self {
+            poster_width,
+            poster_height,
+            scale,
+            diagram_x,
+            diagram_y,
+            sheet_width,
+            sheet_height,
+            sheet_margin,
+            rows,
+            columns,
+            tiles,
+        }
+    }
+
+    /// Lays a diagram of `width` by `height` pixels out on a single page of
+    /// its own size, a point per pixel, without a margin.
+    pub fn actual_size(width: f64, height: f64) -> Self {
+        Self {
+            poster_width: width,
+            poster_height: height,
+            scale: 1.0,
+            diagram_x: 0.0,
+            diagram_y: 0.0,
+            sheet_width: width,
+            sheet_height: height,
+            sheet_margin: 0.0,
+            rows: 1,
+            columns: 1,
+            tiles: vec![Tile {
+                row: 0,
+                column: 0,
+                x: 0.0,
+                y: 0.0,
+            }],
+        }
+    }
+
+    /// Returns whether the poster is split across several sheets.
+    pub fn is_tiled(&self) -> bool {
+        self.sheet_margin > 0.0
+    }
+}
+
+/// Renders an SVG diagram as a poster PDF with a page per tile.
+pub fn export_poster(svg: &str, options: &PosterOptions) -> Result<Vec<u8>, PdfExportError> {
+    let tree = load_svg(svg)?;
+
+    let (width, height) = (f64::from(tree.size.width()), f64::from(tree.size.height()));
+    let layout = PosterLayout::plan(width, height, options);
+    Ok(write_pages(
+        &tree,
+        &layout,
+        options.title.as_deref(),
+        svg2pdf::Options::default(),
+    ))
+}
+
+/// Writes a PDF with a page for each of the layout's tiles, each drawing
+/// its share of the diagram, with `title` as the document's title.
+pub(crate) fn write_pages(
+    tree: &usvg::Tree,
+    layout: &PosterLayout,
+    title: Option<&str>,
+    options: svg2pdf::Options,
+) -> Vec<u8> {
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let font_id = Ref::new(3);
+    let info_id = Ref::new(4);
+    let diagram_id = Ref::new(5);
+    let mut pdf = Pdf::new();
+    let mut next_id = svg2pdf::convert_tree_into(tree, options, &mut pdf, diagram_id);
+    let mut alloc = || {
+        let id = next_id;
+        next_id = next_id.next();
+        id
+    };
+
+    let page_ids: Vec<(Ref, Ref)> = layout.tiles.iter().map(|_| (alloc(), alloc())).collect();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id)
+        .kids(page_ids.iter().map(|(page, _)| *page))
+        .count(page_ids.len() as i32);
+    pdf.type1_font(font_id).base_font(Name(b"Helvetica"));
+    let mut info = pdf.document_info(info_id);
+    info.creator(TextStr("event_modeler"));
+    if let Some(title) = title {
+        info.title(TextStr(title));
+    }
+    info.finish();
+
+    for (tile, (page_id, content_id)) in layout.tiles.iter().zip(&page_ids) {
+        let mut page = pdf.page(*page_id);
+        page.parent(page_tree_id)
+            .media_box(Rect::new(
+                0.0,
+                0.0,
+                layout.sheet_width as f32,
+                layout.sheet_height as f32,
+            ))
+            .contents(*content_id);
+        let mut resources = page.resources();
+        resources.x_objects().pair(Name(b"D"), diagram_id);
+        resources.fonts().pair(Name(b"F"), font_id);
+        resources.finish();
+        page.finish();
+
+        pdf.stream(*content_id, &page_content(layout, tile));
+    }
+
+    pdf.finish()
+}
+
+/// Draws a tile's share of the diagram, plus crop marks and a label when
+/// the poster is tiled.
+fn page_content(layout: &PosterLayout, tile: &Tile) -> Vec<u8> {
+    let margin = layout.sheet_margin;
+    let printed_width = layout.sheet_width - 2.0 * margin;
+    let printed_height = layout.sheet_height - 2.0 * margin;
+    let diagram_width = layout.poster_width - 2.0 * layout.diagram_x;
+    let diagram_height = layout.poster_height - 2.0 * layout.diagram_y;
+
+    let mut content = Content::new();
+    content.save_state();
+    content.rect(
+        margin as f32,
+        margin as f32,
+        printed_width as f32,
+        printed_height as f32,
+    );
+    content.clip_nonzero();
+    content.end_path();
+    // PDF measures from the bottom-left corner, the poster from the top-left
+    content.transform([
+        diagram_width as f32,
+        0.0,
+        0.0,
+        diagram_height as f32,
+        (margin + layout.diagram_x - tile.x) as f32,
+        (margin + printed_height - (layout.diagram_y + diagram_height - tile.y)) as f32,
+    ]);
+    content.x_object(Name(b"D"));
+    content.restore_state();
+
+    if layout.is_tiled() {
+        let length = Length::from_millimeters(CROP_MARK_MM).points();
+        let gap = Length::from_millimeters(CROP_MARK_GAP_MM).points();
+        content.set_line_width(0.5);
+        for (x, outward_x) in [(margin, -1.0), (margin + printed_width, 1.0)] {
+            for (y, outward_y) in [(margin, -1.0), (margin + printed_height, 1.0)] {
+                content.move_to((x + outward_x * gap) as f32, y as f32);
+                content.line_to((x + outward_x * (gap + length)) as f32, y as f32);
+                content.move_to(x as f32, (y + outward_y * gap) as f32);
+                content.line_to(x as f32, (y + outward_y * (gap + length)) as f32);
+            }
+        }
+        content.stroke();
+
+        let label = format!(
+            "Row {} of {}, column {} of {}",
+            tile.row + 1,
+            layout.rows,
+            tile.column + 1,
+            layout.columns
+        );
+        content
+            .begin_text()
+            .set_font(Name(b"F"), 8.0)
+            .next_line(margin as f32, (margin / 3.0) as f32)
+            .show(Str(label.as_bytes()))
+            .end_text();
+    }
+
+    content.finish()
+}
+
+/// Returns a paper size's width and height, turned to landscape if asked.
+fn oriented(paper: &PageSize, landscape: bool) -> (f64, f64) {
+    let (width, height) = paper.dimensions();
+    let (short, long) = (width.points(), height.points());
+    if landscape {
+        (long, short)
+    } else {
+        (short, long)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(paper: PageSize, tile: Option<PageSize>) -> PosterOptions {
+        PosterOptions {
+            paper,
+            tile,
+            overlap: Length::from_millimeters(10.0),
+            title: Some("Ordering".to_string()),
+        }
+    }
+
+    #[test]
+    fn diagram_fills_the_poster_in_its_orientation() {
+        let layout = PosterLayout::plan(2000.0, 500.0, &options(PageSize::A0, None));
+
+        assert!(layout.poster_width > layout.poster_height);
+        assert!(!layout.is_tiled());
+        assert_eq!(layout.tiles.len(), 1);
+        let margin = Length::from_millimeters(POSTER_MARGIN_MM).points();
+        assert!((layout.diagram_x - margin).abs() < 1e-6);
+        assert!((2000.0 * layout.scale - (layout.poster_width - 2.0 * margin)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn tiles_overlap_and_cover_the_poster() {
+        let layout = PosterLayout::plan(1189.0, 841.0, &options(PageSize::A0, Some(PageSize::A3)));
+
+        assert!(layout.is_tiled());
+        assert_eq!(layout.tiles.len(), layout.rows * layout.columns);
+        let printed_width = layout.sheet_width - 2.0 * layout.sheet_margin;
+        let printed_height = layout.sheet_height - 2.0 * layout.sheet_margin;
+        let last = layout.tiles.last().unwrap();
+        assert!(last.x + printed_width >= layout.poster_width);
+        assert!(last.y + printed_height >= layout.poster_height);
+
+        let overlap = Length::from_millimeters(10.0).points();
+        assert!((layout.tiles[1].x - (printed_width - overlap)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn tiled_poster_has_a_page_per_tile() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 400 200"><rect width="400" height="200" fill="red"/></svg>"#;
+        let options = options(PageSize::A2, Some(PageSize::A4));
+        let layout = PosterLayout::plan(400.0, 200.0, &options);
+
+        let pdf = export_poster(svg, &options).unwrap();
+
+        assert!(pdf.starts_with(b"%PDF-"));
+        let count = format!("/Count {}", layout.tiles.len());
+        assert!(pdf.windows(count.len()).any(|w| w == count.as_bytes()));
+    }
+}
@@ -0,0 +1,681 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Static HTML documentation site generated from a model.
+//!
+//! [`build_site`] lays a model out as linked pages: an index holding the
+//! workflow's diagram, its slices, and its entities by type, and a page per
+//! entity with its description, documentation, schema, scenarios, and
+//! backlinks to every entity and scenario referring to it. The diagram is
+//! expected to be rendered with entity links following
+//! [`ENTITY_PAGE_TEMPLATE`], so clicking an entity opens its page.
+
+use crate::diagram::EventModelDiagram;
+use crate::event_model::yaml_types::{
+    ComponentType, Documentation, FieldDefinition, FieldName, FieldType, ImplementationLink,
+    OutputCase, OutputSpec, PlaceholderValue, TestOutcome, TestScenario,
+};
+use crate::infrastructure::markup::escape_xml;
+use std::collections::{BTreeMap, HashMap};
+
+/// Where each entity's page lives relative to the site root; `{name}`
+/// stands for the entity's name.
+pub const ENTITY_PAGE_TEMPLATE: &str = "entities/{name}.html";
+
+/// Styles shared by every page.
+const STYLE: &str = "body { font-family: Arial, sans-serif; max-width: 72rem; margin: 2rem auto; padding: 0 1rem; color: #333333; }
+nav { margin-bottom: 1rem; }
+a { color: #1565c0; }
+table { border-collapse: collapse; margin: 0.5rem 0 1rem; }
+th, td { border: 1px solid #cccccc; padding: 0.25rem 0.5rem; text-align: left; }
+.map { overflow-x: auto; border: 1px solid #cccccc; }
+.map svg { display: block; }
+.kind { color: #666666; }
+.byline { color: #666666; font-style: italic; }
+.issue { font-size: 0.7em; padding: 0.1rem 0.4rem; border-radius: 3px; background: #57606a; color: white; text-decoration: none; }
+.step { font-weight: bold; }";
+
+/// A page of the site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SitePage {
+    /// Path of the page relative to the site root, e.g. `entities/OrderPlaced.html`.
+    pub path: String,
+    /// The page's HTML.
+    pub html: String,
+}
+
+/// An entity and what its page describes.
+struct EntityPage<'a> {
+    name: String,
+    kind: &'static str,
+    swimlane: String,
+    description: Option<String>,
+    documentation: Option<&'a Documentation>,
+    implemented_by: &'a [ImplementationLink],
+    /// Schema tables, each with a caption and its rows of field and type.
+    schema: Vec<(String, Vec<(String, String)>)>,
+    scenarios: Vec<(String, &'a TestScenario)>,
+}
+
+/// Builds the index and one page per entity, the index first and the
+/// entity pages ordered by name. `svg` is embedded in the index as the
+/// workflow's map.
+pub fn build_site(diagram: &EventModelDiagram, svg: &str) -> Vec<SitePage> {
+    let entities = entity_pages(diagram);
+    let title = diagram.workflow_title().as_str().to_string();
+
+    let mut pages = vec![SitePage {
+        path: "index.html".to_string(),
+        html: index_page(diagram, &title, svg, &entities),
+    }];
+    for entity in &entities {
+        pages.push(SitePage {
+            path: ENTITY_PAGE_TEMPLATE.replace("{name}", &entity.name),
+            html: entity_page(diagram, &title, entity, &entities),
+        });
+    }
+    pages
+}
+
+/// Renders the index: the workflow's description and byline, the map, the
+/// slices, and the entities by type.
+fn index_page(
+    diagram: &EventModelDiagram,
+    title: &str,
+    svg: &str,
+    entities: &[EntityPage],
+) -> String {
+    // The XML declaration is not allowed inside an HTML document
+    let svg = svg
+        .strip_prefix(r#"<?xml version="1.0" encoding="UTF-8"?>"#)
+        .unwrap_or(svg)
+        .trim_start();
+    let mut body = format!("<h1>{}</h1>\n", escape_xml(title));
+    let header = diagram.header();
+    if let Some(description) = &header.description {
+        body.push_str(&format!(
+            "<p>{}</p>\n",
+            escape_xml(description.clone().into_inner().as_str())
+        ));
+    }
+    if let Some(byline) = header.byline() {
+        body.push_str(&format!(
+            "<p class=\"byline\">{}</p>\n",
+            escape_xml(&byline)
+        ));
+    }
+    body.push_str(&format!("<div class=\"map\">\n{svg}\n</div>\n"));
+
+    body.push_str("<h2>Slices</h2>\n");
+    for slice in diagram.slices() {
+        let issue = slice.issue.as_ref().map_or(String::new(), |issue| {
+            format!(
+                " <a class=\"issue\" href=\"{}\">{}</a>",
+                escape_xml(&issue.url()),
+                escape_xml(&issue.label())
+            )
+        });
+        body.push_str(&format!(
+            "<h3>{}{issue}</h3>\n<ul>\n",
+            escape_xml(slice.name.clone().into_inner().as_str())
+        ));
+        for connection in slice.connections.iter() {
+            body.push_str(&format!(
+                "<li>{} &rarr; {}</li>\n",
+                entity_link(&connection.from.entity_name(), "entities/"),
+                entity_link(&connection.to.entity_name(), "entities/")
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    body.push_str("<h2>Entities</h2>\n");
+    for kind in [
+        "view",
+        "command",
+        "event",
+        "projection",
+        "query",
+        "automation",
+    ] {
+        let of_kind: Vec<&EntityPage> = entities.iter().filter(|e| e.kind == kind).collect();
+        if of_kind.is_empty() {
+            continue;
+        }
+        body.push_str(&format!("<h3>{}</h3>\n<ul>\n", plural(kind)));
+        for entity in of_kind {
+            body.push_str(&format!("<li>{}", entity_link(&entity.name, "entities/")));
+            if let Some(description) = &entity.description {
+                body.push_str(&format!(": {}", escape_xml(description)));
+            }
+            body.push_str("</li>\n");
+        }
+        body.push_str("</ul>\n");
+    }
+
+    if !diagram.hotspots().is_empty() {
+        body.push_str("<h2>Hotspots</h2>\n<ul>\n");
+        for hotspot in diagram.hotspots().hotspots() {
+            let place = match &hotspot.slice {
+                Some(slice) => format!(
+                    "{} in slice {}",
+                    escape_xml(&hotspot.subject),
+                    escape_xml(slice)
+                ),
+                None => format!(
+                    "{} {}",
+                    hotspot.kind,
+                    entity_link(&hotspot.subject, "entities/")
+                ),
+            };
+            body.push_str(&format!(
+                "<li class=\"hotspot\">&#9888; {place}: {}</li>\n",
+                escape_xml(&hotspot.reason)
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    page(title, &body)
+}
+
+/// Renders the page of one entity.
+fn entity_page(
+    diagram: &EventModelDiagram,
+    title: &str,
+    entity: &EntityPage,
+    entities: &[EntityPage],
+) -> String {
+    // Entities of a custom kind are introduced by it, e.g. "Policy (automation)"
+    let kind = match diagram.kind_of(&entity.name) {
+        Some((custom, _)) => format!(
+            "{} ({})",
+            escape_xml(&capitalize(custom.clone().into_inner().as_str())),
+            entity.kind
+        ),
+        None => capitalize(entity.kind),
+    };
+    let mut body = format!(
+        "<nav><a href=\"../index.html\">{}</a></nav>\n<h1>{}</h1>\n<p class=\"kind\">{kind} in swimlane {}",
+        escape_xml(title),
+        escape_xml(&entity.name),
+        escape_xml(&entity.swimlane)
+    );
+    if let Some(owner) = diagram.ownership().owner_of(&entity.name) {
+        body.push_str(&format!(", owned by {}", escape_xml(owner)));
+    }
+    body.push_str("</p>\n");
+    if let Some(description) = &entity.description {
+        body.push_str(&format!("<p>{}</p>\n", escape_xml(description)));
+    }
+    if let Some(reason) = diagram.hotspots().by_entity().get(entity.name.as_str()) {
+        body.push_str(&format!(
+            "<p class=\"hotspot\">&#9888; {}</p>\n",
+            escape_xml(reason)
+        ));
+    }
+
+    match entity.documentation {
+        Some(Documentation::Markdown(text)) => {
+            body.push_str("<h2>Documentation</h2>\n");
+            for paragraph in text.clone().into_inner().as_str().split("\n\n") {
+                body.push_str(&format!("<p>{}</p>\n", escape_xml(paragraph.trim())));
+            }
+        }
+        Some(Documentation::File(path)) => {
+            let path = path.as_path_buf().display().to_string();
+            body.push_str(&format!(
+                "<h2>Documentation</h2>\n<p>See <code>{}</code>.</p>\n",
+                escape_xml(&path)
+            ));
+        }
+        None => {}
+    }
+
+    for (caption, rows) in &entity.schema {
+        body.push_str(&format!("<h2>{}</h2>\n", escape_xml(caption)));
+        if rows.is_empty() {
+            continue;
+        }
+        body.push_str("<table>\n<tr><th>Field</th><th>Type</th></tr>\n");
+        for (field, field_type) in rows {
+            body.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td></tr>\n",
+                escape_xml(field),
+                escape_xml(field_type)
+            ));
+        }
+        body.push_str("</table>\n");
+    }
+
+    if !entity.scenarios.is_empty() {
+        body.push_str("<h2>Scenarios</h2>\n");
+        for (name, scenario) in &entity.scenarios {
+            body.push_str(&format!("<h3>{}</h3>\n", escape_xml(name)));
+            body.push_str(&scenario_html(scenario));
+        }
+    }
+
+    let (incoming, outgoing) = connections_of(diagram, &entity.name);
+    if !incoming.is_empty() {
+        body.push_str("<h2>Reached from</h2>\n<ul>\n");
+        for (from, slice) in incoming {
+            body.push_str(&format!(
+                "<li>{} <span class=\"kind\">({})</span></li>\n",
+                entity_link(&from, ""),
+                escape_xml(&slice)
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+    if !outgoing.is_empty() {
+        body.push_str("<h2>Leads to</h2>\n<ul>\n");
+        for (to, slice) in outgoing {
+            body.push_str(&format!(
+                "<li>{} <span class=\"kind\">({})</span></li>\n",
+                entity_link(&to, ""),
+                escape_xml(&slice)
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    // Scenarios of other commands mentioning this entity
+    let mentions: Vec<String> = entities
+        .iter()
+        .flat_map(|other| {
+            other
+                .scenarios
+                .iter()
+                .filter(|(_, scenario)| {
+                    other.name != entity.name && scenario_mentions(scenario, &entity.name)
+                })
+                .map(|(scenario, _)| {
+                    format!(
+                        "<li>{}: {}</li>\n",
+                        entity_link(&other.name, ""),
+                        escape_xml(scenario)
+                    )
+                })
+        })
+        .collect();
+    if !mentions.is_empty() {
+        body.push_str("<h2>Used in scenarios</h2>\n<ul>\n");
+        body.push_str(&mentions.concat());
+        body.push_str("</ul>\n");
+    }
+
+    if !entity.implemented_by.is_empty() {
+        body.push_str("<h2>Implemented by</h2>\n<ul>\n");
+        for link in entity.implemented_by {
+            let text = escape_xml(&link.to_string());
+            body.push_str(&match link.href(&entity.name) {
+                Some(href) => format!("<li><a href=\"{}\">{text}</a></li>\n", escape_xml(&href)),
+                None => format!("<li><code>{text}</code></li>\n"),
+            });
+        }
+        body.push_str("</ul>\n");
+    }
+
+    page(&format!("{} - {title}", entity.name), &body)
+}
+
+/// Renders a scenario's steps as a Given/When/Then list.
+fn scenario_html(scenario: &TestScenario) -> String {
+    let given: Vec<String> = scenario
+        .given
+        .iter()
+        .map(|event| {
+            step_html(
+                entity_link(event.name.clone().into_inner().as_str(), ""),
+                &event.fields,
+            )
+        })
+        .collect();
+    let when: Vec<String> = scenario
+        .when
+        .iter()
+        .map(|action| {
+            step_html(
+                entity_link(action.name.clone().into_inner().as_str(), ""),
+                &action.fields,
+            )
+        })
+        .collect();
+    let then: Vec<String> = scenario
+        .then
+        .iter()
+        .map(|outcome| match outcome {
+            TestOutcome::Event(event) => step_html(
+                entity_link(event.name.clone().into_inner().as_str(), ""),
+                &event.fields,
+            ),
+            TestOutcome::Error(error) => format!(
+                "rejected with {}",
+                step_html(
+                    escape_xml(error.name.clone().into_inner().as_str()),
+                    &error.fields
+                )
+            ),
+        })
+        .collect();
+
+    let mut html = String::from("<ul>\n");
+    for (step, items) in [("Given", given), ("When", when), ("Then", then)] {
+        let items = if items.is_empty() {
+            "nothing".to_string()
+        } else {
+            items.join("; ")
+        };
+        html.push_str(&format!(
+            "<li><span class=\"step\">{step}</span> {items}</li>\n"
+        ));
+    }
+    html.push_str("</ul>\n");
+
+    let mut examples: Vec<(String, String)> = scenario
+        .examples
+        .iter()
+        .map(|(placeholder, example)| {
+            (
+                placeholder.clone().into_inner().into_inner(),
+                example.clone().into_inner().into_inner(),
+            )
+        })
+        .collect();
+    if !examples.is_empty() {
+        examples.sort();
+        html.push_str("<table>\n<tr><th>Placeholder</th><th>Example</th></tr>\n");
+        for (placeholder, example) in examples {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td></tr>\n",
+                escape_xml(&placeholder),
+                escape_xml(&example)
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+    html
+}
+
+/// Renders one step, e.g. `OrderPlaced(order_id: A)`, after its already
+/// rendered name.
+fn step_html(name: String, fields: &HashMap<FieldName, PlaceholderValue>) -> String {
+    let mut fields: Vec<String> = fields
+        .iter()
+        .map(|(field, value)| {
+            format!(
+                "{}: {}",
+                escape_xml(field.clone().into_inner().as_str()),
+                escape_xml(value.clone().into_inner().as_str())
+            )
+        })
+        .collect();
+    fields.sort();
+    format!("{name}({})", fields.join(", "))
+}
+
+/// Returns whether any step of a scenario names the entity.
+fn scenario_mentions(scenario: &TestScenario, name: &str) -> bool {
+    scenario
+        .given
+        .iter()
+        .any(|event| event.name.clone().into_inner().as_str() == name)
+        || scenario
+            .when
+            .iter()
+            .any(|action| action.name.clone().into_inner().as_str() == name)
+        || scenario.then.iter().any(|outcome| match outcome {
+            TestOutcome::Event(event) => event.name.clone().into_inner().as_str() == name,
+            TestOutcome::Error(_) => false,
+        })
+}
+
+/// Entities at the other end of an entity's connections, each with the
+/// slice of the connection.
+type Neighbors = Vec<(String, String)>;
+
+/// Returns the entities connecting to and from an entity, in slice order.
+fn connections_of(diagram: &EventModelDiagram, name: &str) -> (Neighbors, Neighbors) {
+    let mut incoming = Vec::new();
+    let mut outgoing = Vec::new();
+    for slice in diagram.slices() {
+        let slice_name = slice.name.clone().into_inner().into_inner();
+        for connection in slice.connections.iter() {
+            let (from, to) = (connection.from.entity_name(), connection.to.entity_name());
+            if to == name {
+                incoming.push((from.clone(), slice_name.clone()));
+            }
+            if from == name {
+                outgoing.push((to, slice_name.clone()));
+            }
+        }
+    }
+    (incoming, outgoing)
+}
+
+/// Collects what each entity's page shows, ordered by name.
+fn entity_pages(diagram: &EventModelDiagram) -> Vec<EntityPage<'_>> {
+    let swimlane_names: HashMap<String, String> = diagram
+        .swimlanes()
+        .iter()
+        .map(|swimlane| {
+            (
+                swimlane.id.clone().into_inner().into_inner(),
+                swimlane.name.clone().into_inner().into_inner(),
+            )
+        })
+        .collect();
+    let swimlane = |id: &crate::event_model::yaml_types::SwimlaneId| {
+        let id = id.clone().into_inner().into_inner();
+        swimlane_names.get(&id).cloned().unwrap_or(id)
+    };
+
+    let mut pages = Vec::new();
+    for (name, def) in diagram.views() {
+        let components: Vec<(String, String)> = def
+            .components
+            .iter()
+            .map(|component| {
+                let component_type = match &component.component_type {
+                    ComponentType::Simple(simple) => simple.clone().into_inner().into_inner(),
+                    ComponentType::Form { .. } => "Form".to_string(),
+                };
+                (
+                    component.name.clone().into_inner().into_inner(),
+                    component_type,
+                )
+            })
+            .collect();
+        pages.push(EntityPage {
+            name: name.clone().into_inner().into_inner(),
+            kind: "view",
+            swimlane: swimlane(&def.swimlane),
+            description: Some(def.description.clone().into_inner().into_inner()),
+            documentation: def.documentation.as_ref(),
+            implemented_by: &[],
+            schema: vec![("Components".to_string(), components)],
+            scenarios: Vec::new(),
+        });
+    }
+    for (name, def) in diagram.commands() {
+        let mut schema = vec![("Data".to_string(), field_rows(&def.data))];
+        let mut errors: Vec<_> = def.errors.iter().collect();
+        errors.sort_by_key(|(error, _)| (*error).clone().into_inner().into_inner());
+        for (error, error_def) in errors {
+            schema.push((
+                format!(
+                    "Error {}: {}",
+                    error.clone().into_inner().into_inner(),
+                    error_def.description.clone().into_inner().into_inner()
+                ),
+                field_rows(&error_def.data),
+            ));
+        }
+        let mut scenarios: Vec<(String, &TestScenario)> = def
+            .tests
+            .iter()
+            .map(|(scenario, test)| (scenario.clone().into_inner().into_inner(), test))
+            .collect();
+        scenarios.sort_by(|a, b| a.0.cmp(&b.0));
+        pages.push(EntityPage {
+            name: name.clone().into_inner().into_inner(),
+            kind: "command",
+            swimlane: swimlane(&def.swimlane),
+            description: Some(def.description.clone().into_inner().into_inner()),
+            documentation: def.documentation.as_ref(),
+            implemented_by: &def.implemented_by,
+            schema,
+            scenarios,
+        });
+    }
+    for (name, def) in diagram.events() {
+        pages.push(EntityPage {
+            name: name.clone().into_inner().into_inner(),
+            kind: "event",
+            swimlane: swimlane(&def.swimlane),
+            description: Some(def.description.clone().into_inner().into_inner()),
+            documentation: def.documentation.as_ref(),
+            implemented_by: &def.implemented_by,
+            schema: vec![("Data".to_string(), field_rows(&def.data))],
+            scenarios: Vec::new(),
+        });
+    }
+    for (name, def) in diagram.projections() {
+        pages.push(EntityPage {
+            name: name.clone().into_inner().into_inner(),
+            kind: "projection",
+            swimlane: swimlane(&def.swimlane),
+            description: Some(def.description.clone().into_inner().into_inner()),
+            documentation: def.documentation.as_ref(),
+            implemented_by: &[],
+            schema: vec![("Fields".to_string(), type_rows(&def.fields))],
+            scenarios: Vec::new(),
+        });
+    }
+    for (name, def) in diagram.queries() {
+        let mut schema = vec![("Inputs".to_string(), type_rows(&def.inputs))];
+        match &def.outputs {
+            OutputSpec::Single(outputs) => {
+                schema.push(("Outputs".to_string(), type_rows(outputs)));
+            }
+            OutputSpec::OneOf(cases) => {
+                let mut cases: Vec<_> = cases.iter().collect();
+                cases.sort_by_key(|(case, _)| (*case).clone().into_inner().into_inner());
+                for (case, output) in cases {
+                    let case = case.clone().into_inner().into_inner();
+                    match output {
+                        OutputCase::Fields(fields) => {
+                            schema.push((format!("Outputs: {case}"), type_rows(fields)));
+                        }
+                        OutputCase::Error(error) => schema.push((
+                            format!(
+                                "Outputs: {case} (error {})",
+                                error.clone().into_inner().into_inner()
+                            ),
+                            Vec::new(),
+                        )),
+                    }
+                }
+            }
+        }
+        pages.push(EntityPage {
+            name: name.clone().into_inner().into_inner(),
+            kind: "query",
+            swimlane: swimlane(&def.swimlane),
+            description: None,
+            documentation: def.documentation.as_ref(),
+            implemented_by: &[],
+            schema,
+            scenarios: Vec::new(),
+        });
+    }
+    for (name, def) in diagram.automations() {
+        pages.push(EntityPage {
+            name: name.clone().into_inner().into_inner(),
+            kind: "automation",
+            swimlane: swimlane(&def.swimlane),
+            description: None,
+            documentation: def.documentation.as_ref(),
+            implemented_by: &[],
+            schema: Vec::new(),
+            scenarios: Vec::new(),
+        });
+    }
+
+    // Tables without rows say nothing unless they stand for an error case
+    for page in &mut pages {
+        page.schema
+            .retain(|(caption, rows)| !rows.is_empty() || caption.starts_with("Outputs:"));
+    }
+    pages.sort_by(|a, b| a.name.cmp(&b.name));
+    pages
+}
+
+/// Returns the rows of a table of fields with definitions, ordered by name,
+/// noting stream ids, generated values, and personal data.
+fn field_rows(fields: &HashMap<FieldName, FieldDefinition>) -> Vec<(String, String)> {
+    let rows: BTreeMap<String, String> = fields
+        .iter()
+        .map(|(field, def)| {
+            let mut field_type = def.field_type.clone().into_inner().into_inner();
+            for (flag, name) in [
+                (def.stream_id, "stream id"),
+                (def.generated, "generated"),
+                (def.pii, "PII"),
+            ] {
+                if flag {
+                    field_type.push_str(&format!(" ({name})"));
+                }
+            }
+            (field.clone().into_inner().into_inner(), field_type)
+        })
+        .collect();
+    rows.into_iter().collect()
+}
+
+/// Returns the rows of a table of typed fields, ordered by name.
+fn type_rows(fields: &HashMap<FieldName, FieldType>) -> Vec<(String, String)> {
+    let rows: BTreeMap<String, String> = fields
+        .iter()
+        .map(|(field, field_type)| {
+            (
+                field.clone().into_inner().into_inner(),
+                field_type.clone().into_inner().into_inner(),
+            )
+        })
+        .collect();
+    rows.into_iter().collect()
+}
+
+/// Links to an entity's page; `prefix` is the path from the linking page to
+/// the entity pages.
+fn entity_link(name: &str, prefix: &str) -> String {
+    let name = escape_xml(name);
+    format!("<a href=\"{prefix}{name}.html\">{name}</a>")
+}
+
+/// Wraps a page body in a self-contained HTML document.
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{STYLE}\n</style>\n</head>\n<body>\n{body}</body>\n</html>\n",
+        escape_xml(title)
+    )
+}
+
+/// Returns the heading for a kind of entity, e.g. `Queries`.
+fn plural(kind: &str) -> String {
+    match kind {
+        "query" => "Queries".to_string(),
+        kind => format!("{}s", capitalize(kind)),
+    }
+}
+
+/// Upper-cases the first letter.
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,434 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Export of a model into an SQLite database for ad-hoc querying.
+//!
+//! Large models raise questions no report anticipates, such as which events
+//! have more than five consumers. [`write_sqlite`] lays a model out in
+//! relational tables so those questions can be answered with SQL:
+//!
+//! - `entities`: every entity with its `type`, custom `kind`, `swimlane`,
+//!   `owner`, and `description`
+//! - `fields`: the fields of each entity, grouped by `section` (`data`,
+//!   `fields`, `inputs`, `outputs`, `outputs.<case>`, `errors.<error>`, or
+//!   `components`)
+//! - `slices` and `connections`: the flows, in model order, with the bounded
+//!   `context`, `estimate`, `status`, and `issue` URL of each slice
+//! - `tests`, `test_steps`, and `test_values`: the Given/When/Then scenarios
+//!   of commands, with each step's placeholders and their examples
+
+use crate::event_model::yaml_types::{
+    ComponentType, Consistency, FieldName, OutputCase, OutputSpec, PlaceholderValue, TestOutcome,
+    YamlEventModel,
+};
+use rusqlite::{Connection, Transaction, params};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// The tables a model is written into.
+const SCHEMA: &str = "
+CREATE TABLE entities (
+    name TEXT PRIMARY KEY,
+    type TEXT NOT NULL,
+    kind TEXT,
+    swimlane TEXT NOT NULL,
+    owner TEXT,
+    description TEXT
+);
+CREATE TABLE fields (
+    entity TEXT NOT NULL,
+    section TEXT NOT NULL,
+    name TEXT NOT NULL,
+    type TEXT NOT NULL
+);
+CREATE TABLE slices (
+    name TEXT PRIMARY KEY,
+    position INTEGER NOT NULL,
+    context TEXT,
+    estimate TEXT,
+    status TEXT,
+    issue TEXT
+);
+CREATE TABLE connections (
+    slice TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    source TEXT NOT NULL,
+    target TEXT NOT NULL,
+    bidirectional INTEGER NOT NULL,
+    consistency TEXT
+);
+CREATE TABLE tests (
+    command TEXT NOT NULL,
+    scenario TEXT NOT NULL,
+    PRIMARY KEY (command, scenario)
+);
+CREATE TABLE test_steps (
+    command TEXT NOT NULL,
+    scenario TEXT NOT NULL,
+    step TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    kind TEXT NOT NULL,
+    name TEXT NOT NULL
+);
+CREATE TABLE test_values (
+    command TEXT NOT NULL,
+    scenario TEXT NOT NULL,
+    step TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    field TEXT NOT NULL,
+    placeholder TEXT NOT NULL,
+    example TEXT
+);
+";
+
+/// Errors that can occur during SQLite export.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum SqliteExportError {
+    /// The previous database could not be replaced.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The database could not be written.
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+}
+
+/// Writes the model into a new SQLite database at `path`, replacing any
+/// file already there.
+pub fn write_sqlite(model: &YamlEventModel, path: &Path) -> Result<(), SqliteExportError> {
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    let mut connection = Connection::open(path)?;
+    let transaction = connection.transaction()?;
+    transaction.execute_batch(SCHEMA)?;
+    insert_entities(&transaction, model)?;
+    insert_slices(&transaction, model)?;
+    insert_tests(&transaction, model)?;
+    transaction.commit()?;
+    Ok(())
+}
+
+/// Fills the `entities` and `fields` tables.
+fn insert_entities(transaction: &Transaction, model: &YamlEventModel) -> rusqlite::Result<()> {
+    let mut entity = transaction.prepare(
+        "INSERT INTO entities (name, type, kind, swimlane, owner, description) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+    let mut field = transaction
+        .prepare("INSERT INTO fields (entity, section, name, type) VALUES (?1, ?2, ?3, ?4)")?;
+
+    for (name, definition) in &model.events {
+        let name = name.clone().into_inner().into_inner();
+        entity.execute(params![
+            name,
+            "event",
+            definition
+                .kind
+                .clone()
+                .map(|kind| kind.into_inner().into_inner()),
+            definition.swimlane.clone().into_inner().into_inner(),
+            definition
+                .owner
+                .clone()
+                .map(|owner| owner.into_inner().into_inner()),
+            definition.description.clone().into_inner().into_inner(),
+        ])?;
+        for (field_name, field_definition) in &definition.data {
+            field.execute(params![
+                name,
+                "data",
+                field_name.clone().into_inner().into_inner(),
+                field_definition
+                    .field_type
+                    .clone()
+                    .into_inner()
+                    .into_inner(),
+            ])?;
+        }
+    }
+
+    for (name, definition) in &model.commands {
+        let name = name.clone().into_inner().into_inner();
+        entity.execute(params![
+            name,
+            "command",
+            definition
+                .kind
+                .clone()
+                .map(|kind| kind.into_inner().into_inner()),
+            definition.swimlane.clone().into_inner().into_inner(),
+            definition
+                .owner
+                .clone()
+                .map(|owner| owner.into_inner().into_inner()),
+            definition.description.clone().into_inner().into_inner(),
+        ])?;
+        for (field_name, field_definition) in &definition.data {
+            field.execute(params![
+                name,
+                "data",
+                field_name.clone().into_inner().into_inner(),
+                field_definition
+                    .field_type
+                    .clone()
+                    .into_inner()
+                    .into_inner(),
+            ])?;
+        }
+        for (error, error_definition) in &definition.errors {
+            let section = format!("errors.{}", error.clone().into_inner().into_inner());
+            for (field_name, field_definition) in &error_definition.data {
+                field.execute(params![
+                    name,
+                    section,
+                    field_name.clone().into_inner().into_inner(),
+                    field_definition
+                        .field_type
+                        .clone()
+                        .into_inner()
+                        .into_inner(),
+                ])?;
+            }
+        }
+    }
+
+    for (name, definition) in &model.views {
+        let name = name.clone().into_inner().into_inner();
+        entity.execute(params![
+            name,
+            "view",
+            definition
+                .kind
+                .clone()
+                .map(|kind| kind.into_inner().into_inner()),
+            definition.swimlane.clone().into_inner().into_inner(),
+            definition
+                .owner
+                .clone()
+                .map(|owner| owner.into_inner().into_inner()),
+            definition.description.clone().into_inner().into_inner(),
+        ])?;
+        for component in definition.components.iter() {
+            let component_type = match &component.component_type {
+                ComponentType::Simple(simple) => simple.clone().into_inner().into_inner(),
+                ComponentType::Form { .. } => "Form".to_string(),
+            };
+            field.execute(params![
+                name,
+                "components",
+                component.name.clone().into_inner().into_inner(),
+                component_type,
+            ])?;
+        }
+    }
+
+    for (name, definition) in &model.projections {
+        let name = name.clone().into_inner().into_inner();
+        entity.execute(params![
+            name,
+            "projection",
+            definition
+                .kind
+                .clone()
+                .map(|kind| kind.into_inner().into_inner()),
+            definition.swimlane.clone().into_inner().into_inner(),
+            definition
+                .owner
+                .clone()
+                .map(|owner| owner.into_inner().into_inner()),
+            definition.description.clone().into_inner().into_inner(),
+        ])?;
+        for (field_name, field_type) in &definition.fields {
+            field.execute(params![
+                name,
+                "fields",
+                field_name.clone().into_inner().into_inner(),
+                field_type.clone().into_inner().into_inner(),
+            ])?;
+        }
+    }
+
+    for (name, definition) in &model.queries {
+        let name = name.clone().into_inner().into_inner();
+        entity.execute(params![
+            name,
+            "query",
+            definition
+                .kind
+                .clone()
+                .map(|kind| kind.into_inner().into_inner()),
+            definition.swimlane.clone().into_inner().into_inner(),
+            definition
+                .owner
+                .clone()
+                .map(|owner| owner.into_inner().into_inner()),
+            Option::<String>::None,
+        ])?;
+        let mut sections = vec![("inputs".to_string(), &definition.inputs)];
+        match &definition.outputs {
+            OutputSpec::Single(outputs) => sections.push(("outputs".to_string(), outputs)),
+            OutputSpec::OneOf(cases) => {
+                for (case, output) in cases {
+                    // Error cases name an error type rather than fields
+                    if let OutputCase::Fields(outputs) = output {
+                        let case = case.clone().into_inner().into_inner();
+                        sections.push((format!("outputs.{case}"), outputs));
+                    }
+                }
+            }
+        }
+        for (section, fields) in sections {
+            for (field_name, field_type) in fields {
+                field.execute(params![
+                    name,
+                    section,
+                    field_name.clone().into_inner().into_inner(),
+                    field_type.clone().into_inner().into_inner(),
+                ])?;
+            }
+        }
+    }
+
+    for (name, definition) in &model.automations {
+        entity.execute(params![
+            name.clone().into_inner().into_inner(),
+            "automation",
+            definition
+                .kind
+                .clone()
+                .map(|kind| kind.into_inner().into_inner()),
+            definition.swimlane.clone().into_inner().into_inner(),
+            definition
+                .owner
+                .clone()
+                .map(|owner| owner.into_inner().into_inner()),
+            Option::<String>::None,
+        ])?;
+    }
+
+    Ok(())
+}
+
+/// Fills the `slices` and `connections` tables.
+fn insert_slices(transaction: &Transaction, model: &YamlEventModel) -> rusqlite::Result<()> {
+    let mut slice_row = transaction.prepare(
+        "INSERT INTO slices (name, position, context, estimate, status, issue) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+    let mut connection_row = transaction.prepare(
+        "INSERT INTO connections (slice, position, source, target, bidirectional, consistency) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+    for (position, slice) in model.slices.iter().enumerate() {
+        let name = slice.name.clone().into_inner().into_inner();
+        let context = slice
+            .context
+            .as_ref()
+            .map(|context| context.clone().into_inner().into_inner());
+        let estimate = slice.estimate.map(|estimate| estimate.to_string());
+        let status = slice.status.map(|status| status.as_str());
+        let issue = slice.issue.as_ref().map(|issue| issue.url());
+        slice_row.execute(params![name, position, context, estimate, status, issue])?;
+        for (position, connection) in slice.connections.iter().enumerate() {
+            let consistency = connection.consistency.map(|consistency| match consistency {
+                Consistency::Eventual => "eventual",
+                Consistency::Immediate => "immediate",
+            });
+            connection_row.execute(params![
+                name,
+                position,
+                connection.from.entity_name(),
+                connection.to.entity_name(),
+                connection.bidirectional,
+                consistency,
+            ])?;
+        }
+    }
+    Ok(())
+}
+
+/// Fills the `tests`, `test_steps`, and `test_values` tables.
+fn insert_tests(transaction: &Transaction, model: &YamlEventModel) -> rusqlite::Result<()> {
+    let mut test_row =
+        transaction.prepare("INSERT INTO tests (command, scenario) VALUES (?1, ?2)")?;
+    let mut step_row = transaction.prepare(
+        "INSERT INTO test_steps (command, scenario, step, position, kind, name) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+    let mut value_row = transaction.prepare(
+        "INSERT INTO test_values (command, scenario, step, position, field, placeholder, example) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    )?;
+
+    for (command, definition) in &model.commands {
+        let command = command.clone().into_inner().into_inner();
+        for (scenario_name, scenario) in &definition.tests {
+            let scenario_name = scenario_name.clone().into_inner().into_inner();
+            test_row.execute(params![command, scenario_name])?;
+
+            let given = scenario.given.iter().map(|event| {
+                (
+                    "given",
+                    "event",
+                    event.name.clone().into_inner(),
+                    &event.fields,
+                )
+            });
+            let when = scenario.when.iter().map(|action| {
+                (
+                    "when",
+                    "command",
+                    action.name.clone().into_inner(),
+                    &action.fields,
+                )
+            });
+            let then = scenario.then.iter().map(|outcome| match outcome {
+                TestOutcome::Event(event) => (
+                    "then",
+                    "event",
+                    event.name.clone().into_inner(),
+                    &event.fields,
+                ),
+                TestOutcome::Error(error) => (
+                    "then",
+                    "error",
+                    error.name.clone().into_inner(),
+                    &error.fields,
+                ),
+            });
+            let steps: Vec<(&str, &str, _, &HashMap<FieldName, PlaceholderValue>)> =
+                given.chain(when).chain(then).collect();
+
+            let mut positions: HashMap<&str, usize> = HashMap::new();
+            for (step, kind, name, fields) in steps {
+                let position = positions.entry(step).or_default();
+                step_row.execute(params![
+                    command,
+                    scenario_name,
+                    step,
+                    *position,
+                    kind,
+                    name.into_inner(),
+                ])?;
+                for (field, placeholder) in fields {
+                    let example = scenario
+                        .examples
+                        .get(placeholder)
+                        .map(|example| example.clone().into_inner().into_inner());
+                    value_row.execute(params![
+                        command,
+                        scenario_name,
+                        step,
+                        *position,
+                        field.clone().into_inner().into_inner(),
+                        placeholder.clone().into_inner().into_inner(),
+                        example,
+                    ])?;
+                }
+                *position += 1;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,336 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Storyboard of a model's screens, for walking stakeholders through it.
+//!
+//! [`build_storyboard`] follows the slices in order and tells each one as a
+//! sequence: a wireframe of every view the slice shows, sketched from the
+//! view's components, with the commands issued and events recorded between
+//! one screen and the next. Components a slice's connections start from are
+//! highlighted on their screen, so a reader sees which button leads where.
+//! The result is a single HTML page with the wireframes inlined as SVG.
+
+use crate::diagram::EventModelDiagram;
+use crate::event_model::yaml_types::{ComponentType, EntityReference, ViewDefinition};
+use crate::infrastructure::markup::escape_xml;
+use std::collections::{HashMap, HashSet};
+
+/// Styles of the storyboard page.
+const STYLE: &str = "body { font-family: Arial, sans-serif; max-width: 48rem; margin: 2rem auto; padding: 0 1rem; color: #333333; }
+ol.story { list-style: none; padding: 0; }
+figure { margin: 1rem 0; }
+figcaption { color: #666666; margin-top: 0.25rem; }
+.step { margin: 0.5rem 0 0.5rem 1.5rem; }
+.kind { font-size: 0.75em; padding: 0.1rem 0.4rem; border-radius: 3px; color: white; }
+.command .kind { background: #4a90e2; }
+.event .kind { background: #9b59b6; }";
+
+/// Width of a wireframe, in pixels.
+const FRAME_WIDTH: u32 = 320;
+/// Height of a wireframe's title bar.
+const TITLE_HEIGHT: u32 = 28;
+/// Height of each row of components.
+const ROW_HEIGHT: u32 = 34;
+/// Space between the frame and its components.
+const PADDING: u32 = 12;
+/// Color outlining the components a slice moves on from.
+const HIGHLIGHT_COLOR: &str = "#e67e22";
+
+/// One moment of a slice's story.
+enum Moment<'a> {
+    /// A screen the user sees, with the components the slice moves on from.
+    Screen {
+        name: String,
+        view: &'a ViewDefinition,
+        highlighted: Vec<String>,
+    },
+    /// A command issued or an event recorded.
+    Step {
+        kind: &'static str,
+        name: String,
+        description: String,
+    },
+}
+
+/// Builds the storyboard page for `diagram`.
+///
+/// Slices with neither views, commands, nor events are left out.
+pub fn build_storyboard(diagram: &EventModelDiagram) -> String {
+    let title = diagram.workflow_title().as_str().to_string();
+    let mut body = format!("<h1>{} storyboard</h1>\n", escape_xml(&title));
+
+    for slice in diagram.slices() {
+        let moments = slice_moments(diagram, slice);
+        if moments.is_empty() {
+            continue;
+        }
+        body.push_str(&format!(
+            "<h2>{}</h2>\n<ol class=\"story\">\n",
+            escape_xml(slice.name.clone().into_inner().as_str())
+        ));
+        for moment in &moments {
+            body.push_str(&render_moment(moment));
+        }
+        body.push_str("</ol>\n");
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{} storyboard</title>\n<style>\n{STYLE}\n</style>\n</head>\n<body>\n{body}</body>\n</html>\n",
+        escape_xml(&title)
+    )
+}
+
+/// Returns the screens and steps of `slice`, in the order its connections
+/// first mention them, moved into the order the slice lists, as drawn.
+fn slice_moments<'a>(
+    diagram: &'a EventModelDiagram,
+    slice: &crate::event_model::yaml_types::Slice,
+) -> Vec<Moment<'a>> {
+    let views: HashMap<String, &ViewDefinition> = diagram
+        .views()
+        .iter()
+        .map(|(name, view)| (name.clone().into_inner().into_inner(), view))
+        .collect();
+    let commands: HashMap<String, String> = diagram
+        .commands()
+        .iter()
+        .map(|(name, command)| {
+            (
+                name.clone().into_inner().into_inner(),
+                command.description.clone().into_inner().into_inner(),
+            )
+        })
+        .collect();
+    let events: HashMap<String, String> = diagram
+        .events()
+        .iter()
+        .map(|(name, event)| {
+            (
+                name.clone().into_inner().into_inner(),
+                event.description.clone().into_inner().into_inner(),
+            )
+        })
+        .collect();
+
+    let mut names = Vec::new();
+    let mut highlighted: HashMap<String, Vec<String>> = HashMap::new();
+    for connection in slice.connections.iter() {
+        if let EntityReference::View(path) = &connection.from {
+            // A form's action, e.g. `Screen.Form.Submit`, highlights the form's actions
+            let path = path.clone().into_inner().into_inner();
+            let mut segments = path.split('.');
+            if let (Some(view), Some(component)) = (segments.next(), segments.next()) {
+                highlighted
+                    .entry(view.to_string())
+                    .or_default()
+                    .push(component.to_string());
+            }
+        }
+        names.push(connection.from.entity_name());
+        names.push(connection.to.entity_name());
+    }
+    let mut seen = HashSet::new();
+    names.retain(|name| seen.insert(name.clone()));
+    order_listed(&mut names, &slice.order);
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            if let Some(view) = views.get(&name) {
+                return Some(Moment::Screen {
+                    highlighted: highlighted.remove(&name).unwrap_or_default(),
+                    name,
+                    view,
+                });
+            }
+            let (kind, description) = match (commands.get(&name), events.get(&name)) {
+                (Some(description), _) => ("command", description.clone()),
+                (None, Some(description)) => ("event", description.clone()),
+                (None, None) => return None,
+            };
+            Some(Moment::Step {
+                kind,
+                name,
+                description,
+            })
+        })
+        .collect()
+}
+
+/// Puts the names `order` lists in that order, in the places they already
+/// take, leaving the others where they are, as the diagram places them.
+fn order_listed(names: &mut [String], order: &[EntityReference]) {
+    let rank = |name: &String| {
+        order
+            .iter()
+            .position(|listed| listed.entity_name() == *name)
+    };
+    let slots: Vec<usize> = (0..names.len())
+        .filter(|&slot| rank(&names[slot]).is_some())
+        .collect();
+    let mut listed: Vec<String> = slots.iter().map(|&slot| names[slot].clone()).collect();
+    listed.sort_by_key(rank);
+    for (slot, name) in slots.into_iter().zip(listed) {
+        names[slot] = name;
+    }
+}
+
+/// Renders a moment as an item of the story.
+fn render_moment(moment: &Moment) -> String {
+    match moment {
+        Moment::Screen {
+            name,
+            view,
+            highlighted,
+        } => format!(
+            "<li><figure>\n{}<figcaption><strong>{}</strong>: {}</figcaption>\n</figure></li>\n",
+            wireframe(name, view, highlighted),
+            escape_xml(name),
+            escape_xml(view.description.clone().into_inner().as_str())
+        ),
+        Moment::Step {
+            kind,
+            name,
+            description,
+        } => format!(
+            "<li class=\"step {kind}\"><span class=\"kind\">{kind}</span> <strong>{}</strong>: {}</li>\n",
+            escape_xml(name),
+            escape_xml(description)
+        ),
+    }
+}
+
+/// A row of a wireframe: a component, or a field or the actions of a form.
+enum Row {
+    /// A labelled input box.
+    Input { label: String },
+    /// Buttons side by side.
+    Buttons(Vec<String>),
+    /// An underlined link.
+    Link(String),
+    /// A box standing in for any other kind of component.
+    Placeholder { name: String, kind: String },
+    /// The name of a form, above its fields.
+    Heading(String),
+}
+
+/// Sketches `view` as a screen, its components one row each from the top,
+/// outlining the `highlighted` ones.
+fn wireframe(name: &str, view: &ViewDefinition, highlighted: &[String]) -> String {
+    let mut rows: Vec<(Row, bool)> = Vec::new();
+    for component in view.components.iter() {
+        let component_name = component.name.clone().into_inner().into_inner();
+        let is_highlighted = highlighted.contains(&component_name);
+        match &component.component_type {
+            ComponentType::Simple(kind) => {
+                let kind = kind.clone().into_inner().into_inner();
+                rows.push((simple_row(component_name, kind), is_highlighted));
+            }
+            ComponentType::Form { fields, actions } => {
+                rows.push((Row::Heading(component_name), false));
+                let mut fields: Vec<String> = fields
+                    .keys()
+                    .map(|field| field.clone().into_inner().into_inner())
+                    .collect();
+                fields.sort();
+                rows.extend(
+                    fields
+                        .into_iter()
+                        .map(|label| (Row::Input { label }, false)),
+                );
+                let actions = actions
+                    .iter()
+                    .map(|action| action.clone().into_inner().into_inner())
+                    .collect();
+                rows.push((Row::Buttons(actions), is_highlighted));
+            }
+        }
+    }
+
+    let height = TITLE_HEIGHT + PADDING * 2 + ROW_HEIGHT * rows.len() as u32;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{FRAME_WIDTH}\" height=\"{height}\" viewBox=\"0 0 {FRAME_WIDTH} {height}\" font-family=\"Arial, sans-serif\" font-size=\"13\">\n"
+    );
+    svg.push_str(&format!(
+        "<rect x=\"0.5\" y=\"0.5\" width=\"{}\" height=\"{}\" rx=\"6\" fill=\"#ffffff\" stroke=\"#555555\"/>\n",
+        FRAME_WIDTH - 1,
+        height - 1
+    ));
+    svg.push_str(&format!(
+        "<path d=\"M0.5 {TITLE_HEIGHT} H{}\" stroke=\"#555555\"/>\n<text x=\"{PADDING}\" y=\"19\" font-weight=\"bold\">{}</text>\n",
+        FRAME_WIDTH - 1,
+        escape_xml(name)
+    ));
+
+    let inner_width = FRAME_WIDTH - PADDING * 2;
+    for (index, (row, is_highlighted)) in rows.iter().enumerate() {
+        let top = TITLE_HEIGHT + PADDING + ROW_HEIGHT * index as u32;
+        let middle = top + ROW_HEIGHT / 2 + 4;
+        if *is_highlighted {
+            svg.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"4\" fill=\"none\" stroke=\"{HIGHLIGHT_COLOR}\" stroke-width=\"2\"/>\n",
+                PADDING - 4,
+                top + 1,
+                inner_width + 8,
+                ROW_HEIGHT - 2
+            ));
+        }
+        match row {
+            Row::Input { label } => svg.push_str(&format!(
+                "<text x=\"{PADDING}\" y=\"{middle}\">{}</text>\n<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"22\" rx=\"2\" fill=\"#ffffff\" stroke=\"#999999\"/>\n",
+                escape_xml(label),
+                PADDING + inner_width / 3,
+                top + 6,
+                inner_width - inner_width / 3
+            )),
+            Row::Buttons(labels) => {
+                let mut left = PADDING;
+                for label in labels {
+                    let width = 24 + 7 * label.chars().count() as u32;
+                    svg.push_str(&format!(
+                        "<rect x=\"{left}\" y=\"{}\" width=\"{width}\" height=\"24\" rx=\"4\" fill=\"#e0e0e0\" stroke=\"#777777\"/>\n<text x=\"{}\" y=\"{middle}\" text-anchor=\"middle\">{}</text>\n",
+                        top + 5,
+                        left + width / 2,
+                        escape_xml(label)
+                    ));
+                    left += width + 8;
+                }
+            }
+            Row::Link(label) => svg.push_str(&format!(
+                "<text x=\"{PADDING}\" y=\"{middle}\" fill=\"#1565c0\" text-decoration=\"underline\">{}</text>\n",
+                escape_xml(label)
+            )),
+            Row::Placeholder { name, kind } => svg.push_str(&format!(
+                "<rect x=\"{PADDING}\" y=\"{}\" width=\"{inner_width}\" height=\"26\" fill=\"#f2f2f2\" stroke=\"#bbbbbb\" stroke-dasharray=\"4 3\"/>\n<text x=\"{}\" y=\"{middle}\">{} <tspan fill=\"#888888\">({})</tspan></text>\n",
+                top + 4,
+                PADDING + 8,
+                escape_xml(name),
+                escape_xml(kind)
+            )),
+            Row::Heading(label) => svg.push_str(&format!(
+                "<text x=\"{PADDING}\" y=\"{middle}\" font-weight=\"bold\" fill=\"#555555\">{}</text>\n",
+                escape_xml(label)
+            )),
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Picks how to sketch a component from its type, e.g. a `SubmitButton` as
+/// a button and an `EmailInput` as an input box.
+fn simple_row(name: String, kind: String) -> Row {
+    let lower = kind.to_lowercase();
+    if lower.contains("button") {
+        Row::Buttons(vec![name])
+    } else if lower.contains("link") {
+        Row::Link(name)
+    } else if ["input", "field", "text"]
+        .iter()
+        .any(|input| lower.contains(input))
+    {
+        Row::Input { label: name }
+    } else {
+        Row::Placeholder { name, kind }
+    }
+}
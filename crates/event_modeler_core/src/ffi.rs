@@ -0,0 +1,236 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! A C interface for embedding the renderer in other languages.
+//!
+//! Python, Node, and other languages with a C foreign function interface can
+//! load the library built with the `ffi` feature instead of running the CLI
+//! for every diagram:
+//!
+//! ```bash
+//! cargo rustc --release --lib --features ffi --crate-type cdylib
+//! ```
+//!
+//! The functions are declared in `include/event_modeler.h`. Strings passed in
+//! are NUL-terminated UTF-8 and stay owned by the caller; strings returned
+//! are owned by the caller once returned and must be released with
+//! [`em_string_free`]. No function keeps state between calls, so each may be
+//! called from many threads at once. A panic inside the library is reported
+//! as an error rather than unwinding into the caller.
+
+use crate::infrastructure::warnings::Warnings;
+use serde::Serialize;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+
+/// Status returned by [`em_validate_yaml`] for a valid model.
+pub const EM_VALID: i32 = 0;
+
+/// Status returned by [`em_validate_yaml`] for an invalid model.
+pub const EM_INVALID: i32 = 1;
+
+/// Status returned by [`em_validate_yaml`] when the source could not be read:
+/// it is NULL or not UTF-8.
+pub const EM_BAD_INPUT: i32 = -1;
+
+/// What validating a model found, as [`em_validate_yaml`] reports it.
+#[derive(Debug, Serialize)]
+struct Findings {
+    /// Why the model is invalid, or `None` if it is valid.
+    error: Option<String>,
+    /// The warnings raised, each with the stage that raised it.
+    warnings: Vec<String>,
+}
+
+/// Renders a model in the YAML format to an SVG document.
+///
+/// Returns the document, or NULL if the model could not be rendered. On
+/// failure the reason is stored in `*error` unless `error` is NULL. Both
+/// strings are to be released with [`em_string_free`].
+///
+/// # Safety
+///
+/// `source` must be NULL or point to a NUL-terminated string, and `error`
+/// must be NULL or point to writable storage for a pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn em_render_yaml_to_svg(
+    source: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    // SAFETY: the caller promises `source` is NULL or NUL-terminated
+    let rendered = unsafe { read_source(source) }
+        .and_then(|source| guard(|| crate::render_svg_str(source).map_err(|e| e.to_string())));
+    match rendered {
+        Ok(svg) => into_c_string(svg),
+        Err(message) => {
+            // SAFETY: the caller promises `error` is NULL or writable
+            unsafe { store(error, message) };
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Checks a model in the YAML format without rendering it.
+///
+/// Returns [`EM_VALID`], [`EM_INVALID`], or [`EM_BAD_INPUT`]. Unless `report`
+/// is NULL, `*report` receives the findings as JSON, an object with the
+/// `error` making the model invalid (or `null`) and the `warnings` raised,
+/// to be released with [`em_string_free`].
+///
+/// # Safety
+///
+/// `source` must be NULL or point to a NUL-terminated string, and `report`
+/// must be NULL or point to writable storage for a pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn em_validate_yaml(source: *const c_char, report: *mut *mut c_char) -> i32 {
+    // SAFETY: the caller promises `source` is NULL or NUL-terminated
+    let source = match unsafe { read_source(source) } {
+        Ok(source) => source,
+        Err(message) => {
+            let findings = Findings {
+                error: Some(message),
+                warnings: Vec::new(),
+            };
+            // SAFETY: the caller promises `report` is NULL or writable
+            unsafe { store(report, to_json(&findings)) };
+            return EM_BAD_INPUT;
+        }
+    };
+    let mut warnings = Warnings::new();
+    let error = guard(|| crate::validation::validate_source(source, &[], &mut warnings)).err();
+    let status = if error.is_some() {
+        EM_INVALID
+    } else {
+        EM_VALID
+    };
+    let findings = Findings {
+        error,
+        warnings: warnings.iter().map(ToString::to_string).collect(),
+    };
+    // SAFETY: the caller promises `report` is NULL or writable
+    unsafe { store(report, to_json(&findings)) };
+    status
+}
+
+/// Releases a string returned by this library. Does nothing given NULL.
+///
+/// # Safety
+///
+/// `string` must be NULL or a string returned by this library that has not
+/// been released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn em_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        // SAFETY: the caller promises `string` came from `CString::into_raw`
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// Reads a string passed in by the caller.
+///
+/// # Safety
+///
+/// `source` must be NULL or point to a NUL-terminated string that outlives
+/// the returned slice.
+unsafe fn read_source<'a>(source: *const c_char) -> Result<&'a str, String> {
+    if source.is_null() {
+        return Err("source is NULL".to_string());
+    }
+    // SAFETY: non-NULL, and NUL-terminated as the caller promises
+    unsafe { CStr::from_ptr(source) }
+        .to_str()
+        .map_err(|e| format!("source is not UTF-8: {e}"))
+}
+
+/// Runs `f`, turning a panic into an error so it never unwinds into C.
+fn guard<T>(f: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|_| Err("event_modeler panicked; please report a bug".to_string()))
+}
+
+/// Hands a string over to the caller.
+fn into_c_string(string: String) -> *mut c_char {
+    // Models read from C strings hold no NUL, but messages quoting them might
+    CString::new(string.replace('\0', ""))
+        .unwrap_or_default()
+        .into_raw()
+}
+
+/// Stores a string for the caller in `*out`, unless `out` is NULL.
+///
+/// # Safety
+///
+/// `out` must be NULL or point to writable storage for a pointer.
+unsafe fn store(out: *mut *mut c_char, string: String) {
+    if !out.is_null() {
+        // SAFETY: non-NULL, and writable as the caller promises
+        unsafe { *out = into_c_string(string) };
+    }
+}
+
+/// Formats findings as JSON.
+fn to_json(findings: &Findings) -> String {
+    serde_json::to_string(findings).unwrap_or_else(|e| format!("{{\"error\":\"{e}\"}}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODEL: &str = "workflow: Ordering\nswimlanes:\n  - backend: \"Backend\"\ncommands:\n  PlaceOrder:\n    description: \"Place an order\"\n    swimlane: backend\nevents:\n  OrderPlaced:\n    description: \"An order was placed\"\n    swimlane: backend\nslices:\n  - name: Placing\n    connections:\n      - PlaceOrder -> OrderPlaced\n";
+
+    /// Takes back a string the library handed over.
+    fn take(string: *mut c_char) -> String {
+        assert!(!string.is_null());
+        let text = unsafe { CStr::from_ptr(string) }
+            .to_string_lossy()
+            .into_owned();
+        unsafe { em_string_free(string) };
+        text
+    }
+
+    #[test]
+    fn renders_and_validates_through_the_c_interface() {
+        let model = CString::new(MODEL).unwrap();
+        let broken = CString::new("workflow: [").unwrap();
+        let mut out: *mut c_char = ptr::null_mut();
+
+        let svg = unsafe { em_render_yaml_to_svg(model.as_ptr(), &mut out) };
+        assert!(take(svg).contains("<svg"));
+        assert!(out.is_null());
+
+        let svg = unsafe { em_render_yaml_to_svg(broken.as_ptr(), &mut out) };
+        assert!(svg.is_null());
+        assert!(take(out).contains("YAML error"));
+
+        assert_eq!(
+            unsafe { em_validate_yaml(model.as_ptr(), &mut out) },
+            EM_VALID
+        );
+        assert!(take(out).starts_with("{\"error\":null,"));
+        assert_eq!(
+            unsafe { em_validate_yaml(broken.as_ptr(), ptr::null_mut()) },
+            EM_INVALID
+        );
+        assert_eq!(
+            unsafe { em_validate_yaml(ptr::null(), ptr::null_mut()) },
+            EM_BAD_INPUT
+        );
+    }
+
+    #[test]
+    fn header_declares_every_exported_function() {
+        let header = include_str!("../include/event_modeler.h");
+        for declaration in [
+            "char *em_render_yaml_to_svg(const char *source, char **error);",
+            "int32_t em_validate_yaml(const char *source, char **report);",
+            "void em_string_free(char *string);",
+            "#define EM_VALID 0",
+            "#define EM_INVALID 1",
+            "#define EM_BAD_INPUT -1",
+        ] {
+            assert!(header.contains(declaration), "missing {declaration}");
+        }
+    }
+}
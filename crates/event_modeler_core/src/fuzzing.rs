@@ -0,0 +1,129 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Entry points for fuzzing the parsers.
+//!
+//! The cargo-fuzz targets in `fuzz/` hand arbitrary bytes to [`fuzz_yaml`]
+//! and [`fuzz_dsl`], which parse them and render them on a small
+//! [`RenderBudget`]. Malformed input must come back as an error, so any
+//! panic is a bug. [`seed_corpus`] derives starting inputs from valid models.
+
+use crate::diagram::{self, RenderBudget, RenderCache, SvgOptions};
+use crate::infrastructure::parsing::yaml_parser::UnknownKeyPolicy;
+use crate::infrastructure::parsing::{simple_parser, yaml_converter, yaml_parser};
+use crate::infrastructure::warnings::Warnings;
+use std::time::Duration;
+
+/// Longest a fuzzed model may spend routing its connectors.
+const RENDER_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Most connectors a fuzzed model may route.
+const ROUTING_ITERATIONS: usize = 50;
+
+/// Parses and converts `data` as a YAML event model, builds its diagram, and
+/// renders it on a small budget, ignoring the outcome.
+pub fn fuzz_yaml(data: &[u8]) {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    for policy in [UnknownKeyPolicy::Reject, UnknownKeyPolicy::Warn] {
+        let mut warnings = Warnings::new();
+        let Ok(model) = yaml_parser::parse_yaml_with_policy(input, policy, &mut warnings) else {
+            continue;
+        };
+        let Ok(model) = yaml_converter::convert_yaml_to_domain_with_warnings(model, &mut warnings)
+        else {
+            continue;
+        };
+        let Ok(diagram) = diagram::build_diagram_from_domain(&model) else {
+            continue;
+        };
+        let options = SvgOptions {
+            budget: RenderBudget::unlimited()
+                .with_timeout(RENDER_TIMEOUT)
+                .with_max_routing_iterations(ROUTING_ITERATIONS),
+            ..SvgOptions::default()
+        };
+        let _ = diagram::render_to_svg_with_cache(
+            &diagram,
+            &options,
+            &mut RenderCache::new(),
+            &mut warnings,
+        );
+    }
+}
+
+/// Parses `data` in the line-based text format, ignoring the outcome.
+pub fn fuzz_dsl(data: &[u8]) {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    let _ = simple_parser::EventModelParser::new().parse(input);
+}
+
+/// Returns seed inputs derived from a valid model: the model itself, the
+/// model without each of its top-level sections, and the model cut off
+/// after each of them.
+///
+/// A top-level section starts at an unindented line and runs until the next
+/// one, which covers both YAML keys and the lines of the text format.
+pub fn seed_corpus(model: &str) -> Vec<String> {
+    let lines: Vec<&str> = model.lines().collect();
+    let starts: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with([' ', '\t']))
+        .map(|(index, _)| index)
+        .collect();
+    let ends = starts.iter().skip(1).copied().chain([lines.len()]);
+
+    let mut seeds = vec![model.to_string()];
+    for (&start, end) in starts.iter().zip(ends) {
+        let without = [&lines[..start], &lines[end..]].concat();
+        seeds.push(without.join("\n"));
+        seeds.push(lines[..end].join("\n"));
+    }
+    seeds.sort();
+    seeds.dedup();
+    seeds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODEL: &str = r#"workflow: Ordering
+swimlanes:
+  - backend: "Backend"
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+"#;
+
+    #[test]
+    fn seeds_are_non_empty_and_parse_while_they_keep_the_required_sections() {
+        let seeds = seed_corpus(MODEL);
+
+        assert_eq!(seeds.len(), 10);
+        assert!(seeds.contains(&MODEL.to_string()));
+        for seed in &seeds {
+            assert!(!seed.trim().is_empty());
+            let required = seed.contains("workflow:") && seed.contains("swimlanes:");
+            assert_eq!(
+                yaml_parser::parse_yaml(seed).is_ok(),
+                required,
+                "seed:\n{seed}"
+            );
+            fuzz_yaml(seed.as_bytes());
+        }
+    }
+}
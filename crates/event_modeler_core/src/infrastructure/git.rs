@@ -0,0 +1,94 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Read-only access to the git history of a model file.
+//!
+//! Past versions are read with `git log` and `git show`, so the working tree,
+//! the index, and the checked-out branch are never touched.
+
+use std::path::Path;
+use std::process::Command;
+
+/// A commit that changed a model file, and the file as of that commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revision {
+    /// The abbreviated commit hash.
+    pub commit: String,
+    /// The commit date, as `YYYY-MM-DD`.
+    pub date: String,
+    /// The file contents at the commit, or `None` if the commit deleted it.
+    pub content: Option<String>,
+}
+
+/// Errors that can occur while reading a file's history.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum GitError {
+    /// `git` could not be run.
+    #[error("Could not run git: {0}")]
+    Spawn(#[from] std::io::Error),
+
+    /// `git` ran but failed, e.g. because the file is not in a repository.
+    #[error("git {command} failed: {stderr}")]
+    Failed {
+        /// The git subcommand that failed.
+        command: String,
+        /// What git printed to standard error.
+        stderr: String,
+    },
+}
+
+/// Returns the last `last` commits that changed the file at `path`, oldest
+/// first, with the file as of each.
+pub fn file_history(path: &Path, last: usize) -> Result<Vec<Revision>, GitError> {
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let log = git(
+        directory,
+        &[
+            "log",
+            "--format=%h%x09%cs",
+            "-n",
+            &last.to_string(),
+            "--",
+            &file_name,
+        ],
+    )?;
+    let mut revisions = Vec::new();
+    for line in log.lines().rev() {
+        let Some((commit, date)) = line.split_once('\t') else {
+            continue;
+        };
+        // `./` makes the path relative to `directory` rather than the repository root
+        let content = git(directory, &["show", &format!("{commit}:./{file_name}")]).ok();
+        revisions.push(Revision {
+            commit: commit.to_string(),
+            date: date.to_string(),
+            content,
+        });
+    }
+    Ok(revisions)
+}
+
+/// Runs a git subcommand in `directory` and returns its standard output.
+fn git(directory: &Path, args: &[&str]) -> Result<String, GitError> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(args)
+        .output()?;
+    if !output.status.success() {
+        return Err(GitError::Failed {
+            command: args.first().copied().unwrap_or_default().to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
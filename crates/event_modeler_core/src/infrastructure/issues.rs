@@ -0,0 +1,174 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Open or closed state of the issues tracking slices.
+//!
+//! On request (`--verify-issues`), each slice's `issue` is looked up in its
+//! tracker's REST API with `curl`, so no TLS stack is built into the binary.
+//! GitHub is queried anonymously unless `GITHUB_TOKEN` is set; Jira with basic
+//! authentication when `JIRA_USER` and `JIRA_TOKEN` are set. Credentials are
+//! handed to `curl` on standard input, never on its command line.
+
+use crate::event_model::yaml_types::{IssueLink, YamlEventModel};
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Whether an issue is still being worked on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueState {
+    /// The issue is open, or in a Jira status not yet done.
+    Open,
+    /// The issue is closed, or in a Jira status that is done.
+    Closed,
+}
+
+impl IssueState {
+    /// Returns the state in lower case, e.g. `open`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Closed => "closed",
+        }
+    }
+}
+
+/// Errors that can occur while looking issues up.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum IssueError {
+    /// `curl` could not be run.
+    #[error("Could not run curl: {0}")]
+    Spawn(#[from] std::io::Error),
+
+    /// The tracker could not be reached or did not know the issue.
+    #[error("Could not look up issue {issue}: {reason}")]
+    Lookup {
+        /// The issue's label, e.g. `PROJ-123`.
+        issue: String,
+        /// What went wrong.
+        reason: String,
+    },
+}
+
+/// Looks up the state of every slice's issue, keyed by the issue's URL.
+pub fn fetch_issue_states(
+    model: &YamlEventModel,
+) -> Result<HashMap<String, IssueState>, IssueError> {
+    let mut states = HashMap::new();
+    for issue in model.slices.iter().filter_map(|slice| slice.issue.as_ref()) {
+        if states.contains_key(&issue.url()) {
+            continue;
+        }
+        let body = fetch(issue)?;
+        let state = parse_state(issue, &body).ok_or_else(|| IssueError::Lookup {
+            issue: issue.label(),
+            reason: "the response has no state".to_string(),
+        })?;
+        states.insert(issue.url(), state);
+    }
+    Ok(states)
+}
+
+/// Returns the address of the API describing the issue.
+fn api_url(issue: &IssueLink) -> String {
+    match issue {
+        IssueLink::Jira { site, key } => format!("{site}/rest/api/2/issue/{key}?fields=status"),
+        IssueLink::GitHub {
+            owner,
+            repo,
+            number,
+        } => format!("https://api.github.com/repos/{owner}/{repo}/issues/{number}"),
+    }
+}
+
+/// Fetches the API description of an issue, passing credentials from the
+/// environment as `curl` configuration on standard input.
+fn fetch(issue: &IssueLink) -> Result<String, IssueError> {
+    let mut config = String::new();
+    match issue {
+        IssueLink::GitHub { .. } => {
+            config.push_str("header = \"Accept: application/vnd.github+json\"\n");
+            if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+                config.push_str(&format!("header = \"Authorization: Bearer {token}\"\n"));
+            }
+        }
+        IssueLink::Jira { .. } => {
+            if let (Ok(user), Ok(token)) = (std::env::var("JIRA_USER"), std::env::var("JIRA_TOKEN"))
+            {
+                config.push_str(&format!("user = \"{user}:{token}\"\n"));
+            }
+        }
+    }
+
+    let mut curl = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--config", "-", "--"])
+        .arg(api_url(issue))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = curl.stdin.take() {
+        stdin.write_all(config.as_bytes())?;
+    }
+    let output = curl.wait_with_output()?;
+    if !output.status.success() {
+        return Err(IssueError::Lookup {
+            issue: issue.label(),
+            reason: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Reads the state from an API response: GitHub's `state`, or whether the
+/// Jira status belongs to the done category.
+fn parse_state(issue: &IssueLink, body: &str) -> Option<IssueState> {
+    let response: serde_json::Value = serde_json::from_str(body).ok()?;
+    let closed = match issue {
+        IssueLink::GitHub { .. } => response["state"].as_str()? == "closed",
+        IssueLink::Jira { .. } => {
+            response["fields"]["status"]["statusCategory"]["key"].as_str()? == "done"
+        }
+    };
+    Some(if closed {
+        IssueState::Closed
+    } else {
+        IssueState::Open
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_state_from_github_and_jira_responses() {
+        let github = IssueLink::GitHub {
+            owner: "acme".to_string(),
+            repo: "shop".to_string(),
+            number: 42,
+        };
+        let jira = IssueLink::Jira {
+            site: "https://acme.atlassian.net".to_string(),
+            key: "SHOP-7".to_string(),
+        };
+
+        assert_eq!(
+            api_url(&github),
+            "https://api.github.com/repos/acme/shop/issues/42"
+        );
+        assert_eq!(
+            parse_state(&github, r#"{"number": 42, "state": "closed"}"#),
+            Some(IssueState::Closed)
+        );
+        assert_eq!(
+            parse_state(
+                &jira,
+                r#"{"fields": {"status": {"name": "In Review", "statusCategory": {"key": "indeterminate"}}}}"#
+            ),
+            Some(IssueState::Open)
+        );
+        assert_eq!(parse_state(&jira, r#"{"fields": {}}"#), None);
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Verification of the links from entities to their implementation.
+//!
+//! `implemented_by` references are checked against the filesystem on request
+//! (`--verify-links`): files must exist relative to the model file, and Rust
+//! item paths must resolve to a module file of the crate containing the
+//! model. URLs are not fetched.
+
+use crate::event_model::yaml_types::{ImplementationLink, YamlEventModel};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// An `implemented_by` reference that does not resolve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenLink {
+    /// The entity declaring the reference.
+    pub entity: String,
+    /// The reference as written.
+    pub link: String,
+    /// Why the reference does not resolve.
+    pub reason: String,
+}
+
+/// Every broken reference found in a model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenLinks(pub Vec<BrokenLink>);
+
+impl fmt::Display for BrokenLinks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Broken implementation links:")?;
+        for broken in &self.0 {
+            write!(
+                f,
+                "\n  {} -> {}: {}",
+                broken.entity, broken.link, broken.reason
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BrokenLinks {}
+
+/// Checks the `implemented_by` references of every command and event.
+///
+/// File paths are resolved against `model_dir`, the directory holding the
+/// model file. Rust paths are resolved against the `src` directory of the
+/// nearest enclosing Cargo package.
+pub fn verify_links(model: &YamlEventModel, model_dir: &Path) -> Result<(), BrokenLinks> {
+    let package_root = model_dir
+        .ancestors()
+        .find(|dir| dir.join("Cargo.toml").is_file());
+
+    let mut entities: Vec<(String, &Vec<ImplementationLink>)> = model
+        .commands
+        .iter()
+        .map(|(name, def)| (name.clone().into_inner().into_inner(), &def.implemented_by))
+        .chain(
+            model
+                .events
+                .iter()
+                .map(|(name, def)| (name.clone().into_inner().into_inner(), &def.implemented_by)),
+        )
+        .collect();
+    entities.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut broken = Vec::new();
+    for (entity, links) in entities {
+        for link in links {
+            let problem = match link {
+                ImplementationLink::Url(_) => None,
+                ImplementationLink::File(path) => {
+                    let path = model_dir.join(path.clone().into_inner().as_str());
+                    (!path.is_file()).then(|| format!("no file at {}", path.display()))
+                }
+                ImplementationLink::RustPath(path) => match package_root {
+                    Some(root) => {
+                        resolve_rust_path(&root.join("src"), path.clone().into_inner().as_str())
+                            .err()
+                    }
+                    None => Some("no Cargo.toml found above the model".to_string()),
+                },
+            };
+            if let Some(reason) = problem {
+                broken.push(BrokenLink {
+                    entity: entity.clone(),
+                    link: link.to_string(),
+                    reason,
+                });
+            }
+        }
+    }
+
+    if broken.is_empty() {
+        Ok(())
+    } else {
+        Err(BrokenLinks(broken))
+    }
+}
+
+/// Finds the module file declaring the item a Rust path names.
+///
+/// The first segment names the crate and is skipped. The longest prefix of
+/// the remaining segments with a module file (`a/b.rs` or `a/b/mod.rs`)
+/// wins; if segments are left over, that file must declare the first of
+/// them as an item.
+fn resolve_rust_path(src: &Path, path: &str) -> Result<PathBuf, String> {
+    let segments: Vec<&str> = path.split("::").map(str::trim).skip(1).collect();
+
+    for depth in (0..=segments.len()).rev() {
+        let Some(file) = module_file(src, &segments[..depth]) else {
+            continue;
+        };
+        return match segments.get(depth) {
+            None => Ok(file),
+            Some(item) => {
+                let source = std::fs::read_to_string(&file)
+                    .map_err(|e| format!("cannot read {}: {e}", file.display()))?;
+                if declares_item(&source, item) {
+                    Ok(file)
+                } else {
+                    Err(format!("'{item}' not found in {}", file.display()))
+                }
+            }
+        };
+    }
+    Err(format!("no crate root in {}", src.display()))
+}
+
+/// Returns whether `source` declares an item named `item`, e.g. `fn item` or
+/// `pub struct item<T>`.
+fn declares_item(source: &str, item: &str) -> bool {
+    const ITEM_KEYWORDS: [&str; 6] = ["fn", "struct", "enum", "trait", "mod", "type"];
+    let words: Vec<&str> = source
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|word| !word.is_empty())
+        .collect();
+    words
+        .windows(2)
+        .any(|pair| ITEM_KEYWORDS.contains(&pair[0]) && pair[1] == item)
+}
+
+/// Returns the file defining a module, given its path below the crate root.
+fn module_file(src: &Path, module: &[&str]) -> Option<PathBuf> {
+    let candidates = match module {
+        [] => vec![src.join("lib.rs"), src.join("main.rs")],
+        [parents @ .., name] => {
+            let dir: PathBuf = parents.iter().fold(src.to_path_buf(), |dir, p| dir.join(p));
+            vec![
+                dir.join(format!("{name}.rs")),
+                dir.join(name).join("mod.rs"),
+            ]
+        }
+    };
+    candidates.into_iter().find(|candidate| candidate.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_rust_paths_to_module_files() {
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+
+        assert_eq!(
+            resolve_rust_path(&src, "crate::render::render_svg_str"),
+            Ok(src.join("render.rs"))
+        );
+        assert_eq!(
+            resolve_rust_path(&src, "event_modeler_core::infrastructure::links"),
+            Ok(src.join("infrastructure").join("links.rs"))
+        );
+        assert_eq!(
+            resolve_rust_path(&src, "crate::diagram::DoesNotExist"),
+            Err(format!(
+                "'DoesNotExist' not found in {}",
+                src.join("diagram").join("mod.rs").display()
+            ))
+        );
+    }
+
+    #[test]
+    fn rust_items_must_be_declared_not_merely_mentioned() {
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+
+        // `render` only appears inside other names and in prose
+        assert_eq!(
+            resolve_rust_path(&src, "crate::render::render"),
+            Err(format!(
+                "'render' not found in {}",
+                src.join("render.rs").display()
+            ))
+        );
+    }
+
+    #[test]
+    fn reports_missing_files_and_unresolved_rust_paths() {
+        let yaml = r#"
+workflow: Links
+swimlanes:
+  - backend: "Backend"
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+    implemented_by:
+      - crate::render::render_svg_str
+      - Cargo.toml
+      - https://example.com/docs/{name}
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+    implemented_by:
+      - src/missing.rs
+      - crate::render::NoSuchItem
+"#;
+        let parsed = crate::infrastructure::parsing::yaml_parser::parse_yaml(yaml).unwrap();
+        let model =
+            crate::infrastructure::parsing::yaml_converter::convert_yaml_to_domain(parsed).unwrap();
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+        let broken = verify_links(&model, root).unwrap_err().0;
+
+        assert_eq!(broken.len(), 2);
+        assert!(broken.iter().all(|link| link.entity == "OrderPlaced"));
+        assert_eq!(broken[0].link, "src/missing.rs");
+        assert_eq!(broken[1].link, "crate::render::NoSuchItem");
+    }
+}
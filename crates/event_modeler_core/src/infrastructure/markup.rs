@@ -0,0 +1,30 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Escaping text for the markup formats the crate writes.
+//!
+//! SVG diagrams, GraphML and badge exports, and the HTML site, glossary, and
+//! storyboard all embed names and descriptions from the model. They escape
+//! them the same way, so user text can neither break the markup nor inject
+//! elements of its own.
+
+/// Escapes text for XML, SVG, or HTML content and attribute values.
+pub(crate) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_markup_characters() {
+        assert_eq!(
+            escape_xml(r#"R&D <core> "team""#),
+            "R&amp;D &lt;core&gt; &quot;team&quot;"
+        );
+    }
+}
@@ -8,5 +8,13 @@
 //! type safety utilities, parsing infrastructure, and other cross-cutting
 //! concerns.
 
+pub mod git;
+pub mod issues;
+pub mod links;
+pub(crate) mod markup;
 pub mod parsing;
+#[cfg(feature = "plugins")]
+pub mod plugins;
+pub mod progress;
 pub mod types;
+pub mod warnings;
@@ -0,0 +1,107 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Lint for YAML anchors (`&name`) and aliases (`*name`).
+//!
+//! Anchors let a model share field definitions between entities. An alias
+//! whose anchor is missing or misspelled makes the whole document unreadable,
+//! and the deserializer only reports "unknown anchor" without naming it, so
+//! anchors are checked directly on the token stream first.
+
+use super::source_map::SourceLocation;
+use std::collections::HashMap;
+use std::fmt;
+use yaml_rust2::scanner::{Scanner, Token, TokenType};
+
+/// An anchor definition or alias reference in the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchorSite {
+    /// The anchor name, without the `&` or `*` sigil.
+    pub name: String,
+    /// Where the anchor or alias appears.
+    pub location: SourceLocation,
+}
+
+impl fmt::Display for AnchorSite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' at {}", self.name, self.location)
+    }
+}
+
+/// Problems found with the anchors of a document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnchorLint {
+    /// Aliases that refer to an anchor not defined earlier in the document.
+    pub unresolved: Vec<AnchorSite>,
+    /// Anchors that no alias refers to.
+    pub unused: Vec<AnchorSite>,
+}
+
+/// Checks every alias in `input` against the anchors defined before it.
+///
+/// Scanning stops at the first syntax error; the deserializer reports those.
+pub fn lint_anchors(input: &str) -> AnchorLint {
+    let mut definitions: Vec<(AnchorSite, bool)> = Vec::new();
+    let mut latest_definition: HashMap<String, usize> = HashMap::new();
+    let mut unresolved = Vec::new();
+
+    let mut scanner = Scanner::new(input.chars());
+    while let Ok(Some(Token(mark, token))) = scanner.next_token() {
+        match token {
+            TokenType::Anchor(name) => {
+                latest_definition.insert(name.clone(), definitions.len());
+                let site = AnchorSite {
+                    name,
+                    location: mark.into(),
+                };
+                definitions.push((site, false));
+            }
+            TokenType::Alias(name) => match latest_definition.get(&name) {
+                Some(&index) => definitions[index].1 = true,
+                None => unresolved.push(AnchorSite {
+                    name,
+                    location: mark.into(),
+                }),
+            },
+            _ => {}
+        }
+    }
+
+    AnchorLint {
+        unresolved,
+        unused: definitions
+            .into_iter()
+            .filter(|(_, used)| !used)
+            .map(|(site, _)| site)
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_aliases_without_a_prior_anchor() {
+        let lint = lint_anchors("early: *shared\nlater: &shared value\nmissing: *nope\n");
+
+        let names: Vec<_> = lint.unresolved.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["shared", "nope"]);
+        assert_eq!(
+            lint.unresolved[1].location,
+            SourceLocation {
+                line: 3,
+                column: 10
+            }
+        );
+    }
+
+    #[test]
+    fn reports_anchors_that_are_never_referenced() {
+        let lint = lint_anchors("a: &used 1\nb: *used\nc: &idle 2\n");
+
+        assert!(lint.unresolved.is_empty());
+        assert_eq!(lint.unused.len(), 1);
+        assert_eq!(lint.unused[0].name, "idle");
+    }
+}
@@ -0,0 +1,872 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Anonymizing a model so it can be shared, e.g. to reproduce a bug.
+//!
+//! Every name that says something about the domain is replaced with a neutral
+//! placeholder: entities become `AlphaEvent`, `BravoEvent`, `AlphaCommand`,
+//! and so on, swimlanes `lane1` labelled `Lane 1`, fields `field1`, domain types `Type1`,
+//! slices `Slice 1`. References to a name are rewritten along with it, so the
+//! anonymized model has the same entities, connections, and slices as the
+//! original and renders the same diagram apart from its text.
+//!
+//! Free text is replaced too: descriptions repeat the entity's new name,
+//! documentation and hotspots become a single word, and what has no neutral
+//! stand-in is dropped: authors, banned words, issues, the Jira site, `implemented_by`
+//! links, scenario examples, and type patterns.
+//!
+//! Names are numbered in the order they are met, walking every map in key
+//! order, so the same model is always anonymized the same way. Entity names
+//! keep the suffix of their type, as the type of a connection's endpoints is
+//! inferred from their names, and are told apart by whole words rather than
+//! numbers, which the terminology check would take for near duplicates.
+
+use super::yaml_converter::{BUILT_IN_TYPES, GENERIC_TYPES, TEMPLATE_PLACEHOLDER};
+use super::yaml_parser::{
+    YamlAutomation, YamlCommand, YamlCommandError, YamlComponent, YamlConnection, YamlConventions,
+    YamlDocumentation, YamlEvent, YamlEventModel, YamlField, YamlLayoutConstraint,
+    YamlModelFragment, YamlProjection, YamlQuery, YamlQueryVariant, YamlSlice, YamlSwimlane,
+    YamlTestScenario, YamlTestStep, YamlTypeDefinition, YamlView, YamlWorkflow, split_connection,
+};
+use std::collections::HashMap;
+use std::mem::take;
+
+/// Neutral names given to one kind of name, numbered in the order first met.
+struct Names {
+    renamed: HashMap<String, String>,
+    label: fn(usize) -> String,
+    suffix: &'static str,
+}
+
+impl Names {
+    fn new(label: fn(usize) -> String) -> Self {
+        Self {
+            renamed: HashMap::new(),
+            label,
+            suffix: "",
+        }
+    }
+
+    /// Names for entities of one type, e.g. `AlphaEvent`.
+    fn entities(suffix: &'static str) -> Self {
+        Self {
+            renamed: HashMap::new(),
+            label: code_word,
+            suffix,
+        }
+    }
+
+    /// Returns the neutral name of `name`, giving it the next one if it has none.
+    fn rename(&mut self, name: &str) -> String {
+        if let Some(renamed) = self.renamed.get(name) {
+            return renamed.clone();
+        }
+        let mut renamed = (self.label)(self.renamed.len() + 1);
+        if name.contains(TEMPLATE_PLACEHOLDER) {
+            // Each instantiation of a template still needs a name of its own
+            renamed.push_str(TEMPLATE_PLACEHOLDER);
+        }
+        renamed.push_str(self.suffix);
+        self.renamed.insert(name.to_string(), renamed.clone());
+        renamed
+    }
+
+    /// Returns the neutral name of `name`, if it has been given one.
+    fn get(&self, name: &str) -> Option<String> {
+        self.renamed.get(name).cloned()
+    }
+}
+
+/// Words told apart at a glance, naming entities.
+const CODE_WORDS: [&str; 26] = [
+    "Alpha", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India", "Juliett",
+    "Kilo", "Lima", "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo", "Sierra", "Tango",
+    "Uniform", "Victor", "Whiskey", "Xray", "Yankee", "Zulu",
+];
+
+/// Returns the `n`th code word, counting from one, numbered once the words
+/// run out: `Alpha`, ..., `Zulu`, `Alpha2`, ...
+fn code_word(n: usize) -> String {
+    let index = n - 1;
+    let word = CODE_WORDS[index % CODE_WORDS.len()];
+    match index / CODE_WORDS.len() {
+        0 => word.to_string(),
+        round => format!("{word}{}", round + 1),
+    }
+}
+
+/// Returns the entries of a map in key order.
+fn sorted<T>(map: HashMap<String, T>) -> Vec<(String, T)> {
+    let mut entries: Vec<_> = map.into_iter().collect();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    entries
+}
+
+/// Returns a copy of `model` with every domain name replaced by a neutral one.
+pub fn anonymize(mut model: YamlEventModel) -> YamlEventModel {
+    let mut anonymizer = Anonymizer::new();
+
+    // Entities are named before anything refers to them, so references
+    // keep the suffix of the type they refer to
+    let mut main = take_fragment(&mut model);
+    anonymizer.declare(&main);
+    for (_, profile) in sorted(model.profiles.clone()) {
+        anonymizer.declare(&profile.add);
+    }
+    for (_, template) in sorted(model.templates.clone()) {
+        anonymizer.declare(&template);
+    }
+    for (kind, _) in sorted(model.extensions.clone()) {
+        anonymizer.kinds.rename(&kind);
+    }
+
+    model.workflow = match model.workflow {
+        YamlWorkflow::Title(_) => YamlWorkflow::Title("Workflow".to_string()),
+        YamlWorkflow::Header(mut header) => {
+            header.title = "Workflow".to_string();
+            header.description = None;
+            header.authors.clear();
+            YamlWorkflow::Header(header)
+        }
+    };
+    model.swimlanes = take(&mut model.swimlanes)
+        .into_iter()
+        .map(|swimlane| anonymizer.swimlane(swimlane))
+        .collect();
+    model.roles = take(&mut model.roles)
+        .iter()
+        .map(|role| anonymizer.roles.rename(role))
+        .collect();
+    model.terminology.banned_words.clear();
+    model.conventions = anonymizer.conventions(take(&mut model.conventions));
+    model.types = sorted(take(&mut model.types))
+        .into_iter()
+        .map(|(name, definition)| {
+            (
+                anonymizer.types.rename(&name),
+                anonymizer.type_definition(definition),
+            )
+        })
+        .collect();
+    model.field_sets = sorted(take(&mut model.field_sets))
+        .into_iter()
+        .map(|(name, mut set)| {
+            set.extends = anonymizer.field_sets(set.extends);
+            set.fields = anonymizer.fields(set.fields);
+            (anonymizer.field_sets.rename(&name), set)
+        })
+        .collect();
+    model.extensions = sorted(take(&mut model.extensions))
+        .into_iter()
+        .map(|(name, mut extension)| {
+            extension.connects_to = extension
+                .connects_to
+                .iter()
+                .map(|target| anonymizer.kinds.get(target).unwrap_or(target.clone()))
+                .collect();
+            (anonymizer.kinds.rename(&name), extension)
+        })
+        .collect();
+
+    main = anonymizer.fragment(main);
+    put_fragment(&mut model, main);
+
+    model.constraints = take(&mut model.constraints)
+        .into_iter()
+        .map(|constraint| anonymizer.constraint(constraint))
+        .collect();
+    model.profiles = sorted(take(&mut model.profiles))
+        .into_iter()
+        .map(|(name, mut profile)| {
+            let removals = &mut profile.remove;
+            removals.entities = take(&mut removals.entities)
+                .iter()
+                .map(|entity| anonymizer.entity(entity))
+                .collect();
+            removals.slices = take(&mut removals.slices)
+                .iter()
+                .map(|slice| anonymizer.slices.rename(slice))
+                .collect();
+            removals.connections = take(&mut removals.connections)
+                .iter()
+                .map(|connection| anonymizer.connection(connection))
+                .collect();
+            profile.add = anonymizer.fragment(profile.add);
+            (anonymizer.profiles.rename(&name), profile)
+        })
+        .collect();
+    model.templates = sorted(take(&mut model.templates))
+        .into_iter()
+        .map(|(name, template)| {
+            (
+                anonymizer.templates.rename(&name),
+                anonymizer.fragment(template),
+            )
+        })
+        .collect();
+    for expansion in &mut model.expand {
+        expansion.template = anonymizer.templates.rename(&expansion.template);
+        expansion.for_each = expansion
+            .for_each
+            .iter()
+            .map(|argument| anonymizer.arguments.rename(argument))
+            .collect();
+    }
+
+    model.jira = None;
+    // Anchors have been resolved while parsing, so the fragments they came
+    // from are no longer needed
+    model.fragments = None;
+    model
+}
+
+/// Moves the entities and slices of a model into a fragment of their own.
+fn take_fragment(model: &mut YamlEventModel) -> YamlModelFragment {
+    YamlModelFragment {
+        events: take(&mut model.events),
+        commands: take(&mut model.commands),
+        views: take(&mut model.views),
+        projections: take(&mut model.projections),
+        queries: take(&mut model.queries),
+        automations: take(&mut model.automations),
+        slices: take(&mut model.slices),
+    }
+}
+
+/// Moves the entities and slices of a fragment back into a model.
+fn put_fragment(model: &mut YamlEventModel, fragment: YamlModelFragment) {
+    model.events = fragment.events;
+    model.commands = fragment.commands;
+    model.views = fragment.views;
+    model.projections = fragment.projections;
+    model.queries = fragment.queries;
+    model.automations = fragment.automations;
+    model.slices = fragment.slices;
+}
+
+/// The neutral names given so far, by kind of name.
+struct Anonymizer {
+    events: Names,
+    commands: Names,
+    views: Names,
+    projections: Names,
+    queries: Names,
+    automations: Names,
+    /// Entities referred to but never defined
+    unknown: Names,
+    lanes: Names,
+    lane_labels: Names,
+    groups: Names,
+    owners: Names,
+    fields: Names,
+    types: Names,
+    field_sets: Names,
+    components: Names,
+    errors: Names,
+    scenarios: Names,
+    placeholders: Names,
+    slices: Names,
+    contexts: Names,
+    features: Names,
+    roles: Names,
+    kinds: Names,
+    profiles: Names,
+    templates: Names,
+    arguments: Names,
+}
+
+impl Anonymizer {
+    fn new() -> Self {
+        Self {
+            events: Names::entities("Event"),
+            commands: Names::entities("Command"),
+            views: Names::entities("View"),
+            projections: Names::entities("Projection"),
+            queries: Names::entities("Query"),
+            automations: Names::entities("Automation"),
+            unknown: Names::new(|n| format!("Unknown{n}")),
+            lanes: Names::new(|n| format!("lane{n}")),
+            lane_labels: Names::new(|n| format!("Lane {n}")),
+            groups: Names::new(|n| format!("Group {n}")),
+            owners: Names::new(|n| format!("Team {n}")),
+            fields: Names::new(|n| format!("field{n}")),
+            types: Names::new(|n| format!("Type{n}")),
+            field_sets: Names::new(|n| format!("FieldSet{n}")),
+            components: Names::new(|n| format!("control{n}")),
+            errors: Names::new(|n| format!("Error{n}")),
+            scenarios: Names::new(|n| format!("Scenario {n}")),
+            placeholders: Names::new(|n| format!("Value{n}")),
+            slices: Names::new(|n| format!("Slice {n}")),
+            contexts: Names::new(|n| format!("Context {n}")),
+            features: Names::new(|n| format!("feature{n}")),
+            roles: Names::new(|n| format!("role{n}")),
+            kinds: Names::new(|n| format!("kind{n}")),
+            profiles: Names::new(|n| format!("profile{n}")),
+            templates: Names::new(|n| format!("template{n}")),
+            arguments: Names::new(code_word),
+        }
+    }
+
+    /// Names the entities a fragment defines.
+    fn declare(&mut self, fragment: &YamlModelFragment) {
+        let declare = |names: &mut Names, mut keys: Vec<&String>| {
+            keys.sort();
+            for key in keys {
+                names.rename(key);
+            }
+        };
+        declare(&mut self.events, fragment.events.keys().collect());
+        declare(&mut self.commands, fragment.commands.keys().collect());
+        declare(&mut self.views, fragment.views.keys().collect());
+        declare(&mut self.projections, fragment.projections.keys().collect());
+        declare(&mut self.queries, fragment.queries.keys().collect());
+        declare(&mut self.automations, fragment.automations.keys().collect());
+    }
+
+    /// Returns the neutral name of an entity of any type.
+    fn entity(&mut self, name: &str) -> String {
+        [
+            &self.events,
+            &self.commands,
+            &self.views,
+            &self.projections,
+            &self.queries,
+            &self.automations,
+        ]
+        .into_iter()
+        .find_map(|names| names.get(name))
+        .unwrap_or_else(|| self.unknown.rename(name))
+    }
+
+    /// Rewrites a connection written `From -> To`.
+    fn connection(&mut self, text: &str) -> String {
+        match split_connection(text) {
+            Some((from, arrow, to)) => {
+                format!("{} {arrow} {}", self.endpoint(from), self.endpoint(to))
+            }
+            None => self.endpoint(text),
+        }
+    }
+
+    /// Rewrites an entity reference, or the path to a view's component.
+    fn endpoint(&mut self, reference: &str) -> String {
+        let mut parts = reference.split('.');
+        let entity = self.entity(parts.next().unwrap_or_default());
+        parts.fold(entity, |path, component| {
+            format!("{path}.{}", self.components.rename(component))
+        })
+    }
+
+    /// Rewrites the domain types named in a type expression, such as
+    /// `List<OrderLine>`, leaving the built-in ones.
+    fn type_expression(&mut self, expression: &str) -> String {
+        let mut rewritten = String::with_capacity(expression.len());
+        let mut word = String::new();
+        for c in expression.chars().map(Some).chain([None]) {
+            if let Some(c) = c.filter(|c| c.is_alphanumeric() || *c == '_') {
+                word.push(c);
+                continue;
+            }
+            let built_in = BUILT_IN_TYPES.contains(&word.as_str())
+                || GENERIC_TYPES.contains(&word.as_str())
+                || word.starts_with(|c: char| c.is_ascii_digit());
+            if word.is_empty() || built_in {
+                rewritten.push_str(&word);
+            } else {
+                rewritten.push_str(&self.types.rename(&word));
+            }
+            word.clear();
+            rewritten.extend(c);
+        }
+        rewritten
+    }
+
+    fn fields(&mut self, fields: HashMap<String, YamlField>) -> HashMap<String, YamlField> {
+        sorted(fields)
+            .into_iter()
+            .map(|(name, field)| {
+                let field = match field {
+                    YamlField::Simple(field_type) => {
+                        YamlField::Simple(self.type_expression(&field_type))
+                    }
+                    YamlField::Complex {
+                        field_type,
+                        stream_id,
+                        generated,
+                        pii,
+                    } => YamlField::Complex {
+                        field_type: self.type_expression(&field_type),
+                        stream_id,
+                        generated,
+                        pii,
+                    },
+                };
+                (self.fields.rename(&name), field)
+            })
+            .collect()
+    }
+
+    /// Rewrites fields written as `name: Type`.
+    fn typed_fields(&mut self, fields: HashMap<String, String>) -> HashMap<String, String> {
+        sorted(fields)
+            .into_iter()
+            .map(|(name, field_type)| {
+                (self.fields.rename(&name), self.type_expression(&field_type))
+            })
+            .collect()
+    }
+
+    fn field_sets(&mut self, sets: Vec<String>) -> Vec<String> {
+        sets.iter().map(|set| self.field_sets.rename(set)).collect()
+    }
+
+    fn conventions(&mut self, conventions: YamlConventions) -> YamlConventions {
+        YamlConventions {
+            envelope: conventions
+                .envelope
+                .iter()
+                .map(|field| self.fields.rename(field))
+                .collect(),
+            propagate: conventions
+                .propagate
+                .iter()
+                .map(|field| self.fields.rename(field))
+                .collect(),
+        }
+    }
+
+    fn type_definition(&mut self, definition: YamlTypeDefinition) -> YamlTypeDefinition {
+        match definition {
+            YamlTypeDefinition::Alias(base) => {
+                YamlTypeDefinition::Alias(self.type_expression(&base))
+            }
+            YamlTypeDefinition::Constrained(mut constrained) => {
+                constrained.base = constrained.base.map(|base| self.type_expression(&base));
+                constrained.variants = constrained
+                    .variants
+                    .iter()
+                    .map(|variant| self.types.rename(variant))
+                    .collect();
+                constrained.pattern = None;
+                YamlTypeDefinition::Constrained(constrained)
+            }
+            YamlTypeDefinition::Record(fields) => {
+                YamlTypeDefinition::Record(self.typed_fields(fields))
+            }
+        }
+    }
+
+    fn swimlane(&mut self, swimlane: YamlSwimlane) -> YamlSwimlane {
+        match swimlane {
+            YamlSwimlane::Simple(id) => YamlSwimlane::Simple(self.lanes.rename(&id)),
+            YamlSwimlane::Map(lanes) => YamlSwimlane::Map(
+                sorted(lanes)
+                    .into_iter()
+                    .map(|(id, label)| (self.lanes.rename(&id), self.lane_labels.rename(&label)))
+                    .collect(),
+            ),
+            YamlSwimlane::Detailed(lanes) => YamlSwimlane::Detailed(
+                sorted(lanes)
+                    .into_iter()
+                    .map(|(id, mut details)| {
+                        details.name = self.lane_labels.rename(&details.name);
+                        details.owner = details.owner.map(|owner| self.owners.rename(&owner));
+                        (self.lanes.rename(&id), details)
+                    })
+                    .collect(),
+            ),
+            YamlSwimlane::Group(mut group) => {
+                group.group = self.groups.rename(&group.group);
+                group.swimlanes = take(&mut group.swimlanes)
+                    .into_iter()
+                    .map(|swimlane| self.swimlane(swimlane))
+                    .collect();
+                YamlSwimlane::Group(group)
+            }
+        }
+    }
+
+    fn constraint(&mut self, constraint: YamlLayoutConstraint) -> YamlLayoutConstraint {
+        match constraint {
+            YamlLayoutConstraint::Align(mut align) => {
+                align.align = align.align.iter().map(|name| self.entity(name)).collect();
+                YamlLayoutConstraint::Align(align)
+            }
+            YamlLayoutConstraint::Order(mut order) => {
+                order.order = order.order.iter().map(|name| self.entity(name)).collect();
+                YamlLayoutConstraint::Order(order)
+            }
+            YamlLayoutConstraint::Gap(mut gap) => {
+                let [left, right] = &gap.gap.between;
+                gap.gap.between = [self.entity(left), self.entity(right)];
+                YamlLayoutConstraint::Gap(gap)
+            }
+        }
+    }
+
+    /// Rewrites the parts every entity type shares.
+    fn common(
+        &mut self,
+        feature: &mut Option<String>,
+        owner: &mut Option<String>,
+        kind: &mut Option<String>,
+        hotspot: &mut Option<String>,
+        doc: &mut Option<YamlDocumentation>,
+    ) {
+        *feature = feature.take().map(|feature| self.features.rename(&feature));
+        *owner = owner.take().map(|owner| self.owners.rename(&owner));
+        *kind = kind.take().map(|kind| self.kinds.rename(&kind));
+        *hotspot = hotspot.take().map(|_| "Hotspot".to_string());
+        *doc = doc
+            .take()
+            .map(|_| YamlDocumentation::Markdown("Documentation".to_string()));
+    }
+
+    fn fragment(&mut self, fragment: YamlModelFragment) -> YamlModelFragment {
+        YamlModelFragment {
+            events: sorted(fragment.events)
+                .into_iter()
+                .map(|(name, event)| self.event(&name, event))
+                .collect(),
+            commands: sorted(fragment.commands)
+                .into_iter()
+                .map(|(name, command)| self.command(&name, command))
+                .collect(),
+            views: sorted(fragment.views)
+                .into_iter()
+                .map(|(name, view)| self.view(&name, view))
+                .collect(),
+            projections: sorted(fragment.projections)
+                .into_iter()
+                .map(|(name, projection)| self.projection(&name, projection))
+                .collect(),
+            queries: sorted(fragment.queries)
+                .into_iter()
+                .map(|(name, query)| self.query(&name, query))
+                .collect(),
+            automations: sorted(fragment.automations)
+                .into_iter()
+                .map(|(name, automation)| self.automation(&name, automation))
+                .collect(),
+            slices: fragment
+                .slices
+                .into_iter()
+                .map(|slice| self.slice(slice))
+                .collect(),
+        }
+    }
+
+    fn event(&mut self, name: &str, mut event: YamlEvent) -> (String, YamlEvent) {
+        let name = self.entity(name);
+        event.description = name.clone();
+        event.swimlane = self.lanes.rename(&event.swimlane);
+        event.data = self.fields(event.data);
+        event.extends = self.field_sets(event.extends);
+        event.implemented_by.clear();
+        self.common(
+            &mut event.feature,
+            &mut event.owner,
+            &mut event.kind,
+            &mut event.hotspot,
+            &mut event.doc,
+        );
+        (name, event)
+    }
+
+    fn command(&mut self, name: &str, mut command: YamlCommand) -> (String, YamlCommand) {
+        let name = self.entity(name);
+        command.description = name.clone();
+        command.swimlane = self.lanes.rename(&command.swimlane);
+        command.data = self.fields(command.data);
+        command.extends = self.field_sets(command.extends);
+        command.errors = sorted(command.errors)
+            .into_iter()
+            .map(|(error, definition)| {
+                let error = self.errors.rename(&error);
+                let definition = YamlCommandError {
+                    description: error.clone(),
+                    data: self.fields(definition.data),
+                };
+                (error, definition)
+            })
+            .collect();
+        command.authorized_roles = command
+            .authorized_roles
+            .iter()
+            .map(|role| self.roles.rename(role))
+            .collect();
+        command.tests = sorted(command.tests)
+            .into_iter()
+            .map(|(scenario, test)| (self.scenarios.rename(&scenario), self.scenario(test)))
+            .collect();
+        command.implemented_by.clear();
+        self.common(
+            &mut command.feature,
+            &mut command.owner,
+            &mut command.kind,
+            &mut command.hotspot,
+            &mut command.doc,
+        );
+        (name, command)
+    }
+
+    fn scenario(&mut self, test: YamlTestScenario) -> YamlTestScenario {
+        let mut steps = |steps: Vec<YamlTestStep>| -> Vec<YamlTestStep> {
+            steps
+                .into_iter()
+                .map(|step| YamlTestStep {
+                    step: sorted(step.step)
+                        .into_iter()
+                        .map(|(entity, data)| {
+                            let entity = match self.errors.get(&entity) {
+                                Some(error) => error,
+                                None => self.entity(&entity),
+                            };
+                            let data = sorted(data)
+                                .into_iter()
+                                .map(|(field, value)| {
+                                    (self.fields.rename(&field), self.placeholders.rename(&value))
+                                })
+                                .collect();
+                            (entity, data)
+                        })
+                        .collect(),
+                })
+                .collect()
+        };
+        YamlTestScenario {
+            given: steps(test.given),
+            when: steps(test.when),
+            then: steps(test.then),
+            // Realistic values are exactly what must not be shared
+            examples: HashMap::new(),
+        }
+    }
+
+    fn view(&mut self, name: &str, mut view: YamlView) -> (String, YamlView) {
+        let name = self.entity(name);
+        view.description = name.clone();
+        view.swimlane = self.lanes.rename(&view.swimlane);
+        view.components = take(&mut view.components)
+            .into_iter()
+            .map(|component| match component {
+                YamlComponent::Simple { component } => YamlComponent::Simple {
+                    component: sorted(component)
+                        .into_iter()
+                        .map(|(name, kind)| (self.components.rename(&name), kind))
+                        .collect(),
+                },
+                YamlComponent::Complex { component } => YamlComponent::Complex {
+                    component: sorted(component)
+                        .into_iter()
+                        .map(|(name, mut complex)| {
+                            let name = self.components.rename(&name);
+                            complex.fields = self.typed_fields(take(&mut complex.fields));
+                            complex.actions = complex
+                                .actions
+                                .iter()
+                                .map(|action| self.components.rename(action))
+                                .collect();
+                            (name, complex)
+                        })
+                        .collect(),
+                },
+            })
+            .collect();
+        self.common(
+            &mut view.feature,
+            &mut view.owner,
+            &mut view.kind,
+            &mut view.hotspot,
+            &mut view.doc,
+        );
+        (name, view)
+    }
+
+    fn projection(
+        &mut self,
+        name: &str,
+        mut projection: YamlProjection,
+    ) -> (String, YamlProjection) {
+        let name = self.entity(name);
+        projection.description = name.clone();
+        projection.swimlane = self.lanes.rename(&projection.swimlane);
+        projection.fields = self.typed_fields(take(&mut projection.fields));
+        self.common(
+            &mut projection.feature,
+            &mut projection.owner,
+            &mut projection.kind,
+            &mut projection.hotspot,
+            &mut projection.doc,
+        );
+        (name, projection)
+    }
+
+    fn query(&mut self, name: &str, mut query: YamlQuery) -> (String, YamlQuery) {
+        let name = self.entity(name);
+        query.swimlane = self.lanes.rename(&query.swimlane);
+        query.inputs = self.typed_fields(take(&mut query.inputs));
+        query.outputs.one_of = sorted(take(&mut query.outputs.one_of))
+            .into_iter()
+            .map(|(variant, output)| {
+                let output = match output {
+                    YamlQueryVariant::Simple(output) => {
+                        YamlQueryVariant::Simple(self.type_expression(&output))
+                    }
+                    YamlQueryVariant::Complex(fields) => {
+                        YamlQueryVariant::Complex(self.typed_fields(fields))
+                    }
+                };
+                (self.types.rename(&variant), output)
+            })
+            .collect();
+        self.common(
+            &mut query.feature,
+            &mut query.owner,
+            &mut query.kind,
+            &mut query.hotspot,
+            &mut query.doc,
+        );
+        (name, query)
+    }
+
+    fn automation(
+        &mut self,
+        name: &str,
+        mut automation: YamlAutomation,
+    ) -> (String, YamlAutomation) {
+        let name = self.entity(name);
+        automation.swimlane = self.lanes.rename(&automation.swimlane);
+        self.common(
+            &mut automation.feature,
+            &mut automation.owner,
+            &mut automation.kind,
+            &mut automation.hotspot,
+            &mut automation.doc,
+        );
+        (name, automation)
+    }
+
+    fn slice(&mut self, mut slice: YamlSlice) -> YamlSlice {
+        slice.name = self.slices.rename(&slice.name);
+        slice.context = slice.context.map(|context| self.contexts.rename(&context));
+        slice.issue = None;
+        slice.order = take(&mut slice.order)
+            .iter()
+            .map(|entity| self.entity(entity))
+            .collect();
+        slice.swimlanes = take(&mut slice.swimlanes)
+            .iter()
+            .map(|lane| self.lanes.rename(lane))
+            .collect();
+        slice.connections = take(&mut slice.connections)
+            .into_iter()
+            .map(|connection| match connection {
+                YamlConnection::Plain(text) => YamlConnection::Plain(self.connection(&text)),
+                YamlConnection::Detailed(mut detailed) => {
+                    detailed.connection = self.connection(&detailed.connection);
+                    detailed.feature = detailed
+                        .feature
+                        .map(|feature| self.features.rename(&feature));
+                    for waypoint in &mut detailed.via {
+                        waypoint.slice = self.slices.rename(&waypoint.slice);
+                        waypoint.lane = self.lanes.rename(&waypoint.lane);
+                    }
+                    detailed.hotspot = detailed.hotspot.map(|_| "Hotspot".to_string());
+                    YamlConnection::Detailed(detailed)
+                }
+            })
+            .collect();
+        slice
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::yaml_parser::parse_yaml;
+
+    const MODEL: &str = r#"workflow: Acme Checkout
+swimlanes:
+  - ui: "Storefront"
+  - backend: "Payments"
+views:
+  CartScreen:
+    description: "Shows the shopper's cart"
+    swimlane: ui
+    components:
+      - Pay: Button
+commands:
+  ChargeCard:
+    description: "Charge the shopper's card"
+    swimlane: backend
+    data:
+      card_number: CardNumber
+      amount: Decimal
+    tests:
+      "Charges the card":
+        Given: []
+        When:
+          - ChargeCard:
+              card_number: A
+        Then:
+          - CardCharged:
+              card_number: A
+        examples:
+          A: "4111111111111111"
+events:
+  CardCharged:
+    description: "The card was charged"
+    swimlane: backend
+    data:
+      card_number: CardNumber
+slices:
+  - name: Checkout
+    connections:
+      - CartScreen.Pay -> ChargeCard
+      - ChargeCard -> CardCharged
+"#;
+
+    #[test]
+    fn replaces_domain_names_keeping_references_intact() {
+        let anonymize_yaml = || {
+            let model = anonymize(parse_yaml(MODEL).unwrap());
+            crate::infrastructure::parsing::merge::to_yaml(&model).unwrap()
+        };
+        let yaml = anonymize_yaml();
+
+        for secret in [
+            "Acme",
+            "Storefront",
+            "Payments",
+            "Cart",
+            "Charge",
+            "card",
+            "Checkout",
+            "4111",
+        ] {
+            assert!(!yaml.contains(secret), "{secret} leaked into:\n{yaml}");
+        }
+        let model = parse_yaml(&yaml).unwrap();
+        assert_eq!(
+            model.slices[0]
+                .connections
+                .iter()
+                .map(|connection| connection.text().to_string())
+                .collect::<Vec<_>>(),
+            [
+                "AlphaView.control1 -> AlphaCommand",
+                "AlphaCommand -> AlphaEvent"
+            ]
+        );
+        assert_eq!(
+            model.commands["AlphaCommand"].data["field2"],
+            YamlField::Simple("Decimal".to_string())
+        );
+        assert_eq!(
+            model.events["AlphaEvent"].data["field1"],
+            YamlField::Simple("Type1".to_string())
+        );
+        assert_eq!(yaml, anonymize_yaml());
+    }
+}
@@ -0,0 +1,235 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Feature flags for conditional model content.
+//!
+//! Entities and connections may declare `feature: <name>`. They are kept in
+//! the model but only rendered when that feature is enabled, so upcoming work
+//! can live alongside what has shipped. Like profiles, flags are applied to
+//! the parsed YAML before conversion.
+
+use super::yaml_parser::{
+    YamlAutomation, YamlCommand, YamlEvent, YamlEventModel, YamlModelFragment, YamlProjection,
+    YamlQuery, YamlSlice, YamlView, split_connection,
+};
+use crate::infrastructure::warnings::{WarningKind, Warnings};
+use std::collections::{BTreeSet, HashMap};
+
+/// An element that may depend on a feature flag.
+trait Flagged {
+    /// Returns the feature the element depends on, if any.
+    fn feature(&self) -> Option<&str>;
+}
+
+macro_rules! impl_flagged {
+    ($($entity:ty),*) => {
+        $(impl Flagged for $entity {
+            fn feature(&self) -> Option<&str> {
+                self.feature.as_deref()
+            }
+        })*
+    };
+}
+
+impl_flagged!(
+    YamlEvent,
+    YamlCommand,
+    YamlView,
+    YamlProjection,
+    YamlQuery,
+    YamlAutomation
+);
+
+/// Removes every entity and connection whose feature is not in `enabled`,
+/// along with the connections that touch a removed entity.
+///
+/// Slices left without connections are dropped. Enabled features that
+/// nothing in the model depends on are reported as warnings, since they are
+/// most likely misspelled.
+pub fn apply_features(model: &mut YamlEventModel, enabled: &[String], warnings: &mut Warnings) {
+    let declared = declared_features(model);
+    for feature in enabled {
+        if !declared.contains(feature.as_str()) {
+            warnings.warn(
+                WarningKind::UnknownFeature,
+                format!("Feature '{feature}' is enabled but nothing in the model depends on it"),
+            );
+        }
+    }
+
+    let is_enabled = |feature: Option<&str>| {
+        feature.is_none_or(|feature| enabled.iter().any(|name| name == feature))
+    };
+
+    let mut removed = Vec::new();
+    retain_enabled(&mut model.events, &is_enabled, &mut removed);
+    retain_enabled(&mut model.commands, &is_enabled, &mut removed);
+    retain_enabled(&mut model.views, &is_enabled, &mut removed);
+    retain_enabled(&mut model.projections, &is_enabled, &mut removed);
+    retain_enabled(&mut model.queries, &is_enabled, &mut removed);
+    retain_enabled(&mut model.automations, &is_enabled, &mut removed);
+    retain_connections(&mut model.slices, &is_enabled, &removed);
+
+    for template in model.templates.values_mut() {
+        let mut removed = removed.clone();
+        retain_enabled(&mut template.events, &is_enabled, &mut removed);
+        retain_enabled(&mut template.commands, &is_enabled, &mut removed);
+        retain_enabled(&mut template.views, &is_enabled, &mut removed);
+        retain_enabled(&mut template.projections, &is_enabled, &mut removed);
+        retain_enabled(&mut template.queries, &is_enabled, &mut removed);
+        retain_enabled(&mut template.automations, &is_enabled, &mut removed);
+        retain_connections(&mut template.slices, &is_enabled, &removed);
+    }
+}
+
+/// Returns every feature named anywhere in the model, including its templates.
+fn declared_features(model: &YamlEventModel) -> BTreeSet<&str> {
+    let mut features = BTreeSet::new();
+    collect_entity_features(&model.events, &mut features);
+    collect_entity_features(&model.commands, &mut features);
+    collect_entity_features(&model.views, &mut features);
+    collect_entity_features(&model.projections, &mut features);
+    collect_entity_features(&model.queries, &mut features);
+    collect_entity_features(&model.automations, &mut features);
+    collect_connection_features(&model.slices, &mut features);
+    for template in model.templates.values() {
+        collect_fragment_features(template, &mut features);
+    }
+    features
+}
+
+/// Adds the features named in a template to `features`.
+fn collect_fragment_features<'a>(
+    fragment: &'a YamlModelFragment,
+    features: &mut BTreeSet<&'a str>,
+) {
+    collect_entity_features(&fragment.events, features);
+    collect_entity_features(&fragment.commands, features);
+    collect_entity_features(&fragment.views, features);
+    collect_entity_features(&fragment.projections, features);
+    collect_entity_features(&fragment.queries, features);
+    collect_entity_features(&fragment.automations, features);
+    collect_connection_features(&fragment.slices, features);
+}
+
+/// Adds the features named by entities to `features`.
+fn collect_entity_features<'a, T: Flagged>(
+    entities: &'a HashMap<String, T>,
+    features: &mut BTreeSet<&'a str>,
+) {
+    features.extend(entities.values().filter_map(Flagged::feature));
+}
+
+/// Adds the features named by slice connections to `features`.
+fn collect_connection_features<'a>(slices: &'a [YamlSlice], features: &mut BTreeSet<&'a str>) {
+    features.extend(
+        slices
+            .iter()
+            .flat_map(|slice| &slice.connections)
+            .filter_map(|connection| connection.feature()),
+    );
+}
+
+/// Removes disabled entities, recording their names in `removed`.
+fn retain_enabled<T: Flagged>(
+    entities: &mut HashMap<String, T>,
+    is_enabled: &dyn Fn(Option<&str>) -> bool,
+    removed: &mut Vec<String>,
+) {
+    entities.retain(|name, entity| {
+        let keep = is_enabled(entity.feature());
+        if !keep {
+            removed.push(name.clone());
+        }
+        keep
+    });
+}
+
+/// Removes disabled connections and those touching a removed entity, then
+/// drops slices left empty.
+fn retain_connections(
+    slices: &mut Vec<YamlSlice>,
+    is_enabled: &dyn Fn(Option<&str>) -> bool,
+    removed: &[String],
+) {
+    for slice in slices.iter_mut() {
+        slice.connections.retain(|connection| {
+            let enabled = is_enabled(connection.feature());
+            let touches_removed =
+                split_connection(connection.text()).is_some_and(|(from, _, to)| {
+                    [from, to].into_iter().any(|endpoint| {
+                        let entity = endpoint.split('.').next().unwrap_or_default();
+                        removed.iter().any(|name| name == entity)
+                    })
+                });
+            enabled && !touches_removed
+        });
+    }
+    slices.retain(|slice| !slice.connections.is_empty());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::yaml_parser::parse_yaml;
+
+    const MODEL: &str = r#"workflow: Orders
+swimlanes:
+  - backend: "Backend"
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+  GiftWrap:
+    description: "Wrap the order as a gift"
+    swimlane: backend
+    feature: gifting
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+      - connection: OrderPlaced -> PlaceOrder
+        feature: reorder
+  - name: Gifting
+    connections:
+      - OrderPlaced -> GiftWrap
+"#;
+
+    #[test]
+    fn disabled_features_remove_entities_connections_and_empty_slices() {
+        let mut model = parse_yaml(MODEL).unwrap();
+        apply_features(&mut model, &[], &mut Warnings::new());
+
+        assert!(!model.commands.contains_key("GiftWrap"));
+        assert_eq!(model.slices.len(), 1);
+        assert_eq!(model.slices[0].connections.len(), 1);
+    }
+
+    #[test]
+    fn enabled_features_keep_their_content() {
+        let mut model = parse_yaml(MODEL).unwrap();
+        let enabled = ["gifting".to_string(), "reorder".to_string()];
+        let mut warnings = Warnings::new();
+        apply_features(&mut model, &enabled, &mut warnings);
+
+        assert!(model.commands.contains_key("GiftWrap"));
+        assert_eq!(model.slices.len(), 2);
+        assert_eq!(model.slices[0].connections.len(), 2);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn warns_about_features_nothing_depends_on() {
+        let mut model = parse_yaml(MODEL).unwrap();
+        let mut warnings = Warnings::new();
+        apply_features(&mut model, &["giftng".to_string()], &mut warnings);
+
+        let unknown: Vec<_> = warnings.of_kind(WarningKind::UnknownFeature).collect();
+        assert_eq!(unknown.len(), 1);
+        assert!(unknown[0].message.contains("'giftng'"));
+    }
+}
@@ -213,6 +213,7 @@ impl<'a> Lexer<'a> {
 
 /// Errors that can occur during lexical analysis.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum LexError {
     /// An unexpected character was encountered.
     #[error("Unexpected character '{0}' at {1}:{2}")]
@@ -0,0 +1,1169 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Combining models that were split across files, e.g. one per team.
+//!
+//! Merging happens on the parsed YAML so the result can be written back out
+//! as a single model file. Definitions that appear in more than one file must
+//! be identical; anything else is reported as a [`MergeConflict`] rather than
+//! silently picking one side.
+//!
+//! Swimlanes are unified by identifier. A lane written without a label
+//! (`- backend`) takes the label another file gives it (`- backend: "Backend"`);
+//! two different labels for the same identifier are a conflict. Swimlane
+//! owners, colors, icons, and roles, the group a swimlane belongs to, and group
+//! backgrounds are unified the same way; a group is collapsed if any file collapses it.
+//! So are the bounded contexts, estimates, statuses, and issues of slices
+//! with the same name.
+
+use super::profiles::normalize_connection;
+use super::yaml_parser::{
+    YamlEventModel, YamlSwimlane, YamlSwimlaneDetails, YamlSwimlaneGroup, YamlSwimlaneRole,
+};
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Top-level sections keyed by name, written out in name order.
+const NAMED_SECTIONS: [&str; 11] = [
+    "types",
+    "field_sets",
+    "extensions",
+    "events",
+    "commands",
+    "views",
+    "projections",
+    "queries",
+    "automations",
+    "profiles",
+    "templates",
+];
+
+/// A model read from a named source, such as a file path.
+#[derive(Debug, Clone)]
+pub struct SourcedModel {
+    /// Where the model came from, used in conflict reports.
+    pub source: String,
+    /// The parsed model.
+    pub model: YamlEventModel,
+}
+
+/// Something that two sources define incompatibly.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MergeConflict {
+    /// The same name is defined with different content.
+    #[error("{kind} '{name}' is defined differently in {first} and {second}")]
+    DifferentDefinitions {
+        /// The kind of definition, e.g. `command` or `profile`.
+        kind: &'static str,
+        /// The conflicting name.
+        name: String,
+        /// The source of the definition that was kept.
+        first: String,
+        /// The source of the conflicting definition.
+        second: String,
+    },
+
+    /// The same name is used for entities of different types.
+    #[error("'{name}' is defined as {first_kind} in {first} but as {second_kind} in {second}")]
+    DifferentEntityTypes {
+        /// The conflicting name.
+        name: String,
+        /// The entity type in the first source.
+        first_kind: &'static str,
+        /// The first source.
+        first: String,
+        /// The entity type in the second source.
+        second_kind: &'static str,
+        /// The second source.
+        second: String,
+    },
+
+    /// The same swimlane identifier is given different labels.
+    #[error(
+        "swimlane '{id}' is labeled \"{first_label}\" in {first} but \"{second_label}\" in {second}"
+    )]
+    SwimlaneLabel {
+        /// The swimlane identifier.
+        id: String,
+        /// The label in the first source.
+        first_label: String,
+        /// The first source.
+        first: String,
+        /// The label in the second source.
+        second_label: String,
+        /// The second source.
+        second: String,
+    },
+
+    /// The same swimlane identifier is given different owners.
+    #[error(
+        "swimlane '{id}' is owned by {first_owner} in {first} but by {second_owner} in {second}"
+    )]
+    SwimlaneOwner {
+        /// The swimlane identifier.
+        id: String,
+        /// The owner in the first source.
+        first_owner: String,
+        /// The first source.
+        first: String,
+        /// The owner in the second source.
+        second_owner: String,
+        /// The second source.
+        second: String,
+    },
+
+    /// The same swimlane is given a different color, icon, or role.
+    #[error(
+        "swimlane '{id}' has {attribute} {first_value} in {first} but {second_value} in {second}"
+    )]
+    SwimlaneAttribute {
+        /// The swimlane identifier.
+        id: String,
+        /// The attribute that differs, `color`, `icon`, or `role`.
+        attribute: &'static str,
+        /// The value in the first source.
+        first_value: String,
+        /// The first source.
+        first: String,
+        /// The value in the second source.
+        second_value: String,
+        /// The second source.
+        second: String,
+    },
+
+    /// The same swimlane is placed in different groups.
+    #[error(
+        "swimlane '{id}' is in group \"{first_group}\" in {first} but \"{second_group}\" in {second}"
+    )]
+    SwimlaneGroup {
+        /// The swimlane identifier.
+        id: String,
+        /// The group in the first source.
+        first_group: String,
+        /// The first source.
+        first: String,
+        /// The group in the second source.
+        second_group: String,
+        /// The second source.
+        second: String,
+    },
+
+    /// The same swimlane group is given different backgrounds.
+    #[error(
+        "swimlane group \"{group}\" has background {first_background} in {first} but {second_background} in {second}"
+    )]
+    SwimlaneGroupBackground {
+        /// The group label.
+        group: String,
+        /// The background in the first source.
+        first_background: String,
+        /// The first source.
+        first: String,
+        /// The background in the second source.
+        second_background: String,
+        /// The second source.
+        second: String,
+    },
+
+    /// The same slice is placed in different bounded contexts.
+    #[error(
+        "slice \"{slice}\" is in context \"{first_context}\" in {first} but \"{second_context}\" in {second}"
+    )]
+    SliceContext {
+        /// The slice name.
+        slice: String,
+        /// The context in the first source.
+        first_context: String,
+        /// The first source.
+        first: String,
+        /// The context in the second source.
+        second_context: String,
+        /// The second source.
+        second: String,
+    },
+
+    /// The same slice is given different estimates.
+    #[error(
+        "slice \"{slice}\" is estimated at \"{first_estimate}\" in {first} but \"{second_estimate}\" in {second}"
+    )]
+    SliceEstimate {
+        /// The slice name.
+        slice: String,
+        /// The estimate in the first source, as written.
+        first_estimate: String,
+        /// The first source.
+        first: String,
+        /// The estimate in the second source, as written.
+        second_estimate: String,
+        /// The second source.
+        second: String,
+    },
+
+    /// The same slice is given different statuses.
+    #[error("slice \"{slice}\" is {first_status} in {first} but {second_status} in {second}")]
+    SliceStatus {
+        /// The slice name.
+        slice: String,
+        /// The status in the first source, as written.
+        first_status: String,
+        /// The first source.
+        first: String,
+        /// The status in the second source, as written.
+        second_status: String,
+        /// The second source.
+        second: String,
+    },
+
+    /// The same slice is tracked by different issues.
+    #[error(
+        "slice \"{slice}\" is tracked by {first_issue} in {first} but {second_issue} in {second}"
+    )]
+    SliceIssue {
+        /// The slice name.
+        slice: String,
+        /// The issue in the first source.
+        first_issue: String,
+        /// The first source.
+        first: String,
+        /// The issue in the second source.
+        second_issue: String,
+        /// The second source.
+        second: String,
+    },
+}
+
+/// Errors that prevent models from being merged.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum MergeError {
+    /// No models were given.
+    #[error("No models to merge")]
+    NoModels,
+
+    /// The merged model could not be written as YAML.
+    #[error("Failed to write merged model: {0}")]
+    Serialization(#[from] serde_yaml::Error),
+
+    /// The models define some elements incompatibly.
+    #[error("{}", ConflictReport(.0))]
+    Conflicts(Vec<MergeConflict>),
+}
+
+/// Formats conflicts as a report, one per line, followed by a count.
+struct ConflictReport<'a>(&'a [MergeConflict]);
+
+impl fmt::Display for ConflictReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for conflict in self.0 {
+            writeln!(f, "Conflict: {conflict}")?;
+        }
+        let plural = if self.0.len() == 1 { "" } else { "s" };
+        write!(f, "{} conflict{plural}", self.0.len())
+    }
+}
+
+/// Merges models into one.
+///
+/// The workflow name and header, version, layout, entity sizing, layout grid,
+/// swimlane order, connection markers, handling of duplicate connections, and
+/// Jira site come from the first model.
+/// Identical definitions are kept once, slices with the same name are
+/// combined, every model's roles, banned words, envelope fields, and lint
+/// plugins are kept, and swimlanes keep the order in which they first appear.
+/// All conflicts are collected before failing, so a single run reports every
+/// one of them.
+pub fn merge_models(models: &[SourcedModel]) -> Result<YamlEventModel, MergeError> {
+    let (first, rest) = models.split_first().ok_or(MergeError::NoModels)?;
+
+    let mut merger = Merger {
+        model: first.model.clone(),
+        origins: HashMap::new(),
+        lanes: Vec::new(),
+        groups: Vec::new(),
+        slice_attributes: HashMap::new(),
+        conflicts: Vec::new(),
+    };
+    merger.model.fragments = None;
+    merger.model.source_map = Default::default();
+    merger.model.slices = Vec::new();
+    merger.record_origins(first);
+
+    for sourced in models {
+        merger.merge_swimlanes(sourced);
+        merger.merge_slices(sourced);
+    }
+    for sourced in rest {
+        merger.merge_definitions(sourced);
+    }
+
+    if !merger.conflicts.is_empty() {
+        return Err(MergeError::Conflicts(merger.conflicts));
+    }
+    merger.model.swimlanes = merger.merged_swimlanes();
+    Ok(merger.model)
+}
+
+/// State accumulated while merging.
+struct Merger {
+    model: YamlEventModel,
+    /// Name to its kind and the source that defined it. Entities share one
+    /// namespace; profiles and templates are keyed as `profile:<name>` and
+    /// `template:<name>`.
+    origins: HashMap<String, (&'static str, String)>,
+    /// Unified swimlanes, in order of first appearance.
+    lanes: Vec<MergedLane>,
+    /// Unified swimlane groups, in order of first appearance.
+    groups: Vec<MergedGroup>,
+    /// Slice name to the attributes the slice was given.
+    slice_attributes: HashMap<String, MergedSlice>,
+    conflicts: Vec<MergeConflict>,
+}
+
+/// An attribute value paired with the source that gave it.
+type Attributed<T = String> = (T, String);
+
+/// A swimlane with the label, owner, style, role, and group it was given.
+struct MergedLane {
+    id: String,
+    label: Option<Attributed>,
+    owner: Option<Attributed>,
+    color: Option<Attributed>,
+    icon: Option<Attributed>,
+    role: Option<Attributed<YamlSwimlaneRole>>,
+    group: Option<Attributed>,
+}
+
+/// The context, estimate, status, and issue a slice was given, as written.
+#[derive(Default)]
+struct MergedSlice {
+    context: Option<Attributed>,
+    estimate: Option<Attributed>,
+    status: Option<Attributed>,
+    issue: Option<Attributed>,
+}
+
+/// A swimlane group with the attributes it was given.
+struct MergedGroup {
+    name: String,
+    collapsed: bool,
+    background: Option<Attributed>,
+}
+
+impl Merger {
+    /// Records which source defined each entity of the first model.
+    fn record_origins(&mut self, sourced: &SourcedModel) {
+        let model = &sourced.model;
+        let entities = model
+            .events
+            .keys()
+            .map(|name| (name, "event"))
+            .chain(model.commands.keys().map(|name| (name, "command")))
+            .chain(model.views.keys().map(|name| (name, "view")))
+            .chain(model.projections.keys().map(|name| (name, "projection")))
+            .chain(model.queries.keys().map(|name| (name, "query")))
+            .chain(model.automations.keys().map(|name| (name, "automation")));
+        for (name, kind) in entities {
+            self.origins
+                .insert(name.clone(), (kind, sourced.source.clone()));
+        }
+        for name in model.profiles.keys() {
+            self.origins.insert(
+                format!("profile:{name}"),
+                ("profile", sourced.source.clone()),
+            );
+        }
+        for name in model.templates.keys() {
+            self.origins.insert(
+                format!("template:{name}"),
+                ("template", sourced.source.clone()),
+            );
+        }
+        for name in model.types.keys() {
+            self.origins
+                .insert(format!("type:{name}"), ("type", sourced.source.clone()));
+        }
+        for name in model.field_sets.keys() {
+            self.origins.insert(
+                format!("field set:{name}"),
+                ("field set", sourced.source.clone()),
+            );
+        }
+        for name in model.extensions.keys() {
+            self.origins.insert(
+                format!("extension:{name}"),
+                ("extension", sourced.source.clone()),
+            );
+        }
+    }
+
+    /// Adds the entities, types, field sets, extensions, profiles, templates, expansions,
+    /// roles, banned words, envelope conventions, and lint plugins of another
+    /// model.
+    fn merge_definitions(&mut self, sourced: &SourcedModel) {
+        let source = &sourced.source;
+        let model = &sourced.model;
+        merge_entities(self, "event", &model.events, source, |m| &mut m.events);
+        merge_entities(self, "command", &model.commands, source, |m| {
+            &mut m.commands
+        });
+        merge_entities(self, "view", &model.views, source, |m| &mut m.views);
+        merge_entities(self, "projection", &model.projections, source, |m| {
+            &mut m.projections
+        });
+        merge_entities(self, "query", &model.queries, source, |m| &mut m.queries);
+        merge_entities(self, "automation", &model.automations, source, |m| {
+            &mut m.automations
+        });
+
+        merge_named(self, "type", &model.types, source, |m| &mut m.types);
+        merge_named(self, "field set", &model.field_sets, source, |m| {
+            &mut m.field_sets
+        });
+        merge_named(self, "extension", &model.extensions, source, |m| {
+            &mut m.extensions
+        });
+        merge_named(self, "profile", &model.profiles, source, |m| {
+            &mut m.profiles
+        });
+        merge_named(self, "template", &model.templates, source, |m| {
+            &mut m.templates
+        });
+        for expansion in &model.expand {
+            let duplicate = self.model.expand.iter().any(|existing| {
+                existing.template == expansion.template && existing.for_each == expansion.for_each
+            });
+            if !duplicate {
+                self.model.expand.push(expansion.clone());
+            }
+        }
+        for role in &model.roles {
+            if !self.model.roles.contains(role) {
+                self.model.roles.push(role.clone());
+            }
+        }
+        for word in &model.terminology.banned_words {
+            if !self.model.terminology.banned_words.contains(word) {
+                self.model.terminology.banned_words.push(word.clone());
+            }
+        }
+        for field in &model.conventions.envelope {
+            if !self.model.conventions.envelope.contains(field) {
+                self.model.conventions.envelope.push(field.clone());
+            }
+        }
+        for field in &model.conventions.propagate {
+            if !self.model.conventions.propagate.contains(field) {
+                self.model.conventions.propagate.push(field.clone());
+            }
+        }
+        for plugin in &model.lint.plugins {
+            if !self.model.lint.plugins.contains(plugin) {
+                self.model.lint.plugins.push(plugin.clone());
+            }
+        }
+    }
+
+    /// Unifies the swimlanes of a model with those merged so far.
+    fn merge_swimlanes(&mut self, sourced: &SourcedModel) {
+        let source = &sourced.source;
+        self.merge_swimlane_groups(&sourced.model.swimlanes, source);
+        for entry in swimlane_entries(&sourced.model.swimlanes) {
+            let label = entry.label.map(|label| (label, source.clone()));
+            let owner = entry.owner.map(|owner| (owner, source.clone()));
+            let color = entry.color.map(|color| (color, source.clone()));
+            let icon = entry.icon.map(|icon| (icon, source.clone()));
+            let role = entry.role.map(|role| (role, source.clone()));
+            let group = entry.group.map(|group| (group, source.clone()));
+            let Some(lane) = self.lanes.iter_mut().find(|lane| lane.id == entry.id) else {
+                self.lanes.push(MergedLane {
+                    id: entry.id,
+                    label,
+                    owner,
+                    color,
+                    icon,
+                    role,
+                    group,
+                });
+                continue;
+            };
+            for (attribute, slot, value) in [
+                ("color", &mut lane.color, color),
+                ("icon", &mut lane.icon, icon),
+            ] {
+                if let Some(((first_value, first), (second_value, second))) = unify(slot, value) {
+                    self.conflicts.push(MergeConflict::SwimlaneAttribute {
+                        id: entry.id.clone(),
+                        attribute,
+                        first_value,
+                        first,
+                        second_value,
+                        second,
+                    });
+                }
+            }
+            if let Some(((first_role, first), (second_role, second))) = unify(&mut lane.role, role)
+            {
+                self.conflicts.push(MergeConflict::SwimlaneAttribute {
+                    id: entry.id.clone(),
+                    attribute: "role",
+                    first_value: first_role.to_string(),
+                    first,
+                    second_value: second_role.to_string(),
+                    second,
+                });
+            }
+            if let Some(((first_group, first), (second_group, second))) =
+                unify(&mut lane.group, group)
+            {
+                self.conflicts.push(MergeConflict::SwimlaneGroup {
+                    id: entry.id.clone(),
+                    first_group,
+                    first,
+                    second_group,
+                    second,
+                });
+            }
+            if let Some(((first_label, first), (second_label, second))) =
+                unify(&mut lane.label, label)
+            {
+                self.conflicts.push(MergeConflict::SwimlaneLabel {
+                    id: entry.id.clone(),
+                    first_label,
+                    first,
+                    second_label,
+                    second,
+                });
+            }
+            if let Some(((first_owner, first), (second_owner, second))) =
+                unify(&mut lane.owner, owner)
+            {
+                self.conflicts.push(MergeConflict::SwimlaneOwner {
+                    id: entry.id,
+                    first_owner,
+                    first,
+                    second_owner,
+                    second,
+                });
+            }
+        }
+    }
+
+    /// Unifies the attributes of a model's swimlane groups with those merged
+    /// so far.
+    fn merge_swimlane_groups(&mut self, swimlanes: &[YamlSwimlane], source: &str) {
+        for lane in swimlanes {
+            let YamlSwimlane::Group(group) = lane else {
+                continue;
+            };
+            let background = group
+                .background
+                .clone()
+                .map(|background| (background, source.to_string()));
+            let Some(merged) = self.groups.iter_mut().find(|g| g.name == group.group) else {
+                self.groups.push(MergedGroup {
+                    name: group.group.clone(),
+                    collapsed: group.collapsed,
+                    background,
+                });
+                continue;
+            };
+            merged.collapsed |= group.collapsed;
+            if let Some(((first_background, first), (second_background, second))) =
+                unify(&mut merged.background, background)
+            {
+                self.conflicts.push(MergeConflict::SwimlaneGroupBackground {
+                    group: group.group.clone(),
+                    first_background,
+                    first,
+                    second_background,
+                    second,
+                });
+            }
+        }
+    }
+
+    /// Writes the unified swimlanes back out, gathering each group's lanes
+    /// where its first lane appeared.
+    fn merged_swimlanes(&self) -> Vec<YamlSwimlane> {
+        let mut swimlanes = Vec::new();
+        let mut written: Vec<&str> = Vec::new();
+        for lane in &self.lanes {
+            let Some((name, _)) = &lane.group else {
+                swimlanes.push(merged_swimlane(lane));
+                continue;
+            };
+            if written.contains(&name.as_str()) {
+                continue;
+            }
+            written.push(name);
+            let group = self.groups.iter().find(|group| &group.name == name);
+            swimlanes.push(YamlSwimlane::Group(YamlSwimlaneGroup {
+                group: name.clone(),
+                swimlanes: self
+                    .lanes
+                    .iter()
+                    .filter(|member| member.group.as_ref().is_some_and(|(g, _)| g == name))
+                    .map(merged_swimlane)
+                    .collect(),
+                collapsed: group.is_some_and(|group| group.collapsed),
+                background: group
+                    .and_then(|group| group.background.as_ref())
+                    .map(|(background, _)| background.clone()),
+            }));
+        }
+        swimlanes
+    }
+
+    /// Adds slices, combining the connections of slices with the same name
+    /// and unifying their contexts, estimates, statuses, and issues.
+    fn merge_slices(&mut self, sourced: &SourcedModel) {
+        for slice in &sourced.model.slices {
+            let merged = self.slice_attributes.entry(slice.name.clone()).or_default();
+            let attributed = |value: String| (value, sourced.source.clone());
+            if let Some(((first_context, first), (second_context, second))) =
+                unify(&mut merged.context, slice.context.clone().map(attributed))
+            {
+                self.conflicts.push(MergeConflict::SliceContext {
+                    slice: slice.name.clone(),
+                    first_context,
+                    first,
+                    second_context,
+                    second,
+                });
+            }
+            if let Some(((first_estimate, first), (second_estimate, second))) = unify(
+                &mut merged.estimate,
+                slice
+                    .estimate
+                    .as_ref()
+                    .map(|estimate| attributed(estimate.text())),
+            ) {
+                self.conflicts.push(MergeConflict::SliceEstimate {
+                    slice: slice.name.clone(),
+                    first_estimate,
+                    first,
+                    second_estimate,
+                    second,
+                });
+            }
+            if let Some(((first_status, first), (second_status, second))) = unify(
+                &mut merged.status,
+                slice
+                    .status
+                    .map(|status| attributed(status.as_str().to_string())),
+            ) {
+                self.conflicts.push(MergeConflict::SliceStatus {
+                    slice: slice.name.clone(),
+                    first_status,
+                    first,
+                    second_status,
+                    second,
+                });
+            }
+            if let Some(((first_issue, first), (second_issue, second))) =
+                unify(&mut merged.issue, slice.issue.clone().map(attributed))
+            {
+                self.conflicts.push(MergeConflict::SliceIssue {
+                    slice: slice.name.clone(),
+                    first_issue,
+                    first,
+                    second_issue,
+                    second,
+                });
+            }
+            match self
+                .model
+                .slices
+                .iter_mut()
+                .find(|existing| existing.name == slice.name)
+            {
+                Some(existing) => {
+                    if existing.context.is_none() {
+                        existing.context = slice.context.clone();
+                    }
+                    if existing.estimate.is_none() {
+                        existing.estimate = slice.estimate.clone();
+                    }
+                    if existing.status.is_none() {
+                        existing.status = slice.status;
+                    }
+                    if existing.issue.is_none() {
+                        existing.issue = slice.issue.clone();
+                    }
+                    for connection in &slice.connections {
+                        let text = normalize_connection(connection.text());
+                        let duplicate = existing
+                            .connections
+                            .iter()
+                            .any(|known| normalize_connection(known.text()) == text);
+                        if !duplicate {
+                            existing.connections.push(connection.clone());
+                        }
+                    }
+                }
+                None => self.model.slices.push(slice.clone()),
+            }
+        }
+    }
+}
+
+/// Adds the entities of one type from another model, reporting conflicts.
+fn merge_entities<T: Clone + Serialize>(
+    merger: &mut Merger,
+    kind: &'static str,
+    entities: &HashMap<String, T>,
+    source: &str,
+    target: impl Fn(&mut YamlEventModel) -> &mut HashMap<String, T>,
+) {
+    let mut names: Vec<&String> = entities.keys().collect();
+    names.sort();
+    for name in names {
+        let definition = &entities[name];
+        match merger.origins.get(name).cloned() {
+            None => {
+                merger
+                    .origins
+                    .insert(name.clone(), (kind, source.to_string()));
+                target(&mut merger.model).insert(name.clone(), definition.clone());
+            }
+            Some((first_kind, first)) if first_kind != kind => {
+                merger.conflicts.push(MergeConflict::DifferentEntityTypes {
+                    name: name.clone(),
+                    first_kind,
+                    first,
+                    second_kind: kind,
+                    second: source.to_string(),
+                });
+            }
+            Some((_, first)) => {
+                if !same_definition(&target(&mut merger.model)[name], definition) {
+                    merger.conflicts.push(MergeConflict::DifferentDefinitions {
+                        kind,
+                        name: name.clone(),
+                        first,
+                        second: source.to_string(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Adds named definitions such as profiles, reporting differing duplicates.
+fn merge_named<T: Clone + Serialize>(
+    merger: &mut Merger,
+    kind: &'static str,
+    additions: &HashMap<String, T>,
+    source: &str,
+    target: impl Fn(&mut YamlEventModel) -> &mut HashMap<String, T>,
+) {
+    let mut names: Vec<&String> = additions.keys().collect();
+    names.sort();
+    for name in names {
+        let definition = &additions[name];
+        let key = format!("{kind}:{name}");
+        match merger.origins.get(&key).cloned() {
+            None => {
+                merger.origins.insert(key, (kind, source.to_string()));
+                target(&mut merger.model).insert(name.clone(), definition.clone());
+            }
+            Some((_, first)) => {
+                if !same_definition(&target(&mut merger.model)[name], definition) {
+                    merger.conflicts.push(MergeConflict::DifferentDefinitions {
+                        kind,
+                        name: name.clone(),
+                        first,
+                        second: source.to_string(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Compares two definitions by content, ignoring map ordering.
+fn same_definition<T: Serialize>(a: &T, b: &T) -> bool {
+    match (serde_yaml::to_value(a), serde_yaml::to_value(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Writes a merged swimlane in the simplest format holding its attributes.
+fn merged_swimlane(lane: &MergedLane) -> YamlSwimlane {
+    let value = |attribute: &Option<Attributed>| attribute.as_ref().map(|(v, _)| v.clone());
+    let detailed =
+        lane.owner.is_some() || lane.color.is_some() || lane.icon.is_some() || lane.role.is_some();
+    match &lane.label {
+        label if detailed => YamlSwimlane::Detailed(HashMap::from([(
+            lane.id.clone(),
+            YamlSwimlaneDetails {
+                name: value(label).unwrap_or_else(|| lane.id.clone()),
+                owner: value(&lane.owner),
+                color: value(&lane.color),
+                icon: value(&lane.icon),
+                role: lane.role.as_ref().map(|(role, _)| *role),
+            },
+        )])),
+        Some((label, _)) => YamlSwimlane::Map(HashMap::from([(lane.id.clone(), label.clone())])),
+        None => YamlSwimlane::Simple(lane.id.clone()),
+    }
+}
+
+/// A swimlane's identifier and the attributes written for it.
+#[derive(Debug, PartialEq, Eq)]
+struct LaneEntry {
+    id: String,
+    label: Option<String>,
+    owner: Option<String>,
+    color: Option<String>,
+    icon: Option<String>,
+    role: Option<YamlSwimlaneRole>,
+    group: Option<String>,
+}
+
+/// Returns each swimlane's identifier, explicit label, owner, style, role,
+/// and group.
+fn swimlane_entries(swimlanes: &[YamlSwimlane]) -> Vec<LaneEntry> {
+    let mut entries = Vec::new();
+    for lane in swimlanes {
+        match lane {
+            YamlSwimlane::Simple(id) => entries.push(LaneEntry {
+                id: id.clone(),
+                label: None,
+                owner: None,
+                color: None,
+                icon: None,
+                role: None,
+                group: None,
+            }),
+            YamlSwimlane::Map(map) => {
+                let mut labeled: Vec<_> = map.iter().collect();
+                labeled.sort();
+                entries.extend(labeled.into_iter().map(|(id, label)| LaneEntry {
+                    id: id.clone(),
+                    label: Some(label.clone()),
+                    owner: None,
+                    color: None,
+                    icon: None,
+                    role: None,
+                    group: None,
+                }));
+            }
+            YamlSwimlane::Detailed(map) => {
+                let mut detailed: Vec<_> = map.iter().collect();
+                detailed.sort_by(|(a, _), (b, _)| a.cmp(b));
+                entries.extend(detailed.into_iter().map(|(id, details)| LaneEntry {
+                    id: id.clone(),
+                    label: Some(details.name.clone()),
+                    owner: details.owner.clone(),
+                    color: details.color.clone(),
+                    icon: details.icon.clone(),
+                    role: details.role,
+                    group: None,
+                }));
+            }
+            YamlSwimlane::Group(group) => {
+                entries.extend(swimlane_entries(&group.swimlanes).into_iter().map(|entry| {
+                    LaneEntry {
+                        group: Some(group.group.clone()),
+                        ..entry
+                    }
+                }));
+            }
+        }
+    }
+    entries
+}
+
+/// Fills an unset attribute from `incoming`, returning both values with their
+/// sources when they disagree.
+fn unify<T: Clone + PartialEq>(
+    slot: &mut Option<Attributed<T>>,
+    incoming: Option<Attributed<T>>,
+) -> Option<(Attributed<T>, Attributed<T>)> {
+    match (slot.as_ref(), incoming) {
+        (None, incoming) => {
+            *slot = incoming;
+            None
+        }
+        (Some(existing), Some(incoming)) if existing.0 != incoming.0 => {
+            Some((existing.clone(), incoming))
+        }
+        _ => None,
+    }
+}
+
+/// Writes a model as YAML with a stable order, so merging the same inputs
+/// always produces the same file.
+///
+/// Sections keep the order of the model format, entities are listed by name,
+/// and empty or unset optional values are left out.
+pub fn to_yaml(model: &YamlEventModel) -> Result<String, MergeError> {
+    let Value::Mapping(sections) = serde_yaml::to_value(model)? else {
+        return Ok(serde_yaml::to_string(model)?);
+    };
+
+    let mut tidied = Mapping::new();
+    for (key, value) in sections {
+        let named = key
+            .as_str()
+            .is_some_and(|key| NAMED_SECTIONS.contains(&key));
+        let value = match (named, value) {
+            (true, Value::Mapping(entries)) => {
+                let mut entries: Vec<_> = entries.into_iter().collect();
+                entries.sort_by(|(a, _), (b, _)| a.as_str().cmp(&b.as_str()));
+                Value::Mapping(
+                    entries
+                        .into_iter()
+                        .filter_map(|(name, definition)| Some((name, tidy(definition, false)?)))
+                        .collect(),
+                )
+            }
+            (_, value) => value,
+        };
+        if let Some(value) = tidy(value, false) {
+            tidied.insert(key, value);
+        }
+    }
+    Ok(serde_yaml::to_string(&tidied)?)
+}
+
+/// Drops null and empty values. Map keys below the first level of a
+/// definition are also sorted, since they come from unordered maps. Sequence
+/// items keep their order.
+fn tidy(value: Value, sort: bool) -> Option<Value> {
+    match value {
+        Value::Null => None,
+        Value::Sequence(items) if items.is_empty() => None,
+        Value::Mapping(entries) if entries.is_empty() => None,
+        Value::Sequence(items) => Some(Value::Sequence(
+            items
+                .into_iter()
+                .filter_map(|item| tidy(item, sort))
+                .collect(),
+        )),
+        Value::Mapping(entries) => {
+            let mut entries: Vec<_> = entries
+                .into_iter()
+                .filter_map(|(key, item)| Some((key, tidy(item, true)?)))
+                .collect();
+            if sort {
+                entries.sort_by(|(a, _), (b, _)| a.as_str().cmp(&b.as_str()));
+            }
+            Some(Value::Mapping(entries.into_iter().collect()))
+        }
+        other => Some(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::yaml_parser::parse_yaml;
+
+    const TEAM_A: &str = r#"workflow: Orders
+swimlanes:
+  - backend
+  - ui: "Storefront"
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+"#;
+
+    const TEAM_B: &str = r#"workflow: Shipping
+swimlanes:
+  - backend: "Backend"
+  - warehouse: "Warehouse"
+types:
+  OrderId: UUID
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+  OrderShipped:
+    description: "An order was shipped"
+    swimlane: warehouse
+commands:
+  ShipOrder:
+    description: "Ship an order"
+    swimlane: warehouse
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder  ->  OrderPlaced
+      - OrderPlaced -> ShipOrder
+  - name: Shipping
+    connections:
+      - ShipOrder -> OrderShipped
+"#;
+
+    fn sourced(source: &str, yaml: &str) -> SourcedModel {
+        SourcedModel {
+            source: source.to_string(),
+            model: parse_yaml(yaml).unwrap(),
+        }
+    }
+
+    #[test]
+    fn merges_identical_definitions_slices_and_swimlanes() {
+        let merged = merge_models(&[sourced("a.yaml", TEAM_A), sourced("b.yaml", TEAM_B)]).unwrap();
+
+        assert_eq!(merged.workflow.title(), "Orders");
+        assert_eq!(merged.events.len(), 2);
+        assert_eq!(merged.commands.len(), 2);
+        assert_eq!(merged.types.len(), 1);
+        let lanes: Vec<_> = swimlane_entries(&merged.swimlanes)
+            .into_iter()
+            .map(|lane| (lane.id, lane.label))
+            .collect();
+        assert_eq!(
+            lanes,
+            [
+                ("backend".to_string(), Some("Backend".to_string())),
+                ("ui".to_string(), Some("Storefront".to_string())),
+                ("warehouse".to_string(), Some("Warehouse".to_string())),
+            ]
+        );
+        let ordering = &merged.slices[0];
+        assert_eq!(ordering.connections.len(), 2);
+        assert_eq!(merged.slices.len(), 2);
+    }
+
+    #[test]
+    fn reports_every_conflict() {
+        let team_c = r#"workflow: Conflicts
+swimlanes:
+  - backend: "Services"
+types:
+  OrderId: String
+events:
+  OrderPlaced:
+    description: "Order placed"
+    swimlane: backend
+  PlaceOrder:
+    description: "Placed"
+    swimlane: backend
+"#;
+        let error = merge_models(&[
+            sourced("a.yaml", TEAM_A),
+            sourced("b.yaml", TEAM_B),
+            sourced("c.yaml", team_c),
+        ])
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Conflict: swimlane 'backend' is labeled \"Backend\" in b.yaml but \"Services\" in c.yaml\n\
+             Conflict: event 'OrderPlaced' is defined differently in a.yaml and c.yaml\n\
+             Conflict: 'PlaceOrder' is defined as command in a.yaml but as event in c.yaml\n\
+             Conflict: type 'OrderId' is defined differently in b.yaml and c.yaml\n\
+             4 conflicts"
+        );
+    }
+
+    #[test]
+    fn unifies_swimlane_groups() {
+        let grouped = |collapsed: bool, background: &str| {
+            format!(
+                "workflow: Orders\nswimlanes:\n  - group: Backend\n    collapsed: {collapsed}\n    background: \"{background}\"\n    swimlanes:\n      - backend\n"
+            )
+        };
+        let merged = merge_models(&[
+            sourced("a.yaml", TEAM_A),
+            sourced("g.yaml", &grouped(true, "#eef3ff")),
+        ])
+        .unwrap();
+
+        let group = merged.swimlanes.iter().find_map(|lane| match lane {
+            YamlSwimlane::Group(group) => Some(group),
+            _ => None,
+        });
+        let group = group.unwrap();
+        assert_eq!(group.group, "Backend");
+        assert!(group.collapsed);
+        assert_eq!(swimlane_entries(&group.swimlanes).len(), 1);
+        assert_eq!(swimlane_entries(&merged.swimlanes).len(), 2);
+
+        let error = merge_models(&[
+            sourced("g.yaml", &grouped(false, "#eef3ff")),
+            sourced("h.yaml", &grouped(false, "lavender")),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Conflict: swimlane group \"Backend\" has background #eef3ff in g.yaml but lavender in h.yaml\n\
+             1 conflict"
+        );
+    }
+
+    #[test]
+    fn unifies_slice_contexts() {
+        let in_context = |context: &str| {
+            TEAM_A.replace(
+                "  - name: Ordering\n",
+                &format!("  - name: Ordering\n    context: {context}\n"),
+            )
+        };
+        let merged = merge_models(&[
+            sourced("a.yaml", TEAM_A),
+            sourced("s.yaml", &in_context("Sales")),
+        ])
+        .unwrap();
+        assert_eq!(merged.slices[0].context.as_deref(), Some("Sales"));
+
+        let error = merge_models(&[
+            sourced("s.yaml", &in_context("Sales")),
+            sourced("f.yaml", &in_context("Fulfillment")),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Conflict: slice \"Ordering\" is in context \"Sales\" in s.yaml but \"Fulfillment\" in f.yaml\n\
+             1 conflict"
+        );
+    }
+
+    #[test]
+    fn reports_conflicting_slice_estimates() {
+        let estimated = |estimate: &str| {
+            TEAM_A.replace(
+                "  - name: Ordering\n",
+                &format!("  - name: Ordering\n    estimate: {estimate}\n"),
+            )
+        };
+        let error = merge_models(&[
+            sourced("a.yaml", &estimated("5")),
+            sourced("b.yaml", &estimated("L")),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Conflict: slice \"Ordering\" is estimated at \"5\" in a.yaml but \"L\" in b.yaml\n\
+             1 conflict"
+        );
+    }
+
+    #[test]
+    fn written_model_parses_back() {
+        let merged = merge_models(&[sourced("a.yaml", TEAM_A), sourced("b.yaml", TEAM_B)]).unwrap();
+        let yaml = to_yaml(&merged).unwrap();
+
+        assert!(yaml.starts_with("version:"));
+        assert!(!yaml.contains("null"));
+        let reparsed = parse_yaml(&yaml).unwrap();
+        assert_eq!(reparsed.commands.len(), 2);
+        assert_eq!(to_yaml(&reparsed).unwrap(), yaml);
+    }
+}
@@ -22,11 +22,19 @@
 //! This ensures that headers are parsed before bodies and that all required sections
 //! are present before building the final EventModel.
 
+pub mod anchors;
+pub mod anonymize;
 pub mod ast;
+pub mod features;
 pub mod lexer;
+pub mod merge;
+pub mod profiles;
+pub mod rename;
 pub mod simple_lexer;
 pub mod simple_parser;
+pub mod source_map;
 pub mod yaml_converter;
+pub mod yaml_edit;
 pub mod yaml_parser;
 
 use ast::EventModel;
@@ -235,6 +243,7 @@ impl<'a> Parser<'a> {
 
 /// Errors that can occur during parsing.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum ParseError {
     /// An error occurred during lexical analysis.
     #[error("Lexical error: {0}")]
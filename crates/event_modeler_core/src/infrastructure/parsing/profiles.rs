@@ -0,0 +1,361 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Profile overlays that turn one model file into several variants.
+//!
+//! A profile (e.g. `mvp` or `v2`) lists entities, slices, and connections to
+//! remove from the base model and new ones to add. Applying a profile happens
+//! on the parsed YAML, before conversion, so the converted model is validated
+//! exactly as if the variant had been written out by hand.
+
+use super::yaml_parser::{YamlEventModel, YamlProfile, YamlSlice, split_connection};
+use std::collections::HashMap;
+
+/// Errors that can occur while applying a profile.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ProfileError {
+    /// The requested profile is not defined in the model.
+    #[error("Unknown profile '{name}' (available: {available})")]
+    UnknownProfile {
+        /// The requested profile name.
+        name: String,
+        /// Comma-separated names of the profiles the model defines.
+        available: String,
+    },
+
+    /// The profile removes an entity the base model does not define.
+    #[error("Profile '{profile}' removes unknown entity '{entity}'")]
+    UnknownEntity {
+        /// The profile being applied.
+        profile: String,
+        /// The entity name that was not found.
+        entity: String,
+    },
+
+    /// The profile removes a slice the base model does not define.
+    #[error("Profile '{profile}' removes unknown slice '{slice}'")]
+    UnknownSlice {
+        /// The profile being applied.
+        profile: String,
+        /// The slice name that was not found.
+        slice: String,
+    },
+
+    /// The profile removes a connection no slice contains.
+    #[error("Profile '{profile}' removes unknown connection '{connection}'")]
+    UnknownConnection {
+        /// The profile being applied.
+        profile: String,
+        /// The connection that was not found.
+        connection: String,
+    },
+
+    /// The profile adds an entity the base model already defines.
+    #[error("Profile '{profile}' adds '{entity}', which is already defined")]
+    DuplicateEntity {
+        /// The profile being applied.
+        profile: String,
+        /// The entity name defined twice.
+        entity: String,
+    },
+}
+
+/// Applies the named profile to `model`: its removals first, then its additions.
+///
+/// Slices left without connections after the removals are dropped.
+pub fn apply_profile(model: &mut YamlEventModel, name: &str) -> Result<(), ProfileError> {
+    let profile = match model.profiles.get(name) {
+        Some(profile) => profile.clone(),
+        None => {
+            let mut available: Vec<&str> = model.profiles.keys().map(String::as_str).collect();
+            available.sort_unstable();
+            return Err(ProfileError::UnknownProfile {
+                name: name.to_string(),
+                available: if available.is_empty() {
+                    "none".to_string()
+                } else {
+                    available.join(", ")
+                },
+            });
+        }
+    };
+
+    apply_removals(model, name, &profile)?;
+    apply_additions(model, name, profile)
+}
+
+/// Removes the profile's entities, slices, and connections from the model.
+fn apply_removals(
+    model: &mut YamlEventModel,
+    profile_name: &str,
+    profile: &YamlProfile,
+) -> Result<(), ProfileError> {
+    for entity in &profile.remove.entities {
+        let removed = model.events.remove(entity).is_some()
+            | model.commands.remove(entity).is_some()
+            | model.views.remove(entity).is_some()
+            | model.projections.remove(entity).is_some()
+            | model.queries.remove(entity).is_some()
+            | model.automations.remove(entity).is_some();
+        if !removed {
+            return Err(ProfileError::UnknownEntity {
+                profile: profile_name.to_string(),
+                entity: entity.clone(),
+            });
+        }
+        for slice in &mut model.slices {
+            slice.connections.retain(|connection| {
+                !connection_endpoints(connection.text())
+                    .is_some_and(|(from, to)| from == entity || to == entity)
+            });
+        }
+    }
+
+    for slice_name in &profile.remove.slices {
+        let before = model.slices.len();
+        model.slices.retain(|slice| &slice.name != slice_name);
+        if model.slices.len() == before {
+            return Err(ProfileError::UnknownSlice {
+                profile: profile_name.to_string(),
+                slice: slice_name.clone(),
+            });
+        }
+    }
+
+    for connection in &profile.remove.connections {
+        let target = normalize_connection(connection);
+        let mut found = false;
+        for slice in &mut model.slices {
+            let before = slice.connections.len();
+            slice
+                .connections
+                .retain(|existing| normalize_connection(existing.text()) != target);
+            found |= slice.connections.len() != before;
+        }
+        if !found {
+            return Err(ProfileError::UnknownConnection {
+                profile: profile_name.to_string(),
+                connection: connection.clone(),
+            });
+        }
+    }
+
+    model.slices.retain(|slice| !slice.connections.is_empty());
+    Ok(())
+}
+
+/// Adds the profile's entities and slices to the model.
+fn apply_additions(
+    model: &mut YamlEventModel,
+    profile_name: &str,
+    profile: YamlProfile,
+) -> Result<(), ProfileError> {
+    let additions = profile.add;
+    let mut defined: Vec<String> = model
+        .events
+        .keys()
+        .chain(model.commands.keys())
+        .chain(model.views.keys())
+        .chain(model.projections.keys())
+        .chain(model.queries.keys())
+        .chain(model.automations.keys())
+        .cloned()
+        .collect();
+
+    insert_new(
+        &mut model.events,
+        additions.events,
+        &mut defined,
+        profile_name,
+    )?;
+    insert_new(
+        &mut model.commands,
+        additions.commands,
+        &mut defined,
+        profile_name,
+    )?;
+    insert_new(
+        &mut model.views,
+        additions.views,
+        &mut defined,
+        profile_name,
+    )?;
+    insert_new(
+        &mut model.projections,
+        additions.projections,
+        &mut defined,
+        profile_name,
+    )?;
+    insert_new(
+        &mut model.queries,
+        additions.queries,
+        &mut defined,
+        profile_name,
+    )?;
+    insert_new(
+        &mut model.automations,
+        additions.automations,
+        &mut defined,
+        profile_name,
+    )?;
+
+    for added in additions.slices {
+        match model
+            .slices
+            .iter_mut()
+            .find(|slice| slice.name == added.name)
+        {
+            Some(existing) => {
+                existing.connections.extend(added.connections);
+                // A profile reordering a slice replaces its order outright
+                if !added.order.is_empty() {
+                    existing.order = added.order;
+                }
+            }
+            None => model.slices.push(YamlSlice {
+                name: added.name,
+                context: added.context,
+                estimate: added.estimate,
+                status: added.status,
+                issue: added.issue,
+                order: added.order,
+                swimlanes: added.swimlanes,
+                connections: added.connections,
+            }),
+        }
+    }
+
+    Ok(())
+}
+
+/// Inserts added definitions, rejecting names already defined anywhere in the model.
+fn insert_new<T>(
+    target: &mut HashMap<String, T>,
+    additions: HashMap<String, T>,
+    defined: &mut Vec<String>,
+    profile_name: &str,
+) -> Result<(), ProfileError> {
+    for (name, definition) in additions {
+        if defined.contains(&name) {
+            return Err(ProfileError::DuplicateEntity {
+                profile: profile_name.to_string(),
+                entity: name,
+            });
+        }
+        defined.push(name.clone());
+        target.insert(name, definition);
+    }
+    Ok(())
+}
+
+/// Splits a connection into the entity names at either end, ignoring any
+/// view component path (`LoginScreen.Form.Submit` refers to `LoginScreen`).
+fn connection_endpoints(connection: &str) -> Option<(&str, &str)> {
+    let (from, _, to) = split_connection(connection)?;
+    Some((entity_name(from), entity_name(to)))
+}
+
+/// Returns the entity a connection endpoint refers to.
+fn entity_name(reference: &str) -> &str {
+    let reference = reference.trim();
+    reference.split('.').next().unwrap_or(reference)
+}
+
+/// Normalizes the whitespace around a connection's arrow for comparison.
+pub(super) fn normalize_connection(connection: &str) -> String {
+    match split_connection(connection) {
+        Some((from, arrow, to)) => format!("{from} {arrow} {to}"),
+        None => connection.trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::yaml_parser::parse_yaml;
+
+    const MODEL: &str = r#"workflow: Orders
+swimlanes:
+  - backend: "Backend"
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+  ShipOrder:
+    description: "Ship an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+  - name: Shipping
+    connections:
+      - OrderPlaced -> ShipOrder
+profiles:
+  mvp:
+    remove:
+      entities: [ShipOrder]
+  v2:
+    add:
+      events:
+        OrderShipped:
+          description: "An order was shipped"
+          swimlane: backend
+      slices:
+        - name: Shipping
+          connections:
+            - ShipOrder -> OrderShipped
+"#;
+
+    #[test]
+    fn removing_an_entity_drops_its_connections_and_empty_slices() {
+        let mut model = parse_yaml(MODEL).unwrap();
+        apply_profile(&mut model, "mvp").unwrap();
+
+        assert!(!model.commands.contains_key("ShipOrder"));
+        let slice_names: Vec<_> = model.slices.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(slice_names, ["Ordering"]);
+    }
+
+    #[test]
+    fn added_slices_extend_existing_slices_of_the_same_name() {
+        let mut model = parse_yaml(MODEL).unwrap();
+        apply_profile(&mut model, "v2").unwrap();
+
+        assert!(model.events.contains_key("OrderShipped"));
+        let shipping = model
+            .slices
+            .iter()
+            .find(|slice| slice.name == "Shipping")
+            .unwrap();
+        assert_eq!(shipping.connections.len(), 2);
+    }
+
+    #[test]
+    fn connections_running_both_ways_keep_their_arrow() {
+        assert_eq!(
+            normalize_connection(" Screen.Form<->PlaceOrder "),
+            "Screen.Form <-> PlaceOrder"
+        );
+        assert_eq!(
+            connection_endpoints("Screen.Form <-> PlaceOrder"),
+            Some(("Screen", "PlaceOrder"))
+        );
+    }
+
+    #[test]
+    fn unknown_profiles_list_the_available_ones() {
+        let mut model = parse_yaml(MODEL).unwrap();
+        let error = apply_profile(&mut model, "beta").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Unknown profile 'beta' (available: mvp, v2)"
+        );
+    }
+}
@@ -0,0 +1,363 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Renaming an entity throughout a model's source.
+//!
+//! The entity's key is renamed along with every reference to it: the
+//! endpoints of slice and profile connections, `Entity.Component` endpoints
+//! included, the items of a slice's `order`, the entities named by layout
+//! constraints and removed by profiles, and the steps of test scenarios.
+//! Values anywhere else, such as titles, descriptions, types, and the slices
+//! of waypoints, are left alone even when they equal the name. The source is edited
+//! through a [`YamlDocument`], so comments and formatting survive the rename
+//! and the diff shows only the names that changed.
+
+use super::source_map::PathSegment;
+use super::yaml_edit::{YamlDocument, YamlEditError, YamlScalar};
+use super::yaml_parser::split_connection;
+
+/// Top-level keys whose entries are entities, in models, profiles, and templates.
+const ENTITY_SECTIONS: [&str; 6] = [
+    "events",
+    "commands",
+    "views",
+    "projections",
+    "queries",
+    "automations",
+];
+
+/// Keys of a test scenario whose items are keyed by entity name.
+const SCENARIO_STEPS: [&str; 3] = ["Given", "When", "Then"];
+
+/// Keys whose items refer to entities by name or connect them.
+const REFERENCE_LISTS: [&str; 3] = ["connections", "order", "align"];
+
+/// Keys whose items refer to entities by name, under the key they belong to.
+const NESTED_REFERENCE_LISTS: [(&str, &str); 2] = [("gap", "between"), ("remove", "entities")];
+
+/// Errors that prevent an entity from being renamed.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum RenameError {
+    /// No entity has the name to rename.
+    #[error("No entity named '{0}'")]
+    UnknownEntity(String),
+
+    /// An entity already has the new name.
+    #[error("An entity named '{0}' already exists")]
+    NameTaken(String),
+
+    /// The new name could not be told apart from the rest of a reference.
+    #[error("'{0}' cannot name an entity: names must not be empty or contain '.', ',' or '->'")]
+    InvalidName(String),
+
+    /// The source could not be edited.
+    #[error("{0}")]
+    Edit(#[from] YamlEditError),
+}
+
+/// A renamed model's source and how many names changed in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Renamed {
+    /// The model's source with the entity renamed.
+    pub source: String,
+    /// Number of scalars changed: the entity's key and its references.
+    pub replacements: usize,
+}
+
+/// Renames the entity `from` to `to` throughout `source`.
+pub fn rename_entity(source: &str, from: &str, to: &str) -> Result<Renamed, RenameError> {
+    if to.trim().is_empty() || to.contains(['.', ',']) || to.contains("->") {
+        return Err(RenameError::InvalidName(to.to_string()));
+    }
+    let mut document = YamlDocument::parse(source)?;
+    if document.scalars().iter().any(|scalar| declares(scalar, to)) {
+        return Err(RenameError::NameTaken(to.to_string()));
+    }
+    if !document
+        .scalars()
+        .iter()
+        .any(|scalar| declares(scalar, from))
+    {
+        return Err(RenameError::UnknownEntity(from.to_string()));
+    }
+
+    let replacements = document.replace_where(|scalar| {
+        if scalar.is_key {
+            (declares(scalar, from) || steps_into(scalar, from)).then(|| to.to_string())
+        } else if refers(scalar) {
+            rename_reference(&scalar.value, from, to)
+        } else {
+            None
+        }
+    })?;
+    Ok(Renamed {
+        source: document.to_string(),
+        replacements,
+    })
+}
+
+/// Returns whether `scalar` is the key declaring the entity `name`.
+fn declares(scalar: &YamlScalar, name: &str) -> bool {
+    let segments = scalar.path.segments();
+    scalar.is_key
+        && scalar.value == name
+        && segments.len() >= 2
+        && matches!(
+            &segments[segments.len() - 2],
+            PathSegment::Key(section) if ENTITY_SECTIONS.contains(&section.as_str())
+        )
+}
+
+/// Returns whether `scalar` is the key of a scenario step on the entity `name`.
+fn steps_into(scalar: &YamlScalar, name: &str) -> bool {
+    let segments = scalar.path.segments();
+    scalar.is_key
+        && scalar.value == name
+        && segments.len() >= 3
+        && matches!(
+            &segments[segments.len() - 3..segments.len() - 1],
+            [PathSegment::Key(step), PathSegment::Index(_)] if SCENARIO_STEPS.contains(&step.as_str())
+        )
+}
+
+/// Returns whether the value `scalar` sits where a model refers to entities:
+/// an item of a reference list, or the `connection` of a connection map.
+fn refers(scalar: &YamlScalar) -> bool {
+    match scalar.path.segments() {
+        [
+            ..,
+            PathSegment::Key(parent),
+            PathSegment::Key(list),
+            PathSegment::Index(_),
+        ] if NESTED_REFERENCE_LISTS.contains(&(parent.as_str(), list.as_str())) => true,
+        [.., PathSegment::Key(list), PathSegment::Index(_)] => {
+            REFERENCE_LISTS.contains(&list.as_str())
+        }
+        [
+            ..,
+            PathSegment::Key(list),
+            PathSegment::Index(_),
+            PathSegment::Key(key),
+        ] => list == "connections" && key == "connection",
+        _ => false,
+    }
+}
+
+/// Returns `value` with references to `from` renamed, if it has any.
+fn rename_reference(value: &str, from: &str, to: &str) -> Option<String> {
+    if let Some(renamed) = rename_endpoint(value, from, to) {
+        return Some(renamed);
+    }
+    let (_, arrow, _) = split_connection(value)?;
+    let (source, target) = value.split_once(arrow)?;
+    let renamed_source = rename_endpoint(source.trim(), from, to);
+    let renamed_target = rename_endpoint(target.trim(), from, to);
+    if renamed_source.is_none() && renamed_target.is_none() {
+        return None;
+    }
+    // Keep the spacing around the arrow as written
+    let side = |text: &str, renamed: Option<String>| match renamed {
+        Some(renamed) => text.replacen(text.trim(), &renamed, 1),
+        None => text.to_string(),
+    };
+    Some(format!(
+        "{}{arrow}{}",
+        side(source, renamed_source),
+        side(target, renamed_target)
+    ))
+}
+
+/// Returns `endpoint` renamed if it names `from` or one of its components.
+fn rename_endpoint(endpoint: &str, from: &str, to: &str) -> Option<String> {
+    if endpoint == from {
+        return Some(to.to_string());
+    }
+    let component = endpoint.strip_prefix(from)?.strip_prefix('.')?;
+    Some(format!("{to}.{component}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODEL: &str = r#"workflow: Orders
+events:
+  # Raised once the order is paid for
+  OrderPlaced:
+    description: "Placed"   # not a reference
+    swimlane: backend
+views:
+  OrderForm:
+    components:
+      - Submit: Button
+slices:
+  - name: Place
+    order: [OrderForm, PlaceOrder, OrderPlaced]
+    connections:
+      - OrderForm.Submit -> PlaceOrder
+      - "PlaceOrder->OrderPlaced"
+      - connection: OrderPlaced -> OrderSummary
+        feature: summaries
+commands:
+  PlaceOrder:
+    tests:
+      "Places":
+        Given: []
+        When:
+          - PlaceOrder: {}
+        Then:
+          - OrderPlaced:
+              id: A
+"#;
+
+    #[test]
+    fn renames_the_entity_and_its_references_only() {
+        let renamed = rename_entity(MODEL, "OrderPlaced", "OrderSubmitted").unwrap();
+
+        assert_eq!(renamed.replacements, 5);
+        assert_eq!(
+            renamed.source,
+            MODEL
+                .replace("  OrderPlaced:", "  OrderSubmitted:")
+                .replace("PlaceOrder, OrderPlaced]", "PlaceOrder, OrderSubmitted]")
+                .replace("PlaceOrder->OrderPlaced", "PlaceOrder->OrderSubmitted")
+                .replace(
+                    "OrderPlaced -> OrderSummary",
+                    "OrderSubmitted -> OrderSummary"
+                )
+                .replace("- OrderPlaced:", "- OrderSubmitted:")
+        );
+    }
+
+    #[test]
+    fn renames_component_endpoints_with_their_view() {
+        let renamed = rename_entity(MODEL, "OrderForm", "CheckoutForm").unwrap();
+
+        assert_eq!(renamed.replacements, 3);
+        assert!(
+            renamed
+                .source
+                .contains("- CheckoutForm.Submit -> PlaceOrder")
+        );
+    }
+
+    #[test]
+    fn leaves_values_outside_references_alone() {
+        let model = r#"workflow: PlaceOrder
+commands:
+  PlaceOrder:
+    description: PlaceOrder
+    data:
+      id:
+        type: PlaceOrder
+slices:
+  - name: PlaceOrder
+    connections:
+      - PlaceOrder -> OrderPlaced
+"#;
+        let renamed = rename_entity(model, "PlaceOrder", "SubmitOrder").unwrap();
+
+        assert_eq!(renamed.replacements, 2);
+        assert_eq!(
+            renamed.source,
+            model
+                .replace("  PlaceOrder:", "  SubmitOrder:")
+                .replace("- PlaceOrder ->", "- SubmitOrder ->")
+        );
+    }
+
+    const CONSTRAINED: &str = r#"workflow: Orders
+swimlanes:
+  - backend: "Backend"
+layout: constraints
+constraints:
+  - align: [PlaceOrder, OrderPlaced]
+  - order: [OrderPlaced, OrderForm]
+  - gap:
+      between: [OrderForm, OrderPlaced]
+      min: 40
+slices:
+  - name: OrderPlaced
+    connections:
+      - connection: OrderForm -> OrderPlaced
+        via:
+          - slice: OrderPlaced
+            lane: backend
+profiles:
+  mvp:
+    remove:
+      entities: [OrderPlaced]
+  lean:
+    remove:
+      connections:
+        - OrderForm -> OrderPlaced
+views:
+  OrderForm:
+    description: "Form"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "Placed"
+    swimlane: backend
+"#;
+
+    /// Renames `OrderPlaced` in [`CONSTRAINED`], checking the count.
+    fn rename_constrained(replacements: usize) -> String {
+        let renamed = rename_entity(CONSTRAINED, "OrderPlaced", "OrderSubmitted").unwrap();
+        assert_eq!(renamed.replacements, replacements);
+        renamed.source
+    }
+
+    #[test]
+    fn renames_aligned_entities() {
+        assert!(rename_constrained(7).contains("  - align: [PlaceOrder, OrderSubmitted]\n"));
+    }
+
+    #[test]
+    fn renames_entities_ordered_by_constraints() {
+        assert!(rename_constrained(7).contains("  - order: [OrderSubmitted, OrderForm]\n"));
+    }
+
+    #[test]
+    fn renames_entities_kept_apart_by_gaps() {
+        assert!(rename_constrained(7).contains("      between: [OrderForm, OrderSubmitted]\n"));
+    }
+
+    #[test]
+    fn renames_entities_and_connections_profiles_remove() {
+        let source = rename_constrained(7);
+
+        assert!(source.contains("      entities: [OrderSubmitted]\n"));
+        assert!(source.contains("        - OrderForm -> OrderSubmitted\n"));
+        // The renamed model still loads with either profile
+        for profile in ["mvp", "lean"] {
+            let mut model = super::super::yaml_parser::parse_yaml(&source).unwrap();
+            super::super::profiles::apply_profile(&mut model, profile).unwrap();
+        }
+    }
+
+    #[test]
+    fn leaves_slices_and_waypoints_named_like_the_entity_alone() {
+        let source = rename_constrained(7);
+
+        assert!(source.contains("  - name: OrderPlaced\n"));
+        assert!(source.contains("          - slice: OrderPlaced\n"));
+    }
+
+    #[test]
+    fn refuses_unknown_taken_and_ambiguous_names() {
+        assert_eq!(
+            rename_entity(MODEL, "ShipOrder", "DispatchOrder"),
+            Err(RenameError::UnknownEntity("ShipOrder".to_string()))
+        );
+        assert_eq!(
+            rename_entity(MODEL, "OrderPlaced", "OrderForm"),
+            Err(RenameError::NameTaken("OrderForm".to_string()))
+        );
+        assert_eq!(
+            rename_entity(MODEL, "OrderPlaced", "Order.Placed"),
+            Err(RenameError::InvalidName("Order.Placed".to_string()))
+        );
+    }
+}
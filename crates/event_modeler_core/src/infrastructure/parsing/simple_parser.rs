@@ -56,6 +56,7 @@ pub struct ParsedConnector {
 
 /// Parser errors.
 #[derive(Debug, thiserror::Error, PartialEq)]
+#[non_exhaustive]
 pub enum ParseError {
     #[error("Missing title section - models must start with 'Title: <name>'")]
     MissingTitle,
@@ -140,7 +141,7 @@ impl EventModelParser {
             }
         }
 
-        Ok(parser_state.build())
+        parser_state.build()
     }
 }
 
@@ -380,14 +381,14 @@ impl ParserState {
         Ok(())
     }
 
-    fn build(self) -> ParsedEventModel {
-        let title = self.title.expect("Title must be set");
+    fn build(self) -> Result<ParsedEventModel, ParseError> {
+        let title = self.title.ok_or(ParseError::MissingTitle)?;
 
-        ParsedEventModel {
+        Ok(ParsedEventModel {
             title,
             swimlanes: self.swimlanes,
             connectors: self.connectors,
-        }
+        })
     }
 }
 
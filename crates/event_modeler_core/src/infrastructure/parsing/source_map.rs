@@ -0,0 +1,311 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Source positions for the nodes of a YAML document.
+//!
+//! Serde deserialization discards where each value came from. The
+//! [`SourceMap`] recovers that information by walking the document's event
+//! stream once and recording the line and column of every mapping key and
+//! sequence item, addressed by its [`SourcePath`] from the document root.
+
+use std::collections::HashMap;
+use std::fmt;
+use yaml_rust2::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust2::scanner::Marker;
+
+/// One step from a YAML node to one of its children.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    /// The value stored under a mapping key.
+    Key(String),
+    /// The item at a position within a sequence.
+    Index(usize),
+}
+
+/// The address of a node within a YAML document, e.g. `events.UserCreated.swimlane`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SourcePath(Vec<PathSegment>);
+
+impl SourcePath {
+    /// The path of the document root.
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    /// Returns this path extended by a mapping key.
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.0.push(PathSegment::Key(key.into()));
+        self
+    }
+
+    /// Returns this path extended by a sequence index.
+    pub fn index(mut self, index: usize) -> Self {
+        self.0.push(PathSegment::Index(index));
+        self
+    }
+
+    /// Returns the segments of the path from the root.
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.0
+    }
+
+    /// Returns the final mapping key of the path, if it ends in one.
+    pub fn last_key(&self) -> Option<&str> {
+        match self.0.last() {
+            Some(PathSegment::Key(key)) => Some(key),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SourcePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "<root>");
+        }
+        for (position, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Key(key) if position == 0 => write!(f, "{key}")?,
+                PathSegment::Key(key) => write!(f, ".{key}")?,
+                PathSegment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A 1-indexed line and column in the source text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Column number, starting at 1.
+    pub column: usize,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+impl From<Marker> for SourceLocation {
+    fn from(mark: Marker) -> Self {
+        Self {
+            line: mark.line(),
+            column: mark.col() + 1,
+        }
+    }
+}
+
+/// Locations of the mapping keys and sequence items of a YAML document.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    locations: HashMap<SourcePath, SourceLocation>,
+}
+
+impl SourceMap {
+    /// Records the location of every key and sequence item in `input`.
+    ///
+    /// Syntax errors are reported by the deserializer, so a malformed document
+    /// simply yields the locations recorded before the error.
+    pub fn build(input: &str) -> Self {
+        let mut recorder = LocationRecorder::default();
+        let _ = Parser::new_from_str(input).load(&mut recorder, false);
+        Self {
+            locations: recorder.locations,
+        }
+    }
+
+    /// Returns the location of the node at exactly `path`.
+    pub fn locate(&self, path: &SourcePath) -> Option<SourceLocation> {
+        self.locations.get(path).copied()
+    }
+
+    /// Returns the location of the node at `path`, or of its closest recorded
+    /// ancestor when the node itself has no location (e.g. it came from an alias).
+    pub fn nearest(&self, path: &SourcePath) -> Option<SourceLocation> {
+        (0..=path.0.len())
+            .rev()
+            .find_map(|length| self.locations.get(&SourcePath(path.0[..length].to_vec())))
+            .copied()
+    }
+}
+
+/// What the next node event inside a collection represents.
+#[derive(Debug)]
+enum Frame {
+    /// Inside a mapping, waiting for a key (`None`) or for the value of a key.
+    Mapping { pending_key: Option<String> },
+    /// Inside a sequence, at the given item position.
+    Sequence { next_index: usize },
+}
+
+/// Event receiver that tracks the current path and records node locations.
+#[derive(Debug, Default)]
+struct LocationRecorder {
+    path: SourcePath,
+    frames: Vec<Frame>,
+    /// Nesting depth inside a non-scalar mapping key, which has no path.
+    complex_key_depth: usize,
+    locations: HashMap<SourcePath, SourceLocation>,
+}
+
+impl LocationRecorder {
+    /// Handles the start of a scalar, alias, mapping, or sequence node.
+    fn start_node(&mut self, key: Option<&str>, is_collection: bool, mark: Marker) {
+        if self.complex_key_depth > 0 {
+            if is_collection {
+                self.complex_key_depth += 1;
+            }
+            return;
+        }
+
+        let segment = match self.frames.last_mut() {
+            None => None,
+            Some(Frame::Mapping { pending_key: None }) => {
+                match key {
+                    Some(key) => {
+                        self.locations
+                            .insert(self.path.clone().key(key), mark.into());
+                    }
+                    None if is_collection => {
+                        self.complex_key_depth = 1;
+                        return;
+                    }
+                    None => {}
+                }
+                if let Some(Frame::Mapping { pending_key }) = self.frames.last_mut() {
+                    *pending_key = Some(key.unwrap_or_default().to_string());
+                }
+                return;
+            }
+            Some(Frame::Mapping { pending_key }) => {
+                Some(PathSegment::Key(pending_key.take().unwrap_or_default()))
+            }
+            Some(Frame::Sequence { next_index }) => {
+                let index = *next_index;
+                *next_index += 1;
+                self.locations
+                    .insert(self.path.clone().index(index), mark.into());
+                Some(PathSegment::Index(index))
+            }
+        };
+
+        // Scalars have no children, so only collections extend the path
+        if let (Some(segment), true) = (segment, is_collection) {
+            self.path.0.push(segment);
+        }
+    }
+
+    /// Opens a mapping or sequence after its start event was recorded.
+    fn open_collection(&mut self, frame: Frame) {
+        if self.complex_key_depth == 0 {
+            self.frames.push(frame);
+        }
+    }
+
+    /// Handles the end of a mapping or sequence node.
+    fn end_collection(&mut self) {
+        if self.complex_key_depth > 0 {
+            self.complex_key_depth -= 1;
+            if self.complex_key_depth == 0 {
+                // The complex key is finished; its value follows.
+                if let Some(Frame::Mapping { pending_key }) = self.frames.last_mut() {
+                    *pending_key = Some(String::new());
+                }
+            }
+            return;
+        }
+
+        self.frames.pop();
+        if !self.frames.is_empty() {
+            self.path.0.pop();
+        }
+    }
+}
+
+impl MarkedEventReceiver for LocationRecorder {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        match event {
+            Event::Scalar(value, ..) => self.start_node(Some(&value), false, mark),
+            Event::Alias(_) => self.start_node(None, false, mark),
+            Event::MappingStart(..) => {
+                self.start_node(None, true, mark);
+                self.open_collection(Frame::Mapping { pending_key: None });
+            }
+            Event::SequenceStart(..) => {
+                self.start_node(None, true, mark);
+                self.open_collection(Frame::Sequence { next_index: 0 });
+            }
+            Event::MappingEnd | Event::SequenceEnd => self.end_collection(),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCUMENT: &str = r#"workflow: Test
+swimlanes:
+  - backend: "Backend"
+events:
+  UserCreated:
+    description: "Created"
+    swimlane: backend
+slices:
+  - name: Signup
+    connections:
+      - "A -> B"
+"#;
+
+    #[test]
+    fn locates_nested_mapping_keys() {
+        let map = SourceMap::build(DOCUMENT);
+        let path = SourcePath::root()
+            .key("events")
+            .key("UserCreated")
+            .key("swimlane");
+
+        assert_eq!(
+            map.locate(&path),
+            Some(SourceLocation { line: 7, column: 5 })
+        );
+    }
+
+    #[test]
+    fn locates_sequence_items_and_their_keys() {
+        let map = SourceMap::build(DOCUMENT);
+        let slice = SourcePath::root().key("slices").index(0);
+
+        assert_eq!(map.locate(&slice).map(|l| l.line), Some(9));
+        assert_eq!(
+            map.locate(&slice.clone().key("connections").index(0)),
+            Some(SourceLocation {
+                line: 11,
+                column: 9
+            })
+        );
+        assert_eq!(map.locate(&slice.key("name")).map(|l| l.line), Some(9));
+    }
+
+    #[test]
+    fn nearest_falls_back_to_closest_ancestor() {
+        let map = SourceMap::build(DOCUMENT);
+        let missing = SourcePath::root()
+            .key("events")
+            .key("UserCreated")
+            .key("data");
+
+        assert_eq!(map.locate(&missing), None);
+        assert_eq!(map.nearest(&missing).map(|l| l.line), Some(5));
+    }
+
+    #[test]
+    fn formats_paths_with_keys_and_indices() {
+        let path = SourcePath::root().key("slices").index(2).key("name");
+        assert_eq!(path.to_string(), "slices[2].name");
+    }
+}
@@ -0,0 +1,5149 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Conversion from YAML parsing types to domain types.
+//!
+//! This module handles the transformation from the intermediate YAML parsing
+//! representation to the strongly-typed domain model.
+
+use crate::event_model::entities::EntityType;
+use crate::event_model::terminology;
+use crate::event_model::yaml_types as domain;
+use crate::infrastructure::parsing::source_map::{SourceLocation, SourceMap, SourcePath};
+use crate::infrastructure::parsing::yaml_parser as parsing;
+use crate::infrastructure::types::{
+    NonEmpty, NonEmptyString, NonNegativeInt, ParseError, PathBuilder, PositiveInt,
+};
+use crate::infrastructure::warnings::{WarningKind, Warnings};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+
+/// Helper function to convert a Vec to NonEmpty.
+fn vec_to_non_empty<T>(vec: Vec<T>, name: &str) -> Result<NonEmpty<T>, ConversionError> {
+    NonEmpty::try_from(vec).map_err(|_| ConversionError::EmptyCollection(name.to_string()))
+}
+
+/// Converts a parsed YAML model into the domain representation.
+///
+/// This function performs all necessary validation and transformation:
+/// - Validates all entity references (swimlanes, etc.)
+/// - Converts stringly-typed data to strongly-typed domain objects
+/// - Ensures all invariants are met
+///
+/// Warnings raised during conversion are discarded; use
+/// [`convert_yaml_to_domain_with_warnings`] to collect them.
+pub fn convert_yaml_to_domain(
+    yaml: parsing::YamlEventModel,
+) -> Result<domain::YamlEventModel, ConversionError> {
+    convert_yaml_to_domain_with_warnings(yaml, &mut Warnings::new())
+}
+
+/// Converts a parsed YAML model into the domain representation, recording
+/// anything that was tolerated rather than rejected in `warnings`.
+pub fn convert_yaml_to_domain_with_warnings(
+    yaml: parsing::YamlEventModel,
+    warnings: &mut Warnings,
+) -> Result<domain::YamlEventModel, ConversionError> {
+    // Convert swimlanes
+    let (swimlanes, swimlane_groups) = convert_swimlanes(yaml.swimlanes)?;
+
+    // Build swimlane ID lookup for validation
+    let swimlane_ids: Vec<String> = swimlanes
+        .iter()
+        .map(|s| s.id.clone().into_inner().into_inner())
+        .collect();
+
+    // Convert entities (with swimlane validation)
+    let mut base = parsing::YamlModelFragment {
+        events: yaml.events,
+        commands: yaml.commands,
+        views: yaml.views,
+        projections: yaml.projections,
+        queries: yaml.queries,
+        automations: yaml.automations,
+        slices: yaml.slices,
+    };
+    include_field_sets(&mut base, &yaml.field_sets)?;
+    let mut entities = ConvertedEntities::convert(&base, &swimlane_ids, &yaml.source_map)?;
+
+    // Expand templates into concrete entities, tagging errors with their origin
+    let mut instances = instantiate_templates(&yaml.templates, &yaml.expand)?;
+    for instance in &mut instances {
+        include_field_sets(&mut instance.fragment, &yaml.field_sets)
+            .map_err(|error| instance.provenance(error))?;
+        ConvertedEntities::convert(&instance.fragment, &swimlane_ids, &SourceMap::default())
+            .and_then(|expanded| entities.absorb(expanded))
+            .map_err(|error| instance.provenance(error))?;
+    }
+
+    let extensions = convert_extensions(yaml.extensions)?;
+    check_entity_kinds(&entities, &extensions)?;
+
+    // Convert slices
+    let jira = yaml.jira.as_deref().map(convert_jira_site).transpose()?;
+    let mut slices = convert_slices(
+        base.slices,
+        &entities,
+        &extensions,
+        jira,
+        &yaml.source_map,
+        warnings,
+    )?;
+    for instance in instances {
+        let expanded = convert_slices(
+            instance.fragment.slices.clone(),
+            &entities,
+            &extensions,
+            jira,
+            &SourceMap::default(),
+            warnings,
+        )
+        .map_err(|error| instance.provenance(error))?;
+        slices.extend(expanded);
+    }
+    if slices.is_empty() {
+        slices = synthesize_slices(&entities, &yaml.source_map, warnings);
+    }
+    check_waypoints(&slices, &swimlane_ids)?;
+    check_slice_swimlanes(&slices, &entities, &swimlane_ids)?;
+    check_test_outcomes(&entities, warnings);
+    let roles: Vec<domain::RoleName> = yaml
+        .roles
+        .into_iter()
+        .map(convert_role)
+        .collect::<Result<_, _>>()?;
+    check_authorized_roles(&entities, &roles)?;
+    let types = convert_types(yaml.types)?;
+    check_declared_types(&entities, &types)?;
+    check_scenario_examples(&entities, &types)?;
+    check_stream_field_types(&entities, warnings);
+    check_field_drift(&entities, &slices, warnings);
+    let layout_constraints = convert_layout_constraints(yaml.constraints, &entities)?;
+    let ConvertedEntities {
+        events,
+        commands,
+        views,
+        projections,
+        queries,
+        automations,
+    } = entities;
+
+    // Build the domain model
+    Ok(domain::YamlEventModel {
+        version: match yaml.version {
+            Some(v) => {
+                let non_empty = NonEmptyString::parse(v).map_err(|e| match e {
+                    ParseError::EmptyString => ConversionError::EmptyField("version".to_string()),
+                    _ => ConversionError::ParseError(e),
+                })?;
+                Some(domain::SchemaVersion::new(non_empty))
+            }
+            None => None,
+        },
+        workflow: domain::WorkflowName::new(
+            NonEmptyString::parse(yaml.workflow.title().to_string()).map_err(|e| match e {
+                ParseError::EmptyString => ConversionError::EmptyField("workflow".to_string()),
+                _ => ConversionError::ParseError(e),
+            })?,
+        ),
+        header: convert_workflow_header(yaml.workflow)?,
+        swimlanes,
+        swimlane_groups,
+        events,
+        commands,
+        views,
+        projections,
+        queries,
+        automations,
+        slices,
+        layout: match yaml.layout {
+            None | Some(parsing::YamlLayout::Standard) => domain::LayoutStrategy::Standard,
+            Some(parsing::YamlLayout::Compact) => domain::LayoutStrategy::Compact,
+            Some(parsing::YamlLayout::Constraints) if cfg!(feature = "constraint-layout") => {
+                domain::LayoutStrategy::Constraints
+            }
+            Some(parsing::YamlLayout::Constraints) => {
+                return Err(ConversionError::ConstraintLayoutUnavailable);
+            }
+        },
+        layout_constraints,
+        slice_headers: convert_slice_headers(yaml.slice_headers)?,
+        clearance: convert_clearance(yaml.routing)?,
+        canvas: convert_canvas(yaml.canvas)?,
+        entity_sizing: convert_entity_sizing(yaml.entity_sizing),
+        grid: yaml
+            .grid
+            .map(|grid| PositiveInt::parse(grid).map_err(|_| ConversionError::InvalidGrid))
+            .transpose()?,
+        swimlane_order: match yaml.swimlane_order {
+            None | Some(parsing::YamlSwimlaneOrder::Declared) => domain::SwimlaneOrder::Declared,
+            Some(parsing::YamlSwimlaneOrder::Convention) => domain::SwimlaneOrder::Convention,
+        },
+        connection_markers: convert_markers(yaml.markers)?,
+        duplicate_connections: match yaml.duplicate_connections {
+            None | Some(parsing::YamlDuplicateConnections::Separate) => {
+                domain::DuplicateConnections::Separate
+            }
+            Some(parsing::YamlDuplicateConnections::Count) => domain::DuplicateConnections::Count,
+            Some(parsing::YamlDuplicateConnections::Slices) => domain::DuplicateConnections::Slices,
+        },
+        unused_lanes: match yaml.unused_lanes {
+            None | Some(parsing::YamlUnusedLanes::Full) => domain::UnusedLanes::Full,
+            Some(parsing::YamlUnusedLanes::Shaded) => domain::UnusedLanes::Shaded,
+        },
+        roles,
+        terminology: domain::Terminology {
+            banned_words: yaml
+                .terminology
+                .banned_words
+                .into_iter()
+                .map(|word| {
+                    NonEmptyString::parse(word)
+                        .map(domain::BannedWord::new)
+                        .map_err(|_| ConversionError::EmptyField("banned word".to_string()))
+                })
+                .collect::<Result<_, _>>()?,
+        },
+        conventions: domain::Conventions {
+            envelope: convert_field_names(yaml.conventions.envelope, "envelope field")?,
+            propagate: convert_field_names(yaml.conventions.propagate, "propagated field")?,
+        },
+        lint: domain::LintConfig {
+            plugins: yaml
+                .lint
+                .plugins
+                .into_iter()
+                .map(|path| {
+                    if !cfg!(feature = "plugins") {
+                        return Err(ConversionError::PluginsUnavailable);
+                    }
+                    NonEmptyString::parse(path)
+                        .map(domain::PluginPath::new)
+                        .map_err(|_| ConversionError::EmptyField("lint plugin".to_string()))
+                })
+                .collect::<Result<_, _>>()?,
+        },
+        extensions,
+        types,
+    })
+}
+
+/// Converts the expected throughput of the named entity.
+fn convert_volume(
+    volume: Option<String>,
+    entity: &str,
+) -> Result<Option<domain::Volume>, ConversionError> {
+    volume
+        .map(|text| {
+            domain::Volume::parse(&text).ok_or_else(|| ConversionError::InvalidVolume {
+                entity: entity.to_string(),
+                volume: text,
+            })
+        })
+        .transpose()
+}
+
+/// Converts the name of a role commands may be authorized for.
+fn convert_role(role: String) -> Result<domain::RoleName, ConversionError> {
+    NonEmptyString::parse(role)
+        .map(domain::RoleName::new)
+        .map_err(|_| ConversionError::EmptyField("role".to_string()))
+}
+
+/// Checks that every role a command is authorized for is declared under `roles`.
+fn check_authorized_roles(
+    entities: &ConvertedEntities,
+    roles: &[domain::RoleName],
+) -> Result<(), ConversionError> {
+    let mut commands: Vec<_> = entities.commands.iter().collect();
+    commands.sort_by_key(|(name, _)| (*name).clone().into_inner().into_inner());
+    for (command, definition) in commands {
+        if let Some(role) = definition
+            .authorized_roles
+            .iter()
+            .find(|role| !roles.contains(role))
+        {
+            return Err(ConversionError::UnknownRole {
+                command: command.clone().into_inner().into_inner(),
+                role: role.clone().into_inner().into_inner(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Types every model may use without declaring them.
+pub(super) const BUILT_IN_TYPES: [&str; 9] = [
+    "String",
+    "Integer",
+    "Float",
+    "Decimal",
+    "Boolean",
+    "Timestamp",
+    "Date",
+    "Time",
+    "UUID",
+];
+
+/// Built-in types whose parameters are types themselves, e.g. `List<OrderId>`.
+/// Parameters of any other type are states, e.g. `EmailAddress<Verified>`.
+pub(super) const GENERIC_TYPES: [&str; 4] = ["List", "Set", "Map", "Option"];
+
+/// Converts the domain types declared under `types`.
+fn convert_types(
+    types: HashMap<String, parsing::YamlTypeDefinition>,
+) -> Result<HashMap<domain::TypeName, domain::TypeDefinition>, ConversionError> {
+    let field_type = |type_str: String| {
+        NonEmptyString::parse(type_str)
+            .map(domain::FieldType::new)
+            .map_err(|_| ConversionError::EmptyField("field type".to_string()))
+    };
+    let mut result = HashMap::new();
+    for (name, definition) in types {
+        let name = domain::TypeName::new(
+            NonEmptyString::parse(name)
+                .map_err(|_| ConversionError::EmptyField("type name".to_string()))?,
+        );
+        let definition = match definition {
+            parsing::YamlTypeDefinition::Alias(type_str) => {
+                domain::TypeDefinition::Alias(field_type(type_str)?)
+            }
+            parsing::YamlTypeDefinition::Constrained(constrained) => {
+                convert_constrained_type(&name, constrained)?
+            }
+            parsing::YamlTypeDefinition::Record(fields) => domain::TypeDefinition::Record(
+                fields
+                    .into_iter()
+                    .map(|(field, type_str)| {
+                        let field = NonEmptyString::parse(field)
+                            .map(domain::FieldName::new)
+                            .map_err(|_| ConversionError::EmptyField("field name".to_string()))?;
+                        Ok((field, field_type(type_str)?))
+                    })
+                    .collect::<Result<_, ConversionError>>()?,
+            ),
+        };
+        result.insert(name, definition);
+    }
+    Ok(result)
+}
+
+/// Built-in types that `min` and `max` may restrict.
+const NUMERIC_TYPES: [&str; 3] = ["Integer", "Float", "Decimal"];
+
+/// Converts a declared enumeration, or a type restricting another's values.
+fn convert_constrained_type(
+    name: &domain::TypeName,
+    constrained: parsing::YamlConstrainedType,
+) -> Result<domain::TypeDefinition, ConversionError> {
+    let invalid = |reason: String| ConversionError::InvalidType {
+        name: name.clone().into_inner().into_inner(),
+        reason,
+    };
+    let parsing::YamlConstrainedType {
+        base,
+        variants,
+        min,
+        max,
+        pattern,
+    } = constrained;
+
+    let Some(base) = base else {
+        if min.is_some() || max.is_some() || pattern.is_some() {
+            return Err(invalid(
+                "an enum cannot also declare min, max, or pattern".to_string(),
+            ));
+        }
+        let mut converted: Vec<domain::EnumVariant> = Vec::new();
+        for variant in variants {
+            let variant = NonEmptyString::parse(variant)
+                .map(domain::EnumVariant::new)
+                .map_err(|_| ConversionError::EmptyField("enum variant".to_string()))?;
+            if converted.contains(&variant) {
+                return Err(invalid(format!(
+                    "variant '{}' is listed twice",
+                    variant.into_inner().as_str()
+                )));
+            }
+            converted.push(variant);
+        }
+        return NonEmpty::try_from(converted)
+            .map(domain::TypeDefinition::Enum)
+            .map_err(|_| {
+                invalid(
+                    "declare the type it restricts with `type`, or its values with `enum`"
+                        .to_string(),
+                )
+            });
+    };
+
+    if !variants.is_empty() {
+        return Err(invalid(
+            "declare either the type it restricts with `type` or its values with `enum`, not both"
+                .to_string(),
+        ));
+    }
+    if (min.is_some() || max.is_some()) && !NUMERIC_TYPES.contains(&without_states(&base).as_str())
+    {
+        return Err(invalid(format!(
+            "min and max only apply to Integer, Float, or Decimal, not {base}"
+        )));
+    }
+    if let Some(bound) = min.into_iter().chain(max).find(|bound| !bound.is_finite()) {
+        return Err(invalid(format!("min and max must be numbers, not {bound}")));
+    }
+    if let (Some(min), Some(max)) = (min, max) {
+        if min > max {
+            return Err(invalid(format!("min {min} is greater than max {max}")));
+        }
+    }
+    let pattern = pattern
+        .map(|pattern| {
+            regex::Regex::new(&pattern)
+                .map_err(|error| invalid(format!("pattern '{pattern}' is not valid: {error}")))
+        })
+        .transpose()?;
+    let base = NonEmptyString::parse(base)
+        .map(domain::FieldType::new)
+        .map_err(|_| ConversionError::EmptyField("field type".to_string()))?;
+    Ok(domain::TypeDefinition::Constrained(
+        domain::ConstrainedType {
+            base,
+            min,
+            max,
+            pattern,
+        },
+    ))
+}
+
+/// Checks, once the model declares any types, that every field refers only
+/// to declared or built-in types.
+fn check_declared_types(
+    entities: &ConvertedEntities,
+    types: &HashMap<domain::TypeName, domain::TypeDefinition>,
+) -> Result<(), ConversionError> {
+    if types.is_empty() {
+        return Ok(());
+    }
+    let declared: HashSet<String> = types
+        .keys()
+        .map(|name| name.clone().into_inner().into_inner())
+        .collect();
+
+    let name_of = |name: String, field: &domain::FieldName, field_type: &domain::FieldType| {
+        (
+            name,
+            field.clone().into_inner().into_inner(),
+            field_type.clone().into_inner().into_inner(),
+        )
+    };
+    let data_fields = |name: String, data: &HashMap<domain::FieldName, domain::FieldDefinition>| {
+        data.iter()
+            .map(|(field, def)| name_of(name.clone(), field, &def.field_type))
+            .collect::<Vec<_>>()
+    };
+    let typed_fields = |name: String, fields: &HashMap<domain::FieldName, domain::FieldType>| {
+        fields
+            .iter()
+            .map(|(field, field_type)| name_of(name.clone(), field, field_type))
+            .collect::<Vec<_>>()
+    };
+
+    let mut fields = Vec::new();
+    for (name, event) in &entities.events {
+        fields.extend(data_fields(
+            name.clone().into_inner().into_inner(),
+            &event.data,
+        ));
+    }
+    for (name, command) in &entities.commands {
+        fields.extend(data_fields(
+            name.clone().into_inner().into_inner(),
+            &command.data,
+        ));
+        for (error, definition) in &command.errors {
+            fields.extend(data_fields(
+                error.clone().into_inner().into_inner(),
+                &definition.data,
+            ));
+        }
+    }
+    for (name, projection) in &entities.projections {
+        fields.extend(typed_fields(
+            name.clone().into_inner().into_inner(),
+            &projection.fields,
+        ));
+    }
+    for (name, query) in &entities.queries {
+        let name = name.clone().into_inner().into_inner();
+        fields.extend(typed_fields(name.clone(), &query.inputs));
+        match &query.outputs {
+            domain::OutputSpec::Single(outputs) => {
+                fields.extend(typed_fields(name.clone(), outputs));
+            }
+            domain::OutputSpec::OneOf(cases) => {
+                for case in cases.values() {
+                    if let domain::OutputCase::Fields(outputs) = case {
+                        fields.extend(typed_fields(name.clone(), outputs));
+                    }
+                }
+            }
+        }
+    }
+    for (name, definition) in types {
+        let name = name.clone().into_inner().into_inner();
+        match definition {
+            domain::TypeDefinition::Alias(field_type) => fields.push((
+                name.clone(),
+                name,
+                field_type.clone().into_inner().into_inner(),
+            )),
+            domain::TypeDefinition::Record(members) => {
+                fields.extend(typed_fields(name, members));
+            }
+            domain::TypeDefinition::Constrained(constrained) => fields.push((
+                name.clone(),
+                name,
+                constrained.base.clone().into_inner().into_inner(),
+            )),
+            domain::TypeDefinition::Enum(_) => {}
+        }
+    }
+
+    fields.sort();
+    for (entity, field, field_type) in fields {
+        if let Some(undeclared) = undeclared_type(&field_type, &declared) {
+            return Err(ConversionError::UndeclaredType {
+                entity,
+                field,
+                field_type: undeclared.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Returns the first type named in a type expression that is neither built
+/// in nor declared, e.g. `Sku` in `List<Sku>`.
+fn undeclared_type<'a>(expression: &'a str, declared: &HashSet<String>) -> Option<&'a str> {
+    let expression = expression.trim();
+    let (head, parameters) = match expression.split_once('<') {
+        Some((head, rest)) => (head.trim(), rest.strip_suffix('>').unwrap_or(rest)),
+        None => (expression, ""),
+    };
+    if GENERIC_TYPES.contains(&head) {
+        return split_type_parameters(parameters)
+            .into_iter()
+            .find_map(|parameter| undeclared_type(parameter, declared));
+    }
+    (!BUILT_IN_TYPES.contains(&head) && !declared.contains(head)).then_some(head)
+}
+
+/// Drops the states from a type expression, so `EmailAddress<Verified>` and
+/// `EmailAddress<Unverified>` both read `EmailAddress`.
+fn without_states(expression: &str) -> String {
+    let expression = expression.trim();
+    let Some((head, rest)) = expression.split_once('<') else {
+        return expression.to_string();
+    };
+    let head = head.trim();
+    if !GENERIC_TYPES.contains(&head) {
+        return head.to_string();
+    }
+    let parameters: Vec<String> = split_type_parameters(rest.strip_suffix('>').unwrap_or(rest))
+        .into_iter()
+        .map(without_states)
+        .collect();
+    format!("{head}<{}>", parameters.join(", "))
+}
+
+/// Splits the parameters of a generic type at its top-level commas.
+fn split_type_parameters(parameters: &str) -> Vec<&str> {
+    let mut split = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, c) in parameters.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                split.push(&parameters[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(&parameters[start..]);
+    split.retain(|parameter| !parameter.trim().is_empty());
+    split
+}
+
+/// Checks that every example in a test scenario stands in for a placeholder
+/// the scenario uses, and suits the type of each field that placeholder fills.
+fn check_scenario_examples(
+    entities: &ConvertedEntities,
+    types: &HashMap<domain::TypeName, domain::TypeDefinition>,
+) -> Result<(), ConversionError> {
+    type Fields = HashMap<domain::FieldName, domain::FieldDefinition>;
+    let event_data = |name: &domain::EventName| entities.events.get(name).map(|def| &def.data);
+    let command_data =
+        |name: &domain::CommandName| entities.commands.get(name).map(|def| &def.data);
+
+    let mut commands: Vec<_> = entities.commands.iter().collect();
+    commands.sort_by_key(|(name, _)| (*name).clone().into_inner().into_inner());
+    for (command, definition) in commands {
+        let mut scenarios: Vec<_> = definition.tests.iter().collect();
+        scenarios.sort_by_key(|(name, _)| (*name).clone().into_inner().into_inner());
+        for (scenario, test) in scenarios {
+            if test.examples.is_empty() {
+                continue;
+            }
+            // Every field a placeholder fills, with the entity it belongs to
+            let mut steps: Vec<(
+                String,
+                &HashMap<domain::FieldName, domain::PlaceholderValue>,
+                Option<&Fields>,
+            )> = Vec::new();
+            for event in &test.given {
+                steps.push((
+                    event.name.clone().into_inner().into_inner(),
+                    &event.fields,
+                    event_data(&event.name),
+                ));
+            }
+            for action in test.when.iter() {
+                steps.push((
+                    action.name.clone().into_inner().into_inner(),
+                    &action.fields,
+                    command_data(&action.name),
+                ));
+            }
+            for outcome in test.then.iter() {
+                steps.push(match outcome {
+                    domain::TestOutcome::Event(event) => (
+                        event.name.clone().into_inner().into_inner(),
+                        &event.fields,
+                        event_data(&event.name),
+                    ),
+                    domain::TestOutcome::Error(error) => (
+                        error.name.clone().into_inner().into_inner(),
+                        &error.fields,
+                        definition.errors.get(&error.name).map(|def| &def.data),
+                    ),
+                });
+            }
+
+            let mut examples: Vec<_> = test.examples.iter().collect();
+            examples
+                .sort_by_key(|(placeholder, _)| (*placeholder).clone().into_inner().into_inner());
+            for (placeholder, example) in examples {
+                let invalid = |reason: String| ConversionError::InvalidExample {
+                    command: command.clone().into_inner().into_inner(),
+                    scenario: scenario.clone().into_inner().into_inner(),
+                    placeholder: placeholder.clone().into_inner().into_inner(),
+                    reason,
+                };
+                let example = example.clone().into_inner().into_inner();
+                let mut used = false;
+                for (entity, fields, schema) in &steps {
+                    let mut filled: Vec<_> = fields
+                        .iter()
+                        .filter(|(_, value)| *value == placeholder)
+                        .map(|(field, _)| field)
+                        .collect();
+                    filled.sort_by_key(|field| (*field).clone().into_inner().into_inner());
+                    for field in filled {
+                        used = true;
+                        let Some(definition) = schema.and_then(|schema| schema.get(field)) else {
+                            continue;
+                        };
+                        let field_type = definition.field_type.clone().into_inner().into_inner();
+                        if let Err(problem) = example_fits(&example, &field_type, types, 0) {
+                            return Err(invalid(format!(
+                                "'{example}' {problem}, as field '{}' of '{entity}' requires",
+                                field.clone().into_inner().as_str()
+                            )));
+                        }
+                    }
+                }
+                if !used {
+                    return Err(invalid(
+                        "the scenario does not use this placeholder".to_string(),
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks an example value against a field type, describing the problem if it
+/// does not suit it. Types the model does not describe accept any value.
+fn example_fits(
+    example: &str,
+    field_type: &str,
+    types: &HashMap<domain::TypeName, domain::TypeDefinition>,
+    depth: usize,
+) -> Result<(), String> {
+    let field_type = without_states(field_type);
+    // Aliases of aliases are followed only so far, in case they form a cycle
+    if depth > 8 {
+        return Ok(());
+    }
+    let declared = types
+        .iter()
+        .find(|(name, _)| (*name).clone().into_inner().into_inner() == field_type);
+    if let Some((_, definition)) = declared {
+        return match definition {
+            domain::TypeDefinition::Alias(aliased) => example_fits(
+                example,
+                aliased.clone().into_inner().as_str(),
+                types,
+                depth + 1,
+            ),
+            domain::TypeDefinition::Enum(variants) => {
+                if variants
+                    .iter()
+                    .any(|variant| variant.clone().into_inner().as_str() == example)
+                {
+                    Ok(())
+                } else {
+                    Err(format!("is not one of the {field_type} values"))
+                }
+            }
+            domain::TypeDefinition::Constrained(constrained) => {
+                example_fits(
+                    example,
+                    constrained.base.clone().into_inner().as_str(),
+                    types,
+                    depth + 1,
+                )?;
+                let number = example.parse::<f64>().ok();
+                if let (Some(number), Some(min)) = (number, constrained.min) {
+                    if number < min {
+                        return Err(format!("is below the {field_type} minimum of {min}"));
+                    }
+                }
+                if let (Some(number), Some(max)) = (number, constrained.max) {
+                    if number > max {
+                        return Err(format!("is above the {field_type} maximum of {max}"));
+                    }
+                }
+                match &constrained.pattern {
+                    Some(pattern) if !pattern.is_match(example) => Err(format!(
+                        "does not match the {field_type} pattern `{}`",
+                        pattern.as_str()
+                    )),
+                    _ => Ok(()),
+                }
+            }
+            domain::TypeDefinition::Record(_) => Ok(()),
+        };
+    }
+
+    let digits =
+        |part: &str, count: usize| part.len() == count && part.chars().all(|c| c.is_ascii_digit());
+    let is_date = |value: &str| {
+        let parts: Vec<&str> = value.split('-').collect();
+        matches!(parts[..], [year, month, day] if digits(year, 4) && digits(month, 2) && digits(day, 2))
+    };
+    let is_time = |value: &str| {
+        let value = value.split('.').next().unwrap_or(value);
+        let parts: Vec<&str> = value.split(':').collect();
+        matches!(parts[..], [hours, minutes] if digits(hours, 2) && digits(minutes, 2))
+            || matches!(parts[..], [hours, minutes, seconds] if digits(hours, 2) && digits(minutes, 2) && digits(seconds, 2))
+    };
+    let fits = match field_type.as_str() {
+        "Integer" => example.parse::<i64>().is_ok(),
+        "Float" | "Decimal" => example.parse::<f64>().is_ok(),
+        "Boolean" => example == "true" || example == "false",
+        "Date" => is_date(example),
+        "Time" => is_time(example),
+        "Timestamp" => example.split_once('T').is_some_and(|(date, time)| {
+            let time = time.trim_end_matches('Z');
+            let time = time.split(['+', '-']).next().unwrap_or(time);
+            is_date(date) && is_time(time)
+        }),
+        "UUID" => {
+            let groups: Vec<&str> = example.split('-').collect();
+            groups.iter().map(|group| group.len()).eq([8, 4, 4, 4, 12])
+                && groups
+                    .iter()
+                    .all(|group| group.chars().all(|c| c.is_ascii_hexdigit()))
+        }
+        _ => true,
+    };
+    if fits {
+        Ok(())
+    } else {
+        let article = if field_type.starts_with(['A', 'E', 'I', 'O', 'U']) {
+            "an"
+        } else {
+            "a"
+        };
+        Err(format!("is not {article} {field_type}"))
+    }
+}
+
+/// Converts a list of field names, rejecting empty ones.
+fn convert_field_names(
+    names: Vec<String>,
+    what: &str,
+) -> Result<Vec<domain::FieldName>, ConversionError> {
+    names
+        .into_iter()
+        .map(|name| {
+            NonEmptyString::parse(name)
+                .map(domain::FieldName::new)
+                .map_err(|_| ConversionError::EmptyField(what.to_string()))
+        })
+        .collect()
+}
+
+/// Warns when a field name has different types across the commands and
+/// events sharing a stream, i.e. having the same `stream-id` field. Types
+/// differing only in their state are expected as a stream evolves.
+fn check_stream_field_types(entities: &ConvertedEntities, warnings: &mut Warnings) {
+    let members: Vec<(String, &HashMap<domain::FieldName, domain::FieldDefinition>)> = entities
+        .commands
+        .iter()
+        .map(|(name, def)| (name.clone().into_inner().into_inner(), &def.data))
+        .chain(
+            entities
+                .events
+                .iter()
+                .map(|(name, def)| (name.clone().into_inner().into_inner(), &def.data)),
+        )
+        .collect();
+
+    let mut streams: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (index, (_, data)) in members.iter().enumerate() {
+        for (field, definition) in data.iter() {
+            if definition.stream_id {
+                streams
+                    .entry(field.clone().into_inner().into_inner())
+                    .or_default()
+                    .push(index);
+            }
+        }
+    }
+
+    for (stream, sharing) in streams {
+        let mut types_by_field: BTreeMap<String, BTreeMap<String, Vec<&str>>> = BTreeMap::new();
+        for &index in &sharing {
+            let (entity, data) = &members[index];
+            for (field, definition) in data.iter() {
+                types_by_field
+                    .entry(field.clone().into_inner().into_inner())
+                    .or_default()
+                    .entry(without_states(
+                        definition.field_type.clone().into_inner().as_str(),
+                    ))
+                    .or_default()
+                    .push(entity);
+            }
+        }
+        for (field, types) in types_by_field
+            .into_iter()
+            .filter(|(_, types)| types.len() > 1)
+        {
+            let described: Vec<String> = types
+                .into_iter()
+                .map(|(field_type, mut entities)| {
+                    entities.sort();
+                    format!("{field_type} in {}", entities.join(", "))
+                })
+                .collect();
+            warnings.warn(
+                WarningKind::FieldTypeMismatch,
+                format!(
+                    "Field '{field}' has different types in entities sharing the '{stream}' stream: {}",
+                    described.join("; ")
+                ),
+            );
+        }
+    }
+}
+
+/// Warns when a field of a command reappears in an event the command records,
+/// by a slice connection, with another type, or with the same type under a
+/// name spelled differently (`email` and `emailAddress`). Either is usually a
+/// copy and paste error. Types differing only in their state are expected.
+fn check_field_drift(
+    entities: &ConvertedEntities,
+    slices: &[domain::Slice],
+    warnings: &mut Warnings,
+) {
+    let mut pairs: BTreeSet<(String, String)> = BTreeSet::new();
+    for connection in slices.iter().flat_map(|slice| slice.connections.iter()) {
+        let (from, to) = (connection.from.entity_name(), connection.to.entity_name());
+        if entities.entity_type(&from) == Some(EntityType::Command)
+            && entities.entity_type(&to) == Some(EntityType::Event)
+        {
+            pairs.insert((from, to));
+        }
+    }
+
+    let fields = |data: &HashMap<domain::FieldName, domain::FieldDefinition>| {
+        data.iter()
+            .map(|(name, def)| {
+                (
+                    name.clone().into_inner().into_inner(),
+                    without_states(def.field_type.clone().into_inner().as_str()),
+                )
+            })
+            .collect::<BTreeMap<String, String>>()
+    };
+    for (command, event) in pairs {
+        let (Ok(command_name), Ok(event_name)) = (
+            NonEmptyString::parse(command.clone()),
+            NonEmptyString::parse(event.clone()),
+        ) else {
+            continue;
+        };
+        let command_fields =
+            fields(&entities.commands[&domain::CommandName::new(command_name)].data);
+        let event_fields = fields(&entities.events[&domain::EventName::new(event_name)].data);
+
+        for (field, command_type) in &command_fields {
+            match event_fields.get(field) {
+                Some(event_type) if event_type != command_type => warnings.warn(
+                    WarningKind::FieldDrift,
+                    format!(
+                        "Field '{field}' is {command_type} in command '{command}' but {event_type} in event '{event}', which it records"
+                    ),
+                ),
+                Some(_) => {}
+                None => {
+                    let renamed = event_fields.iter().filter(|(other, other_type)| {
+                        *other_type == command_type
+                            && !command_fields.contains_key(*other)
+                            && spelled_alike(field, other)
+                    });
+                    for (other, _) in renamed {
+                        warnings.warn(
+                            WarningKind::FieldDrift,
+                            format!(
+                                "Field '{field}' of command '{command}' is named '{other}' in event '{event}', which it records"
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns whether two field names likely name the same concept: the words of
+/// one are all in the other (`email` and `emailAddress`, `account_id` and
+/// `accountId`), or the names differ by one letter.
+fn spelled_alike(a: &str, b: &str) -> bool {
+    let (a_words, b_words) = (terminology::words(a), terminology::words(b));
+    let (shorter, longer) = if a_words.len() <= b_words.len() {
+        (&a_words, &b_words)
+    } else {
+        (&b_words, &a_words)
+    };
+    (!shorter.is_empty() && shorter.iter().all(|word| longer.contains(word)))
+        || terminology::alike(a, b)
+}
+
+/// Warns about `Then` steps naming something that is neither a defined event
+/// nor one of the errors declared by the command under test.
+fn check_test_outcomes(entities: &ConvertedEntities, warnings: &mut Warnings) {
+    let mut unknown: Vec<String> = Vec::new();
+    for (command, definition) in &entities.commands {
+        for (scenario, test) in &definition.tests {
+            for outcome in test.then.iter() {
+                let domain::TestOutcome::Event(event) = outcome else {
+                    continue;
+                };
+                if !entities.events.contains_key(&event.name) {
+                    unknown.push(format!(
+                        "Test '{}' of command '{}' expects '{}', which is neither an event nor one of the command's errors",
+                        scenario.clone().into_inner().as_str(),
+                        command.clone().into_inner().as_str(),
+                        event.name.clone().into_inner().as_str()
+                    ));
+                }
+            }
+        }
+    }
+    unknown.sort();
+    for message in unknown {
+        warnings.warn(WarningKind::UnknownTestOutcome, message);
+    }
+}
+
+/// Converts the model's marker table, whose keys are `from -> to` patterns
+/// of entity types or `*`.
+fn convert_markers(
+    markers: HashMap<String, parsing::YamlMarker>,
+) -> Result<domain::ConnectionMarkers, ConversionError> {
+    let entity_type = |name: &str| {
+        match name.trim() {
+        "*" => Ok(None),
+        other => entity_type_named(other).map(Some).ok_or_else(|| {
+            ConversionError::InvalidMarkers(format!(
+                "'{other}' is not an entity type (view, command, event, projection, query, automation) or '*'"
+            ))
+        }),
+    }
+    };
+    markers
+        .into_iter()
+        .map(|(pattern, marker)| {
+            let (from, to) = pattern.split_once("->").ok_or_else(|| {
+                ConversionError::InvalidMarkers(format!("expected 'from -> to', got '{pattern}'"))
+            })?;
+            let pattern = domain::ConnectionPattern {
+                from: entity_type(from)?,
+                to: entity_type(to)?,
+            };
+            Ok((pattern, convert_marker(marker)))
+        })
+        .collect::<Result<_, _>>()
+        .map(domain::ConnectionMarkers)
+}
+
+/// Returns the entity type written as `name` in a model, e.g. `command`.
+fn entity_type_named(name: &str) -> Option<EntityType> {
+    match name {
+        "view" => Some(EntityType::View),
+        "command" => Some(EntityType::Command),
+        "event" => Some(EntityType::Event),
+        "projection" => Some(EntityType::Projection),
+        "query" => Some(EntityType::Query),
+        "automation" => Some(EntityType::Automation),
+        _ => None,
+    }
+}
+
+/// Converts the custom entity kinds declared under `extensions`.
+fn convert_extensions(
+    extensions: HashMap<String, parsing::YamlExtension>,
+) -> Result<HashMap<domain::ExtensionName, domain::EntityExtension>, ConversionError> {
+    let declared: Vec<String> = extensions.keys().cloned().collect();
+    let mut result = HashMap::new();
+    for (name, extension) in extensions {
+        let invalid = |reason: String| ConversionError::InvalidExtension {
+            name: name.clone(),
+            reason,
+        };
+        if entity_type_named(&name).is_some() {
+            return Err(invalid("a built-in entity type has that name".to_string()));
+        }
+        let base = entity_type_named(&extension.base).ok_or_else(|| {
+            invalid(format!(
+                "base '{}' is not an entity type (view, command, event, projection, query, automation)",
+                extension.base
+            ))
+        })?;
+        let color = extension
+            .color
+            .map(|color| {
+                domain::SvgColor::try_new(color.clone()).map_err(|_| {
+                    invalid(format!(
+                        "color '{color}' is not a #rgb, #rrggbb, or named color"
+                    ))
+                })
+            })
+            .transpose()?;
+        let icon = extension
+            .icon
+            .map(|icon| {
+                domain::EntityIcon::try_new(icon.clone()).map_err(|_| {
+                    invalid(format!(
+                        "icon '{icon}' must be a short symbol without spaces"
+                    ))
+                })
+            })
+            .transpose()?;
+        let mut connects_to = Vec::new();
+        for target in extension.connects_to {
+            if entity_type_named(&target).is_none() && !declared.contains(&target) {
+                return Err(invalid(format!(
+                    "connects_to '{target}' is neither an entity type nor a declared kind"
+                )));
+            }
+            connects_to.push(domain::KindName::new(
+                NonEmptyString::parse(target)
+                    .map_err(|_| ConversionError::EmptyField("connects_to".to_string()))?,
+            ));
+        }
+        let name = NonEmptyString::parse(name)
+            .map(domain::ExtensionName::new)
+            .map_err(|_| ConversionError::EmptyField("extension name".to_string()))?;
+        result.insert(
+            name,
+            domain::EntityExtension {
+                base,
+                color,
+                icon,
+                connects_to,
+            },
+        );
+    }
+    Ok(result)
+}
+
+/// Checks that the kind of every entity declaring one is declared under
+/// `extensions` with the entity's type as its base.
+fn check_entity_kinds(
+    entities: &ConvertedEntities,
+    extensions: &HashMap<domain::ExtensionName, domain::EntityExtension>,
+) -> Result<(), ConversionError> {
+    for (entity, entity_type, kind) in entities.kinds() {
+        let invalid = |reason: String| ConversionError::InvalidKind {
+            entity: entity.clone(),
+            reason,
+        };
+        let name = kind.clone().into_inner().into_inner();
+        match extensions.get(kind) {
+            None => {
+                return Err(invalid(format!(
+                    "'{name}' is not declared under extensions"
+                )));
+            }
+            Some(extension) if extension.base != entity_type => {
+                return Err(invalid(format!(
+                    "'{name}' is based on {}, but '{entity}' is a {}",
+                    extension.base.name(),
+                    entity_type.name()
+                )));
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Checks that a connection leaving an entity of a custom kind leads to an
+/// entity type or kind the kind connects to. Both ends of a connection
+/// running both ways are checked.
+fn check_connection_kinds(
+    connection: &domain::Connection,
+    entities: &ConvertedEntities,
+    extensions: &HashMap<domain::ExtensionName, domain::EntityExtension>,
+    location: Option<SourceLocation>,
+) -> Result<(), ConversionError> {
+    let from = connection.from.entity_name();
+    let to = connection.to.entity_name();
+    let mut directions = vec![(&from, &to)];
+    if connection.bidirectional {
+        directions.push((&to, &from));
+    }
+    for (source, target) in directions {
+        let Some(kind) = entities.kind(source) else {
+            continue;
+        };
+        let Some(extension) = extensions.get(kind) else {
+            continue;
+        };
+        if extension.connects_to.is_empty() {
+            continue;
+        }
+        let target_kind = entities
+            .kind(target)
+            .map(|kind| kind.clone().into_inner().into_inner());
+        let target_type = entities.entity_type(target).map(EntityType::name);
+        let allowed: Vec<String> = extension
+            .connects_to
+            .iter()
+            .map(|name| name.clone().into_inner().into_inner())
+            .collect();
+        let permitted = allowed
+            .iter()
+            .any(|name| Some(name) == target_kind.as_ref() || Some(name.as_str()) == target_type);
+        if !permitted {
+            return Err(ConversionError::InvalidConnection {
+                message: format!(
+                    "'{from} {} {to}' connects {} '{source}' to '{target}'; a {} may only connect to {}",
+                    if connection.bidirectional {
+                        "<->"
+                    } else {
+                        "->"
+                    },
+                    kind.clone().into_inner().as_str(),
+                    kind.clone().into_inner().as_str(),
+                    allowed.join(", ")
+                ),
+                location,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Converts a marker shape.
+fn convert_marker(marker: parsing::YamlMarker) -> domain::MarkerStyle {
+    match marker {
+        parsing::YamlMarker::Filled => domain::MarkerStyle::Filled,
+        parsing::YamlMarker::Open => domain::MarkerStyle::Open,
+        parsing::YamlMarker::Diamond => domain::MarkerStyle::Diamond,
+        parsing::YamlMarker::Circle => domain::MarkerStyle::Circle,
+        parsing::YamlMarker::None => domain::MarkerStyle::None,
+    }
+}
+
+/// Converts the metadata given alongside the workflow title.
+fn convert_workflow_header(
+    workflow: parsing::YamlWorkflow,
+) -> Result<domain::WorkflowHeader, ConversionError> {
+    let parsing::YamlWorkflow::Header(header) = workflow else {
+        return Ok(domain::WorkflowHeader::default());
+    };
+    let text = |value: String, field: &str| {
+        NonEmptyString::parse(value).map_err(|_| ConversionError::EmptyField(field.to_string()))
+    };
+    Ok(domain::WorkflowHeader {
+        description: header
+            .description
+            .map(|description| text(description, "workflow description"))
+            .transpose()?
+            .map(domain::Description::new),
+        authors: header
+            .authors
+            .into_iter()
+            .map(|author| text(author, "workflow author").map(domain::AuthorName::new))
+            .collect::<Result<_, _>>()?,
+        version: header
+            .version
+            .map(|version| text(version, "workflow version"))
+            .transpose()?
+            .map(domain::ModelVersion::new),
+        date: header
+            .date
+            .map(|date| {
+                domain::ModelDate::try_new(date.clone())
+                    .map_err(|_| ConversionError::InvalidDate(date))
+            })
+            .transpose()?,
+    })
+}
+
+/// Converts the size policies of entity boxes; types without a policy of
+/// their own use the model's default.
+fn convert_entity_sizing(sizing: Option<parsing::YamlEntitySizing>) -> domain::EntitySizing {
+    let policy = |policy: parsing::YamlSizePolicy| match policy {
+        parsing::YamlSizePolicy::Standard => domain::SizePolicy::Standard,
+        parsing::YamlSizePolicy::Fixed => domain::SizePolicy::Fixed,
+        parsing::YamlSizePolicy::FitContent => domain::SizePolicy::FitContent,
+        parsing::YamlSizePolicy::Uniform => domain::SizePolicy::Uniform,
+    };
+    match sizing {
+        None => domain::EntitySizing::default(),
+        Some(parsing::YamlEntitySizing::All(all)) => domain::EntitySizing {
+            default: policy(all),
+            overrides: HashMap::new(),
+        },
+        Some(parsing::YamlEntitySizing::PerType(by_type)) => domain::EntitySizing {
+            default: by_type.default.map(policy).unwrap_or_default(),
+            overrides: [
+                (EntityType::View, by_type.views),
+                (EntityType::Command, by_type.commands),
+                (EntityType::Event, by_type.events),
+                (EntityType::Projection, by_type.projections),
+                (EntityType::Query, by_type.queries),
+                (EntityType::Automation, by_type.automations),
+            ]
+            .into_iter()
+            .filter_map(|(entity_type, sizing)| sizing.map(|sizing| (entity_type, policy(sizing))))
+            .collect(),
+        },
+    }
+}
+
+/// Converts the slice header styling, defaulting every unset attribute.
+fn convert_slice_headers(
+    headers: Option<parsing::YamlSliceHeaders>,
+) -> Result<domain::SliceHeaderStyle, ConversionError> {
+    let Some(headers) = headers else {
+        return Ok(domain::SliceHeaderStyle::default());
+    };
+    let positive = |value: Option<u32>, field: &str| {
+        value
+            .map(|value| {
+                PositiveInt::parse(value).map_err(|_| {
+                    ConversionError::InvalidSliceHeaders(format!("{field} must be at least 1"))
+                })
+            })
+            .transpose()
+    };
+    Ok(domain::SliceHeaderStyle {
+        height: positive(headers.height, "height")?,
+        background: headers
+            .background
+            .map(|color| {
+                domain::SvgColor::try_new(color.clone()).map_err(|_| {
+                    ConversionError::InvalidSliceHeaders(format!(
+                        "background '{color}' is not a #rgb, #rrggbb, or named color"
+                    ))
+                })
+            })
+            .transpose()?,
+        max_lines: positive(headers.max_lines, "max_lines")?,
+    })
+}
+
+/// Converts the room connectors keep from entities and from each other.
+fn convert_clearance(
+    routing: Option<parsing::YamlRouting>,
+) -> Result<domain::ConnectorClearance, ConversionError> {
+    let Some(routing) = routing else {
+        return Ok(domain::ConnectorClearance::default());
+    };
+    Ok(domain::ConnectorClearance {
+        margin: routing.margin.map(NonNegativeInt::new),
+        separation: routing
+            .separation
+            .map(|separation| {
+                PositiveInt::parse(separation).map_err(|_| {
+                    ConversionError::InvalidRouting("separation must be at least 1".to_string())
+                })
+            })
+            .transpose()?,
+    })
+}
+
+/// Converts the canvas background and watermark.
+fn convert_canvas(canvas: Option<parsing::YamlCanvas>) -> Result<domain::Canvas, ConversionError> {
+    let Some(canvas) = canvas else {
+        return Ok(domain::Canvas::default());
+    };
+    let background = |value: Option<String>, field: &str| {
+        value
+            .map(|color| {
+                if color == "transparent" {
+                    return Ok(domain::CanvasBackground::Transparent);
+                }
+                domain::SvgColor::try_new(color.clone())
+                    .map(domain::CanvasBackground::Color)
+                    .map_err(|_| {
+                        ConversionError::InvalidCanvas(format!(
+                            "{field} '{color}' is not a #rgb, #rrggbb, or named color, or transparent"
+                        ))
+                    })
+            })
+            .transpose()
+    };
+    Ok(domain::Canvas {
+        background: background(canvas.background, "background")?,
+        dark_background: background(canvas.dark_background, "dark_background")?,
+        watermark: canvas
+            .watermark
+            .map(|text| {
+                NonEmptyString::parse(text.trim().to_string()).map_err(|_| {
+                    ConversionError::InvalidCanvas("watermark must not be empty".to_string())
+                })
+            })
+            .transpose()?,
+    })
+}
+
+/// Converts the rules of the constraint layout, checking that each names
+/// entities the model defines.
+fn convert_layout_constraints(
+    constraints: Vec<parsing::YamlLayoutConstraint>,
+    entities: &ConvertedEntities,
+) -> Result<Vec<domain::LayoutConstraint>, ConversionError> {
+    let known = entities.names();
+    let check = |name: &String| {
+        if known.contains(name) {
+            Ok(())
+        } else {
+            Err(ConversionError::UnknownConstraintEntity { name: name.clone() })
+        }
+    };
+    constraints
+        .into_iter()
+        .map(|constraint| {
+            Ok(match constraint {
+                parsing::YamlLayoutConstraint::Align(parsing::YamlAlign { align }) => {
+                    align.iter().try_for_each(check)?;
+                    domain::LayoutConstraint::Align(align)
+                }
+                parsing::YamlLayoutConstraint::Order(parsing::YamlOrder { order }) => {
+                    order.iter().try_for_each(check)?;
+                    domain::LayoutConstraint::Order(order)
+                }
+                parsing::YamlLayoutConstraint::Gap(parsing::YamlGapConstraint { gap }) => {
+                    let [left, right] = gap.between;
+                    check(&left)?;
+                    check(&right)?;
+                    domain::LayoutConstraint::Gap {
+                        left,
+                        right,
+                        min: gap.min,
+                    }
+                }
+            })
+        })
+        .collect()
+}
+
+/// Entity definitions converted to domain types, keyed by name.
+struct ConvertedEntities {
+    events: HashMap<domain::EventName, domain::EventDefinition>,
+    commands: HashMap<domain::CommandName, domain::CommandDefinition>,
+    views: HashMap<domain::ViewName, domain::ViewDefinition>,
+    projections: HashMap<domain::ProjectionName, domain::ProjectionDefinition>,
+    queries: HashMap<domain::QueryName, domain::QueryDefinition>,
+    automations: HashMap<domain::AutomationName, domain::AutomationDefinition>,
+}
+
+impl ConvertedEntities {
+    /// Converts the entities of a fragment, validating swimlane references.
+    fn convert(
+        fragment: &parsing::YamlModelFragment,
+        swimlane_ids: &[String],
+        source_map: &SourceMap,
+    ) -> Result<Self, ConversionError> {
+        Ok(Self {
+            events: convert_events(fragment.events.clone(), swimlane_ids, source_map)?,
+            commands: convert_commands(fragment.commands.clone(), swimlane_ids, source_map)?,
+            views: convert_views(fragment.views.clone(), swimlane_ids, source_map)?,
+            projections: convert_projections(
+                fragment.projections.clone(),
+                swimlane_ids,
+                source_map,
+            )?,
+            queries: convert_queries(fragment.queries.clone(), swimlane_ids, source_map)?,
+            automations: convert_automations(
+                fragment.automations.clone(),
+                swimlane_ids,
+                source_map,
+            )?,
+        })
+    }
+
+    /// Returns the names of all entities, regardless of type.
+    fn names(&self) -> Vec<String> {
+        let name = |inner: NonEmptyString| inner.into_inner();
+        self.events
+            .keys()
+            .map(|n| name(n.clone().into_inner()))
+            .chain(self.commands.keys().map(|n| name(n.clone().into_inner())))
+            .chain(self.views.keys().map(|n| name(n.clone().into_inner())))
+            .chain(
+                self.projections
+                    .keys()
+                    .map(|n| name(n.clone().into_inner())),
+            )
+            .chain(self.queries.keys().map(|n| name(n.clone().into_inner())))
+            .chain(
+                self.automations
+                    .keys()
+                    .map(|n| name(n.clone().into_inner())),
+            )
+            .collect()
+    }
+
+    /// Returns the type of the entity with the given name, if it is defined.
+    fn entity_type(&self, name: &str) -> Option<EntityType> {
+        let name = NonEmptyString::parse(name.to_string()).ok()?;
+        if self
+            .events
+            .contains_key(&domain::EventName::new(name.clone()))
+        {
+            Some(EntityType::Event)
+        } else if self
+            .commands
+            .contains_key(&domain::CommandName::new(name.clone()))
+        {
+            Some(EntityType::Command)
+        } else if self
+            .views
+            .contains_key(&domain::ViewName::new(name.clone()))
+        {
+            Some(EntityType::View)
+        } else if self
+            .projections
+            .contains_key(&domain::ProjectionName::new(name.clone()))
+        {
+            Some(EntityType::Projection)
+        } else if self
+            .queries
+            .contains_key(&domain::QueryName::new(name.clone()))
+        {
+            Some(EntityType::Query)
+        } else if self
+            .automations
+            .contains_key(&domain::AutomationName::new(name))
+        {
+            Some(EntityType::Automation)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the id of the swimlane the entity with the given name is drawn
+    /// in, if it is defined.
+    fn swimlane_of(&self, name: &str) -> Option<String> {
+        let name = NonEmptyString::parse(name.to_string()).ok()?;
+        let lane = self
+            .events
+            .get(&domain::EventName::new(name.clone()))
+            .map(|d| &d.swimlane)
+            .or_else(|| {
+                self.commands
+                    .get(&domain::CommandName::new(name.clone()))
+                    .map(|d| &d.swimlane)
+            })
+            .or_else(|| {
+                self.views
+                    .get(&domain::ViewName::new(name.clone()))
+                    .map(|d| &d.swimlane)
+            })
+            .or_else(|| {
+                self.projections
+                    .get(&domain::ProjectionName::new(name.clone()))
+                    .map(|d| &d.swimlane)
+            })
+            .or_else(|| {
+                self.queries
+                    .get(&domain::QueryName::new(name.clone()))
+                    .map(|d| &d.swimlane)
+            })
+            .or_else(|| {
+                self.automations
+                    .get(&domain::AutomationName::new(name))
+                    .map(|d| &d.swimlane)
+            })?;
+        Some(lane.clone().into_inner().into_inner())
+    }
+
+    /// Returns the entities declaring a custom kind with their type and kind,
+    /// ordered by name.
+    fn kinds(&self) -> Vec<(String, EntityType, &domain::ExtensionName)> {
+        let name = |inner: NonEmptyString| inner.into_inner();
+        let mut kinds: Vec<_> = self
+            .events
+            .iter()
+            .filter_map(|(n, d)| {
+                let kind = d.kind.as_ref()?;
+                Some((name(n.clone().into_inner()), EntityType::Event, kind))
+            })
+            .chain(self.commands.iter().filter_map(|(n, d)| {
+                let kind = d.kind.as_ref()?;
+                Some((name(n.clone().into_inner()), EntityType::Command, kind))
+            }))
+            .chain(self.views.iter().filter_map(|(n, d)| {
+                let kind = d.kind.as_ref()?;
+                Some((name(n.clone().into_inner()), EntityType::View, kind))
+            }))
+            .chain(self.projections.iter().filter_map(|(n, d)| {
+                let kind = d.kind.as_ref()?;
+                Some((name(n.clone().into_inner()), EntityType::Projection, kind))
+            }))
+            .chain(self.queries.iter().filter_map(|(n, d)| {
+                let kind = d.kind.as_ref()?;
+                Some((name(n.clone().into_inner()), EntityType::Query, kind))
+            }))
+            .chain(self.automations.iter().filter_map(|(n, d)| {
+                let kind = d.kind.as_ref()?;
+                Some((name(n.clone().into_inner()), EntityType::Automation, kind))
+            }))
+            .collect();
+        kinds.sort_by(|a, b| a.0.cmp(&b.0));
+        kinds
+    }
+
+    /// Returns the custom kind of the entity with the given name, if it
+    /// declares one.
+    fn kind(&self, name: &str) -> Option<&domain::ExtensionName> {
+        let name = NonEmptyString::parse(name.to_string()).ok()?;
+        let kind = match self.entity_type(name.as_str())? {
+            EntityType::Event => &self.events[&domain::EventName::new(name)].kind,
+            EntityType::Command => &self.commands[&domain::CommandName::new(name)].kind,
+            EntityType::View | EntityType::Wireframe => {
+                &self.views[&domain::ViewName::new(name)].kind
+            }
+            EntityType::Projection => &self.projections[&domain::ProjectionName::new(name)].kind,
+            EntityType::Query => &self.queries[&domain::QueryName::new(name)].kind,
+            EntityType::Automation => &self.automations[&domain::AutomationName::new(name)].kind,
+        };
+        kind.as_ref()
+    }
+
+    /// Adds another set of entities, rejecting any name that is already defined.
+    fn absorb(&mut self, other: Self) -> Result<(), ConversionError> {
+        let existing = self.names();
+        if let Some(duplicate) = other.names().into_iter().find(|n| existing.contains(n)) {
+            return Err(ConversionError::DuplicateEntity(duplicate));
+        }
+        self.events.extend(other.events);
+        self.commands.extend(other.commands);
+        self.views.extend(other.views);
+        self.projections.extend(other.projections);
+        self.queries.extend(other.queries);
+        self.automations.extend(other.automations);
+        Ok(())
+    }
+}
+
+/// A template instantiated for one argument.
+struct TemplateInstance {
+    template: String,
+    argument: String,
+    fragment: parsing::YamlModelFragment,
+}
+
+impl TemplateInstance {
+    /// Wraps an error raised while converting this instance with its origin.
+    fn provenance(&self, error: ConversionError) -> ConversionError {
+        ConversionError::InTemplate {
+            template: self.template.clone(),
+            argument: self.argument.clone(),
+            source: Box::new(error),
+        }
+    }
+}
+
+/// Adds the fields of the field sets each event and command `extends` to its
+/// data. Fields an entity declares itself override included ones.
+fn include_field_sets(
+    fragment: &mut parsing::YamlModelFragment,
+    field_sets: &HashMap<String, parsing::YamlFieldSet>,
+) -> Result<(), ConversionError> {
+    let entities = fragment
+        .events
+        .iter_mut()
+        .map(|(name, event)| (format!("event '{name}'"), &event.extends, &mut event.data))
+        .chain(fragment.commands.iter_mut().map(|(name, command)| {
+            (
+                format!("command '{name}'"),
+                &command.extends,
+                &mut command.data,
+            )
+        }));
+    for (extender, extends, data) in entities {
+        if extends.is_empty() {
+            continue;
+        }
+        let mut fields = extended_fields(&extender, extends, field_sets, &mut Vec::new())?;
+        fields.extend(std::mem::take(data));
+        *data = fields;
+    }
+    Ok(())
+}
+
+/// Returns the fields of the named field sets, following the sets they extend
+/// in turn. `path` holds the sets being expanded, to detect cycles.
+fn extended_fields(
+    extender: &str,
+    extends: &[String],
+    field_sets: &HashMap<String, parsing::YamlFieldSet>,
+    path: &mut Vec<String>,
+) -> Result<HashMap<String, parsing::YamlField>, ConversionError> {
+    // Each field with the set that gave it
+    let mut fields: HashMap<String, (parsing::YamlField, &str)> = HashMap::new();
+    for name in extends {
+        let set = field_sets
+            .get(name)
+            .ok_or_else(|| ConversionError::UnknownFieldSet {
+                name: name.clone(),
+                extender: extender.to_string(),
+            })?;
+        if let Some(start) = path.iter().position(|seen| seen == name) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(name.clone());
+            return Err(ConversionError::FieldSetCycle { cycle });
+        }
+        path.push(name.clone());
+        let mut included = extended_fields(
+            &format!("field set '{name}'"),
+            &set.extends,
+            field_sets,
+            path,
+        )?;
+        path.pop();
+        included.extend(set.fields.clone());
+
+        for (field, definition) in included {
+            match fields.get(&field) {
+                Some((existing, first)) if *existing != definition => {
+                    return Err(ConversionError::ConflictingFieldSets {
+                        extender: extender.to_string(),
+                        field,
+                        first: first.to_string(),
+                        second: name.clone(),
+                    });
+                }
+                Some(_) => {}
+                None => {
+                    fields.insert(field, (definition, name));
+                }
+            }
+        }
+    }
+    Ok(fields
+        .into_iter()
+        .map(|(field, (definition, _))| (field, definition))
+        .collect())
+}
+
+/// Placeholder replaced by each `for_each` argument when a template is expanded.
+pub(super) const TEMPLATE_PLACEHOLDER: &str = "{{name}}";
+
+/// Instantiates every requested template once per argument, substituting the
+/// argument for the placeholder in all names and text.
+fn instantiate_templates(
+    templates: &HashMap<String, parsing::YamlModelFragment>,
+    expansions: &[parsing::YamlTemplateExpansion],
+) -> Result<Vec<TemplateInstance>, ConversionError> {
+    let mut instances = Vec::new();
+    for expansion in expansions {
+        let template = templates
+            .get(&expansion.template)
+            .ok_or_else(|| ConversionError::UnknownTemplate(expansion.template.clone()))?;
+        let body = serde_yaml::to_value(template)
+            .map_err(|e| ConversionError::InvalidTemplate(e.to_string()))?;
+
+        for argument in &expansion.for_each {
+            let mut instance = TemplateInstance {
+                template: expansion.template.clone(),
+                argument: argument.clone(),
+                fragment: parsing::YamlModelFragment::default(),
+            };
+            let expanded = substitute_placeholder(&body, argument)
+                .map_err(|error| instance.provenance(error))?;
+            instance.fragment = serde_yaml::from_value(expanded).map_err(|e| {
+                instance.provenance(ConversionError::InvalidTemplate(e.to_string()))
+            })?;
+            instances.push(instance);
+        }
+    }
+    Ok(instances)
+}
+
+/// Replaces the template placeholder in every string and mapping key.
+fn substitute_placeholder(
+    value: &serde_yaml::Value,
+    argument: &str,
+) -> Result<serde_yaml::Value, ConversionError> {
+    use serde_yaml::Value;
+
+    let substitute = |text: &str| {
+        let replaced = text.replace(TEMPLATE_PLACEHOLDER, argument);
+        if replaced.contains("{{") {
+            Err(ConversionError::UnresolvedPlaceholder(text.to_string()))
+        } else {
+            Ok(replaced)
+        }
+    };
+
+    Ok(match value {
+        Value::String(text) => Value::String(substitute(text)?),
+        Value::Sequence(items) => Value::Sequence(
+            items
+                .iter()
+                .map(|item| substitute_placeholder(item, argument))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Mapping(mapping) => {
+            let mut substituted = serde_yaml::Mapping::new();
+            for (key, item) in mapping {
+                substituted.insert(
+                    substitute_placeholder(key, argument)?,
+                    substitute_placeholder(item, argument)?,
+                );
+            }
+            Value::Mapping(substituted)
+        }
+        other => other.clone(),
+    })
+}
+
+/// Converts swimlane definitions, flattening groups into the swimlane list
+/// and returning the groups alongside it.
+fn convert_swimlanes(
+    swimlanes: Vec<parsing::YamlSwimlane>,
+) -> Result<(NonEmpty<domain::Swimlane>, Vec<domain::SwimlaneGroup>), ConversionError> {
+    let mut result = Vec::new();
+    let mut groups = Vec::new();
+
+    for swimlane in swimlanes {
+        match swimlane {
+            parsing::YamlSwimlane::Group(group) => {
+                let first = result.len();
+                for member in group.swimlanes {
+                    convert_swimlane(member, &mut result)?;
+                }
+                groups.push(convert_swimlane_group(
+                    group.group,
+                    result[first..].iter().map(|lane| lane.id.clone()).collect(),
+                    group.collapsed,
+                    group.background,
+                )?);
+            }
+            other => convert_swimlane(other, &mut result)?,
+        }
+    }
+
+    Ok((vec_to_non_empty(result, "swimlanes")?, groups))
+}
+
+/// Converts a group's label and attributes, given its flattened members.
+fn convert_swimlane_group(
+    name: String,
+    members: Vec<domain::SwimlaneId>,
+    collapsed: bool,
+    background: Option<String>,
+) -> Result<domain::SwimlaneGroup, ConversionError> {
+    let swimlanes = vec_to_non_empty(members, &format!("swimlanes of group '{name}'"))?;
+    let background = background
+        .map(|color| {
+            domain::SvgColor::try_new(color.clone()).map_err(|_| {
+                ConversionError::InvalidSwimlaneGroup(format!(
+                    "'{name}' background '{color}' is not a #rgb, #rrggbb, or named color"
+                ))
+            })
+        })
+        .transpose()?;
+    Ok(domain::SwimlaneGroup {
+        name: domain::SwimlaneGroupName::new(
+            NonEmptyString::parse(name)
+                .map_err(|_| ConversionError::EmptyField("swimlane group name".to_string()))?,
+        ),
+        swimlanes,
+        collapsed,
+        background,
+    })
+}
+
+/// Converts a single non-group swimlane entry, appending its lanes.
+fn convert_swimlane(
+    swimlane: parsing::YamlSwimlane,
+    result: &mut Vec<domain::Swimlane>,
+) -> Result<(), ConversionError> {
+    match swimlane {
+        parsing::YamlSwimlane::Simple(name) => {
+            // For simple format, use the name as both ID and display name
+            let id = domain::SwimlaneId::new(
+                NonEmptyString::parse(name.clone())
+                    .map_err(|_| ConversionError::EmptyField("swimlane ID".to_string()))?,
+            );
+            let display_name = domain::SwimlaneName::new(
+                NonEmptyString::parse(name)
+                    .map_err(|_| ConversionError::EmptyField("swimlane name".to_string()))?,
+            );
+            result.push(domain::Swimlane {
+                id,
+                name: display_name,
+                owner: None,
+                style: domain::SwimlaneStyle::default(),
+                role: None,
+            });
+        }
+        parsing::YamlSwimlane::Map(map) => {
+            // For map format, key is ID, value is display name
+            for (id_str, name_str) in map {
+                let id = domain::SwimlaneId::new(
+                    NonEmptyString::parse(id_str)
+                        .map_err(|_| ConversionError::EmptyField("swimlane ID".to_string()))?,
+                );
+                let name = domain::SwimlaneName::new(
+                    NonEmptyString::parse(name_str)
+                        .map_err(|_| ConversionError::EmptyField("swimlane name".to_string()))?,
+                );
+                result.push(domain::Swimlane {
+                    id,
+                    name,
+                    owner: None,
+                    style: domain::SwimlaneStyle::default(),
+                    role: None,
+                });
+            }
+        }
+        parsing::YamlSwimlane::Group(group) => {
+            return Err(ConversionError::InvalidSwimlaneGroup(format!(
+                "'{}' contains another group; groups cannot be nested",
+                group.group
+            )));
+        }
+        parsing::YamlSwimlane::Detailed(map) => {
+            for (id_str, details) in map {
+                let id = domain::SwimlaneId::new(
+                    NonEmptyString::parse(id_str.clone())
+                        .map_err(|_| ConversionError::EmptyField("swimlane ID".to_string()))?,
+                );
+                let name = domain::SwimlaneName::new(
+                    NonEmptyString::parse(details.name)
+                        .map_err(|_| ConversionError::EmptyField("swimlane name".to_string()))?,
+                );
+                let style = convert_swimlane_style(&id_str, details.color, details.icon)?;
+                result.push(domain::Swimlane {
+                    id,
+                    name,
+                    owner: convert_owner(details.owner)?,
+                    style,
+                    role: details.role.map(|role| match role {
+                        parsing::YamlSwimlaneRole::Interface => domain::SwimlaneRole::Interface,
+                        parsing::YamlSwimlaneRole::Application => domain::SwimlaneRole::Application,
+                        parsing::YamlSwimlaneRole::Stream => domain::SwimlaneRole::Stream,
+                    }),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Converts a swimlane's tint and icon.
+fn convert_swimlane_style(
+    id: &str,
+    color: Option<String>,
+    icon: Option<String>,
+) -> Result<domain::SwimlaneStyle, ConversionError> {
+    Ok(domain::SwimlaneStyle {
+        color: color
+            .map(|color| {
+                domain::SvgColor::try_new(color.clone()).map_err(|_| {
+                    ConversionError::InvalidSwimlaneStyle(format!(
+                        "'{id}' color '{color}' is not a #rgb, #rrggbb, or named color"
+                    ))
+                })
+            })
+            .transpose()?,
+        icon: icon
+            .map(|icon| {
+                domain::SwimlaneIcon::try_new(icon.clone()).map_err(|_| {
+                    ConversionError::InvalidSwimlaneStyle(format!(
+                        "'{id}' icon '{icon}' must be a short symbol without spaces"
+                    ))
+                })
+            })
+            .transpose()?,
+    })
+}
+
+/// Converts the name of an entity's custom kind.
+fn convert_kind(kind: Option<String>) -> Result<Option<domain::ExtensionName>, ConversionError> {
+    kind.map(|kind| {
+        NonEmptyString::parse(kind)
+            .map(domain::ExtensionName::new)
+            .map_err(|_| ConversionError::EmptyField("kind".to_string()))
+    })
+    .transpose()
+}
+
+/// Converts the risk raised about an entity or connection.
+fn convert_hotspot(hotspot: Option<String>) -> Result<Option<domain::Hotspot>, ConversionError> {
+    hotspot
+        .map(|hotspot| {
+            NonEmptyString::parse(hotspot)
+                .map(domain::Hotspot::new)
+                .map_err(|_| ConversionError::EmptyField("hotspot".to_string()))
+        })
+        .transpose()
+}
+
+/// Converts an optional owner name.
+fn convert_owner(owner: Option<String>) -> Result<Option<domain::OwnerName>, ConversionError> {
+    owner
+        .map(|owner| {
+            NonEmptyString::parse(owner)
+                .map(domain::OwnerName::new)
+                .map_err(|_| ConversionError::EmptyField("owner".to_string()))
+        })
+        .transpose()
+}
+
+/// Converts optional entity documentation.
+fn convert_documentation(
+    doc: Option<parsing::YamlDocumentation>,
+) -> Result<Option<domain::Documentation>, ConversionError> {
+    doc.map(|doc| match doc {
+        parsing::YamlDocumentation::Markdown(text) => {
+            NonEmptyString::parse(text.trim().to_string())
+                .map(|text| domain::Documentation::Markdown(domain::DocumentationText::new(text)))
+                .map_err(|_| ConversionError::EmptyField("doc".to_string()))
+        }
+        parsing::YamlDocumentation::File(link) => {
+            PathBuilder::parse_markdown_file(PathBuf::from(&link.file))
+                .map(domain::Documentation::File)
+                .map_err(|_| ConversionError::InvalidDocumentationFile(link.file))
+        }
+    })
+    .transpose()
+}
+
+/// Converts the references to an entity's implementation, telling URLs,
+/// Rust item paths, and file paths apart by their form.
+fn convert_implementation_links(
+    links: Vec<String>,
+) -> Result<Vec<domain::ImplementationLink>, ConversionError> {
+    links
+        .into_iter()
+        .map(|link| {
+            let text = NonEmptyString::parse(link.trim().to_string())
+                .map_err(|_| ConversionError::EmptyField("implemented_by".to_string()))?;
+            let link =
+                if text.as_str().starts_with("https://") || text.as_str().starts_with("http://") {
+                    domain::ImplementationLink::Url(domain::UrlTemplate::new(text))
+                } else if text.as_str().contains("::") && !text.as_str().contains('/') {
+                    domain::ImplementationLink::RustPath(domain::RustItemPath::new(text))
+                } else if domain::has_uri_scheme(text.as_str()) {
+                    return Err(ConversionError::InvalidImplementationLink(
+                        text.into_inner(),
+                    ));
+                } else {
+                    domain::ImplementationLink::File(domain::SourceFilePath::new(text))
+                };
+            Ok(link)
+        })
+        .collect()
+}
+
+/// Converts event definitions.
+fn convert_events(
+    events: HashMap<String, parsing::YamlEvent>,
+    swimlane_ids: &[String],
+    source_map: &SourceMap,
+) -> Result<HashMap<domain::EventName, domain::EventDefinition>, ConversionError> {
+    let mut result = HashMap::new();
+
+    for (name_str, event) in events {
+        // Validate swimlane reference
+        let entity_path = SourcePath::root().key("events").key(name_str.as_str());
+        validate_swimlane(&event.swimlane, swimlane_ids, entity_path, source_map)?;
+
+        let name = domain::EventName::new(
+            NonEmptyString::parse(name_str)
+                .map_err(|_| ConversionError::EmptyField("event name".to_string()))?,
+        );
+
+        let definition = domain::EventDefinition {
+            description: domain::Description::new(
+                NonEmptyString::parse(event.description)
+                    .map_err(|_| ConversionError::EmptyField("event description".to_string()))?,
+            ),
+            swimlane: domain::SwimlaneId::new(
+                NonEmptyString::parse(event.swimlane)
+                    .map_err(|_| ConversionError::EmptyField("swimlane ID".to_string()))?,
+            ),
+            data: convert_field_definitions(event.data)?,
+            owner: convert_owner(event.owner)?,
+            kind: convert_kind(event.kind)?,
+            hotspot: convert_hotspot(event.hotspot)?,
+            volume: convert_volume(event.volume, name.clone().into_inner().as_str())?,
+            documentation: convert_documentation(event.doc)?,
+            implemented_by: convert_implementation_links(event.implemented_by)?,
+        };
+
+        result.insert(name, definition);
+    }
+
+    Ok(result)
+}
+
+/// Converts field definitions from parsing to domain types.
+fn convert_field_definitions(
+    fields: HashMap<String, parsing::YamlField>,
+) -> Result<HashMap<domain::FieldName, domain::FieldDefinition>, ConversionError> {
+    let mut result = HashMap::new();
+
+    for (name_str, field) in fields {
+        let name = domain::FieldName::new(
+            NonEmptyString::parse(name_str)
+                .map_err(|_| ConversionError::EmptyField("field name".to_string()))?,
+        );
+
+        let definition = match field {
+            parsing::YamlField::Simple(type_str) => domain::FieldDefinition {
+                field_type: domain::FieldType::new(
+                    NonEmptyString::parse(type_str)
+                        .map_err(|_| ConversionError::EmptyField("field type".to_string()))?,
+                ),
+                stream_id: false,
+                generated: false,
+                pii: false,
+            },
+            parsing::YamlField::Complex {
+                field_type,
+                stream_id,
+                generated,
+                pii,
+            } => domain::FieldDefinition {
+                field_type: domain::FieldType::new(
+                    NonEmptyString::parse(field_type)
+                        .map_err(|_| ConversionError::EmptyField("field type".to_string()))?,
+                ),
+                stream_id,
+                generated,
+                pii,
+            },
+        };
+
+        result.insert(name, definition);
+    }
+
+    Ok(result)
+}
+
+/// Converts command definitions.
+fn convert_commands(
+    commands: HashMap<String, parsing::YamlCommand>,
+    swimlane_ids: &[String],
+    source_map: &SourceMap,
+) -> Result<HashMap<domain::CommandName, domain::CommandDefinition>, ConversionError> {
+    let mut result = HashMap::new();
+
+    for (name_str, command) in commands {
+        // Validate swimlane reference
+        let entity_path = SourcePath::root().key("commands").key(name_str.as_str());
+        validate_swimlane(&command.swimlane, swimlane_ids, entity_path, source_map)?;
+
+        let name = domain::CommandName::new(
+            NonEmptyString::parse(name_str)
+                .map_err(|_| ConversionError::EmptyField("command name".to_string()))?,
+        );
+
+        let errors = convert_command_errors(command.errors)?;
+        let definition = domain::CommandDefinition {
+            description: domain::Description::new(
+                NonEmptyString::parse(command.description)
+                    .map_err(|_| ConversionError::EmptyField("command description".to_string()))?,
+            ),
+            swimlane: domain::SwimlaneId::new(
+                NonEmptyString::parse(command.swimlane)
+                    .map_err(|_| ConversionError::EmptyField("swimlane ID".to_string()))?,
+            ),
+            data: convert_field_definitions(command.data)?,
+            tests: convert_test_scenarios(
+                command.tests,
+                name.clone().into_inner().as_str(),
+                &errors,
+            )?,
+            errors,
+            idempotent: command.idempotent,
+            volume: convert_volume(command.volume, name.clone().into_inner().as_str())?,
+            authorized_roles: command
+                .authorized_roles
+                .into_iter()
+                .map(convert_role)
+                .collect::<Result<_, _>>()?,
+            owner: convert_owner(command.owner)?,
+            kind: convert_kind(command.kind)?,
+            hotspot: convert_hotspot(command.hotspot)?,
+            documentation: convert_documentation(command.doc)?,
+            implemented_by: convert_implementation_links(command.implemented_by)?,
+        };
+
+        result.insert(name, definition);
+    }
+
+    Ok(result)
+}
+
+/// Converts the errors a command declares.
+fn convert_command_errors(
+    errors: HashMap<String, parsing::YamlCommandError>,
+) -> Result<HashMap<domain::ErrorTypeName, domain::CommandErrorDefinition>, ConversionError> {
+    let mut result = HashMap::new();
+
+    for (name_str, error) in errors {
+        let name = domain::ErrorTypeName::new(
+            NonEmptyString::parse(name_str)
+                .map_err(|_| ConversionError::EmptyField("error name".to_string()))?,
+        );
+        let definition = domain::CommandErrorDefinition {
+            description: domain::Description::new(
+                NonEmptyString::parse(error.description)
+                    .map_err(|_| ConversionError::EmptyField("error description".to_string()))?,
+            ),
+            data: convert_field_definitions(error.data)?,
+        };
+        result.insert(name, definition);
+    }
+
+    Ok(result)
+}
+
+/// Converts test scenarios; `Then` steps naming one of the command's
+/// `errors` expect the command to be rejected.
+fn convert_test_scenarios(
+    tests: HashMap<String, parsing::YamlTestScenario>,
+    command: &str,
+    errors: &HashMap<domain::ErrorTypeName, domain::CommandErrorDefinition>,
+) -> Result<HashMap<domain::TestScenarioName, domain::TestScenario>, ConversionError> {
+    let mut result = HashMap::new();
+
+    for (name_str, scenario) in tests {
+        let empty_section = |section| ConversionError::EmptyScenarioSection {
+            command: command.to_string(),
+            scenario: name_str.clone(),
+            section,
+        };
+        if scenario.when.is_empty() {
+            return Err(empty_section("When"));
+        }
+        if scenario.then.is_empty() {
+            return Err(empty_section("Then"));
+        }
+        let name = domain::TestScenarioName::new(
+            NonEmptyString::parse(name_str)
+                .map_err(|_| ConversionError::EmptyField("test scenario name".to_string()))?,
+        );
+
+        // Convert Given events
+        let given = convert_test_events(scenario.given)?;
+
+        // Convert When actions
+        let when_actions = convert_test_actions(scenario.when)?;
+        let when = vec_to_non_empty(when_actions, "when actions")?;
+
+        // Convert Then outcomes
+        let then_outcomes = convert_test_events(scenario.then)?
+            .into_iter()
+            .map(|event| {
+                let name = domain::ErrorTypeName::new(event.name.clone().into_inner());
+                if errors.contains_key(&name) {
+                    domain::TestOutcome::Error(domain::TestError {
+                        name,
+                        fields: event.fields,
+                    })
+                } else {
+                    domain::TestOutcome::Event(event)
+                }
+            })
+            .collect();
+        let then = vec_to_non_empty(then_outcomes, "then events")?;
+
+        let examples = scenario
+            .examples
+            .into_iter()
+            .map(|(placeholder, example)| {
+                let placeholder = NonEmptyString::parse(placeholder)
+                    .map(domain::PlaceholderValue::new)
+                    .map_err(|_| ConversionError::EmptyField("placeholder value".to_string()))?;
+                let example = NonEmptyString::parse(example)
+                    .map(domain::ExampleValue::new)
+                    .map_err(|_| ConversionError::EmptyField("example value".to_string()))?;
+                Ok((placeholder, example))
+            })
+            .collect::<Result<_, ConversionError>>()?;
+
+        let test_scenario = domain::TestScenario {
+            given,
+            when,
+            then,
+            examples,
+        };
+
+        result.insert(name, test_scenario);
+    }
+
+    Ok(result)
+}
+
+/// Converts test events.
+fn convert_test_events(
+    events: Vec<parsing::YamlTestStep>,
+) -> Result<Vec<domain::TestEvent>, ConversionError> {
+    let mut result = Vec::new();
+
+    for step in events {
+        for (entity_name, fields) in step.step {
+            let event_name = domain::EventName::new(
+                NonEmptyString::parse(entity_name)
+                    .map_err(|_| ConversionError::EmptyField("test event name".to_string()))?,
+            );
+
+            let mut event_fields = HashMap::new();
+            for (field_name, value) in fields {
+                let field = domain::FieldName::new(
+                    NonEmptyString::parse(field_name)
+                        .map_err(|_| ConversionError::EmptyField("test field name".to_string()))?,
+                );
+                let placeholder =
+                    domain::PlaceholderValue::new(NonEmptyString::parse(value).map_err(|_| {
+                        ConversionError::EmptyField("placeholder value".to_string())
+                    })?);
+                event_fields.insert(field, placeholder);
+            }
+
+            result.push(domain::TestEvent {
+                name: event_name,
+                fields: event_fields,
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+/// Converts test actions (commands).
+fn convert_test_actions(
+    actions: Vec<parsing::YamlTestStep>,
+) -> Result<Vec<domain::TestAction>, ConversionError> {
+    let mut result = Vec::new();
+
+    for step in actions {
+        for (entity_name, fields) in step.step {
+            let command_name = domain::CommandName::new(
+                NonEmptyString::parse(entity_name)
+                    .map_err(|_| ConversionError::EmptyField("test command name".to_string()))?,
+            );
+
+            let mut command_fields = HashMap::new();
+            for (field_name, value) in fields {
+                let field = domain::FieldName::new(
+                    NonEmptyString::parse(field_name)
+                        .map_err(|_| ConversionError::EmptyField("test field name".to_string()))?,
+                );
+                let placeholder =
+                    domain::PlaceholderValue::new(NonEmptyString::parse(value).map_err(|_| {
+                        ConversionError::EmptyField("placeholder value".to_string())
+                    })?);
+                command_fields.insert(field, placeholder);
+            }
+
+            result.push(domain::TestAction {
+                name: command_name,
+                fields: command_fields,
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+/// Converts view definitions.
+fn convert_views(
+    views: HashMap<String, parsing::YamlView>,
+    swimlane_ids: &[String],
+    source_map: &SourceMap,
+) -> Result<HashMap<domain::ViewName, domain::ViewDefinition>, ConversionError> {
+    let mut result = HashMap::new();
+
+    for (name_str, view) in views {
+        // Validate swimlane reference
+        let entity_path = SourcePath::root().key("views").key(name_str.as_str());
+        validate_swimlane(&view.swimlane, swimlane_ids, entity_path, source_map)?;
+
+        let name = domain::ViewName::new(
+            NonEmptyString::parse(name_str)
+                .map_err(|_| ConversionError::EmptyField("view name".to_string()))?,
+        );
+
+        let components = convert_components(view.components)?;
+        let non_empty_components = vec_to_non_empty(components, "view components")?;
+
+        let definition = domain::ViewDefinition {
+            description: domain::Description::new(
+                NonEmptyString::parse(view.description)
+                    .map_err(|_| ConversionError::EmptyField("view description".to_string()))?,
+            ),
+            swimlane: domain::SwimlaneId::new(
+                NonEmptyString::parse(view.swimlane)
+                    .map_err(|_| ConversionError::EmptyField("swimlane ID".to_string()))?,
+            ),
+            components: non_empty_components,
+            owner: convert_owner(view.owner)?,
+            kind: convert_kind(view.kind)?,
+            hotspot: convert_hotspot(view.hotspot)?,
+            documentation: convert_documentation(view.doc)?,
+        };
+
+        result.insert(name, definition);
+    }
+
+    Ok(result)
+}
+
+/// Converts UI components.
+fn convert_components(
+    components: Vec<parsing::YamlComponent>,
+) -> Result<Vec<domain::Component>, ConversionError> {
+    let mut result = Vec::new();
+
+    for component in components {
+        match component {
+            parsing::YamlComponent::Simple { component } => {
+                // Simple component: name -> type mapping
+                for (name_str, type_str) in component {
+                    let name =
+                        domain::ComponentName::new(NonEmptyString::parse(name_str).map_err(
+                            |_| ConversionError::EmptyField("component name".to_string()),
+                        )?);
+                    let component_type = domain::ComponentType::Simple(
+                        domain::SimpleComponentType::new(NonEmptyString::parse(type_str).map_err(
+                            |_| ConversionError::EmptyField("component type".to_string()),
+                        )?),
+                    );
+                    result.push(domain::Component {
+                        name,
+                        component_type,
+                    });
+                }
+            }
+            parsing::YamlComponent::Complex { component } => {
+                // Complex component: name -> { type, fields, actions }
+                for (name_str, complex) in component {
+                    let name =
+                        domain::ComponentName::new(NonEmptyString::parse(name_str).map_err(
+                            |_| ConversionError::EmptyField("component name".to_string()),
+                        )?);
+
+                    // Check if this is a form component
+                    if complex.component_type.to_lowercase() == "form" {
+                        // Convert form fields
+                        let mut form_fields = HashMap::new();
+                        for (field_name, field_type) in complex.fields {
+                            let field =
+                                domain::FieldName::new(NonEmptyString::parse(field_name).map_err(
+                                    |_| ConversionError::EmptyField("form field name".to_string()),
+                                )?);
+                            let field_type = domain::SimpleComponentType::new(
+                                NonEmptyString::parse(field_type).map_err(|_| {
+                                    ConversionError::EmptyField("form field type".to_string())
+                                })?,
+                            );
+                            form_fields.insert(field, field_type);
+                        }
+
+                        // Convert actions
+                        let mut action_vec = Vec::new();
+                        for action_str in complex.actions {
+                            let action = domain::ActionName::new(
+                                NonEmptyString::parse(action_str).map_err(|_| {
+                                    ConversionError::EmptyField("action name".to_string())
+                                })?,
+                            );
+                            action_vec.push(action);
+                        }
+                        let actions = vec_to_non_empty(action_vec, "form actions")?;
+
+                        let component_type = domain::ComponentType::Form {
+                            fields: form_fields,
+                            actions,
+                        };
+                        result.push(domain::Component {
+                            name,
+                            component_type,
+                        });
+                    } else {
+                        // Not a form, treat as simple component
+                        let component_type =
+                            domain::ComponentType::Simple(domain::SimpleComponentType::new(
+                                NonEmptyString::parse(complex.component_type).map_err(|_| {
+                                    ConversionError::EmptyField("component type".to_string())
+                                })?,
+                            ));
+                        result.push(domain::Component {
+                            name,
+                            component_type,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Converts projection definitions.
+fn convert_projections(
+    projections: HashMap<String, parsing::YamlProjection>,
+    swimlane_ids: &[String],
+    source_map: &SourceMap,
+) -> Result<HashMap<domain::ProjectionName, domain::ProjectionDefinition>, ConversionError> {
+    let mut result = HashMap::new();
+
+    for (name_str, projection) in projections {
+        // Validate swimlane reference
+        let entity_path = SourcePath::root().key("projections").key(name_str.as_str());
+        validate_swimlane(&projection.swimlane, swimlane_ids, entity_path, source_map)?;
+
+        let name = domain::ProjectionName::new(
+            NonEmptyString::parse(name_str)
+                .map_err(|_| ConversionError::EmptyField("projection name".to_string()))?,
+        );
+
+        let mut fields = HashMap::new();
+        for (field_name, field_type) in projection.fields {
+            let field =
+                domain::FieldName::new(NonEmptyString::parse(field_name).map_err(|_| {
+                    ConversionError::EmptyField("projection field name".to_string())
+                })?);
+            let ftype =
+                domain::FieldType::new(NonEmptyString::parse(field_type).map_err(|_| {
+                    ConversionError::EmptyField("projection field type".to_string())
+                })?);
+            fields.insert(field, ftype);
+        }
+
+        let definition = domain::ProjectionDefinition {
+            description: domain::Description::new(
+                NonEmptyString::parse(projection.description).map_err(|_| {
+                    ConversionError::EmptyField("projection description".to_string())
+                })?,
+            ),
+            swimlane: domain::SwimlaneId::new(
+                NonEmptyString::parse(projection.swimlane)
+                    .map_err(|_| ConversionError::EmptyField("swimlane ID".to_string()))?,
+            ),
+            fields,
+            owner: convert_owner(projection.owner)?,
+            kind: convert_kind(projection.kind)?,
+            hotspot: convert_hotspot(projection.hotspot)?,
+            documentation: convert_documentation(projection.doc)?,
+        };
+
+        result.insert(name, definition);
+    }
+
+    Ok(result)
+}
+
+/// Converts query definitions.
+fn convert_queries(
+    queries: HashMap<String, parsing::YamlQuery>,
+    swimlane_ids: &[String],
+    source_map: &SourceMap,
+) -> Result<HashMap<domain::QueryName, domain::QueryDefinition>, ConversionError> {
+    let mut result = HashMap::new();
+
+    for (name_str, query) in queries {
+        // Validate swimlane reference
+        let entity_path = SourcePath::root().key("queries").key(name_str.as_str());
+        validate_swimlane(&query.swimlane, swimlane_ids, entity_path, source_map)?;
+
+        let name = domain::QueryName::new(
+            NonEmptyString::parse(name_str)
+                .map_err(|_| ConversionError::EmptyField("query name".to_string()))?,
+        );
+
+        // Convert inputs
+        let mut inputs = HashMap::new();
+        for (input_name, input_type) in query.inputs {
+            let iname = domain::FieldName::new(
+                NonEmptyString::parse(input_name)
+                    .map_err(|_| ConversionError::EmptyField("query input name".to_string()))?,
+            );
+            let itype = domain::FieldType::new(
+                NonEmptyString::parse(input_type)
+                    .map_err(|_| ConversionError::EmptyField("query input type".to_string()))?,
+            );
+            inputs.insert(iname, itype);
+        }
+
+        // Convert outputs
+        let outputs = convert_output_spec(query.outputs)?;
+
+        let definition = domain::QueryDefinition {
+            swimlane: domain::SwimlaneId::new(
+                NonEmptyString::parse(query.swimlane)
+                    .map_err(|_| ConversionError::EmptyField("swimlane ID".to_string()))?,
+            ),
+            inputs,
+            outputs,
+            owner: convert_owner(query.owner)?,
+            kind: convert_kind(query.kind)?,
+            hotspot: convert_hotspot(query.hotspot)?,
+            documentation: convert_documentation(query.doc)?,
+        };
+
+        result.insert(name, definition);
+    }
+
+    Ok(result)
+}
+
+/// Converts query output specifications.
+fn convert_output_spec(
+    output: parsing::YamlQueryOutput,
+) -> Result<domain::OutputSpec, ConversionError> {
+    let mut cases = HashMap::new();
+
+    for (case_name, variant) in output.one_of {
+        let case_name_domain = domain::OutputCaseName::new(
+            NonEmptyString::parse(case_name)
+                .map_err(|_| ConversionError::EmptyField("output case name".to_string()))?,
+        );
+
+        let case = match variant {
+            parsing::YamlQueryVariant::Simple(error_type) => {
+                // Simple string indicates an error type
+                domain::OutputCase::Error(domain::ErrorTypeName::new(
+                    NonEmptyString::parse(error_type)
+                        .map_err(|_| ConversionError::EmptyField("error type name".to_string()))?,
+                ))
+            }
+            parsing::YamlQueryVariant::Complex(fields) => {
+                // Complex object with fields
+                let mut field_map = HashMap::new();
+                for (field_name, field_type) in fields {
+                    let fname = domain::FieldName::new(NonEmptyString::parse(field_name).map_err(
+                        |_| ConversionError::EmptyField("output field name".to_string()),
+                    )?);
+                    let ftype = domain::FieldType::new(NonEmptyString::parse(field_type).map_err(
+                        |_| ConversionError::EmptyField("output field type".to_string()),
+                    )?);
+                    field_map.insert(fname, ftype);
+                }
+                domain::OutputCase::Fields(field_map)
+            }
+        };
+
+        cases.insert(case_name_domain, case);
+    }
+
+    // If there's only one case that's a Fields variant, convert to Single
+    match cases.len() {
+        1 => {
+            // Take ownership of the single item
+            match cases.into_iter().next() {
+                Some((_, domain::OutputCase::Fields(fields))) => {
+                    Ok(domain::OutputSpec::Single(fields))
+                }
+                // Not a Fields variant, recreate the map
+                other => Ok(domain::OutputSpec::OneOf(other.into_iter().collect())),
+            }
+        }
+        _ => Ok(domain::OutputSpec::OneOf(cases)),
+    }
+}
+
+/// Converts automation definitions.
+fn convert_automations(
+    automations: HashMap<String, parsing::YamlAutomation>,
+    swimlane_ids: &[String],
+    source_map: &SourceMap,
+) -> Result<HashMap<domain::AutomationName, domain::AutomationDefinition>, ConversionError> {
+    let mut result = HashMap::new();
+
+    for (name_str, automation) in automations {
+        // Validate swimlane reference
+        let entity_path = SourcePath::root().key("automations").key(name_str.as_str());
+        validate_swimlane(&automation.swimlane, swimlane_ids, entity_path, source_map)?;
+
+        let name = domain::AutomationName::new(
+            NonEmptyString::parse(name_str)
+                .map_err(|_| ConversionError::EmptyField("automation name".to_string()))?,
+        );
+
+        let definition = domain::AutomationDefinition {
+            swimlane: domain::SwimlaneId::new(
+                NonEmptyString::parse(automation.swimlane)
+                    .map_err(|_| ConversionError::EmptyField("swimlane ID".to_string()))?,
+            ),
+            owner: convert_owner(automation.owner)?,
+            kind: convert_kind(automation.kind)?,
+            hotspot: convert_hotspot(automation.hotspot)?,
+            documentation: convert_documentation(automation.doc)?,
+        };
+
+        result.insert(name, definition);
+    }
+
+    Ok(result)
+}
+
+/// Checks the `jira` site is an absolute `https://` URL and drops any
+/// trailing slash. Issue links are drawn as links to the site and
+/// `--verify-issues` sends Jira credentials to it, so it must not run script
+/// or go out in the clear.
+fn convert_jira_site(site: &str) -> Result<&str, ConversionError> {
+    let site_url = site.trim_end_matches('/');
+    let authority = site_url
+        .strip_prefix("https://")
+        .and_then(|rest| rest.split('/').next())
+        .unwrap_or_default();
+    let invalid = authority.is_empty()
+        || authority.starts_with(':')
+        || authority.contains('@')
+        || site_url.chars().any(|c| {
+            c.is_whitespace() || c.is_control() || matches!(c, '"' | '<' | '>' | '\\' | '`')
+        });
+    if invalid {
+        return Err(ConversionError::InvalidJiraSite(site.to_string()));
+    }
+    Ok(site_url)
+}
+
+/// Converts slice definitions.
+fn convert_slices(
+    slices: Vec<parsing::YamlSlice>,
+    entities: &ConvertedEntities,
+    extensions: &HashMap<domain::ExtensionName, domain::EntityExtension>,
+    jira: Option<&str>,
+    source_map: &SourceMap,
+    warnings: &mut Warnings,
+) -> Result<Vec<domain::Slice>, ConversionError> {
+    let mut result = Vec::new();
+
+    for (slice_index, yaml_slice) in slices.into_iter().enumerate() {
+        let name = domain::SliceName::new(
+            NonEmptyString::parse(yaml_slice.name)
+                .map_err(|_| ConversionError::EmptyField("slice name".to_string()))?,
+        );
+
+        let mut converted_connections = Vec::new();
+        let connections_path = SourcePath::root()
+            .key("slices")
+            .index(slice_index)
+            .key("connections");
+        for (connection_index, connection) in yaml_slice.connections.iter().enumerate() {
+            let location = source_map.locate(&connections_path.clone().index(connection_index));
+            let mut converted =
+                parse_connection(connection.text(), location, &entities.commands, warnings)?;
+            converted.consistency = connection
+                .consistency()
+                .map(|consistency| match consistency {
+                    parsing::YamlConsistency::Eventual => domain::Consistency::Eventual,
+                    parsing::YamlConsistency::Immediate => domain::Consistency::Immediate,
+                });
+            check_connection_shape(&converted, entities, location)?;
+            check_connection_kinds(&converted, entities, extensions, location)?;
+            converted.via = convert_waypoints(connection.via())?;
+            converted.marker = connection.marker().map(convert_marker);
+            converted.hotspot = convert_hotspot(connection.hotspot().map(str::to_string))?;
+            converted_connections.push(converted);
+        }
+
+        let order = yaml_slice
+            .order
+            .iter()
+            .map(|entity| {
+                converted_connections
+                    .iter()
+                    .flat_map(|connection| [&connection.from, &connection.to])
+                    .find(|reference| reference.entity_name() == *entity)
+                    .ok_or_else(|| ConversionError::UnconnectedOrderedEntity {
+                        slice: name.clone().into_inner().into_inner(),
+                        name: entity.clone(),
+                    })
+                    .and_then(drawn_entity)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let non_empty_connections = vec_to_non_empty(converted_connections, "slice connections")?;
+        let context = yaml_slice
+            .context
+            .map(|context| {
+                NonEmptyString::parse(context)
+                    .map(domain::ContextName::new)
+                    .map_err(|_| ConversionError::EmptyField("slice context".to_string()))
+            })
+            .transpose()?;
+        let estimate = yaml_slice
+            .estimate
+            .map(|estimate| match estimate {
+                parsing::YamlEstimate::Points(points) => Ok(domain::Estimate::Points(points)),
+                parsing::YamlEstimate::Size(size) => domain::TShirtSize::parse(&size)
+                    .map(domain::Estimate::Size)
+                    .ok_or_else(|| ConversionError::InvalidEstimate {
+                        slice: name.clone().into_inner().into_inner(),
+                        estimate: size,
+                    }),
+            })
+            .transpose()?;
+
+        let swimlanes = yaml_slice
+            .swimlanes
+            .into_iter()
+            .map(|lane| {
+                NonEmptyString::parse(lane)
+                    .map(domain::SwimlaneId::new)
+                    .map_err(|_| ConversionError::EmptyField("slice swimlane".to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let status = yaml_slice.status.map(|status| match status {
+            parsing::YamlSliceStatus::Done => domain::SliceStatus::Done,
+            parsing::YamlSliceStatus::InProgress => domain::SliceStatus::InProgress,
+            parsing::YamlSliceStatus::Todo => domain::SliceStatus::Todo,
+        });
+
+        let issue = yaml_slice
+            .issue
+            .map(|issue| {
+                convert_issue(&issue, jira).map_err(|reason| ConversionError::InvalidIssue {
+                    slice: name.clone().into_inner().into_inner(),
+                    issue,
+                    reason,
+                })
+            })
+            .transpose()?;
+
+        result.push(domain::Slice {
+            name,
+            context,
+            estimate,
+            status,
+            issue,
+            order,
+            swimlanes,
+            connections: non_empty_connections,
+        });
+    }
+
+    Ok(result)
+}
+
+/// Infers slices for a model that declares none: one per command with test
+/// scenarios, connecting it to the defined events its scenarios expect. The
+/// slices follow the order the commands are declared in, and each is named
+/// after its command, e.g. `Place Order` for `PlaceOrder`.
+fn synthesize_slices(
+    entities: &ConvertedEntities,
+    source_map: &SourceMap,
+    warnings: &mut Warnings,
+) -> Vec<domain::Slice> {
+    let declared_at = |name: &domain::CommandName| {
+        source_map
+            .locate(
+                &SourcePath::root()
+                    .key("commands")
+                    .key(name.clone().into_inner().as_str()),
+            )
+            .map(|location| location.line)
+    };
+    let mut commands: Vec<_> = entities.commands.iter().collect();
+    commands
+        .sort_by_key(|(name, _)| (declared_at(name), (*name).clone().into_inner().into_inner()));
+
+    let mut slices = Vec::new();
+    for (name, command) in commands {
+        let mut events: Vec<&domain::EventName> = command
+            .tests
+            .values()
+            .flat_map(|scenario| scenario.then.iter())
+            .filter_map(|outcome| match outcome {
+                domain::TestOutcome::Event(event) => Some(&event.name),
+                domain::TestOutcome::Error(_) => None,
+            })
+            .filter(|event| entities.events.contains_key(*event))
+            .collect();
+        events.sort_by_key(|event| (*event).clone().into_inner().into_inner());
+        events.dedup();
+
+        let connections: Vec<domain::Connection> = events
+            .into_iter()
+            .map(|event| domain::Connection {
+                from: domain::EntityReference::Command(name.clone()),
+                to: domain::EntityReference::Event(event.clone()),
+                via: Vec::new(),
+                marker: None,
+                bidirectional: false,
+                consistency: None,
+                hotspot: None,
+            })
+            .collect();
+        let Ok(connections) = vec_to_non_empty(connections, "slice connections") else {
+            continue;
+        };
+        let Ok(slice_name) = NonEmptyString::parse(words(name.clone().into_inner().as_str()))
+        else {
+            continue;
+        };
+        slices.push(domain::Slice {
+            name: domain::SliceName::new(slice_name),
+            context: None,
+            estimate: None,
+            status: None,
+            issue: None,
+            order: Vec::new(),
+            swimlanes: Vec::new(),
+            connections,
+        });
+    }
+
+    let message = if slices.is_empty() {
+        "No slices are declared and no command has a test scenario expecting an event to infer one from; declare `slices:` to draw the model".to_string()
+    } else {
+        let names: Vec<String> = slices
+            .iter()
+            .map(|slice| format!("'{}'", slice.name.clone().into_inner().as_str()))
+            .collect();
+        format!(
+            "No slices are declared; inferred {} from the commands' test scenarios: {}. Declare `slices:` to draw the rest of the model",
+            if slices.len() == 1 {
+                "1 slice".to_string()
+            } else {
+                format!("{} slices", slices.len())
+            },
+            names.join(", ")
+        )
+    };
+    warnings.warn(WarningKind::InferredSlices, message);
+    slices
+}
+
+/// Splits a PascalCase name into words, e.g. `Place Order` for `PlaceOrder`.
+fn words(name: &str) -> String {
+    let mut words = String::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if c.is_uppercase() && previous.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit()) {
+            words.push(' ');
+        }
+        words.push(c);
+        previous = Some(c);
+    }
+    words
+}
+
+/// Converts the waypoints of a connection. Whether the slices and swimlanes
+/// they name exist is checked once every slice is known.
+fn convert_waypoints(
+    via: &[parsing::YamlWaypoint],
+) -> Result<Vec<domain::Waypoint>, ConversionError> {
+    via.iter()
+        .map(|waypoint| {
+            Ok(domain::Waypoint {
+                slice: domain::SliceName::new(
+                    NonEmptyString::parse(waypoint.slice.clone())
+                        .map_err(|_| ConversionError::EmptyField("waypoint slice".to_string()))?,
+                ),
+                swimlane: domain::SwimlaneId::new(
+                    NonEmptyString::parse(waypoint.lane.clone())
+                        .map_err(|_| ConversionError::EmptyField("waypoint lane".to_string()))?,
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Checks that every waypoint names a slice and swimlane of the model.
+fn check_waypoints(
+    slices: &[domain::Slice],
+    swimlane_ids: &[String],
+) -> Result<(), ConversionError> {
+    for slice in slices {
+        for waypoint in slice.connections.iter().flat_map(|c| &c.via) {
+            let slice_name = slice.name.clone().into_inner().into_inner();
+            let target = waypoint.slice.clone().into_inner().into_inner();
+            if !slices.iter().any(|s| s.name == waypoint.slice) {
+                return Err(ConversionError::InvalidWaypoint(format!(
+                    "a connection in slice '{slice_name}' passes through unknown slice '{target}'"
+                )));
+            }
+            let lane = waypoint.swimlane.clone().into_inner().into_inner();
+            if !swimlane_ids.contains(&lane) {
+                return Err(ConversionError::InvalidWaypoint(format!(
+                    "a connection in slice '{slice_name}' passes through unknown swimlane '{lane}'"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the swimlanes each slice declares exist and hold every entity
+/// the slice draws.
+fn check_slice_swimlanes(
+    slices: &[domain::Slice],
+    entities: &ConvertedEntities,
+    swimlane_ids: &[String],
+) -> Result<(), ConversionError> {
+    for slice in slices.iter().filter(|slice| !slice.swimlanes.is_empty()) {
+        let slice_name = slice.name.clone().into_inner().into_inner();
+        let declared: Vec<String> = slice
+            .swimlanes
+            .iter()
+            .map(|lane| lane.clone().into_inner().into_inner())
+            .collect();
+        if let Some(lane) = declared.iter().find(|lane| !swimlane_ids.contains(lane)) {
+            return Err(ConversionError::InvalidSliceSwimlanes(format!(
+                "slice '{slice_name}' declares unknown swimlane '{lane}'"
+            )));
+        }
+        for connection in slice.connections.iter() {
+            for entity in [connection.from.entity_name(), connection.to.entity_name()] {
+                let Some(lane) = entities.swimlane_of(&entity) else {
+                    continue;
+                };
+                if !declared.contains(&lane) {
+                    return Err(ConversionError::InvalidSliceSwimlanes(format!(
+                        "slice '{slice_name}' draws '{entity}' in swimlane '{lane}', which it does not declare"
+                    )));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parses a connection string like "LoginScreen.CreateAccountLink -> CreateAccount".
+fn parse_connection(
+    conn_str: &str,
+    location: Option<SourceLocation>,
+    commands: &HashMap<domain::CommandName, domain::CommandDefinition>,
+    warnings: &mut Warnings,
+) -> Result<domain::Connection, ConversionError> {
+    let bidirectional = conn_str.contains("<->");
+    let arrow = if bidirectional { "<->" } else { "->" };
+    let parts: Vec<&str> = conn_str.split(arrow).map(|s| s.trim()).collect();
+
+    if parts.len() != 2 {
+        return Err(ConversionError::InvalidConnection {
+            message: format!("Expected 'from -> to' or 'from <-> to' format, got: {conn_str}"),
+            location,
+        });
+    }
+
+    let from = parse_entity_reference(parts[0], location, commands, warnings)?;
+    let to = parse_entity_reference(parts[1], location, commands, warnings)?;
+
+    Ok(domain::Connection {
+        from,
+        to,
+        via: Vec::new(),
+        marker: None,
+        bidirectional,
+        consistency: None,
+        hotspot: None,
+    })
+}
+
+/// Checks that a connection looping back to its source, or running both
+/// ways, joins entities that can be drawn that way. Only commands and
+/// automations loop back, retrying themselves; events never take part in a
+/// bidirectional link, since nothing flows back into a recorded fact.
+/// Consistency is only declared where an event feeds a projection.
+fn check_connection_shape(
+    connection: &domain::Connection,
+    entities: &ConvertedEntities,
+    location: Option<SourceLocation>,
+) -> Result<(), ConversionError> {
+    let from = connection.from.entity_name();
+    let to = connection.to.entity_name();
+    let invalid = |message: String| ConversionError::InvalidConnection { message, location };
+
+    if from == to {
+        if connection.bidirectional {
+            return Err(invalid(format!(
+                "'{from} <-> {to}' cannot run both ways from an entity to itself"
+            )));
+        }
+        if let Some(kind) = entities
+            .entity_type(&from)
+            .filter(|kind| !matches!(kind, EntityType::Command | EntityType::Automation))
+        {
+            return Err(invalid(format!(
+                "'{from} -> {to}' loops back to {} '{from}'; only commands and automations may connect to themselves",
+                kind.name()
+            )));
+        }
+    } else if connection.bidirectional {
+        if let Some(event) = [&from, &to]
+            .into_iter()
+            .find(|name| entities.entity_type(name) == Some(EntityType::Event))
+        {
+            return Err(invalid(format!(
+                "'{from} <-> {to}' runs both ways through event '{event}'; connections to and from events are one-way"
+            )));
+        }
+    }
+    if connection.consistency.is_some()
+        && (entities.entity_type(&from) != Some(EntityType::Event)
+            || entities.entity_type(&to) != Some(EntityType::Projection))
+    {
+        return Err(invalid(format!(
+            "'{from} -> {to}' declares consistency, which only applies to connections from an event to a projection"
+        )));
+    }
+    Ok(())
+}
+
+/// Reduces a connection endpoint to the entity it draws, so a slice orders a
+/// view rather than one of its components.
+fn drawn_entity(
+    reference: &domain::EntityReference,
+) -> Result<domain::EntityReference, ConversionError> {
+    match reference {
+        domain::EntityReference::View(_) => NonEmptyString::parse(reference.entity_name())
+            .map(|name| domain::EntityReference::View(domain::ViewPath::new(name)))
+            .map_err(|_| ConversionError::EmptyField("view path".to_string())),
+        other => Ok(other.clone()),
+    }
+}
+
+/// Parses an entity reference, determining its type from context.
+fn parse_entity_reference(
+    ref_str: &str,
+    location: Option<SourceLocation>,
+    commands: &HashMap<domain::CommandName, domain::CommandDefinition>,
+    warnings: &mut Warnings,
+) -> Result<domain::EntityReference, ConversionError> {
+    if ref_str.is_empty() {
+        return Err(ConversionError::EmptyField("entity reference".to_string()));
+    }
+
+    // Handle view paths (contain dots)
+    if ref_str.contains('.') {
+        let path = domain::ViewPath::new(
+            NonEmptyString::parse(ref_str.to_string())
+                .map_err(|_| ConversionError::EmptyField("view path".to_string()))?,
+        );
+        return Ok(domain::EntityReference::View(path));
+    }
+
+    // For other entity types, we need context to determine the type
+    // This is a limitation of the current approach - we're guessing based on naming conventions
+    // In a real implementation, we'd need to look up the entity in the registry
+
+    // Try to guess based on common naming patterns
+    let lower = ref_str.to_lowercase();
+
+    if lower.ends_with("event") || lower.ends_with("ed") || lower.ends_with("sent") {
+        // Likely an event (past tense or event-like ending)
+        let name = domain::EventName::new(
+            NonEmptyString::parse(ref_str.to_string())
+                .map_err(|_| ConversionError::EmptyField("event name".to_string()))?,
+        );
+        Ok(domain::EntityReference::Event(name))
+    } else if lower.ends_with("command")
+        || lower.starts_with("create")
+        || lower.starts_with("update")
+        || lower.starts_with("delete")
+    {
+        // Likely a command
+        let name = domain::CommandName::new(
+            NonEmptyString::parse(ref_str.to_string())
+                .map_err(|_| ConversionError::EmptyField("command name".to_string()))?,
+        );
+        Ok(domain::EntityReference::Command(name))
+    } else if lower.ends_with("projection") {
+        // Likely a projection
+        let name = domain::ProjectionName::new(
+            NonEmptyString::parse(ref_str.to_string())
+                .map_err(|_| ConversionError::EmptyField("projection name".to_string()))?,
+        );
+        Ok(domain::EntityReference::Projection(name))
+    } else if lower.ends_with("screen") || lower.ends_with("view") || lower.ends_with("page") {
+        // Likely a view
+        let path = domain::ViewPath::new(
+            NonEmptyString::parse(ref_str.to_string())
+                .map_err(|_| ConversionError::EmptyField("view path".to_string()))?,
+        );
+        Ok(domain::EntityReference::View(path))
+    } else if lower.ends_with("query") || lower.starts_with("get") || lower.starts_with("find") {
+        // Likely a query
+        let name = domain::QueryName::new(
+            NonEmptyString::parse(ref_str.to_string())
+                .map_err(|_| ConversionError::EmptyField("query name".to_string()))?,
+        );
+        Ok(domain::EntityReference::Query(name))
+    } else if lower.ends_with("automation")
+        || lower.contains("process")
+        || lower.ends_with("verifier")
+        || lower.ends_with("handler")
+        || lower.ends_with("worker")
+        || lower.ends_with("service")
+    {
+        // Likely an automation
+        let name = domain::AutomationName::new(
+            NonEmptyString::parse(ref_str.to_string())
+                .map_err(|_| ConversionError::EmptyField("automation name".to_string()))?,
+        );
+        Ok(domain::EntityReference::Automation(name))
+    } else {
+        // Default to command if we can't determine
+        let name = domain::CommandName::new(
+            NonEmptyString::parse(ref_str.to_string())
+                .map_err(|_| ConversionError::EmptyField("command name".to_string()))?,
+        );
+        if !commands.contains_key(&name) {
+            warnings.warn(
+                WarningKind::InferredEntityType,
+                format!(
+                    "Could not infer the type of '{ref_str}'{} from its name; treating it as a command",
+                    describe_location(location)
+                ),
+            );
+        }
+        Ok(domain::EntityReference::Command(name))
+    }
+}
+
+/// Rejects a reference to a swimlane that is not defined, pointing at the
+/// `swimlane:` key of the entity at `entity_path`.
+fn validate_swimlane(
+    swimlane: &str,
+    swimlane_ids: &[String],
+    entity_path: SourcePath,
+    source_map: &SourceMap,
+) -> Result<(), ConversionError> {
+    if swimlane_ids.iter().any(|id| id == swimlane) {
+        return Ok(());
+    }
+    Err(ConversionError::UnknownSwimlane {
+        swimlane: swimlane.to_string(),
+        location: source_map.nearest(&entity_path.key("swimlane")),
+    })
+}
+
+/// Formats an optional source location as a message suffix.
+fn describe_location(location: Option<SourceLocation>) -> String {
+    location
+        .map(|location| format!(" at {location}"))
+        .unwrap_or_default()
+}
+
+/// Converts a slice's `issue`: a GitHub issue or pull request URL, or a
+/// Jira key on the model's `jira` site. Returns why the issue is invalid
+/// otherwise.
+fn convert_issue(issue: &str, jira: Option<&str>) -> Result<domain::IssueLink, String> {
+    let issue = issue.trim();
+    if let Some(path) = issue.strip_prefix("https://github.com/") {
+        let parts: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+        return match parts[..] {
+            [owner, repo, "issues" | "pull", number] if !owner.is_empty() && !repo.is_empty() => {
+                let number = number
+                    .parse()
+                    .map_err(|_| format!("'{number}' is not an issue number"))?;
+                Ok(domain::IssueLink::GitHub {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    number,
+                })
+            }
+            _ => Err("expected https://github.com/<owner>/<repo>/issues/<number>".to_string()),
+        };
+    }
+
+    let is_key = issue.split_once('-').is_some_and(|(project, number)| {
+        project.starts_with(|c: char| c.is_ascii_uppercase())
+            && project
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+            && !number.is_empty()
+            && number.chars().all(|c| c.is_ascii_digit())
+    });
+    if !is_key {
+        return Err("expected a Jira key such as PROJ-123 or a GitHub issue URL".to_string());
+    }
+    match jira {
+        Some(site) => Ok(domain::IssueLink::Jira {
+            site: site.to_string(),
+            key: issue.to_string(),
+        }),
+        None => Err(
+            "Jira keys need the model's `jira` site, e.g. jira: https://acme.atlassian.net"
+                .to_string(),
+        ),
+    }
+}
+
+/// Errors that can occur during conversion.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ConversionError {
+    /// A required field was empty.
+    #[error("Field '{0}' cannot be empty")]
+    EmptyField(String),
+
+    /// An unknown swimlane was referenced.
+    #[error("Unknown swimlane reference: {swimlane}{}", describe_location(*.location))]
+    UnknownSwimlane {
+        /// The swimlane identifier that was referenced.
+        swimlane: String,
+        /// Where the reference appears in the source, when known.
+        location: Option<SourceLocation>,
+    },
+
+    /// A slice connection was invalid.
+    #[error("Invalid connection syntax: {message}{}", describe_location(*.location))]
+    InvalidConnection {
+        /// What is wrong with the connection.
+        message: String,
+        /// Where the connection appears in the source, when known.
+        location: Option<SourceLocation>,
+    },
+
+    /// A collection that must be non-empty was empty.
+    #[error("Collection '{0}' must not be empty")]
+    EmptyCollection(String),
+
+    /// A parse error occurred.
+    #[error("Parse error: {0}")]
+    ParseError(#[from] ParseError),
+
+    /// A connection waypoint names a slice or swimlane that does not exist.
+    #[error("Invalid waypoint: {0}")]
+    InvalidWaypoint(String),
+
+    /// A slice declares a swimlane that does not exist, or leaves out one it
+    /// draws an entity in.
+    #[error("Invalid slice swimlanes: {0}")]
+    InvalidSliceSwimlanes(String),
+
+    /// A swimlane's color or icon is invalid.
+    #[error("Invalid swimlane style: {0}")]
+    InvalidSwimlaneStyle(String),
+
+    /// A swimlane group is nested or has an invalid attribute.
+    #[error("Invalid swimlane group: {0}")]
+    InvalidSwimlaneGroup(String),
+
+    /// Connector clearance is out of range.
+    #[error("Invalid routing: {0}")]
+    InvalidRouting(String),
+
+    /// Canvas background or watermark is malformed.
+    #[error("Invalid canvas: {0}")]
+    InvalidCanvas(String),
+
+    /// Slice header styling is out of range.
+    #[error("Invalid slice_headers: {0}")]
+    InvalidSliceHeaders(String),
+
+    /// The workflow date is not a `YYYY-MM-DD` calendar date.
+    #[error("Invalid date '{0}': expected YYYY-MM-DD, e.g. 2026-10-17")]
+    InvalidDate(String),
+
+    /// The layout grid has no spacing.
+    #[error("Invalid grid: spacing must be at least 1 pixel")]
+    InvalidGrid,
+
+    /// A `markers` pattern is malformed or names an unknown entity type.
+    #[error("Invalid markers: {0}")]
+    InvalidMarkers(String),
+
+    /// An entity's `volume` is not a count per period.
+    #[error(
+        "Invalid volume '{volume}' on '{entity}': expected a number, optionally followed by k, M, or B, per s, min, hour, day, week, or month, e.g. 10k/day"
+    )]
+    InvalidVolume {
+        /// The entity declaring the volume.
+        entity: String,
+        /// The volume as written.
+        volume: String,
+    },
+
+    /// A slice's `estimate` is neither story points nor a t-shirt size.
+    #[error(
+        "Invalid estimate '{estimate}' on slice '{slice}': expected story points, e.g. 5, or a t-shirt size from XS to XXL"
+    )]
+    InvalidEstimate {
+        /// The slice declaring the estimate.
+        slice: String,
+        /// The estimate as written.
+        estimate: String,
+    },
+
+    /// A slice's `issue` is neither a Jira key nor a GitHub issue URL, or is
+    /// a Jira key in a model without a `jira` site.
+    #[error("Invalid issue '{issue}' on slice '{slice}': {reason}")]
+    InvalidIssue {
+        /// The slice declaring the issue.
+        slice: String,
+        /// The issue as written.
+        issue: String,
+        /// Why the issue is invalid.
+        reason: String,
+    },
+
+    /// The `jira` site is not an absolute `https://` URL.
+    #[error(
+        "Invalid jira site '{0}': expected an absolute https:// URL, e.g. https://example.atlassian.net"
+    )]
+    InvalidJiraSite(String),
+
+    /// An `implemented_by` reference is neither a Rust path, an `http(s)`
+    /// URL, nor a file path.
+    #[error("Invalid implemented_by '{0}': expected a Rust path, an http(s) URL, or a file path")]
+    InvalidImplementationLink(String),
+
+    /// An event, command, or field set extends a field set the model does
+    /// not declare.
+    #[error("Unknown field set '{name}' extended by {extender}")]
+    UnknownFieldSet {
+        /// The field set that was referenced.
+        name: String,
+        /// What extends it, e.g. `event 'OrderPlaced'`.
+        extender: String,
+    },
+
+    /// A layout constraint names an entity the model does not define.
+    #[error("Unknown entity '{name}' in layout constraints")]
+    UnknownConstraintEntity {
+        /// The entity that was referenced.
+        name: String,
+    },
+
+    /// A slice's `order` names an entity none of its connections draws.
+    #[error("Slice '{slice}' orders '{name}', which none of its connections draws")]
+    UnconnectedOrderedEntity {
+        /// The slice whose order names the entity.
+        slice: String,
+        /// The entity that was named.
+        name: String,
+    },
+
+    /// The model asks for the constraint layout, which this build lacks.
+    #[error("`layout: constraints` needs event_modeler built with the `constraint-layout` feature")]
+    ConstraintLayoutUnavailable,
+
+    /// The model lists lint plugins, which this build cannot run.
+    #[error("`lint: plugins` needs event_modeler built with the `plugins` feature")]
+    PluginsUnavailable,
+
+    /// Field sets extend each other in a cycle.
+    #[error("Field sets extend each other in a cycle: {}", cycle.join(" -> "))]
+    FieldSetCycle {
+        /// The sets in the cycle, starting and ending with the same set.
+        cycle: Vec<String>,
+    },
+
+    /// Two field sets extended together define a field differently.
+    #[error(
+        "Field '{field}' of {extender} is defined differently by field sets '{first}' and '{second}'"
+    )]
+    ConflictingFieldSets {
+        /// What extends both sets, e.g. `event 'OrderPlaced'`.
+        extender: String,
+        /// The field defined differently.
+        field: String,
+        /// The set extended first.
+        first: String,
+        /// The set extended later.
+        second: String,
+    },
+
+    /// A command is authorized for a role the model does not declare.
+    #[error("Command '{command}' is authorized for undeclared role '{role}'")]
+    UnknownRole {
+        /// The command naming the role.
+        command: String,
+        /// The role missing from `roles`.
+        role: String,
+    },
+
+    /// A field refers to a type the model does not declare.
+    #[error("Field '{field}' of '{entity}' has undeclared type '{field_type}'")]
+    UndeclaredType {
+        /// The entity, error, or declared type owning the field.
+        entity: String,
+        /// The field.
+        field: String,
+        /// The type missing from `types`.
+        field_type: String,
+    },
+
+    /// A test scenario has no steps under `When` or `Then`.
+    #[error(
+        "Test '{scenario}' of command '{command}' has no steps under {section}; every scenario needs a command under When and an expected event or error under Then"
+    )]
+    EmptyScenarioSection {
+        /// The command under test.
+        command: String,
+        /// The scenario missing steps.
+        scenario: String,
+        /// The empty section, `When` or `Then`.
+        section: &'static str,
+    },
+
+    /// A test scenario's example value is unused or does not suit its fields.
+    #[error(
+        "Invalid example for '{placeholder}' in test '{scenario}' of command '{command}': {reason}"
+    )]
+    InvalidExample {
+        /// The command under test.
+        command: String,
+        /// The scenario giving the example.
+        scenario: String,
+        /// The placeholder the example stands in for.
+        placeholder: String,
+        /// What is wrong with the example.
+        reason: String,
+    },
+
+    /// A declared type's variants or constraints are inconsistent.
+    #[error("Invalid type '{name}': {reason}")]
+    InvalidType {
+        /// The declared type.
+        name: String,
+        /// What is wrong with its declaration.
+        reason: String,
+    },
+
+    /// A custom entity kind under `extensions` is declared inconsistently.
+    #[error("Invalid extension '{name}': {reason}")]
+    InvalidExtension {
+        /// The declared kind.
+        name: String,
+        /// What is wrong with its declaration.
+        reason: String,
+    },
+
+    /// An entity's `kind` is not declared, or is based on another type.
+    #[error("Invalid kind of '{entity}': {reason}")]
+    InvalidKind {
+        /// The entity declaring the kind.
+        entity: String,
+        /// What is wrong with it.
+        reason: String,
+    },
+
+    /// A documentation link does not point to a Markdown file.
+    #[error("Documentation file '{0}' must be a Markdown (.md) file")]
+    InvalidDocumentationFile(String),
+
+    /// An entity name is defined more than once.
+    #[error("Entity '{0}' is defined more than once")]
+    DuplicateEntity(String),
+
+    /// An expansion refers to a template that is not defined.
+    #[error("Unknown template '{0}'")]
+    UnknownTemplate(String),
+
+    /// A template does not describe a valid model fragment once expanded.
+    #[error("Invalid template: {0}")]
+    InvalidTemplate(String),
+
+    /// Text in a template contains a placeholder other than `{{name}}`.
+    #[error("Unresolved placeholder in '{0}' (templates only support {{{{name}}}})")]
+    UnresolvedPlaceholder(String),
+
+    /// An error raised by an entity or slice produced from a template.
+    #[error("In template '{template}' expanded for '{argument}': {source}")]
+    InTemplate {
+        /// The template that was expanded.
+        template: String,
+        /// The `for_each` argument the template was expanded for.
+        argument: String,
+        /// The underlying error.
+        source: Box<ConversionError>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::parsing::yaml_parser;
+
+    #[test]
+    fn converts_minimal_event_model() {
+        let yaml = r#"
+workflow: Test Workflow
+swimlanes:
+  - user: "User Interface"
+  - backend: "Backend System"
+"#;
+        let parsed = yaml_parser::parse_yaml(yaml).unwrap();
+        let result = convert_yaml_to_domain(parsed);
+
+        assert!(result.is_ok());
+        let model = result.unwrap();
+        assert_eq!(model.workflow.into_inner().into_inner(), "Test Workflow");
+        assert_eq!(model.swimlanes.len(), 2);
+    }
+
+    #[test]
+    fn converts_workflow_header() {
+        let model = |date: &str| {
+            format!(
+                "workflow:\n  title: Orders\n  description: How orders are placed\n  authors: [Alice, Bob]\n  version: 2.1\n  date: {date}\nswimlanes:\n  - backend: \"Backend\"\n"
+            )
+        };
+
+        let parsed = yaml_parser::parse_yaml(&model("2026-10-17")).unwrap();
+        let converted = convert_yaml_to_domain(parsed).unwrap();
+        assert_eq!(converted.workflow.into_inner().into_inner(), "Orders");
+        assert_eq!(
+            converted.header.byline().as_deref(),
+            Some("Version 2.1 \u{b7} 2026-10-17 \u{b7} Alice, Bob")
+        );
+
+        let parsed = yaml_parser::parse_yaml(&model("2026-13-01")).unwrap();
+        assert!(matches!(
+            convert_yaml_to_domain(parsed),
+            Err(ConversionError::InvalidDate(date)) if date == "2026-13-01"
+        ));
+    }
+
+    #[test]
+    fn lint_plugins_need_the_plugins_feature() {
+        let parsed = yaml_parser::parse_yaml(
+            "workflow: Orders\nswimlanes:\n  - backend: \"Backend\"\nlint:\n  plugins: [rules/owners.wasm]\n",
+        )
+        .unwrap();
+
+        let converted = convert_yaml_to_domain(parsed);
+
+        if cfg!(feature = "plugins") {
+            assert_eq!(converted.unwrap().lint.plugins.len(), 1);
+        } else {
+            assert!(matches!(
+                converted,
+                Err(ConversionError::PluginsUnavailable)
+            ));
+        }
+    }
+
+    #[test]
+    fn requires_an_https_jira_site() {
+        let model = |site: &str| {
+            format!("workflow: Orders\nswimlanes:\n  - backend: \"Backend\"\njira: \"{site}\"\n")
+        };
+        let convert = |site: &str| {
+            convert_yaml_to_domain(yaml_parser::parse_yaml(&model(site)).unwrap()).map(|_| ())
+        };
+
+        assert!(convert("https://acme.atlassian.net/").is_ok());
+        for site in [
+            "javascript:alert(1)",
+            "http://acme.atlassian.net",
+            "-K/etc/passwd",
+            "https://",
+            "https://user@acme.atlassian.net",
+        ] {
+            assert!(
+                matches!(convert(site), Err(ConversionError::InvalidJiraSite(_))),
+                "{site} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn refuses_implementation_links_with_other_schemes() {
+        let model = |link: &str| {
+            format!(
+                "workflow: Orders\nswimlanes:\n  - backend: \"Backend\"\nevents:\n  OrderPlaced:\n    description: \"Placed\"\n    swimlane: backend\n    implemented_by:\n      - \"{link}\"\n"
+            )
+        };
+        let convert = |link: &str| {
+            convert_yaml_to_domain(yaml_parser::parse_yaml(&model(link)).unwrap()).map(|_| ())
+        };
+
+        assert!(convert("src/orders.rs").is_ok());
+        assert!(convert("C:/orders/src/lib.rs").is_ok());
+        for link in [
+            "javascript:alert(1)",
+            "data:text/html,<b>hi</b>",
+            "file:///etc/passwd",
+        ] {
+            assert!(
+                matches!(
+                    convert(link),
+                    Err(ConversionError::InvalidImplementationLink(written)) if written == link
+                ),
+                "{link} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn converts_events_with_validation() {
+        let yaml = r#"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+events:
+  UserCreated:
+    description: "A new user was created"
+    swimlane: backend
+    data:
+      userId:
+        type: UserId
+        stream-id: true
+      email: EmailAddress
+"#;
+        let parsed = yaml_parser::parse_yaml(yaml).unwrap();
+        let result = convert_yaml_to_domain(parsed);
+
+        assert!(result.is_ok());
+        let model = result.unwrap();
+        assert_eq!(model.events.len(), 1);
+
+        let event = model.events.iter().next().unwrap();
+        assert_eq!(event.0.clone().into_inner().into_inner(), "UserCreated");
+        assert_eq!(
+            event.1.description.clone().into_inner().into_inner(),
+            "A new user was created"
+        );
+        assert_eq!(event.1.data.len(), 2);
+    }
+
+    #[test]
+    fn rejects_unknown_swimlane() {
+        let yaml = r#"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+events:
+  UserCreated:
+    description: "A new user was created"
+    swimlane: unknown
+"#;
+        let parsed = yaml_parser::parse_yaml(yaml).unwrap();
+        let result = convert_yaml_to_domain(parsed);
+
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            ConversionError::UnknownSwimlane { swimlane, location } => {
+                assert_eq!(swimlane, "unknown");
+                assert_eq!(location, Some(SourceLocation { line: 8, column: 5 }));
+            }
+            _ => panic!("Expected UnknownSwimlane error"),
+        }
+    }
+
+    #[test]
+    fn converts_commands_with_tests() {
+        let yaml = r#"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+commands:
+  CreateUser:
+    description: "Create a new user"
+    swimlane: backend
+    data:
+      email: EmailAddress
+    tests:
+      happy_path:
+        Given: []
+        When:
+          - CreateUser:
+              email: A
+        Then:
+          - UserCreated:
+              email: A
+"#;
+        let parsed = yaml_parser::parse_yaml(yaml).unwrap();
+        let result = convert_yaml_to_domain(parsed);
+
+        assert!(result.is_ok());
+        let model = result.unwrap();
+        assert_eq!(model.commands.len(), 1);
+
+        let command = model.commands.iter().next().unwrap();
+        assert_eq!(command.1.tests.len(), 1);
+
+        let test = command.1.tests.iter().next().unwrap();
+        assert_eq!(test.0.clone().into_inner().into_inner(), "happy_path");
+        assert_eq!(test.1.given.len(), 0);
+        assert_eq!(test.1.when.len(), 1);
+        assert_eq!(test.1.then.len(), 1);
+    }
+
+    #[test]
+    fn then_steps_naming_declared_errors_expect_a_rejection() {
+        let yaml = r#"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+commands:
+  CreateUser:
+    description: "Create a new user"
+    swimlane: backend
+    errors:
+      DuplicateUserError:
+        description: "A user with this email already exists"
+        data:
+          email: EmailAddress
+    tests:
+      duplicate:
+        Given:
+          - UserCreated:
+              email: A
+        When:
+          - CreateUser:
+              email: A
+        Then:
+          - DuplicateUserError:
+              email: A
+      misspelled:
+        When:
+          - CreateUser:
+              email: A
+        Then:
+          - DuplicateUsrError:
+              email: A
+events:
+  UserCreated:
+    description: "A user was created"
+    swimlane: backend
+"#;
+        let parsed = yaml_parser::parse_yaml(yaml).unwrap();
+        let mut warnings = Warnings::new();
+        let model = convert_yaml_to_domain_with_warnings(parsed, &mut warnings).unwrap();
+
+        let command = model.commands.values().next().unwrap();
+        assert_eq!(command.errors.len(), 1);
+        let outcome = |scenario: &str| {
+            let name =
+                domain::TestScenarioName::new(NonEmptyString::parse(scenario.to_string()).unwrap());
+            command.tests[&name].then.first().clone()
+        };
+        assert!(matches!(
+            outcome("duplicate"),
+            domain::TestOutcome::Error(error) if error.fields.len() == 1
+        ));
+        assert!(matches!(
+            outcome("misspelled"),
+            domain::TestOutcome::Event(_)
+        ));
+
+        let unknown: Vec<_> = warnings.of_kind(WarningKind::UnknownTestOutcome).collect();
+        assert_eq!(unknown.len(), 1);
+        assert!(unknown[0].message.contains("'DuplicateUsrError'"));
+    }
+
+    #[test]
+    fn converts_view_components() {
+        let yaml = r#"
+workflow: Test
+swimlanes:
+  - ui: "UI"
+views:
+  LoginScreen:
+    description: "User login screen"
+    swimlane: ui
+    components:
+      - Title: Label
+      - LoginForm:
+          type: Form
+          fields:
+            email: TextInput
+            password: PasswordInput
+          actions:
+            - Submit
+"#;
+        let parsed = yaml_parser::parse_yaml(yaml).unwrap();
+        let result = convert_yaml_to_domain(parsed);
+
+        assert!(result.is_ok());
+        let model = result.unwrap();
+        assert_eq!(model.views.len(), 1);
+
+        let view = model.views.iter().next().unwrap();
+        assert_eq!(view.1.components.len(), 2);
+    }
+
+    #[test]
+    fn converts_query_with_one_of_outputs() {
+        let yaml = r#"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+queries:
+  GetUser:
+    swimlane: backend
+    inputs:
+      userId: UserId
+    outputs:
+      one_of:
+        success:
+          user: UserData
+        notFound: NotFoundError
+"#;
+        let parsed = yaml_parser::parse_yaml(yaml).unwrap();
+        let result = convert_yaml_to_domain(parsed);
+
+        assert!(result.is_ok());
+        let model = result.unwrap();
+        let query = model.queries.iter().next().unwrap();
+
+        match &query.1.outputs {
+            domain::OutputSpec::OneOf(cases) => {
+                assert_eq!(cases.len(), 2);
+            }
+            _ => panic!("Expected OneOf output spec"),
+        }
+    }
+
+    #[test]
+    fn converts_slices_with_connections() {
+        let yaml = r#"
+workflow: Test
+swimlanes:
+  - ui: "UI"
+slices:
+  - name: UserRegistration
+    connections:
+      - "LoginScreen.CreateAccountLink -> CreateAccount"
+      - "CreateAccount -> UserCreated"
+"#;
+        let parsed = yaml_parser::parse_yaml(yaml).unwrap();
+        let result = convert_yaml_to_domain(parsed);
+
+        assert!(result.is_ok());
+        let model = result.unwrap();
+        assert_eq!(model.slices.len(), 1);
+
+        let slice = &model.slices[0];
+        assert_eq!(slice.name.clone().into_inner().as_str(), "UserRegistration");
+        assert_eq!(slice.connections.len(), 2);
+    }
+
+    #[test]
+    fn slice_order_names_entities_its_connections_draw() {
+        let yaml = |order: &str| {
+            format!(
+                r#"
+workflow: Test
+swimlanes:
+  - ui: "UI"
+slices:
+  - name: UserRegistration
+    order: [{order}]
+    connections:
+      - "LoginScreen.CreateAccountLink -> CreateAccount"
+      - "CreateAccount -> UserCreated"
+"#
+            )
+        };
+
+        let parsed = yaml_parser::parse_yaml(&yaml("UserCreated, LoginScreen")).unwrap();
+        let model = convert_yaml_to_domain(parsed).unwrap();
+        assert_eq!(
+            model.slices[0].order,
+            vec![
+                domain::EntityReference::Event(domain::EventName::new(
+                    NonEmptyString::parse("UserCreated".to_string()).unwrap()
+                )),
+                domain::EntityReference::View(domain::ViewPath::new(
+                    NonEmptyString::parse("LoginScreen".to_string()).unwrap()
+                )),
+            ]
+        );
+
+        let parsed = yaml_parser::parse_yaml(&yaml("UserCreated, UserDeleted")).unwrap();
+        assert!(matches!(
+            convert_yaml_to_domain(parsed),
+            Err(ConversionError::UnconnectedOrderedEntity { name, .. }) if name == "UserDeleted"
+        ));
+    }
+
+    #[test]
+    fn invalid_connection_points_at_its_line() {
+        let yaml = r#"
+workflow: Test
+swimlanes:
+  - ui: "UI"
+slices:
+  - name: Checkout
+    connections:
+      - "CreateOrder -> OrderCreated"
+      - "OrderCreated OrderShipped"
+"#;
+        let parsed = yaml_parser::parse_yaml(yaml).unwrap();
+
+        match convert_yaml_to_domain(parsed) {
+            Err(error @ ConversionError::InvalidConnection { .. }) => {
+                assert!(error.to_string().ends_with("at line 9, column 9"));
+            }
+            other => panic!("Expected InvalidConnection error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn warns_when_entity_type_falls_back_to_command() {
+        let yaml = r#"
+workflow: Test
+swimlanes:
+  - ui: "UI"
+slices:
+  - name: Checkout
+    connections:
+      - "Checkout -> OrderPlaced"
+"#;
+        let parsed = yaml_parser::parse_yaml(yaml).unwrap();
+        let mut warnings = Warnings::new();
+        convert_yaml_to_domain_with_warnings(parsed, &mut warnings).unwrap();
+
+        let inferred: Vec<_> = warnings.of_kind(WarningKind::InferredEntityType).collect();
+        assert_eq!(inferred.len(), 1);
+        assert!(inferred[0].message.contains("'Checkout'"));
+    }
+
+    const TEMPLATED_MODEL: &str = r#"
+workflow: Billing
+swimlanes:
+  - backend: "Backend"
+templates:
+  create:
+    commands:
+      "Create{{name}}":
+        description: "Create a {{name}}"
+        swimlane: backend
+    events:
+      "{{name}}Created":
+        description: "A {{name}} was created"
+        swimlane: backend
+    slices:
+      - name: "Create {{name}}"
+        connections:
+          - "Create{{name}} -> {{name}}Created"
+expand:
+  - template: create
+    for_each: [Invoice, Receipt]
+"#;
+
+    #[test]
+    fn expands_templates_for_each_argument() {
+        let parsed = yaml_parser::parse_yaml(TEMPLATED_MODEL).unwrap();
+        let mut warnings = Warnings::new();
+        let model = convert_yaml_to_domain_with_warnings(parsed, &mut warnings).unwrap();
+
+        let mut events: Vec<_> = model
+            .events
+            .keys()
+            .map(|name| name.clone().into_inner().into_inner())
+            .collect();
+        events.sort();
+        assert_eq!(events, ["InvoiceCreated", "ReceiptCreated"]);
+        assert_eq!(model.commands.len(), 2);
+        assert_eq!(model.slices.len(), 2);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn template_errors_name_the_template_and_argument() {
+        let yaml = TEMPLATED_MODEL.replace("swimlane: backend", "swimlane: billing");
+        let parsed = yaml_parser::parse_yaml(&yaml).unwrap();
+
+        let error = convert_yaml_to_domain(parsed).unwrap_err();
+        assert!(
+            error.to_string().starts_with(
+                "In template 'create' expanded for 'Invoice': Unknown swimlane reference: billing"
+            ),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn rejects_expanded_names_that_collide() {
+        let yaml = format!(
+            "{TEMPLATED_MODEL}events:\n  InvoiceCreated:\n    description: \"Written by hand\"\n    swimlane: backend\n"
+        );
+        let parsed = yaml_parser::parse_yaml(&yaml).unwrap();
+
+        match convert_yaml_to_domain(parsed) {
+            Err(ConversionError::InTemplate { source, .. }) => {
+                assert!(
+                    matches!(*source, ConversionError::DuplicateEntity(ref name) if name == "InvoiceCreated")
+                );
+            }
+            other => panic!("Expected InTemplate error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_empty_collections() {
+        let yaml = r#"
+workflow: Test
+swimlanes: []
+"#;
+        let parsed = yaml_parser::parse_yaml(yaml).unwrap();
+        let result = convert_yaml_to_domain(parsed);
+
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            ConversionError::EmptyCollection(s) => assert_eq!(s, "swimlanes"),
+            _ => panic!("Expected EmptyCollection error"),
+        }
+    }
+
+    #[test]
+    fn rejects_empty_strings() {
+        let yaml = r#"
+workflow: ""
+swimlanes:
+  - test: "Test"
+"#;
+        let parsed = yaml_parser::parse_yaml(yaml).unwrap();
+        let result = convert_yaml_to_domain(parsed);
+
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            ConversionError::EmptyField(s) => assert_eq!(s, "workflow"),
+            _ => panic!("Expected EmptyField error"),
+        }
+    }
+
+    #[test]
+    fn converts_inline_and_linked_documentation() {
+        let yaml = r#"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+    doc: |
+      Rejected when the **cart** is empty.
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+    doc:
+      file: docs/order-placed.md
+"#;
+        let parsed = yaml_parser::parse_yaml(yaml).unwrap();
+        let model = convert_yaml_to_domain(parsed).unwrap();
+
+        let command = model.commands.values().next().unwrap();
+        match &command.documentation {
+            Some(domain::Documentation::Markdown(text)) => assert_eq!(
+                text.clone().into_inner().as_str(),
+                "Rejected when the **cart** is empty."
+            ),
+            other => panic!("Expected inline documentation, got {other:?}"),
+        }
+        let event = model.events.values().next().unwrap();
+        match &event.documentation {
+            Some(domain::Documentation::File(path)) => {
+                assert_eq!(path.as_path_buf(), &PathBuf::from("docs/order-placed.md"))
+            }
+            other => panic!("Expected a documentation file, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_documentation_files_that_are_not_markdown() {
+        let yaml = r#"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+    doc:
+      file: docs/order-placed.txt
+"#;
+        let parsed = yaml_parser::parse_yaml(yaml).unwrap();
+
+        match convert_yaml_to_domain(parsed).unwrap_err() {
+            ConversionError::InvalidDocumentationFile(file) => {
+                assert_eq!(file, "docs/order-placed.txt")
+            }
+            other => panic!("Expected InvalidDocumentationFile error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_slice_header_styling() {
+        let model = |headers: &str| {
+            format!(
+                "workflow: Test\nslice_headers:\n{headers}\nswimlanes:\n  - backend: \"Backend\"\n"
+            )
+        };
+
+        let parsed = yaml_parser::parse_yaml(&model("  background: \"#12345\"")).unwrap();
+        assert!(matches!(
+            convert_yaml_to_domain(parsed),
+            Err(ConversionError::InvalidSliceHeaders(message)) if message.contains("'#12345'")
+        ));
+
+        let parsed = yaml_parser::parse_yaml(&model("  max_lines: 0")).unwrap();
+        assert!(matches!(
+            convert_yaml_to_domain(parsed),
+            Err(ConversionError::InvalidSliceHeaders(message)) if message == "max_lines must be at least 1"
+        ));
+    }
+
+    #[test]
+    fn converts_canvas_backgrounds_per_theme() {
+        let model = |canvas: &str| {
+            format!("workflow: Test\ncanvas:\n{canvas}\nswimlanes:\n  - backend: \"Backend\"\n")
+        };
+
+        let parsed = yaml_parser::parse_yaml(&model(
+            "  background: transparent\n  dark_background: \"#0d1117\"\n  watermark: DRAFT",
+        ))
+        .unwrap();
+        let canvas = convert_yaml_to_domain(parsed).unwrap().canvas;
+        assert_eq!(
+            canvas.background_for(false),
+            Some(&domain::CanvasBackground::Transparent)
+        );
+        assert!(matches!(
+            canvas.background_for(true),
+            Some(domain::CanvasBackground::Color(color)) if color.as_ref() == "#0d1117"
+        ));
+        assert_eq!(canvas.watermark.unwrap().as_str(), "DRAFT");
+
+        let parsed = yaml_parser::parse_yaml(&model("  background: \"#12345\"")).unwrap();
+        assert!(matches!(
+            convert_yaml_to_domain(parsed),
+            Err(ConversionError::InvalidCanvas(message)) if message.contains("'#12345'")
+        ));
+    }
+
+    #[test]
+    fn converts_waypoints_and_rejects_unknown_cells() {
+        let model = |via: &str| {
+            format!(
+                r#"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+  - automation: "Automation"
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "Order placed"
+    swimlane: backend
+slices:
+  - name: Ordering
+    connections:
+      - connection: PlaceOrder -> OrderPlaced
+        via:
+          - {via}
+  - name: SendEmail
+    connections:
+      - PlaceOrder -> OrderPlaced
+"#
+            )
+        };
+
+        let parsed =
+            yaml_parser::parse_yaml(&model("{ slice: SendEmail, lane: automation }")).unwrap();
+        let converted = convert_yaml_to_domain(parsed).unwrap();
+        let via = &converted.slices[0].connections.first().via;
+        assert_eq!(via.len(), 1);
+        assert_eq!(via[0].slice.clone().into_inner().as_str(), "SendEmail");
+        assert_eq!(via[0].swimlane.clone().into_inner().as_str(), "automation");
+
+        let parsed =
+            yaml_parser::parse_yaml(&model("{ slice: Shipping, lane: automation }")).unwrap();
+        assert!(matches!(
+            convert_yaml_to_domain(parsed),
+            Err(ConversionError::InvalidWaypoint(message)) if message.contains("unknown slice 'Shipping'")
+        ));
+
+        let parsed = yaml_parser::parse_yaml(&model("{ slice: SendEmail, lane: ui }")).unwrap();
+        assert!(matches!(
+            convert_yaml_to_domain(parsed),
+            Err(ConversionError::InvalidWaypoint(message)) if message.contains("unknown swimlane 'ui'")
+        ));
+    }
+
+    #[test]
+    fn accepts_retry_loops_and_two_way_links_only_where_they_make_sense() {
+        let model = |connection: &str| {
+            format!(
+                r#"
+workflow: Test
+swimlanes:
+  - ui: "UI"
+  - backend: "Backend"
+views:
+  OrderScreen:
+    description: "Order screen"
+    swimlane: ui
+    components:
+      - Submit: Button
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "Order placed"
+    swimlane: backend
+slices:
+  - name: Ordering
+    connections:
+      - {connection}
+"#
+            )
+        };
+        let convert = |connection: &str| {
+            convert_yaml_to_domain(yaml_parser::parse_yaml(&model(connection)).unwrap())
+        };
+
+        let converted = convert("OrderScreen <-> PlaceOrder").unwrap();
+        let connection = converted.slices[0].connections.first();
+        assert!(connection.bidirectional);
+        assert_eq!(connection.to.entity_name(), "PlaceOrder");
+        assert!(
+            !convert("PlaceOrder -> PlaceOrder").unwrap().slices[0]
+                .connections
+                .first()
+                .bidirectional
+        );
+
+        for (connection, expected) in [
+            (
+                "OrderPlaced -> OrderPlaced",
+                "loops back to event 'OrderPlaced'",
+            ),
+            ("PlaceOrder <-> PlaceOrder", "cannot run both ways"),
+            (
+                "PlaceOrder <-> OrderPlaced",
+                "runs both ways through event 'OrderPlaced'",
+            ),
+        ] {
+            match convert(connection) {
+                Err(ConversionError::InvalidConnection { message, .. }) => {
+                    assert!(message.contains(expected), "{message}");
+                }
+                other => panic!("Expected InvalidConnection for {connection}, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn authorized_roles_must_be_declared() {
+        let model = |roles: &str| {
+            format!(
+                r#"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+roles: [{roles}]
+commands:
+  RefundOrder:
+    description: "Refund an order"
+    swimlane: backend
+    idempotent: true
+    authorized_roles: [support, finance]
+"#
+            )
+        };
+        let convert =
+            |roles: &str| convert_yaml_to_domain(yaml_parser::parse_yaml(&model(roles)).unwrap());
+
+        let converted = convert("support, finance, admin").unwrap();
+        let command = converted.commands.values().next().unwrap();
+        assert_eq!(command.idempotent, Some(true));
+        assert_eq!(command.authorized_roles.len(), 2);
+        assert_eq!(converted.roles.len(), 3);
+
+        assert_eq!(
+            convert("support").unwrap_err().to_string(),
+            "Command 'RefundOrder' is authorized for undeclared role 'finance'"
+        );
+    }
+
+    #[test]
+    fn field_types_must_be_declared_once_any_are() {
+        let model = |types: &str| {
+            format!(
+                r#"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+{types}
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+    data:
+      order_id: OrderId
+      lines: List<OrderLine>
+      placed_by: EmailAddress<Verified>
+"#
+            )
+        };
+        let convert =
+            |types: &str| convert_yaml_to_domain(yaml_parser::parse_yaml(&model(types)).unwrap());
+
+        assert!(convert("").is_ok());
+        let declared = convert(
+            "types:\n  OrderId: UUID\n  EmailAddress: String\n  OrderLine: { sku: Sku, quantity: Integer }\n  Sku: String",
+        )
+        .unwrap();
+        assert_eq!(declared.types.len(), 4);
+
+        assert_eq!(
+            convert("types:\n  OrderId: UUID\n  EmailAddress: String")
+                .unwrap_err()
+                .to_string(),
+            "Field 'lines' of 'OrderPlaced' has undeclared type 'OrderLine'"
+        );
+        assert_eq!(
+            convert("types:\n  OrderId: UUID\n  EmailAddress: String\n  OrderLine: { sku: Sku }")
+                .unwrap_err()
+                .to_string(),
+            "Field 'sku' of 'OrderLine' has undeclared type 'Sku'"
+        );
+    }
+
+    #[test]
+    fn declared_types_may_enumerate_or_constrain_values() {
+        let convert = |declaration: &str| {
+            convert_yaml_to_domain(
+                yaml_parser::parse_yaml(&format!(
+                    "workflow: Test\nswimlanes:\n  - backend: \"Backend\"\ntypes:\n  {declaration}\n"
+                ))
+                .unwrap(),
+            )
+        };
+        let described = |declaration: &str| {
+            convert(declaration)
+                .unwrap()
+                .types
+                .values()
+                .next()
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(
+            described("Currency: { enum: [USD, EUR, GBP] }"),
+            "one of USD, EUR, GBP"
+        );
+        assert_eq!(
+            described("Quantity: { type: Integer, min: 1, max: 100 }"),
+            "Integer from 1 to 100"
+        );
+        assert_eq!(
+            described("Sku: { type: String, pattern: '^[A-Z]{3}-[0-9]{4}$' }"),
+            "String matching `^[A-Z]{3}-[0-9]{4}$`"
+        );
+        assert_eq!(
+            described("Money: { amount: Decimal, currency: String }"),
+            "{ amount: Decimal, currency: String }"
+        );
+
+        assert_eq!(
+            convert("Quantity: { type: Integer, min: 10, max: 1 }")
+                .unwrap_err()
+                .to_string(),
+            "Invalid type 'Quantity': min 10 is greater than max 1"
+        );
+        assert_eq!(
+            convert("Sku: { type: String, min: 1 }")
+                .unwrap_err()
+                .to_string(),
+            "Invalid type 'Sku': min and max only apply to Integer, Float, or Decimal, not String"
+        );
+        assert_eq!(
+            convert("Quantity: { type: Integer, min: 1, max: .nan }")
+                .unwrap_err()
+                .to_string(),
+            "Invalid type 'Quantity': min and max must be numbers, not NaN"
+        );
+        assert_eq!(
+            convert("Quantity: { type: Float, min: -.inf }")
+                .unwrap_err()
+                .to_string(),
+            "Invalid type 'Quantity': min and max must be numbers, not -inf"
+        );
+        assert!(convert("Sku: { type: String, pattern: '[' }").is_err());
+        assert_eq!(
+            convert("Currency: { enum: [USD, USD] }")
+                .unwrap_err()
+                .to_string(),
+            "Invalid type 'Currency': variant 'USD' is listed twice"
+        );
+    }
+
+    #[test]
+    fn infers_a_slice_per_tested_command_when_none_are_declared() {
+        let mut warnings = Warnings::new();
+        let model = convert_yaml_to_domain_with_warnings(
+            yaml_parser::parse_yaml(
+                r#"workflow: Test
+swimlanes:
+  - backend: "Backend"
+commands:
+  ShipOrder:
+    description: "Ship an order"
+    swimlane: backend
+    tests:
+      "Ships":
+        When:
+          - ShipOrder:
+              id: A
+        Then:
+          - OrderShipped:
+              id: A
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+    tests:
+      "Places":
+        When:
+          - PlaceOrder:
+              id: A
+        Then:
+          - OrderPlaced:
+              id: A
+  CancelOrder:
+    description: "Cancel an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+  OrderShipped:
+    description: "An order was shipped"
+    swimlane: backend
+"#,
+            )
+            .unwrap(),
+            &mut warnings,
+        )
+        .unwrap();
+
+        let slices: Vec<(String, String)> = model
+            .slices
+            .iter()
+            .map(|slice| {
+                let connection = slice.connections.first();
+                (
+                    slice.name.clone().into_inner().into_inner(),
+                    format!("{:?} -> {:?}", connection.from, connection.to),
+                )
+            })
+            .collect();
+        assert_eq!(slices.len(), 2);
+        assert_eq!(slices[0].0, "Ship Order");
+        assert!(slices[0].1.contains("ShipOrder") && slices[0].1.contains("OrderShipped"));
+        assert_eq!(slices[1].0, "Place Order");
+        let inferred: Vec<_> = warnings.of_kind(WarningKind::InferredSlices).collect();
+        assert_eq!(inferred.len(), 1);
+        assert!(inferred[0].message.contains("'Ship Order', 'Place Order'"));
+    }
+
+    #[test]
+    fn scenarios_without_when_or_then_steps_are_named_in_the_error() {
+        let convert = |sections: &str| {
+            convert_yaml_to_domain(
+                yaml_parser::parse_yaml(&format!(
+                    r#"workflow: Test
+swimlanes:
+  - backend: "Backend"
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+    tests:
+      "Places":
+{sections}
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+"#
+                ))
+                .unwrap(),
+            )
+        };
+
+        assert_eq!(
+            convert("        When:\n        Then:\n          - OrderPlaced:\n              id: A")
+                .unwrap_err()
+                .to_string(),
+            "Test 'Places' of command 'PlaceOrder' has no steps under When; every scenario needs a command under When and an expected event or error under Then"
+        );
+        assert!(matches!(
+            convert("        When:\n          - PlaceOrder:\n              id: A"),
+            Err(ConversionError::EmptyScenarioSection {
+                section: "Then",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn scenario_examples_must_suit_their_fields() {
+        let convert = |examples: &str| {
+            convert_yaml_to_domain(
+                yaml_parser::parse_yaml(&format!(
+                    r#"workflow: Test
+swimlanes:
+  - backend: "Backend"
+types:
+  Quantity: {{ type: Integer, min: 1, max: 100 }}
+  Currency: {{ enum: [USD, EUR] }}
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+    data:
+      quantity: Quantity
+      currency: Currency
+      placed_at: Timestamp
+    tests:
+      "Places":
+        examples: {examples}
+        When:
+          - PlaceOrder:
+              quantity: A
+              currency: B
+              placed_at: C
+        Then:
+          - OrderPlaced:
+              quantity: A
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+    data:
+      quantity: Quantity
+"#
+                ))
+                .unwrap(),
+            )
+        };
+
+        let model = convert("{ A: 3, B: EUR, C: 2025-06-01T09:30:00Z }").unwrap();
+        let examples = &model
+            .commands
+            .values()
+            .next()
+            .unwrap()
+            .tests
+            .values()
+            .next()
+            .unwrap()
+            .examples;
+        assert_eq!(examples.len(), 3);
+
+        assert_eq!(
+            convert("{ A: 500 }").unwrap_err().to_string(),
+            "Invalid example for 'A' in test 'Places' of command 'PlaceOrder': \
+             '500' is above the Quantity maximum of 100, as field 'quantity' of 'PlaceOrder' requires"
+        );
+        assert_eq!(
+            convert("{ A: three }").unwrap_err().to_string(),
+            "Invalid example for 'A' in test 'Places' of command 'PlaceOrder': \
+             'three' is not an Integer, as field 'quantity' of 'PlaceOrder' requires"
+        );
+        assert_eq!(
+            convert("{ B: JPY }").unwrap_err().to_string(),
+            "Invalid example for 'B' in test 'Places' of command 'PlaceOrder': \
+             'JPY' is not one of the Currency values, as field 'currency' of 'PlaceOrder' requires"
+        );
+        assert_eq!(
+            convert("{ C: yesterday }").unwrap_err().to_string(),
+            "Invalid example for 'C' in test 'Places' of command 'PlaceOrder': \
+             'yesterday' is not a Timestamp, as field 'placed_at' of 'PlaceOrder' requires"
+        );
+        assert_eq!(
+            convert("{ D: 7 }").unwrap_err().to_string(),
+            "Invalid example for 'D' in test 'Places' of command 'PlaceOrder': \
+             the scenario does not use this placeholder"
+        );
+    }
+
+    #[test]
+    fn field_types_must_agree_within_a_stream() {
+        let yaml = r#"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+events:
+  AccountOpened:
+    description: "An account was opened"
+    swimlane: backend
+    data:
+      account_id:
+        type: AccountId
+        stream-id: true
+      email: EmailAddress<Unverified>
+      balance: Decimal
+  EmailVerified:
+    description: "An email address was verified"
+    swimlane: backend
+    data:
+      account_id:
+        type: AccountId
+        stream-id: true
+      email: EmailAddress<Verified>
+      balance: Integer
+  NewsletterSent:
+    description: "A newsletter was sent"
+    swimlane: backend
+    data:
+      balance: String
+"#;
+        let mut warnings = Warnings::new();
+        convert_yaml_to_domain_with_warnings(yaml_parser::parse_yaml(yaml).unwrap(), &mut warnings)
+            .unwrap();
+
+        let messages: Vec<&str> = warnings
+            .iter()
+            .filter(|warning| warning.kind == WarningKind::FieldTypeMismatch)
+            .map(|warning| warning.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "Field 'balance' has different types in entities sharing the 'account_id' stream: Decimal in AccountOpened; Integer in EmailVerified"
+            ]
+        );
+    }
+
+    #[test]
+    fn fields_must_keep_their_name_and_type_from_command_to_event() {
+        let yaml = r#"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+commands:
+  Register:
+    description: "Register an account"
+    swimlane: backend
+    data:
+      account_id: AccountId
+      email: EmailAddress<Unverified>
+      age: Integer
+      nickname: String
+events:
+  Registered:
+    description: "An account was registered"
+    swimlane: backend
+    data:
+      account_id: AccountId
+      emailAddress: EmailAddress<Verified>
+      age: String
+      display_name: String
+slices:
+  - name: Registration
+    connections:
+      - Register -> Registered
+"#;
+        let mut warnings = Warnings::new();
+        convert_yaml_to_domain_with_warnings(yaml_parser::parse_yaml(yaml).unwrap(), &mut warnings)
+            .unwrap();
+
+        let messages: Vec<&str> = warnings
+            .iter()
+            .filter(|warning| warning.kind == WarningKind::FieldDrift)
+            .map(|warning| warning.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "Field 'age' is Integer in command 'Register' but String in event 'Registered', which it records",
+                "Field 'email' of command 'Register' is named 'emailAddress' in event 'Registered', which it records",
+            ]
+        );
+    }
+
+    #[test]
+    fn entities_include_the_fields_of_the_sets_they_extend() {
+        let yaml = r#"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+field_sets:
+  Envelope:
+    fields:
+      occurred_at: Timestamp
+      correlation_id: CorrelationId
+  Audited:
+    extends: [Envelope]
+    fields:
+      actor: UserId
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+    extends: [Audited]
+    data:
+      order_id: OrderId
+      occurred_at: Date
+"#;
+        let model = convert_yaml_to_domain(yaml_parser::parse_yaml(yaml).unwrap()).unwrap();
+
+        let event = model.events.values().next().unwrap();
+        let mut fields: Vec<String> = event
+            .data
+            .iter()
+            .map(|(name, def)| {
+                format!(
+                    "{}: {}",
+                    name.clone().into_inner().as_str(),
+                    def.field_type.clone().into_inner().as_str()
+                )
+            })
+            .collect();
+        fields.sort();
+        assert_eq!(
+            fields,
+            [
+                "actor: UserId",
+                "correlation_id: CorrelationId",
+                "occurred_at: Date",
+                "order_id: OrderId"
+            ]
+        );
+    }
+
+    #[test]
+    fn field_sets_must_exist_agree_and_not_extend_each_other_in_a_cycle() {
+        let convert = |field_sets: &str, extends: &str| {
+            let yaml = format!(
+                r#"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+field_sets:
+{field_sets}
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+    extends: {extends}
+"#
+            );
+            convert_yaml_to_domain(yaml_parser::parse_yaml(&yaml).unwrap())
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            convert("  Envelope:\n    fields:\n      id: Uuid", "[Header]"),
+            "Unknown field set 'Header' extended by command 'PlaceOrder'"
+        );
+        assert_eq!(
+            convert(
+                "  A:\n    extends: [B]\n  B:\n    extends: [C]\n  C:\n    extends: [B]",
+                "[A]"
+            ),
+            "Field sets extend each other in a cycle: B -> C -> B"
+        );
+        assert_eq!(
+            convert(
+                "  A:\n    fields:\n      id: Uuid\n  B:\n    fields:\n      id: String",
+                "[A, B]"
+            ),
+            "Field 'id' of command 'PlaceOrder' is defined differently by field sets 'A' and 'B'"
+        );
+    }
+
+    #[test]
+    fn volumes_must_be_a_count_per_period() {
+        let convert = |volume: &str| {
+            convert_yaml_to_domain(
+                yaml_parser::parse_yaml(&format!(
+                    r#"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+    volume: {volume}
+"#
+                ))
+                .unwrap(),
+            )
+        };
+
+        let converted = convert("10k/day").unwrap();
+        let event = converted.events.values().next().unwrap();
+        assert_eq!(event.volume.as_ref().unwrap().per_day(), 10_000.0);
+
+        assert_eq!(
+            convert("often").unwrap_err().to_string(),
+            "Invalid volume 'often' on 'OrderPlaced': expected a number, optionally followed by k, M, or B, per s, min, hour, day, week, or month, e.g. 10k/day"
+        );
+    }
+
+    #[test]
+    fn consistency_is_declared_only_where_an_event_feeds_a_projection() {
+        let model = |connection: &str| {
+            format!(
+                r#"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+  - read: "Read Models"
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "Order placed"
+    swimlane: backend
+projections:
+  OrderSummary:
+    description: "Orders by customer"
+    swimlane: read
+slices:
+  - name: Ordering
+    connections:
+      - connection: {connection}
+        consistency: eventual
+"#
+            )
+        };
+        let convert = |connection: &str| {
+            convert_yaml_to_domain(yaml_parser::parse_yaml(&model(connection)).unwrap())
+        };
+
+        let converted = convert("OrderPlaced -> OrderSummary").unwrap();
+        assert_eq!(
+            converted.slices[0].connections.first().consistency,
+            Some(domain::Consistency::Eventual)
+        );
+
+        match convert("PlaceOrder -> OrderPlaced") {
+            Err(ConversionError::InvalidConnection { message, .. }) => {
+                assert!(
+                    message.contains("only applies to connections from an event to a projection"),
+                    "{message}"
+                );
+            }
+            other => panic!("Expected InvalidConnection, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn converts_connection_markers_and_rejects_unknown_types() {
+        let model = |markers: &str| {
+            format!(
+                r#"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+markers:
+{markers}
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "Order placed"
+    swimlane: backend
+slices:
+  - name: Ordering
+    connections:
+      - connection: PlaceOrder -> OrderPlaced
+        marker: diamond
+"#
+            )
+        };
+
+        let parsed = yaml_parser::parse_yaml(&model(
+            "  \"event -> automation\": circle\n  \"* -> *\": open",
+        ))
+        .unwrap();
+        let converted = convert_yaml_to_domain(parsed).unwrap();
+        let markers = &converted.connection_markers;
+        assert_eq!(
+            markers.style_for(EntityType::Event, EntityType::Automation),
+            domain::MarkerStyle::Circle
+        );
+        assert_eq!(
+            markers.style_for(EntityType::Command, EntityType::Event),
+            domain::MarkerStyle::Open
+        );
+        assert_eq!(
+            converted.slices[0].connections.first().marker,
+            Some(domain::MarkerStyle::Diamond)
+        );
+
+        let parsed = yaml_parser::parse_yaml(&model("  \"event -> robot\": circle")).unwrap();
+        assert!(matches!(
+            convert_yaml_to_domain(parsed),
+            Err(ConversionError::InvalidMarkers(message)) if message.starts_with("'robot' is not an entity type")
+        ));
+    }
+
+    #[test]
+    fn converts_swimlane_tints_and_icons() {
+        let model = |details: &str| {
+            format!(
+                "workflow: Test\nswimlanes:\n  - ui:\n      name: \"Storefront\"\n{details}\n  - backend\n"
+            )
+        };
+
+        let parsed =
+            yaml_parser::parse_yaml(&model("      color: teal\n      icon: \"🖥\"")).unwrap();
+        let converted = convert_yaml_to_domain(parsed).unwrap();
+        let style = &converted.swimlanes.first().style;
+        assert_eq!(
+            style.color.as_ref().map(|color| color.as_ref()),
+            Some("teal")
+        );
+        assert_eq!(style.icon.as_ref().map(|icon| icon.as_ref()), Some("🖥"));
+        assert_eq!(
+            converted.swimlanes.last().style,
+            domain::SwimlaneStyle::default()
+        );
+
+        let parsed = yaml_parser::parse_yaml(&model("      icon: \"two words\"")).unwrap();
+        assert!(matches!(
+            convert_yaml_to_domain(parsed),
+            Err(ConversionError::InvalidSwimlaneStyle(message)) if message.contains("'ui' icon")
+        ));
+    }
+
+    #[test]
+    fn flattens_swimlane_groups_and_rejects_nested_ones() {
+        let yaml = r##"
+workflow: Test
+swimlanes:
+  - ui: "Storefront"
+  - group: "Backend"
+    background: "#eef3ff"
+    swimlanes:
+      - handlers: "Command Handlers"
+      - stream
+"##;
+        let model = convert_yaml_to_domain(yaml_parser::parse_yaml(yaml).unwrap()).unwrap();
+
+        let ids: Vec<_> = model
+            .swimlanes
+            .iter()
+            .map(|lane| lane.id.clone().into_inner().into_inner())
+            .collect();
+        assert_eq!(ids, ["ui", "handlers", "stream"]);
+        assert_eq!(model.swimlane_groups.len(), 1);
+        let group = &model.swimlane_groups[0];
+        assert_eq!(group.name.clone().into_inner().as_str(), "Backend");
+        assert_eq!(group.swimlanes.len(), 2);
+        assert!(!group.collapsed);
+        assert_eq!(
+            group.background.as_ref().map(|color| color.as_ref()),
+            Some("#eef3ff")
+        );
+
+        let nested = r#"
+workflow: Test
+swimlanes:
+  - group: "Backend"
+    swimlanes:
+      - group: "Storage"
+        swimlanes:
+          - stream
+"#;
+        assert!(matches!(
+            convert_yaml_to_domain(yaml_parser::parse_yaml(nested).unwrap()),
+            Err(ConversionError::InvalidSwimlaneGroup(message)) if message.contains("'Storage'")
+        ));
+    }
+
+    #[test]
+    fn checks_custom_entity_kinds() {
+        let model = |kind: &str, connection: &str| {
+            format!(
+                r##"
+workflow: Test
+swimlanes:
+  - backend: "Backend"
+extensions:
+  policy:
+    base: automation
+    color: "#e8daef"
+    icon: "⚖"
+    connects_to: [command]
+commands:
+  RefundOrder:
+    description: "Refund the order"
+    swimlane: backend
+events:
+  OrderCancelled:
+    description: "The order was cancelled"
+    swimlane: backend
+automations:
+  RefundPolicy:
+    swimlane: backend
+    kind: {kind}
+slices:
+  - name: Refunds
+    connections:
+      - {connection}
+"##
+            )
+        };
+        let convert =
+            |yaml: String| convert_yaml_to_domain(yaml_parser::parse_yaml(&yaml).unwrap());
+
+        let converted = convert(model("policy", "RefundPolicy -> RefundOrder")).unwrap();
+        let (name, policy) = converted.extensions.iter().next().unwrap();
+        assert_eq!(name.clone().into_inner().as_str(), "policy");
+        assert_eq!(policy.base, EntityType::Automation);
+        assert_eq!(policy.icon.as_ref().map(|icon| icon.as_ref()), Some("⚖"));
+
+        assert!(matches!(
+            convert(model("policy", "RefundPolicy -> OrderCancelled")),
+            Err(ConversionError::InvalidConnection { message, .. })
+                if message.contains("a policy may only connect to command")
+        ));
+        assert!(matches!(
+            convert(model("rule", "RefundPolicy -> RefundOrder")),
+            Err(ConversionError::InvalidKind { entity, reason })
+                if entity == "RefundPolicy" && reason.contains("not declared")
+        ));
+
+        let wrong_base = model("policy", "RefundPolicy -> RefundOrder")
+            .replace("base: automation", "base: command");
+        assert!(matches!(
+            convert(wrong_base),
+            Err(ConversionError::InvalidKind { reason, .. }) if reason.contains("based on command")
+        ));
+        let built_in =
+            model("policy", "RefundPolicy -> RefundOrder").replace("  policy:\n", "  event:\n");
+        assert!(matches!(
+            convert(built_in),
+            Err(ConversionError::InvalidExtension { name, .. }) if name == "event"
+        ));
+    }
+
+    #[test]
+    fn converts_layout_constraints_naming_defined_entities() {
+        let model = |constraints: &str| {
+            format!(
+                r#"
+workflow: Test
+layout: constraints
+constraints:
+{constraints}
+swimlanes:
+  - backend: "Backend"
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "Order placed"
+    swimlane: backend
+"#
+            )
+        };
+
+        let parsed = yaml_parser::parse_yaml(&model(
+            "  - align: [PlaceOrder, OrderPlaced]\n  - gap: {between: [PlaceOrder, OrderPlaced], min: 40}",
+        ))
+        .unwrap();
+        let converted = convert_yaml_to_domain(parsed);
+        if cfg!(feature = "constraint-layout") {
+            let converted = converted.unwrap();
+            assert_eq!(converted.layout, domain::LayoutStrategy::Constraints);
+            assert_eq!(
+                converted.layout_constraints,
+                [
+                    domain::LayoutConstraint::Align(vec![
+                        "PlaceOrder".to_string(),
+                        "OrderPlaced".to_string()
+                    ]),
+                    domain::LayoutConstraint::Gap {
+                        left: "PlaceOrder".to_string(),
+                        right: "OrderPlaced".to_string(),
+                        min: 40
+                    },
+                ]
+            );
+        } else {
+            assert!(matches!(
+                converted,
+                Err(ConversionError::ConstraintLayoutUnavailable)
+            ));
+        }
+
+        let parsed =
+            yaml_parser::parse_yaml(&model("  - order: [PlaceOrder, OrderShipped]")).unwrap();
+        assert!(matches!(
+            convert_yaml_to_domain(parsed),
+            Err(ConversionError::UnknownConstraintEntity { name }) if name == "OrderShipped"
+        ));
+    }
+}
@@ -0,0 +1,481 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Editing a YAML document in place, keeping its comments and formatting.
+//!
+//! Deserializing a model and serializing it again loses everything serde
+//! does not model: comments, blank lines, key order, quoting, and flow
+//! style. Tools that change a few names in a hand-written model use a
+//! [`YamlDocument`] instead, which records where each scalar sits in the
+//! source text and splices replacements into exactly those spans, leaving
+//! every other byte of the file as it was.
+//!
+//! Only scalars on a single line can be replaced: plain, single-quoted, and
+//! double-quoted ones. Block scalars (`|` and `>`) and plain scalars folded
+//! over several lines report [`YamlEditError::Unsupported`].
+
+use super::source_map::{PathSegment, SourceLocation, SourcePath};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+use yaml_rust2::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust2::scanner::{Marker, TScalarStyle};
+
+/// A scalar of a [`YamlDocument`]: a mapping key or a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YamlScalar {
+    /// The path of the node, e.g. `events.UserCreated.swimlane`. A mapping
+    /// key has the path of the value stored under it.
+    pub path: SourcePath,
+    /// Whether the scalar is a mapping key rather than a value.
+    pub is_key: bool,
+    /// The scalar's value, with quotes and escapes resolved.
+    pub value: String,
+    /// Where the scalar starts in the source.
+    pub location: SourceLocation,
+    /// How the scalar is written.
+    style: TScalarStyle,
+    /// The bytes of the source the scalar spans, if it can be replaced.
+    span: Option<Range<usize>>,
+}
+
+/// Errors editing a YAML document.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum YamlEditError {
+    /// The document is not valid YAML.
+    #[error("YAML syntax error: {0}")]
+    Syntax(String),
+
+    /// No scalar is at the path edited.
+    #[error("No scalar at {0}")]
+    NotFound(SourcePath),
+
+    /// The scalar is written in a way that cannot be replaced in place.
+    #[error("Cannot edit the block or multi-line scalar at {path} ({location})")]
+    Unsupported {
+        /// The path of the scalar.
+        path: SourcePath,
+        /// Where the scalar starts.
+        location: SourceLocation,
+    },
+}
+
+/// A YAML document whose scalars can be replaced without reformatting it.
+///
+/// Edits are recorded against the source as parsed; [`YamlDocument`]'s
+/// `Display` writes the source with them applied. The scalars keep their
+/// original values, so edits never affect which scalars later edits match.
+#[derive(Debug, Clone)]
+pub struct YamlDocument {
+    source: String,
+    scalars: Vec<YamlScalar>,
+    /// Replacement text for the spans edited, keyed by where they start.
+    edits: BTreeMap<usize, (usize, String)>,
+}
+
+impl YamlDocument {
+    /// Parses `source`, recording the position of every scalar.
+    pub fn parse(source: &str) -> Result<Self, YamlEditError> {
+        let mut recorder = ScalarRecorder::new(source);
+        Parser::new_from_str(source)
+            .load(&mut recorder, false)
+            .map_err(|e| YamlEditError::Syntax(e.to_string()))?;
+        Ok(Self {
+            source: source.to_string(),
+            scalars: recorder.scalars,
+            edits: BTreeMap::new(),
+        })
+    }
+
+    /// Returns the scalars of the document in source order.
+    pub fn scalars(&self) -> &[YamlScalar] {
+        &self.scalars
+    }
+
+    /// Returns whether any scalar has been replaced.
+    pub fn is_modified(&self) -> bool {
+        !self.edits.is_empty()
+    }
+
+    /// Replaces the value at `path` with `value`.
+    pub fn set_value(&mut self, path: &SourcePath, value: &str) -> Result<(), YamlEditError> {
+        let index = self.find(path, false)?;
+        self.replace(index, value)
+    }
+
+    /// Renames the mapping key whose value is at `path` to `key`.
+    pub fn rename_key(&mut self, path: &SourcePath, key: &str) -> Result<(), YamlEditError> {
+        let index = self.find(path, true)?;
+        self.replace(index, key)
+    }
+
+    /// Replaces each scalar `edit` returns a new value for, returning how
+    /// many were replaced.
+    ///
+    /// Nothing is replaced if any scalar to replace cannot be.
+    pub fn replace_where(
+        &mut self,
+        mut edit: impl FnMut(&YamlScalar) -> Option<String>,
+    ) -> Result<usize, YamlEditError> {
+        let replacements: Vec<_> = self
+            .scalars
+            .iter()
+            .enumerate()
+            .filter_map(|(index, scalar)| edit(scalar).map(|value| (index, value)))
+            .collect();
+        if let Some((index, _)) = replacements
+            .iter()
+            .find(|(index, _)| self.scalars[*index].span.is_none())
+        {
+            return Err(self.unsupported(*index));
+        }
+        for (index, value) in &replacements {
+            self.replace(*index, value)?;
+        }
+        Ok(replacements.len())
+    }
+
+    /// Returns the index of the key or value scalar at `path`.
+    fn find(&self, path: &SourcePath, is_key: bool) -> Result<usize, YamlEditError> {
+        self.scalars
+            .iter()
+            .position(|scalar| scalar.is_key == is_key && &scalar.path == path)
+            .ok_or_else(|| YamlEditError::NotFound(path.clone()))
+    }
+
+    /// Records the replacement of the scalar at `index`, written in its own
+    /// style where that can hold `value`.
+    fn replace(&mut self, index: usize, value: &str) -> Result<(), YamlEditError> {
+        let scalar = &self.scalars[index];
+        let Some(span) = scalar.span.clone() else {
+            return Err(self.unsupported(index));
+        };
+        let text = quote(value, scalar.style);
+        self.edits.insert(span.start, (span.end, text));
+        Ok(())
+    }
+
+    fn unsupported(&self, index: usize) -> YamlEditError {
+        let scalar = &self.scalars[index];
+        YamlEditError::Unsupported {
+            path: scalar.path.clone(),
+            location: scalar.location,
+        }
+    }
+}
+
+impl fmt::Display for YamlDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut copied = 0;
+        for (&start, (end, text)) in &self.edits {
+            f.write_str(&self.source[copied..start])?;
+            f.write_str(text)?;
+            copied = *end;
+        }
+        f.write_str(&self.source[copied..])
+    }
+}
+
+/// Writes `value` as a scalar, keeping `style` unless it cannot hold the
+/// value unchanged, in which case the value is double-quoted.
+fn quote(value: &str, style: TScalarStyle) -> String {
+    match style {
+        TScalarStyle::Plain if is_plain_string(value) => value.to_string(),
+        TScalarStyle::SingleQuoted if !value.contains('\n') => {
+            format!("'{}'", value.replace('\'', "''"))
+        }
+        // JSON strings are valid double-quoted YAML scalars
+        _ => serde_json::to_string(value).expect("strings always serialize"),
+    }
+}
+
+/// Returns whether `value` reads back as the same string when written plain.
+fn is_plain_string(value: &str) -> bool {
+    let reserved = ["true", "false", "yes", "no", "on", "off", "null", "~"];
+    value
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_alphanumeric() || first == '_')
+        && value
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ' ' | '<' | '>'))
+        && !value.ends_with(' ')
+        && !reserved.contains(&value.to_lowercase().as_str())
+        && value.parse::<f64>().is_err()
+}
+
+/// What the next node event inside a collection represents.
+#[derive(Debug)]
+enum Frame {
+    /// Inside a mapping, waiting for a key (`None`) or for the value of a key.
+    Mapping { pending_key: Option<String> },
+    /// Inside a sequence, at the given item position.
+    Sequence { next_index: usize },
+}
+
+/// Event receiver that tracks the current path and records every scalar.
+struct ScalarRecorder<'a> {
+    source: &'a str,
+    /// Byte offset of each character of the source, and of its end.
+    offsets: Vec<usize>,
+    path: Vec<PathSegment>,
+    frames: Vec<Frame>,
+    /// Nesting depth inside a non-scalar mapping key, which has no path.
+    complex_key_depth: usize,
+    scalars: Vec<YamlScalar>,
+}
+
+impl<'a> ScalarRecorder<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            offsets: source
+                .char_indices()
+                .map(|(offset, _)| offset)
+                .chain([source.len()])
+                .collect(),
+            path: Vec::new(),
+            frames: Vec::new(),
+            complex_key_depth: 0,
+            scalars: Vec::new(),
+        }
+    }
+
+    /// Handles the start of a node, returning the path of its value and
+    /// whether it is a mapping key, or `None` inside a complex key.
+    fn start_node(
+        &mut self,
+        key: Option<&str>,
+        is_collection: bool,
+    ) -> Option<(Vec<PathSegment>, bool)> {
+        if self.complex_key_depth > 0 {
+            if is_collection {
+                self.complex_key_depth += 1;
+            }
+            return None;
+        }
+
+        let segment = match self.frames.last_mut() {
+            None => None,
+            Some(Frame::Mapping { pending_key: None }) => {
+                if key.is_none() && is_collection {
+                    self.complex_key_depth = 1;
+                    return None;
+                }
+                let key = key.unwrap_or_default().to_string();
+                let mut path = self.path.clone();
+                path.push(PathSegment::Key(key.clone()));
+                if let Some(Frame::Mapping { pending_key }) = self.frames.last_mut() {
+                    *pending_key = Some(key);
+                }
+                return Some((path, true));
+            }
+            Some(Frame::Mapping { pending_key }) => {
+                Some(PathSegment::Key(pending_key.take().unwrap_or_default()))
+            }
+            Some(Frame::Sequence { next_index }) => {
+                *next_index += 1;
+                Some(PathSegment::Index(*next_index - 1))
+            }
+        };
+
+        let mut path = self.path.clone();
+        path.extend(segment);
+        if is_collection {
+            self.path = path.clone();
+        }
+        Some((path, false))
+    }
+
+    fn open_collection(&mut self, frame: Frame) {
+        if self.complex_key_depth == 0 {
+            self.frames.push(frame);
+        }
+    }
+
+    fn end_collection(&mut self) {
+        if self.complex_key_depth > 0 {
+            self.complex_key_depth -= 1;
+            if self.complex_key_depth == 0 {
+                if let Some(Frame::Mapping { pending_key }) = self.frames.last_mut() {
+                    *pending_key = Some(String::new());
+                }
+            }
+            return;
+        }
+
+        self.frames.pop();
+        if !self.frames.is_empty() {
+            self.path.pop();
+        }
+    }
+
+    /// Returns the bytes a scalar starting at `start` spans, if it lies on
+    /// one line and can be replaced.
+    fn span(&self, start: usize, value: &str, style: TScalarStyle) -> Option<Range<usize>> {
+        let text = &self.source[start..];
+        let length = match style {
+            TScalarStyle::Plain if !value.is_empty() && text.starts_with(value) => value.len(),
+            TScalarStyle::SingleQuoted if text.starts_with('\'') => {
+                let mut chars = text.char_indices().skip(1).peekable();
+                loop {
+                    match chars.next()? {
+                        (_, '\n') => return None,
+                        (_, '\'') if chars.peek().is_some_and(|&(_, c)| c == '\'') => {
+                            chars.next();
+                        }
+                        (offset, '\'') => break offset + 1,
+                        _ => {}
+                    }
+                }
+            }
+            TScalarStyle::DoubleQuoted if text.starts_with('"') => {
+                let mut chars = text.char_indices().skip(1);
+                loop {
+                    match chars.next()? {
+                        (_, '\n') => return None,
+                        (_, '\\') => {
+                            chars.next();
+                        }
+                        (offset, '"') => break offset + 1,
+                        _ => {}
+                    }
+                }
+            }
+            _ => return None,
+        };
+        Some(start..start + length)
+    }
+}
+
+impl MarkedEventReceiver for ScalarRecorder<'_> {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        match event {
+            Event::Scalar(value, style, ..) => {
+                if let Some((path, is_key)) = self.start_node(Some(&value), false) {
+                    let start = self.offsets[mark.index().min(self.offsets.len() - 1)];
+                    let span = self.span(start, &value, style);
+                    self.scalars.push(YamlScalar {
+                        path: path.into_iter().fold(SourcePath::root(), |path, segment| {
+                            match segment {
+                                PathSegment::Key(key) => path.key(key),
+                                PathSegment::Index(index) => path.index(index),
+                            }
+                        }),
+                        is_key,
+                        value,
+                        location: mark.into(),
+                        style,
+                        span,
+                    });
+                }
+            }
+            Event::Alias(_) => {
+                self.start_node(None, false);
+            }
+            Event::MappingStart(..) => {
+                self.start_node(None, true);
+                self.open_collection(Frame::Mapping { pending_key: None });
+            }
+            Event::SequenceStart(..) => {
+                self.start_node(None, true);
+                self.open_collection(Frame::Sequence { next_index: 0 });
+            }
+            Event::MappingEnd | Event::SequenceEnd => self.end_collection(),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCUMENT: &str = r#"# Order handling
+workflow: Orders   # the title
+events:
+  # Raised once paid
+  OrderPlaced:
+    description: 'Customer''s order'
+    swimlane: backend
+slices:
+  - name: "Place order"
+    connections: [PlaceOrder -> OrderPlaced]
+notes: |
+  Kept as written
+"#;
+
+    #[test]
+    fn replaces_scalars_without_touching_anything_else() {
+        let mut document = YamlDocument::parse(DOCUMENT).unwrap();
+        let events = SourcePath::root().key("events");
+        document
+            .rename_key(&events.clone().key("OrderPlaced"), "OrderSubmitted")
+            .unwrap();
+        // Paths stay those of the source as parsed
+        document
+            .set_value(
+                &events.key("OrderPlaced").key("description"),
+                "Customer's submitted order",
+            )
+            .unwrap();
+        document
+            .set_value(&SourcePath::root().key("workflow"), "true")
+            .unwrap();
+        let replaced = document
+            .replace_where(|scalar| {
+                (scalar.value == "PlaceOrder -> OrderPlaced")
+                    .then(|| "PlaceOrder -> OrderSubmitted".to_string())
+            })
+            .unwrap();
+
+        assert_eq!(replaced, 1);
+        assert_eq!(
+            document.to_string(),
+            DOCUMENT
+                .replace("Orders   #", "\"true\"   #")
+                .replace("  OrderPlaced:", "  OrderSubmitted:")
+                .replace("'Customer''s order'", "'Customer''s submitted order'")
+                .replace("-> OrderPlaced]", "-> OrderSubmitted]")
+        );
+    }
+
+    #[test]
+    fn records_paths_of_keys_and_values() {
+        let document = YamlDocument::parse(DOCUMENT).unwrap();
+        let name = document
+            .scalars()
+            .iter()
+            .find(|scalar| scalar.value == "Place order")
+            .unwrap();
+
+        assert_eq!(name.path.to_string(), "slices[0].name");
+        assert!(!name.is_key);
+        assert_eq!(
+            name.location,
+            SourceLocation {
+                line: 9,
+                column: 11
+            }
+        );
+    }
+
+    #[test]
+    fn refuses_to_edit_block_scalars() {
+        let mut document = YamlDocument::parse(DOCUMENT).unwrap();
+        let error = document
+            .replace_where(|scalar| {
+                (scalar.value.starts_with("Kept") || scalar.value == "backend")
+                    .then(|| "x".to_string())
+            })
+            .unwrap_err();
+
+        assert!(matches!(error, YamlEditError::Unsupported { .. }));
+        assert!(!document.is_modified());
+        assert!(matches!(
+            YamlDocument::parse("a: [b"),
+            Err(YamlEditError::Syntax(_))
+        ));
+    }
+}
@@ -0,0 +1,1789 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! YAML parsing types for Event Model format.
+//!
+//! This module contains Serde-compatible types that map directly to the YAML structure
+//! of `.eventmodel` files. These types are used as an intermediate representation
+//! before conversion to domain types.
+
+use super::anchors::{AnchorSite, lint_anchors};
+use super::source_map::{SourceLocation, SourceMap, SourcePath};
+use crate::VERSION;
+use crate::infrastructure::warnings::{WarningKind, Warnings};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Root structure of an Event Model YAML file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlEventModel {
+    /// Optional version field, defaults to current application version
+    #[serde(default)]
+    pub version: Option<String>,
+
+    /// The name of the workflow being modeled, optionally with descriptive
+    /// metadata
+    pub workflow: YamlWorkflow,
+
+    /// Swimlane definitions
+    pub swimlanes: Vec<YamlSwimlane>,
+
+    /// Event definitions
+    #[serde(default)]
+    pub events: HashMap<String, YamlEvent>,
+
+    /// Command definitions
+    #[serde(default)]
+    pub commands: HashMap<String, YamlCommand>,
+
+    /// View definitions
+    #[serde(default)]
+    pub views: HashMap<String, YamlView>,
+
+    /// Projection definitions
+    #[serde(default)]
+    pub projections: HashMap<String, YamlProjection>,
+
+    /// Query definitions
+    #[serde(default)]
+    pub queries: HashMap<String, YamlQuery>,
+
+    /// Automation definitions
+    #[serde(default)]
+    pub automations: HashMap<String, YamlAutomation>,
+
+    /// Slice definitions
+    #[serde(default)]
+    pub slices: Vec<YamlSlice>,
+
+    /// How slices are arranged into columns, `standard` when unset
+    #[serde(default)]
+    pub layout: Option<YamlLayout>,
+
+    /// Alignment, ordering, and spacing rules kept by the `constraints` layout
+    #[serde(default)]
+    pub constraints: Vec<YamlLayoutConstraint>,
+
+    /// How slice headers are drawn
+    #[serde(default)]
+    pub slice_headers: Option<YamlSliceHeaders>,
+
+    /// How entity boxes are sized from their names, `standard` when unset
+    #[serde(default)]
+    pub entity_sizing: Option<YamlEntitySizing>,
+
+    /// Spacing in pixels of the grid entities and connector bends snap to;
+    /// no snapping when unset
+    #[serde(default)]
+    pub grid: Option<u32>,
+
+    /// Room connectors keep from entities and from each other
+    #[serde(default)]
+    pub routing: Option<YamlRouting>,
+
+    /// Background and watermark of the canvas behind the diagram
+    #[serde(default)]
+    pub canvas: Option<YamlCanvas>,
+
+    /// Base URL of the Jira site slice `issue` keys refer to, e.g.
+    /// `https://acme.atlassian.net`
+    #[serde(default)]
+    pub jira: Option<String>,
+
+    /// How swimlanes are ordered top to bottom, `declared` when unset
+    #[serde(default)]
+    pub swimlane_order: Option<YamlSwimlaneOrder>,
+
+    /// Markers drawn at connection targets, keyed by the types of entity a
+    /// connection joins, e.g. `event -> automation`; `*` matches any type
+    #[serde(default)]
+    pub markers: HashMap<String, YamlMarker>,
+
+    /// Whether connections drawn between the same two entities are merged
+    /// into one connector, `separate` when unset
+    #[serde(default)]
+    pub duplicate_connections: Option<YamlDuplicateConnections>,
+
+    /// How swimlanes a slice leaves empty are drawn in its column, `full`
+    /// when unset
+    #[serde(default)]
+    pub unused_lanes: Option<YamlUnusedLanes>,
+
+    /// Roles commands may be authorized for
+    #[serde(default)]
+    pub roles: Vec<String>,
+
+    /// Naming rules checked across entity and field names
+    #[serde(default)]
+    pub terminology: YamlTerminology,
+
+    /// Fields every event and command must carry, e.g. correlation ids
+    #[serde(default)]
+    pub conventions: YamlConventions,
+
+    /// Custom lint rules run against the model
+    #[serde(default)]
+    pub lint: YamlLint,
+
+    /// Custom entity kinds, e.g. `policy`, that entities opt into with `kind:`
+    #[serde(default)]
+    pub extensions: HashMap<String, YamlExtension>,
+
+    /// Domain types fields may refer to; once any are declared, every field
+    /// type must be declared or built in
+    #[serde(default)]
+    pub types: HashMap<String, YamlTypeDefinition>,
+
+    /// Reusable groups of fields that events and commands include with `extends`
+    #[serde(default)]
+    pub field_sets: HashMap<String, YamlFieldSet>,
+
+    /// Named variants of the model, selected at render time
+    #[serde(default)]
+    pub profiles: HashMap<String, YamlProfile>,
+
+    /// Reusable model fragments whose names and text contain a `{{name}}` placeholder
+    #[serde(default)]
+    pub templates: HashMap<String, YamlModelFragment>,
+
+    /// Template instantiations, expanded into concrete entities during conversion
+    #[serde(default)]
+    pub expand: Vec<YamlTemplateExpansion>,
+
+    /// Shared YAML fragments referenced elsewhere through anchors and aliases.
+    /// Not part of the model itself.
+    #[serde(default)]
+    pub fragments: Option<serde_yaml::Value>,
+
+    /// Where each key and sequence item appears in the source document.
+    #[serde(skip)]
+    pub source_map: SourceMap,
+}
+
+/// The workflow header.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum YamlWorkflow {
+    /// Simple format: just the title
+    Title(String),
+    /// Header format: the title with a description, authors, model version, and date
+    Header(YamlWorkflowHeader),
+}
+
+impl YamlWorkflow {
+    /// The workflow's title.
+    pub fn title(&self) -> &str {
+        match self {
+            Self::Title(title) => title,
+            Self::Header(header) => &header.title,
+        }
+    }
+}
+
+/// Naming rules for a model.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlTerminology {
+    /// Words names and descriptions must not use, e.g. `Client` where the
+    /// domain says `Customer`
+    #[serde(default)]
+    pub banned_words: Vec<String>,
+}
+
+/// Envelope fields a model's events and commands must carry.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlConventions {
+    /// Fields every event must include, e.g. `correlation_id`
+    #[serde(default)]
+    pub envelope: Vec<String>,
+    /// Fields every command must declare so the events it records can carry them on
+    #[serde(default)]
+    pub propagate: Vec<String>,
+}
+
+/// Custom lint rules for a model.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlLint {
+    /// WebAssembly modules checking the model, relative to the model file
+    #[serde(default)]
+    pub plugins: Vec<String>,
+}
+
+/// Definitions shared by many models, such as a company-wide vocabulary of
+/// types, kept in a file of their own and passed to `validate --shared`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlVocabulary {
+    /// Domain types, as under a model's `types`
+    #[serde(default)]
+    pub types: HashMap<String, YamlTypeDefinition>,
+
+    /// Reusable groups of fields, as under a model's `field_sets`
+    #[serde(default)]
+    pub field_sets: HashMap<String, YamlFieldSet>,
+
+    /// Custom entity kinds, as under a model's `extensions`
+    #[serde(default)]
+    pub extensions: HashMap<String, YamlExtension>,
+
+    /// Naming rules, as under a model's `terminology`
+    #[serde(default)]
+    pub terminology: YamlTerminology,
+
+    /// Envelope fields, as under a model's `conventions`
+    #[serde(default)]
+    pub conventions: YamlConventions,
+}
+
+/// A reusable group of fields declared under `field_sets`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlFieldSet {
+    /// Other field sets whose fields this one includes
+    #[serde(default)]
+    pub extends: Vec<String>,
+    /// The fields, overriding any of the same name from extended sets
+    #[serde(default)]
+    pub fields: HashMap<String, YamlField>,
+}
+
+/// A custom entity kind declared under `extensions`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlExtension {
+    /// Built-in entity type the kind's entities are declared and laid out
+    /// as, e.g. `automation`
+    pub base: String,
+    /// Fill of the kind's entities; the base type's when unset
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Symbol marking the kind's entities
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// Entity types and kinds the kind's entities may connect to; any when empty
+    #[serde(default)]
+    pub connects_to: Vec<String>,
+}
+
+/// A workflow title with descriptive metadata.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlWorkflowHeader {
+    /// The name of the workflow being modeled
+    pub title: String,
+
+    /// What the workflow is about, shown under the title
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// People who wrote the model
+    #[serde(default)]
+    pub authors: Vec<String>,
+
+    /// Version of the model itself, unrelated to the schema `version`
+    #[serde(default, deserialize_with = "scalar_as_text")]
+    pub version: Option<String>,
+
+    /// When this version of the model was written, as `YYYY-MM-DD`
+    #[serde(default, deserialize_with = "scalar_as_text")]
+    pub date: Option<String>,
+}
+
+/// Reads a scalar as text, so numbers such as `version: 2` need no quotes.
+fn scalar_as_text<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    match Option::<serde_yaml::Value>::deserialize(deserializer)? {
+        None | Some(serde_yaml::Value::Null) => Ok(None),
+        Some(serde_yaml::Value::String(text)) => Ok(Some(text)),
+        Some(serde_yaml::Value::Number(number)) => Ok(Some(number.to_string())),
+        Some(serde_yaml::Value::Bool(flag)) => Ok(Some(flag.to_string())),
+        Some(_) => Err(D::Error::custom("expected a single value")),
+    }
+}
+
+/// Swimlane definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum YamlSwimlane {
+    /// Simple format: just a name
+    Simple(String),
+    /// Map format: key is identifier, value is display name
+    Map(HashMap<String, String>),
+    /// Detailed format: key is identifier, value holds the display name and attributes
+    Detailed(HashMap<String, YamlSwimlaneDetails>),
+    /// Group format: swimlanes gathered under a parent label
+    Group(YamlSwimlaneGroup),
+}
+
+/// Swimlanes gathered under a parent label.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlSwimlaneGroup {
+    /// Label drawn beside the group's swimlanes
+    pub group: String,
+
+    /// Swimlanes in the group, in any non-group format
+    pub swimlanes: Vec<YamlSwimlane>,
+
+    /// Draw the group as a single swimlane holding all of its members' entities
+    #[serde(default)]
+    pub collapsed: bool,
+
+    /// Fill behind the group's swimlanes
+    #[serde(default)]
+    pub background: Option<String>,
+}
+
+/// Display name and attributes of a swimlane written in the detailed format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlSwimlaneDetails {
+    /// Display name
+    pub name: String,
+
+    /// Team or person responsible for the entities in this swimlane
+    #[serde(default)]
+    pub owner: Option<String>,
+
+    /// Tint of the swimlane's background and label accent
+    #[serde(default)]
+    pub color: Option<String>,
+
+    /// Short symbol, such as an emoji, drawn before the swimlane's label
+    #[serde(default)]
+    pub icon: Option<String>,
+
+    /// Kind of entities the swimlane holds
+    #[serde(default)]
+    pub role: Option<YamlSwimlaneRole>,
+}
+
+/// Kind of entities a swimlane holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum YamlSwimlaneRole {
+    /// Screens and automations
+    Interface,
+    /// Commands and read models
+    Application,
+    /// Event streams
+    Stream,
+}
+
+impl fmt::Display for YamlSwimlaneRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Interface => "interface",
+            Self::Application => "application",
+            Self::Stream => "stream",
+        })
+    }
+}
+
+/// How swimlanes are ordered top to bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum YamlSwimlaneOrder {
+    /// In the order they are written
+    Declared,
+    /// Interface lanes first, then application lanes, then stream lanes
+    Convention,
+}
+
+/// How connections drawn between the same two entities are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum YamlDuplicateConnections {
+    /// One connector per connection
+    Separate,
+    /// One connector with a `×N` badge
+    Count,
+    /// One connector badged with the names of the slices it comes from
+    Slices,
+}
+
+/// How swimlanes a slice leaves empty are drawn in its column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum YamlUnusedLanes {
+    /// Like any other part of the swimlane
+    Full,
+    /// Shaded, with swimlanes no slice uses shrunk to a thin band
+    Shaded,
+}
+
+/// Shape drawn where a connection meets its target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum YamlMarker {
+    /// Solid triangular arrowhead
+    Filled,
+    /// Outlined arrowhead
+    Open,
+    /// Hollow diamond, for aggregation-like links
+    Diamond,
+    /// Hollow circle, for subscriptions
+    Circle,
+    /// No marker
+    None,
+}
+
+/// How soon a projection reflects the events feeding it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum YamlConsistency {
+    /// Updated asynchronously, some time after the event is recorded
+    Eventual,
+    /// Updated in the same transaction that records the event
+    Immediate,
+}
+
+/// Event entity definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlEvent {
+    /// Event description
+    pub description: String,
+
+    /// Swimlane this event belongs to
+    pub swimlane: String,
+
+    /// Event data schema
+    #[serde(default)]
+    pub data: HashMap<String, YamlField>,
+
+    /// Field sets whose fields the data includes, e.g. a common envelope
+    #[serde(default)]
+    pub extends: Vec<String>,
+
+    /// Feature flag that must be enabled for this event to be rendered
+    #[serde(default)]
+    pub feature: Option<String>,
+
+    /// Team or person responsible for this event; defaults to its swimlane's owner
+    #[serde(default)]
+    pub owner: Option<String>,
+
+    /// Custom kind of this event, declared under `extensions`
+    #[serde(default)]
+    pub kind: Option<String>,
+
+    /// Risk or open question raised about this event, e.g. in a workshop
+    #[serde(default)]
+    pub hotspot: Option<String>,
+
+    /// Markdown documenting this event, inline or in a linked file
+    #[serde(default)]
+    pub doc: Option<YamlDocumentation>,
+
+    /// Rust item paths, source files, or URLs of the code implementing this event
+    #[serde(default)]
+    pub implemented_by: Vec<String>,
+
+    /// Expected throughput, e.g. `10k/day`
+    #[serde(default)]
+    pub volume: Option<String>,
+}
+
+/// Command entity definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlCommand {
+    /// Command description
+    pub description: String,
+
+    /// Swimlane this command belongs to
+    pub swimlane: String,
+
+    /// Command data schema
+    #[serde(default)]
+    pub data: HashMap<String, YamlField>,
+
+    /// Field sets whose fields the data includes, e.g. a common envelope
+    #[serde(default)]
+    pub extends: Vec<String>,
+
+    /// Errors the command may be rejected with, by name
+    #[serde(default)]
+    pub errors: HashMap<String, YamlCommandError>,
+
+    /// Whether issuing the command again with the same data has no further effect
+    #[serde(default)]
+    pub idempotent: Option<bool>,
+
+    /// Roles allowed to issue the command, each declared under `roles`
+    #[serde(default)]
+    pub authorized_roles: Vec<String>,
+
+    /// Test scenarios
+    #[serde(default)]
+    pub tests: HashMap<String, YamlTestScenario>,
+    /// Feature flag that must be enabled for this command to be rendered
+    #[serde(default)]
+    pub feature: Option<String>,
+
+    /// Team or person responsible for this command; defaults to its swimlane's owner
+    #[serde(default)]
+    pub owner: Option<String>,
+
+    /// Custom kind of this command, declared under `extensions`
+    #[serde(default)]
+    pub kind: Option<String>,
+
+    /// Risk or open question raised about this command, e.g. in a workshop
+    #[serde(default)]
+    pub hotspot: Option<String>,
+
+    /// Markdown documenting this command, inline or in a linked file
+    #[serde(default)]
+    pub doc: Option<YamlDocumentation>,
+
+    /// Rust item paths, source files, or URLs of the code implementing this command
+    #[serde(default)]
+    pub implemented_by: Vec<String>,
+
+    /// Expected throughput, e.g. `500/hour`
+    #[serde(default)]
+    pub volume: Option<String>,
+}
+
+/// Error a command may be rejected with instead of recording events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlCommandError {
+    /// Why the command is rejected
+    pub description: String,
+
+    /// Error data schema
+    #[serde(default)]
+    pub data: HashMap<String, YamlField>,
+}
+
+/// View entity definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlView {
+    /// View description
+    pub description: String,
+
+    /// Swimlane this view belongs to
+    pub swimlane: String,
+
+    /// UI components
+    #[serde(default)]
+    pub components: Vec<YamlComponent>,
+    /// Feature flag that must be enabled for this view to be rendered
+    #[serde(default)]
+    pub feature: Option<String>,
+
+    /// Team or person responsible for this view; defaults to its swimlane's owner
+    #[serde(default)]
+    pub owner: Option<String>,
+
+    /// Custom kind of this view, declared under `extensions`
+    #[serde(default)]
+    pub kind: Option<String>,
+
+    /// Risk or open question raised about this view, e.g. in a workshop
+    #[serde(default)]
+    pub hotspot: Option<String>,
+
+    /// Markdown documenting this view, inline or in a linked file
+    #[serde(default)]
+    pub doc: Option<YamlDocumentation>,
+}
+
+/// Projection entity definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlProjection {
+    /// Projection description
+    pub description: String,
+
+    /// Swimlane this projection belongs to
+    pub swimlane: String,
+
+    /// Projection fields
+    #[serde(default)]
+    pub fields: HashMap<String, String>,
+    /// Feature flag that must be enabled for this projection to be rendered
+    #[serde(default)]
+    pub feature: Option<String>,
+
+    /// Team or person responsible for this projection; defaults to its swimlane's owner
+    #[serde(default)]
+    pub owner: Option<String>,
+
+    /// Custom kind of this projection, declared under `extensions`
+    #[serde(default)]
+    pub kind: Option<String>,
+
+    /// Risk or open question raised about this projection, e.g. in a workshop
+    #[serde(default)]
+    pub hotspot: Option<String>,
+
+    /// Markdown documenting this projection, inline or in a linked file
+    #[serde(default)]
+    pub doc: Option<YamlDocumentation>,
+}
+
+/// Query entity definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlQuery {
+    /// Swimlane this query belongs to
+    pub swimlane: String,
+
+    /// Query inputs
+    #[serde(default)]
+    pub inputs: HashMap<String, String>,
+
+    /// Query outputs
+    pub outputs: YamlQueryOutput,
+    /// Feature flag that must be enabled for this query to be rendered
+    #[serde(default)]
+    pub feature: Option<String>,
+
+    /// Team or person responsible for this query; defaults to its swimlane's owner
+    #[serde(default)]
+    pub owner: Option<String>,
+
+    /// Custom kind of this query, declared under `extensions`
+    #[serde(default)]
+    pub kind: Option<String>,
+
+    /// Risk or open question raised about this query, e.g. in a workshop
+    #[serde(default)]
+    pub hotspot: Option<String>,
+
+    /// Markdown documenting this query, inline or in a linked file
+    #[serde(default)]
+    pub doc: Option<YamlDocumentation>,
+}
+
+/// Query output structure.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlQueryOutput {
+    /// One-of output variants
+    pub one_of: HashMap<String, YamlQueryVariant>,
+}
+
+/// Query output variant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum YamlQueryVariant {
+    /// Simple type reference
+    Simple(String),
+    /// Complex output with fields
+    Complex(HashMap<String, String>),
+}
+
+/// Automation entity definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlAutomation {
+    /// Swimlane this automation belongs to
+    pub swimlane: String,
+    /// Feature flag that must be enabled for this automation to be rendered
+    #[serde(default)]
+    pub feature: Option<String>,
+
+    /// Team or person responsible for this automation; defaults to its swimlane's owner
+    #[serde(default)]
+    pub owner: Option<String>,
+
+    /// Custom kind of this automation, declared under `extensions`
+    #[serde(default)]
+    pub kind: Option<String>,
+
+    /// Risk or open question raised about this automation, e.g. in a workshop
+    #[serde(default)]
+    pub hotspot: Option<String>,
+
+    /// Markdown documenting this automation, inline or in a linked file
+    #[serde(default)]
+    pub doc: Option<YamlDocumentation>,
+}
+
+/// Declaration of a domain type under `types`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum YamlTypeDefinition {
+    /// The type another type is represented as, e.g. `EmailAddress: String`
+    Alias(String),
+    /// An enumeration, or another type whose values are restricted, e.g.
+    /// `Quantity: { type: Integer, min: 1, max: 100 }`
+    Constrained(YamlConstrainedType),
+    /// A type made of named fields, e.g. `Money: { amount: Decimal, currency: Currency }`
+    Record(HashMap<String, String>),
+}
+
+/// A declared type given by its variants or by constraints on another type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlConstrainedType {
+    /// The type whose values are restricted
+    #[serde(rename = "type", default)]
+    pub base: Option<String>,
+    /// The values of an enumeration, in order
+    #[serde(rename = "enum", default)]
+    pub variants: Vec<String>,
+    /// Smallest allowed value of a numeric type, inclusive
+    #[serde(default)]
+    pub min: Option<f64>,
+    /// Largest allowed value of a numeric type, inclusive
+    #[serde(default)]
+    pub max: Option<f64>,
+    /// Regular expression every value must match
+    #[serde(default)]
+    pub pattern: Option<String>,
+}
+
+/// Field definition in data schemas.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum YamlField {
+    /// Simple type reference
+    Simple(String),
+    /// Complex field with properties
+    Complex {
+        #[serde(rename = "type")]
+        field_type: String,
+        #[serde(rename = "stream-id")]
+        #[serde(default)]
+        stream_id: bool,
+        #[serde(default)]
+        generated: bool,
+        /// Whether the field holds personally identifiable information
+        #[serde(default)]
+        pii: bool,
+    },
+}
+
+/// Test scenario definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlTestScenario {
+    /// Given section - initial state
+    #[serde(rename = "Given")]
+    #[serde(default)]
+    pub given: Vec<YamlTestStep>,
+
+    /// When section - action to test
+    #[serde(rename = "When")]
+    #[serde(default)]
+    pub when: Vec<YamlTestStep>,
+
+    /// Then section - expected outcome: events, or one of the command's errors
+    #[serde(rename = "Then")]
+    #[serde(default)]
+    pub then: Vec<YamlTestStep>,
+
+    /// Realistic values for the placeholders, e.g. `B: jane@example.com`
+    #[serde(default)]
+    pub examples: HashMap<String, String>,
+}
+
+/// Test step in a scenario.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlTestStep {
+    /// Entity name and its data
+    #[serde(flatten)]
+    pub step: HashMap<String, HashMap<String, String>>,
+}
+
+/// UI component definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum YamlComponent {
+    /// Simple component with just a name and type
+    Simple {
+        #[serde(flatten)]
+        component: HashMap<String, String>,
+    },
+    /// Complex component with nested structure
+    Complex {
+        #[serde(flatten)]
+        component: HashMap<String, YamlComplexComponent>,
+    },
+}
+
+/// Complex UI component with nested properties.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlComplexComponent {
+    #[serde(rename = "type")]
+    pub component_type: String,
+    #[serde(default)]
+    pub fields: HashMap<String, String>,
+    #[serde(default)]
+    pub actions: Vec<String>,
+}
+
+/// Slice definition with name and connections.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlSlice {
+    /// Display name of the slice
+    pub name: String,
+
+    /// Bounded context the slice belongs to
+    #[serde(default)]
+    pub context: Option<String>,
+
+    /// Effort the slice is estimated to take
+    #[serde(default)]
+    pub estimate: Option<YamlEstimate>,
+
+    /// How far implementation of the slice has come
+    #[serde(default)]
+    pub status: Option<YamlSliceStatus>,
+
+    /// Issue tracking the slice: a Jira key such as `PROJ-123` or a GitHub
+    /// issue URL
+    #[serde(default)]
+    pub issue: Option<String>,
+
+    /// Entities drawn left to right in this order wherever this slice draws
+    /// them side by side in one swimlane, e.g. `order: [LoginScreen,
+    /// NewAccountScreen]`, in place of the order found automatically
+    #[serde(default)]
+    pub order: Vec<String>,
+
+    /// Swimlanes the slice uses, by id; every swimlane it draws an entity in
+    /// when empty
+    #[serde(default)]
+    pub swimlanes: Vec<String>,
+
+    /// Connections in this slice
+    pub connections: Vec<YamlConnection>,
+}
+
+/// Effort a slice is estimated to take.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum YamlEstimate {
+    /// Story points, e.g. `5`
+    Points(u32),
+    /// A t-shirt size, e.g. `M`
+    Size(String),
+}
+
+/// How far implementation of a slice has come.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum YamlSliceStatus {
+    /// Implemented
+    Done,
+    /// Being implemented
+    InProgress,
+    /// Not started
+    Todo,
+}
+
+impl YamlSliceStatus {
+    /// Returns the status as written, e.g. `in_progress`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Done => "done",
+            Self::InProgress => "in_progress",
+            Self::Todo => "todo",
+        }
+    }
+}
+
+impl YamlEstimate {
+    /// Returns the estimate as written, e.g. `5` or `M`.
+    pub fn text(&self) -> String {
+        match self {
+            Self::Points(points) => points.to_string(),
+            Self::Size(size) => size.clone(),
+        }
+    }
+}
+
+/// Documentation for an entity, written inline or as a link to a Markdown file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum YamlDocumentation {
+    /// Markdown written inline, usually as a `|` block
+    Markdown(String),
+    /// A link to a Markdown file
+    File(YamlDocumentationFile),
+}
+
+/// A link to a Markdown file documenting an entity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlDocumentationFile {
+    /// Path to the Markdown file, relative to the model file
+    pub file: String,
+}
+
+/// A connection in a slice, written as `From -> To` or as a mapping with attributes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum YamlConnection {
+    /// A connection written as `From -> To`
+    Plain(String),
+    /// A connection with attributes
+    Detailed(YamlDetailedConnection),
+}
+
+impl YamlConnection {
+    /// Returns the connection as written, `From -> To`.
+    pub fn text(&self) -> &str {
+        match self {
+            Self::Plain(text) => text,
+            Self::Detailed(detailed) => &detailed.connection,
+        }
+    }
+
+    /// Returns the feature flag the connection depends on, if any.
+    pub fn feature(&self) -> Option<&str> {
+        match self {
+            Self::Plain(_) => None,
+            Self::Detailed(detailed) => detailed.feature.as_deref(),
+        }
+    }
+
+    /// Returns the points the connection must pass through, in order.
+    pub fn via(&self) -> &[YamlWaypoint] {
+        match self {
+            Self::Plain(_) => &[],
+            Self::Detailed(detailed) => &detailed.via,
+        }
+    }
+
+    /// Returns the marker chosen for the connection's target, if any.
+    pub fn marker(&self) -> Option<YamlMarker> {
+        match self {
+            Self::Plain(_) => None,
+            Self::Detailed(detailed) => detailed.marker,
+        }
+    }
+
+    /// Returns how soon the target projection reflects the source event, if declared.
+    pub fn consistency(&self) -> Option<YamlConsistency> {
+        match self {
+            Self::Plain(_) => None,
+            Self::Detailed(detailed) => detailed.consistency,
+        }
+    }
+
+    /// Returns the risk raised about the connection, if any.
+    pub fn hotspot(&self) -> Option<&str> {
+        match self {
+            Self::Plain(_) => None,
+            Self::Detailed(detailed) => detailed.hotspot.as_deref(),
+        }
+    }
+}
+
+/// Splits a connection written `From -> To`, or `From <-> To` when it runs
+/// both ways, into its trimmed endpoints and arrow.
+pub fn split_connection(text: &str) -> Option<(&str, &'static str, &str)> {
+    let arrow = if text.contains("<->") { "<->" } else { "->" };
+    let (from, to) = text.split_once(arrow)?;
+    Some((from.trim(), arrow, to.trim()))
+}
+
+/// A connection written as a mapping, with attributes beyond its endpoints.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlDetailedConnection {
+    /// The connection, written as `From -> To`
+    pub connection: String,
+
+    /// Feature flag that must be enabled for this connection to be rendered
+    #[serde(default)]
+    pub feature: Option<String>,
+
+    /// Points the connection must pass through, in order
+    #[serde(default)]
+    pub via: Vec<YamlWaypoint>,
+
+    /// Marker drawn at the target, overriding the model's `markers`
+    #[serde(default)]
+    pub marker: Option<YamlMarker>,
+
+    /// How soon the target projection reflects the source event
+    #[serde(default)]
+    pub consistency: Option<YamlConsistency>,
+
+    /// Risk or open question raised about this connection
+    #[serde(default)]
+    pub hotspot: Option<String>,
+}
+
+/// A point a connection must pass through: where a slice's column crosses a
+/// swimlane.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlWaypoint {
+    /// Name of the slice whose column the connection passes through
+    pub slice: String,
+
+    /// Identifier of the swimlane the connection passes through
+    pub lane: String,
+}
+
+/// How slices are arranged into columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum YamlLayout {
+    /// One column per slice
+    Standard,
+    /// Consecutive slices on different swimlanes share a column
+    Compact,
+    /// One column per slice, with entities placed by a constraint solver
+    /// keeping the rules under `constraints`
+    Constraints,
+}
+
+/// A rule the `constraints` layout keeps when placing entities.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum YamlLayoutConstraint {
+    /// Entities whose centers line up in every slice drawing more than one
+    /// of them, e.g. `align: [PlaceOrder, OrderPlaced]`
+    Align(YamlAlign),
+
+    /// Entities drawn left to right in this order wherever a slice draws
+    /// them side by side in one swimlane, e.g. `order: [CartView, CheckoutView]`
+    Order(YamlOrder),
+
+    /// Space to keep between two entities drawn in one slice
+    Gap(YamlGapConstraint),
+}
+
+/// Entities to align, e.g. `align: [PlaceOrder, OrderPlaced]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlAlign {
+    /// The entities, by name
+    pub align: Vec<String>,
+}
+
+/// Entities to keep in order, e.g. `order: [CartView, CheckoutView]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlOrder {
+    /// The entities, by name, from left to right
+    pub order: Vec<String>,
+}
+
+/// A `gap` rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlGapConstraint {
+    /// The entities to keep apart, and by how much
+    pub gap: YamlGap,
+}
+
+/// Space to keep between two entities, e.g.
+/// `gap: { between: [OrderPlaced, PaymentTaken], min: 60 }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlGap {
+    /// The entity on the left, then the entity on the right
+    pub between: [String; 2],
+
+    /// Pixels at least between the first entity's right edge and the
+    /// second's left edge
+    pub min: u32,
+}
+
+/// How entity boxes are sized, for every entity type or per type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum YamlEntitySizing {
+    /// One policy for every entity type, e.g. `entity_sizing: uniform`
+    All(YamlSizePolicy),
+    /// A default policy with overrides for individual entity types
+    PerType(YamlEntitySizingByType),
+}
+
+/// How an entity box is sized from its name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum YamlSizePolicy {
+    /// Standard width, widened only for a word that does not fit
+    Standard,
+    /// Exactly the standard size; names that do not fit are cut short
+    Fixed,
+    /// As wide and tall as the wrapped name
+    FitContent,
+    /// The size of the largest entity of the same type
+    Uniform,
+}
+
+/// Size policies by entity type.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlEntitySizingByType {
+    /// Policy for every type not listed, `standard` when unset
+    #[serde(default)]
+    pub default: Option<YamlSizePolicy>,
+
+    /// Policy for views
+    #[serde(default)]
+    pub views: Option<YamlSizePolicy>,
+
+    /// Policy for commands
+    #[serde(default)]
+    pub commands: Option<YamlSizePolicy>,
+
+    /// Policy for events
+    #[serde(default)]
+    pub events: Option<YamlSizePolicy>,
+
+    /// Policy for projections
+    #[serde(default)]
+    pub projections: Option<YamlSizePolicy>,
+
+    /// Policy for queries
+    #[serde(default)]
+    pub queries: Option<YamlSizePolicy>,
+
+    /// Policy for automations
+    #[serde(default)]
+    pub automations: Option<YamlSizePolicy>,
+}
+
+/// Styling of the row of slice headers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlSliceHeaders {
+    /// Minimum height of the header row in pixels
+    #[serde(default)]
+    pub height: Option<u32>,
+
+    /// Fill behind the header row, e.g. `"#eef2ff"` or `lavender`
+    #[serde(default)]
+    pub background: Option<String>,
+
+    /// Lines a slice title may wrap onto before it is rotated instead
+    #[serde(default)]
+    pub max_lines: Option<u32>,
+}
+
+/// Background and watermark of the canvas behind the diagram.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlCanvas {
+    /// Fill of the canvas, e.g. `"#ffffff"`, `ivory`, or `transparent`
+    #[serde(default)]
+    pub background: Option<String>,
+
+    /// Fill of the canvas when rendered with `--dark`, `background` when unset
+    #[serde(default)]
+    pub dark_background: Option<String>,
+
+    /// Text drawn diagonally across the canvas beneath the diagram, e.g. `DRAFT`
+    #[serde(default)]
+    pub watermark: Option<String>,
+}
+
+/// Room connectors keep from entities and from each other.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlRouting {
+    /// Pixels kept between a connector and entities it does not join
+    #[serde(default)]
+    pub margin: Option<u32>,
+
+    /// Pixels kept between parallel segments of different connectors
+    #[serde(default)]
+    pub separation: Option<u32>,
+}
+
+/// A named overlay that removes and adds parts of the model.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct YamlProfile {
+    /// Parts of the base model left out of this variant
+    #[serde(default)]
+    pub remove: YamlProfileRemovals,
+
+    /// Parts added to the base model in this variant
+    #[serde(default)]
+    pub add: YamlModelFragment,
+}
+
+/// What a profile removes from the base model.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct YamlProfileRemovals {
+    /// Entities of any type, removed together with every connection touching them
+    #[serde(default)]
+    pub entities: Vec<String>,
+
+    /// Slices, by name
+    #[serde(default)]
+    pub slices: Vec<String>,
+
+    /// Individual connections, written as in a slice (`From -> To`)
+    #[serde(default)]
+    pub connections: Vec<String>,
+}
+
+/// Entities and slices that extend a model, as added by a profile or
+/// produced by a template.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct YamlModelFragment {
+    /// Additional events
+    #[serde(default)]
+    pub events: HashMap<String, YamlEvent>,
+
+    /// Additional commands
+    #[serde(default)]
+    pub commands: HashMap<String, YamlCommand>,
+
+    /// Additional views
+    #[serde(default)]
+    pub views: HashMap<String, YamlView>,
+
+    /// Additional projections
+    #[serde(default)]
+    pub projections: HashMap<String, YamlProjection>,
+
+    /// Additional queries
+    #[serde(default)]
+    pub queries: HashMap<String, YamlQuery>,
+
+    /// Additional automations
+    #[serde(default)]
+    pub automations: HashMap<String, YamlAutomation>,
+
+    /// Additional slices; connections for an existing slice name are appended to it
+    #[serde(default)]
+    pub slices: Vec<YamlSlice>,
+}
+
+/// Instantiation of a template once per argument.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlTemplateExpansion {
+    /// Name of the template to instantiate
+    pub template: String,
+
+    /// Values substituted for `{{name}}`, one instantiation each
+    pub for_each: Vec<String>,
+}
+
+/// How keys that are not part of the schema are treated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnknownKeyPolicy {
+    /// Fail parsing so typos cannot silently drop parts of the model.
+    #[default]
+    Reject,
+    /// Ignore the keys and report each one as a warning.
+    Warn,
+}
+
+/// A key in the document that the schema does not recognize.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownKey {
+    /// Path of the key from the document root.
+    pub path: SourcePath,
+    /// Where the key appears in the source, when it could be determined.
+    pub location: Option<SourceLocation>,
+}
+
+impl fmt::Display for UnknownKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown key '{}'", self.path)?;
+        if let Some(location) = self.location {
+            write!(f, " at {location}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats a list of unknown keys for an error message, one per line.
+fn format_unknown_keys(keys: &[UnknownKey]) -> String {
+    keys.iter()
+        .map(|key| format!("\n  {key}"))
+        .collect::<String>()
+}
+
+/// Formats a list of unresolvable aliases for an error message, one per line.
+fn format_unresolved_aliases(aliases: &[AnchorSite]) -> String {
+    aliases
+        .iter()
+        .map(|alias| format!("\n  alias {alias} has no matching anchor earlier in the document"))
+        .collect::<String>()
+}
+
+/// Errors that can occur during YAML parsing.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum YamlParseError {
+    /// YAML syntax error without location information.
+    #[error("YAML syntax error: {0}")]
+    YamlError(#[from] serde_yaml::Error),
+
+    /// Version incompatibility error.
+    #[error(
+        "Schema version mismatch: file version {file_version} is not compatible with application version {app_version}"
+    )]
+    VersionMismatch {
+        file_version: String,
+        app_version: String,
+    },
+
+    /// YAML parsing error with location information.
+    #[error("YAML error at line {line}, column {column}: {message}")]
+    ParseError {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+
+    /// Aliases refer to anchors that are not defined before them.
+    #[error("Unresolvable aliases in event model:{}", format_unresolved_aliases(.0))]
+    UnresolvedAliases(Vec<AnchorSite>),
+
+    /// The document contains keys the schema does not recognize.
+    #[error("Unrecognized keys in event model:{}", format_unknown_keys(.0))]
+    UnknownKeys(Vec<UnknownKey>),
+}
+
+/// Parses a YAML event model from a string.
+///
+/// This function:
+/// 1. Parses the YAML into intermediate types
+/// 2. Validates the schema version (if present)
+/// 3. Returns the parsed model or an error
+///
+/// Keys the schema does not recognize are rejected; use
+/// [`parse_yaml_with_policy`] to tolerate them instead.
+pub fn parse_yaml(input: &str) -> Result<YamlEventModel, YamlParseError> {
+    parse_yaml_with_policy(input, UnknownKeyPolicy::Reject, &mut Warnings::new())
+}
+
+/// Parses a YAML event model from a string, treating unrecognized keys
+/// according to `policy`.
+///
+/// With [`UnknownKeyPolicy::Warn`] each unrecognized key is recorded in
+/// `warnings` together with its location.
+pub fn parse_yaml_with_policy(
+    input: &str,
+    policy: UnknownKeyPolicy,
+    warnings: &mut Warnings,
+) -> Result<YamlEventModel, YamlParseError> {
+    // Aliases must resolve before anything else can be read
+    let anchor_lint = lint_anchors(input);
+    if !anchor_lint.unresolved.is_empty() {
+        return Err(YamlParseError::UnresolvedAliases(anchor_lint.unresolved));
+    }
+    for anchor in anchor_lint.unused {
+        warnings.warn(
+            WarningKind::UnusedAnchor,
+            format!("Anchor {anchor} is never referenced"),
+        );
+    }
+
+    // Parse the YAML, recording every key the schema does not consume.
+    // Merge keys (`<<: *anchor`) are only applied on the generic value tree,
+    // so documents using them are deserialized from that tree; others are
+    // read straight from the text so type errors keep their locations.
+    let mut ignored_paths = Vec::new();
+    let mut value: serde_yaml::Value = serde_yaml::from_str(input).map_err(located_error)?;
+    let parsed: Result<YamlEventModel, _> = if contains_merge_key(&value) {
+        value.apply_merge().map_err(located_error)?;
+        serde_ignored::deserialize(value, |path| ignored_paths.push(source_path(&path)))
+    } else {
+        let deserializer = serde_yaml::Deserializer::from_str(input);
+        serde_ignored::deserialize(deserializer, |path| ignored_paths.push(source_path(&path)))
+    };
+    let mut model = parsed.map_err(located_error)?;
+
+    // Keep key positions so later stages can point errors at the source
+    model.source_map = SourceMap::build(input);
+
+    if !ignored_paths.is_empty() {
+        let unknown_keys: Vec<UnknownKey> = ignored_paths
+            .into_iter()
+            .map(|path| UnknownKey {
+                location: model.source_map.nearest(&path),
+                path,
+            })
+            .collect();
+        match policy {
+            UnknownKeyPolicy::Reject => return Err(YamlParseError::UnknownKeys(unknown_keys)),
+            UnknownKeyPolicy::Warn => {
+                for key in unknown_keys {
+                    warnings.warn(WarningKind::UnknownKey, format!("Ignored {key}"));
+                }
+            }
+        }
+    }
+
+    // If no version specified, use current version
+    if model.version.is_none() {
+        model.version = Some(VERSION.to_string());
+    }
+
+    // For now, we accept any version since we're pre-1.0
+    // Post-1.0, we'll add version compatibility checks here
+
+    Ok(model)
+}
+
+/// Parses a file of definitions shared by many models. Keys other than those
+/// of [`YamlVocabulary`] are rejected.
+pub fn parse_vocabulary(input: &str) -> Result<YamlVocabulary, YamlParseError> {
+    serde_yaml::from_str(input).map_err(located_error)
+}
+
+/// Wraps a deserialization error, keeping its location when it has one.
+fn located_error(e: serde_yaml::Error) -> YamlParseError {
+    match e.location() {
+        Some(location) => YamlParseError::ParseError {
+            line: location.line(),
+            column: location.column(),
+            message: e.to_string(),
+        },
+        None => YamlParseError::YamlError(e),
+    }
+}
+
+/// Returns true if any mapping in the tree uses a `<<` merge key.
+fn contains_merge_key(value: &serde_yaml::Value) -> bool {
+    match value {
+        serde_yaml::Value::Mapping(mapping) => mapping
+            .iter()
+            .any(|(key, value)| key.as_str() == Some("<<") || contains_merge_key(value)),
+        serde_yaml::Value::Sequence(sequence) => sequence.iter().any(contains_merge_key),
+        serde_yaml::Value::Tagged(tagged) => contains_merge_key(&tagged.value),
+        _ => false,
+    }
+}
+
+/// Converts a path reported by `serde_ignored` into a [`SourcePath`].
+fn source_path(path: &serde_ignored::Path<'_>) -> SourcePath {
+    match path {
+        serde_ignored::Path::Root => SourcePath::root(),
+        serde_ignored::Path::Seq { parent, index } => source_path(parent).index(*index),
+        serde_ignored::Path::Map { parent, key } => source_path(parent).key(key.as_str()),
+        serde_ignored::Path::Some { parent }
+        | serde_ignored::Path::NewtypeStruct { parent }
+        | serde_ignored::Path::NewtypeVariant { parent } => source_path(parent),
+    }
+}
+
+/// Checks if a file version is compatible with the current application version.
+///
+/// Currently always returns true as we're pre-1.0 and have no compatibility guarantees.
+/// Post-1.0, this will implement semantic versioning checks.
+#[allow(dead_code)] // Will be used post-1.0 for version compatibility checks
+fn is_version_compatible(file_version: &str, app_version: &str) -> bool {
+    // Pre-1.0: Accept any version
+    let _ = (file_version, app_version); // Silence unused warnings
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yaml_event_model_deserializes_minimal_file() {
+        let yaml = r#"
+workflow: Test Workflow
+swimlanes:
+  - test: "Test Lane"
+"#;
+        let model: YamlEventModel = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(model.workflow.title(), "Test Workflow");
+        assert_eq!(model.swimlanes.len(), 1);
+    }
+
+    #[test]
+    fn yaml_event_model_deserializes_workflow_header() {
+        let yaml = r#"
+workflow:
+  title: Test Workflow
+  description: How orders are placed
+  authors: [Alice, Bob]
+  version: 2
+  date: 2026-10-17
+swimlanes:
+  - test: "Test Lane"
+"#;
+        let model: YamlEventModel = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(model.workflow.title(), "Test Workflow");
+        let YamlWorkflow::Header(header) = model.workflow else {
+            panic!("expected a workflow header");
+        };
+        assert_eq!(header.authors, ["Alice", "Bob"]);
+        assert_eq!(header.version.as_deref(), Some("2"));
+        assert_eq!(header.date.as_deref(), Some("2026-10-17"));
+    }
+
+    #[test]
+    fn yaml_event_model_deserializes_with_version() {
+        let yaml = r#"
+version: "0.3.0"
+workflow: Test Workflow
+swimlanes:
+  - test: "Test Lane"
+"#;
+        let model: YamlEventModel = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(model.version, Some("0.3.0".to_string()));
+    }
+
+    #[test]
+    fn yaml_field_deserializes_simple_type() {
+        let yaml = "String";
+        let field: YamlField = serde_yaml::from_str(yaml).unwrap();
+        match field {
+            YamlField::Simple(s) => assert_eq!(s, "String"),
+            _ => panic!("Expected simple field"),
+        }
+    }
+
+    #[test]
+    fn yaml_field_deserializes_complex_type() {
+        let yaml = r#"
+type: UserAccountId
+stream-id: true
+generated: true
+"#;
+        let field: YamlField = serde_yaml::from_str(yaml).unwrap();
+        match field {
+            YamlField::Complex {
+                field_type,
+                stream_id,
+                generated,
+                pii,
+            } => {
+                assert_eq!(field_type, "UserAccountId");
+                assert!(!pii);
+                assert!(stream_id);
+                assert!(generated);
+            }
+            _ => panic!("Expected complex field"),
+        }
+    }
+
+    #[test]
+    fn parse_yaml_adds_default_version() {
+        let yaml = r#"
+workflow: Test Workflow
+swimlanes:
+  - test: "Test Lane"
+"#;
+        let model = parse_yaml(yaml).unwrap();
+        assert_eq!(model.version, Some(VERSION.to_string()));
+    }
+
+    #[test]
+    fn parse_yaml_preserves_explicit_version() {
+        let yaml = r#"
+version: "0.2.0"
+workflow: Test Workflow
+swimlanes:
+  - test: "Test Lane"
+"#;
+        let model = parse_yaml(yaml).unwrap();
+        assert_eq!(model.version, Some("0.2.0".to_string()));
+    }
+
+    #[test]
+    fn parse_yaml_handles_syntax_errors() {
+        let yaml = r#"
+workflow: Test Workflow
+swimlanes
+  - test: "Test Lane"  # Missing colon
+"#;
+        let result = parse_yaml(yaml);
+        assert!(matches!(result, Err(YamlParseError::ParseError { .. })));
+    }
+
+    #[test]
+    fn parse_yaml_extracts_error_location() {
+        // Use actual invalid YAML syntax - unclosed quote
+        let yaml = r#"workflow: Test Workflow
+swimlanes:
+  - test: "Test Lane
+  - backend: "Backend"
+"#;
+        let result = parse_yaml(yaml);
+        match result {
+            Err(YamlParseError::ParseError {
+                line,
+                column,
+                message,
+            }) => {
+                // serde_yaml reports 1-indexed line numbers
+                println!("Error at line {line}, column {column}: {message}");
+                assert!(line > 0); // Line should be greater than 0
+                assert!(column > 0); // Column should be greater than 0
+                assert!(!message.is_empty());
+            }
+            Err(e) => panic!("Expected ParseError but got: {e:?}"),
+            Ok(_) => panic!("Expected an error but parsing succeeded"),
+        }
+    }
+
+    #[test]
+    fn parse_yaml_location_with_duplicate_key() {
+        // Duplicate keys should cause an error
+        let yaml = r#"workflow: Test Workflow
+swimlanes:
+  - test: "Test Lane"
+workflow: Another Workflow  # Duplicate key
+"#;
+        let result = parse_yaml(yaml);
+        match result {
+            Err(YamlParseError::ParseError {
+                line,
+                column,
+                message,
+            }) => {
+                println!("Error at line {line}, column {column}: {message}");
+                // Note: serde_yaml reports duplicate key errors at the start of the document
+                assert!(line > 0);
+                assert!(column > 0);
+                assert!(message.contains("duplicate"));
+            }
+            Err(e) => panic!("Expected ParseError but got: {e:?}"),
+            Ok(_) => panic!("Expected an error but parsing succeeded"),
+        }
+    }
+
+    #[test]
+    fn parse_yaml_rejects_unknown_keys_with_locations() {
+        let yaml = r#"workflow: Test Workflow
+swimlanes:
+  - backend: "Backend"
+events:
+  UserCreated:
+    description: "A user was created"
+    swimlane: backend
+    dta:
+      user_id: UserId
+"#;
+        match parse_yaml(yaml) {
+            Err(YamlParseError::UnknownKeys(keys)) => {
+                assert_eq!(keys.len(), 1);
+                assert_eq!(keys[0].path.to_string(), "events.UserCreated.dta");
+                assert_eq!(
+                    keys[0].location,
+                    Some(SourceLocation { line: 8, column: 5 })
+                );
+            }
+            Err(e) => panic!("Expected UnknownKeys but got: {e:?}"),
+            Ok(_) => panic!("Expected an error but parsing succeeded"),
+        }
+    }
+
+    #[test]
+    fn parse_yaml_with_warn_policy_reports_unknown_keys() {
+        let yaml = r#"workflow: Test Workflow
+swimlanes:
+  - backend: "Backend"
+commands:
+  CreateUser:
+    description: "Create a user"
+    swimlane: backend
+    test:
+      "Creates user":
+        When:
+          - CreateUser: {}
+        Then:
+          - UserCreated: {}
+"#;
+        let mut warnings = Warnings::new();
+        let model = parse_yaml_with_policy(yaml, UnknownKeyPolicy::Warn, &mut warnings).unwrap();
+
+        assert_eq!(model.commands.len(), 1);
+        let unknown: Vec<_> = warnings.of_kind(WarningKind::UnknownKey).collect();
+        assert_eq!(unknown.len(), 1);
+        assert!(unknown[0].message.contains("commands.CreateUser.test"));
+        assert!(unknown[0].message.contains("line 8"));
+    }
+
+    #[test]
+    fn parse_yaml_resolves_aliases_and_merge_keys_from_fragments() {
+        let yaml = r#"workflow: Shared Fields
+swimlanes:
+  - backend: "Backend"
+fragments:
+  audit: &audit
+    occurred_at: Timestamp
+    actor_id:
+      type: UserId
+      generated: true
+events:
+  UserCreated:
+    description: "A user was created"
+    swimlane: backend
+    data:
+      <<: *audit
+      user_id: UserId
+  UserDeleted:
+    description: "A user was deleted"
+    swimlane: backend
+    data: *audit
+"#;
+        let mut warnings = Warnings::new();
+        let model = parse_yaml_with_policy(yaml, UnknownKeyPolicy::Reject, &mut warnings).unwrap();
+
+        let created = &model.events["UserCreated"].data;
+        assert_eq!(created.len(), 3);
+        assert!(matches!(
+            created["actor_id"],
+            YamlField::Complex {
+                generated: true,
+                ..
+            }
+        ));
+        assert_eq!(model.events["UserDeleted"].data.len(), 2);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn parse_yaml_names_unresolvable_aliases() {
+        let yaml = r#"workflow: Test Workflow
+swimlanes:
+  - backend: "Backend"
+events:
+  UserCreated:
+    description: "A user was created"
+    swimlane: backend
+    data: *user_fields
+"#;
+        match parse_yaml(yaml) {
+            Err(YamlParseError::UnresolvedAliases(aliases)) => {
+                assert_eq!(aliases.len(), 1);
+                assert_eq!(aliases[0].name, "user_fields");
+                assert_eq!(
+                    aliases[0].location,
+                    SourceLocation {
+                        line: 8,
+                        column: 11
+                    }
+                );
+            }
+            Err(e) => panic!("Expected UnresolvedAliases but got: {e:?}"),
+            Ok(_) => panic!("Expected an error but parsing succeeded"),
+        }
+    }
+
+    #[test]
+    fn parse_yaml_warns_about_unused_anchors() {
+        let yaml = r#"workflow: Test Workflow
+swimlanes:
+  - backend: &lane "Backend"
+"#;
+        let mut warnings = Warnings::new();
+        parse_yaml_with_policy(yaml, UnknownKeyPolicy::Reject, &mut warnings).unwrap();
+
+        let unused: Vec<_> = warnings.of_kind(WarningKind::UnusedAnchor).collect();
+        assert_eq!(unused.len(), 1);
+        assert!(unused[0].message.contains("'lane' at line 3"));
+    }
+
+    #[test]
+    fn is_version_compatible_accepts_any_version_pre_1_0() {
+        // Pre-1.0, we accept any version
+        assert!(is_version_compatible("0.1.0", "0.3.0"));
+        assert!(is_version_compatible("0.3.0", "0.1.0"));
+        assert!(is_version_compatible("1.0.0", "0.3.0"));
+        assert!(is_version_compatible("0.3.0", "1.0.0"));
+    }
+}
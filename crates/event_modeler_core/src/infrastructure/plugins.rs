@@ -0,0 +1,278 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Custom lint rules compiled to WebAssembly.
+//!
+//! Organizations keep governance rules of their own (naming schemes,
+//! mandatory fields, forbidden couplings) outside this crate by compiling
+//! them to WebAssembly modules listed under `lint: plugins:`. A plugin
+//! exports:
+//!
+//! - `memory`, its linear memory;
+//! - `alloc(len: i32) -> i32`, returning where `len` bytes of input may be
+//!   written;
+//! - `lint(ptr: i32, len: i32) -> i64`, receiving the model as JSON (the
+//!   same document `--emit domain` writes) and returning where its findings
+//!   are, with the pointer in the high 32 bits and the length in the low 32.
+//!
+//! The findings are a JSON array of `{"rule": ..., "message": ...}` objects,
+//! each reported as a warning. Plugins import nothing, so they can read the
+//! model but not the filesystem or network, and run on a fixed budget of
+//! fuel and memory so a runaway rule cannot hang a render or exhaust the
+//! host.
+
+use crate::event_model::yaml_types::YamlEventModel;
+use crate::infrastructure::warnings::{Warning, WarningKind, Warnings};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use wasmi::{Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// Instructions a plugin may execute per model, roughly.
+const FUEL: u64 = 100_000_000;
+
+/// Bytes of linear memory a plugin may grow to.
+const MEMORY_LIMIT: usize = 64 * 1024 * 1024;
+
+/// Elements a plugin's tables may grow to.
+const TABLE_LIMIT: u32 = 10_000;
+
+/// A rule violation reported by a plugin.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Finding {
+    /// Identifier of the rule, e.g. `commands-need-owner`.
+    pub rule: String,
+    /// What is wrong, naming the offending model element.
+    pub message: String,
+}
+
+/// A lint plugin that could not be loaded or run.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum PluginError {
+    /// The plugin file could not be read.
+    #[error("Cannot read lint plugin {path}: {source}")]
+    Read {
+        /// Where the plugin was looked for.
+        path: PathBuf,
+        /// Why reading failed.
+        source: std::io::Error,
+    },
+
+    /// The plugin is not a valid module, trapped, or returned malformed
+    /// findings.
+    #[error("Lint plugin {path} failed: {reason}")]
+    Failed {
+        /// The plugin file.
+        path: PathBuf,
+        /// What went wrong.
+        reason: String,
+    },
+}
+
+/// Runs every plugin the model lists, reporting their findings as warnings.
+///
+/// Plugin paths are resolved against `model_dir`, the directory holding the
+/// model file.
+pub fn run_plugins(
+    model: &YamlEventModel,
+    model_dir: &Path,
+    warnings: &mut Warnings,
+) -> Result<(), PluginError> {
+    if model.lint.plugins.is_empty() {
+        return Ok(());
+    }
+    let input = serde_json::to_vec(model).expect("domain models serialize to JSON");
+    for plugin in &model.lint.plugins {
+        let written = plugin.clone().into_inner().into_inner();
+        let path = model_dir.join(&written);
+        let wasm = std::fs::read(&path).map_err(|source| PluginError::Read {
+            path: path.clone(),
+            source,
+        })?;
+        let findings = lint(&wasm, &input).map_err(|reason| PluginError::Failed {
+            path: path.clone(),
+            reason,
+        })?;
+        for finding in findings {
+            warnings.push(Warning::new(
+                WarningKind::PluginFinding,
+                format!("{written} [{}]: {}", finding.rule, finding.message),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Runs one plugin module against a JSON model.
+pub fn lint(wasm: &[u8], input: &[u8]) -> Result<Vec<Finding>, String> {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module =
+        Module::new(&engine, wasm).map_err(|e| format!("not a WebAssembly module: {e}"))?;
+    let limits = StoreLimitsBuilder::new()
+        .memory_size(MEMORY_LIMIT)
+        .table_elements(TABLE_LIMIT)
+        .build();
+    let mut store = Store::new(&engine, limits);
+    store.limiter(|limits: &mut StoreLimits| limits);
+    store.set_fuel(FUEL).map_err(|e| e.to_string())?;
+    let instance = Linker::<StoreLimits>::new(&engine)
+        .instantiate(&mut store, &module)
+        .and_then(|pre| pre.start(&mut store))
+        .map_err(|e| format!("cannot be instantiated: {e}"))?;
+
+    let memory = instance
+        .get_memory(&store, "memory")
+        .ok_or("exports no `memory`")?;
+    let alloc = instance
+        .get_typed_func::<i32, i32>(&store, "alloc")
+        .map_err(|_| "exports no `alloc(i32) -> i32`")?;
+    let run = instance
+        .get_typed_func::<(i32, i32), i64>(&store, "lint")
+        .map_err(|_| "exports no `lint(i32, i32) -> i64`")?;
+
+    let len = i32::try_from(input.len()).map_err(|_| "the model is too large")?;
+    let ptr = alloc
+        .call(&mut store, len)
+        .map_err(|e| format!("`alloc` trapped: {e}"))?;
+    memory
+        .write(&mut store, ptr as u32 as usize, input)
+        .map_err(|_| "`alloc` returned memory out of bounds")?;
+    let packed = run
+        .call(&mut store, (ptr, len))
+        .map_err(|e| format!("`lint` trapped: {e}"))? as u64;
+
+    // Bounds-check the plugin's answer against its memory before reading
+    let start = (packed >> 32) as usize;
+    let end = start.saturating_add((packed & 0xffff_ffff) as usize);
+    let output = memory
+        .data(&store)
+        .get(start..end)
+        .ok_or("`lint` returned findings out of bounds")?;
+    serde_json::from_slice(output).map_err(|e| format!("returned malformed findings: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A plugin returning `findings` whatever the model.
+    fn constant_plugin(findings: &str) -> Vec<u8> {
+        let escaped = findings.replace('"', "\\\"");
+        wat::parse_str(format!(
+            r#"(module
+                (memory (export "memory") 1)
+                (data (i32.const 16) "{escaped}")
+                (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+                (func (export "lint") (param i32 i32) (result i64)
+                    (i64.or (i64.shl (i64.const 16) (i64.const 32)) (i64.const {}))))"#,
+            findings.len()
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn findings_are_read_from_the_plugin_memory() {
+        let wasm =
+            constant_plugin(r#"[{"rule":"no-todo","message":"TodoAdded is a placeholder"}]"#);
+
+        let findings = lint(&wasm, b"{}").unwrap();
+
+        assert_eq!(
+            findings,
+            vec![Finding {
+                rule: "no-todo".to_string(),
+                message: "TodoAdded is a placeholder".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn the_model_is_written_where_alloc_says() {
+        // Reports a finding only when its input starts with `{`
+        let wasm = wat::parse_str(
+            r#"(module
+                (memory (export "memory") 1)
+                (data (i32.const 16) "[]")
+                (data (i32.const 32) "[{\"rule\":\"starts-with-brace\",\"message\":\"ok\"}]")
+                (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+                (func (export "lint") (param $ptr i32) (param $len i32) (result i64)
+                    (if (result i64) (i32.eq (i32.load8_u (local.get $ptr)) (i32.const 123))
+                        (then (i64.or (i64.shl (i64.const 32) (i64.const 32)) (i64.const 45)))
+                        (else (i64.or (i64.shl (i64.const 16) (i64.const 32)) (i64.const 2))))))"#,
+        )
+        .unwrap();
+
+        assert_eq!(lint(&wasm, b"{}").unwrap().len(), 1);
+        assert!(lint(&wasm, b"[]").unwrap().is_empty());
+    }
+
+    #[test]
+    fn runaway_plugins_run_out_of_fuel() {
+        let wasm = wat::parse_str(
+            r#"(module
+                (memory (export "memory") 1)
+                (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+                (func (export "lint") (param i32 i32) (result i64)
+                    (loop $forever (br $forever))
+                    (i64.const 0)))"#,
+        )
+        .unwrap();
+
+        let error = lint(&wasm, b"{}").unwrap_err();
+
+        assert!(error.contains("`lint` trapped"), "{error}");
+    }
+
+    #[test]
+    fn malformed_plugins_and_findings_are_errors() {
+        assert!(
+            lint(b"not wasm", b"{}")
+                .unwrap_err()
+                .contains("not a WebAssembly module")
+        );
+        assert!(
+            lint(&constant_plugin("not json"), b"{}")
+                .unwrap_err()
+                .contains("malformed findings")
+        );
+        let no_exports = wat::parse_str("(module)").unwrap();
+        assert!(lint(&no_exports, b"{}").unwrap_err().contains("`memory`"));
+    }
+
+    #[test]
+    fn findings_past_the_end_of_memory_are_refused() {
+        // Claims 4 GiB of findings from a single page of memory
+        let wasm = wat::parse_str(
+            r#"(module
+                (memory (export "memory") 1)
+                (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+                (func (export "lint") (param i32 i32) (result i64)
+                    (i64.const 0xffffffff)))"#,
+        )
+        .unwrap();
+
+        let error = lint(&wasm, b"{}").unwrap_err();
+
+        assert!(error.contains("out of bounds"), "{error}");
+    }
+
+    #[test]
+    fn plugins_cannot_grow_memory_past_the_limit() {
+        // Traps unless growing by 2 GiB fails
+        let wasm = wat::parse_str(
+            r#"(module
+                (memory (export "memory") 1)
+                (data (i32.const 16) "[]")
+                (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+                (func (export "lint") (param i32 i32) (result i64)
+                    (if (i32.ne (memory.grow (i32.const 32768)) (i32.const -1))
+                        (then unreachable))
+                    (i64.or (i64.shl (i64.const 16) (i64.const 32)) (i64.const 2))))"#,
+        )
+        .unwrap();
+
+        assert!(lint(&wasm, b"{}").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Progress reports from the render pipeline, for showing progress bars.
+//!
+//! Large models take a while to lay out and route. A [`ProgressSink`] passed
+//! in through [`Progress`] hears how far each stage has got, so a GUI or web
+//! front end can show it. Reporting is synchronous and happens on the
+//! rendering thread; a sink that needs to update a UI on another thread
+//! should hand the event over, e.g. through a channel, and return quickly.
+
+use std::fmt;
+use std::sync::Arc;
+
+/// A stage of the pipeline progress is reported for, in the order they run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ProgressStage {
+    /// Reading the YAML document and converting it to the domain model.
+    Parsing,
+    /// Sizing entities and placing them in swimlanes and slice columns.
+    Layout,
+    /// Routing the connectors between entities.
+    Routing,
+    /// Drawing everything else that goes into the SVG.
+    Rendering,
+}
+
+impl fmt::Display for ProgressStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parsing => write!(f, "parsing"),
+            Self::Layout => write!(f, "layout"),
+            Self::Routing => write!(f, "routing"),
+            Self::Rendering => write!(f, "rendering"),
+        }
+    }
+}
+
+/// How far a stage has got: `done` of its `total` steps.
+///
+/// Stages that cannot be split into steps report `0` of `1` when they start
+/// and `1` of `1` when they finish; routing counts connectors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgressEvent {
+    /// The stage reporting.
+    pub stage: ProgressStage,
+    /// Steps of the stage finished.
+    pub done: usize,
+    /// Steps the stage takes.
+    pub total: usize,
+}
+
+impl ProgressEvent {
+    /// Returns how much of the stage is done, from 0 to 100. A stage with no
+    /// steps is complete.
+    pub fn percent(&self) -> u8 {
+        match self.total {
+            0 => 100,
+            total => (self.done.min(total) * 100 / total) as u8,
+        }
+    }
+}
+
+/// Receives progress reports from a render.
+///
+/// Closures taking a [`ProgressEvent`] are sinks.
+pub trait ProgressSink: Send + Sync {
+    /// Called each time a stage makes progress.
+    fn report(&self, event: ProgressEvent);
+}
+
+impl<F: Fn(ProgressEvent) + Send + Sync> ProgressSink for F {
+    fn report(&self, event: ProgressEvent) {
+        self(event)
+    }
+}
+
+/// Where a render reports its progress, if anywhere.
+///
+/// The default reports nowhere. Clones report to the same sink.
+#[derive(Clone, Default)]
+pub struct Progress(Option<Arc<dyn ProgressSink>>);
+
+impl Progress {
+    /// Reports nowhere.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Reports to `sink`.
+    pub fn to(sink: impl ProgressSink + 'static) -> Self {
+        Self(Some(Arc::new(sink)))
+    }
+
+    /// Reports that `stage` has finished `done` of its `total` steps.
+    pub fn report(&self, stage: ProgressStage, done: usize, total: usize) {
+        if let Some(sink) = &self.0 {
+            sink.report(ProgressEvent { stage, done, total });
+        }
+    }
+
+    /// Reports that `stage`, taken as a single step, has started.
+    pub fn start(&self, stage: ProgressStage) {
+        self.report(stage, 0, 1);
+    }
+
+    /// Reports that `stage`, taken as a single step, has finished.
+    pub fn finish(&self, stage: ProgressStage) {
+        self.report(stage, 1, 1);
+    }
+}
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(_) => write!(f, "Progress(sink)"),
+            None => write!(f, "Progress(none)"),
+        }
+    }
+}
@@ -11,6 +11,7 @@
 //! - **Parse, Don't Validate**: Validation happens once at boundaries
 //! - **Make Illegal States Unrepresentable**: Invalid states cannot be constructed
 
+use serde::{Serialize, Serializer};
 use std::marker::PhantomData;
 use std::path::PathBuf;
 
@@ -90,6 +91,38 @@ impl<T> NonEmpty<T> {
         Self { head, tail }
     }
 
+    /// Collects the items of an iterator, failing if there are none.
+    pub fn try_from_iter(items: impl IntoIterator<Item = T>) -> Result<Self, EmptyCollection> {
+        let mut items = items.into_iter();
+        let head = items.next().ok_or(EmptyCollection)?;
+        Ok(Self {
+            head,
+            tail: items.collect(),
+        })
+    }
+
+    /// Appends an element.
+    pub fn push(&mut self, value: T) {
+        self.tail.push(value);
+    }
+
+    /// Applies `f` to every element, keeping their order.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> NonEmpty<U> {
+        NonEmpty {
+            head: f(self.head),
+            tail: self.tail.into_iter().map(f).collect(),
+        }
+    }
+
+    /// Borrows every element, so that [`map`](Self::map) can convert a
+    /// collection without consuming it.
+    pub fn as_ref(&self) -> NonEmpty<&T> {
+        NonEmpty {
+            head: &self.head,
+            tail: self.tail.iter().collect(),
+        }
+    }
+
     /// Returns a reference to the first (head) element.
     ///
     /// This is guaranteed to exist and never panics.
@@ -103,10 +136,15 @@ impl<T> NonEmpty<T> {
     }
 
     /// Returns an iterator over all elements in the collection.
-    pub fn iter(&self) -> impl Iterator<Item = &T> {
+    pub fn iter(&self) -> NonEmptyIter<'_, T> {
         std::iter::once(&self.head).chain(self.tail.iter())
     }
 
+    /// Returns an iterator that allows modifying each element.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        std::iter::once(&mut self.head).chain(self.tail.iter_mut())
+    }
+
     /// Returns the number of elements in the collection.
     ///
     /// Always returns at least 1.
@@ -145,6 +183,48 @@ impl<T> NonEmpty<T> {
     }
 }
 
+/// Iterator over the elements of a [`NonEmpty`] collection.
+pub type NonEmptyIter<'a, T> = std::iter::Chain<std::iter::Once<&'a T>, std::slice::Iter<'a, T>>;
+
+impl<T> TryFrom<Vec<T>> for NonEmpty<T> {
+    type Error = EmptyCollection;
+
+    fn try_from(items: Vec<T>) -> Result<Self, Self::Error> {
+        Self::try_from_iter(items)
+    }
+}
+
+impl<T> IntoIterator for NonEmpty<T> {
+    type Item = T;
+    type IntoIter = std::iter::Chain<std::iter::Once<T>, std::vec::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        std::iter::once(self.head).chain(self.tail)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a NonEmpty<T> {
+    type Item = &'a T;
+    type IntoIter = NonEmptyIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Serializes as a sequence, the head first.
+impl<T: Serialize> Serialize for NonEmpty<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+/// The error returned when building a [`NonEmpty`] collection from no
+/// elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("Collection cannot be empty")]
+pub struct EmptyCollection;
+
 // Type-safe path with phantom types
 
 /// A path with compile-time guarantees about its type and properties.
@@ -174,6 +254,13 @@ impl<F, P, E> TypedPath<F, P, E> {
     }
 }
 
+/// Serializes as the path alone.
+impl<F, P, E> Serialize for TypedPath<F, P, E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.path.serialize(serializer)
+    }
+}
+
 // Builder for creating typed paths at compile time
 
 /// Builder for parsing and validating paths at system boundaries.
@@ -213,6 +300,31 @@ impl PathBuilder {
         }
     }
 
+    /// Parses a path as an existing file of any type.
+    ///
+    /// # Requirements
+    ///
+    /// - Must exist on the filesystem
+    /// - Must be a file (not a directory)
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::InvalidFile` if requirements are not met.
+    pub fn parse_existing_file(
+        path: PathBuf,
+    ) -> Result<TypedPath<AnyFile, File, Exists>, ParseError> {
+        if path.is_file() {
+            Ok(TypedPath {
+                path,
+                _file_type: PhantomData,
+                _path_type: PhantomData,
+                _existence: PhantomData,
+            })
+        } else {
+            Err(ParseError::InvalidFile)
+        }
+    }
+
     /// Parses a path as a Markdown file.
     ///
     /// # Requirements
@@ -294,11 +406,16 @@ impl PathBuilder {
 
 /// Errors that can occur during parsing at system boundaries.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum ParseError {
     /// The path is not a valid Event Model file.
     #[error("Invalid event model file: must have .eventmodel extension and exist")]
     InvalidEventModelFile,
 
+    /// The path is not an existing file.
+    #[error("Invalid file: must exist and be a file")]
+    InvalidFile,
+
     /// The path is not a valid Markdown file.
     #[error("Invalid markdown file: must have .md extension")]
     InvalidMarkdownFile,
@@ -377,7 +494,7 @@ pub struct EntityAdded<Id>(PhantomData<Id>);
 ///
 /// This type can only be created through parsing at system boundaries,
 /// eliminating runtime validation throughout the codebase.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct NonEmptyString(String);
 
 impl NonEmptyString {
@@ -392,6 +509,15 @@ impl NonEmptyString {
         }
     }
 
+    /// Builds a non-empty string from text the program derives itself, such
+    /// as a literal or an identifier with a fixed prefix.
+    ///
+    /// Such text is never empty, so failure means a bug rather than bad
+    /// input, and is reported as an [`InvariantError`].
+    pub fn derived(s: String) -> Result<Self, InvariantError> {
+        Self::parse(s).map_err(|_| InvariantError::new("a derived name is empty"))
+    }
+
     /// Returns the inner string value.
     pub fn as_str(&self) -> &str {
         &self.0
@@ -403,6 +529,21 @@ impl NonEmptyString {
     }
 }
 
+/// An invariant the program maintains itself turned out not to hold.
+///
+/// Unlike [`ParseError`], this points at a bug rather than at the input. It
+/// lets such a bug end a run with an error message instead of a panic.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Internal error: {0} (please report this as a bug)")]
+pub struct InvariantError(String);
+
+impl InvariantError {
+    /// Describes the invariant that does not hold.
+    pub fn new(invariant: impl Into<String>) -> Self {
+        Self(invariant.into())
+    }
+}
+
 /// A compile-time guaranteed identifier string.
 ///
 /// Valid identifiers match the pattern `[a-zA-Z_][a-zA-Z0-9_]*`.
@@ -449,11 +590,9 @@ impl EventName {
     ///
     /// This should only be called at system boundaries.
     pub fn parse(s: String) -> Result<Self, ParseError> {
-        if s.is_empty() {
+        let Some(first_char) = s.chars().next() else {
             return Err(ParseError::EmptyString);
-        }
-
-        let first_char = s.chars().next().unwrap();
+        };
         if !first_char.is_ascii_uppercase() {
             return Err(ParseError::InvalidEventName);
         }
@@ -470,7 +609,7 @@ impl EventName {
 // Compile-time safe numeric types
 
 /// A non-negative integer.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 pub struct NonNegativeInt(u32);
 
 impl NonNegativeInt {
@@ -488,7 +627,7 @@ impl NonNegativeInt {
 }
 
 /// A positive (greater than zero) integer.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 pub struct PositiveInt(u32);
 
 impl PositiveInt {
@@ -769,4 +908,28 @@ mod tests {
         assert_eq!(ne.get(3), Some(&40));
         assert_eq!(ne.get(4), None);
     }
+
+    #[test]
+    fn non_empty_builds_from_vectors_and_iterators_only_when_they_have_elements() {
+        assert_eq!(NonEmpty::try_from(Vec::<u8>::new()), Err(EmptyCollection));
+        assert_eq!(
+            NonEmpty::try_from(vec![1, 2]),
+            Ok(NonEmpty::from_head_and_tail(1, vec![2]))
+        );
+        assert_eq!(
+            NonEmpty::try_from_iter((1..4).filter(|n| n % 2 == 1)),
+            Ok(NonEmpty::from_head_and_tail(1, vec![3]))
+        );
+    }
+
+    #[test]
+    fn non_empty_maps_pushes_and_iterates() {
+        let mut ne = NonEmpty::singleton(1);
+        ne.push(2);
+
+        let doubled = ne.as_ref().map(|n| n * 2);
+        assert_eq!(doubled, NonEmpty::from_head_and_tail(2, vec![4]));
+        assert_eq!((&ne).into_iter().sum::<i32>(), 3);
+        assert_eq!(ne.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+    }
 }
@@ -0,0 +1,249 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Non-fatal diagnostics collected while processing a model.
+//!
+//! Errors stop the pipeline; warnings do not. Anything the pipeline tolerates
+//! or repairs on the user's behalf (ignoring an unrecognized key, guessing an
+//! entity's type, leaving out an entity no slice references, skipping a
+//! connection it cannot resolve) is recorded as a [`Warning`] so it can be
+//! reported instead of happening silently.
+
+use std::fmt;
+
+/// The pipeline stage that produced a warning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WarningStage {
+    /// Reading the YAML document.
+    Parsing,
+    /// Conversion from parsed YAML to domain types.
+    Conversion,
+    /// Placement of entities on the diagram.
+    Layout,
+    /// Rendering and connector routing.
+    Rendering,
+}
+
+impl fmt::Display for WarningStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parsing => write!(f, "parsing"),
+            Self::Conversion => write!(f, "conversion"),
+            Self::Layout => write!(f, "layout"),
+            Self::Rendering => write!(f, "rendering"),
+        }
+    }
+}
+
+/// The kind of condition a warning reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WarningKind {
+    /// A key the schema does not recognize was ignored.
+    UnknownKey,
+    /// A YAML anchor is defined but no alias refers to it.
+    UnusedAnchor,
+    /// A feature was enabled that nothing in the model depends on.
+    UnknownFeature,
+    /// The type of a referenced entity could not be determined and was assumed.
+    InferredEntityType,
+    /// An entity is in a swimlane whose declared role does not suit its type
+    /// and placement was checked leniently.
+    MisplacedEntity,
+    /// A test scenario expects an outcome that is neither a defined event nor
+    /// an error declared by the command under test.
+    UnknownTestOutcome,
+    /// A field name has different types in entities sharing a stream.
+    FieldTypeMismatch,
+    /// A field is copied from a command into an event the command records
+    /// under another type or a differently spelled name.
+    FieldDrift,
+    /// The model declares no slices, so slices were inferred from its test
+    /// scenarios.
+    InferredSlices,
+    /// An entity in a slice cannot be reached from any view or automation.
+    UnreachableEntity,
+    /// No event can result from anything a view leads to.
+    DeadEndView,
+    /// Two entities of one type have names differing by a single word or
+    /// letter.
+    SimilarEntityNames,
+    /// Field names mix naming styles such as `snake_case` and `camelCase`.
+    MixedFieldNaming,
+    /// A name or description uses a word the model bans.
+    BannedWord,
+    /// An event lacks an envelope field, or a command a propagated field,
+    /// that the model's conventions require.
+    MissingConventionField,
+    /// A slice matches none of the Event Modeling patterns.
+    UnpatternedSlice,
+    /// A lint plugin reported a violation of one of its rules.
+    PluginFinding,
+    /// An entity is declared but no slice references it, so it is not drawn.
+    OrphanedEntity,
+    /// Entities the model asks to align could not be, without breaking a
+    /// constraint that must hold.
+    UnmetLayoutConstraint,
+    /// A connection references an entity that is not on the diagram and was skipped.
+    UnresolvedConnection,
+    /// A routed connector passes through an entity it does not join, or runs
+    /// on top of or too close beside another connector.
+    ConnectorCollision,
+    /// The render ran out of time or routing iterations and drew the
+    /// remaining connectors as straight lines.
+    RenderBudgetExceeded,
+}
+
+impl WarningKind {
+    /// Returns the pipeline stage that reports this kind of warning.
+    pub fn stage(self) -> WarningStage {
+        match self {
+            Self::UnknownKey | Self::UnusedAnchor | Self::UnknownFeature => WarningStage::Parsing,
+            Self::InferredEntityType
+            | Self::MisplacedEntity
+            | Self::UnknownTestOutcome
+            | Self::FieldTypeMismatch
+            | Self::FieldDrift
+            | Self::InferredSlices
+            | Self::UnreachableEntity
+            | Self::DeadEndView
+            | Self::SimilarEntityNames
+            | Self::MixedFieldNaming
+            | Self::BannedWord
+            | Self::UnpatternedSlice
+            | Self::MissingConventionField
+            | Self::PluginFinding => WarningStage::Conversion,
+            Self::OrphanedEntity | Self::UnmetLayoutConstraint => WarningStage::Layout,
+            Self::UnresolvedConnection | Self::ConnectorCollision | Self::RenderBudgetExceeded => {
+                WarningStage::Rendering
+            }
+        }
+    }
+}
+
+/// A single non-fatal diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// What kind of condition was encountered.
+    pub kind: WarningKind,
+    /// Human-readable description naming the offending model element.
+    pub message: String,
+}
+
+impl Warning {
+    /// Creates a new warning.
+    pub fn new(kind: WarningKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// Returns the pipeline stage that produced this warning.
+    pub fn stage(&self) -> WarningStage {
+        self.kind.stage()
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.stage(), self.message)
+    }
+}
+
+/// Warnings collected across the pipeline, in the order they were reported.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Warnings(Vec<Warning>);
+
+impl Warnings {
+    /// Creates an empty collection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a warning.
+    pub fn push(&mut self, warning: Warning) {
+        self.0.push(warning);
+    }
+
+    /// Records a warning of the given kind.
+    pub fn warn(&mut self, kind: WarningKind, message: impl Into<String>) {
+        self.push(Warning::new(kind, message));
+    }
+
+    /// Returns the number of warnings.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if nothing was reported.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over the warnings in reporting order.
+    pub fn iter(&self) -> impl Iterator<Item = &Warning> {
+        self.0.iter()
+    }
+
+    /// Iterates over the warnings of a single kind.
+    pub fn of_kind(&self, kind: WarningKind) -> impl Iterator<Item = &Warning> {
+        self.0.iter().filter(move |warning| warning.kind == kind)
+    }
+}
+
+impl fmt::Display for Warnings {
+    /// Formats the warnings as a report, one per line, followed by a count.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for warning in &self.0 {
+            writeln!(f, "Warning: {warning}")?;
+        }
+        let plural = if self.0.len() == 1 { "" } else { "s" };
+        write!(f, "{} warning{plural}", self.0.len())
+    }
+}
+
+impl IntoIterator for Warnings {
+    type Item = Warning;
+    type IntoIter = std::vec::IntoIter<Warning>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_lists_each_warning_with_its_stage() {
+        let mut warnings = Warnings::new();
+        warnings.warn(
+            WarningKind::OrphanedEntity,
+            "Event 'Unused' is not in any slice",
+        );
+        warnings.warn(
+            WarningKind::UnresolvedConnection,
+            "Skipped connection A -> B",
+        );
+
+        assert_eq!(
+            warnings.to_string(),
+            "Warning: [layout] Event 'Unused' is not in any slice\n\
+             Warning: [rendering] Skipped connection A -> B\n\
+             2 warnings"
+        );
+    }
+
+    #[test]
+    fn of_kind_filters_warnings() {
+        let mut warnings = Warnings::new();
+        warnings.warn(WarningKind::OrphanedEntity, "one");
+        warnings.warn(WarningKind::InferredEntityType, "two");
+
+        let orphans: Vec<_> = warnings.of_kind(WarningKind::OrphanedEntity).collect();
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].message, "one");
+    }
+}
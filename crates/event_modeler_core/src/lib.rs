@@ -0,0 +1,84 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Event Modeler core - parsing, layout, and rendering of Event Modeling diagrams.
+//!
+//! This crate provides tools for generating professional Event Modeling diagrams
+//! from YAML-based event model descriptions. It is the library behind the
+//! `event_modeler` command-line tool, and carries none of the tool's own
+//! dependencies, so other tools can embed the renderer.
+//!
+//! ## Stability
+//!
+//! The public API follows semantic versioning, checked on every change with
+//! `cargo semver-checks`. Error enums and other types likely to grow are
+//! `#[non_exhaustive]`, so adding a variant is not a breaking change; match
+//! them with a wildcard arm.
+//!
+//! ## Features
+//!
+//! With no features enabled the crate parses models and renders them to SVG
+//! strings, and nothing more. Each output format adding dependencies of its
+//! own is opt-in:
+//!
+//! - `pdf`: PDF documents and posters, through `svg2pdf`
+//! - `png`: PNG images, rasterized with `resvg`
+//! - `html`: the static HTML documentation site
+//! - `sqlite`: exporting a model to an SQLite database, with SQLite compiled in
+//! - `libavoid`: the libavoid connector router, built from its C++ sources
+//! - `constraint-layout`: the Cassowary constraint layout
+//! - `ffi`: the C interface
+
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+// TODO: Re-enable missing_docs after implementing incremental rendering
+// #![deny(missing_docs)]
+#![deny(rustdoc::broken_intra_doc_links)]
+
+/// The version of Event Modeler, used as the default schema version for YAML files.
+/// This must match the version in Cargo.toml.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub use error::{Error, Result};
+pub use render::{
+    render_svg_str, render_svg_str_with_budget, render_svg_str_with_progress,
+    render_svg_str_with_warnings,
+};
+
+/// Code generated from event models.
+pub mod codegen;
+
+/// Diagram generation and rendering.
+pub mod diagram;
+
+/// The crate-level error type.
+pub mod error;
+
+/// Event model domain types and operations.
+pub mod event_model;
+
+/// Export to documentation formats.
+pub mod export;
+
+/// A C interface for embedding the renderer in other languages.
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+/// Entry points for fuzzing the parsers.
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+
+/// Infrastructure and utility types.
+pub mod infrastructure;
+
+/// Rendering a model from its source, safe to call from many threads at once.
+pub mod render;
+
+/// Connector routing configuration, and the libavoid router with the
+/// `libavoid` feature.
+pub mod routing;
+
+/// Synthetic models for stress-testing the renderer.
+pub mod stress;
+
+/// Validation of many models at once against shared definitions.
+pub mod validation;
@@ -0,0 +1,236 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Rendering a model straight from its source, for embedding in a service.
+//!
+//! [`render_svg_str`] runs the whole pipeline on a model held in memory. It
+//! keeps no state between calls and shares none with other calls: the caches
+//! and warnings it uses are its own and dropped when it returns, and nothing
+//! is read from the file system or the environment. A web service can
+//! therefore call it from as many threads at once as it likes, and the same
+//! model always renders the same diagram however many renders run beside it.
+//!
+//! The types the pipeline passes between stages are checked to be `Send` and
+//! `Sync` when the crate is compiled, so a change that would tie rendering to
+//! one thread fails the build rather than a server under load.
+
+use crate::Result;
+use crate::diagram::{self, EventModelDiagram, RenderBudget, RenderCache, SvgOptions};
+use crate::event_model::roles::{self, PlacementPolicy};
+use crate::event_model::yaml_types::YamlEventModel;
+use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+use crate::infrastructure::progress::{Progress, ProgressStage};
+use crate::infrastructure::warnings::Warnings;
+
+// Fails to compile if a type rendering passes along is tied to one thread
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<yaml_parser::YamlEventModel>();
+    assert_send_sync::<YamlEventModel>();
+    assert_send_sync::<EventModelDiagram>();
+    assert_send_sync::<SvgOptions>();
+    assert_send_sync::<RenderCache>();
+    assert_send_sync::<Warnings>();
+    assert_send_sync::<crate::Error>();
+};
+
+/// Renders a model in the YAML format to an SVG document.
+///
+/// Misplaced entities are tolerated, as they are by the diagram server;
+/// unrecognized keys are refused.
+pub fn render_svg_str(source: &str) -> Result<String> {
+    render_svg_str_with_warnings(source, &mut Warnings::new())
+}
+
+/// Renders a model in the YAML format to an SVG document, reporting what the
+/// pipeline tolerated to `warnings`.
+pub fn render_svg_str_with_warnings(source: &str, warnings: &mut Warnings) -> Result<String> {
+    render_svg_str_with_progress(source, warnings, Progress::none())
+}
+
+/// Renders a model in the YAML format to an SVG document like
+/// [`render_svg_str_with_warnings`], reporting how far each stage has got to
+/// `progress`.
+pub fn render_svg_str_with_progress(
+    source: &str,
+    warnings: &mut Warnings,
+    progress: Progress,
+) -> Result<String> {
+    render_with_options(
+        source,
+        warnings,
+        SvgOptions {
+            progress,
+            ..SvgOptions::default()
+        },
+    )
+}
+
+/// Renders a model in the YAML format to an SVG document like
+/// [`render_svg_str`], routing connectors only as long as `budget` allows.
+/// Connectors left once it runs out are drawn as straight lines, so a
+/// service can bound the time any one model takes.
+pub fn render_svg_str_with_budget(source: &str, budget: RenderBudget) -> Result<String> {
+    render_with_options(
+        source,
+        &mut Warnings::new(),
+        SvgOptions {
+            budget,
+            ..SvgOptions::default()
+        },
+    )
+}
+
+/// Runs the whole pipeline on `source`, rendering with `options`.
+fn render_with_options(
+    source: &str,
+    warnings: &mut Warnings,
+    options: SvgOptions,
+) -> Result<String> {
+    let progress = options.progress.clone();
+    progress.start(ProgressStage::Parsing);
+    let parsed = yaml_parser::parse_yaml_with_policy(
+        source,
+        yaml_parser::UnknownKeyPolicy::Reject,
+        warnings,
+    )?;
+    let model = yaml_converter::convert_yaml_to_domain_with_warnings(parsed, warnings)?;
+    roles::check_placement(&model, PlacementPolicy::Warn, warnings)?;
+    let diagram = diagram::build_diagram_from_domain(&model)?;
+    progress.finish(ProgressStage::Parsing);
+    Ok(diagram::render_to_svg_with_cache(
+        &diagram,
+        &options,
+        &mut RenderCache::new(),
+        warnings,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDERING: &str = r#"workflow: Ordering
+swimlanes:
+  - ui: "UI"
+  - backend: "Backend"
+views:
+  OrderScreen:
+    description: "Order screen"
+    swimlane: ui
+    components:
+      - Submit: Button
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+slices:
+  - name: Ordering
+    connections:
+      - OrderScreen.Submit -> PlaceOrder
+      - PlaceOrder -> OrderPlaced
+      - OrderPlaced -> OrderScreen
+"#;
+
+    const EXAMPLE: &str = include_str!("../../../tests/fixtures/acceptance/example.eventmodel");
+
+    #[test]
+    fn shades_only_the_lanes_no_entity_is_placed_in() {
+        // `E` cannot be typed from its name, yet is drawn in its event's lane
+        let model = r#"workflow: Lanes
+unused_lanes: shaded
+swimlanes:
+  - ui: "UI"
+  - backend: "Backend"
+  - other: "Other"
+commands:
+  C:
+    description: "Do it"
+    swimlane: ui
+events:
+  E:
+    description: "Done"
+    swimlane: backend
+slices:
+  - name: Do
+    connections:
+      - C -> E
+"#;
+
+        let svg = render_svg_str(model).unwrap();
+
+        assert_eq!(svg.matches(r#"class="unused-lane""#).count(), 1);
+    }
+
+    #[test]
+    fn renders_many_models_from_many_threads_as_it_does_from_one() {
+        let models = [ORDERING, EXAMPLE, "workflow: ["];
+        let expected: Vec<_> = models
+            .iter()
+            .map(|model| render_svg_str(model).map_err(|e| e.to_string()))
+            .collect();
+        assert!(expected[0].is_ok() && expected[1].is_ok() && expected[2].is_err());
+
+        std::thread::scope(|scope| {
+            let renderers: Vec<_> = (0..8)
+                .map(|thread| {
+                    scope.spawn(move || {
+                        (0..12)
+                            .map(|render| {
+                                // Each thread starts on a different model
+                                let model = (thread + render) % models.len();
+                                (
+                                    model,
+                                    render_svg_str(models[model]).map_err(|e| e.to_string()),
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            for renderer in renderers {
+                for (model, rendered) in renderer.join().unwrap() {
+                    assert_eq!(rendered, expected[model]);
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn reports_each_stage_in_order_and_each_connector_routed() {
+        use crate::infrastructure::progress::ProgressEvent;
+        use std::sync::{Arc, Mutex};
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        let progress = Progress::to(move |event: ProgressEvent| sink.lock().unwrap().push(event));
+
+        let svg = render_svg_str_with_progress(ORDERING, &mut Warnings::new(), progress).unwrap();
+        assert_eq!(svg, render_svg_str(ORDERING).unwrap());
+
+        let events = events.lock().unwrap();
+        let stages: Vec<_> = events.iter().map(|event| event.stage).collect();
+        let mut order = stages.clone();
+        order.dedup();
+        assert_eq!(
+            order,
+            [
+                ProgressStage::Parsing,
+                ProgressStage::Layout,
+                ProgressStage::Routing,
+                ProgressStage::Rendering
+            ]
+        );
+        let routing: Vec<_> = events
+            .iter()
+            .filter(|event| event.stage == ProgressStage::Routing)
+            .map(|event| (event.done, event.total, event.percent()))
+            .collect();
+        assert_eq!(routing, [(0, 3, 0), (1, 3, 33), (2, 3, 66), (3, 3, 100)]);
+        assert_eq!(events.last().map(ProgressEvent::percent), Some(100));
+    }
+}
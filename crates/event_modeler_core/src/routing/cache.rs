@@ -0,0 +1,248 @@
+//! Caching of routed connector paths.
+//!
+//! Routing is the most expensive stage on dense models. A routed path only
+//! depends on the source and target rectangles, the waypoints it must pass
+//! through, the set of obstacles the router has to avoid, and the routing
+//! configuration, so [`RouteCache`]
+//! stores paths under exactly that key. Re-rendering an unchanged model (watch
+//! mode, several output formats in one run) then reuses every route.
+
+use crate::diagram::Px;
+use crate::diagram::routing_types::{Point, Rectangle, RoutePath};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use super::RoutingConfig;
+
+/// Order-independent fingerprint of a set of obstacles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObstacleSetHash(u64);
+
+impl ObstacleSetHash {
+    /// Fingerprints a set of obstacle rectangles.
+    ///
+    /// The order in which obstacles are supplied does not affect the result.
+    pub fn from_obstacles<'a>(obstacles: impl IntoIterator<Item = &'a Rectangle>) -> Self {
+        let mut sorted: Vec<&Rectangle> = obstacles.into_iter().collect();
+        sorted.sort_by_key(|rect| (rect.x, rect.y, rect.width, rect.height));
+
+        let mut hasher = DefaultHasher::new();
+        sorted.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+}
+
+/// Everything that determines the outcome of routing a single connector.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct RouteKey {
+    source: Rectangle,
+    target: Rectangle,
+    waypoints: Vec<Point>,
+    obstacles: ObstacleSetHash,
+    config: ConfigFingerprint,
+}
+
+/// Bit-exact fingerprint of a [`RoutingConfig`], since floats are not `Hash`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct ConfigFingerprint {
+    segment_penalty: u64,
+    obstacle_margin: Px,
+    min_separation: Px,
+    grid: Option<Px>,
+}
+
+impl From<&RoutingConfig> for ConfigFingerprint {
+    fn from(config: &RoutingConfig) -> Self {
+        Self {
+            segment_penalty: config.segment_penalty.to_bits(),
+            obstacle_margin: config.obstacle_margin,
+            min_separation: config.min_separation,
+            grid: config.grid,
+        }
+    }
+}
+
+/// Memoized connector routes.
+#[derive(Debug, Clone, Default)]
+pub struct RouteCache {
+    routes: HashMap<RouteKey, RoutePath>,
+    hits: usize,
+    misses: usize,
+}
+
+impl RouteCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of cached routes.
+    pub fn len(&self) -> usize {
+        self.routes.len()
+    }
+
+    /// Returns true if no routes have been cached yet.
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    /// Returns how many lookups were answered from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Returns how many lookups required routing.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Removes all cached routes and resets the statistics.
+    pub fn clear(&mut self) {
+        self.routes.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    /// Returns the cached route between `source` and `target`, invoking `route`
+    /// to compute it if this combination has not been routed before.
+    pub fn get_or_route(
+        &mut self,
+        source: &Rectangle,
+        target: &Rectangle,
+        obstacles: ObstacleSetHash,
+        config: &RoutingConfig,
+        route: impl FnOnce() -> RoutePath,
+    ) -> &RoutePath {
+        self.get_or_route_via(source, target, &[], obstacles, config, route)
+    }
+
+    /// Returns the cached route between `source` and `target` that passes
+    /// through `waypoints` in order, invoking `route` to compute it if this
+    /// combination has not been routed before.
+    pub fn get_or_route_via(
+        &mut self,
+        source: &Rectangle,
+        target: &Rectangle,
+        waypoints: &[Point],
+        obstacles: ObstacleSetHash,
+        config: &RoutingConfig,
+        route: impl FnOnce() -> RoutePath,
+    ) -> &RoutePath {
+        let key = RouteKey {
+            source: source.clone(),
+            target: target.clone(),
+            waypoints: waypoints.to_vec(),
+            obstacles,
+            config: ConfigFingerprint::from(config),
+        };
+
+        if self.routes.contains_key(&key) {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+
+        self.routes.entry(key).or_insert_with(route)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::types::NonEmpty;
+
+    fn point(x: u32, y: u32) -> Point {
+        Point::new(Px::new(x), Px::new(y))
+    }
+
+    fn rect(x: u32, y: u32, width: u32, height: u32) -> Rectangle {
+        Rectangle::new(Px::new(x), Px::new(y), Px::new(width), Px::new(height))
+    }
+
+    fn straight_route() -> RoutePath {
+        RoutePath::new(
+            NonEmpty::from_head_and_tail(point(0, 0), vec![point(10, 0)]),
+            Px::new(10),
+        )
+    }
+
+    #[test]
+    fn obstacle_hash_ignores_order() {
+        let a = rect(0, 0, 10, 10);
+        let b = rect(20, 20, 10, 10);
+
+        assert_eq!(
+            ObstacleSetHash::from_obstacles([&a, &b]),
+            ObstacleSetHash::from_obstacles([&b, &a])
+        );
+        assert_ne!(
+            ObstacleSetHash::from_obstacles([&a, &b]),
+            ObstacleSetHash::from_obstacles([&a])
+        );
+    }
+
+    #[test]
+    fn cache_reuses_routes_for_identical_inputs() {
+        let mut cache = RouteCache::new();
+        let source = rect(0, 0, 10, 10);
+        let target = rect(50, 0, 10, 10);
+        let obstacles = ObstacleSetHash::from_obstacles([&source, &target]);
+        let config = RoutingConfig::default();
+
+        cache.get_or_route(&source, &target, obstacles, &config, straight_route);
+        cache.get_or_route(&source, &target, obstacles, &config, || {
+            panic!("route should have been cached")
+        });
+
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 1);
+    }
+
+    #[test]
+    fn cache_misses_when_config_changes() {
+        let mut cache = RouteCache::new();
+        let source = rect(0, 0, 10, 10);
+        let target = rect(50, 0, 10, 10);
+        let obstacles = ObstacleSetHash::from_obstacles([&source, &target]);
+        let wider_margin = RoutingConfig {
+            obstacle_margin: Px::new(20),
+            ..RoutingConfig::default()
+        };
+
+        cache.get_or_route(
+            &source,
+            &target,
+            obstacles,
+            &RoutingConfig::default(),
+            straight_route,
+        );
+        cache.get_or_route(&source, &target, obstacles, &wider_margin, straight_route);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.hits(), 0);
+    }
+
+    #[test]
+    fn cache_misses_when_waypoints_change() {
+        let mut cache = RouteCache::new();
+        let source = rect(0, 0, 10, 10);
+        let target = rect(50, 0, 10, 10);
+        let obstacles = ObstacleSetHash::from_obstacles([&source, &target]);
+        let config = RoutingConfig::default();
+
+        cache.get_or_route(&source, &target, obstacles, &config, straight_route);
+        cache.get_or_route_via(
+            &source,
+            &target,
+            &[point(25, 40)],
+            obstacles,
+            &config,
+            straight_route,
+        );
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.hits(), 0);
+    }
+}
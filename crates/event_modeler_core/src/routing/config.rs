@@ -0,0 +1,76 @@
+//! Configuration and errors of connector routing.
+//!
+//! The renderer routes connectors itself and reads only the clearances
+//! here; they are kept apart from the libavoid router so that rendering does
+//! not need the router's C++ build.
+
+use crate::diagram::Px;
+use thiserror::Error;
+
+/// Errors that can occur during routing operations.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum RoutingError {
+    /// Failed to create router
+    #[error("Failed to create router")]
+    RouterCreation,
+
+    /// Failed to create shape
+    #[error("Failed to create shape: {0}")]
+    ShapeCreation(String),
+
+    /// Failed to create connector
+    #[error("Failed to create connector: {0}")]
+    ConnectorCreation(String),
+
+    /// Failed to route connector
+    #[error("Failed to route connector: {0}")]
+    RoutingFailed(String),
+
+    /// Invalid parameters
+    #[error("Invalid parameters: {0}")]
+    InvalidParameters(String),
+}
+
+/// Result type for routing operations.
+pub type Result<T> = std::result::Result<T, RoutingError>;
+
+/// Configuration for the routing algorithm.
+#[derive(Debug, Clone)]
+pub struct RoutingConfig {
+    /// Segment penalty for orthogonal routing
+    pub segment_penalty: f64,
+
+    /// Margin around obstacles
+    pub obstacle_margin: Px,
+
+    /// Closest two parallel segments of different connectors may run
+    pub min_separation: Px,
+
+    /// Spacing of the grid connector bends snap to, if any
+    pub grid: Option<Px>,
+}
+
+impl Default for RoutingConfig {
+    fn default() -> Self {
+        Self {
+            segment_penalty: 50.0,
+            obstacle_margin: Px::new(10),
+            min_separation: Px::new(8),
+            grid: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routing_config_default() {
+        let config = RoutingConfig::default();
+        assert_eq!(config.segment_penalty, 50.0);
+        assert_eq!(config.obstacle_margin, Px::new(10));
+        assert_eq!(config.min_separation, Px::new(8));
+    }
+}
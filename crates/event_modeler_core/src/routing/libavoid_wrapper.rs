@@ -7,37 +7,10 @@
 
 use crate::diagram::routing_types::{Point, Rectangle, RoutePath};
 use std::ptr::NonNull;
-use thiserror::Error;
 
+use super::config::{Result, RoutingConfig, RoutingError};
 use super::libavoid_ffi::{self, Router};
 
-/// Errors that can occur during routing operations.
-#[derive(Debug, Error)]
-pub enum RoutingError {
-    /// Failed to create router
-    #[error("Failed to create router")]
-    RouterCreation,
-
-    /// Failed to create shape
-    #[error("Failed to create shape: {0}")]
-    ShapeCreation(String),
-
-    /// Failed to create connector
-    #[error("Failed to create connector: {0}")]
-    ConnectorCreation(String),
-
-    /// Failed to route connector
-    #[error("Failed to route connector: {0}")]
-    RoutingFailed(String),
-
-    /// Invalid parameters
-    #[error("Invalid parameters: {0}")]
-    InvalidParameters(String),
-}
-
-/// Result type for routing operations.
-pub type Result<T> = std::result::Result<T, RoutingError>;
-
 /// Safe wrapper around libavoid Router.
 #[derive(Debug)]
 pub struct LibavoidRouter {
@@ -47,7 +20,14 @@ pub struct LibavoidRouter {
 impl LibavoidRouter {
     /// Creates a new router instance.
     pub fn new() -> Result<Self> {
-        // TODO: Create router with OrthogonalRouting flag
+        Self::with_config(&RoutingConfig::default())
+    }
+
+    /// Creates a router keeping the clearances of `config`.
+    pub fn with_config(config: &RoutingConfig) -> Result<Self> {
+        // TODO: Create router with OrthogonalRouting flag, setting the
+        // shapeBufferDistance routing parameter to `config.obstacle_margin`
+        // and idealNudgingDistance to `config.min_separation`
         // This will be implemented once autocxx bindings are working
         Err(RoutingError::RouterCreation)
     }
@@ -85,25 +65,6 @@ impl Drop for LibavoidRouter {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ObstacleId(u32);
 
-/// Configuration for the routing algorithm.
-#[derive(Debug, Clone)]
-pub struct RoutingConfig {
-    /// Segment penalty for orthogonal routing
-    pub segment_penalty: f64,
-
-    /// Margin around obstacles
-    pub obstacle_margin: f64,
-}
-
-impl Default for RoutingConfig {
-    fn default() -> Self {
-        Self {
-            segment_penalty: 50.0,
-            obstacle_margin: 10.0,
-        }
-    }
-}
-
 // Helper functions for converting between our types and libavoid types
 
 /// Converts our Point to libavoid Point.
@@ -136,11 +97,4 @@ mod tests {
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), RoutingError::RouterCreation));
     }
-
-    #[test]
-    fn test_routing_config_default() {
-        let config = RoutingConfig::default();
-        assert_eq!(config.segment_penalty, 50.0);
-        assert_eq!(config.obstacle_margin, 10.0);
-    }
 }
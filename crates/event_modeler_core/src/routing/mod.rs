@@ -0,0 +1,21 @@
+//! Connector routing using libavoid.
+//!
+//! This module provides orthogonal connector routing functionality
+//! using the libavoid library for collision-free path finding. The router
+//! and its C++ bindings come with the `libavoid` feature; the routing
+//! configuration and route cache are always available.
+
+mod cache;
+mod config;
+#[cfg(feature = "libavoid")]
+mod libavoid_ffi;
+#[cfg(feature = "libavoid")]
+mod libavoid_wrapper;
+
+pub use cache::{ObstacleSetHash, RouteCache};
+pub use config::{Result, RoutingConfig, RoutingError};
+#[cfg(feature = "libavoid")]
+pub use libavoid_wrapper::{LibavoidRouter, ObstacleId};
+
+// Re-export routing types from diagram module for convenience
+pub use crate::diagram::routing_types::{Point, Rectangle, RoutePath};
@@ -0,0 +1,125 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Synthetic models for stress-testing the renderer.
+//!
+//! [`synthetic_model`] writes a model of any size in the YAML format, made of
+//! slices following the usual rhythm of a workflow: a screen submits a
+//! command, which records an event, which feeds a projection, which the next
+//! slice's screen reads. Some projections also read events recorded slices
+//! earlier, picked by a seeded generator, so connectors cross the diagram the
+//! way they do in real models. The same size and seed always give the same
+//! model.
+//!
+//! Names carry their slice's number twice, e.g. `Form12Entry12Recorded`, so
+//! no two names of a kind are one letter apart and the terminology check
+//! stays quiet however large the model grows.
+
+use crate::diagram::Jitter;
+use std::fmt::Write;
+
+/// Entities each slice adds: a screen, a command, an event, and a projection.
+const ENTITIES_PER_SLICE: usize = 4;
+
+/// Swimlanes events are recorded in, spread across in turn.
+const STREAMS: usize = 3;
+
+/// One in this many projections also reads an event recorded slices earlier.
+const CROSS_LINK_EVERY: u64 = 3;
+
+/// Returns a model of at least `entities` entities, rounded up to whole slices.
+pub fn synthetic_model(entities: usize, seed: u64) -> String {
+    let slices = entities.div_ceil(ENTITIES_PER_SLICE).max(1);
+    let mut jitter = Jitter::new(seed);
+    let screen = |n: usize| format!("Page{n}Form{n}Screen");
+    let command = |n: usize| format!("Submit{n}Form{n}");
+    let event = |n: usize| format!("Form{n}Entry{n}Recorded");
+    let projection = |n: usize| format!("Form{n}Entry{n}Projection");
+
+    let mut yaml = String::new();
+    let _ = writeln!(yaml, "workflow: Synthetic Workflow of {slices} Slices");
+    yaml.push_str("swimlanes:\n  - ui: \"Screens\"\n  - app: \"Application\"\n");
+    for stream in 0..STREAMS {
+        let _ = writeln!(yaml, "  - stream{stream}: \"Stream {}\"", stream + 1);
+    }
+
+    yaml.push_str("views:\n");
+    for n in 1..=slices {
+        let _ = write!(
+            yaml,
+            "  {}:\n    description: \"Form {n}\"\n    swimlane: ui\n    components:\n      - Submit: Button\n",
+            screen(n)
+        );
+    }
+    yaml.push_str("commands:\n");
+    for n in 1..=slices {
+        let _ = write!(
+            yaml,
+            "  {}:\n    description: \"Submit form {n}\"\n    swimlane: app\n    data:\n      entry_id: UUID\n      value: String\n",
+            command(n)
+        );
+    }
+    yaml.push_str("events:\n");
+    for n in 1..=slices {
+        let _ = write!(
+            yaml,
+            "  {}:\n    description: \"Form {n} was submitted\"\n    swimlane: stream{}\n    data:\n      entry_id: UUID\n      value: String\n",
+            event(n),
+            n % STREAMS
+        );
+    }
+    yaml.push_str("projections:\n");
+    for n in 1..=slices {
+        let _ = write!(
+            yaml,
+            "  {}:\n    description: \"Entries of form {n}\"\n    swimlane: app\n    fields:\n      entry_id: UUID\n      value: String\n",
+            projection(n)
+        );
+    }
+
+    yaml.push_str("slices:\n");
+    for n in 1..=slices {
+        let _ = write!(
+            yaml,
+            "  - name: \"Step {n}\"\n    connections:\n      - {screen}.Submit -> {command}\n      - {command} -> {event}\n      - {event} -> {projection}\n",
+            screen = screen(n),
+            command = command(n),
+            event = event(n),
+            projection = projection(n)
+        );
+        if n < slices {
+            let _ = writeln!(yaml, "      - {} -> {}", projection(n), screen(n + 1));
+        }
+        if n > 1 && jitter.next_u64() % CROSS_LINK_EVERY == 0 {
+            let earlier = 1 + (jitter.next_u64() % (n as u64 - 1)) as usize;
+            let _ = writeln!(yaml, "      - {} -> {}", event(earlier), projection(n));
+        }
+    }
+    yaml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_a_valid_model_of_the_size_asked_for() {
+        let yaml = synthetic_model(40, 7);
+        let mut warnings = crate::infrastructure::warnings::Warnings::new();
+
+        let model = crate::infrastructure::parsing::yaml_parser::parse_yaml(&yaml).unwrap();
+        let entities =
+            model.views.len() + model.commands.len() + model.events.len() + model.projections.len();
+        crate::render_svg_str_with_warnings(&yaml, &mut warnings).unwrap();
+
+        assert_eq!(entities, 40);
+        assert_eq!(model.slices.len(), 10);
+        assert!(
+            warnings
+                .iter()
+                .all(|warning| !warning.to_string().contains("nearly the same")),
+            "{warnings}"
+        );
+        assert_eq!(yaml, synthetic_model(40, 7));
+    }
+}
@@ -0,0 +1,277 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Validation of many models at once, as in CI.
+//!
+//! Repositories holding dozens of models often keep the types, field sets,
+//! and naming rules the models share in files of their own. [`validate_models`]
+//! parses each shared file once, adds its definitions to every model (a
+//! model's own definitions win), checks the models, in parallel with the
+//! `parallel` feature, and gathers the errors and warnings of all of them
+//! into one [`ValidationReport`].
+
+use crate::event_model::reachability::Reachability;
+use crate::event_model::roles::{PlacementPolicy, check_placement};
+use crate::event_model::yaml_types::YamlEventModel;
+use crate::infrastructure::parsing::yaml_converter::convert_yaml_to_domain_with_warnings;
+use crate::infrastructure::parsing::yaml_parser::{
+    self, UnknownKeyPolicy, YamlEventModel as ParsedModel, YamlVocabulary,
+};
+use crate::infrastructure::warnings::Warnings;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Errors that can occur while reading the shared definitions.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// A shared file could not be read.
+    #[error("Could not read shared file {path}: {source}")]
+    Read {
+        /// The shared file.
+        path: PathBuf,
+        /// Why it could not be read.
+        source: std::io::Error,
+    },
+
+    /// A shared file is not a valid set of shared definitions.
+    #[error("Invalid shared file {path}: {source}")]
+    Parse {
+        /// The shared file.
+        path: PathBuf,
+        /// What is wrong with it.
+        source: yaml_parser::YamlParseError,
+    },
+}
+
+/// What validating one model found.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModelFindings {
+    /// The model file.
+    pub path: PathBuf,
+    /// Why the model is invalid, or `None` if it is valid.
+    pub error: Option<String>,
+    /// The warnings raised, each with the stage that raised it.
+    pub warnings: Vec<String>,
+}
+
+/// The findings of every model validated together, in the order given.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ValidationReport {
+    models: Vec<ModelFindings>,
+}
+
+impl ValidationReport {
+    /// Returns the findings of each model.
+    pub fn models(&self) -> &[ModelFindings] {
+        &self.models
+    }
+
+    /// Returns the number of invalid models.
+    pub fn invalid(&self) -> usize {
+        self.models
+            .iter()
+            .filter(|model| model.error.is_some())
+            .count()
+    }
+
+    /// Formats the report as a Markdown document: a summary, then the
+    /// findings of each model that has any.
+    pub fn to_markdown(&self) -> String {
+        let warnings: usize = self.models.iter().map(|model| model.warnings.len()).sum();
+        let mut markdown = format!(
+            "# Validation\n\nModels: {}. Valid: {}. Invalid: {}. Warnings: {warnings}.\n",
+            self.models.len(),
+            self.models.len() - self.invalid(),
+            self.invalid()
+        );
+        for model in &self.models {
+            if model.error.is_none() && model.warnings.is_empty() {
+                continue;
+            }
+            markdown.push_str(&format!("\n## {}\n\n", model.path.display()));
+            if let Some(error) = &model.error {
+                markdown.push_str(&format!("Invalid: {error}\n"));
+            }
+            for warning in &model.warnings {
+                markdown.push_str(&format!("- {warning}\n"));
+            }
+        }
+        markdown
+    }
+
+    /// Formats the report as JSON.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Reads the shared files, then validates the models in parallel against
+/// them.
+pub fn validate_models(
+    models: &[PathBuf],
+    shared: &[PathBuf],
+) -> Result<ValidationReport, ValidationError> {
+    let vocabularies = shared
+        .iter()
+        .map(|path| {
+            let content =
+                std::fs::read_to_string(path).map_err(|source| ValidationError::Read {
+                    path: path.clone(),
+                    source,
+                })?;
+            yaml_parser::parse_vocabulary(&content).map_err(|source| ValidationError::Parse {
+                path: path.clone(),
+                source,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    #[cfg(feature = "parallel")]
+    let models = models.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let models = models.iter();
+    let models = models
+        .map(|path| {
+            let mut warnings = Warnings::new();
+            let error = validate_model(path, &vocabularies, &mut warnings).err();
+            ModelFindings {
+                path: path.clone(),
+                error,
+                warnings: warnings.iter().map(ToString::to_string).collect(),
+            }
+        })
+        .collect();
+    Ok(ValidationReport { models })
+}
+
+/// Reports naming inconsistencies, slices matching no pattern, and breaches
+/// of the model's envelope conventions.
+pub fn lint_model(model: &YamlEventModel, warnings: &mut Warnings) {
+    crate::event_model::terminology::TerminologyReport::from_model(model).warn(warnings);
+    crate::event_model::patterns::SlicePatterns::from_model(model).warn(warnings);
+    crate::event_model::conventions::ConventionReport::from_model(model).warn(warnings);
+}
+
+/// Checks one model the way rendering it would, returning why it is
+/// invalid.
+fn validate_model(
+    path: &Path,
+    vocabularies: &[YamlVocabulary],
+    warnings: &mut Warnings,
+) -> Result<(), String> {
+    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    validate_source(&content, vocabularies, warnings)
+}
+
+/// Checks a model held in memory the way rendering it would, with the given
+/// shared definitions, returning why it is invalid.
+pub fn validate_source(
+    source: &str,
+    vocabularies: &[YamlVocabulary],
+    warnings: &mut Warnings,
+) -> Result<(), String> {
+    let mut parsed =
+        yaml_parser::parse_yaml_with_policy(source, UnknownKeyPolicy::Reject, warnings)
+            .map_err(|e| format!("YAML parse error: {e}"))?;
+    for vocabulary in vocabularies {
+        include_vocabulary(&mut parsed, vocabulary);
+    }
+    let model = convert_yaml_to_domain_with_warnings(parsed, warnings)
+        .map_err(|e| format!("YAML conversion error: {e}"))?;
+    check_placement(&model, PlacementPolicy::Reject, warnings).map_err(|e| e.to_string())?;
+    Reachability::from_model(&model).warn(warnings);
+    lint_model(&model, warnings);
+    Ok(())
+}
+
+/// Adds the shared definitions a model does not make itself.
+fn include_vocabulary(model: &mut ParsedModel, vocabulary: &YamlVocabulary) {
+    for (name, definition) in &vocabulary.types {
+        model
+            .types
+            .entry(name.clone())
+            .or_insert_with(|| definition.clone());
+    }
+    for (name, set) in &vocabulary.field_sets {
+        model
+            .field_sets
+            .entry(name.clone())
+            .or_insert_with(|| set.clone());
+    }
+    for (name, extension) in &vocabulary.extensions {
+        model
+            .extensions
+            .entry(name.clone())
+            .or_insert_with(|| extension.clone());
+    }
+    let union = |into: &mut Vec<String>, from: &[String]| {
+        for item in from {
+            if !into.contains(item) {
+                into.push(item.clone());
+            }
+        }
+    };
+    union(
+        &mut model.terminology.banned_words,
+        &vocabulary.terminology.banned_words,
+    );
+    union(
+        &mut model.conventions.envelope,
+        &vocabulary.conventions.envelope,
+    );
+    union(
+        &mut model.conventions.propagate,
+        &vocabulary.conventions.propagate,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_models_against_shared_definitions() {
+        // Named after the test and process so concurrent runs do not share it
+        let dir = std::env::temp_dir().join(format!(
+            "event_modeler_validates_models_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let shared = dir.join("vocabulary.yaml");
+        std::fs::write(
+            &shared,
+            "field_sets:\n  Envelope:\n    fields:\n      correlation_id: CorrelationId\n",
+        )
+        .unwrap();
+        let model = |name: &str, extends: &str| {
+            let path = dir.join(format!("{name}.eventmodel"));
+            std::fs::write(
+                &path,
+                format!(
+                    "workflow: {name}\nswimlanes:\n  - backend: \"Backend\"\nevents:\n  Happened:\n    description: \"Something happened\"\n    swimlane: backend\n    extends: [{extends}]\n"
+                ),
+            )
+            .unwrap();
+            path
+        };
+        let models = [model("Valid", "Envelope"), model("Broken", "Header")];
+
+        let report = validate_models(&models, &[shared]).unwrap();
+
+        assert_eq!(report.invalid(), 1);
+        assert_eq!(report.models()[0].error, None);
+        assert_eq!(
+            report.models()[1].error.as_deref(),
+            Some("YAML conversion error: Unknown field set 'Header' extended by event 'Happened'")
+        );
+        assert!(
+            report
+                .to_markdown()
+                .starts_with("# Validation\n\nModels: 2. Valid: 1. Invalid: 1. ")
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,13 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Feeds arbitrary input through the YAML parser, converter, and diagram
+//! builder.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    event_modeler_core::fuzzing::fuzz_yaml(data);
+});
@@ -0,0 +1,121 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+// Renders an event model exported by `event_modeler model.eventmodel -o model.mdx`.
+//
+// Copy this file to `src/components/EventModel.jsx` in a Docusaurus site. The
+// exported MDX page passes the diagram as `svg` along with the props in the
+// `.props.json` file written beside it. Hovering an entity shows its type,
+// swimlane, owner, slices, and description; the filters fade every entity
+// outside the chosen slice, type, or owner.
+
+import React, {useEffect, useMemo, useRef, useState} from 'react';
+
+const LINK_PREFIX = '#entity-';
+const FADED_FILL = 'rgba(248, 248, 248, 0.7)';
+
+function Filter({label, value, options, onChange}) {
+  return (
+    <label style={{marginRight: '1rem'}}>
+      {label}{' '}
+      <select value={value} onChange={(event) => onChange(event.target.value)}>
+        <option value="">All</option>
+        {options.map((option) => (
+          <option key={option} value={option}>
+            {option}
+          </option>
+        ))}
+      </select>
+    </label>
+  );
+}
+
+export default function EventModel({svg, workflow, slices = [], entities = []}) {
+  const container = useRef(null);
+  const [slice, setSlice] = useState('');
+  const [type, setType] = useState('');
+  const [owner, setOwner] = useState('');
+  const [tooltip, setTooltip] = useState(null);
+
+  const byName = useMemo(
+    () => Object.fromEntries(entities.map((entity) => [entity.name, entity])),
+    [entities],
+  );
+  const types = useMemo(
+    () => [...new Set(entities.map((entity) => entity.type))].sort(),
+    [entities],
+  );
+  const owners = useMemo(
+    () => [...new Set(entities.map((entity) => entity.owner).filter(Boolean))].sort(),
+    [entities],
+  );
+
+  useEffect(() => {
+    const links = container.current.querySelectorAll('a.entity-link');
+    const cleanups = [];
+    links.forEach((link) => {
+      const name = decodeURIComponent(link.getAttribute('href').slice(LINK_PREFIX.length));
+      const entity = byName[name];
+      const rect = link.querySelector('rect');
+      const matches =
+        entity &&
+        (!slice || entity.slices.includes(slice)) &&
+        (!type || entity.type === type) &&
+        (!owner || entity.owner === owner);
+      rect.setAttribute('fill', matches ? 'transparent' : FADED_FILL);
+
+      const show = (event) => setTooltip({entity, x: event.clientX, y: event.clientY});
+      const hide = () => setTooltip(null);
+      const stay = (event) => event.preventDefault();
+      link.addEventListener('mousemove', show);
+      link.addEventListener('mouseleave', hide);
+      link.addEventListener('click', stay);
+      cleanups.push(() => {
+        link.removeEventListener('mousemove', show);
+        link.removeEventListener('mouseleave', hide);
+        link.removeEventListener('click', stay);
+      });
+    });
+    return () => cleanups.forEach((cleanup) => cleanup());
+  }, [svg, byName, slice, type, owner]);
+
+  return (
+    <figure aria-label={workflow}>
+      <div>
+        <Filter label="Slice" value={slice} options={slices} onChange={setSlice} />
+        <Filter label="Type" value={type} options={types} onChange={setType} />
+        {owners.length > 0 && (
+          <Filter label="Owner" value={owner} options={owners} onChange={setOwner} />
+        )}
+      </div>
+      <div
+        ref={container}
+        style={{overflowX: 'auto'}}
+        dangerouslySetInnerHTML={{__html: svg}}
+      />
+      {tooltip?.entity && (
+        <div
+          role="tooltip"
+          style={{
+            position: 'fixed',
+            left: tooltip.x + 12,
+            top: tooltip.y + 12,
+            maxWidth: '24rem',
+            padding: '0.5rem',
+            background: 'var(--ifm-background-surface-color, white)',
+            border: '1px solid #cccccc',
+            pointerEvents: 'none',
+            zIndex: 10,
+          }}>
+          <strong>{tooltip.entity.name}</strong> ({tooltip.entity.type} in{' '}
+          {tooltip.entity.swimlane})
+          {tooltip.entity.owner && <div>Owner: {tooltip.entity.owner}</div>}
+          {tooltip.entity.slices.length > 0 && (
+            <div>Slices: {tooltip.entity.slices.join(', ')}</div>
+          )}
+          {tooltip.entity.description && <p>{tooltip.entity.description}</p>}
+        </div>
+      )}
+    </figure>
+  );
+}
@@ -0,0 +1,62 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Writes seed corpora for the fuzz targets in `fuzz/`.
+//!
+//! Usage: `cargo run --features fuzzing --bin fuzz_corpus -- <corpus-dir> <model>...`
+//!
+//! Every model's seeds go into the corpus of each target, `<corpus-dir>/yaml`
+//! and `<corpus-dir>/dsl`; inputs in the other format are quickly rejected
+//! and do no harm.
+
+use event_modeler_core::fuzzing::seed_corpus;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// The fuzz targets defined in `fuzz/Cargo.toml`.
+const TARGETS: [&str; 2] = ["yaml", "dsl"];
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let Some(corpus) = args.next().map(PathBuf::from) else {
+        eprintln!("Usage: fuzz_corpus <corpus-dir> <model>...");
+        return ExitCode::FAILURE;
+    };
+    let models: Vec<PathBuf> = args.map(PathBuf::from).collect();
+    if models.is_empty() {
+        eprintln!("Usage: fuzz_corpus <corpus-dir> <model>...");
+        return ExitCode::FAILURE;
+    }
+
+    match write_corpus(&corpus, &models) {
+        Ok(count) => {
+            println!("Wrote {count} seeds to {}", corpus.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Writes the seeds of every model into the corpus of every target and
+/// returns how many seeds each target got.
+fn write_corpus(corpus: &Path, models: &[PathBuf]) -> std::io::Result<usize> {
+    let mut count = 0;
+    for model in models {
+        let content = std::fs::read_to_string(model)?;
+        let stem = model
+            .file_stem()
+            .map_or_else(|| "model".into(), |stem| stem.to_string_lossy());
+        for (index, seed) in seed_corpus(&content).iter().enumerate() {
+            for target in TARGETS {
+                let dir = corpus.join(target);
+                std::fs::create_dir_all(&dir)?;
+                std::fs::write(dir.join(format!("{stem}-{index}")), seed)?;
+            }
+            count += 1;
+        }
+    }
+    Ok(count)
+}
@@ -0,0 +1,61 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Writes a synthetic model for stress-testing the renderer.
+//!
+//! Usage: `cargo run --bin stress_model -- [--entities 1000] [--seed <n>] [-o <model.eventmodel>]`
+//!
+//! The model is printed to standard output unless `-o` names a file.
+
+use event_modeler_core::stress::synthetic_model;
+use std::process::ExitCode;
+
+/// Entities in the model unless `--entities` says otherwise.
+const DEFAULT_ENTITIES: usize = 1000;
+
+const USAGE: &str = "Usage: stress_model [--entities 1000] [--seed <n>] [-o <model.eventmodel>]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut entities = DEFAULT_ENTITIES;
+    let mut seed = 0;
+    let mut output = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        let value = args.get(i + 1);
+        match (args[i].as_str(), value) {
+            ("--entities", Some(value)) => match value.parse() {
+                Ok(count) => entities = count,
+                Err(_) => return fail(&format!("Invalid --entities: {value}")),
+            },
+            ("--seed", Some(value)) => match value.parse() {
+                Ok(value) => seed = value,
+                Err(_) => return fail(&format!("Invalid --seed: {value}")),
+            },
+            ("-o", Some(value)) => output = Some(value.clone()),
+            _ => return fail(USAGE),
+        }
+        i += 2;
+    }
+
+    let yaml = synthetic_model(entities, seed);
+    match output {
+        Some(path) => match std::fs::write(&path, yaml) {
+            Ok(()) => {
+                println!("Wrote a model of {entities} entities to {path}");
+                ExitCode::SUCCESS
+            }
+            Err(e) => fail(&format!("Error: {e}")),
+        },
+        None => {
+            print!("{yaml}");
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+fn fail(message: &str) -> ExitCode {
+    eprintln!("{message}");
+    ExitCode::FAILURE
+}
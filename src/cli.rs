@@ -4,6 +4,9 @@
 //! type-safe constructs. All path validation happens at parse time,
 //! ensuring that the rest of the application works with valid paths.
 
+use crate::event_model::roles::PlacementPolicy;
+use crate::infrastructure::parsing::yaml_parser::{UnknownKeyPolicy, YamlParseError};
+use crate::infrastructure::progress::{Progress, ProgressEvent, ProgressStage};
 use crate::infrastructure::types::{
     AnyFile, Directory, EventModelFile, Exists, File, MaybeExists, NonEmpty, PathBuilder,
     Port as ValidatedPort, TypedPath,
@@ -11,6 +14,10 @@ use crate::infrastructure::types::{
 use nutype::nutype;
 use std::env;
 use std::path::PathBuf;
+use std::time::Duration;
+
+#[cfg(feature = "pdf")]
+use crate::export::{pdf::PageSize, poster::PosterOptions};
 
 /// The main CLI structure containing the command to execute.
 #[derive(Debug, Clone)]
@@ -22,12 +29,46 @@ pub struct Cli {
 /// Available commands for the Event Modeler CLI.
 #[derive(Debug, Clone)]
 pub enum Command {
-    /// Render an event model to SVG/PDF.
-    Render(RenderCommand),
+    /// Render an event model to SVG, PDF, PNG, and other formats.
+    Render(Box<RenderCommand>),
     /// Watch a directory for changes and auto-render.
     Watch(WatchCommand),
     /// Validate an event model file without rendering.
     Validate(ValidateCommand),
+    /// Combine models split across files into one.
+    Merge(MergeCommand),
+    /// Replace a model's domain names with neutral ones for sharing.
+    Anonymize(AnonymizeCommand),
+    /// Rename an entity and its references, keeping the file's comments.
+    Rename(RenameCommand),
+    /// Print a report about a model.
+    Report(ReportCommand),
+    /// List the entities whose names contain some text.
+    Find(FindCommand),
+    /// Generate code from a model.
+    Codegen(CodegenCommand),
+    /// Export a model for other tools.
+    #[cfg(feature = "sqlite")]
+    Export(ExportCommand),
+    /// Build a documentation site for a model.
+    #[cfg(feature = "html")]
+    Docs(DocsCommand),
+    /// Write a storyboard of a model's screens and the steps between them.
+    #[cfg(feature = "html")]
+    Storyboard(StoryboardCommand),
+    /// Render the diagrams the pages of an MkDocs site ask for.
+    Mkdocs(MkdocsCommand),
+    /// Write a status badge summarizing a model.
+    Badge(BadgeCommand),
+    /// Compare a rendered diagram against a reference image.
+    #[cfg(feature = "png")]
+    Compare(CompareCommand),
+    /// Write a starter model from event and command names.
+    Scaffold(ScaffoldCommand),
+    /// Build slices interactively in the terminal.
+    Wizard(WizardCommand),
+    /// Serve diagrams over HTTP.
+    Serve(ServeCommand),
 }
 
 /// Command to render an event model file to various output formats.
@@ -48,35 +89,439 @@ pub struct WatchCommand {
     pub serve_port: Option<ServePort>,
 }
 
-/// Command to validate an event model file.
+/// Command to validate event model files without rendering them.
 #[derive(Debug, Clone)]
 pub struct ValidateCommand {
-    /// The input event model file to validate (must exist with .eventmodel extension).
+    /// The event model files to validate (must exist with .eventmodel extension).
+    pub inputs: NonEmpty<TypedPath<EventModelFile, File, Exists>>,
+    /// Files of definitions shared by the models, read once for all of them.
+    pub shared: Vec<TypedPath<AnyFile, File, Exists>>,
+    /// Whether to print the findings as JSON rather than Markdown.
+    pub json: bool,
+}
+
+/// Command to merge several model files into one.
+#[derive(Debug, Clone)]
+pub struct MergeCommand {
+    /// The model files to merge, in priority order: the first supplies the
+    /// workflow name and version.
+    pub inputs: NonEmpty<TypedPath<AnyFile, File, Exists>>,
+    /// Where to write the merged model; printed to standard output if unset.
+    pub output: Option<MergeOutput>,
+    /// Whether unrecognized YAML keys in the inputs fail the merge or only warn.
+    pub unknown_keys: UnknownKeyPolicy,
+}
+
+/// Command to anonymize a model so it can be shared.
+#[derive(Debug, Clone)]
+pub struct AnonymizeCommand {
+    /// The model file to anonymize.
+    pub input: TypedPath<AnyFile, File, Exists>,
+    /// Where to write the anonymized model; printed to standard output if unset.
+    pub output: Option<MergeOutput>,
+    /// Whether unrecognized YAML keys in the input fail or only warn.
+    pub unknown_keys: UnknownKeyPolicy,
+}
+
+/// Command to rename an entity throughout a model file.
+#[derive(Debug, Clone)]
+pub struct RenameCommand {
+    /// The model file declaring the entity.
+    pub input: TypedPath<AnyFile, File, Exists>,
+    /// The entity's current name.
+    pub from: String,
+    /// The entity's new name.
+    pub to: String,
+    /// Where to write the renamed model; the input is rewritten if unset.
+    pub output: Option<MergeOutput>,
+}
+
+/// The file a merged model is written to.
+#[derive(Debug, Clone)]
+pub struct MergeOutput {
+    /// Directory to write the file to (parent must exist).
+    pub directory: TypedPath<AnyFile, Directory, MaybeExists>,
+    /// Name of the file within `directory`.
+    pub file_name: String,
+}
+
+impl MergeOutput {
+    /// Returns the full path of the file to write.
+    pub fn path(&self) -> PathBuf {
+        self.directory.as_path_buf().join(&self.file_name)
+    }
+}
+
+/// Command to print a report about an event model.
+#[derive(Debug, Clone)]
+pub struct ReportCommand {
+    /// The report to print.
+    pub kind: ReportKind,
+    /// The input event model file.
+    pub input: TypedPath<EventModelFile, File, Exists>,
+    /// Whether to print the report as JSON rather than text.
+    pub json: bool,
+    /// Whether to print the report as CSV rather than Markdown.
+    pub csv: bool,
+    /// Whether to print the report as HTML rather than Markdown.
+    pub html: bool,
+    /// How many of the latest commits the history report covers.
+    pub last: usize,
+}
+
+/// Reports that can be printed about a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportKind {
+    /// Entities per owner, plus entities missing an owner.
+    Owners,
+    /// Stated volumes of events and commands, busiest first.
+    Scale,
+    /// Fields tagged as PII and the projections, queries, and views they reach.
+    Pii,
+    /// Events, writers, and consumers of each stream, as Markdown or JSON.
+    Streams,
+    /// What each test scenario changes on the read side, and the scenario
+    /// data no projection carries.
+    Simulation,
+    /// Size and test coverage of the model at each of its recent commits.
+    History,
+    /// Entity and field names with their descriptions, types, and uses, as
+    /// Markdown or HTML.
+    Glossary,
+    /// Bounded contexts and the events shared between them, as Markdown or
+    /// JSON.
+    Contexts,
+    /// Slice estimates added up per owner and for the workflow, as Markdown
+    /// or JSON.
+    Estimates,
+    /// Share of slices, and of story points, done, as Markdown or JSON.
+    Progress,
+    /// The Event Modeling pattern of each slice, as Markdown or JSON.
+    Patterns,
+    /// Events and commands lacking the fields the model's conventions
+    /// require, as Markdown or JSON.
+    Conventions,
+}
+
+impl ReportKind {
+    /// Parses a report name as accepted by `report` (e.g. `owners`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "owners" => Some(Self::Owners),
+            "scale" => Some(Self::Scale),
+            "pii" => Some(Self::Pii),
+            "streams" => Some(Self::Streams),
+            "simulation" => Some(Self::Simulation),
+            "history" => Some(Self::History),
+            "glossary" => Some(Self::Glossary),
+            "contexts" => Some(Self::Contexts),
+            "estimates" => Some(Self::Estimates),
+            "progress" => Some(Self::Progress),
+            "patterns" => Some(Self::Patterns),
+            "conventions" => Some(Self::Conventions),
+            _ => None,
+        }
+    }
+}
+
+/// Command to generate code from an event model.
+#[derive(Debug, Clone)]
+pub struct CodegenCommand {
+    /// What to generate.
+    pub target: CodegenTarget,
+    /// The input event model file.
+    pub input: TypedPath<EventModelFile, File, Exists>,
+    /// Where to write the generated code; printed to standard output if unset.
+    pub output: Option<MergeOutput>,
+}
+
+/// Code that can be generated from a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodegenTarget {
+    /// Rust test skeletons for the Given/When/Then scenarios of commands.
+    RustTests,
+}
+
+/// Command to export an event model for other tools.
+#[cfg(feature = "sqlite")]
+#[derive(Debug, Clone)]
+pub struct ExportCommand {
+    /// What to export to.
+    pub target: ExportTarget,
+    /// The input event model file.
+    pub input: TypedPath<EventModelFile, File, Exists>,
+    /// Where to write the export.
+    pub output: MergeOutput,
+}
+
+/// Command to build a static HTML documentation site for an event model.
+#[cfg(feature = "html")]
+#[derive(Debug, Clone)]
+pub struct DocsCommand {
+    /// The input event model file.
+    pub input: TypedPath<EventModelFile, File, Exists>,
+    /// The directory to write the site into, created if missing.
+    pub output: PathBuf,
+}
+
+/// Command to write a model's storyboard as an HTML page.
+#[cfg(feature = "html")]
+#[derive(Debug, Clone)]
+pub struct StoryboardCommand {
+    /// The input event model file.
+    pub input: TypedPath<EventModelFile, File, Exists>,
+    /// The HTML file to write.
+    pub output: MergeOutput,
+}
+
+/// Command to render the diagrams placeholders in MkDocs pages ask for and
+/// replace the placeholders with images.
+#[derive(Debug, Clone)]
+pub struct MkdocsCommand {
+    /// The docs directory holding the pages.
+    pub docs: PathBuf,
+    /// Where to write the diagrams, relative to the docs directory.
+    pub assets: PathBuf,
+}
+
+/// Command to find entities by part of their name.
+#[derive(Debug, Clone)]
+pub struct FindCommand {
+    /// The input event model file.
+    pub input: TypedPath<EventModelFile, File, Exists>,
+    /// Text the entity names must contain, ignoring case.
+    pub query: String,
+    /// Where to write the diagram with the matching entities highlighted, if
+    /// anywhere.
+    pub render: Option<PathBuf>,
+}
+
+/// Command to write a README badge summarizing a model's health.
+#[derive(Debug, Clone)]
+pub struct BadgeCommand {
+    /// The input event model file.
+    pub input: TypedPath<EventModelFile, File, Exists>,
+    /// Where to write the badge SVG.
+    pub output: PathBuf,
+}
+
+/// Command to compare a rendered SVG against a reference image.
+#[cfg(feature = "png")]
+#[derive(Debug, Clone)]
+pub struct CompareCommand {
+    /// The rendered SVG.
+    pub rendered: TypedPath<AnyFile, File, Exists>,
+    /// The reference image, a PNG or an SVG.
+    pub reference: TypedPath<AnyFile, File, Exists>,
+    /// The share of pixels allowed to differ perceptibly.
+    pub threshold: crate::compare::DifferenceThreshold,
+    /// Where to write the heat map of differences.
+    pub heat_map: MergeOutput,
+}
+
+/// Command to write a starter model from an event-storming word list.
+#[derive(Debug, Clone)]
+pub struct ScaffoldCommand {
+    /// The workflow name and the event and command names to declare.
+    pub words: crate::codegen::scaffold::WordList,
+    /// Where to write the model; printed to standard output if unset.
+    pub output: Option<MergeOutput>,
+}
+
+/// Command to build slices of an event model interactively.
+#[derive(Debug, Clone)]
+pub struct WizardCommand {
+    /// The event model file the slices are written back to.
     pub input: TypedPath<EventModelFile, File, Exists>,
 }
 
+/// Command to serve diagrams over HTTP the way Kroki does.
+#[derive(Debug, Clone)]
+pub struct ServeCommand {
+    /// Address to listen on, e.g. `127.0.0.1:8000`.
+    pub address: String,
+}
+
+/// Formats a model can be exported to.
+#[cfg(feature = "sqlite")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportTarget {
+    /// An SQLite database of the model's entities, fields, slices, and tests.
+    Sqlite,
+}
+
 /// Options for rendering event models.
 #[derive(Debug, Clone)]
 pub struct RenderOptions {
-    /// Output formats (at least one required).
-    pub formats: NonEmpty<OutputFormat>,
+    /// Files to produce (at least one required). All outputs share a single
+    /// parse, layout, and routing pass.
+    pub outputs: NonEmpty<OutputTarget>,
     /// Visual style for rendering.
     pub style: RenderStyle,
     /// Whether to include documentation links in the output.
     pub include_links: IncludeLinks,
-    /// Directory to write output files (parent must exist).
-    pub output_dir: TypedPath<AnyFile, Directory, MaybeExists>,
-    /// Optional specific output filename (if not provided, uses input filename).
-    pub output_filename: Option<String>,
+    /// Whether unrecognized YAML keys fail the render or only warn.
+    pub unknown_keys: UnknownKeyPolicy,
+    /// Whether entities in swimlanes of the wrong role fail the render or only warn.
+    pub role_placement: PlacementPolicy,
+    /// Name of the profile overlay to apply, rendering the base model if unset.
+    pub profile: Option<String>,
+    /// Feature flags to enable; content depending on any other feature is left out.
+    pub features: Vec<String>,
+    /// Whether to badge entities with their owners and add an ownership legend.
+    pub show_owners: bool,
+    /// Whether to badge slice headers with the slices' estimates.
+    pub show_estimates: bool,
+    /// Whether to tint slice columns by the pattern each slice follows.
+    pub tint_patterns: bool,
+    /// Whether to check `implemented_by` references against the filesystem.
+    pub verify_links: bool,
+    /// Whether to look up the open or closed state of slice issues.
+    pub verify_issues: bool,
+    /// Whether to draw each command's error outcomes beneath it.
+    pub show_errors: bool,
+    /// Whether scenario tables show example values in place of placeholders.
+    pub show_examples: bool,
+    /// Whether to fade entities no view or automation leads to.
+    pub dim_unreachable: bool,
+    /// Slice whose connections are emphasized, fading all others.
+    pub focus: Option<String>,
+    /// Whether to overlay the layout grid on the diagram.
+    pub debug_grid: bool,
+    /// Pipeline stages to write as JSON beside the first output.
+    pub emit: Vec<PipelineStage>,
+    /// Wall-clock time the render may take before the remaining connectors
+    /// are drawn as straight lines.
+    pub timeout: Option<Duration>,
+    /// Seed for nudging apart overlapping connector badges, or `None` to
+    /// derive one from the model.
+    pub seed: Option<u64>,
+    /// Number of entity orderings to try for fewer and shorter connectors,
+    /// or `None` to skip the search.
+    pub optimize: Option<u32>,
+    /// Whether to print the layout's score: connector length, bends,
+    /// crossings, and area.
+    pub score: bool,
+    /// Whether to trade polish for speed: standard-size boxes, straight
+    /// connectors, and no ordering search or collision checks.
+    pub fast: bool,
+    /// Whether to show a progress bar on standard error while rendering.
+    pub progress: bool,
+    /// Paper the PDF output is printed on as a poster, possibly tiled.
+    #[cfg(feature = "pdf")]
+    pub poster: Option<PosterOptions>,
+    /// Directory each slice is also rendered into on its own, as an SVG and
+    /// a PNG named after the slice.
+    pub per_slice_out: Option<PathBuf>,
+}
+
+/// A single file to produce from a render.
+#[derive(Debug, Clone)]
+pub struct OutputTarget {
+    /// Format to write.
+    pub format: OutputFormat,
+    /// Directory to write the file to (parent must exist).
+    pub directory: TypedPath<AnyFile, Directory, MaybeExists>,
+    /// Name of the file within `directory`.
+    pub file_name: String,
+}
+
+impl OutputTarget {
+    /// Returns the full path of the file to write.
+    pub fn path(&self) -> PathBuf {
+        self.directory.as_path_buf().join(&self.file_name)
+    }
 }
 
 /// Supported output formats for rendered diagrams.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OutputFormat {
     /// Scalable Vector Graphics format.
     Svg,
     /// Portable Document Format.
     Pdf,
+    /// The SVG rasterized to a Portable Network Graphics image.
+    Png,
+    /// Markdown documentation embedding the diagram.
+    Markdown,
+    /// GraphML of the entity/connection graph, for Gephi or yEd.
+    GraphMl,
+    /// Cytoscape JSON of the entity/connection graph.
+    Cytoscape,
+    /// MDX page for Docusaurus, with a JSON props file beside it.
+    Mdx,
+}
+
+impl OutputFormat {
+    /// Parses a format name as accepted by `--formats` (e.g. `svg`, `pdf`, `md`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "svg" => Some(Self::Svg),
+            "pdf" => Some(Self::Pdf),
+            "png" => Some(Self::Png),
+            "md" | "markdown" => Some(Self::Markdown),
+            "graphml" => Some(Self::GraphMl),
+            "cyjs" | "cytoscape" => Some(Self::Cytoscape),
+            "mdx" => Some(Self::Mdx),
+            _ => None,
+        }
+    }
+
+    /// Infers the format from a file path's extension.
+    pub fn from_path(path: &str) -> Option<Self> {
+        PathBuf::from(path)
+            .extension()
+            .and_then(|ext| Self::from_name(&ext.to_string_lossy()))
+    }
+
+    /// Returns the file extension used for this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Svg => "svg",
+            Self::Pdf => "pdf",
+            Self::Png => "png",
+            Self::Markdown => "md",
+            Self::GraphMl => "graphml",
+            Self::Cytoscape => "cyjs",
+            Self::Mdx => "mdx",
+        }
+    }
+}
+
+/// Intermediate results of the render pipeline that `--emit` can write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelineStage {
+    /// The parsed YAML, after profiles and feature flags are applied.
+    Ast,
+    /// The validated domain model.
+    Domain,
+    /// Where swimlanes, slice columns, and entities were placed.
+    Layout,
+    /// The path of every connector.
+    Routes,
+}
+
+impl PipelineStage {
+    /// Parses a stage name as accepted by `--emit` (e.g. `ast`, `routes`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "ast" => Some(Self::Ast),
+            "domain" => Some(Self::Domain),
+            "layout" => Some(Self::Layout),
+            "routes" => Some(Self::Routes),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the stage, used in the emitted file's name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Ast => "ast",
+            Self::Domain => "domain",
+            Self::Layout => "layout",
+            Self::Routes => "routes",
+        }
+    }
 }
 
 /// Visual rendering styles optimized for different environments.
@@ -126,8 +571,106 @@ pub enum Error {
     /// I/O error during file operations.
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
+
+    /// `implemented_by` references that do not resolve (with `--verify-links`).
+    #[error("{0}")]
+    BrokenLinks(#[from] crate::infrastructure::links::BrokenLinks),
+
+    /// A slice's issue could not be looked up (with `--verify-issues`).
+    #[error("{0}")]
+    Issue(#[from] crate::infrastructure::issues::IssueError),
+
+    /// A rendered diagram could not be compared with its reference.
+    #[cfg(feature = "png")]
+    #[error("{0}")]
+    Compare(#[from] crate::compare::CompareError),
+
+    /// A rendered diagram differs from its reference by more than allowed.
+    #[error(
+        "{difference:.2}% of pixels differ from the reference, above the threshold of {threshold:.2}% (heat map: {heat_map})"
+    )]
+    ImagesDiffer {
+        /// Percentage of pixels differing perceptibly.
+        difference: f64,
+        /// Percentage allowed to differ.
+        threshold: f64,
+        /// Where the heat map of differences was written.
+        heat_map: String,
+    },
+
+    /// A file of shared definitions could not be read for `validate`.
+    #[error("{0}")]
+    Validation(#[from] crate::validation::ValidationError),
+
+    /// Some of the models passed to `validate` are invalid.
+    #[error("{invalid} of {total} models are invalid")]
+    InvalidModels {
+        /// Number of invalid models.
+        invalid: usize,
+        /// Number of models validated.
+        total: usize,
+    },
+
+    /// A lint plugin could not be loaded or run.
+    #[cfg(feature = "plugins")]
+    #[error("{0}")]
+    Plugin(#[from] crate::infrastructure::plugins::PluginError),
+
+    /// A PNG could not be produced.
+    #[cfg(feature = "png")]
+    #[error("PNG export error: {0}")]
+    Png(#[from] crate::export::PngExportError),
+
+    /// A PDF could not be produced.
+    #[cfg(feature = "pdf")]
+    #[error("PDF export error: {0}")]
+    Pdf(#[from] crate::export::PdfExportError),
+
+    /// The diagram server could not start.
+    #[error("{0}")]
+    Server(#[from] crate::server::ServerError),
+
+    /// The slice wizard could not run or write its slices.
+    #[error("{0}")]
+    Wizard(#[from] crate::wizard::WizardError),
+
+    /// Entities placed in swimlanes whose role does not suit them.
+    #[error("{0}\n(pass --lenient to draw them anyway)")]
+    MisplacedEntities(#[from] crate::event_model::roles::MisplacedEntities),
 }
 
+/// Share of pixels allowed to differ in `compare` unless `--threshold` says otherwise.
+#[cfg(feature = "png")]
+const DEFAULT_COMPARE_THRESHOLD: f64 = 0.02;
+
+/// Host `serve` listens on unless `--host` says otherwise.
+const DEFAULT_SERVE_HOST: &str = "127.0.0.1";
+
+/// Port `serve` listens on unless `--port` says otherwise; Kroki's own.
+const DEFAULT_SERVE_PORT: u16 = 8000;
+
+/// Number of commits the history report covers unless `--last` says otherwise.
+const DEFAULT_HISTORY_LENGTH: usize = 20;
+
+/// Usage text shown when the arguments cannot be parsed.
+const USAGE: &str = "Usage: event_modeler <input.eventmodel> [-o <output.svg|.pdf|.png|.md|.graphml|.cyjs|.mdx>]... [--formats svg,pdf,png,md,graphml,cyjs,mdx] [--dark] [--lenient] [--profile <name>] [--features a,b] [--owners] [--estimates] [--patterns] [--errors] [--examples] [--dim-unreachable] [--verify-links] [--verify-issues] [--focus <slice>] [--debug-grid] [--emit ast,domain,layout,routes] [--timeout 30s] [--seed <n>] [--optimize <iterations>] [--score] [--fast] [--progress] [--per-slice-out <dir>] [--poster a0 [--tile a3] [--overlap 10mm]]
+       event_modeler validate <model>... [--shared <vocabulary.yaml>]... [--json]
+       event_modeler merge <model>... [-o <combined.yaml>] [--lenient]
+       event_modeler anonymize <model> [-o <demo.yaml>] [--lenient]
+       event_modeler rename <model> <OldName> <NewName> [-o <renamed.yaml>]
+       event_modeler report owners|scale|pii|streams|simulation|history|glossary|contexts|estimates|progress|patterns|conventions <input.eventmodel> [--json] [--csv] [--html] [--last <n>]
+       event_modeler find <input.eventmodel> <text> [--render <highlighted.svg>]
+       event_modeler codegen tests --lang rust <input.eventmodel> [-o <tests.rs>]
+       event_modeler export sqlite <input.eventmodel> -o <model.db>
+       event_modeler docs build <input.eventmodel> -o <site/>
+       event_modeler docs storyboard <input.eventmodel> -o <storyboard.html>
+       event_modeler docs mkdocs <docs/> [--assets <assets/eventmodels>]
+       event_modeler badge <input.eventmodel> -o <badge.svg>
+       event_modeler compare <rendered.svg> <reference.png|.svg> [--threshold 0.02] [-o <diff.png>]
+       event_modeler scaffold --events <A,B> --commands <C,D> [--workflow <name>] [-o <model.eventmodel>]
+       event_modeler wizard <input.eventmodel>
+       event_modeler serve --kroki [--host 127.0.0.1] [--port 8000]";
+
 impl Cli {
     /// Parse command line arguments into a CLI structure.
     pub fn from_args() -> Result<Self> {
@@ -135,80 +678,292 @@ impl Cli {
 
         // Basic argument parsing - for now just support: event_modeler input.eventmodel -o output.svg
         if args.len() < 2 {
-            return Err(Error::InvalidArguments(
-                "Usage: event_modeler <input.eventmodel> [-o <output.svg>] [--dark]".to_string(),
-            ));
+            return Err(Error::InvalidArguments(USAGE.to_string()));
+        }
+
+        if args[1] == "validate" {
+            return parse_validate_args(&args[2..]);
+        }
+        if args[1] == "merge" {
+            return parse_merge_args(&args[2..]);
+        }
+        if args[1] == "anonymize" {
+            return parse_anonymize_args(&args[2..]);
+        }
+        if args[1] == "rename" {
+            return parse_rename_args(&args[2..]);
+        }
+        if args[1] == "report" {
+            return parse_report_args(&args[2..]);
+        }
+        if args[1] == "find" {
+            return parse_find_args(&args[2..]);
+        }
+        if args[1] == "codegen" {
+            return parse_codegen_args(&args[2..]);
+        }
+        if args[1] == "export" {
+            #[cfg(feature = "sqlite")]
+            return parse_export_args(&args[2..]);
+            #[cfg(not(feature = "sqlite"))]
+            return Err(feature_disabled("sqlite", "The export command"));
+        }
+        if args[1] == "docs" {
+            #[cfg(feature = "html")]
+            return parse_docs_args(&args[2..]);
+            #[cfg(not(feature = "html"))]
+            return Err(feature_disabled("html", "The docs command"));
+        }
+        if args[1] == "badge" {
+            return parse_badge_args(&args[2..]);
+        }
+        if args[1] == "compare" {
+            #[cfg(feature = "png")]
+            return parse_compare_args(&args[2..]);
+            #[cfg(not(feature = "png"))]
+            return Err(feature_disabled("png", "The compare command"));
+        }
+        if args[1] == "scaffold" {
+            return parse_scaffold_args(&args[2..]);
+        }
+        if args[1] == "serve" {
+            return parse_serve_args(&args[2..]);
+        }
+        if args[1] == "wizard" {
+            let [input] = &args[2..] else {
+                return Err(Error::InvalidArguments(USAGE.to_string()));
+            };
+            let input = PathBuilder::parse_event_model_file(PathBuf::from(input))
+                .map_err(|e| Error::InvalidPath(format!("Input file error: {e}")))?;
+            return Ok(Cli {
+                command: Command::Wizard(WizardCommand { input }),
+            });
         }
 
         let input_path = &args[1];
-        let mut output_path = None;
+        let mut output_paths = Vec::new();
+        let mut requested_formats = Vec::new();
         let mut use_dark_theme = false;
+        let mut unknown_keys = UnknownKeyPolicy::Reject;
+        let mut role_placement = PlacementPolicy::Reject;
+        let mut profile = None;
+        let mut features = Vec::new();
+        let mut show_owners = false;
+        let mut show_estimates = false;
+        let mut tint_patterns = false;
+        let mut show_errors = false;
+        let mut show_examples = false;
+        let mut dim_unreachable = false;
+        let mut verify_links = false;
+        let mut verify_issues = false;
+        let mut focus = None;
+        let mut debug_grid = false;
+        let mut emit = Vec::new();
+        let mut timeout = None;
+        let mut seed = None;
+        let mut score = false;
+        let mut fast = false;
+        let mut progress = false;
+        let mut optimize = None;
+        let mut poster = None;
+        let mut tile = None;
+        let mut overlap = None;
+        let mut per_slice_out = None;
 
-        // Parse output flag
+        // Parse flags
         let mut i = 2;
         while i < args.len() {
             if args[i] == "-o" && i + 1 < args.len() {
-                output_path = Some(args[i + 1].clone());
+                output_paths.push(args[i + 1].clone());
+                i += 2;
+            } else if args[i] == "--formats" && i + 1 < args.len() {
+                for name in args[i + 1]
+                    .split(',')
+                    .filter(|name| !name.trim().is_empty())
+                {
+                    let format = OutputFormat::from_name(name).ok_or_else(|| {
+                        Error::InvalidArguments(format!("Unknown output format: {name}"))
+                    })?;
+                    requested_formats.push(format);
+                }
                 i += 2;
             } else if args[i] == "--dark" {
                 use_dark_theme = true;
                 i += 1;
+            } else if args[i] == "--lenient" {
+                unknown_keys = UnknownKeyPolicy::Warn;
+                role_placement = PlacementPolicy::Warn;
+                i += 1;
+            } else if args[i] == "--owners" {
+                show_owners = true;
+                i += 1;
+            } else if args[i] == "--estimates" {
+                show_estimates = true;
+                i += 1;
+            } else if args[i] == "--patterns" {
+                tint_patterns = true;
+                i += 1;
+            } else if args[i] == "--errors" {
+                show_errors = true;
+                i += 1;
+            } else if args[i] == "--emit" && i + 1 < args.len() {
+                for name in args[i + 1]
+                    .split(',')
+                    .filter(|name| !name.trim().is_empty())
+                {
+                    let stage = PipelineStage::from_name(name).ok_or_else(|| {
+                        Error::InvalidArguments(format!(
+                            "Unknown stage for --emit: {name}; expected ast, domain, layout, or routes"
+                        ))
+                    })?;
+                    if !emit.contains(&stage) {
+                        emit.push(stage);
+                    }
+                }
+                i += 2;
+            } else if args[i] == "--timeout" && i + 1 < args.len() {
+                timeout = Some(parse_timeout(&args[i + 1]).ok_or_else(|| {
+                    Error::InvalidArguments(format!(
+                        "Invalid --timeout: {}; expected a number with a unit, e.g. 30s, 500ms, or 2m",
+                        args[i + 1]
+                    ))
+                })?);
+                i += 2;
+            } else if args[i] == "--seed" && i + 1 < args.len() {
+                seed = Some(args[i + 1].parse().map_err(|_| {
+                    Error::InvalidArguments(format!(
+                        "Invalid --seed: {}; expected a non-negative integer",
+                        args[i + 1]
+                    ))
+                })?);
+                i += 2;
+            } else if (args[i] == "--poster" || args[i] == "--tile") && i + 1 < args.len() {
+                let paper = args[i + 1].clone();
+                if args[i] == "--poster" {
+                    poster = Some(paper);
+                } else {
+                    tile = Some(paper);
+                }
+                i += 2;
+            } else if args[i] == "--overlap" && i + 1 < args.len() {
+                overlap = Some(
+                    args[i + 1]
+                        .strip_suffix("mm")
+                        .and_then(|millimeters| millimeters.parse::<f64>().ok())
+                        .filter(|millimeters| millimeters.is_finite() && *millimeters >= 0.0)
+                        .map(crate::diagram::Length::from_millimeters)
+                        .ok_or_else(|| {
+                            Error::InvalidArguments(format!(
+                                "Invalid --overlap: {}; expected millimeters, e.g. 10mm",
+                                args[i + 1]
+                            ))
+                        })?,
+                );
+                i += 2;
+            } else if args[i] == "--optimize" && i + 1 < args.len() {
+                let iterations = args[i + 1].parse().map_err(|_| {
+                    Error::InvalidArguments(format!(
+                        "Invalid --optimize: {}; expected a number of iterations, e.g. 5000",
+                        args[i + 1]
+                    ))
+                })?;
+                optimize = Some(iterations);
+                i += 2;
+            } else if args[i] == "--score" {
+                score = true;
+                i += 1;
+            } else if args[i] == "--fast" {
+                fast = true;
+                i += 1;
+            } else if args[i] == "--progress" {
+                progress = true;
+                i += 1;
+            } else if args[i] == "--per-slice-out" && i + 1 < args.len() {
+                per_slice_out = Some(PathBuf::from(&args[i + 1]));
+                i += 2;
+            } else if args[i] == "--debug-grid" {
+                debug_grid = true;
+                i += 1;
+            } else if args[i] == "--examples" {
+                show_examples = true;
+                i += 1;
+            } else if args[i] == "--dim-unreachable" {
+                dim_unreachable = true;
+                i += 1;
+            } else if args[i] == "--verify-links" {
+                verify_links = true;
+                i += 1;
+            } else if args[i] == "--verify-issues" {
+                verify_issues = true;
+                i += 1;
+            } else if args[i] == "--focus" && i + 1 < args.len() {
+                focus = Some(args[i + 1].clone());
+                i += 2;
+            } else if args[i] == "--profile" && i + 1 < args.len() {
+                profile = Some(args[i + 1].clone());
+                i += 2;
+            } else if args[i] == "--features" && i + 1 < args.len() {
+                features.extend(
+                    args[i + 1]
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty())
+                        .map(str::to_string),
+                );
+                i += 2;
             } else {
                 i += 1;
             }
         }
 
-        // Determine output directory, format, and filename
-        let (output_dir, format, output_filename) = if let Some(path) = output_path {
-            let path_buf = PathBuf::from(&path);
-            let dir = path_buf
-                .parent()
-                .map(|p| p.to_path_buf())
-                .unwrap_or_else(|| PathBuf::from("."));
-
-            let format = if path.ends_with(".svg") {
-                OutputFormat::Svg
-            } else if path.ends_with(".pdf") {
-                OutputFormat::Pdf
-            } else {
-                OutputFormat::Svg // Default to SVG
-            };
-
-            let filename = path_buf
-                .file_name()
-                .map(|n| n.to_string_lossy().to_string());
-
-            (dir, format, filename)
-        } else {
-            // Default to current directory and SVG
-            (PathBuf::from("."), OutputFormat::Svg, None)
-        };
-
         // Parse the input file path
         let input = PathBuilder::parse_event_model_file(PathBuf::from(input_path))
             .map_err(|e| Error::InvalidPath(format!("Input file error: {e}")))?;
 
-        // Parse the output directory
-        let output_dir = PathBuilder::parse_output_directory(output_dir)
-            .map_err(|e| Error::InvalidPath(format!("Output directory error: {e}")))?;
-
-        // Create formats list with the determined format
-        let formats = NonEmpty::singleton(format);
+        let outputs = parse_output_targets(&input, &output_paths, &requested_formats)?;
+        #[cfg(feature = "pdf")]
+        let poster = poster_options(poster.as_deref(), tile.as_deref(), overlap, &outputs)?;
+        #[cfg(not(feature = "pdf"))]
+        if poster.is_some() || tile.is_some() || overlap.is_some() {
+            return Err(feature_disabled("pdf", "--poster"));
+        }
 
-        let command = Command::Render(RenderCommand {
+        let command = Command::Render(Box::new(RenderCommand {
             input,
             options: RenderOptions {
-                formats,
+                outputs,
                 style: if use_dark_theme {
                     RenderStyle::GithubDark
                 } else {
                     RenderStyle::GithubLight
                 },
                 include_links: IncludeLinks::new(false), // Default to no links
-                output_dir,
-                output_filename,
+                unknown_keys,
+                role_placement,
+                profile,
+                features,
+                show_owners,
+                show_estimates,
+                tint_patterns,
+                verify_links,
+                verify_issues,
+                show_errors,
+                show_examples,
+                dim_unreachable,
+                focus,
+                debug_grid,
+                emit,
+                timeout,
+                seed,
+                optimize,
+                score,
+                fast,
+                progress,
+                #[cfg(feature = "pdf")]
+                poster,
+                per_slice_out,
             },
-        });
+        }));
 
         Ok(Cli { command })
     }
@@ -216,74 +971,1802 @@ impl Cli {
     /// Execute the CLI command.
     pub fn execute(self) -> Result<()> {
         match self.command {
-            Command::Render(cmd) => execute_render(cmd),
+            Command::Render(cmd) => execute_render(*cmd),
             Command::Watch(_) => todo!("Watch command not implemented"),
-            Command::Validate(_) => todo!("Validate command not implemented"),
+            Command::Validate(cmd) => execute_validate(cmd),
+            Command::Merge(cmd) => execute_merge(cmd),
+            Command::Anonymize(cmd) => execute_anonymize(cmd),
+            Command::Rename(cmd) => execute_rename(cmd),
+            Command::Find(cmd) => execute_find(cmd),
+            Command::Report(cmd) => execute_report(cmd),
+            Command::Codegen(cmd) => execute_codegen(cmd),
+            #[cfg(feature = "sqlite")]
+            Command::Export(cmd) => execute_export(cmd),
+            #[cfg(feature = "html")]
+            Command::Docs(cmd) => execute_docs(cmd),
+            #[cfg(feature = "html")]
+            Command::Storyboard(cmd) => execute_storyboard(cmd),
+            Command::Mkdocs(cmd) => execute_mkdocs(cmd),
+            Command::Badge(cmd) => execute_badge(cmd),
+            #[cfg(feature = "png")]
+            Command::Compare(cmd) => execute_compare(cmd),
+            Command::Scaffold(cmd) => execute_scaffold(cmd),
+            Command::Wizard(cmd) => execute_wizard(cmd),
+            Command::Serve(cmd) => execute_serve(cmd),
         }
     }
 }
 
-/// Execute a render command.
-fn execute_render(cmd: RenderCommand) -> Result<()> {
-    use std::fs;
-    use std::io::Write;
+/// Parses the arguments following `report`.
+fn parse_report_args(args: &[String]) -> Result<Cli> {
+    let mut json = false;
+    let mut csv = false;
+    let mut html = false;
+    let mut last = None;
+    let mut positional = Vec::new();
 
-    // 1. Read the input file
-    let input_content = fs::read_to_string(cmd.input.as_path_buf())?;
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--json" {
+            json = true;
+            i += 1;
+        } else if args[i] == "--csv" {
+            csv = true;
+            i += 1;
+        } else if args[i] == "--html" {
+            html = true;
+            i += 1;
+        } else if args[i] == "--last" && i + 1 < args.len() {
+            let count = args[i + 1]
+                .parse::<usize>()
+                .ok()
+                .filter(|count| *count > 0)
+                .ok_or_else(|| {
+                    Error::InvalidArguments(format!(
+                        "--last needs a positive number of commits, not {}",
+                        args[i + 1]
+                    ))
+                })?;
+            last = Some(count);
+            i += 2;
+        } else {
+            positional.push(&args[i]);
+            i += 1;
+        }
+    }
 
-    // 2. Parse the YAML event model
-    let yaml_model = crate::infrastructure::parsing::yaml_parser::parse_yaml(&input_content)
-        .map_err(|e| Error::InvalidArguments(format!("YAML parse error: {e}")))?;
+    let [name, input] = positional[..] else {
+        return Err(Error::InvalidArguments(USAGE.to_string()));
+    };
+    let kind = ReportKind::from_name(name)
+        .ok_or_else(|| Error::InvalidArguments(format!("Unknown report: {name}")))?;
+    if json
+        && !matches!(
+            kind,
+            ReportKind::Streams
+                | ReportKind::Contexts
+                | ReportKind::Estimates
+                | ReportKind::Progress
+                | ReportKind::Patterns
+                | ReportKind::Conventions
+        )
+    {
+        return Err(Error::InvalidArguments(format!(
+            "The {name} report has no JSON form"
+        )));
+    }
+    if csv && kind != ReportKind::History {
+        return Err(Error::InvalidArguments(format!(
+            "The {name} report has no CSV form"
+        )));
+    }
+    if html && kind != ReportKind::Glossary {
+        return Err(Error::InvalidArguments(format!(
+            "The {name} report has no HTML form"
+        )));
+    }
+    if last.is_some() && kind != ReportKind::History {
+        return Err(Error::InvalidArguments(format!(
+            "The {name} report does not cover past commits"
+        )));
+    }
+    let input = PathBuilder::parse_event_model_file(PathBuf::from(input))
+        .map_err(|e| Error::InvalidPath(format!("Input file error: {e}")))?;
 
-    // 3. Convert YAML to domain types
-    let domain_model =
-        crate::infrastructure::parsing::yaml_converter::convert_yaml_to_domain(yaml_model)
-            .map_err(|e| Error::InvalidArguments(format!("YAML conversion error: {e}")))?;
+    Ok(Cli {
+        command: Command::Report(ReportCommand {
+            kind,
+            input,
+            json,
+            csv,
+            html,
+            last: last.unwrap_or(DEFAULT_HISTORY_LENGTH),
+        }),
+    })
+}
 
-    // 4. Build diagram from domain model
-    let diagram = crate::diagram::build_diagram_from_domain(&domain_model)
-        .map_err(|e| Error::InvalidArguments(format!("Diagram building error: {e}")))?;
+/// Parses the arguments following `merge`.
+fn parse_merge_args(args: &[String]) -> Result<Cli> {
+    let mut input_paths = Vec::new();
+    let mut output_path = None;
+    let mut unknown_keys = UnknownKeyPolicy::Reject;
 
-    println!(
-        "Successfully converted event model: {}",
-        diagram.workflow_title().as_str()
-    );
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "-o" && i + 1 < args.len() {
+            output_path = Some(PathBuf::from(&args[i + 1]));
+            i += 2;
+        } else if args[i] == "--lenient" {
+            unknown_keys = UnknownKeyPolicy::Warn;
+            i += 1;
+        } else {
+            input_paths.push(PathBuf::from(&args[i]));
+            i += 1;
+        }
+    }
 
-    // 5. Render to requested formats
-    for format in cmd.options.formats.iter() {
-        match format {
-            OutputFormat::Svg => {
-                // Render diagram to SVG
-                let svg_doc = crate::diagram::render_to_svg(&diagram)
-                    .map_err(|e| Error::InvalidArguments(format!("SVG rendering error: {e}")))?;
+    let mut inputs = Vec::with_capacity(input_paths.len());
+    for path in input_paths {
+        let display = path.display().to_string();
+        let input = PathBuilder::parse_existing_file(path)
+            .map_err(|e| Error::InvalidPath(format!("Input file error ({display}): {e}")))?;
+        inputs.push(input);
+    }
+    let inputs =
+        NonEmpty::try_from(inputs).map_err(|_| Error::InvalidArguments(USAGE.to_string()))?;
 
-                // Generate output filename
-                let output_filename = if let Some(filename) = &cmd.options.output_filename {
-                    filename.clone()
-                } else {
-                    let input_stem = cmd
-                        .input
-                        .as_path_buf()
-                        .file_stem()
-                        .unwrap_or_default()
-                        .to_string_lossy();
-                    format!("{input_stem}.svg")
-                };
-                let output_path = cmd.options.output_dir.as_path_buf().join(&output_filename);
+    let output = output_path.map(parse_output_file).transpose()?;
 
-                // Write SVG to file
-                let svg_content = svg_doc;
-                let mut file = fs::File::create(&output_path)?;
-                file.write_all(svg_content.as_bytes())?;
+    Ok(Cli {
+        command: Command::Merge(MergeCommand {
+            inputs,
+            output,
+            unknown_keys,
+        }),
+    })
+}
 
-                println!("Generated SVG: {}", output_path.display());
-            }
-            OutputFormat::Pdf => {
-                // PDF export not yet implemented
-                eprintln!("Warning: PDF export not yet implemented");
-            }
+/// Parses the arguments following `anonymize`.
+fn parse_anonymize_args(args: &[String]) -> Result<Cli> {
+    let mut input_paths = Vec::new();
+    let mut output_path = None;
+    let mut unknown_keys = UnknownKeyPolicy::Reject;
+
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "-o" && i + 1 < args.len() {
+            output_path = Some(PathBuf::from(&args[i + 1]));
+            i += 2;
+        } else if args[i] == "--lenient" {
+            unknown_keys = UnknownKeyPolicy::Warn;
+            i += 1;
+        } else {
+            input_paths.push(PathBuf::from(&args[i]));
+            i += 1;
+        }
+    }
+
+    let [path] = &input_paths[..] else {
+        return Err(Error::InvalidArguments(USAGE.to_string()));
+    };
+    let input = PathBuilder::parse_existing_file(path.clone())
+        .map_err(|e| Error::InvalidPath(format!("Input file error: {e}")))?;
+    let output = output_path.map(parse_output_file).transpose()?;
+
+    Ok(Cli {
+        command: Command::Anonymize(AnonymizeCommand {
+            input,
+            output,
+            unknown_keys,
+        }),
+    })
+}
+
+/// Parses the arguments following `rename`.
+fn parse_rename_args(args: &[String]) -> Result<Cli> {
+    let mut positional = Vec::new();
+    let mut output_path = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "-o" && i + 1 < args.len() {
+            output_path = Some(PathBuf::from(&args[i + 1]));
+            i += 2;
+        } else {
+            positional.push(args[i].clone());
+            i += 1;
+        }
+    }
+
+    let [path, from, to] = &positional[..] else {
+        return Err(Error::InvalidArguments(USAGE.to_string()));
+    };
+    let input = PathBuilder::parse_existing_file(PathBuf::from(path))
+        .map_err(|e| Error::InvalidPath(format!("Input file error: {e}")))?;
+    let output = output_path.map(parse_output_file).transpose()?;
+
+    Ok(Cli {
+        command: Command::Rename(RenameCommand {
+            input,
+            from: from.clone(),
+            to: to.clone(),
+            output,
+        }),
+    })
+}
+
+/// Parses the arguments following `validate`.
+fn parse_validate_args(args: &[String]) -> Result<Cli> {
+    let mut input_paths = Vec::new();
+    let mut shared = Vec::new();
+    let mut json = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--shared" && i + 1 < args.len() {
+            let display = args[i + 1].clone();
+            shared.push(
+                PathBuilder::parse_existing_file(PathBuf::from(&args[i + 1])).map_err(|e| {
+                    Error::InvalidPath(format!("Shared file error ({display}): {e}"))
+                })?,
+            );
+            i += 2;
+        } else if args[i] == "--json" {
+            json = true;
+            i += 1;
+        } else {
+            input_paths.push(PathBuf::from(&args[i]));
+            i += 1;
+        }
+    }
+
+    let mut inputs = Vec::with_capacity(input_paths.len());
+    for path in input_paths {
+        let display = path.display().to_string();
+        let input = PathBuilder::parse_event_model_file(path)
+            .map_err(|e| Error::InvalidPath(format!("Input file error ({display}): {e}")))?;
+        inputs.push(input);
+    }
+    let inputs =
+        NonEmpty::try_from(inputs).map_err(|_| Error::InvalidArguments(USAGE.to_string()))?;
+
+    Ok(Cli {
+        command: Command::Validate(ValidateCommand {
+            inputs,
+            shared,
+            json,
+        }),
+    })
+}
+
+/// Parses the arguments following `codegen`.
+fn parse_codegen_args(args: &[String]) -> Result<Cli> {
+    let mut language = None;
+    let mut output_path = None;
+    let mut positional = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--lang" && i + 1 < args.len() {
+            language = Some(args[i + 1].as_str());
+            i += 2;
+        } else if args[i] == "-o" && i + 1 < args.len() {
+            output_path = Some(PathBuf::from(&args[i + 1]));
+            i += 2;
+        } else {
+            positional.push(&args[i]);
+            i += 1;
+        }
+    }
+
+    let [kind, input] = positional[..] else {
+        return Err(Error::InvalidArguments(USAGE.to_string()));
+    };
+    let target = match (kind.as_str(), language) {
+        ("tests", Some("rust")) => CodegenTarget::RustTests,
+        ("tests", Some(language)) => {
+            return Err(Error::InvalidArguments(format!(
+                "Unsupported language for tests: {language} (supported: rust)"
+            )));
+        }
+        ("tests", None) => {
+            return Err(Error::InvalidArguments(
+                "codegen tests needs --lang rust".to_string(),
+            ));
+        }
+        (kind, _) => {
+            return Err(Error::InvalidArguments(format!(
+                "Unknown codegen target: {kind}"
+            )));
+        }
+    };
+    let input = PathBuilder::parse_event_model_file(PathBuf::from(input))
+        .map_err(|e| Error::InvalidPath(format!("Input file error: {e}")))?;
+    let output = output_path.map(parse_output_file).transpose()?;
+
+    Ok(Cli {
+        command: Command::Codegen(CodegenCommand {
+            target,
+            input,
+            output,
+        }),
+    })
+}
+
+/// Parses the arguments following `export`.
+#[cfg(feature = "sqlite")]
+fn parse_export_args(args: &[String]) -> Result<Cli> {
+    let mut output_path = None;
+    let mut positional = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "-o" && i + 1 < args.len() {
+            output_path = Some(PathBuf::from(&args[i + 1]));
+            i += 2;
+        } else {
+            positional.push(&args[i]);
+            i += 1;
         }
     }
 
+    let [target, input] = positional[..] else {
+        return Err(Error::InvalidArguments(USAGE.to_string()));
+    };
+    let target = match target.as_str() {
+        "sqlite" => ExportTarget::Sqlite,
+        target => {
+            return Err(Error::InvalidArguments(format!(
+                "Unknown export target: {target} (supported: sqlite)"
+            )));
+        }
+    };
+    let input = PathBuilder::parse_event_model_file(PathBuf::from(input))
+        .map_err(|e| Error::InvalidPath(format!("Input file error: {e}")))?;
+    let output = output_path
+        .ok_or_else(|| Error::InvalidArguments("export needs -o <output file>".to_string()))
+        .and_then(parse_output_file)?;
+
+    Ok(Cli {
+        command: Command::Export(ExportCommand {
+            target,
+            input,
+            output,
+        }),
+    })
+}
+
+/// Parses the arguments following `docs`.
+#[cfg(feature = "html")]
+fn parse_docs_args(args: &[String]) -> Result<Cli> {
+    let mut output_path = None;
+    let mut assets = None;
+    let mut positional = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "-o" && i + 1 < args.len() {
+            output_path = Some(PathBuf::from(&args[i + 1]));
+            i += 2;
+        } else if args[i] == "--assets" && i + 1 < args.len() {
+            assets = Some(PathBuf::from(&args[i + 1]));
+            i += 2;
+        } else {
+            positional.push(&args[i]);
+            i += 1;
+        }
+    }
+
+    let input = match positional
+        .iter()
+        .map(|arg| arg.as_str())
+        .collect::<Vec<_>>()[..]
+    {
+        ["build", input] => input.to_string(),
+        ["storyboard", input] => {
+            let input = PathBuilder::parse_event_model_file(PathBuf::from(input))
+                .map_err(|e| Error::InvalidPath(format!("Input file error: {e}")))?;
+            let output = output_path.ok_or_else(|| {
+                Error::InvalidArguments("docs storyboard needs -o <storyboard.html>".to_string())
+            })?;
+            return Ok(Cli {
+                command: Command::Storyboard(StoryboardCommand {
+                    input,
+                    output: parse_output_file(output)?,
+                }),
+            });
+        }
+        ["mkdocs", docs] => {
+            let docs = PathBuf::from(docs);
+            if !docs.is_dir() {
+                return Err(Error::InvalidPath(format!(
+                    "Docs directory not found: {}",
+                    docs.display()
+                )));
+            }
+            return Ok(Cli {
+                command: Command::Mkdocs(MkdocsCommand {
+                    docs,
+                    assets: assets.unwrap_or_else(|| {
+                        PathBuf::from(crate::export::mkdocs::DEFAULT_ASSETS_DIR)
+                    }),
+                }),
+            });
+        }
+        _ => return Err(Error::InvalidArguments(USAGE.to_string())),
+    };
+    let input = PathBuilder::parse_event_model_file(PathBuf::from(input))
+        .map_err(|e| Error::InvalidPath(format!("Input file error: {e}")))?;
+    let output = output_path.ok_or_else(|| {
+        Error::InvalidArguments("docs build needs -o <site directory>".to_string())
+    })?;
+
+    Ok(Cli {
+        command: Command::Docs(DocsCommand { input, output }),
+    })
+}
+
+/// Parses the arguments following `compare`.
+/// Parses the arguments following `badge`.
+fn parse_badge_args(args: &[String]) -> Result<Cli> {
+    let mut output_path = None;
+    let mut positional = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "-o" && i + 1 < args.len() {
+            output_path = Some(PathBuf::from(&args[i + 1]));
+            i += 2;
+        } else {
+            positional.push(&args[i]);
+            i += 1;
+        }
+    }
+
+    let [input] = positional[..] else {
+        return Err(Error::InvalidArguments(USAGE.to_string()));
+    };
+    let input = PathBuilder::parse_event_model_file(PathBuf::from(input))
+        .map_err(|e| Error::InvalidPath(format!("Input file error: {e}")))?;
+    let output = output_path
+        .ok_or_else(|| Error::InvalidArguments("badge needs -o <badge.svg>".to_string()))?;
+
+    Ok(Cli {
+        command: Command::Badge(BadgeCommand { input, output }),
+    })
+}
+
+/// Parses the arguments following `find`.
+fn parse_find_args(args: &[String]) -> Result<Cli> {
+    let mut render = None;
+    let mut positional = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--render" && i + 1 < args.len() {
+            render = Some(PathBuf::from(&args[i + 1]));
+            i += 2;
+        } else {
+            positional.push(&args[i]);
+            i += 1;
+        }
+    }
+
+    let [input, query] = positional[..] else {
+        return Err(Error::InvalidArguments(USAGE.to_string()));
+    };
+    let input = PathBuilder::parse_event_model_file(PathBuf::from(input))
+        .map_err(|e| Error::InvalidPath(format!("Input file error: {e}")))?;
+
+    Ok(Cli {
+        command: Command::Find(FindCommand {
+            input,
+            query: query.clone(),
+            render,
+        }),
+    })
+}
+
+/// Parses the arguments following `serve`.
+fn parse_serve_args(args: &[String]) -> Result<Cli> {
+    let mut kroki = false;
+    let mut host = DEFAULT_SERVE_HOST.to_string();
+    let mut port = DEFAULT_SERVE_PORT;
+
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--kroki" {
+            kroki = true;
+            i += 1;
+        } else if args[i] == "--host" && i + 1 < args.len() {
+            host = args[i + 1].clone();
+            i += 2;
+        } else if args[i] == "--port" && i + 1 < args.len() {
+            port = args[i + 1].parse().map_err(|_| {
+                Error::InvalidArguments(format!("--port needs a port number, not {}", args[i + 1]))
+            })?;
+            i += 2;
+        } else {
+            return Err(Error::InvalidArguments(USAGE.to_string()));
+        }
+    }
+
+    // Kroki's conventions are the only protocol served so far; the flag
+    // leaves room for others
+    if !kroki {
+        return Err(Error::InvalidArguments(
+            "serve needs a protocol; pass --kroki".to_string(),
+        ));
+    }
+
+    Ok(Cli {
+        command: Command::Serve(ServeCommand {
+            address: format!("{host}:{port}"),
+        }),
+    })
+}
+
+#[cfg(feature = "png")]
+fn parse_compare_args(args: &[String]) -> Result<Cli> {
+    use crate::compare::DifferenceThreshold;
+
+    let mut threshold = None;
+    let mut output_path = None;
+    let mut positional = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--threshold" && i + 1 < args.len() {
+            threshold = Some(
+                args[i + 1]
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(|share| DifferenceThreshold::try_new(share).ok())
+                    .ok_or_else(|| {
+                        Error::InvalidArguments(format!(
+                            "--threshold needs a share of pixels between 0 and 1, not {}",
+                            args[i + 1]
+                        ))
+                    })?,
+            );
+            i += 2;
+        } else if args[i] == "-o" && i + 1 < args.len() {
+            output_path = Some(PathBuf::from(&args[i + 1]));
+            i += 2;
+        } else {
+            positional.push(PathBuf::from(&args[i]));
+            i += 1;
+        }
+    }
+
+    let [rendered, reference] = &positional[..] else {
+        return Err(Error::InvalidArguments(USAGE.to_string()));
+    };
+    // The heat map goes beside the rendered diagram unless placed elsewhere
+    let output_path = output_path.unwrap_or_else(|| rendered.with_extension("diff.png"));
+    let rendered = PathBuilder::parse_existing_file(rendered.clone())
+        .map_err(|e| Error::InvalidPath(format!("Rendered file error: {e}")))?;
+    let reference = PathBuilder::parse_existing_file(reference.clone())
+        .map_err(|e| Error::InvalidPath(format!("Reference file error: {e}")))?;
+    let threshold = match threshold {
+        Some(threshold) => threshold,
+        None => DifferenceThreshold::try_new(DEFAULT_COMPARE_THRESHOLD)
+            .map_err(|e| Error::InvalidArguments(format!("Default threshold error: {e}")))?,
+    };
+
+    Ok(Cli {
+        command: Command::Compare(CompareCommand {
+            rendered,
+            reference,
+            threshold,
+            heat_map: parse_output_file(output_path)?,
+        }),
+    })
+}
+
+/// Parses the arguments following `scaffold`.
+fn parse_scaffold_args(args: &[String]) -> Result<Cli> {
+    let mut words = crate::codegen::scaffold::WordList::default();
+    let mut output_path = None;
+
+    let split = |list: &str| -> Vec<String> {
+        list.split(',')
+            .map(|word| word.trim().to_string())
+            .filter(|word| !word.is_empty())
+            .collect()
+    };
+
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--events" && i + 1 < args.len() {
+            words.events.extend(split(&args[i + 1]));
+            i += 2;
+        } else if args[i] == "--commands" && i + 1 < args.len() {
+            words.commands.extend(split(&args[i + 1]));
+            i += 2;
+        } else if args[i] == "--workflow" && i + 1 < args.len() {
+            words.workflow = Some(args[i + 1].clone());
+            i += 2;
+        } else if args[i] == "-o" && i + 1 < args.len() {
+            output_path = Some(PathBuf::from(&args[i + 1]));
+            i += 2;
+        } else {
+            return Err(Error::InvalidArguments(format!(
+                "Unexpected scaffold argument: {}",
+                args[i]
+            )));
+        }
+    }
+
+    if words.events.is_empty() && words.commands.is_empty() {
+        return Err(Error::InvalidArguments(
+            "scaffold needs --events or --commands".to_string(),
+        ));
+    }
+    let output = output_path.map(parse_output_file).transpose()?;
+
+    Ok(Cli {
+        command: Command::Scaffold(ScaffoldCommand { words, output }),
+    })
+}
+
+/// Parses a `-o` path into the file to write, checking its directory exists.
+fn parse_output_file(path: PathBuf) -> Result<MergeOutput> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| {
+            Error::InvalidPath(format!("Output path has no file name: {}", path.display()))
+        })?;
+    let directory = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .map(|parent| parent.to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("."));
+    let directory = PathBuilder::parse_output_directory(directory)
+        .map_err(|e| Error::InvalidPath(format!("Output directory error: {e}")))?;
+    Ok(MergeOutput {
+        directory,
+        file_name,
+    })
+}
+
+/// Determines the files to write from `-o` paths and `--formats` names.
+///
+/// Each `-o` path produces one output whose format is inferred from its
+/// extension (defaulting to SVG). Each requested format produces an output
+/// next to the first `-o` path (or in the current directory when none was
+/// given), named after that path's stem or the input file's stem.
+fn parse_output_targets(
+    input: &TypedPath<EventModelFile, File, Exists>,
+    output_paths: &[String],
+    requested_formats: &[OutputFormat],
+) -> Result<NonEmpty<OutputTarget>> {
+    let mut targets: Vec<(OutputFormat, PathBuf)> = output_paths
+        .iter()
+        .map(|path| {
+            let format = OutputFormat::from_path(path).unwrap_or(OutputFormat::Svg);
+            (format, PathBuf::from(path))
+        })
+        .collect();
+
+    let base = match output_paths.first() {
+        Some(path) => PathBuf::from(path),
+        None => PathBuf::from(input.as_path_buf().file_name().unwrap_or_default()),
+    };
+    for &format in requested_formats {
+        let path = base.with_extension(format.extension());
+        if !targets.iter().any(|(_, existing)| existing == &path) {
+            targets.push((format, path));
+        }
+    }
+
+    #[cfg(not(feature = "pdf"))]
+    if targets
+        .iter()
+        .any(|(format, _)| *format == OutputFormat::Pdf)
+    {
+        return Err(feature_disabled("pdf", "PDF output"));
+    }
+    #[cfg(not(feature = "png"))]
+    if targets
+        .iter()
+        .any(|(format, _)| *format == OutputFormat::Png)
+    {
+        return Err(feature_disabled("png", "PNG output"));
+    }
+
+    if targets.is_empty() {
+        targets.push((
+            OutputFormat::Svg,
+            base.with_extension(OutputFormat::Svg.extension()),
+        ));
+    }
+
+    let mut outputs = Vec::with_capacity(targets.len());
+    for (format, path) in targets {
+        let directory = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .map(|parent| parent.to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .ok_or_else(|| {
+                Error::InvalidPath(format!("Output path has no file name: {}", path.display()))
+            })?;
+        let directory = PathBuilder::parse_output_directory(directory)
+            .map_err(|e| Error::InvalidPath(format!("Output directory error: {e}")))?;
+        outputs.push(OutputTarget {
+            format,
+            directory,
+            file_name,
+        });
+    }
+
+    NonEmpty::try_from(outputs).map_err(|_| Error::InvalidArguments(USAGE.to_string()))
+}
+
+/// Gathers `--poster`, `--tile`, and `--overlap` into poster options,
+/// checking the papers are known and a PDF is among the outputs.
+#[cfg(feature = "pdf")]
+fn poster_options(
+    poster: Option<&str>,
+    tile: Option<&str>,
+    overlap: Option<crate::diagram::Length>,
+    outputs: &NonEmpty<OutputTarget>,
+) -> Result<Option<PosterOptions>> {
+    let parse_paper = |flag: &str, name: &str| {
+        PageSize::from_name(name).ok_or_else(|| {
+            Error::InvalidArguments(format!(
+                "Invalid {flag}: {name}; expected a0, a1, a2, a3, a4, letter, or legal"
+            ))
+        })
+    };
+    let Some(poster) = poster else {
+        if tile.is_some() || overlap.is_some() {
+            return Err(Error::InvalidArguments(
+                "--tile and --overlap need --poster".to_string(),
+            ));
+        }
+        return Ok(None);
+    };
+    let paper = parse_paper("--poster", poster)?;
+    let tile = tile.map(|tile| parse_paper("--tile", tile)).transpose()?;
+    if !outputs
+        .iter()
+        .any(|target| target.format == OutputFormat::Pdf)
+    {
+        return Err(Error::InvalidArguments(
+            "--poster needs a PDF output, e.g. -o model.pdf".to_string(),
+        ));
+    }
+    Ok(Some(PosterOptions {
+        paper,
+        tile,
+        overlap: overlap.unwrap_or_else(|| crate::diagram::Length::from_millimeters(10.0)),
+        title: None,
+    }))
+}
+
+/// The error for a command or flag needing a cargo feature this build was
+/// compiled without.
+#[cfg(not(all(feature = "pdf", feature = "png", feature = "html", feature = "sqlite")))]
+fn feature_disabled(feature: &str, what: &str) -> Error {
+    Error::InvalidArguments(format!(
+        "{what} needs event_modeler built with the `{feature}` feature"
+    ))
+}
+
+/// Parses a duration such as `30s`, `500ms`, or `2m` as accepted by
+/// `--timeout`.
+fn parse_timeout(text: &str) -> Option<Duration> {
+    let digits = text.find(|c: char| !c.is_ascii_digit())?;
+    let amount: u64 = text[..digits].parse().ok()?;
+    match &text[digits..] {
+        "ms" => Some(Duration::from_millis(amount)),
+        "s" => Some(Duration::from_secs(amount)),
+        "m" => Some(Duration::from_secs(amount.checked_mul(60)?)),
+        _ => None,
+    }
+}
+
+/// Writes `value` as JSON if `--emit` asked for `stage`, beside the first
+/// output and named after it, e.g. `model.routes.json` for `model.svg`.
+fn emit_stage(
+    options: &RenderOptions,
+    stage: PipelineStage,
+    value: &impl serde::Serialize,
+) -> Result<()> {
+    if !options.emit.contains(&stage) {
+        return Ok(());
+    }
+    let target = options.outputs.first();
+    let stem = PathBuf::from(&target.file_name)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let path = target
+        .directory
+        .as_path_buf()
+        .join(format!("{stem}.{}.json", stage.name()));
+    // Going through a `Value` sorts map keys, so emitted files diff cleanly
+    let json = serde_json::to_value(value)
+        .and_then(|value| serde_json::to_string_pretty(&value))
+        .map_err(std::io::Error::from)?;
+    std::fs::write(&path, json)?;
+    println!("Emitted {}: {}", stage.name(), path.display());
+    Ok(())
+}
+
+/// Execute a render command.
+///
+/// The model is parsed, laid out, and rendered once; every requested output
+/// is then produced from that single result.
+fn execute_render(cmd: RenderCommand) -> Result<()> {
+    use std::fs;
+
+    // The timeout covers the whole render, so the clock starts before parsing
+    let budget = cmd
+        .options
+        .timeout
+        .map_or_else(crate::diagram::RenderBudget::unlimited, |timeout| {
+            crate::diagram::RenderBudget::unlimited().with_timeout(timeout)
+        });
+
+    let progress_bar = cmd.options.progress.then(progress_bar);
+    let progress = progress_bar.clone().map_or_else(Progress::none, |bar| {
+        Progress::to(move |event| report_progress(&bar, event))
+    });
+
+    // 1. Read the input file
+    let input_content = fs::read_to_string(cmd.input.as_path_buf())?;
+    progress.start(ProgressStage::Parsing);
+
+    // 2. Parse the YAML event model, collecting warnings for the final report
+    let mut warnings = crate::infrastructure::warnings::Warnings::new();
+    let mut yaml_model = crate::infrastructure::parsing::yaml_parser::parse_yaml_with_policy(
+        &input_content,
+        cmd.options.unknown_keys,
+        &mut warnings,
+    )
+    .map_err(|e| match e {
+        YamlParseError::UnknownKeys(_) => Error::InvalidArguments(format!(
+            "YAML parse error: {e}\n(pass --lenient to ignore unrecognized keys)"
+        )),
+        _ => Error::InvalidArguments(format!("YAML parse error: {e}")),
+    })?;
+
+    // 3. Select the requested variant of the model and its enabled features
+    if let Some(profile) = &cmd.options.profile {
+        crate::infrastructure::parsing::profiles::apply_profile(&mut yaml_model, profile)
+            .map_err(|e| Error::InvalidArguments(format!("Profile error: {e}")))?;
+    }
+    crate::infrastructure::parsing::features::apply_features(
+        &mut yaml_model,
+        &cmd.options.features,
+        &mut warnings,
+    );
+    // Each stage is written as soon as it exists, so a later failure still
+    // leaves the stages before it to inspect
+    emit_stage(&cmd.options, PipelineStage::Ast, &yaml_model)?;
+
+    // 4. Convert YAML to domain types
+    let domain_model =
+        crate::infrastructure::parsing::yaml_converter::convert_yaml_to_domain_with_warnings(
+            yaml_model,
+            &mut warnings,
+        )
+        .map_err(|e| Error::InvalidArguments(format!("YAML conversion error: {e}")))?;
+    emit_stage(&cmd.options, PipelineStage::Domain, &domain_model)?;
+    crate::event_model::roles::check_placement(
+        &domain_model,
+        cmd.options.role_placement,
+        &mut warnings,
+    )?;
+    let reachability = crate::event_model::reachability::Reachability::from_model(&domain_model);
+    reachability.warn(&mut warnings);
+    crate::validation::lint_model(&domain_model, &mut warnings);
+    // Absolute, so the search for the enclosing Cargo package can walk up
+    let model_path = fs::canonicalize(cmd.input.as_path_buf())?;
+    let model_dir = model_path
+        .parent()
+        .unwrap_or_else(|| std::path::Path::new("/"));
+    #[cfg(feature = "plugins")]
+    crate::infrastructure::plugins::run_plugins(&domain_model, model_dir, &mut warnings)?;
+    if cmd.options.verify_links {
+        crate::infrastructure::links::verify_links(&domain_model, model_dir)?;
+    }
+    let issue_states = if cmd.options.verify_issues {
+        crate::infrastructure::issues::fetch_issue_states(&domain_model)?
+    } else {
+        std::collections::HashMap::new()
+    };
+
+    // 5. Build diagram from domain model
+    let diagram = crate::diagram::build_diagram_from_domain(&domain_model)
+        .map_err(|e| Error::InvalidArguments(format!("Diagram building error: {e}")))?;
+    progress.finish(ProgressStage::Parsing);
+
+    println!(
+        "Successfully converted event model: {}",
+        diagram.workflow_title().as_str()
+    );
+
+    // 6. Lay out, route, and render the diagram once for all outputs
+    let mut render_cache = crate::diagram::RenderCache::new();
+    let focus_slice = match &cmd.options.focus {
+        Some(name) => Some(
+            diagram
+                .slices()
+                .iter()
+                .map(|slice| slice.name.clone())
+                .find(|slice| slice.clone().into_inner().as_str() == name)
+                .ok_or_else(|| {
+                    let known: Vec<String> = diagram
+                        .slices()
+                        .iter()
+                        .map(|slice| slice.name.clone().into_inner().into_inner())
+                        .collect();
+                    Error::InvalidArguments(format!(
+                        "Unknown slice '{name}' for --focus; the model has: {}",
+                        known.join(", ")
+                    ))
+                })?,
+        ),
+        None => None,
+    };
+    let svg_options = crate::diagram::SvgOptions {
+        show_owners: cmd.options.show_owners,
+        show_estimates: cmd.options.show_estimates,
+        tint_patterns: cmd.options.tint_patterns,
+        issue_states,
+        show_errors: cmd.options.show_errors,
+        focus_slice,
+        dimmed: if cmd.options.dim_unreachable {
+            reachability.unreachable().to_vec()
+        } else {
+            Vec::new()
+        },
+        highlighted: Vec::new(),
+        entity_links: None,
+        show_grid: cmd.options.debug_grid,
+        budget,
+        seed: cmd.options.seed,
+        optimize: cmd
+            .options
+            .optimize
+            .map(crate::diagram::OptimizeBudget::new),
+        fast: cmd.options.fast,
+        dark: matches!(cmd.options.style, RenderStyle::GithubDark),
+        progress: progress.clone(),
+    };
+    let (svg_content, trace) = crate::diagram::render_to_svg_traced(
+        &diagram,
+        &svg_options,
+        &mut render_cache,
+        &mut warnings,
+    )
+    .map_err(|e| Error::InvalidArguments(format!("SVG rendering error: {e}")))?;
+    if let Some(bar) = &progress_bar {
+        bar.finish_and_clear();
+    }
+    emit_stage(&cmd.options, PipelineStage::Layout, &trace.layout)?;
+    emit_stage(&cmd.options, PipelineStage::Routes, &trace.routes)?;
+    if cmd.options.score {
+        println!(
+            "Layout score: {}",
+            crate::diagram::LayoutScore::from_trace(&trace)
+        );
+    }
+
+    // 7. Fan out to the requested formats
+    for target in cmd.options.outputs.iter() {
+        let output_path = target.path();
+        match target.format {
+            OutputFormat::Svg => {
+                fs::write(&output_path, &svg_content)?;
+                println!("Generated SVG: {}", output_path.display());
+            }
+            #[cfg(feature = "pdf")]
+            OutputFormat::Pdf => match &cmd.options.poster {
+                Some(poster) => {
+                    let options = PosterOptions {
+                        title: Some(diagram.workflow_title().as_str().to_string()),
+                        ..poster.clone()
+                    };
+                    let pdf = crate::export::poster::export_poster(&svg_content, &options)?;
+                    fs::write(&output_path, pdf)?;
+                    println!("Generated poster PDF: {}", output_path.display());
+                }
+                None => {
+                    use crate::export::pdf::PdfTitle;
+                    let config = crate::export::PdfExportConfig {
+                        title: Some(PdfTitle::new(diagram.workflow_title().clone())),
+                        ..Default::default()
+                    };
+                    crate::export::PdfExporter::new(config).export(&svg_content, &output_path)?;
+                    println!("Generated PDF: {}", output_path.display());
+                }
+            },
+            #[cfg(not(feature = "pdf"))]
+            OutputFormat::Pdf => return Err(feature_disabled("pdf", "PDF output")),
+            #[cfg(feature = "png")]
+            OutputFormat::Png => {
+                crate::export::PngExporter::new(crate::export::PngExportConfig::default())
+                    .export(&svg_content, &output_path)?;
+                println!("Generated PNG: {}", output_path.display());
+            }
+            #[cfg(not(feature = "png"))]
+            OutputFormat::Png => return Err(feature_disabled("png", "PNG output")),
+            OutputFormat::Markdown => {
+                write_markdown(
+                    &diagram,
+                    &svg_content,
+                    target,
+                    &cmd.options.outputs,
+                    cmd.options.show_examples,
+                )?;
+                println!("Generated Markdown: {}", output_path.display());
+            }
+            OutputFormat::GraphMl => {
+                fs::write(&output_path, crate::export::graph::to_graphml(&diagram))?;
+                println!("Generated GraphML: {}", output_path.display());
+            }
+            OutputFormat::Cytoscape => {
+                let json = crate::export::graph::to_cytoscape_json(&diagram)
+                    .map_err(std::io::Error::from)?;
+                fs::write(&output_path, json)?;
+                println!("Generated Cytoscape JSON: {}", output_path.display());
+            }
+            OutputFormat::Mdx => {
+                use crate::event_model::yaml_types::UrlTemplate;
+                use crate::export::mdx::{MDX_ENTITY_LINKS, to_mdx, to_props_json};
+                use crate::infrastructure::types::NonEmptyString;
+
+                // The component finds entities through their links, which
+                // the other outputs do without
+                let linked_options = crate::diagram::SvgOptions {
+                    entity_links: NonEmptyString::parse(MDX_ENTITY_LINKS.to_string())
+                        .ok()
+                        .map(UrlTemplate::new),
+                    ..svg_options.clone()
+                };
+                let linked_svg = crate::diagram::render_to_svg_with_cache(
+                    &diagram,
+                    &linked_options,
+                    &mut render_cache,
+                    &mut crate::infrastructure::warnings::Warnings::new(),
+                )
+                .map_err(|e| Error::InvalidArguments(format!("SVG rendering error: {e}")))?;
+                let stem = target
+                    .file_name
+                    .strip_suffix(".mdx")
+                    .unwrap_or(&target.file_name);
+                let props_file = format!("{stem}.props.json");
+                let props = to_props_json(&diagram).map_err(std::io::Error::from)?;
+                fs::write(target.directory.as_path_buf().join(&props_file), props)?;
+                fs::write(&output_path, to_mdx(&diagram, &linked_svg, &props_file))?;
+                println!("Generated MDX: {}", output_path.display());
+            }
+        }
+    }
+
+    if let Some(directory) = &cmd.options.per_slice_out {
+        write_slice_images(&domain_model, &svg_options, directory)?;
+    }
+
+    // 8. Report anything that was tolerated instead of rejected
+    if !warnings.is_empty() {
+        eprintln!("{warnings}");
+    }
+
+    Ok(())
+}
+
+/// Creates the bar `--progress` shows on standard error.
+fn progress_bar() -> indicatif::ProgressBar {
+    let bar = indicatif::ProgressBar::new(100);
+    bar.set_style(
+        indicatif::ProgressStyle::with_template("{msg:>9} [{bar:40}] {pos:>3}%")
+            .expect("the progress template is valid")
+            .progress_chars("=> "),
+    );
+    bar
+}
+
+/// Shows a stage's progress on the `--progress` bar.
+fn report_progress(bar: &indicatif::ProgressBar, event: ProgressEvent) {
+    bar.set_message(event.stage.to_string());
+    bar.set_position(u64::from(event.percent()));
+}
+
+/// Renders each slice of the model on its own, as an SVG and (with the
+/// `png` feature) a PNG named after the slice, into a directory created if
+/// missing.
+fn write_slice_images(
+    model: &crate::event_model::yaml_types::YamlEventModel,
+    svg_options: &crate::diagram::SvgOptions,
+    directory: &std::path::Path,
+) -> Result<()> {
+    use crate::event_model::yaml_types::{UnusedLanes, YamlEventModel};
+
+    std::fs::create_dir_all(directory)?;
+    let options = crate::diagram::SvgOptions {
+        focus_slice: None,
+        ..svg_options.clone()
+    };
+    for slice in &model.slices {
+        let name = slice.name.clone().into_inner().into_inner();
+        // Swimlanes the slice leaves empty shrink to thin bands
+        let slice_model = YamlEventModel {
+            slices: vec![slice.clone()],
+            unused_lanes: UnusedLanes::Shaded,
+            ..model.clone()
+        };
+        let diagram = crate::diagram::build_diagram_from_domain(&slice_model).map_err(|e| {
+            Error::InvalidArguments(format!("Diagram building error for slice '{name}': {e}"))
+        })?;
+        // Whatever the slice leaves out was reported for the full diagram
+        let svg = crate::diagram::render_to_svg_with_cache(
+            &diagram,
+            &options,
+            &mut crate::diagram::RenderCache::new(),
+            &mut crate::infrastructure::warnings::Warnings::new(),
+        )
+        .map_err(|e| {
+            Error::InvalidArguments(format!("SVG rendering error for slice '{name}': {e}"))
+        })?;
+        let stem = crate::export::mkdocs::slug(&name);
+        std::fs::write(directory.join(format!("{stem}.svg")), &svg)?;
+        #[cfg(feature = "png")]
+        {
+            crate::export::PngExporter::new(crate::export::PngExportConfig::default())
+                .export(&svg, &directory.join(format!("{stem}.png")))
+                .map_err(|e| {
+                    Error::InvalidArguments(format!("PNG rendering error for slice '{name}': {e}"))
+                })?;
+        }
+    }
+    println!(
+        "Generated {} slice images: {}",
+        model.slices.len(),
+        directory.display()
+    );
+    Ok(())
+}
+
+/// Reads, parses, and converts a model for the commands that describe it
+/// rather than draw it.
+fn load_model(
+    input: &TypedPath<EventModelFile, File, Exists>,
+) -> Result<(
+    crate::event_model::yaml_types::YamlEventModel,
+    crate::infrastructure::warnings::Warnings,
+)> {
+    let content = std::fs::read_to_string(input.as_path_buf())?;
+    let mut warnings = crate::infrastructure::warnings::Warnings::new();
+    let yaml_model = crate::infrastructure::parsing::yaml_parser::parse_yaml_with_policy(
+        &content,
+        UnknownKeyPolicy::Reject,
+        &mut warnings,
+    )
+    .map_err(|e| Error::InvalidArguments(format!("YAML parse error: {e}")))?;
+    let domain_model =
+        crate::infrastructure::parsing::yaml_converter::convert_yaml_to_domain_with_warnings(
+            yaml_model,
+            &mut warnings,
+        )
+        .map_err(|e| Error::InvalidArguments(format!("YAML conversion error: {e}")))?;
+    // These commands describe the model as written, so misplaced entities only warn
+    crate::event_model::roles::check_placement(
+        &domain_model,
+        PlacementPolicy::Warn,
+        &mut warnings,
+    )?;
+    Ok((domain_model, warnings))
+}
+
+/// Execute a report command.
+fn execute_report(cmd: ReportCommand) -> Result<()> {
+    if cmd.kind == ReportKind::History {
+        use crate::event_model::history::HistoryReport;
+
+        let revisions = crate::infrastructure::git::file_history(cmd.input.as_path_buf(), cmd.last)
+            .map_err(|e| Error::InvalidArguments(format!("History error: {e}")))?;
+        let report = HistoryReport::from_revisions(&revisions);
+        if cmd.csv {
+            print!("{}", report.to_csv());
+        } else {
+            print!("{}", report.to_markdown());
+        }
+        return Ok(());
+    }
+
+    let (domain_model, warnings) = load_model(&cmd.input)?;
+
+    match cmd.kind {
+        ReportKind::Owners => {
+            let report = crate::event_model::ownership::OwnershipReport::from_model(&domain_model);
+            println!("{report}");
+        }
+        ReportKind::Scale => {
+            let report = crate::event_model::scale::ScaleReport::from_model(&domain_model);
+            println!("{report}");
+        }
+        ReportKind::Pii => {
+            let report = crate::event_model::pii::PiiReport::from_model(&domain_model);
+            println!("{report}");
+        }
+        ReportKind::Simulation => {
+            let report =
+                crate::event_model::simulation::SimulationReport::from_model(&domain_model);
+            println!("{report}");
+        }
+        ReportKind::Streams => {
+            let report = crate::event_model::streams::StreamReport::from_model(&domain_model);
+            if cmd.json {
+                let json = report.to_json().map_err(std::io::Error::from)?;
+                println!("{json}");
+            } else {
+                print!("{}", report.to_markdown());
+            }
+        }
+        ReportKind::Glossary => {
+            let glossary = crate::event_model::glossary::Glossary::from_model(&domain_model);
+            if cmd.html {
+                print!("{}", glossary.to_html());
+            } else {
+                print!("{}", glossary.to_markdown());
+            }
+        }
+        ReportKind::Contexts => {
+            let map = crate::event_model::contexts::ContextMap::from_model(&domain_model);
+            if cmd.json {
+                let json = map.to_json().map_err(std::io::Error::from)?;
+                println!("{json}");
+            } else {
+                print!("{}", map.to_markdown());
+            }
+        }
+        ReportKind::Estimates => {
+            let rollup = crate::event_model::estimates::EstimateRollup::from_model(&domain_model);
+            if cmd.json {
+                let json = rollup.to_json().map_err(std::io::Error::from)?;
+                println!("{json}");
+            } else {
+                print!("{}", rollup.to_markdown());
+            }
+        }
+        ReportKind::Progress => {
+            let report = crate::event_model::progress::ProgressReport::from_model(&domain_model);
+            if cmd.json {
+                let json = report.to_json().map_err(std::io::Error::from)?;
+                println!("{json}");
+            } else {
+                print!("{}", report.to_markdown());
+            }
+        }
+        ReportKind::Patterns => {
+            let patterns = crate::event_model::patterns::SlicePatterns::from_model(&domain_model);
+            if cmd.json {
+                let json = patterns.to_json().map_err(std::io::Error::from)?;
+                println!("{json}");
+            } else {
+                print!("{}", patterns.to_markdown());
+            }
+        }
+        ReportKind::Conventions => {
+            let report =
+                crate::event_model::conventions::ConventionReport::from_model(&domain_model);
+            if cmd.json {
+                let json = report.to_json().map_err(std::io::Error::from)?;
+                println!("{json}");
+            } else {
+                print!("{}", report.to_markdown());
+            }
+        }
+        // Reported above, without loading the current model
+        ReportKind::History => {}
+    }
+
+    if !warnings.is_empty() {
+        eprintln!("{warnings}");
+    }
+
+    Ok(())
+}
+
+/// Execute a codegen command.
+fn execute_codegen(cmd: CodegenCommand) -> Result<()> {
+    let (domain_model, warnings) = load_model(&cmd.input)?;
+
+    let source = match cmd.target {
+        CodegenTarget::RustTests => crate::codegen::rust_tests::generate(&domain_model),
+    };
+    match &cmd.output {
+        Some(output) => {
+            std::fs::write(output.path(), source)?;
+            println!("Wrote test skeletons to {}", output.path().display());
+        }
+        None => print!("{source}"),
+    }
+
+    if !warnings.is_empty() {
+        eprintln!("{warnings}");
+    }
+
+    Ok(())
+}
+
+/// Execute a scaffold command.
+fn execute_scaffold(cmd: ScaffoldCommand) -> Result<()> {
+    let yaml = crate::codegen::scaffold::generate(&cmd.words);
+    match &cmd.output {
+        Some(output) => {
+            std::fs::write(output.path(), yaml)?;
+            println!("Wrote starter model to {}", output.path().display());
+        }
+        None => print!("{yaml}"),
+    }
+
+    Ok(())
+}
+
+/// Execute a wizard command.
+fn execute_wizard(cmd: WizardCommand) -> Result<()> {
+    let added = crate::wizard::run(cmd.input.as_path_buf())?;
+    match added {
+        0 => println!("No slices added"),
+        1 => println!("Added 1 slice to {}", cmd.input.as_path_buf().display()),
+        n => println!("Added {n} slices to {}", cmd.input.as_path_buf().display()),
+    }
+    Ok(())
+}
+
+/// Execute a serve command, serving until the process is stopped.
+fn execute_serve(cmd: ServeCommand) -> Result<()> {
+    crate::server::serve(cmd.address.as_str())?;
+    Ok(())
+}
+
+/// Execute an export command.
+#[cfg(feature = "sqlite")]
+fn execute_export(cmd: ExportCommand) -> Result<()> {
+    let (domain_model, warnings) = load_model(&cmd.input)?;
+
+    match cmd.target {
+        ExportTarget::Sqlite => {
+            crate::export::sqlite::write_sqlite(&domain_model, &cmd.output.path())
+                .map_err(|e| Error::InvalidArguments(format!("SQLite export error: {e}")))?;
+            println!("Wrote SQLite database to {}", cmd.output.path().display());
+        }
+    }
+
+    if !warnings.is_empty() {
+        eprintln!("{warnings}");
+    }
+
+    Ok(())
+}
+
+/// Execute a docs command.
+#[cfg(feature = "html")]
+fn execute_docs(cmd: DocsCommand) -> Result<()> {
+    use crate::event_model::yaml_types::UrlTemplate;
+    use crate::export::site::{ENTITY_PAGE_TEMPLATE, build_site};
+    use crate::infrastructure::types::NonEmptyString;
+
+    let (domain_model, mut warnings) = load_model(&cmd.input)?;
+    let diagram = crate::diagram::build_diagram_from_domain(&domain_model)
+        .map_err(|e| Error::InvalidArguments(format!("Diagram building error: {e}")))?;
+    let svg_options = crate::diagram::SvgOptions {
+        entity_links: NonEmptyString::parse(ENTITY_PAGE_TEMPLATE.to_string())
+            .ok()
+            .map(UrlTemplate::new),
+        ..crate::diagram::SvgOptions::default()
+    };
+    let svg_content = crate::diagram::render_to_svg_with_cache(
+        &diagram,
+        &svg_options,
+        &mut crate::diagram::RenderCache::new(),
+        &mut warnings,
+    )
+    .map_err(|e| Error::InvalidArguments(format!("SVG rendering error: {e}")))?;
+
+    let pages = build_site(&diagram, &svg_content);
+    for page in &pages {
+        let path = cmd.output.join(&page.path);
+        if let Some(directory) = path.parent() {
+            std::fs::create_dir_all(directory)?;
+        }
+        std::fs::write(&path, &page.html)?;
+    }
+    println!("Wrote {} pages to {}", pages.len(), cmd.output.display());
+
+    if !warnings.is_empty() {
+        eprintln!("{warnings}");
+    }
+
+    Ok(())
+}
+
+/// Execute a storyboard command: write the slices' screens and steps as one page.
+#[cfg(feature = "html")]
+fn execute_storyboard(cmd: StoryboardCommand) -> Result<()> {
+    let (domain_model, warnings) = load_model(&cmd.input)?;
+    let diagram = crate::diagram::build_diagram_from_domain(&domain_model)
+        .map_err(|e| Error::InvalidArguments(format!("Diagram building error: {e}")))?;
+    if diagram.views().is_empty() {
+        eprintln!("The model has no views; the storyboard lists only its commands and events");
+    }
+
+    let html = crate::export::storyboard::build_storyboard(&diagram);
+    std::fs::write(cmd.output.path(), html)?;
+    println!("Generated storyboard: {}", cmd.output.path().display());
+
+    if !warnings.is_empty() {
+        eprintln!("{warnings}");
+    }
+
+    Ok(())
+}
+
+/// Execute an mkdocs command: render each diagram the pages ask for once,
+/// then replace the placeholders in each page.
+fn execute_mkdocs(cmd: MkdocsCommand) -> Result<()> {
+    use crate::export::mkdocs::{DiagramRequest, Placeholder, find_placeholders, rewrite};
+
+    let mut pages: Vec<(PathBuf, String, Vec<Placeholder>)> = Vec::new();
+    let mut directories = vec![cmd.docs.clone()];
+    while let Some(directory) = directories.pop() {
+        for entry in std::fs::read_dir(&directory)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                directories.push(path);
+            } else if path.extension().is_some_and(|extension| extension == "md") {
+                let content = std::fs::read_to_string(&path)?;
+                let placeholders = find_placeholders(&content);
+                if !placeholders.is_empty() {
+                    pages.push((path, content, placeholders));
+                }
+            }
+        }
+    }
+    pages.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let requests: std::collections::BTreeSet<&DiagramRequest> = pages
+        .iter()
+        .flat_map(|(_, _, placeholders)| placeholders.iter().map(|p| &p.request))
+        .collect();
+    let assets = cmd.docs.join(&cmd.assets);
+    std::fs::create_dir_all(&assets)?;
+    let mut warnings = crate::infrastructure::warnings::Warnings::new();
+    let mut diagrams = std::collections::HashMap::new();
+    for request in &requests {
+        // Each model is loaded and its warnings reported once, however many
+        // of its slices the pages put in focus
+        let first_render = !diagrams.contains_key(&request.model);
+        if first_render {
+            let input = PathBuilder::parse_event_model_file(cmd.docs.join(&request.model))
+                .map_err(|e| Error::InvalidPath(format!("Model file error: {e}")))?;
+            let (domain_model, model_warnings) = load_model(&input)?;
+            model_warnings
+                .iter()
+                .for_each(|warning| warnings.push(warning.clone()));
+            let diagram = crate::diagram::build_diagram_from_domain(&domain_model)
+                .map_err(|e| Error::InvalidArguments(format!("Diagram building error: {e}")))?;
+            diagrams.insert(request.model.clone(), diagram);
+        }
+        let Some(diagram) = diagrams.get(&request.model) else {
+            continue;
+        };
+        let focus_slice = match &request.slice {
+            Some(name) => Some(
+                diagram
+                    .slices()
+                    .iter()
+                    .map(|slice| slice.name.clone())
+                    .find(|slice| slice.clone().into_inner().as_str() == name)
+                    .ok_or_else(|| {
+                        Error::InvalidArguments(format!(
+                            "Unknown slice '{name}' in {}",
+                            request.model.display()
+                        ))
+                    })?,
+            ),
+            None => None,
+        };
+        let mut render_warnings = crate::infrastructure::warnings::Warnings::new();
+        let svg = crate::diagram::render_to_svg_with_cache(
+            diagram,
+            &crate::diagram::SvgOptions {
+                focus_slice,
+                ..crate::diagram::SvgOptions::default()
+            },
+            &mut crate::diagram::RenderCache::new(),
+            &mut render_warnings,
+        )
+        .map_err(|e| Error::InvalidArguments(format!("SVG rendering error: {e}")))?;
+        if first_render {
+            render_warnings
+                .iter()
+                .for_each(|warning| warnings.push(warning.clone()));
+        }
+        std::fs::write(assets.join(request.asset_name()), svg)?;
+    }
+
+    for (path, content, placeholders) in &pages {
+        let page = path.strip_prefix(&cmd.docs).unwrap_or(path);
+        std::fs::write(path, rewrite(content, placeholders, page, &cmd.assets))?;
+    }
+    println!(
+        "Rendered {} diagrams into {} for {} pages",
+        requests.len(),
+        assets.display(),
+        pages.len()
+    );
+
+    if !warnings.is_empty() {
+        eprintln!("{warnings}");
+    }
+
+    Ok(())
+}
+
+/// Execute a badge command. A model that fails to load gets a red badge
+/// rather than failing the command, so CI can publish its state either way.
+fn execute_badge(cmd: BadgeCommand) -> Result<()> {
+    use crate::export::badge::{ModelHealth, markdown_snippet, render_badge};
+
+    let health = match load_model(&cmd.input) {
+        Ok((domain_model, mut warnings)) => {
+            crate::event_model::reachability::Reachability::from_model(&domain_model)
+                .warn(&mut warnings);
+            crate::validation::lint_model(&domain_model, &mut warnings);
+            ModelHealth::of(&domain_model, warnings.len())
+        }
+        Err(error) => {
+            eprintln!("Error: {error}");
+            ModelHealth::Invalid
+        }
+    };
+    std::fs::write(&cmd.output, render_badge(&health))?;
+    print!(
+        "{}",
+        markdown_snippet(&health, &cmd.output.to_string_lossy())
+    );
+    Ok(())
+}
+
+/// Execute a find command, listing the matching entities and optionally
+/// rendering the diagram with them highlighted.
+fn execute_find(cmd: FindCommand) -> Result<()> {
+    let (domain_model, warnings) = load_model(&cmd.input)?;
+    let results = crate::event_model::search::SearchResults::search(&domain_model, &cmd.query);
+    println!("{results}");
+
+    if let Some(path) = &cmd.render {
+        let diagram = crate::diagram::build_diagram_from_domain(&domain_model)
+            .map_err(|e| Error::InvalidArguments(format!("Diagram building error: {e}")))?;
+        let svg = crate::diagram::render_to_svg_with_cache(
+            &diagram,
+            &crate::diagram::SvgOptions {
+                highlighted: results.names(),
+                ..crate::diagram::SvgOptions::default()
+            },
+            &mut crate::diagram::RenderCache::new(),
+            // The diagram is for finding one's way, not for reviewing the layout
+            &mut crate::infrastructure::warnings::Warnings::new(),
+        )
+        .map_err(|e| Error::InvalidArguments(format!("SVG rendering error: {e}")))?;
+        std::fs::write(path, svg)?;
+        println!("Generated SVG: {}", path.display());
+    }
+
+    if !warnings.is_empty() {
+        eprintln!("{warnings}");
+    }
+    Ok(())
+}
+
+/// Execute a validate command, printing the findings of every model and
+/// failing if any model is invalid.
+fn execute_validate(cmd: ValidateCommand) -> Result<()> {
+    let inputs: Vec<PathBuf> = cmd
+        .inputs
+        .iter()
+        .map(|input| input.as_path_buf().clone())
+        .collect();
+    let shared: Vec<PathBuf> = cmd
+        .shared
+        .iter()
+        .map(|path| path.as_path_buf().clone())
+        .collect();
+    let report = crate::validation::validate_models(&inputs, &shared)?;
+    if cmd.json {
+        let json = report.to_json().map_err(std::io::Error::from)?;
+        println!("{json}");
+    } else {
+        print!("{}", report.to_markdown());
+    }
+    match report.invalid() {
+        0 => Ok(()),
+        invalid => Err(Error::InvalidModels {
+            invalid,
+            total: report.models().len(),
+        }),
+    }
+}
+
+/// Execute a compare command, failing if the images differ by more than
+/// the threshold.
+#[cfg(feature = "png")]
+fn execute_compare(cmd: CompareCommand) -> Result<()> {
+    let svg = std::fs::read_to_string(cmd.rendered.as_path_buf())?;
+    let comparison = crate::compare::compare_svg_to_reference(&svg, cmd.reference.as_path_buf())?;
+    let heat_map = cmd.heat_map.path();
+    comparison.write_heat_map(&heat_map)?;
+
+    let difference = comparison.difference() * 100.0;
+    if !comparison.within(cmd.threshold) {
+        return Err(Error::ImagesDiffer {
+            difference,
+            threshold: cmd.threshold.into_inner() * 100.0,
+            heat_map: heat_map.display().to_string(),
+        });
+    }
+    println!(
+        "{difference:.2}% of pixels differ from the reference (heat map: {})",
+        heat_map.display()
+    );
+    Ok(())
+}
+
+/// Execute a merge command.
+///
+/// Every input is parsed before merging, and every conflict is reported
+/// before failing, so one run surfaces all problems at once.
+fn execute_merge(cmd: MergeCommand) -> Result<()> {
+    use crate::infrastructure::parsing::merge::{SourcedModel, merge_models, to_yaml};
+    use std::fs;
+
+    let mut warnings = crate::infrastructure::warnings::Warnings::new();
+    let mut models = Vec::with_capacity(cmd.inputs.len());
+    for input in cmd.inputs.iter() {
+        let source = input.as_path_buf().display().to_string();
+        let content = fs::read_to_string(input.as_path_buf())?;
+        let model = crate::infrastructure::parsing::yaml_parser::parse_yaml_with_policy(
+            &content,
+            cmd.unknown_keys,
+            &mut warnings,
+        )
+        .map_err(|e| Error::InvalidArguments(format!("YAML parse error in {source}: {e}")))?;
+        models.push(SourcedModel { source, model });
+    }
+
+    let merged = merge_models(&models)
+        .map_err(|e| Error::InvalidArguments(format!("Merge failed:\n{e}")))?;
+    let yaml = to_yaml(&merged).map_err(|e| Error::InvalidArguments(e.to_string()))?;
+
+    match &cmd.output {
+        Some(output) => {
+            fs::write(output.path(), yaml)?;
+            println!(
+                "Merged {} models into {}",
+                models.len(),
+                output.path().display()
+            );
+        }
+        None => print!("{yaml}"),
+    }
+
+    if !warnings.is_empty() {
+        eprintln!("{warnings}");
+    }
+
+    Ok(())
+}
+
+fn execute_anonymize(cmd: AnonymizeCommand) -> Result<()> {
+    use crate::infrastructure::parsing::{anonymize::anonymize, merge::to_yaml};
+    use std::fs;
+
+    let mut warnings = crate::infrastructure::warnings::Warnings::new();
+    let content = fs::read_to_string(cmd.input.as_path_buf())?;
+    let model = crate::infrastructure::parsing::yaml_parser::parse_yaml_with_policy(
+        &content,
+        cmd.unknown_keys,
+        &mut warnings,
+    )
+    .map_err(|e| Error::InvalidArguments(format!("YAML parse error: {e}")))?;
+    let yaml = to_yaml(&anonymize(model)).map_err(|e| Error::InvalidArguments(e.to_string()))?;
+
+    match &cmd.output {
+        Some(output) => {
+            fs::write(output.path(), yaml)?;
+            println!(
+                "Anonymized {} into {}",
+                cmd.input.as_path_buf().display(),
+                output.path().display()
+            );
+        }
+        None => print!("{yaml}"),
+    }
+
+    if !warnings.is_empty() {
+        eprintln!("{warnings}");
+    }
+
+    Ok(())
+}
+
+fn execute_rename(cmd: RenameCommand) -> Result<()> {
+    use crate::infrastructure::parsing::rename::rename_entity;
+    use std::fs;
+
+    let content = fs::read_to_string(cmd.input.as_path_buf())?;
+    let renamed = rename_entity(&content, &cmd.from, &cmd.to)
+        .map_err(|e| Error::InvalidArguments(format!("Rename failed: {e}")))?;
+    let path = match &cmd.output {
+        Some(output) => output.path(),
+        None => cmd.input.as_path_buf().clone(),
+    };
+    fs::write(&path, renamed.source)?;
+    println!(
+        "Renamed {} to {} in {} places, writing {}",
+        cmd.from,
+        cmd.to,
+        renamed.replacements,
+        path.display()
+    );
+
+    Ok(())
+}
+
+/// Writes the Markdown output, referencing an SVG produced in the same run
+/// when one was requested and embedding the SVG inline otherwise.
+fn write_markdown(
+    diagram: &crate::diagram::EventModelDiagram,
+    svg_content: &str,
+    target: &OutputTarget,
+    outputs: &NonEmpty<OutputTarget>,
+    show_examples: bool,
+) -> Result<()> {
+    use crate::export::markdown::{
+        EmbedSvgOption, ImagePath, LinkStyle, MarkdownExportConfig, MarkdownExporter,
+        MarkdownFlavor,
+    };
+    use crate::infrastructure::types::NonEmptyString;
+
+    let sibling_svg = outputs
+        .iter()
+        .find(|other| other.format == OutputFormat::Svg)
+        .map(|svg| {
+            if svg.directory.as_path_buf() == target.directory.as_path_buf() {
+                svg.file_name.clone()
+            } else {
+                svg.path().display().to_string()
+            }
+        });
+    let embed_svg = match sibling_svg.and_then(|path| NonEmptyString::parse(path).ok()) {
+        Some(path) => EmbedSvgOption::Reference(ImagePath::new(path)),
+        None => EmbedSvgOption::Inline,
+    };
+
+    let exporter = MarkdownExporter::new(MarkdownExportConfig {
+        flavor: MarkdownFlavor::Github,
+        embed_svg,
+        link_style: LinkStyle::Relative,
+        show_examples,
+    });
+    let document = exporter
+        .export_diagram(diagram, svg_content)
+        .map_err(|e| Error::InvalidArguments(format!("Markdown export error: {e}")))?;
+    exporter
+        .write_to_file(&document, &target.path())
+        .map_err(|e| Error::InvalidArguments(format!("Markdown export error: {e}")))?;
+
     Ok(())
 }
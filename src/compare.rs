@@ -0,0 +1,256 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Perceptual comparison of a rendered diagram against a reference image.
+//!
+//! Acceptance tests used to end with opening the rendered SVG next to a
+//! reference screenshot and comparing them by eye. [`compare_svg_to_reference`]
+//! rasterizes the SVG at the reference's size and compares the two pixel by
+//! pixel in the YIQ color space, which weighs differences the way the eye
+//! does: anti-aliasing and slightly different font hinting barely register,
+//! while a moved box or a changed color does. The result tells what share of
+//! the pixels differ perceptibly and carries a heat map of where.
+
+use nutype::nutype;
+use resvg::tiny_skia::{Pixmap, PremultipliedColorU8, Transform};
+use resvg::usvg;
+use std::path::Path;
+
+/// Smallest YIQ distance, relative to the largest possible, at which two
+/// pixels count as different.
+const PERCEPTIBLE_DIFFERENCE: f64 = 0.1;
+
+/// Largest possible YIQ distance between two colors.
+const MAX_YIQ_DELTA: f64 = 35215.0;
+
+/// Opacity of the reference image drawn faintly under the heat map.
+const HEAT_MAP_BACKDROP_OPACITY: f64 = 0.1;
+
+/// The share of pixels, from 0 to 1, allowed to differ perceptibly.
+#[nutype(
+    validate(finite, greater_or_equal = 0.0, less_or_equal = 1.0),
+    derive(Debug, Clone, Copy, PartialEq, PartialOrd)
+)]
+pub struct DifferenceThreshold(f64);
+
+/// Errors that can occur while comparing images.
+#[derive(Debug, thiserror::Error)]
+pub enum CompareError {
+    /// The SVG could not be parsed.
+    #[error("Could not read SVG: {0}")]
+    Svg(#[from] usvg::Error),
+
+    /// A PNG could not be read or written.
+    #[error("Could not {action} PNG {path}: {reason}")]
+    Png {
+        /// Whether the PNG was being read or written.
+        action: &'static str,
+        /// The PNG's path.
+        path: String,
+        /// Why it failed.
+        reason: String,
+    },
+
+    /// An image has no pixels to compare.
+    #[error("Image has no area: {width}x{height}")]
+    EmptyImage {
+        /// Width in pixels.
+        width: u32,
+        /// Height in pixels.
+        height: u32,
+    },
+
+    /// The images to compare differ in size.
+    #[error("Images differ in size: {actual} and {expected}")]
+    SizeMismatch {
+        /// Size of the rendered image, as `WIDTHxHEIGHT`.
+        actual: String,
+        /// Size of the reference image, as `WIDTHxHEIGHT`.
+        expected: String,
+    },
+
+    /// A file could not be read.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// The outcome of comparing two images of the same size.
+#[derive(Debug, Clone)]
+pub struct ImageComparison {
+    differing: usize,
+    total: usize,
+    heat_map: Pixmap,
+}
+
+impl ImageComparison {
+    /// Returns the share of pixels, from 0 to 1, that differ perceptibly.
+    pub fn difference(&self) -> f64 {
+        self.differing as f64 / self.total as f64
+    }
+
+    /// Returns whether the share of differing pixels stays within `threshold`.
+    pub fn within(&self, threshold: DifferenceThreshold) -> bool {
+        self.difference() <= threshold.into_inner()
+    }
+
+    /// Returns the heat map: the reference drawn faintly, with differing
+    /// pixels from yellow (barely) to red (entirely different).
+    pub fn heat_map(&self) -> &Pixmap {
+        &self.heat_map
+    }
+
+    /// Writes the heat map as a PNG.
+    pub fn write_heat_map(&self, path: &Path) -> Result<(), CompareError> {
+        self.heat_map.save_png(path).map_err(|e| CompareError::Png {
+            action: "write",
+            path: path.display().to_string(),
+            reason: e.to_string(),
+        })
+    }
+}
+
+/// Rasterizes `svg` at the size of the reference image at `reference`, a PNG
+/// or another SVG, and compares the two.
+pub fn compare_svg_to_reference(
+    svg: &str,
+    reference: &Path,
+) -> Result<ImageComparison, CompareError> {
+    let is_svg = reference
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+    let expected = if is_svg {
+        rasterize(&std::fs::read_to_string(reference)?, None)?
+    } else {
+        Pixmap::load_png(reference).map_err(|e| CompareError::Png {
+            action: "read",
+            path: reference.display().to_string(),
+            reason: e.to_string(),
+        })?
+    };
+    let actual = rasterize(svg, Some((expected.width(), expected.height())))?;
+    compare_images(&actual, &expected)
+}
+
+/// Compares two images of the same size pixel by pixel, treating
+/// transparent areas as white.
+pub fn compare_images(actual: &Pixmap, expected: &Pixmap) -> Result<ImageComparison, CompareError> {
+    if (actual.width(), actual.height()) != (expected.width(), expected.height()) {
+        return Err(CompareError::SizeMismatch {
+            actual: format!("{}x{}", actual.width(), actual.height()),
+            expected: format!("{}x{}", expected.width(), expected.height()),
+        });
+    }
+
+    let mut heat_map = expected.clone();
+    let limit = MAX_YIQ_DELTA * PERCEPTIBLE_DIFFERENCE * PERCEPTIBLE_DIFFERENCE;
+    let mut differing = 0;
+    for ((a, e), out) in actual
+        .pixels()
+        .iter()
+        .zip(expected.pixels())
+        .zip(heat_map.pixels_mut())
+    {
+        let (a, e) = (over_white(*a), over_white(*e));
+        let delta = yiq_delta(a, e);
+        let color = if delta > limit {
+            differing += 1;
+            // Yellow for barely perceptible differences, red for the largest
+            let green = (255.0 * (1.0 - delta / MAX_YIQ_DELTA).clamp(0.0, 1.0)) as u8;
+            [255, green, 0]
+        } else {
+            let luma = y_of(e);
+            let faded = 255.0 - (255.0 - luma) * HEAT_MAP_BACKDROP_OPACITY;
+            [faded as u8; 3]
+        };
+        *out = PremultipliedColorU8::from_rgba(color[0], color[1], color[2], 255)
+            .unwrap_or(PremultipliedColorU8::TRANSPARENT);
+    }
+
+    Ok(ImageComparison {
+        differing,
+        total: actual.pixels().len(),
+        heat_map,
+    })
+}
+
+/// Renders an SVG with the system fonts, stretched to `size` if given and at
+/// its own size otherwise.
+fn rasterize(svg: &str, size: Option<(u32, u32)>) -> Result<Pixmap, CompareError> {
+    let mut options = usvg::Options::default();
+    options.fontdb_mut().load_system_fonts();
+    let tree = usvg::Tree::from_str(svg, &options)?;
+    let natural = tree.size();
+    let (width, height) = size.unwrap_or((
+        natural.width().ceil() as u32,
+        natural.height().ceil() as u32,
+    ));
+    let mut pixmap =
+        Pixmap::new(width, height).ok_or(CompareError::EmptyImage { width, height })?;
+    let transform = Transform::from_scale(
+        width as f32 / natural.width(),
+        height as f32 / natural.height(),
+    );
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+    Ok(pixmap)
+}
+
+/// Returns the color of a pixel composited over white.
+fn over_white(pixel: PremultipliedColorU8) -> [f64; 3] {
+    let uncovered = 255.0 - f64::from(pixel.alpha());
+    [
+        f64::from(pixel.red()) + uncovered,
+        f64::from(pixel.green()) + uncovered,
+        f64::from(pixel.blue()) + uncovered,
+    ]
+}
+
+/// Returns the luma of a color.
+fn y_of([r, g, b]: [f64; 3]) -> f64 {
+    r * 0.298_895_31 + g * 0.586_622_47 + b * 0.114_482_23
+}
+
+/// Returns the squared YIQ distance between two colors.
+fn yiq_delta(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let i = |[r, g, b]: [f64; 3]| r * 0.595_977_99 - g * 0.274_176_10 - b * 0.321_801_89;
+    let q = |[r, g, b]: [f64; 3]| r * 0.211_470_17 - g * 0.522_617_11 + b * 0.311_146_94;
+    let y = y_of(a) - y_of(b);
+    let i = i(a) - i(b);
+    let q = q(a) - q(b);
+    0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boxes(second_fill: &str) -> String {
+        format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 50">
+  <rect x="0" y="0" width="50" height="50" fill="#336699"/>
+  <rect x="50" y="0" width="50" height="50" fill="{second_fill}"/>
+</svg>"##
+        )
+    }
+
+    #[test]
+    fn measures_the_share_of_perceptibly_different_pixels() {
+        let expected = rasterize(&boxes("#ffffff"), Some((100, 50))).unwrap();
+
+        let same = compare_images(
+            &rasterize(&boxes("#fefefe"), Some((100, 50))).unwrap(),
+            &expected,
+        )
+        .unwrap();
+        assert_eq!(same.difference(), 0.0);
+
+        let changed = compare_images(
+            &rasterize(&boxes("#cc0000"), Some((100, 50))).unwrap(),
+            &expected,
+        )
+        .unwrap();
+        assert_eq!(changed.difference(), 0.5);
+        assert!(!changed.within(DifferenceThreshold::try_new(0.02).unwrap()));
+        let marked = changed.heat_map().pixel(75, 25).unwrap();
+        assert_eq!((marked.red(), marked.blue()), (255, 0));
+    }
+}
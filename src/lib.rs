@@ -3,29 +3,32 @@
 
 //! Event Modeler - A type-safe Event Modeling diagram generator.
 //!
-//! This crate provides tools for generating professional Event Modeling diagrams
-//! from YAML-based event model descriptions.
+//! This crate is the `event_modeler` command-line tool. Parsing, the domain
+//! model, layout, rendering, and exports live in [`event_modeler_core`],
+//! re-exported here whole; this crate adds the commands and the parts only
+//! they need, such as the terminal wizard, the diagram server, and image
+//! comparison. Tools embedding the renderer should depend on
+//! `event_modeler_core` directly.
+//!
+//! Every output format is built by default. Building with
+//! `--no-default-features` and a selection of the `pdf`, `png`, `html`,
+//! `sqlite`, and `libavoid` features leaves the others out; commands and
+//! flags needing a missing one say which feature to enable.
 
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
-// TODO: Re-enable missing_docs after implementing incremental rendering
-// #![deny(missing_docs)]
 #![deny(rustdoc::broken_intra_doc_links)]
 
-/// The version of Event Modeler, used as the default schema version for YAML files.
-/// This must match the version in Cargo.toml.
-pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+pub use event_modeler_core::*;
 
 /// Command-line interface.
 pub mod cli;
 
-/// Diagram generation and rendering.
-pub mod diagram;
-
-/// Event model domain types and operations.
-pub mod event_model;
+/// Comparison of rendered diagrams against reference images.
+#[cfg(feature = "png")]
+pub mod compare;
 
-/// Infrastructure and utility types.
-pub mod infrastructure;
+/// Kroki-compatible HTTP service rendering diagrams.
+pub mod server;
 
-/// Connector routing using libavoid.
-pub mod routing;
+/// Interactive slice building in the terminal.
+pub mod wizard;
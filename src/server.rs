@@ -0,0 +1,514 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! A diagram service speaking Kroki's HTTP conventions.
+//!
+//! [Kroki](https://kroki.io) renders many diagram languages behind one HTTP
+//! interface, and documentation tools and editor plugins talk to it rather
+//! than to each language's renderer. `event_modeler serve --kroki` answers
+//! the same requests for the `eventmodel` diagram type, so it can be set up
+//! as a Kroki companion service or used directly wherever a Kroki URL is
+//! configured:
+//!
+//! - `POST /eventmodel/{svg,png,pdf}` with the model as the body
+//! - `POST /` with a JSON body of `diagram_source`, `diagram_type`, and
+//!   `output_format`
+//! - `GET /eventmodel/{svg,png,pdf}/<source>`, the source deflated and
+//!   base64url-encoded as Kroki expects
+//! - `GET /health`
+//!
+//! Models that cannot be rendered are answered with `400 Bad Request` and
+//! the reason as plain text.
+//!
+//! The server is meant to be reachable by anyone who can reach a Kroki
+//! instance, so every request is bounded: connections are served by a fixed
+//! pool of workers and refused with `503 Service Unavailable` when its queue
+//! is full, reads and writes time out, headers and bodies are capped, as are
+//! decoded sources, and connectors stop being routed once a render has taken
+//! [`RENDER_TIMEOUT`].
+
+use event_modeler_core::diagram::RenderBudget;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{Receiver, TrySendError, sync_channel};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// The diagram type models are rendered under.
+pub const DIAGRAM_TYPE: &str = "eventmodel";
+
+/// Largest request body accepted, in bytes; also the largest source a
+/// `GET` request may inflate to.
+const MAX_BODY_LENGTH: usize = 8 * 1024 * 1024;
+
+/// Largest request line and headers accepted together, in bytes.
+const MAX_HEADER_LENGTH: u64 = 16 * 1024;
+
+/// How long a connection may take to send its request or read the response.
+const IO_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long a render may spend routing connectors before drawing the rest
+/// as straight lines.
+pub const RENDER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connections waiting for a worker, per worker, before new ones are refused.
+const QUEUED_PER_WORKER: usize = 4;
+
+/// Errors that stop the server.
+#[derive(Debug, thiserror::Error)]
+pub enum ServerError {
+    /// The address could not be listened on.
+    #[error("Could not listen on {address}: {source}")]
+    Bind {
+        /// The address asked for.
+        address: String,
+        /// Why listening failed.
+        source: std::io::Error,
+    },
+}
+
+/// A format diagrams are returned in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagramFormat {
+    /// SVG, as rendered.
+    Svg,
+    /// The SVG rasterized to PNG.
+    Png,
+    /// The SVG converted to a one-page PDF.
+    Pdf,
+}
+
+impl DiagramFormat {
+    /// Parses a Kroki output format name, e.g. `svg`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "svg" => Some(Self::Svg),
+            "png" => Some(Self::Png),
+            "pdf" => Some(Self::Pdf),
+            _ => None,
+        }
+    }
+
+    /// Returns the media type of responses in this format.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Svg => "image/svg+xml",
+            Self::Png => "image/png",
+            Self::Pdf => "application/pdf",
+        }
+    }
+}
+
+/// Renders a model in the YAML format to a diagram in `format`.
+///
+/// Each request is rendered on a worker thread by
+/// [`crate::render_svg_str_with_budget`], which shares nothing between
+/// renders, within [`RENDER_TIMEOUT`]. Misplaced entities are tolerated as
+/// they are by reports, while unrecognized keys are refused as they are by a
+/// render.
+pub fn render(source: &str, format: DiagramFormat) -> Result<Vec<u8>, String> {
+    let budget = RenderBudget::unlimited().with_timeout(RENDER_TIMEOUT);
+    let svg = crate::render_svg_str_with_budget(source, budget).map_err(|e| match e {
+        crate::Error::Parse(e) => format!("YAML parse error: {e}"),
+        crate::Error::Conversion(e) => format!("YAML conversion error: {e}"),
+        e => e.to_string(),
+    })?;
+
+    match format {
+        DiagramFormat::Svg => Ok(svg.into_bytes()),
+        #[cfg(feature = "png")]
+        DiagramFormat::Png => crate::export::PngExporter::new(Default::default())
+            .export_to_buffer(&svg)
+            .map_err(|e| e.to_string()),
+        #[cfg(not(feature = "png"))]
+        DiagramFormat::Png => Err("This server was built without PNG output".to_string()),
+        #[cfg(feature = "pdf")]
+        DiagramFormat::Pdf => crate::export::PdfExporter::new(Default::default())
+            .export_to_buffer(&svg)
+            .map_err(|e| e.to_string()),
+        #[cfg(not(feature = "pdf"))]
+        DiagramFormat::Pdf => Err("This server was built without PDF output".to_string()),
+    }
+}
+
+/// Serves diagrams at `address` until the process is stopped, one request
+/// per connection, on a worker per available CPU.
+pub fn serve(address: impl ToSocketAddrs + std::fmt::Display) -> Result<(), ServerError> {
+    let listener = TcpListener::bind(&address).map_err(|source| ServerError::Bind {
+        address: address.to_string(),
+        source,
+    })?;
+    if let Ok(local) = listener.local_addr() {
+        println!("Serving Kroki-compatible {DIAGRAM_TYPE} diagrams on http://{local}");
+    }
+
+    let workers = std::thread::available_parallelism().map_or(4, |n| n.get());
+    let (queue, connections) = sync_channel(workers * QUEUED_PER_WORKER);
+    let connections = Arc::new(Mutex::new(connections));
+    for _ in 0..workers {
+        let connections = Arc::clone(&connections);
+        std::thread::spawn(move || work(&connections));
+    }
+
+    for stream in listener.incoming().flatten() {
+        if stream.set_read_timeout(Some(IO_TIMEOUT)).is_err()
+            || stream.set_write_timeout(Some(IO_TIMEOUT)).is_err()
+        {
+            continue;
+        }
+        if let Err(TrySendError::Full(stream)) = queue.try_send(stream) {
+            let _ = write_response(&stream, &Response::text(503, "The server is busy"));
+        }
+    }
+    Ok(())
+}
+
+/// Serves the connections queued for the workers until the queue closes.
+fn work(connections: &Mutex<Receiver<TcpStream>>) {
+    loop {
+        let Ok(guard) = connections.lock() else {
+            return;
+        };
+        let Ok(stream) = guard.recv() else {
+            return;
+        };
+        drop(guard);
+        // A client that hangs up or stalls mid-request is no concern of the server
+        let _ = handle_connection(stream);
+    }
+}
+
+/// A response to send back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    /// HTTP status code.
+    pub status: u16,
+    /// Media type of the body.
+    pub content_type: &'static str,
+    /// The body.
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    fn text(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: message.into().into_bytes(),
+        }
+    }
+}
+
+/// Answers a request with the given method, path, and body.
+pub fn respond(method: &str, path: &str, body: &[u8]) -> Response {
+    let path = path.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    let (diagram_type, source, format) = match (method, segments.as_slice()) {
+        ("GET", ["health"]) => {
+            return Response {
+                status: 200,
+                content_type: "application/json",
+                body: br#"{"status":"pass"}"#.to_vec(),
+            };
+        }
+        ("POST", []) => match json_request(body) {
+            Ok(request) => request,
+            Err(response) => return response,
+        },
+        ("POST", [diagram_type, format]) => {
+            let Ok(source) = std::str::from_utf8(body) else {
+                return Response::text(400, "The model is not valid UTF-8");
+            };
+            (
+                (*diagram_type).to_string(),
+                source.to_string(),
+                (*format).to_string(),
+            )
+        }
+        ("GET", [diagram_type, format, encoded]) => match decode_source(encoded) {
+            Some(source) => ((*diagram_type).to_string(), source, (*format).to_string()),
+            None => {
+                return Response::text(
+                    400,
+                    "The diagram source is not deflated and base64url-encoded",
+                );
+            }
+        },
+        ("GET" | "POST", _) => return Response::text(404, "Not found"),
+        _ => return Response::text(405, "Method not allowed"),
+    };
+
+    let format = match (diagram_type.as_str(), DiagramFormat::from_name(&format)) {
+        (DIAGRAM_TYPE, Some(format)) => format,
+        (DIAGRAM_TYPE, None) => {
+            return Response::text(
+                400,
+                format!("Unsupported output format: {format}; expected svg, png, or pdf"),
+            );
+        }
+        (other, _) => {
+            return Response::text(
+                404,
+                format!("Unsupported diagram type: {other}; this service renders {DIAGRAM_TYPE}"),
+            );
+        }
+    };
+    match render(&source, format) {
+        Ok(body) => Response {
+            status: 200,
+            content_type: format.content_type(),
+            body,
+        },
+        Err(message) => Response::text(400, message),
+    }
+}
+
+/// Reads the diagram type, source, and output format of a JSON request.
+fn json_request(body: &[u8]) -> Result<(String, String, String), Response> {
+    #[derive(serde::Deserialize)]
+    struct Request {
+        diagram_source: String,
+        diagram_type: String,
+        #[serde(default = "default_format")]
+        output_format: String,
+    }
+    fn default_format() -> String {
+        "svg".to_string()
+    }
+
+    let request: Request = serde_json::from_slice(body)
+        .map_err(|e| Response::text(400, format!("Invalid JSON request: {e}")))?;
+    Ok((
+        request.diagram_type,
+        request.diagram_source,
+        request.output_format,
+    ))
+}
+
+/// Decodes a diagram source as Kroki encodes it in URLs: deflated with a
+/// zlib header, then base64url-encoded.
+fn decode_source(encoded: &str) -> Option<String> {
+    let compressed = decode_base64url(encoded)?;
+    let source =
+        miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(&compressed, MAX_BODY_LENGTH)
+            .ok()?;
+    String::from_utf8(source).ok()
+}
+
+/// Decodes base64url, with or without padding.
+fn decode_base64url(encoded: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in encoded.trim_end_matches('=').bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'-' | b'+' => 62,
+            b'_' | b'/' => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+/// Reads one request from a connection and writes the response.
+fn handle_connection(stream: TcpStream) -> std::io::Result<()> {
+    let mut reader = BufReader::new(&stream);
+    // The request line and headers share one allowance
+    let mut head = (&mut reader).take(MAX_HEADER_LENGTH);
+    let mut request_line = String::new();
+    head.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return write_response(&stream, &Response::text(400, "Malformed request"));
+    };
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        let read = head.read_line(&mut header)?;
+        if !header.ends_with('\n') && head.limit() == 0 {
+            return write_response(&stream, &Response::text(431, "The headers are too large"));
+        }
+        if read == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    if content_length > MAX_BODY_LENGTH {
+        return write_response(&stream, &Response::text(413, "The model is too large"));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    write_response(&stream, &respond(method, path, &body))
+}
+
+/// Writes a response and closes the connection.
+fn write_response(mut stream: &TcpStream, response: &Response) -> std::io::Result<()> {
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {reason}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    )?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODEL: &str = r#"workflow: Ordering
+swimlanes:
+  - backend: "Backend"
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+slices:
+  - name: Placing
+    connections:
+      - PlaceOrder -> OrderPlaced
+"#;
+
+    #[test]
+    #[cfg(all(feature = "png", feature = "pdf"))]
+    fn posted_model_is_rendered_in_the_requested_format() {
+        let svg = respond("POST", "/eventmodel/svg", MODEL.as_bytes());
+        assert_eq!(svg.status, 200);
+        assert_eq!(svg.content_type, "image/svg+xml");
+        assert!(String::from_utf8(svg.body).unwrap().contains("<svg"));
+
+        let png = respond("POST", "/eventmodel/png", MODEL.as_bytes());
+        assert_eq!(png.status, 200);
+        assert!(png.body.starts_with(b"\x89PNG"));
+
+        let pdf = respond("POST", "/eventmodel/pdf", MODEL.as_bytes());
+        assert_eq!(pdf.status, 200);
+        assert!(pdf.body.starts_with(b"%PDF-"));
+    }
+
+    #[test]
+    fn json_requests_name_type_and_format() {
+        let body = serde_json::json!({
+            "diagram_source": MODEL,
+            "diagram_type": "eventmodel",
+            "output_format": "svg",
+        })
+        .to_string();
+
+        let response = respond("POST", "/", body.as_bytes());
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.content_type, "image/svg+xml");
+    }
+
+    #[test]
+    fn encoded_get_requests_are_decoded() {
+        let compressed = miniz_oxide::deflate::compress_to_vec_zlib(MODEL.as_bytes(), 9);
+        let alphabet = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+        let mut encoded = String::new();
+        for chunk in compressed.chunks(3) {
+            let n = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |n, (i, byte)| n | u32::from(*byte) << (16 - 8 * i));
+            for i in 0..=chunk.len() {
+                encoded.push(alphabet[(n >> (18 - 6 * i) & 63) as usize] as char);
+            }
+        }
+
+        let response = respond("GET", &format!("/eventmodel/svg/{encoded}"), &[]);
+
+        assert_eq!(response.status, 200);
+    }
+
+    #[test]
+    fn encoded_sources_inflating_past_the_body_limit_are_refused() {
+        let bomb = miniz_oxide::deflate::compress_to_vec_zlib(&vec![b' '; MAX_BODY_LENGTH + 1], 9);
+        let encoded: String = bomb
+            .chunks(3)
+            .flat_map(|chunk| {
+                let n = chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0u32, |n, (i, byte)| n | u32::from(*byte) << (16 - 8 * i));
+                (0..=chunk.len()).map(move |i| {
+                    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_"
+                        [(n >> (18 - 6 * i) & 63) as usize] as char
+                })
+            })
+            .collect();
+
+        assert_eq!(decode_source(&encoded), None);
+    }
+
+    #[test]
+    fn oversized_headers_are_refused() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let client = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            // Exactly the allowance, so the server reads all that is sent
+            let mut request = "GET /health HTTP/1.1\r\nX-Padding: ".to_string();
+            request.push_str(&"x".repeat(MAX_HEADER_LENGTH as usize - request.len()));
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            let _ = stream.read_to_string(&mut response);
+            response
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        handle_connection(stream).unwrap();
+
+        assert!(client.join().unwrap().starts_with("HTTP/1.1 431"));
+    }
+
+    #[test]
+    fn invalid_requests_are_refused_with_a_reason() {
+        let unknown_type = respond("POST", "/plantuml/svg", b"@startuml");
+        assert_eq!(unknown_type.status, 404);
+
+        let unknown_format = respond("POST", "/eventmodel/jpeg", MODEL.as_bytes());
+        assert_eq!(unknown_format.status, 400);
+
+        let invalid_model = respond("POST", "/eventmodel/svg", b"workflow: [");
+        assert_eq!(invalid_model.status, 400);
+        assert!(
+            String::from_utf8(invalid_model.body)
+                .unwrap()
+                .contains("YAML parse error")
+        );
+    }
+}
@@ -0,0 +1,718 @@
+// Copyright (c) 2025 John Wilger
+// SPDX-License-Identifier: MIT
+
+//! Interactive assembly of slices in the terminal.
+//!
+//! `event_modeler wizard` lists the entities a model declares and lets the
+//! user chain them into slice connections with the arrow keys. Each
+//! connection is checked as soon as it is added, by running the model with
+//! the slice in progress through the same parsing and conversion as a
+//! render, so a wrong link is refused on the spot rather than at the next
+//! render. Finished slices are appended to the model's `slices:` section,
+//! leaving the rest of the file as written.
+
+use crate::event_model::yaml_types::{ComponentType, YamlEventModel};
+use crate::infrastructure::parsing::{yaml_converter, yaml_parser};
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+
+/// Name the slice in progress goes by while its connections are checked.
+const DRAFT_SLICE_NAME: &str = "(slice in progress)";
+
+/// Key bindings shown at the top of the screen.
+const HELP: &str =
+    "Up/Down choose  Enter add  Backspace undo  Tab new chain  s name slice  w write  q quit";
+
+/// Errors that stop the wizard.
+#[derive(Debug, thiserror::Error)]
+pub enum WizardError {
+    /// Standard input or output is not a terminal.
+    #[error("The wizard needs an interactive terminal")]
+    NotATerminal,
+
+    /// The model cannot be loaded, so there is nothing to build slices from.
+    #[error("The model is not valid: {0}")]
+    InvalidModel(String),
+
+    /// Reading the model, writing it back, or driving the terminal failed.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// An entity that can be picked as either end of a connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityChoice {
+    /// How connections refer to the entity, e.g. `OrderForm.Submit`.
+    pub reference: String,
+    /// The entity type, e.g. `command`.
+    pub kind: &'static str,
+}
+
+/// A slice assembled in the wizard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DraftSlice {
+    /// Display name of the slice.
+    pub name: String,
+    /// Connections in the order they were added, e.g. `PlaceOrder -> OrderPlaced`.
+    pub connections: Vec<String>,
+}
+
+/// A key press the wizard responds to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// Move the selection up.
+    Up,
+    /// Move the selection down.
+    Down,
+    /// Add the selected entity, or confirm a slice name.
+    Enter,
+    /// Undo the last entity, or delete a character of a slice name.
+    Backspace,
+    /// Start a new chain within the same slice.
+    Tab,
+    /// Discard the slice in progress, or quit when there is none.
+    Esc,
+    /// A typed character.
+    Char(char),
+}
+
+/// What the wizard should do after a key press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// Wait for the next key.
+    Continue,
+    /// Write the added slices to the model and stop.
+    Write,
+    /// Stop without writing.
+    Quit,
+}
+
+/// Whether keys pick entities or type the name of the slice in progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Pick,
+    Name,
+}
+
+/// State of a wizard session over one model.
+#[derive(Debug, Clone)]
+pub struct Wizard {
+    source: String,
+    entities: Vec<EntityChoice>,
+    existing_slices: Vec<String>,
+    cursor: usize,
+    /// Entities of the current chain, each with whether adding it added a
+    /// connection.
+    chain: Vec<(usize, bool)>,
+    connections: Vec<String>,
+    slices: Vec<DraftSlice>,
+    mode: Mode,
+    name: String,
+    message: Option<String>,
+}
+
+impl Wizard {
+    /// Starts a session over the model written in `source`.
+    pub fn new(source: String) -> Result<Self, WizardError> {
+        let model = load(&source).map_err(WizardError::InvalidModel)?;
+        let entities = entity_choices(&model);
+        if entities.is_empty() {
+            return Err(WizardError::InvalidModel(
+                "it declares no entities to connect".to_string(),
+            ));
+        }
+        let existing_slices = model
+            .slices
+            .iter()
+            .map(|slice| slice.name.clone().into_inner().into_inner())
+            .collect();
+        Ok(Self {
+            source,
+            entities,
+            existing_slices,
+            cursor: 0,
+            chain: Vec::new(),
+            connections: Vec::new(),
+            slices: Vec::new(),
+            mode: Mode::Pick,
+            name: String::new(),
+            message: None,
+        })
+    }
+
+    /// Returns the entities that can be picked, in the order listed.
+    pub fn entities(&self) -> &[EntityChoice] {
+        &self.entities
+    }
+
+    /// Returns the slices added so far.
+    pub fn slices(&self) -> &[DraftSlice] {
+        &self.slices
+    }
+
+    /// Returns the model with the added slices appended to its `slices:`.
+    pub fn result(&self) -> String {
+        append_slices(&self.source, &self.slices)
+    }
+
+    /// Responds to a key press.
+    pub fn handle(&mut self, key: Key) -> Outcome {
+        self.message = None;
+        match self.mode {
+            Mode::Pick => self.handle_pick(key),
+            Mode::Name => {
+                self.handle_name(key);
+                Outcome::Continue
+            }
+        }
+    }
+
+    fn handle_pick(&mut self, key: Key) -> Outcome {
+        match key {
+            Key::Up => self.cursor = self.cursor.saturating_sub(1),
+            Key::Down => self.cursor = (self.cursor + 1).min(self.entities.len() - 1),
+            Key::Enter => self.add_selected(),
+            Key::Backspace => {
+                if let Some((_, true)) = self.chain.pop() {
+                    self.connections.pop();
+                }
+            }
+            Key::Tab => self.chain.clear(),
+            Key::Char('s') if self.connections.is_empty() => {
+                self.message = Some("Connect at least two entities first".to_string());
+            }
+            Key::Char('s') => {
+                self.mode = Mode::Name;
+                self.name.clear();
+            }
+            Key::Char('w') if !self.connections.is_empty() => {
+                self.message =
+                    Some("Press s to name the slice in progress, or Esc to discard it".to_string());
+            }
+            Key::Char('w') => return Outcome::Write,
+            Key::Char('q') => return Outcome::Quit,
+            Key::Esc if self.chain.is_empty() && self.connections.is_empty() => {
+                return Outcome::Quit;
+            }
+            Key::Esc => {
+                self.chain.clear();
+                self.connections.clear();
+                self.message = Some("Discarded the slice in progress".to_string());
+            }
+            Key::Char(_) => {}
+        }
+        Outcome::Continue
+    }
+
+    fn handle_name(&mut self, key: Key) {
+        match key {
+            Key::Char(c) => self.name.push(c),
+            Key::Backspace => {
+                self.name.pop();
+            }
+            Key::Esc => self.mode = Mode::Pick,
+            Key::Enter => self.add_slice(),
+            Key::Up | Key::Down | Key::Tab => {}
+        }
+    }
+
+    /// Adds the selected entity to the chain, connecting it to the chain's
+    /// last entity if the model accepts that connection.
+    fn add_selected(&mut self) {
+        let Some(&(last, _)) = self.chain.last() else {
+            self.chain.push((self.cursor, false));
+            return;
+        };
+        let connection = format!(
+            "{} -> {}",
+            self.entities[last].reference, self.entities[self.cursor].reference
+        );
+        if self.connections.contains(&connection) {
+            self.message = Some(format!("{connection} is already in this slice"));
+            return;
+        }
+        let mut connections = self.connections.clone();
+        connections.push(connection);
+        let draft = DraftSlice {
+            name: DRAFT_SLICE_NAME.to_string(),
+            connections,
+        };
+        match self.check(draft) {
+            Ok(draft) => {
+                self.connections = draft.connections;
+                self.chain.push((self.cursor, true));
+            }
+            Err(message) => self.message = Some(message),
+        }
+    }
+
+    /// Names the slice in progress and adds it if the model accepts it.
+    fn add_slice(&mut self) {
+        let name = self.name.trim().to_string();
+        if name.is_empty() {
+            self.message = Some("A slice needs a name".to_string());
+            return;
+        }
+        if self.existing_slices.contains(&name) || self.slices.iter().any(|s| s.name == name) {
+            self.message = Some(format!("There is already a slice named '{name}'"));
+            return;
+        }
+        let draft = DraftSlice {
+            name,
+            connections: self.connections.clone(),
+        };
+        match self.check(draft) {
+            Ok(draft) => {
+                self.message = Some(format!("Added slice '{}'", draft.name));
+                self.slices.push(draft);
+                self.chain.clear();
+                self.connections.clear();
+                self.mode = Mode::Pick;
+            }
+            Err(message) => self.message = Some(message),
+        }
+    }
+
+    /// Loads the model with the added slices and `draft`, returning `draft`
+    /// if it loads.
+    fn check(&self, draft: DraftSlice) -> Result<DraftSlice, String> {
+        let mut slices = self.slices.clone();
+        slices.push(draft);
+        load(&append_slices(&self.source, &slices))?;
+        Ok(slices.pop().expect("the draft was just pushed"))
+    }
+
+    /// Lays out the screen as lines fitting `height` rows, scrolling the
+    /// entity list to keep the selection visible.
+    pub fn screen(&self, height: usize) -> Vec<String> {
+        let mut lines = vec![HELP.to_string()];
+        let added: Vec<&str> = self.slices.iter().map(|s| s.name.as_str()).collect();
+        lines.push(match added.as_slice() {
+            [] => "Slices added: none".to_string(),
+            names => format!("Slices added: {}", names.join(", ")),
+        });
+        lines.push("Slice in progress:".to_string());
+        for connection in &self.connections {
+            lines.push(format!("  {connection}"));
+        }
+        let chain: Vec<&str> = self
+            .chain
+            .iter()
+            .map(|&(index, _)| self.entities[index].reference.as_str())
+            .collect();
+        lines.push(match chain.as_slice() {
+            [] => "Chain: pick an entity to start from".to_string(),
+            chain => format!("Chain: {}", chain.join(" -> ")),
+        });
+        if self.mode == Mode::Name {
+            lines.push(format!("Slice name: {}_", self.name));
+        }
+        lines.push(String::new());
+
+        // Leave a row for the message
+        let rows = height.saturating_sub(lines.len() + 1).max(1);
+        let first = self.cursor.saturating_sub(rows - 1);
+        for (index, entity) in self.entities.iter().enumerate().skip(first).take(rows) {
+            let marker = if index == self.cursor { '>' } else { ' ' };
+            lines.push(format!("{marker} {:<10} {}", entity.kind, entity.reference));
+        }
+        if let Some(message) = &self.message {
+            lines.push(message.clone());
+        }
+        lines
+    }
+}
+
+/// Runs the wizard on the model at `path`, writing the added slices back to
+/// it, and returns how many were added.
+pub fn run(path: &Path) -> Result<usize, WizardError> {
+    use crossterm::{cursor, execute, terminal};
+
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return Err(WizardError::NotATerminal);
+    }
+    let mut wizard = Wizard::new(std::fs::read_to_string(path)?)?;
+
+    terminal::enable_raw_mode()?;
+    let mut stdout = std::io::stdout();
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+    let outcome = drive(&mut wizard, &mut stdout);
+    // Restore the terminal whatever happened in the session
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+
+    match outcome? {
+        Outcome::Write if !wizard.slices().is_empty() => {
+            std::fs::write(path, wizard.result())?;
+            Ok(wizard.slices().len())
+        }
+        _ => Ok(0),
+    }
+}
+
+/// Draws the wizard and feeds it key presses until it is done.
+fn drive(wizard: &mut Wizard, stdout: &mut std::io::Stdout) -> std::io::Result<Outcome> {
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+    use crossterm::{cursor, queue, terminal};
+
+    loop {
+        let (_, height) = terminal::size()?;
+        queue!(
+            stdout,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo(0, 0)
+        )?;
+        for line in wizard.screen(usize::from(height)) {
+            write!(stdout, "{line}\r\n")?;
+        }
+        stdout.flush()?;
+
+        let Event::Key(press) = event::read()? else {
+            continue;
+        };
+        if press.kind != KeyEventKind::Press {
+            continue;
+        }
+        let key = match press.code {
+            KeyCode::Up => Key::Up,
+            KeyCode::Down => Key::Down,
+            KeyCode::Enter => Key::Enter,
+            KeyCode::Backspace => Key::Backspace,
+            KeyCode::Tab => Key::Tab,
+            KeyCode::Esc => Key::Esc,
+            KeyCode::Char(c) => Key::Char(c),
+            _ => continue,
+        };
+        match wizard.handle(key) {
+            Outcome::Continue => {}
+            outcome => return Ok(outcome),
+        }
+    }
+}
+
+/// Parses and converts a model as a render would, describing why it fails.
+fn load(source: &str) -> Result<YamlEventModel, String> {
+    let parsed = yaml_parser::parse_yaml(source).map_err(|e| e.to_string())?;
+    yaml_converter::convert_yaml_to_domain(parsed).map_err(|e| e.to_string())
+}
+
+/// Lists the entities of a model in the order Event Modeling flows: views
+/// and their components, commands, events, projections, queries, and
+/// automations, each by name.
+fn entity_choices(model: &YamlEventModel) -> Vec<EntityChoice> {
+    fn sorted(names: impl Iterator<Item = String>, kind: &'static str) -> Vec<EntityChoice> {
+        let mut names: Vec<String> = names.collect();
+        names.sort();
+        names
+            .into_iter()
+            .map(|reference| EntityChoice { reference, kind })
+            .collect()
+    }
+
+    let mut views: Vec<_> = model.views.iter().collect();
+    views.sort_by_key(|(name, _)| (*name).clone().into_inner().into_inner());
+    let mut choices = Vec::new();
+    for (name, view) in views {
+        let name = name.clone().into_inner().into_inner();
+        for component in view.components.iter() {
+            let component_name = component.name.clone().into_inner().into_inner();
+            match &component.component_type {
+                ComponentType::Simple(_) => choices.push(EntityChoice {
+                    reference: format!("{name}.{component_name}"),
+                    kind: "view",
+                }),
+                ComponentType::Form { actions, .. } => {
+                    for action in actions.iter() {
+                        choices.push(EntityChoice {
+                            reference: format!(
+                                "{name}.{component_name}.{}",
+                                action.clone().into_inner().into_inner()
+                            ),
+                            kind: "view",
+                        });
+                    }
+                }
+            }
+        }
+        choices.push(EntityChoice {
+            reference: name,
+            kind: "view",
+        });
+    }
+    choices.extend(sorted(
+        model
+            .commands
+            .keys()
+            .map(|n| n.clone().into_inner().into_inner()),
+        "command",
+    ));
+    choices.extend(sorted(
+        model
+            .events
+            .keys()
+            .map(|n| n.clone().into_inner().into_inner()),
+        "event",
+    ));
+    choices.extend(sorted(
+        model
+            .projections
+            .keys()
+            .map(|n| n.clone().into_inner().into_inner()),
+        "projection",
+    ));
+    choices.extend(sorted(
+        model
+            .queries
+            .keys()
+            .map(|n| n.clone().into_inner().into_inner()),
+        "query",
+    ));
+    choices.extend(sorted(
+        model
+            .automations
+            .keys()
+            .map(|n| n.clone().into_inner().into_inner()),
+        "automation",
+    ));
+    choices
+}
+
+/// Appends `slices` to the `slices:` section of a model, or adds the section
+/// at the end if the model has none. Everything else is kept as written.
+fn append_slices(source: &str, slices: &[DraftSlice]) -> String {
+    if slices.is_empty() {
+        return source.to_string();
+    }
+    let lines: Vec<&str> = source.lines().collect();
+    let Some(start) = lines
+        .iter()
+        .position(|line| matches!(line.trim_end(), "slices:" | "slices: []"))
+    else {
+        let mut yaml = source.to_string();
+        if !yaml.is_empty() && !yaml.ends_with('\n') {
+            yaml.push('\n');
+        }
+        yaml.push_str("\nslices:\n");
+        yaml.push_str(&slice_entries(slices, "  "));
+        return yaml;
+    };
+
+    // The section runs until the next top-level key
+    let mut end = start + 1;
+    while end < lines.len()
+        && (lines[end].is_empty() || lines[end].starts_with([' ', '\t', '#', '-']))
+    {
+        end += 1;
+    }
+    // New slices go after the last one, before any trailing blank lines or
+    // comments that separate the section from the next key
+    let mut insert = end;
+    while insert > start + 1
+        && (lines[insert - 1].trim().is_empty() || lines[insert - 1].starts_with('#'))
+    {
+        insert -= 1;
+    }
+    let indent = lines[start + 1..insert]
+        .iter()
+        .find_map(|line| {
+            let item = line.trim_start();
+            item.starts_with('-')
+                .then(|| &line[..line.len() - item.len()])
+        })
+        .unwrap_or("  ");
+
+    let mut yaml = String::new();
+    for line in &lines[..start] {
+        yaml.push_str(line);
+        yaml.push('\n');
+    }
+    yaml.push_str("slices:\n");
+    for line in &lines[start + 1..insert] {
+        yaml.push_str(line);
+        yaml.push('\n');
+    }
+    yaml.push_str(&slice_entries(slices, indent));
+    for line in &lines[insert..] {
+        yaml.push_str(line);
+        yaml.push('\n');
+    }
+    yaml
+}
+
+/// Writes slices as entries of a `slices:` list indented by `indent`.
+fn slice_entries(slices: &[DraftSlice], indent: &str) -> String {
+    let mut yaml = String::new();
+    for slice in slices {
+        // A JSON string is a valid double-quoted YAML scalar
+        let name = serde_json::to_string(&slice.name).unwrap_or_default();
+        yaml.push_str(&format!("{indent}- name: {name}\n{indent}  connections:\n"));
+        for connection in &slice.connections {
+            yaml.push_str(&format!("{indent}    - {connection}\n"));
+        }
+    }
+    yaml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODEL: &str = r#"workflow: Ordering
+swimlanes:
+  - ux: "UX"
+  - backend: "Backend"
+
+views:
+  OrderForm:
+    description: "Form to place an order"
+    swimlane: ux
+    components:
+      - Submit: Button
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: ux
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+slices:
+  - name: Browsing
+    connections:
+      - OrderForm.Submit -> PlaceOrder
+
+# Nothing else yet
+"#;
+
+    fn select(wizard: &mut Wizard, reference: &str) {
+        let index = wizard
+            .entities()
+            .iter()
+            .position(|entity| entity.reference == reference)
+            .expect("entity is listed");
+        while wizard.cursor > index {
+            wizard.handle(Key::Up);
+        }
+        while wizard.cursor < index {
+            wizard.handle(Key::Down);
+        }
+        wizard.handle(Key::Enter);
+    }
+
+    fn type_name(wizard: &mut Wizard, name: &str) {
+        wizard.handle(Key::Char('s'));
+        for c in name.chars() {
+            wizard.handle(Key::Char(c));
+        }
+        wizard.handle(Key::Enter);
+    }
+
+    #[test]
+    fn lists_entities_with_view_components_in_flow_order() {
+        let wizard = Wizard::new(MODEL.to_string()).unwrap();
+
+        let references: Vec<&str> = wizard
+            .entities()
+            .iter()
+            .map(|entity| entity.reference.as_str())
+            .collect();
+        assert_eq!(
+            references,
+            ["OrderForm.Submit", "OrderForm", "PlaceOrder", "OrderPlaced"]
+        );
+    }
+
+    #[test]
+    fn chained_entities_become_a_named_slice() {
+        let mut wizard = Wizard::new(MODEL.to_string()).unwrap();
+
+        select(&mut wizard, "OrderForm.Submit");
+        select(&mut wizard, "PlaceOrder");
+        select(&mut wizard, "OrderPlaced");
+        type_name(&mut wizard, "Placing");
+
+        assert_eq!(
+            wizard.slices(),
+            [DraftSlice {
+                name: "Placing".to_string(),
+                connections: vec![
+                    "OrderForm.Submit -> PlaceOrder".to_string(),
+                    "PlaceOrder -> OrderPlaced".to_string(),
+                ],
+            }]
+        );
+        let model = load(&wizard.result()).unwrap();
+        assert_eq!(model.slices.len(), 2);
+    }
+
+    #[test]
+    fn connections_the_model_rejects_are_refused_immediately() {
+        let mut wizard = Wizard::new(MODEL.to_string()).unwrap();
+
+        select(&mut wizard, "OrderPlaced");
+        select(&mut wizard, "OrderPlaced");
+
+        assert!(wizard.connections.is_empty());
+        let message = wizard.message.clone().unwrap_or_default();
+        assert!(message.contains("loops back"), "{message}");
+    }
+
+    #[test]
+    fn backspace_undoes_the_last_connection() {
+        let mut wizard = Wizard::new(MODEL.to_string()).unwrap();
+
+        select(&mut wizard, "PlaceOrder");
+        select(&mut wizard, "OrderPlaced");
+        wizard.handle(Key::Backspace);
+
+        assert!(wizard.connections.is_empty());
+        assert_eq!(wizard.chain.len(), 1);
+    }
+
+    #[test]
+    fn slice_names_must_be_new() {
+        let mut wizard = Wizard::new(MODEL.to_string()).unwrap();
+
+        select(&mut wizard, "PlaceOrder");
+        select(&mut wizard, "OrderPlaced");
+        type_name(&mut wizard, "Browsing");
+
+        assert!(wizard.slices().is_empty());
+        assert_eq!(
+            wizard.message.as_deref(),
+            Some("There is already a slice named 'Browsing'")
+        );
+    }
+
+    #[test]
+    fn slices_are_appended_to_the_existing_section() {
+        let slices = [DraftSlice {
+            name: "Placing".to_string(),
+            connections: vec!["PlaceOrder -> OrderPlaced".to_string()],
+        }];
+
+        let yaml = append_slices(MODEL, &slices);
+
+        assert!(yaml.contains(
+            "      - OrderForm.Submit -> PlaceOrder\n  - name: \"Placing\"\n    connections:\n      - PlaceOrder -> OrderPlaced\n\n# Nothing else yet\n"
+        ));
+    }
+
+    #[test]
+    fn a_slices_section_is_added_when_missing() {
+        let slices = [DraftSlice {
+            name: "Placing".to_string(),
+            connections: vec!["PlaceOrder -> OrderPlaced".to_string()],
+        }];
+
+        let yaml = append_slices("workflow: Ordering", &slices);
+
+        assert_eq!(
+            yaml,
+            "workflow: Ordering\n\nslices:\n  - name: \"Placing\"\n    connections:\n      - PlaceOrder -> OrderPlaced\n"
+        );
+    }
+}
@@ -298,3 +298,5192 @@ fn test_nonexistent_file_shows_error() {
             || stderr.contains("must have .eventmodel extension and exist")
     );
 }
+
+#[test]
+fn test_multiple_outputs_from_single_invocation() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_multi_output");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let svg_path = temp_dir.join("model.svg");
+    let md_path = temp_dir.join("model.md");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "-o",
+            svg_path.to_str().unwrap(),
+            "--formats",
+            "md",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let svg_content = fs::read_to_string(&svg_path).expect("Failed to read SVG output");
+    assert!(svg_content.contains("<svg"));
+
+    let md_content = fs::read_to_string(&md_path).expect("Failed to read Markdown output");
+    assert!(md_content.starts_with("# User Account Signup"));
+    assert!(md_content.contains("![User Account Signup](model.svg)"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_warning_report_for_orphaned_entities_and_unresolved_connections() {
+    let test_input = r#"workflow: Warning Report
+
+swimlanes:
+  - backend: "Backend"
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+  OrderArchived:
+    description: "An order was archived"
+    swimlane: backend
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+      - OrderPlaced -> ShipOrder
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_warnings");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("warnings.eventmodel");
+    let output_path = temp_dir.join("warnings.svg");
+    fs::write(&input_path, test_input).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            output_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "CLI failed: {stderr}");
+    assert!(stderr.contains("Warning: [conversion] Could not infer the type of 'ShipOrder'"));
+    assert!(stderr.contains("Warning: [layout] 'OrderArchived' is not referenced by any slice"));
+    assert!(stderr.contains("Warning: [rendering] Skipped connection OrderPlaced -> ShipOrder"));
+    assert!(!stderr.contains("'PlaceOrder'"));
+    assert!(stderr.contains("3 warnings"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_unknown_keys_fail_unless_lenient() {
+    let test_input = r#"workflow: Strict Keys
+
+swimlanes:
+  - backend: "Backend"
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+    dta:
+      order_id: OrderId
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_strict_keys");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("strict.eventmodel");
+    let output_path = temp_dir.join("strict.svg");
+    fs::write(&input_path, test_input).expect("Failed to write test input file");
+
+    let run = |extra_args: &[&str]| {
+        Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--",
+                input_path.to_str().unwrap(),
+                "-o",
+                output_path.to_str().unwrap(),
+            ])
+            .args(extra_args)
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let strict = run(&[]);
+    let stderr = String::from_utf8_lossy(&strict.stderr);
+    assert!(!strict.status.success());
+    assert!(stderr.contains("unknown key 'events.OrderPlaced.dta' at line 10, column 5"));
+    assert!(stderr.contains("--lenient"));
+
+    let lenient = run(&["--lenient"]);
+    let stderr = String::from_utf8_lossy(&lenient.stderr);
+    assert!(lenient.status.success(), "CLI failed: {stderr}");
+    assert!(stderr.contains(
+        "Warning: [parsing] Ignored unknown key 'events.OrderPlaced.dta' at line 10, column 5"
+    ));
+    assert!(output_path.exists());
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_features_select_flagged_content() {
+    let test_input = r#"workflow: Feature Flags
+
+swimlanes:
+  - backend: "Backend"
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+  GiftWrap:
+    description: "Wrap the order as a gift"
+    swimlane: backend
+    feature: gifting
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+      - connection: OrderPlaced -> GiftWrap
+        feature: gifting
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_features");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("features.eventmodel");
+    let output_path = temp_dir.join("features.svg");
+    fs::write(&input_path, test_input).expect("Failed to write test input file");
+
+    let render = |extra_args: &[&str]| {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--",
+                input_path.to_str().unwrap(),
+                "-o",
+                output_path.to_str().unwrap(),
+            ])
+            .args(extra_args)
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "CLI failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        fs::read_to_string(&output_path).expect("Failed to read output SVG")
+    };
+
+    let base = render(&[]);
+    assert!(base.contains("Place"));
+    assert!(!base.contains("Gift"));
+
+    let gifting = render(&["--features", "gifting"]);
+    assert!(gifting.contains("Gift"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_merge_combines_team_files_and_reports_conflicts() {
+    let team_a = r#"workflow: Orders
+swimlanes:
+  - backend
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+"#;
+    let team_b = r#"workflow: Shipping
+swimlanes:
+  - backend: "Backend"
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+  OrderShipped:
+    description: "An order was shipped"
+    swimlane: backend
+slices:
+  - name: Shipping
+    connections:
+      - OrderPlaced -> OrderShipped
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_merge");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let team_a_path = temp_dir.join("teamA.yaml");
+    let team_b_path = temp_dir.join("teamB.yaml");
+    let combined_path = temp_dir.join("combined.yaml");
+    fs::write(&team_a_path, team_a).expect("Failed to write team A model");
+    fs::write(&team_b_path, team_b).expect("Failed to write team B model");
+
+    let merge = || {
+        Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--",
+                "merge",
+                team_a_path.to_str().unwrap(),
+                team_b_path.to_str().unwrap(),
+                "-o",
+                combined_path.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = merge();
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let combined = fs::read_to_string(&combined_path).expect("Failed to read merged model");
+    assert!(combined.contains("workflow: Orders"));
+    assert!(combined.contains("- backend: Backend"));
+    assert!(combined.contains("OrderShipped:"));
+    assert!(combined.contains("- name: Shipping"));
+
+    fs::write(
+        &team_b_path,
+        team_b.replace("An order was placed", "Order placed"),
+    )
+    .expect("Failed to write team B model");
+    let output = merge();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("Conflict: event 'OrderPlaced' is defined differently in"));
+    assert!(stderr.contains("1 conflict"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_ownership_badges_and_report() {
+    let test_input = r#"workflow: Ownership
+
+swimlanes:
+  - ui: "Storefront"
+  - payments:
+      name: "Payments"
+      owner: team-payments
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: ui
+  ChargeCard:
+    description: "Charge the card"
+    swimlane: payments
+
+events:
+  CardCharged:
+    description: "The card was charged"
+    swimlane: payments
+    owner: "R&D <core>"
+
+slices:
+  - name: Checkout
+    connections:
+      - PlaceOrder -> ChargeCard
+      - ChargeCard -> CardCharged
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_owners");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("owners.eventmodel");
+    let output_path = temp_dir.join("owners.svg");
+    fs::write(&input_path, test_input).expect("Failed to write test input file");
+
+    let render = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            output_path.to_str().unwrap(),
+            "--owners",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        render.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&render.stderr)
+    );
+    let svg = fs::read_to_string(&output_path).expect("Failed to read output SVG");
+    assert!(svg.contains("<!-- Ownership legend -->"));
+    assert!(svg.contains(">team-payments (1)</text>"));
+    // Owner names are escaped in both the badge and the legend
+    assert!(svg.contains(">R&amp;D &lt;core&gt;</text>"));
+    assert!(svg.contains(">R&amp;D &lt;core&gt; (1)</text>"));
+    assert!(!svg.contains("<core>"));
+    assert!(svg.contains(">Missing owner (1)</text>"));
+
+    let report = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "report",
+            "owners",
+            input_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&report.stdout);
+    assert!(report.status.success());
+    assert!(stdout.contains("team-payments (1)\n  command ChargeCard (from swimlane 'payments')"));
+    assert!(stdout.contains("Missing owner (1)\n  command PlaceOrder"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_markdown_export_includes_entity_documentation() {
+    let test_input = r#"workflow: Documented
+
+swimlanes:
+  - backend: "Backend"
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+    doc: |
+      Rejected when the cart is empty.
+
+      See the *pricing rules* for discounts.
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+    doc:
+      file: docs/order-placed.md
+
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_entity_docs");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("documented.eventmodel");
+    let output_path = temp_dir.join("documented.md");
+    fs::write(&input_path, test_input).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            output_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let markdown = fs::read_to_string(&output_path).expect("Failed to read Markdown output");
+    assert!(markdown.contains(
+        "## Documentation\n\n\
+         ### OrderPlaced\n\n\
+         See [docs/order-placed.md](docs/order-placed.md).\n\n\
+         ### PlaceOrder\n\n\
+         Rejected when the cart is empty.\n\n\
+         See the *pricing rules* for discounts.\n\n"
+    ));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_implementation_links_are_clickable_and_verifiable() {
+    let test_input = r#"workflow: Traced
+
+swimlanes:
+  - backend: "Backend"
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+    implemented_by:
+      - https://example.com/code?entity={name}&view=source
+      - orders.rs
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+    implemented_by:
+      - missing.rs
+
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_trace_links");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("traced.eventmodel");
+    let output_path = temp_dir.join("traced.svg");
+    fs::write(&input_path, test_input).expect("Failed to write test input file");
+    fs::write(temp_dir.join("orders.rs"), "pub struct PlaceOrder;\n")
+        .expect("Failed to write source file");
+
+    let render = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            output_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        render.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&render.stderr)
+    );
+    let svg = fs::read_to_string(&output_path).expect("Failed to read output SVG");
+    assert!(
+        svg.contains(r#"<a href="https://example.com/code?entity=PlaceOrder&amp;view=source">"#)
+    );
+    assert!(svg.contains("<a href=\"missing.rs\">"));
+
+    let verify = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            output_path.to_str().unwrap(),
+            "--verify-links",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let stderr = String::from_utf8_lossy(&verify.stderr);
+    assert!(!verify.status.success());
+    assert!(
+        stderr.contains("Broken implementation links:\n  OrderPlaced -> missing.rs: no file at")
+    );
+    assert!(!stderr.contains("orders.rs"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_compact_layout_shares_columns_between_slices_on_different_swimlanes() {
+    let model = |layout: &str| {
+        format!(
+            r#"workflow: Compact
+{layout}
+swimlanes:
+  - ui: "UI"
+  - backend: "Backend"
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: ui
+  CancelOrder:
+    description: "Cancel an order"
+    swimlane: ui
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+  OrderShipped:
+    description: "An order was shipped"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> CancelOrder
+  - name: Shipping
+    connections:
+      - OrderPlaced -> OrderShipped
+"#
+        )
+    };
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_compact_layout");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let render = |name: &str, layout: &str| {
+        let input_path = temp_dir.join(format!("{name}.eventmodel"));
+        let output_path = temp_dir.join(format!("{name}.svg"));
+        fs::write(&input_path, model(layout)).expect("Failed to write test input file");
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--",
+                input_path.to_str().unwrap(),
+                "-o",
+                output_path.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "CLI failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        fs::read_to_string(&output_path).expect("Failed to read output SVG")
+    };
+    let view_box_width = |svg: &str| -> u32 {
+        let view_box = svg.split("viewBox=\"0 0 ").nth(1).unwrap();
+        view_box.split(' ').next().unwrap().parse().unwrap()
+    };
+
+    let standard = render("standard", "");
+    let compact = render("compact", "layout: compact");
+
+    assert!(standard.contains("    Ordering\n"));
+    assert!(standard.contains("    Shipping\n"));
+    assert!(compact.contains("    Ordering / Shipping\n"));
+    assert!(view_box_width(&compact) < view_box_width(&standard));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_long_slice_titles_wrap_or_rotate_in_styled_headers() {
+    let test_input = r##"workflow: Headers
+
+slice_headers:
+  height: 40
+  background: "#eef2ff"
+
+swimlanes:
+  - backend: "Backend"
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+  CancelOrder:
+    description: "Cancel an order"
+    swimlane: backend
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+slices:
+  - name: Customer places an order and receives a confirmation email
+    connections:
+      - PlaceOrder -> OrderPlaced
+  - name: reconcile_settlement_batches_against_the_ledger_and_bank_statements
+    connections:
+      - CancelOrder -> OrderPlaced
+"##;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_slice_headers");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("headers.eventmodel");
+    let output_path = temp_dir.join("headers.svg");
+    fs::write(&input_path, test_input).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            output_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let svg = fs::read_to_string(&output_path).expect("Failed to read output SVG");
+    assert!(svg.contains(r##"fill="#eef2ff"/>"##));
+    assert!(svg.contains(">Customer places an order and receives a</tspan>"));
+    assert!(svg.contains(">confirmation email</tspan>"));
+    assert!(svg.contains("transform=\"rotate(-90"));
+    assert!(
+        svg.contains("    reconcile_settlement_batches_against_the_ledger_and_bank_statements\n")
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_swimlane_groups_render_bracketed_labels_and_collapse() {
+    let model = |collapsed: bool| {
+        format!(
+            r##"workflow: Grouped
+swimlanes:
+  - ui: "Storefront"
+  - group: "Backend"
+    collapsed: {collapsed}
+    background: "#eef3ff"
+    swimlanes:
+      - handlers: "Command Handlers"
+      - stream: "Event Stream"
+
+commands:
+  StartCheckout:
+    description: "Start checking out"
+    swimlane: ui
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: handlers
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: stream
+
+slices:
+  - name: Ordering
+    connections:
+      - StartCheckout -> PlaceOrder
+      - PlaceOrder -> OrderPlaced
+"##
+        )
+    };
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_swimlane_groups");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let render = |collapsed: bool| {
+        let input_path = temp_dir.join(format!("{collapsed}.eventmodel"));
+        let output_path = temp_dir.join(format!("{collapsed}.svg"));
+        fs::write(&input_path, model(collapsed)).expect("Failed to write test input file");
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--",
+                input_path.to_str().unwrap(),
+                "-o",
+                output_path.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "CLI failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        fs::read_to_string(&output_path).expect("Failed to read output SVG")
+    };
+
+    let expanded = render(false);
+    assert!(expanded.contains("    Backend\n"));
+    assert!(expanded.contains("    Command Handlers\n"));
+    assert!(expanded.contains("    Event Stream\n"));
+    assert!(expanded.contains(r##"fill="#eef3ff""##));
+    assert!(expanded.contains(r#"<path d="M "#));
+
+    let collapsed = render(true);
+    assert!(collapsed.contains("    Backend\n"));
+    assert!(!collapsed.contains("Command Handlers"));
+    assert!(!collapsed.contains("Event Stream"));
+    assert!(collapsed.contains(r##"fill="#eef3ff""##));
+    assert!(collapsed.contains(">Place Order<"));
+    assert!(collapsed.contains(">Order Placed<"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_swimlane_tints_and_icons_decorate_lanes() {
+    let input = r##"workflow: Tinted
+swimlanes:
+  - ui:
+      name: "Storefront"
+      color: "#3a7bd5"
+      icon: "🖥"
+  - backend: "Backend"
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: ui
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+"##;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_swimlane_tints");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("tinted.eventmodel");
+    let output_path = temp_dir.join("tinted.svg");
+    fs::write(&input_path, input).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            output_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let svg = fs::read_to_string(&output_path).expect("Failed to read output SVG");
+    assert!(svg.contains(r##"fill="#3a7bd5" fill-opacity="0.15""##));
+    assert_eq!(svg.matches(r##"fill="#3a7bd5""##).count(), 2);
+    assert!(svg.contains("    🖥 Storefront\n"));
+    assert!(svg.contains("    Backend\n"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_convention_order_places_lanes_by_role_and_lenient_warns_on_misplacement() {
+    let input = r#"workflow: Roles
+swimlane_order: convention
+swimlanes:
+  - stream:
+      name: "Event Stream"
+      role: stream
+  - app:
+      name: "Application"
+      role: application
+  - ui:
+      name: "Storefront"
+      role: interface
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: app
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: stream
+  CartViewed:
+    description: "A cart was viewed"
+    swimlane: ui
+
+slices:
+  - name: Ordering
+    connections:
+      - CartViewed -> PlaceOrder
+      - PlaceOrder -> OrderPlaced
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_swimlane_roles");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("roles.eventmodel");
+    let output_path = temp_dir.join("roles.svg");
+    fs::write(&input_path, input).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            output_path.to_str().unwrap(),
+            "--lenient",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(
+        "event 'CartViewed' is in swimlane 'ui', whose role is interface; events belong in stream swimlanes"
+    ));
+    let svg = fs::read_to_string(&output_path).expect("Failed to read output SVG");
+    let position = |label: &str| svg.find(&format!("    {label}\n")).unwrap();
+    assert!(position("Storefront") < position("Application"));
+    assert!(position("Application") < position("Event Stream"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_entities_in_lanes_of_the_wrong_role_are_rejected() {
+    let input = r#"workflow: Roles
+swimlanes:
+  - ui:
+      name: "Storefront"
+      role: interface
+  - stream:
+      name: "Event Stream"
+      role: stream
+
+views:
+  OrderForm:
+    description: "Order form"
+    swimlane: stream
+    components:
+      - Submit: Button
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: ui
+
+slices:
+  - name: Ordering
+    connections:
+      - OrderForm -> OrderPlaced
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_role_placement");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("roles.eventmodel");
+    let output_path = temp_dir.join("roles.svg");
+    fs::write(&input_path, input).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            output_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(
+        "event 'OrderPlaced' is in swimlane 'ui', whose role is interface; events belong in stream swimlanes"
+    ));
+    assert!(stderr.contains(
+        "view 'OrderForm' is in swimlane 'stream', whose role is stream; views belong in interface swimlanes"
+    ));
+    assert!(stderr.contains("--lenient"));
+    assert!(!output_path.exists());
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_connection_waypoints_are_routed_through() {
+    let model = |via: &str| {
+        format!(
+            r#"workflow: Waypoints
+swimlanes:
+  - ui: "UI"
+  - automation: "Automation"
+  - backend: "Backend"
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: ui
+  SendReceipt:
+    description: "Send a receipt"
+    swimlane: automation
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    connections:
+      - connection: PlaceOrder -> OrderPlaced
+{via}
+  - name: SendEmail
+    connections:
+      - SendReceipt -> OrderPlaced
+"#
+        )
+    };
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_waypoints");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let render = |name: &str, via: &str| {
+        let input_path = temp_dir.join(format!("{name}.eventmodel"));
+        let output_path = temp_dir.join(format!("{name}.svg"));
+        fs::write(&input_path, model(via)).expect("Failed to write test input file");
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--",
+                input_path.to_str().unwrap(),
+                "-o",
+                output_path.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "CLI failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        fs::read_to_string(&output_path).expect("Failed to read output SVG")
+    };
+    let connection_paths = |svg: &str| -> Vec<String> {
+        svg.split("<!-- Connections -->")
+            .nth(1)
+            .unwrap()
+            .lines()
+            .filter(|line| line.contains("<path d="))
+            .map(str::to_string)
+            .collect()
+    };
+
+    let direct = render("direct", "");
+    let routed = render(
+        "routed",
+        "        via:\n          - slice: SendEmail\n            lane: automation",
+    );
+
+    let direct_paths = connection_paths(&direct);
+    let routed_paths = connection_paths(&routed);
+    assert_eq!(direct_paths.len(), routed_paths.len());
+    assert_ne!(direct_paths[0], routed_paths[0]);
+    // The waypoint sits at the center of the SendEmail column's Automation cell
+    let column = routed.split("    SendEmail\n").next().unwrap();
+    let header_x: u32 = column
+        .rsplit("<text x=\"")
+        .next()
+        .unwrap()
+        .split('"')
+        .next()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(
+        routed_paths[0].contains(&format!(" {header_x} ")),
+        "route should pass through x={header_x}: {}",
+        routed_paths[0]
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_connector_collisions_are_reported() {
+    let test_input = r#"workflow: Crowded
+swimlanes:
+  - backend: "Backend"
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+  ShipOrder:
+    description: "Ship an order"
+    swimlane: backend
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+      - OrderPlaced -> ShipOrder
+      - PlaceOrder -> ShipOrder
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_collisions");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("crowded.eventmodel");
+    let output_path = temp_dir.join("crowded.svg");
+    fs::write(&input_path, test_input).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            output_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "CLI failed: {stderr}");
+    assert!(output_path.exists());
+    // ShipOrder sits beyond OrderPlaced in the same lane, so the direct
+    // connector has to cross it
+    assert!(
+        stderr.contains(
+            "Warning: [rendering] Connection PlaceOrder -> ShipOrder passes through entity 'OrderPlaced'"
+        ),
+        "missing collision warning: {stderr}"
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_connection_markers_are_chosen_by_kind_and_override() {
+    let test_input = r#"workflow: Markers
+swimlanes:
+  - ui: "UI"
+  - backend: "Backend"
+
+markers:
+  "event -> projection": circle
+  "command -> *": open
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: ui
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+projections:
+  OrderSummary:
+    description: "Orders placed so far"
+    swimlane: ui
+    fields:
+      count: Int
+
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+      - OrderPlaced -> OrderSummary
+      - connection: OrderSummary -> PlaceOrder
+        marker: none
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_markers");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("markers.eventmodel");
+    let output_path = temp_dir.join("markers.svg");
+    fs::write(&input_path, test_input).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            output_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let svg = fs::read_to_string(&output_path).expect("Failed to read output SVG");
+    let (defs, body) = svg.split_once("</defs>").unwrap();
+    // Each marker in use is defined once; unused shapes are left out
+    assert_eq!(defs.matches("<marker id=\"arrowhead-open\"").count(), 1);
+    assert_eq!(defs.matches("<marker id=\"marker-circle\"").count(), 1);
+    assert!(!defs.contains("marker-diamond"));
+
+    let paths: Vec<&str> = body
+        .lines()
+        .filter(|line| line.contains("<path d="))
+        .collect();
+    assert_eq!(paths.len(), 3);
+    assert!(paths[0].contains("marker-end=\"url(#arrowhead-open)\""));
+    assert!(paths[1].contains("marker-end=\"url(#marker-circle)\""));
+    assert!(!paths[2].contains("marker-end"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_self_loops_and_bidirectional_connections_render() {
+    let test_input = r#"workflow: Loops
+swimlanes:
+  - ui: "UI"
+  - backend: "Backend"
+
+views:
+  OrderScreen:
+    description: "Order screen"
+    swimlane: ui
+    components:
+      - Submit: Button
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    connections:
+      - OrderScreen <-> PlaceOrder
+      - PlaceOrder -> PlaceOrder
+      - PlaceOrder -> OrderPlaced
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_loops");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("loops.eventmodel");
+    let output_path = temp_dir.join("loops.svg");
+    fs::write(&input_path, test_input).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            output_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "CLI failed: {stderr}");
+    // The loop stays within the lane margin, clear of neighbouring entities
+    assert!(!stderr.contains("Warning"), "unexpected warnings: {stderr}");
+
+    let svg = fs::read_to_string(&output_path).expect("Failed to read output SVG");
+    let paths: Vec<&str> = svg
+        .lines()
+        .filter(|line| line.contains("<path d="))
+        .collect();
+    assert_eq!(paths.len(), 3);
+    assert!(paths[0].contains("marker-start=\"url(#arrowhead)\""));
+    assert!(paths[0].contains("marker-end=\"url(#arrowhead)\""));
+    // The retry loop leaves and re-enters the same edge through three bends
+    let points = paths[1].split(" L ").count();
+    assert_eq!(points, 4, "{}", paths[1]);
+    assert!(!paths[1].contains("marker-start"));
+
+    fs::write(
+        &input_path,
+        test_input.replace("PlaceOrder -> PlaceOrder", "OrderPlaced -> OrderPlaced"),
+    )
+    .expect("Failed to write test input file");
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            output_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("only commands and automations may connect to themselves")
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_duplicate_connections_merge_into_one_badged_connector() {
+    let model = |setting: &str| {
+        format!(
+            r#"workflow: Duplicates
+swimlanes:
+  - ui: "UI"
+  - backend: "Backend"
+{setting}
+views:
+  OrderScreen:
+    description: "Order screen"
+    swimlane: ui
+    components:
+      - Submit: Button
+      - Cancel: Button
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    connections:
+      - OrderScreen.Submit -> PlaceOrder
+      - OrderScreen.Cancel -> PlaceOrder
+"#
+        )
+    };
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_duplicates");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let render = |name: &str, setting: &str| {
+        let input_path = temp_dir.join(format!("{name}.eventmodel"));
+        let output_path = temp_dir.join(format!("{name}.svg"));
+        fs::write(&input_path, model(setting)).expect("Failed to write test input file");
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--",
+                input_path.to_str().unwrap(),
+                "-o",
+                output_path.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "CLI failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let svg = fs::read_to_string(&output_path).expect("Failed to read output SVG");
+        svg.split("<!-- Connections -->")
+            .nth(1)
+            .unwrap()
+            .to_string()
+    };
+    let path_count = |connections: &str| connections.matches("<path d=").count();
+
+    // Both components lead into the same command, so the arrows coincide
+    let separate = render("separate", "");
+    assert_eq!(path_count(&separate), 2);
+
+    let counted = render("count", "duplicate_connections: count");
+    assert_eq!(path_count(&counted), 1);
+    assert!(counted.contains(">×2</text>"));
+
+    let named = render("slices", "duplicate_connections: slices");
+    assert_eq!(path_count(&named), 1);
+    assert!(named.contains(">Ordering</text>"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_focus_emphasizes_one_slice_and_fades_the_others() {
+    let model = r#"workflow: Focus
+swimlanes:
+  - ui: "UI"
+  - backend: "Backend"
+
+views:
+  OrderScreen:
+    description: "Order screen"
+    swimlane: ui
+    components:
+      - Submit: Button
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+  ShipOrder:
+    description: "Ship an order"
+    swimlane: backend
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+  OrderShipped:
+    description: "An order was shipped"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    connections:
+      - OrderScreen.Submit -> PlaceOrder
+      - PlaceOrder -> OrderPlaced
+  - name: Shipping
+    connections:
+      - ShipOrder -> OrderShipped
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_focus");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("focus.eventmodel");
+    let output_path = temp_dir.join("focus.svg");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+    let render = |focus: &str| {
+        Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--",
+                input_path.to_str().unwrap(),
+                "-o",
+                output_path.to_str().unwrap(),
+                "--focus",
+                focus,
+            ])
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = render("Ordering");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let svg = fs::read_to_string(&output_path).expect("Failed to read output SVG");
+    let connections = svg.split("<!-- Connections -->").nth(1).unwrap();
+    let paths: Vec<&str> = connections
+        .lines()
+        .filter(|line| line.contains("<path d="))
+        .collect();
+    assert_eq!(paths.len(), 3);
+    assert_eq!(
+        paths
+            .iter()
+            .filter(|path| path.contains(r#"stroke-width="3""#) && !path.contains("opacity"))
+            .count(),
+        2
+    );
+    assert_eq!(
+        paths
+            .iter()
+            .filter(|path| path.contains(r#"stroke-width="2""#)
+                && path.contains(r#"opacity="0.3""#))
+            .count(),
+        1
+    );
+
+    // An unknown slice is rejected, naming the slices that exist
+    let output = render("Billing");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Unknown slice 'Billing'"));
+    assert!(stderr.contains("Ordering, Shipping"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_command_errors_render_as_red_outcome_boxes() {
+    let model = r#"workflow: Rejections
+swimlanes:
+  - backend: "Backend"
+
+commands:
+  CreateAccount:
+    description: "Create an account"
+    swimlane: backend
+    errors:
+      DuplicateAccountError:
+        description: "An account already exists for this email"
+      InvalidEmailError:
+        description: "The email address is malformed"
+    tests:
+      "Account Already Exists":
+        Given:
+          - AccountCreated:
+              email: A
+        When:
+          - CreateAccount:
+              email: A
+        Then:
+          - DuplicateAccountError:
+              email: A
+
+events:
+  AccountCreated:
+    description: "An account was created"
+    swimlane: backend
+
+slices:
+  - name: Signup
+    connections:
+      - CreateAccount -> AccountCreated
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_errors");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("errors.eventmodel");
+    let output_path = temp_dir.join("errors.svg");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+    let render = |extra: &[&str]| {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--",
+                input_path.to_str().unwrap(),
+                "-o",
+                output_path.to_str().unwrap(),
+            ])
+            .args(extra)
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "CLI failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        // The Then step names a declared error, so nothing is reported
+        assert!(!String::from_utf8_lossy(&output.stderr).contains("Warning"));
+        fs::read_to_string(&output_path).expect("Failed to read output SVG")
+    };
+
+    let plain = render(&[]);
+    assert!(!plain.contains("<!-- Error outcomes -->"));
+
+    let svg = render(&["--errors"]);
+    let outcomes = svg.split("<!-- Error outcomes -->").nth(1).unwrap();
+    assert!(outcomes.contains(">DuplicateAccountError</text>"));
+    assert!(outcomes.contains(">InvalidEmailError</text>"));
+    assert_eq!(outcomes.matches(r##"fill="#fdecea""##).count(), 2);
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_eventual_consistency_is_badged_and_exported() {
+    let model = r#"workflow: Read Models
+swimlanes:
+  - backend: "Backend"
+  - read: "Read Models"
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+  OrderCancelled:
+    description: "An order was cancelled"
+    swimlane: backend
+
+projections:
+  OrderSummary:
+    description: "Orders by customer"
+    swimlane: read
+
+slices:
+  - name: Placing
+    connections:
+      - connection: OrderPlaced -> OrderSummary
+        consistency: eventual
+  - name: Cancelling
+    connections:
+      - connection: OrderCancelled -> OrderSummary
+        consistency: immediate
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_consistency");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("consistency.eventmodel");
+    let svg_path = temp_dir.join("consistency.svg");
+    let md_path = temp_dir.join("consistency.md");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+            "-o",
+            md_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // Only the eventually consistent connection carries a clock
+    let svg = fs::read_to_string(&svg_path).expect("Failed to read output SVG");
+    assert_eq!(
+        svg.matches(r#"<g class="eventual-consistency">"#).count(),
+        1
+    );
+
+    let markdown = fs::read_to_string(&md_path).expect("Failed to read Markdown output");
+    assert!(markdown.contains("- `OrderPlaced` → `OrderSummary` (eventual consistency)"));
+    assert!(markdown.contains("| Projection | Description | Swimlane | Consistency |"));
+    assert!(markdown.contains(
+        "| OrderSummary | Orders by customer | read | OrderCancelled: immediate, OrderPlaced: eventual |"
+    ));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_command_access_is_badged_and_exported() {
+    let model = r#"workflow: Refunds
+swimlanes:
+  - backend: "Backend"
+
+roles: [support, finance]
+
+commands:
+  RefundOrder:
+    description: "Refund an order"
+    swimlane: backend
+    idempotent: true
+    authorized_roles: [support, finance]
+  CloseTicket:
+    description: "Close a support ticket"
+    swimlane: backend
+
+events:
+  OrderRefunded:
+    description: "An order was refunded"
+    swimlane: backend
+
+slices:
+  - name: Refunding
+    connections:
+      - RefundOrder -> OrderRefunded
+      - CloseTicket -> OrderRefunded
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_access");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("access.eventmodel");
+    let svg_path = temp_dir.join("access.svg");
+    let md_path = temp_dir.join("access.md");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+            "-o",
+            md_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let svg = fs::read_to_string(&svg_path).expect("Failed to read output SVG");
+    let access = svg.split("<!-- Command access -->").nth(1).unwrap();
+    assert_eq!(access.matches("\u{1f512}").count(), 1);
+    assert!(access.contains(">idempotent \u{b7} \u{1f512} support, finance</text>"));
+
+    let markdown = fs::read_to_string(&md_path).expect("Failed to read Markdown output");
+    assert!(
+        markdown.contains("| Command | Description | Swimlane | Idempotent | Authorized Roles |")
+    );
+    assert!(
+        markdown.contains("| RefundOrder | Refund an order | backend | yes | support, finance |")
+    );
+    assert!(markdown.contains("| CloseTicket | Close a support ticket | backend |  |  |"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_volumes_are_labelled_and_reported() {
+    let model = r#"workflow: Checkout
+swimlanes:
+  - backend: "Backend"
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+    volume: 10k/day
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+    volume: 500/hour
+
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_volume");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("volume.eventmodel");
+    let svg_path = temp_dir.join("volume.svg");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let svg = fs::read_to_string(&svg_path).expect("Failed to read output SVG");
+    let volumes = svg.split("<!-- Volumes -->").nth(1).unwrap();
+    assert!(volumes.contains(">10k/day</text>"));
+    assert!(volumes.contains(">500/hour</text>"));
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "report",
+            "scale",
+            input_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Scale of Checkout\n\nCommands (~10k/day)\n  PlaceOrder 10k/day\n\nEvents (~12k/day)\n  OrderPlaced 500/hour (~12k/day)\n\nEvery event and command states a volume.\n"
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_pii_fields_are_shielded_and_traced() {
+    let model = r#"workflow: Signup
+swimlanes:
+  - backend: "Backend"
+  - read: "Read Models"
+
+commands:
+  Register:
+    description: "Register a user"
+    swimlane: backend
+    data:
+      email:
+        type: EmailAddress
+        pii: true
+
+events:
+  UserRegistered:
+    description: "A user registered"
+    swimlane: backend
+    data:
+      email:
+        type: EmailAddress
+        pii: true
+      registered_at: Timestamp
+
+projections:
+  Profiles:
+    description: "Profiles by user"
+    swimlane: read
+
+slices:
+  - name: Signup
+    connections:
+      - Register -> UserRegistered
+      - UserRegistered -> Profiles
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_pii");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("pii.eventmodel");
+    let svg_path = temp_dir.join("pii.svg");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let svg = fs::read_to_string(&svg_path).expect("Failed to read output SVG");
+    assert!(svg.contains("<!-- PII -->"));
+    assert_eq!(svg.matches(r#"class="pii""#).count(), 2);
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "report",
+            "pii",
+            input_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "PII lineage of Signup\n\nTagged fields\n  command Register: email\n  event UserRegistered: email\n\nExposed by\n  projection Profiles: Register.email, UserRegistered.email\n"
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_declared_types_are_exported_with_their_constraints() {
+    let model = r#"workflow: Ordering
+swimlanes:
+  - backend: "Backend"
+
+types:
+  Currency:
+    enum: [USD, EUR]
+  Quantity:
+    type: Integer
+    min: 1
+    max: 100
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+    data:
+      quantity: Quantity
+      currency: Currency
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_types");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("types.eventmodel");
+    let md_path = temp_dir.join("types.md");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            md_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let markdown = fs::read_to_string(&md_path).expect("Failed to read Markdown output");
+    assert!(markdown.contains("| Type | Definition |"));
+    assert!(markdown.contains("| Currency | one of USD, EUR |"));
+    assert!(markdown.contains("| Quantity | Integer from 1 to 100 |"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_stream_report_as_json() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "report",
+            "streams",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "--json",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(r#""stream": "account_id""#));
+    assert!(stdout.contains(
+        r#""creating_events": [
+        "UserAccountCredentialsCreated"
+      ]"#
+    ));
+    assert!(stdout.contains(r#""missing_creation": false"#));
+    assert!(stdout.contains(r#""unconsumed": false"#));
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "report",
+            "owners",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "--json",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("The owners report has no JSON form"));
+}
+
+#[test]
+fn test_codegen_writes_rust_test_skeletons() {
+    let temp_dir = std::env::temp_dir();
+    let output_path = temp_dir.join("codegen_scenarios.rs");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "codegen",
+            "tests",
+            "--lang",
+            "rust",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "-o",
+            output_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let source = fs::read_to_string(&output_path).expect("Failed to read generated tests");
+    assert!(source.contains("mod create_user_account_credentials {"));
+    assert!(source.contains("    fn main_success() {"));
+    assert!(source.contains("    fn account_already_exists() {"));
+    assert!(source.contains("let when = CreateUserAccountCredentials {"));
+    assert!(source.contains(r#"#[ignore = "generated skeleton"]"#));
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "codegen",
+            "tests",
+            "--lang",
+            "kotlin",
+            "tests/fixtures/acceptance/example.eventmodel",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Unsupported language for tests: kotlin (supported: rust)")
+    );
+}
+
+#[test]
+fn test_markdown_export_tabulates_command_scenarios() {
+    let model = r#"workflow: Email Changes
+swimlanes:
+  - backend: "Backend"
+
+commands:
+  UpdateEmail:
+    description: "Change a user's email"
+    swimlane: backend
+    errors:
+      UnknownUser:
+        description: "No such user"
+    tests:
+      "Changes Address":
+        examples:
+          B: jane@example.com
+          C: jane@work.example
+        Given:
+          - UserRegistered:
+              user_id: A
+              email: B
+        When:
+          - UpdateEmail:
+              user_id: A
+              email: C
+        Then:
+          - EmailUpdated:
+              user_id: A
+              old_email: B
+              new_email: C
+      "Unknown User":
+        Given:
+        When:
+          - UpdateEmail:
+              user_id: A
+              email: "someone@example.com"
+        Then:
+          - UnknownUser:
+              user_id: A
+
+events:
+  UserRegistered:
+    description: "A user registered"
+    swimlane: backend
+  EmailUpdated:
+    description: "A user's email changed"
+    swimlane: backend
+
+slices:
+  - name: Changing
+    connections:
+      - UpdateEmail -> EmailUpdated
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_specifications");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("specifications.eventmodel");
+    let md_path = temp_dir.join("specifications.md");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            md_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let markdown = fs::read_to_string(&md_path).expect("Failed to read Markdown output");
+    assert!(markdown.contains(
+        "## Specifications\n\n\
+         ### UpdateEmail\n\n\
+         | Scenario | Given | When | Then |\n\
+         | --- | --- | --- | --- |\n\
+         | Changes Address \
+         | UserRegistered(email: *email*, user_id: *user_id*) \
+         | UpdateEmail(email: *email 2*, user_id: *user_id*) \
+         | EmailUpdated(new_email: *email 2*, old_email: *email*, user_id: *user_id*) |\n\
+         | Unknown User \
+         | nothing \
+         | UpdateEmail(email: `someone@example.com`, user_id: *user_id*) \
+         | rejected with UnknownUser(user_id: *user_id*) |\n"
+    ));
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            md_path.to_str().unwrap(),
+            "--examples",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let markdown = fs::read_to_string(&md_path).expect("Failed to read Markdown output");
+    assert!(markdown.contains(
+        "| Changes Address \
+         | UserRegistered(email: `jane@example.com`, user_id: *user_id*) \
+         | UpdateEmail(email: `jane@work.example`, user_id: *user_id*) \
+         | EmailUpdated(new_email: `jane@work.example`, old_email: `jane@example.com`, user_id: *user_id*) |\n"
+    ));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_simulation_report_flags_uncarried_scenario_data() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "report",
+            "simulation",
+            "tests/fixtures/acceptance/example.eventmodel",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(
+        "CreateUserAccountCredentials: Main Success\n  \
+         changes projection UserCredentialsProjection: account_id A, email_address B, password C\n"
+    ));
+    assert!(stdout.contains(
+        "SendEmailVerification: Main Success\n  \
+         changes projection UserEmailVerificationTokenProjection: account_id A, verification_token C'\n  \
+         no projection carries EmailVerificationMessageSent.email_address\n"
+    ));
+    assert!(stdout.contains(
+        "CreateUserAccountCredentials: Account Already Exists\n  \
+         rejected with DuplicateUserAccountError; nothing changes\n"
+    ));
+}
+
+#[test]
+fn test_unreachable_entities_are_reported_and_dimmed() {
+    let model = r#"workflow: Orders
+swimlanes:
+  - ui: "UI"
+  - backend: "Backend"
+
+views:
+  OrderForm:
+    description: "Place an order"
+    swimlane: ui
+    components:
+      - Submit: Button
+  Receipt:
+    description: "Shows the receipt"
+    swimlane: ui
+    components:
+      - Total: TextField
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+  RefundOrder:
+    description: "Refund an order"
+    swimlane: backend
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+  OrderRefunded:
+    description: "An order was refunded"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    connections:
+      - OrderForm -> PlaceOrder
+      - PlaceOrder -> OrderPlaced
+      - OrderPlaced -> Receipt
+  - name: Refunding
+    connections:
+      - RefundOrder -> OrderRefunded
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_reachability");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("reachability.eventmodel");
+    let svg_path = temp_dir.join("reachability.svg");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+            "--dim-unreachable",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "CLI failed: {stderr}");
+    assert!(stderr.contains(
+        "Warning: [conversion] 'OrderRefunded' cannot be reached from any view or automation"
+    ));
+    assert!(stderr.contains(
+        "Warning: [conversion] 'RefundOrder' cannot be reached from any view or automation"
+    ));
+    assert!(stderr.contains("Warning: [conversion] View 'Receipt' leads to no event"));
+
+    let svg = fs::read_to_string(&svg_path).expect("Failed to read SVG output");
+    assert!(svg.contains("<!-- Dimmed -->"));
+    assert_eq!(svg.matches(r#"class="dimmed""#).count(), 2);
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_graph_export_writes_graphml_and_cytoscape_json() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_graph_export");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let graphml_path = temp_dir.join("model.graphml");
+    let cyjs_path = temp_dir.join("model.cyjs");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "-o",
+            graphml_path.to_str().unwrap(),
+            "-o",
+            cyjs_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let graphml = fs::read_to_string(&graphml_path).expect("Failed to read GraphML output");
+    assert!(graphml.contains(r#"<graph id="User Account Signup" edgedefault="directed">"#));
+    assert!(graphml.contains(
+        "    <node id=\"EmailAddressVerified\">\n      \
+         <data key=\"label\">EmailAddressVerified</data>\n      \
+         <data key=\"type\">event</data>\n      \
+         <data key=\"swimlane\">account_stream</data>\n      \
+         <data key=\"slices\">Verify Email Address</data>\n    \
+         </node>"
+    ));
+    assert!(graphml.contains(r#"source="NewAccountScreen" target="CreateUserAccountCredentials""#));
+
+    let cyjs = fs::read_to_string(&cyjs_path).expect("Failed to read Cytoscape JSON output");
+    let graph: serde_json::Value = serde_json::from_str(&cyjs).expect("Invalid Cytoscape JSON");
+    assert_eq!(graph["data"]["name"], "User Account Signup");
+    let edges = graph["elements"]["edges"].as_array().expect("No edges");
+    assert!(edges.iter().any(|edge| {
+        edge["data"]["source"] == "NewAccountScreen"
+            && edge["data"]["target"] == "CreateUserAccountCredentials"
+            && edge["data"]["slice"] == "Create User Account Credentials"
+    }));
+    let nodes = graph["elements"]["nodes"].as_array().expect("No nodes");
+    assert!(nodes.iter().any(|node| {
+        node["data"]["id"] == "CreateUserAccountCredentials" && node["data"]["type"] == "command"
+    }));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_sqlite_export_supports_queries_over_the_model() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_sqlite_export");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let db_path = temp_dir.join("model.db");
+    // A stale file is replaced rather than appended to
+    fs::write(&db_path, "stale").expect("Failed to write stale file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "export",
+            "sqlite",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "-o",
+            db_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let db = rusqlite::Connection::open(&db_path).expect("Failed to open database");
+    let consumers: Vec<(String, i64)> = db
+        .prepare(
+            "SELECT source, COUNT(*) FROM connections \
+             JOIN entities ON entities.name = connections.source \
+             WHERE entities.type = 'event' \
+             GROUP BY source ORDER BY source",
+        )
+        .and_then(|mut statement| {
+            statement
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect()
+        })
+        .expect("Failed to query consumers");
+    assert!(consumers.contains(&("UserAccountCredentialsCreated".to_string(), 3)));
+
+    let field_type: String = db
+        .query_row(
+            "SELECT type FROM fields WHERE entity = 'CreateUserAccountCredentials' \
+             AND section = 'data' AND name = 'email_address'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("Failed to query field");
+    assert_eq!(field_type, "UserEmailAddress<Unverified>");
+
+    let given: Vec<String> = db
+        .prepare(
+            "SELECT name FROM test_steps WHERE command = 'CreateUserAccountCredentials' \
+             AND scenario = 'Account Already Exists' AND step = 'given'",
+        )
+        .and_then(|mut statement| statement.query_map([], |row| row.get(0))?.collect())
+        .expect("Failed to query test steps");
+    assert_eq!(given, ["UserAccountCredentialsCreated"]);
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_history_report_tracks_model_statistics_across_commits() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_history_report");
+    fs::remove_dir_all(&temp_dir).ok();
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let git = |args: &[&str]| {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&temp_dir)
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .expect("Failed to run git");
+        assert!(
+            output.status.success(),
+            "git failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    };
+    let model_path = temp_dir.join("orders.eventmodel");
+    let first = r#"workflow: Orders
+swimlanes:
+  - backend: "Backend"
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+"#;
+    let second = format!(
+        "{first}      - OrderPlaced -> OrderHistory\nprojections:\n  OrderHistory:\n    description: \"Orders placed\"\n    swimlane: backend\n    fields:\n      order_id: OrderId\n"
+    );
+
+    git(&["init", "--quiet"]);
+    fs::write(&model_path, first).expect("Failed to write model");
+    git(&["add", "orders.eventmodel"]);
+    git(&["commit", "--quiet", "-m", "First"]);
+    fs::write(&model_path, &second).expect("Failed to write model");
+    git(&["commit", "--quiet", "-am", "Second"]);
+    // Uncommitted changes are not part of the history
+    fs::write(&model_path, "workflow: [").expect("Failed to write model");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "report",
+            "history",
+            model_path.to_str().unwrap(),
+            "--last",
+            "5",
+            "--csv",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let csv = String::from_utf8_lossy(&output.stdout);
+    let rows: Vec<Vec<&str>> = csv.lines().map(|line| line.split(',').collect()).collect();
+    assert_eq!(rows.len(), 3, "Unexpected CSV: {csv}");
+    assert_eq!(rows[0][2], "entities");
+    assert_eq!(
+        rows[1][2..],
+        ["2", "1", "1", "0", "0", "0", "0", "1", "1", "0", "0", "0"]
+    );
+    assert_eq!(
+        rows[2][2..],
+        ["3", "1", "1", "0", "1", "0", "0", "1", "2", "0", "0", "0"]
+    );
+    // The working tree is left as it was
+    assert_eq!(
+        fs::read_to_string(&model_path).expect("Failed to read model"),
+        "workflow: ["
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_docs_build_writes_linked_html_site() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_docs_site");
+    fs::remove_dir_all(&temp_dir).ok();
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "docs",
+            "build",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "-o",
+            temp_dir.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let index = fs::read_to_string(temp_dir.join("index.html")).expect("Failed to read index");
+    assert!(index.contains("<h1>User Account Signup</h1>"));
+    assert!(index.contains("<svg"));
+    assert!(!index.contains("<?xml"));
+    assert!(
+        index.contains(
+            r#"<a class="entity-link" href="entities/CreateUserAccountCredentials.html">"#
+        )
+    );
+
+    let command = fs::read_to_string(temp_dir.join("entities/CreateUserAccountCredentials.html"))
+        .expect("Failed to read entity page");
+    assert!(command.contains(r#"<nav><a href="../index.html">User Account Signup</a></nav>"#));
+    assert!(
+        command
+            .contains("<tr><td>email_address</td><td>UserEmailAddress&lt;Unverified&gt;</td></tr>")
+    );
+    assert!(command.contains("<h3>Account Already Exists</h3>"));
+    assert!(command.contains("rejected with DuplicateUserAccountError(account_id: A)"));
+
+    // Events link back to the commands whose scenarios mention them
+    let event = fs::read_to_string(temp_dir.join("entities/UserAccountCredentialsCreated.html"))
+        .expect("Failed to read entity page");
+    assert!(event.contains("<h2>Used in scenarios</h2>"));
+    assert!(event.contains(
+        r#"<li><a href="CreateUserAccountCredentials.html">CreateUserAccountCredentials</a>: Main Success</li>"#
+    ));
+    assert!(event.contains("<h2>Reached from</h2>"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_mdx_export_embeds_svg_and_writes_component_props() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_mdx_export");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let mdx_path = temp_dir.join("signup.mdx");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "-o",
+            mdx_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let mdx = fs::read_to_string(&mdx_path).expect("Failed to read MDX output");
+    assert!(mdx.starts_with("---\ntitle: \"User Account Signup\"\n---\n"));
+    assert!(mdx.contains("import EventModel from \"@site/src/components/EventModel\";"));
+    assert!(mdx.contains("import props from \"./signup.props.json\";"));
+    assert!(mdx.contains("export const svg = \"<svg "));
+    assert!(
+        mdx.contains(
+            r##"<a class=\"entity-link\" href=\"#entity-CreateUserAccountCredentials\">"##
+        )
+    );
+    assert!(mdx.ends_with("<EventModel svg={svg} {...props} />\n"));
+
+    let props = fs::read_to_string(temp_dir.join("signup.props.json"))
+        .expect("Failed to read props output");
+    let props: serde_json::Value = serde_json::from_str(&props).expect("Invalid props JSON");
+    assert_eq!(props["workflow"], "User Account Signup");
+    assert_eq!(props["slices"][0], "Create User Account Credentials");
+    let command = props["entities"]
+        .as_array()
+        .expect("No entities")
+        .iter()
+        .find(|entity| entity["name"] == "CreateUserAccountCredentials")
+        .expect("Command missing from props");
+    assert_eq!(command["type"], "command");
+    assert_eq!(
+        command["description"],
+        "Create a new user account with credentials."
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_compare_passes_for_matching_images_and_fails_above_threshold() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_compare");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let rendered = temp_dir.join("rendered.svg");
+    let changed = temp_dir.join("changed.svg");
+    let heat_map = temp_dir.join("heat.png");
+    fs::write(
+        &rendered,
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="50"><rect width="100" height="50" fill="#336699"/></svg>"##,
+    )
+    .expect("Failed to write SVG");
+    fs::write(
+        &changed,
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="50"><rect width="100" height="50" fill="#336699"/><rect width="10" height="50" fill="#cc0000"/></svg>"##,
+    )
+    .expect("Failed to write SVG");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "compare",
+            rendered.to_str().unwrap(),
+            rendered.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("0.00% of pixels differ"));
+    assert!(temp_dir.join("rendered.diff.png").exists());
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "compare",
+            changed.to_str().unwrap(),
+            rendered.to_str().unwrap(),
+            "--threshold",
+            "0.05",
+            "-o",
+            heat_map.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("10.00% of pixels differ from the reference, above the threshold of 5.00%"),
+        "Unexpected error: {stderr}"
+    );
+    assert!(heat_map.exists());
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_entity_sizing_policies_control_box_sizes() {
+    let model = |setting: &str| {
+        format!(
+            r#"workflow: Sizing
+swimlanes:
+  - backend: "Backend"
+{setting}
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+commands:
+  Go:
+    description: "Start"
+    swimlane: backend
+  PlaceOrderForRegisteredCustomerAccountToday:
+    description: "Place an order"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    connections:
+      - Go -> OrderPlaced
+      - PlaceOrderForRegisteredCustomerAccountToday -> OrderPlaced
+"#
+        )
+    };
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_sizing");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let render = |name: &str, setting: &str| {
+        let input_path = temp_dir.join(format!("{name}.eventmodel"));
+        let output_path = temp_dir.join(format!("{name}.svg"));
+        fs::write(&input_path, model(setting)).expect("Failed to write test input file");
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--",
+                input_path.to_str().unwrap(),
+                "-o",
+                output_path.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "CLI failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        fs::read_to_string(&output_path).expect("Failed to read output SVG")
+    };
+    // Width and height of each command box, smallest first
+    let command_sizes = |svg: &str| {
+        let mut sizes: Vec<(u32, u32)> = svg
+            .lines()
+            .filter(|line| line.contains(r##"fill="#4a90e2""##) && line.contains("<rect"))
+            .map(|line| {
+                let attribute = |name: &str| {
+                    let start = line.find(&format!(" {name}=\"")).unwrap() + name.len() + 3;
+                    let end = start + line[start..].find('"').unwrap();
+                    line[start..end].parse::<u32>().unwrap()
+                };
+                (attribute("width"), attribute("height"))
+            })
+            .collect();
+        sizes.sort();
+        sizes
+    };
+
+    // The long name wraps onto four lines and grows its box
+    let standard = render("standard", "");
+    assert_eq!(command_sizes(&standard), [(120, 60), (120, 68)]);
+
+    let uniform = render("uniform", "entity_sizing: uniform");
+    assert_eq!(command_sizes(&uniform), [(120, 68), (120, 68)]);
+
+    let fixed = render("fixed", "entity_sizing:\n  commands: fixed");
+    assert_eq!(command_sizes(&fixed), [(120, 60), (120, 60)]);
+    assert!(fixed.contains(">Customer\u{2026}</text>"));
+
+    let fit = render(
+        "fit",
+        "entity_sizing:\n  default: uniform\n  commands: fit-content",
+    );
+    assert_eq!(command_sizes(&fit)[0], (32, 32));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_grid_snaps_entities_and_debug_grid_draws_gridlines() {
+    let model = |grid: &str| {
+        format!(
+            r#"workflow: Grid
+swimlanes:
+  - ui: "UI"
+  - backend: "Backend"
+{grid}
+
+commands:
+  OpenOrderForm:
+    description: "Open the order form"
+    swimlane: ui
+  PlaceOrderForRegisteredCustomerAccount:
+    description: "Place an order"
+    swimlane: backend
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    connections:
+      - OpenOrderForm -> PlaceOrderForRegisteredCustomerAccount
+      - PlaceOrderForRegisteredCustomerAccount -> OrderPlaced
+"#
+        )
+    };
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_grid");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let render = |name: &str, grid: &str, flags: &[&str]| {
+        let input_path = temp_dir.join(format!("{name}.eventmodel"));
+        let output_path = temp_dir.join(format!("{name}.svg"));
+        fs::write(&input_path, model(grid)).expect("Failed to write test input file");
+        let mut args = vec![
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            output_path.to_str().unwrap(),
+        ];
+        args.extend_from_slice(flags);
+        let output = Command::new("cargo")
+            .args(&args)
+            .output()
+            .expect("Failed to execute command");
+        (output, fs::read_to_string(&output_path).unwrap_or_default())
+    };
+    // Top-left corner of each entity box
+    let entity_corners = |svg: &str| -> Vec<(u32, u32)> {
+        svg.lines()
+            .filter(|line| {
+                line.contains("<rect")
+                    && (line.contains(r##"fill="#4a90e2""##)
+                        || line.contains(r##"fill="#9b59b6""##))
+            })
+            .map(|line| {
+                let attribute = |name: &str| {
+                    let start = line.find(&format!(" {name}=\"")).unwrap() + name.len() + 3;
+                    let end = start + line[start..].find('"').unwrap();
+                    line[start..end].parse::<u32>().unwrap()
+                };
+                (attribute("x"), attribute("y"))
+            })
+            .collect()
+    };
+
+    let (output, snapped) = render("snapped", "grid: 7", &["--debug-grid"]);
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let corners = entity_corners(&snapped);
+    assert_eq!(corners.len(), 3);
+    for (x, y) in corners {
+        assert_eq!((x % 7, y % 7), (0, 0), "({x}, {y}) is off the grid");
+    }
+    assert!(snapped.contains(r#"<g class="debug-grid""#));
+    assert!(snapped.contains(r#"<line x1="7" y1="0" x2="7""#));
+
+    let (_, free) = render("free", "", &[]);
+    assert!(!free.contains("debug-grid"));
+
+    let (output, _) = render("invalid", "grid: 0", &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid grid"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_workflow_header_is_rendered_and_exported() {
+    let model = r#"workflow:
+  title: Ordering
+  description: How customers place & pay for orders
+  authors: [Alice, Bob]
+  version: 2
+  date: 2026-10-17
+swimlanes:
+  - backend: "Backend"
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+slices:
+  - name: Placing
+    connections:
+      - PlaceOrder -> OrderPlaced
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_header");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("header.eventmodel");
+    let svg_path = temp_dir.join("header.svg");
+    let md_path = temp_dir.join("header.md");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+            "-o",
+            md_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let svg = fs::read_to_string(&svg_path).expect("Failed to read output SVG");
+    assert!(svg.contains(">How customers place &amp; pay for orders</tspan>"));
+
+    let markdown = fs::read_to_string(&md_path).expect("Failed to read Markdown output");
+    assert!(markdown.starts_with(
+        "# Ordering\n\nHow customers place & pay for orders\n\n*Version 2 \u{b7} 2026-10-17 \u{b7} Alice, Bob*\n\n"
+    ));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_emit_writes_each_pipeline_stage() {
+    let model = |grid: u32| {
+        format!(
+            r#"workflow: Emitting
+grid: {grid}
+swimlanes:
+  - backend: "Backend"
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+slices:
+  - name: Placing
+    connections:
+      - PlaceOrder -> OrderPlaced
+"#
+        )
+    };
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_emit");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("emit.eventmodel");
+    let svg_path = temp_dir.join("emit.svg");
+    let stage = |name: &str| temp_dir.join(format!("emit.{name}.json"));
+    let render = || {
+        Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--",
+                input_path.to_str().unwrap(),
+                "-o",
+                svg_path.to_str().unwrap(),
+                "--emit",
+                "ast,domain,layout,routes",
+            ])
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    fs::write(&input_path, model(10)).expect("Failed to write test input file");
+    let output = render();
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let read = |name: &str| -> serde_json::Value {
+        serde_json::from_str(&fs::read_to_string(stage(name)).expect("Stage not emitted"))
+            .expect("Stage is not JSON")
+    };
+    assert_eq!(read("ast")["grid"], 10);
+    assert_eq!(read("domain")["workflow"], "Emitting");
+    let layout = read("layout");
+    assert_eq!(layout["swimlanes"][0]["id"], "backend");
+    assert_eq!(layout["entities"][0]["name"], "OrderPlaced");
+    assert_eq!(layout["entities"][0]["x"].as_u64().unwrap() % 10, 0);
+    let routes = read("routes");
+    assert_eq!(routes[0]["connection"], "PlaceOrder -> OrderPlaced");
+    assert!(routes[0]["points"].as_array().unwrap().len() >= 2);
+
+    // A stage that fails leaves the earlier stages behind
+    for name in ["ast", "domain", "layout", "routes"] {
+        fs::remove_file(stage(name)).ok();
+    }
+    fs::write(&input_path, model(0)).expect("Failed to write test input file");
+    assert!(!render().status.success());
+    assert!(stage("ast").exists());
+    assert!(!stage("domain").exists());
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_timeout_draws_remaining_connectors_as_straight_lines() {
+    let model = r#"workflow: Timing Out
+swimlanes:
+  - ux: "UX"
+  - backend: "Backend"
+
+views:
+  OrderForm:
+    description: "Form to place an order"
+    swimlane: ux
+    components:
+      - Submit: Button
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: ux
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+slices:
+  - name: Placing
+    connections:
+      - OrderForm.Submit -> PlaceOrder
+      - PlaceOrder -> OrderPlaced
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_timeout");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("timeout.eventmodel");
+    let svg_path = temp_dir.join("timeout.svg");
+    fs::write(&input_path, model).expect("Failed to write test file");
+
+    let render = |timeout: &str| {
+        Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--",
+                input_path.to_str().unwrap(),
+                "-o",
+                svg_path.to_str().unwrap(),
+                "--timeout",
+                timeout,
+            ])
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    // An elapsed budget still produces a diagram, with every connector straight
+    let output = render("0s");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "Render failed: {stderr}");
+    assert!(
+        stderr.contains("the remaining 2 were drawn as straight lines"),
+        "Expected a render budget warning, got: {stderr}"
+    );
+    let svg = fs::read_to_string(&svg_path).expect("Failed to read SVG");
+    assert!(svg.contains("<path"), "Expected connectors in the SVG");
+
+    // A generous budget routes everything without a warning
+    let output = render("30s");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "Render failed: {stderr}");
+    assert!(
+        !stderr.contains("Render budget"),
+        "Unexpected warning: {stderr}"
+    );
+
+    let output = render("soon");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid --timeout: soon"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_seed_keeps_renders_reproducible() {
+    let model = r#"workflow: Seeded
+swimlanes:
+  - backend: "Backend"
+  - read: "Read Models"
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+projections:
+  OrderSummary:
+    description: "Orders by customer"
+    swimlane: read
+
+slices:
+  - name: Placing
+    connections:
+      - connection: OrderPlaced -> OrderSummary
+        consistency: eventual
+  - name: Replaying
+    connections:
+      - connection: OrderPlaced -> OrderSummary
+        consistency: eventual
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_seed");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("seed.eventmodel");
+    let svg_path = temp_dir.join("seed.svg");
+    fs::write(&input_path, model).expect("Failed to write test file");
+
+    let render = |extra: &[&str]| {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--",
+                input_path.to_str().unwrap(),
+                "-o",
+                svg_path.to_str().unwrap(),
+            ])
+            .args(extra)
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "Render failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        fs::read_to_string(&svg_path).expect("Failed to read SVG")
+    };
+
+    // Without a seed the model itself fixes the arrangement
+    assert_eq!(render(&[]), render(&[]));
+    assert_eq!(render(&["--seed", "7"]), render(&["--seed", "7"]));
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "--seed",
+            "lucky",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid --seed: lucky"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_scaffold_writes_a_starter_model_that_renders() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_scaffold");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let model_path = temp_dir.join("orders.eventmodel");
+    let svg_path = temp_dir.join("orders.svg");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "scaffold",
+            "--events",
+            "OrderPlaced,OrderShipped",
+            "--commands",
+            "PlaceOrder,ShipOrder",
+            "-o",
+            model_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "Scaffold failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let model = fs::read_to_string(&model_path).expect("Failed to read starter model");
+    assert!(model.contains("description: \"TODO: Describe PlaceOrder\""));
+    assert!(model.contains("- PlaceOrder -> OrderPlaced"));
+    assert!(model.contains("- ShipOrder -> OrderShipped"));
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            model_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "Render of starter model failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_wizard_needs_an_interactive_terminal() {
+    let output = Command::new("cargo")
+        .args(["run", "--quiet", "--", "wizard", "minimal.eventmodel"])
+        .stdin(std::process::Stdio::null())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("needs an interactive terminal"),
+        "Unexpected output: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_inconsistent_terminology_is_reported() {
+    let model = r#"workflow: Accounts
+swimlanes:
+  - backend: "Backend"
+
+terminology:
+  banned_words: [client]
+
+commands:
+  CreateUser:
+    description: "Create a user"
+    swimlane: backend
+    data:
+      account_id: AccountId
+      displayName: Name
+
+events:
+  UserCreated:
+    description: "A user was created"
+    swimlane: backend
+    data:
+      account_id: AccountId
+      created_at: Timestamp
+  UserAccountCreated:
+    description: "A user account was created for a client"
+    swimlane: backend
+
+slices:
+  - name: Signup
+    connections:
+      - CreateUser -> UserCreated
+      - CreateUser -> UserAccountCreated
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_terminology");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("terminology.eventmodel");
+    let svg_path = temp_dir.join("terminology.svg");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "CLI failed: {stderr}");
+    assert!(stderr.contains(
+        "Warning: [conversion] The event names 'UserAccountCreated' and 'UserCreated' are nearly the same"
+    ));
+    assert!(stderr.contains(
+        "Warning: [conversion] Field names are mostly snake_case but use camelCase in CreateUser.displayName"
+    ));
+    assert!(stderr.contains(
+        "Warning: [conversion] The banned word 'client' is used in UserAccountCreated description"
+    ));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_lint_plugins_report_findings() {
+    let model = r#"workflow: Accounts
+swimlanes:
+  - backend: "Backend"
+
+lint:
+  plugins: [rules/owners.wasm]
+
+commands:
+  CreateUser:
+    description: "Create a user"
+    swimlane: backend
+
+events:
+  UserCreated:
+    description: "A user was created"
+    swimlane: backend
+
+slices:
+  - name: Signup
+    connections:
+      - CreateUser -> UserCreated
+"#;
+    let findings = r#"[{"rule":"commands-need-owner","message":"CreateUser has no owner"}]"#;
+    let plugin = wat::parse_str(format!(
+        r#"(module
+            (memory (export "memory") 1)
+            (data (i32.const 16) "{}")
+            (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+            (func (export "lint") (param i32 i32) (result i64)
+                (i64.or (i64.shl (i64.const 16) (i64.const 32)) (i64.const {}))))"#,
+        findings.replace('"', "\\\""),
+        findings.len()
+    ))
+    .expect("Failed to assemble plugin");
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_lint_plugins");
+    fs::create_dir_all(temp_dir.join("rules")).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("plugins.eventmodel");
+    let svg_path = temp_dir.join("plugins.svg");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+    fs::write(temp_dir.join("rules/owners.wasm"), plugin).expect("Failed to write plugin");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "CLI failed: {stderr}");
+    assert!(stderr.contains(
+        "Warning: [conversion] rules/owners.wasm [commands-need-owner]: CreateUser has no owner"
+    ));
+
+    // A plugin that cannot be loaded fails the render
+    fs::remove_file(temp_dir.join("rules/owners.wasm")).expect("Failed to remove plugin");
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Cannot read lint plugin"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_custom_entity_kinds_render_with_their_icon() {
+    let model = r##"workflow: Refunds
+swimlanes:
+  - backend: "Backend"
+
+extensions:
+  policy:
+    base: automation
+    color: "#e8daef"
+    icon: "⚖"
+    connects_to: [command]
+
+commands:
+  RefundOrder:
+    description: "Refund the order"
+    swimlane: backend
+
+events:
+  OrderCancelled:
+    description: "The order was cancelled"
+    swimlane: backend
+
+automations:
+  RefundPolicy:
+    swimlane: backend
+    kind: policy
+
+slices:
+  - name: Refunds
+    connections:
+      - OrderCancelled -> RefundPolicy
+      - RefundPolicy -> RefundOrder
+"##;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_custom_kinds");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("kinds.eventmodel");
+    let svg_path = temp_dir.join("kinds.svg");
+    let db_path = temp_dir.join("kinds.db");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let svg = fs::read_to_string(&svg_path).expect("Failed to read output SVG");
+    assert!(svg.contains(r##"fill="#e8daef""##));
+    assert!(svg.contains(">⚖</text>"));
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "export",
+            "sqlite",
+            input_path.to_str().unwrap(),
+            "-o",
+            db_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+    let db = rusqlite::Connection::open(&db_path).expect("Failed to open database");
+    let kind: Option<String> = db
+        .query_row(
+            "SELECT kind FROM entities WHERE name = 'RefundPolicy'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("Failed to query kinds");
+    assert_eq!(kind.as_deref(), Some("policy"));
+
+    // A connection the kind does not allow fails the render
+    fs::write(
+        &input_path,
+        model.replace(
+            "RefundPolicy -> RefundOrder",
+            "RefundPolicy -> OrderCancelled",
+        ),
+    )
+    .expect("Failed to write test input file");
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("a policy may only connect to command")
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_bounded_contexts_are_drawn_and_mapped() {
+    let model = r#"workflow: Shop
+swimlanes:
+  - backend: "Backend"
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+  ShipOrder:
+    description: "Ship an order"
+    swimlane: backend
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    context: Sales
+    connections:
+      - PlaceOrder -> OrderPlaced
+  - name: Shipping
+    context: Fulfillment
+    connections:
+      - OrderPlaced -> ShipOrder
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_contexts");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("contexts.eventmodel");
+    let svg_path = temp_dir.join("contexts.svg");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let svg = fs::read_to_string(&svg_path).expect("Failed to read output SVG");
+    assert!(svg.contains("<!-- Bounded contexts -->"));
+    assert!(svg.contains(">Sales</text>"));
+    assert!(svg.contains(">Fulfillment</text>"));
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "report",
+            "contexts",
+            input_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("  c0 -- \"OrderPlaced\" --> c1\n"));
+    assert!(stdout.contains("| OrderPlaced | Sales | Fulfillment |\n"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_hotspots_render_as_triangles_and_an_appendix() {
+    let model = r#"workflow: Payments
+
+swimlanes:
+  - backend: "Backend"
+
+commands:
+  ChargeCard:
+    description: "Charge the card"
+    swimlane: backend
+    hotspot: "Provider times out under load"
+
+events:
+  CardCharged:
+    description: "The card was charged"
+    swimlane: backend
+
+slices:
+  - name: Charging
+    connections:
+      - connection: ChargeCard -> CardCharged
+        hotspot: "Duplicate charges on retry?"
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_hotspots");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("hotspots.eventmodel");
+    let svg_path = temp_dir.join("hotspots.svg");
+    let markdown_path = temp_dir.join("hotspots.md");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    for path in [&svg_path, &markdown_path] {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--",
+                input_path.to_str().unwrap(),
+                "-o",
+                path.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "CLI failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let svg = fs::read_to_string(&svg_path).expect("Failed to read output SVG");
+    assert_eq!(svg.matches("<g class=\"hotspot\">").count(), 2);
+    assert!(svg.contains("<title>Provider times out under load</title>"));
+    assert!(svg.contains("<title>Duplicate charges on retry?</title>"));
+
+    let markdown = fs::read_to_string(&markdown_path).expect("Failed to read output Markdown");
+    assert!(markdown.contains("## Hotspots"));
+    assert!(markdown.contains("Provider times out under load"));
+    assert!(markdown.contains("Duplicate charges on retry?"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_estimates_roll_up_and_badge_slice_headers() {
+    let model = r#"workflow: Shop
+
+swimlanes:
+  - backend:
+      name: "Backend"
+      owner: team-orders
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+  ShipOrder:
+    description: "Ship an order"
+    swimlane: backend
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    estimate: 5
+    connections:
+      - PlaceOrder -> OrderPlaced
+  - name: Shipping
+    estimate: XL
+    connections:
+      - OrderPlaced -> ShipOrder
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_estimates");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("estimates.eventmodel");
+    let svg_path = temp_dir.join("estimates.svg");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+            "--estimates",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let svg = fs::read_to_string(&svg_path).expect("Failed to read output SVG");
+    assert_eq!(svg.matches("<g class=\"estimate\">").count(), 2);
+    assert!(svg.contains(">XL</text>"));
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "report",
+            "estimates",
+            input_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("| team-orders | 2 | 5 | 1 × XL | 0 |\n"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_progress_report_and_status_shading() {
+    let model = r#"workflow: Shop
+
+swimlanes:
+  - backend: "Backend"
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+  ShipOrder:
+    description: "Ship an order"
+    swimlane: backend
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    status: done
+    connections:
+      - PlaceOrder -> OrderPlaced
+  - name: Shipping
+    status: in_progress
+    connections:
+      - OrderPlaced -> ShipOrder
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_progress");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("progress.eventmodel");
+    let svg_path = temp_dir.join("progress.svg");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let svg = fs::read_to_string(&svg_path).expect("Failed to read output SVG");
+    assert!(svg.contains("<!-- Slice status -->"));
+    assert!(svg.contains("<title>Ordering: done</title>"));
+    assert!(svg.contains("<title>Shipping: in progress</title>"));
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "report",
+            "progress",
+            input_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("1 of 2 slices done (50%), 1 in progress, 0 to do.\n"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_slice_issues_render_as_linked_badges() {
+    let model = r#"workflow: Shop
+
+jira: https://acme.atlassian.net/
+
+swimlanes:
+  - backend: "Backend"
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+  ShipOrder:
+    description: "Ship an order"
+    swimlane: backend
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    issue: SHOP-123
+    connections:
+      - PlaceOrder -> OrderPlaced
+  - name: Shipping
+    issue: https://github.com/acme/shop/pull/42
+    connections:
+      - OrderPlaced -> ShipOrder
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_issues");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("issues.eventmodel");
+    let svg_path = temp_dir.join("issues.svg");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let svg = fs::read_to_string(&svg_path).expect("Failed to read output SVG");
+    assert!(svg.contains(r#"<a class="issue" href="https://acme.atlassian.net/browse/SHOP-123">"#));
+    assert!(svg.contains(r#"<a class="issue" href="https://github.com/acme/shop/issues/42">"#));
+    assert!(svg.contains(">#42</text>"));
+
+    // A Jira key needs the model's Jira site
+    fs::write(
+        &input_path,
+        model.replace("jira: https://acme.atlassian.net/\n", ""),
+    )
+    .expect("Failed to write test input file");
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Invalid issue 'SHOP-123' on slice 'Ordering'")
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_patterns_tint_slice_columns() {
+    let model = r#"workflow: Shop
+
+swimlanes:
+  - backend: "Backend"
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+projections:
+  OpenOrders:
+    description: "Orders not yet shipped"
+    swimlane: backend
+    fields:
+      id: String
+
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+  - name: Listing
+    connections:
+      - OrderPlaced -> OpenOrders
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_patterns");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("patterns.eventmodel");
+    let svg_path = temp_dir.join("patterns.svg");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+            "--patterns",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let svg = fs::read_to_string(&svg_path).expect("Failed to read output SVG");
+    assert!(svg.contains("<title>Ordering: State change</title>"));
+    assert!(svg.contains("<title>Listing: State view</title>"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_patterns_report_classifies_slices_and_warns_of_unmatched_ones() {
+    let model = r#"workflow: Payments
+
+swimlanes:
+  - backend: "Backend"
+  - provider: "Provider"
+
+commands:
+  MarkPaid:
+    description: "Mark an order paid"
+    swimlane: backend
+
+events:
+  OrderPaid:
+    description: "An order was paid"
+    swimlane: backend
+  PaymentSettled:
+    description: "The provider settled a payment"
+    swimlane: provider
+
+projections:
+  Payments:
+    description: "Payments by order"
+    swimlane: backend
+    fields:
+      id: String
+
+automations:
+  Reconciler:
+    swimlane: backend
+
+slices:
+  - name: Settling
+    connections:
+      - PaymentSettled -> Reconciler
+      - Reconciler -> MarkPaid
+      - MarkPaid -> OrderPaid
+  - name: Retrying
+    connections:
+      - Payments -> MarkPaid
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_patterns_report");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("payments.eventmodel");
+    let svg_path = temp_dir.join("payments.svg");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+            "--patterns",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "CLI failed: {stderr}");
+    assert!(stderr.contains("Slice 'Retrying' matches no Event Modeling pattern"));
+    let svg = fs::read_to_string(&svg_path).expect("Failed to read output SVG");
+    assert!(svg.contains("<title>Settling: Translation</title>"));
+    assert!(!svg.contains("<title>Retrying:"));
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "report",
+            "patterns",
+            input_path.to_str().unwrap(),
+            "--json",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect("Report is not JSON");
+    assert_eq!(report["slices"][0]["pattern"], "translation");
+    assert!(report["slices"][1]["pattern"].is_null());
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_conventions_report_lists_entities_lacking_envelope_fields() {
+    let model = r#"workflow: Shop
+
+swimlanes:
+  - backend: "Backend"
+
+conventions:
+  envelope: [correlation_id]
+  propagate: [correlation_id]
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+    data:
+      order_id: OrderId
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+    data:
+      order_id: OrderId
+      correlation_id: CorrelationId
+
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_conventions");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("conventions.eventmodel");
+    let svg_path = temp_dir.join("conventions.svg");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "CLI failed: {stderr}");
+    assert!(stderr.contains("The command 'PlaceOrder' lacks the propagated fields correlation_id"));
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "report",
+            "conventions",
+            input_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("1 of 2 entities compliant.\n"));
+    assert!(stdout.contains("| PlaceOrder | command | correlation_id |\n"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_badge_summarizes_model_health() {
+    let model = r#"workflow: Shop
+
+swimlanes:
+  - backend: "Backend"
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: backend
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+
+slices:
+  - name: Ordering
+    connections:
+      - PlaceOrder -> OrderPlaced
+"#;
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_badge");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("shop.eventmodel");
+    let badge_path = temp_dir.join("badge.svg");
+    fs::write(&input_path, model).expect("Failed to write test input file");
+
+    let badge = |input: &std::path::Path| {
+        Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--",
+                "badge",
+                input.to_str().unwrap(),
+                "-o",
+                badge_path.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = badge(&input_path);
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let snippet = String::from_utf8_lossy(&output.stdout);
+    assert!(snippet.starts_with("![Event Model: 1 slice, 2 entities, \u{2713} valid]("));
+    let svg = fs::read_to_string(&badge_path).expect("Failed to read badge");
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("1 slice, 2 entities, \u{2713} valid"));
+
+    // A broken model still gets a badge, marked invalid
+    fs::write(&input_path, "workflow: Shop\nswimlanes: []\n").expect("Failed to write model");
+    let output = badge(&input_path);
+    assert!(output.status.success());
+    let svg = fs::read_to_string(&badge_path).expect("Failed to read badge");
+    assert!(svg.contains("\u{2717} invalid"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_validate_checks_many_models_against_a_shared_vocabulary() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_validate");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let vocabulary_path = temp_dir.join("vocabulary.yaml");
+    fs::write(
+        &vocabulary_path,
+        "types:\n  OrderId: UUID\nconventions:\n  envelope: [correlation_id]\n",
+    )
+    .expect("Failed to write vocabulary");
+    let model = |name: &str, field_type: &str| {
+        let path = temp_dir.join(format!("{name}.eventmodel"));
+        fs::write(
+            &path,
+            format!(
+                r#"workflow: {name}
+
+swimlanes:
+  - backend: "Backend"
+
+events:
+  OrderPlaced:
+    description: "An order was placed"
+    swimlane: backend
+    data:
+      order_id: {field_type}
+"#
+            ),
+        )
+        .expect("Failed to write model");
+        path
+    };
+    let shop = model("shop", "OrderId");
+    let billing = model("billing", "InvoiceId");
+
+    let validate = |models: &[&std::path::Path]| {
+        let mut args = vec!["run", "--quiet", "--", "validate"];
+        args.extend(models.iter().map(|path| path.to_str().unwrap()));
+        args.extend(["--shared", vocabulary_path.to_str().unwrap()]);
+        Command::new("cargo")
+            .args(&args)
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = validate(&[&shop]);
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report = String::from_utf8_lossy(&output.stdout);
+    assert!(report.contains("Valid: 1. Invalid: 0."));
+    assert!(report.contains("lacks the envelope fields correlation_id"));
+
+    // Undeclared types fail the model lacking them, but not the others
+    let output = validate(&[&shop, &billing]);
+    assert!(!output.status.success());
+    let report = String::from_utf8_lossy(&output.stdout);
+    assert!(report.contains("Valid: 1. Invalid: 1."));
+    assert!(report.contains("InvoiceId"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("1 of 2 models are invalid"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+/// Layout score of the acceptance example when it was last improved: connector
+/// length, bends, crossings, and area. Lower these when layout gets better.
+const EXAMPLE_LAYOUT_BASELINE: [u64; 4] = [6125, 20, 5, 1_078_000];
+
+/// Renders the acceptance example with `--score` and the given flags,
+/// returning its connector length, bends, crossings, and area.
+fn example_layout_score(name: &str, flags: &[&str]) -> [u64; 4] {
+    let temp_dir = std::env::temp_dir().join(format!("event_modeler_score_{name}"));
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let output_path = temp_dir.join("example.svg");
+
+    let mut args = vec![
+        "run",
+        "--quiet",
+        "--",
+        "tests/fixtures/acceptance/example.eventmodel",
+        "-o",
+        output_path.to_str().unwrap(),
+        "--score",
+    ];
+    args.extend(flags);
+    let output = Command::new("cargo")
+        .args(&args)
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    fs::remove_dir_all(&temp_dir).ok();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("Layout score: "))
+        .expect("No layout score printed");
+    let score: Vec<u64> = line
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|number| !number.is_empty())
+        .map(|number| number.parse().unwrap())
+        .collect();
+    score
+        .try_into()
+        .unwrap_or_else(|_| panic!("Unexpected score: {line}"))
+}
+
+#[test]
+fn test_layout_score_does_not_regress() {
+    let score = example_layout_score("baseline", &[]);
+
+    for ((measure, value), baseline) in ["connector length", "bends", "crossings", "area"]
+        .iter()
+        .zip(score)
+        .zip(EXAMPLE_LAYOUT_BASELINE)
+    {
+        assert!(
+            value <= baseline,
+            "Layout got worse: {measure} is {value}, up from {baseline}"
+        );
+    }
+}
+
+#[test]
+fn test_optimize_untangles_connectors() {
+    let [length, _, crossings, area] = example_layout_score("optimized", &["--optimize", "2000"]);
+    let [baseline_length, _, baseline_crossings, baseline_area] = EXAMPLE_LAYOUT_BASELINE;
+
+    assert!(crossings < baseline_crossings, "{crossings} crossings");
+    assert!(length < baseline_length, "connector length {length}px");
+    assert_eq!(area, baseline_area);
+}
+
+#[test]
+fn test_docs_mkdocs_renders_placeholders_into_assets() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_mkdocs");
+    fs::remove_dir_all(&temp_dir).ok();
+    fs::create_dir_all(temp_dir.join("models")).expect("Failed to create models dir");
+    fs::create_dir_all(temp_dir.join("guide")).expect("Failed to create guide dir");
+    fs::copy(
+        "tests/fixtures/acceptance/example.eventmodel",
+        temp_dir.join("models/signup.eventmodel"),
+    )
+    .expect("Failed to copy model");
+    fs::write(
+        temp_dir.join("index.md"),
+        "# Signup\n\n{{ eventmodel(\"models/signup.eventmodel\") }}\n",
+    )
+    .expect("Failed to write page");
+    fs::write(
+        temp_dir.join("guide/accounts.md"),
+        "{{ eventmodel(\"models/signup.eventmodel\", slice=\"Create User Account Credentials\") }}\n",
+    )
+    .expect("Failed to write page");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "docs",
+            "mkdocs",
+            temp_dir.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("Rendered 2 diagrams"),
+        "Unexpected output: {}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+
+    let assets = temp_dir.join("assets/eventmodels");
+    assert!(
+        fs::read_to_string(assets.join("models-signup.svg"))
+            .expect("Failed to read diagram")
+            .contains("<svg")
+    );
+    assert!(
+        assets
+            .join("models-signup--create-user-account-credentials.svg")
+            .exists()
+    );
+    assert_eq!(
+        fs::read_to_string(temp_dir.join("index.md")).expect("Failed to read page"),
+        "# Signup\n\n![signup](assets/eventmodels/models-signup.svg)\n"
+    );
+    assert_eq!(
+        fs::read_to_string(temp_dir.join("guide/accounts.md")).expect("Failed to read page"),
+        "![Create User Account Credentials](../assets/eventmodels/models-signup--create-user-account-credentials.svg)\n"
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_anonymize_writes_a_model_free_of_domain_terms() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_anonymize");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let demo_path = temp_dir.join("demo.eventmodel");
+    let svg_path = temp_dir.join("demo.svg");
+    let _ = fs::remove_file(&demo_path);
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "anonymize",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "-o",
+            demo_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "anonymize failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let demo = fs::read_to_string(&demo_path).expect("Failed to read anonymized model");
+    for term in ["User", "Account", "Email", "Signup", "Verif"] {
+        assert!(!demo.contains(term), "'{term}' leaked into:\n{demo}");
+    }
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            demo_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "anonymized model failed to render: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_fast_render_of_a_thousand_entity_model() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_fast_render");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let model_path = temp_dir.join("big.eventmodel");
+    let output_path = temp_dir.join("big.svg");
+
+    let generated = Command::new(env!("CARGO_BIN_EXE_stress_model"))
+        .args(["--entities", "1000", "-o", model_path.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        generated.status.success(),
+        "Generator failed: {}",
+        String::from_utf8_lossy(&generated.stderr)
+    );
+
+    let started = std::time::Instant::now();
+    let output = Command::new(env!("CARGO_BIN_EXE_event_modeler"))
+        .args([
+            model_path.to_str().unwrap(),
+            "-o",
+            output_path.to_str().unwrap(),
+            "--fast",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let elapsed = started.elapsed();
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let svg = fs::read_to_string(&output_path).expect("Failed to read output");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    fs::remove_dir_all(&temp_dir).ok();
+
+    // Every slice is drawn, through to the last
+    assert!(svg.contains(">Page250 Form250<"));
+    // Straight connectors cross entities freely, so none are reported
+    assert!(!stderr.contains("passes through entity"), "{stderr}");
+    // Generous for unoptimized builds on slow machines; a release build
+    // takes well under a second
+    assert!(elapsed.as_secs() < 20, "Fast render took {elapsed:?}");
+}
+
+#[test]
+fn test_slice_order_places_entities_of_a_cell_left_to_right() {
+    let model = |order: &str| {
+        format!(
+            r#"workflow: Signing In
+swimlanes:
+  - ui: "Screens"
+
+views:
+  WelcomeScreen:
+    description: "Greets visitors"
+    swimlane: ui
+    components:
+      - SignIn: Button
+      - Join: Button
+  LoginScreen:
+    description: "Asks for credentials"
+    swimlane: ui
+    components:
+      - Password: TextInput
+  NewAccountScreen:
+    description: "Asks for account details"
+    swimlane: ui
+    components:
+      - Email: TextInput
+
+slices:
+  - name: "Arrive"
+{order}    connections:
+      - WelcomeScreen.Join -> NewAccountScreen
+      - WelcomeScreen.SignIn -> LoginScreen
+"#
+        )
+    };
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_slice_order");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("order.eventmodel");
+    let svg_path = temp_dir.join("order.svg");
+    let render = |order: &str, flags: &[&str]| {
+        fs::write(&input_path, model(order)).expect("Failed to write test input file");
+        let mut args = vec![
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+            "--emit",
+            "layout",
+        ];
+        args.extend(flags);
+        let output = Command::new("cargo")
+            .args(&args)
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "CLI failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let layout: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(temp_dir.join("order.layout.json")).expect("Layout not emitted"),
+        )
+        .expect("Layout is not JSON");
+        let x = |name: &str| {
+            layout["entities"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|entity| entity["name"] == name)
+                .and_then(|entity| entity["x"].as_u64())
+                .unwrap_or_else(|| panic!("{name} not placed"))
+        };
+        x("LoginScreen") < x("NewAccountScreen")
+    };
+
+    // Left alone, entities follow the order the connections mention them
+    assert!(!render("", &[]));
+    let order = "    order: [LoginScreen, NewAccountScreen]\n";
+    assert!(render(order, &[]));
+    // The search for better orderings leaves an ordered slice as written
+    assert!(render(order, &["--optimize", "500"]));
+
+    // Ordering an entity the slice does not draw is an error
+    fs::write(
+        &input_path,
+        model("    order: [LoginScreen, SignUpScreen]\n"),
+    )
+    .expect("Failed to write test input file");
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Slice 'Arrive' orders 'SignUpScreen', which none of its connections draws")
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_unused_lanes_are_shaded_per_slice() {
+    let model = |settings: &str, declared: &str| {
+        format!(
+            r#"workflow: Lanes
+{settings}
+swimlanes:
+  - ui: "Screens"
+  - app: "Application"
+  - audit: "Audit"
+  - orders: "Orders"
+
+views:
+  CartScreen:
+    description: "Shows the cart"
+    swimlane: ui
+    components:
+      - Pay: Button
+
+commands:
+  PlaceOrder:
+    description: "Place an order"
+    swimlane: app
+
+events:
+  OrderPlaced:
+    description: "Order placed"
+    swimlane: orders
+
+projections:
+  OrderSummaryProjection:
+    description: "Summarizes orders"
+    swimlane: app
+
+slices:
+  - name: "Place"
+    connections:
+      - CartScreen.Pay -> PlaceOrder
+      - PlaceOrder -> OrderPlaced
+  - name: "Summarize"
+{declared}    connections:
+      - OrderPlaced -> OrderSummaryProjection
+"#
+        )
+    };
+
+    let temp_dir = std::env::temp_dir().join("event_modeler_unused_lanes");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("lanes.eventmodel");
+    let svg_path = temp_dir.join("lanes.svg");
+    let render = |settings: &str, declared: &str| {
+        fs::write(&input_path, model(settings, declared)).expect("Failed to write test input file");
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--",
+                input_path.to_str().unwrap(),
+                "-o",
+                svg_path.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to execute command");
+        (
+            output.status.success(),
+            fs::read_to_string(&svg_path).unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).into_owned(),
+        )
+    };
+    let height = |svg: &str| {
+        svg.split("viewBox=\"0 0 ")
+            .nth(1)
+            .and_then(|rest| rest.split(['"', ' ']).nth(1))
+            .and_then(|height| height.parse::<u32>().ok())
+            .expect("No viewBox")
+    };
+
+    let (_, full, _) = render("", "");
+    assert!(!full.contains("unused-lane"));
+
+    // Audit is shaded in both columns and shrinks, and Screens in the
+    // column of the slice that draws nothing there
+    let (succeeded, shaded, stderr) = render("unused_lanes: shaded", "");
+    assert!(succeeded, "CLI failed: {stderr}");
+    assert_eq!(shaded.matches("class=\"unused-lane\"").count(), 3);
+    assert!(height(&shaded) < height(&full));
+
+    // A slice declaring a swimlane keeps it unshaded, even when empty
+    let (succeeded, declared, stderr) =
+        render("unused_lanes: shaded", "    swimlanes: [ui, app, orders]\n");
+    assert!(succeeded, "CLI failed: {stderr}");
+    assert_eq!(declared.matches("class=\"unused-lane\"").count(), 2);
+
+    // Leaving out a swimlane the slice draws in is an error
+    fs::remove_file(&svg_path).ok();
+    let (succeeded, _, stderr) = render("", "    swimlanes: [app]\n");
+    assert!(!succeeded);
+    assert!(stderr.contains(
+        "slice 'Summarize' draws 'OrderPlaced' in swimlane 'orders', which it does not declare"
+    ));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_routing_clearance_is_configurable_and_checked() {
+    let example = fs::read_to_string("tests/fixtures/acceptance/example.eventmodel")
+        .expect("Failed to read example");
+    let temp_dir = std::env::temp_dir().join("event_modeler_clearance");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("clearance.eventmodel");
+    let svg_path = temp_dir.join("clearance.svg");
+    let render = |routing: &str| {
+        fs::write(&input_path, format!("{routing}{example}"))
+            .expect("Failed to write test input file");
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--",
+                input_path.to_str().unwrap(),
+                "-o",
+                svg_path.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to execute command");
+        (
+            output.status.success(),
+            String::from_utf8_lossy(&output.stderr).into_owned(),
+        )
+    };
+
+    let (succeeded, stderr) = render("");
+    assert!(succeeded, "CLI failed: {stderr}");
+    assert!(!stderr.contains("px from entity"), "{stderr}");
+
+    // A wider margin than the layout leaves is reported connector by connector
+    let (succeeded, stderr) = render("routing:\n  margin: 40\n  separation: 12\n");
+    assert!(succeeded, "CLI failed: {stderr}");
+    assert!(
+        stderr.contains(
+            "Connection NewAccountScreen -> CreateUserAccountCredentials passes 30px from entity 'LoginScreen'"
+        ),
+        "{stderr}"
+    );
+
+    let (succeeded, stderr) = render("routing:\n  separation: 0\n");
+    assert!(!succeeded);
+    assert!(stderr.contains("Invalid routing: separation must be at least 1"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_canvas_background_per_theme_and_watermark() {
+    let example = fs::read_to_string("tests/fixtures/acceptance/example.eventmodel")
+        .expect("Failed to read example");
+    let temp_dir = std::env::temp_dir().join("event_modeler_canvas");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let input_path = temp_dir.join("canvas.eventmodel");
+    let svg_path = temp_dir.join("canvas.svg");
+    fs::write(
+        &input_path,
+        format!(
+            "canvas:\n  background: transparent\n  dark_background: \"#0d1117\"\n  watermark: CONFIDENTIAL\n{example}"
+        ),
+    )
+    .expect("Failed to write test input file");
+    let render = |extra: &[&str]| {
+        let mut args = vec![
+            "run",
+            "--quiet",
+            "--",
+            input_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+        ];
+        args.extend_from_slice(extra);
+        let output = Command::new("cargo")
+            .args(&args)
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "CLI failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        fs::read_to_string(&svg_path).expect("Failed to read SVG")
+    };
+
+    let light = render(&[]);
+    assert!(!light.contains(r#"<rect x="0" y="0""#));
+    let watermark = light
+        .find(r#"class="watermark""#)
+        .expect("watermark is drawn");
+    assert!(light[watermark..].contains(">CONFIDENTIAL</text>"));
+    // Drawn before, and so beneath, everything from the title on
+    assert!(watermark < light.find("<!-- Workflow title -->").unwrap());
+
+    let dark = render(&["--dark"]);
+    assert!(dark.contains(r##"<rect x="0" y="0" width=""##));
+    assert!(dark.contains(r##"fill="#0d1117" stroke="none"/>"##));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_per_slice_out_renders_each_slice_on_its_own() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_per_slice");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let svg_path = temp_dir.join("model.svg");
+    let slice_dir = temp_dir.join("slices");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "-o",
+            svg_path.to_str().unwrap(),
+            "--per-slice-out",
+            slice_dir.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // The full diagram is still written
+    let full = fs::read_to_string(&svg_path).expect("Failed to read full SVG");
+    assert!(full.contains("Verify Email"));
+
+    let slice = fs::read_to_string(slice_dir.join("send-email-verification.svg"))
+        .expect("Failed to read slice SVG");
+    assert!(slice.contains(">Message Sent<"));
+    // Entities of other slices are left out
+    assert!(!slice.contains(">Screen<") && !slice.contains("Profile"));
+    for name in [
+        "create-user-account-credentials",
+        "send-email-verification",
+        "verify-email-address",
+    ] {
+        let png = fs::read(slice_dir.join(format!("{name}.png"))).expect("Failed to read PNG");
+        assert!(png.starts_with(b"\x89PNG"), "{name}.png is not a PNG");
+    }
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_find_lists_and_highlights_matching_entities() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_find");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let svg_path = temp_dir.join("highlighted.svg");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "find",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "verification",
+            "--render",
+            svg_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("4 entities match 'verification':"));
+    assert!(stdout.contains(
+        "command SendEmailVerification in swimlane 'Commands, Projections, Queries', used in Send Email Verification"
+    ));
+    assert!(stdout.contains(
+        "event EmailVerificationMessageSent in swimlane 'User Account Event Stream', used in Send Email Verification"
+    ));
+
+    let svg = fs::read_to_string(&svg_path).expect("Failed to read SVG");
+    assert!(svg.contains(r#"class="highlighted""#));
+    assert!(svg.contains("<title>SendEmailVerification</title></rect>"));
+    assert!(!svg.contains("<title>LoginScreen</title></rect>"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_png_output_rasterizes_the_svg() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_png_output");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let base = temp_dir.join("model.svg");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "-o",
+            base.to_str().unwrap(),
+            "--formats",
+            "png",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Generated PNG"));
+
+    let svg = fs::read_to_string(&base).expect("Failed to read SVG");
+    let png = fs::read(temp_dir.join("model.png")).expect("Failed to read PNG");
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+    // The image is as large as the diagram
+    let attribute = |name: &str| -> u32 {
+        let start = svg.find(&format!("{name}=\"")).unwrap() + name.len() + 2;
+        let end = start + svg[start..].find('"').unwrap();
+        svg[start..end].parse::<f64>().unwrap().ceil() as u32
+    };
+    let field = |at: usize| u32::from_be_bytes(png[at..at + 4].try_into().unwrap());
+    assert_eq!(
+        (field(16), field(20)),
+        (attribute("width"), attribute("height"))
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_progress_bar_leaves_the_output_unchanged() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_progress_bar");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let plain = temp_dir.join("plain.svg");
+    let with_progress = temp_dir.join("progress.svg");
+
+    for (path, extra) in [(&plain, None), (&with_progress, Some("--progress"))] {
+        let mut args = vec![
+            "run",
+            "--quiet",
+            "--",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "-o",
+            path.to_str().unwrap(),
+        ];
+        args.extend(extra);
+        let output = Command::new("cargo")
+            .args(&args)
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "CLI failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    assert_eq!(
+        fs::read_to_string(&plain).unwrap(),
+        fs::read_to_string(&with_progress).unwrap()
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_rename_keeps_comments_and_formatting() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_rename");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let model_path = temp_dir.join("model.eventmodel");
+    let svg_path = temp_dir.join("model.svg");
+    let original = fs::read_to_string("tests/fixtures/acceptance/example.eventmodel")
+        .expect("Failed to read fixture")
+        .replacen(
+            "events:\n",
+            "events:\n  # Raised when the link in the email is followed\n",
+            1,
+        );
+    fs::write(&model_path, &original).expect("Failed to write model");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "rename",
+            model_path.to_str().unwrap(),
+            "EmailAddressVerified",
+            "EmailConfirmed",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "rename failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("in 6 places"));
+
+    let renamed = fs::read_to_string(&model_path).expect("Failed to read renamed model");
+    assert_eq!(
+        renamed,
+        original.replace("EmailAddressVerified", "EmailConfirmed")
+    );
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            model_path.to_str().unwrap(),
+            "-o",
+            svg_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "renamed model failed to render: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let svg = fs::read_to_string(&svg_path).expect("Failed to read SVG");
+    assert!(svg.contains("Email Confirmed") || svg.contains("EmailConfirmed"));
+}
+
+#[test]
+fn test_storyboard_interleaves_wireframes_with_steps() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_storyboard");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let storyboard_path = temp_dir.join("storyboard.html");
+    let _ = fs::remove_file(&storyboard_path);
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "docs",
+            "storyboard",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "-o",
+            storyboard_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "storyboard failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let html = fs::read_to_string(&storyboard_path).expect("Failed to read storyboard");
+    let position = |text: &str| {
+        html.find(text)
+            .unwrap_or_else(|| panic!("'{text}' missing from:\n{html}"))
+    };
+    // The first slice, in the order its connections tell it
+    let story = [
+        "<h2>Create User Account Credentials</h2>",
+        "<strong>LoginScreen</strong>",
+        "<strong>NewAccountScreen</strong>",
+        "<strong>CreateUserAccountCredentials</strong>",
+        "<strong>UserAccountCredentialsCreated</strong>",
+        "<strong>VerifyEmailAddressScreen</strong>",
+        "<h2>Send Email Verification</h2>",
+    ];
+    for pair in story.windows(2) {
+        assert!(
+            position(pair[0]) < position(pair[1]),
+            "{pair:?} out of order"
+        );
+    }
+    // Each screen is sketched from its components, the form's fields and actions included
+    assert!(html.contains("<svg"));
+    assert!(html.contains(">email_address</text>"));
+    assert!(html.contains(">Submit</text>"));
+    // Projections and queries are not part of the story
+    assert!(!html.contains("<strong>UserCredentialsProjection</strong>"));
+}
+
+#[test]
+fn test_glossary_report_as_html() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "report",
+            "glossary",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "--html",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("<h1>Glossary of User Account Signup</h1>"));
+    assert!(stdout.contains("<h2>User Account Event Stream</h2>"));
+    assert!(stdout.contains(
+        "<dt id=\"useraccountcredentialscreated\">UserAccountCredentialsCreated <em>event</em></dt>"
+    ));
+    assert!(stdout.contains("<li><code>account_id</code>: <code>UserAccountId</code></li>"));
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "report",
+            "owners",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "--html",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("The owners report has no HTML form"));
+}
+
+#[test]
+fn test_pdf_output_is_titled_after_the_workflow() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_pdf_output");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let pdf_path = temp_dir.join("model.pdf");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "-o",
+            pdf_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Generated PDF"));
+
+    let pdf = fs::read(&pdf_path).expect("Failed to read PDF");
+    assert!(pdf.starts_with(b"%PDF-"));
+    let title = b"/Title (User Account Signup)";
+    assert!(pdf.windows(title.len()).any(|window| window == title));
+    assert!(
+        pdf.windows(b"/Count 1".len())
+            .any(|window| window == b"/Count 1")
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_poster_is_tiled_across_pdf_pages() {
+    let temp_dir = std::env::temp_dir().join("event_modeler_poster");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let pdf_path = temp_dir.join("poster.pdf");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "-o",
+            pdf_path.to_str().unwrap(),
+            "--poster",
+            "a1",
+            "--tile",
+            "a4",
+            "--overlap",
+            "15mm",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "CLI failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let pdf = fs::read(&pdf_path).expect("Failed to read poster");
+    assert!(pdf.starts_with(b"%PDF-"));
+    let pages = pdf
+        .windows(b"/Type /Page\n".len())
+        .filter(|window| *window == b"/Type /Page\n")
+        .count();
+    assert!(pages > 1, "expected several tiles, found {pages} pages");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "tests/fixtures/acceptance/example.eventmodel",
+            "-o",
+            pdf_path.to_str().unwrap(),
+            "--tile",
+            "a4",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--tile and --overlap need --poster"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_serve_kroki_renders_posted_models() {
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    // Run the binary itself, so killing it stops the server rather than cargo
+    let mut server = Command::new(env!("CARGO_BIN_EXE_event_modeler"))
+        .args(["serve", "--kroki", "--port", "0"])
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .expect("Failed to start server");
+    let mut banner = String::new();
+    BufReader::new(server.stdout.take().unwrap())
+        .read_line(&mut banner)
+        .expect("Failed to read server banner");
+    let address = banner
+        .trim()
+        .rsplit("http://")
+        .next()
+        .expect("Banner should name the address")
+        .to_string();
+
+    let model = fs::read_to_string("tests/fixtures/acceptance/example.eventmodel")
+        .expect("Failed to read model");
+    let mut stream = std::net::TcpStream::connect(&address).expect("Failed to connect");
+    write!(
+        stream,
+        "POST /eventmodel/svg HTTP/1.1\r\nHost: {address}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\n\r\n{model}",
+        model.len()
+    )
+    .expect("Failed to send request");
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .expect("Failed to read response");
+    server.kill().ok();
+    server.wait().ok();
+
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+    assert!(response.contains("Content-Type: image/svg+xml\r\n"));
+    assert!(response.contains("User Account Signup"));
+}